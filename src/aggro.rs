@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use crate::{
+    game::AppState,
+    components::TauntTarget,
+};
+
+// How strongly each kind of threat pulls horrors towards it; a threat at half the weight has to be
+// twice as close to win out
+pub const SURVIVOR_THREAT_WEIGHT: f32 = 1.0;
+pub const DECOY_THREAT_WEIGHT: f32 = 1.0;
+pub const SENTRY_THREAT_WEIGHT: f32 = 0.5;
+
+pub struct AggroPlugin;
+
+impl Plugin for AggroPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThreatMap>()
+            .add_systems(Update, assess_threats.in_set(ThreatAssessment).run_if(in_state(AppState::InGame)));
+    }
+}
+
+// Horror AI runs after this, so every horror picks from the same snapshot of threats each frame
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThreatAssessment;
+
+// Anything horrors may choose to go after: the survivor, decoys, sentries, escorted caravans, and a partner once one exists
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Threat {
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThreatCandidate {
+    pub position: Vec2,
+    pub weight: f32,
+    pub taunt_radius: Option<f32>,
+}
+
+impl ThreatCandidate {
+    pub fn is_taunting(&self, horror_pos: Vec2) -> bool {
+        self.taunt_radius.is_some_and(|radius| self.position.distance_squared(horror_pos) < radius * radius)
+    }
+
+    // Lower is more pressing
+    pub fn score(&self, horror_pos: Vec2) -> f32 {
+        self.position.distance(horror_pos) / self.weight.max(f32::EPSILON)
+    }
+}
+
+// A horror inside any taunt only considers the taunters; otherwise every threat competes on weighted distance
+pub fn select_target(horror_pos: Vec2, threats: &[ThreatCandidate]) -> Option<Vec2> {
+    most_pressing(horror_pos, threats.iter().filter(|threat| threat.is_taunting(horror_pos))).or_else(|| most_pressing(horror_pos, threats.iter()))
+}
+
+fn most_pressing<'a>(horror_pos: Vec2, candidates: impl Iterator<Item = &'a ThreatCandidate>) -> Option<Vec2> {
+    candidates.min_by(|a, b| a.score(horror_pos).total_cmp(&b.score(horror_pos))).map(|threat| threat.position)
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ThreatMap {
+    pub threats: Vec<ThreatCandidate>,
+}
+
+impl ThreatMap {
+    pub fn target_for(&self, horror_pos: Vec2) -> Option<Vec2> { select_target(horror_pos, &self.threats) }
+}
+
+fn assess_threats(mut threat_map: ResMut<ThreatMap>, threat_query: Query<(&Transform, &Threat, Option<&TauntTarget>)>) {
+    threat_map.threats.clear();
+    threat_map.threats.extend(threat_query.iter().map(|(transform, threat, taunt)| ThreatCandidate {
+        position: transform.translation.truncate(),
+        weight: threat.weight,
+        taunt_radius: taunt.map(|taunt| taunt.radius),
+    }));
+}
@@ -1,10 +1,57 @@
-use bevy::prelude::*;
-use crate::game::AppState;
+use bevy::{prelude::*, audio::SpatialScale, utils::HashMap};
+use serde::Deserialize;
+use rand::Rng;
+use crate::{game::AppState, game_events::{GameEvent, DamageOrigin}, camera_systems::MainCamera, skills::SkillId, horror::{HorrorType, ElitePhase}, survivor::LowHealth, components::Health};
 
-#[derive(Event)]
-pub struct PlaySoundEvent(pub SoundEffect);
+const EMBEDDED_SOUND_MANIFEST: &str = include_str!("../assets/data/sounds.ron");
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// Positioned sounds play at full volume within this distance of the camera and fall off with the square of distance beyond it
+pub const FULL_VOLUME_RADIUS: f32 = 500.0;
+// Beyond this a positioned sound would be too faint to hear, so it isn't played at all
+pub const MAX_AUDIBLE_DISTANCE: f32 = 2500.0;
+// World-space distance between the listener's ears; wider pans harder
+const LISTENER_EAR_GAP: f32 = 400.0;
+// How far the playback speed (and with it the pitch) and the volume of a frequent sound may stray from normal
+pub const FREQUENT_SOUND_PITCH_SPREAD: f32 = 0.08;
+pub const FREQUENT_SOUND_VOLUME_SPREAD: f32 = 0.15;
+const MUSIC_VOLUME: f32 = 0.3;
+// How long a music layer takes to fade fully in or out when the elite phase changes
+const MUSIC_LAYER_FADE_SECS: f32 = 1.5;
+// Bevy's audio has no filters, so low health "muffles" the music by ducking it and dragging its pitch down a touch
+const LOW_HEALTH_MUSIC_DUCK: f32 = 0.6;
+const LOW_HEALTH_MUSIC_SLOWDOWN: f32 = 0.05;
+// The heartbeat sample is one loop at this tempo; playback speed sets the actual rate
+const HEARTBEAT_SAMPLE_BPM: f32 = 70.0;
+const HEARTBEAT_MAX_VOLUME: f32 = 0.9;
+// Ogg Vorbis is decoded by every build; desktop builds with the extra_audio_formats feature take the others too.
+// The browser build sticks to ogg to keep its download small
+#[cfg(all(feature = "extra_audio_formats", not(target_arch = "wasm32")))]
+pub const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["ogg", "wav", "flac", "mp3"];
+#[cfg(not(all(feature = "extra_audio_formats", not(target_arch = "wasm32"))))]
+pub const SUPPORTED_AUDIO_EXTENSIONS: &[&str] = &["ogg"];
+
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct PlaySoundEvent {
+    pub effect: SoundEffect,
+    // Where the sound comes from in the world; None plays it centred, as UI and survivor sounds do
+    pub position: Option<Vec2>,
+}
+
+impl PlaySoundEvent {
+    pub fn global(effect: SoundEffect) -> Self {
+        Self { effect, position: None }
+    }
+
+    pub fn at(effect: SoundEffect, position: Vec2) -> Self {
+        Self { effect, position: Some(position) }
+    }
+}
+
+pub fn is_audible(position: Vec2, listener_position: Vec2) -> bool {
+    position.distance_squared(listener_position) <= MAX_AUDIBLE_DISTANCE * MAX_AUDIBLE_DISTANCE
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SoundEffect {
     RitualCast,
     HorrorHit,
@@ -15,96 +62,336 @@ pub enum SoundEffect {
     MadnessConsumes,
     OmenAccepted,
     HorrorProjectile, 
+    Heartbeat,
+    ReliquaryOpen,
+    ItemPickup,
+    Taunt,
+    SkillCast(SkillId),
+    SkillImpact(SkillId),
+    // Elites are the closest thing to a boss, so their arrival gets a stinger
+    BossStinger(HorrorType),
+    // Goes out with every beacon, however far away it was raised
+    BeaconPing,
+}
+
+impl SoundEffect {
+    // What plays when the manifest has no file of its own for this sound
+    pub fn fallback(self) -> Option<SoundEffect> {
+        match self {
+            SoundEffect::SkillCast(_) => Some(SoundEffect::RitualCast),
+            SoundEffect::SkillImpact(_) => Some(SoundEffect::HorrorHit),
+            SoundEffect::ReliquaryOpen => Some(SoundEffect::Revelation),
+            SoundEffect::ItemPickup => Some(SoundEffect::SoulCollect),
+            SoundEffect::Taunt => Some(SoundEffect::OmenAccepted),
+            SoundEffect::BeaconPing => Some(SoundEffect::OmenAccepted),
+            _ => None,
+        }
+    }
+
+    // This sound followed by its fallbacks, in the order they are tried
+    pub fn with_fallbacks(self) -> impl Iterator<Item = SoundEffect> {
+        std::iter::successors(Some(self), |sound| sound.fallback())
+    }
+
+    // Hits and casts can fire many times a second, so they are varied to keep long runs from grating
+    pub fn is_frequent(self) -> bool {
+        matches!(self, SoundEffect::HorrorHit | SoundEffect::SkillImpact(_) | SoundEffect::RitualCast | SoundEffect::SkillCast(_) | SoundEffect::HorrorProjectile)
+    }
+}
+
+// Speed and volume multipliers for one play of a sound; roll is in -1..=1 for each
+pub fn sound_variation(sound: SoundEffect, pitch_roll: f32, volume_roll: f32) -> (f32, f32) {
+    if !sound.is_frequent() { return (1.0, 1.0); }
+    (1.0 + pitch_roll.clamp(-1.0, 1.0) * FREQUENT_SOUND_PITCH_SPREAD, 1.0 - volume_roll.abs().min(1.0) * FREQUENT_SOUND_VOLUME_SPREAD)
+}
+
+// Volume and speed for the background music
+pub fn music_playback(low_health: &LowHealth) -> (f32, f32) {
+    (MUSIC_VOLUME * (1.0 - LOW_HEALTH_MUSIC_DUCK * low_health.intensity), 1.0 - LOW_HEALTH_MUSIC_SLOWDOWN * low_health.intensity)
+}
+
+// Speed and volume for the heartbeat loop, or None when health isn't low
+pub fn heartbeat_playback(low_health: &LowHealth) -> Option<(f32, f32)> {
+    low_health.is_active().then(|| (low_health.heartbeat_bpm() / HEARTBEAT_SAMPLE_BPM, HEARTBEAT_MAX_VOLUME * low_health.intensity))
+}
+
+// Which sample each sound played last, so sounds with several takes cycle through them instead of repeating one
+#[derive(Resource, Default, Debug)]
+pub struct SampleRotation {
+    next: HashMap<SoundEffect, usize>,
+}
+
+impl SampleRotation {
+    pub fn next_index(&mut self, sound: SoundEffect, sample_count: usize) -> usize {
+        if sample_count <= 1 { return 0; }
+        let next = self.next.entry(sound).or_insert(0);
+        let index = *next % sample_count;
+        *next = index + 1;
+        index
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SoundManifestEntry {
+    pub sound: SoundEffect,
+    pub path: String,
+    // Extra takes of the same sound, played in turn with path
+    #[serde(default)]
+    pub variations: Vec<String>,
+}
+
+impl SoundManifestEntry {
+    pub fn samples(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.path.as_str()).chain(self.variations.iter().map(String::as_str))
+    }
+}
+
+// A track that loops in step with the music and fades in once the fiercest living elite reaches phase
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MusicLayerEntry {
+    pub phase: ElitePhase,
+    pub path: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SoundManifest {
+    pub music: String,
+    #[serde(default)]
+    pub music_layers: Vec<MusicLayerEntry>,
+    pub sounds: Vec<SoundManifestEntry>,
+}
+
+impl SoundManifest {
+    pub fn from_ron_str(source: &str) -> Result<Self, SoundManifestError> {
+        let manifest: SoundManifest = ron::de::from_str(source)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> Result<(), SoundManifestError> {
+        if self.music.is_empty() || self.music_layers.iter().any(|layer| layer.path.is_empty()) { return Err(SoundManifestError::EmptyMusicPath); }
+        let mut seen = std::collections::HashSet::new();
+        for entry in self.sounds.iter() {
+            if entry.samples().any(str::is_empty) { return Err(SoundManifestError::EmptyPath(entry.sound)); }
+            if !seen.insert(entry.sound) { return Err(SoundManifestError::DuplicateSound(entry.sound)); }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SoundManifestError {
+    Parse(ron::error::SpannedError),
+    EmptyMusicPath,
+    EmptyPath(SoundEffect),
+    DuplicateSound(SoundEffect),
 }
 
+impl std::fmt::Display for SoundManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoundManifestError::Parse(error) => write!(f, "could not parse sound manifest: {}", error),
+            SoundManifestError::EmptyMusicPath => write!(f, "music has an empty path"),
+            SoundManifestError::EmptyPath(sound) => write!(f, "{:?} has an empty path", sound),
+            SoundManifestError::DuplicateSound(sound) => write!(f, "{:?} is listed more than once", sound),
+        }
+    }
+}
+
+impl std::error::Error for SoundManifestError {}
+
+impl From<ron::error::SpannedError> for SoundManifestError {
+    fn from(error: ron::error::SpannedError) -> Self { SoundManifestError::Parse(error) }
+}
+
+// Handles for every sample of each manifest entry; lookups walk the same fallback chain as the manifest
 #[derive(Resource)]
 pub struct GameAudioHandles {
-    pub ritual_cast: Handle<AudioSource>,
-    pub horror_hit: Handle<AudioSource>,
-    pub horror_death: Handle<AudioSource>,
-    pub survivor_hit: Handle<AudioSource>,
-    pub revelation: Handle<AudioSource>,
-    pub soul_collect: Handle<AudioSource>,
-    pub madness_consumes: Handle<AudioSource>,
-    pub omen_accepted: Handle<AudioSource>,
-    pub horror_projectile: Handle<AudioSource>,
+    pub sounds: HashMap<SoundEffect, Vec<Handle<AudioSource>>>,
     pub background_music: Handle<AudioSource>,
+    pub music_layers: Vec<(ElitePhase, Handle<AudioSource>)>,
+}
+
+impl GameAudioHandles {
+    // The sound that will actually play, and its samples
+    pub fn samples_for(&self, sound: SoundEffect) -> Option<(SoundEffect, &[Handle<AudioSource>])> {
+        sound.with_fallbacks().find_map(|candidate| self.sounds.get(&candidate).filter(|samples| !samples.is_empty()).map(|samples| (candidate, samples.as_slice())))
+    }
 }
 
 #[derive(Component)]
 struct BackgroundMusicController;
 
+#[derive(Component)]
+struct HeartbeatLoop;
+
+// Every music track, the base one included; fade is how far it has faded in, 0 to 1
+#[derive(Component)]
+struct MusicLayer {
+    phase: ElitePhase,
+    fade: f32,
+}
+
+// The fiercest phase any living elite has reached, which sets how many music layers play
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MusicIntensity {
+    pub phase: ElitePhase,
+}
+
+impl MusicIntensity {
+    // Phase changes only ever raise the intensity; it drops back once no elite is left alive
+    pub fn apply(&mut self, event: &GameEvent, any_elite_alive: bool) {
+        if let GameEvent::ElitePhaseChanged { phase, .. } = event { self.phase = self.phase.max(*phase); }
+        if !any_elite_alive { self.phase = ElitePhase::Opening; }
+    }
+
+    pub fn layer_target(&self, layer_phase: ElitePhase) -> f32 {
+        if layer_phase <= self.phase { 1.0 } else { 0.0 }
+    }
+}
+
 pub struct GameAudioPlugin;
 
 impl Plugin for GameAudioPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<PlaySoundEvent>()
+            .init_resource::<MusicIntensity>()
+            .init_resource::<SampleRotation>()
             .add_systems(Startup, setup_audio_handles)
-            .add_systems(Update, play_sound_system)
+            .add_systems(Update, attach_spatial_listener)
+            .add_systems(Update, (game_event_sound_system, play_sound_system).chain())
+            .add_systems(Update, low_health_audio_system)
+            .add_systems(Update, (music_intensity_system, update_music_layers).chain())
             .add_systems(OnEnter(AppState::InGame), start_background_music)
             .add_systems(OnExit(AppState::InGame), stop_background_music);
     }
 }
 
+pub fn is_supported_audio_path(path: &str) -> bool {
+    path.rsplit_once('.').is_some_and(|(_, extension)| SUPPORTED_AUDIO_EXTENSIONS.iter().any(|supported| supported.eq_ignore_ascii_case(extension)))
+}
+
+// A sample this build cannot decode is skipped, so the sound falls back along its chain instead of failing to load
 fn setup_audio_handles(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let manifest = SoundManifest::from_ron_str(EMBEDDED_SOUND_MANIFEST).expect("embedded sound manifest is invalid");
+    let load = |path: &str| -> Option<Handle<AudioSource>> {
+        if is_supported_audio_path(path) { return Some(asset_server.load(path.to_string())); }
+        warn!("Skipping {}: this build cannot decode it", path);
+        None
+    };
     commands.insert_resource(GameAudioHandles {
-        ritual_cast: asset_server.load("audio/ritual_cast_placeholder.ogg"),
-        horror_hit: asset_server.load("audio/horror_hit_placeholder.ogg"),
-        horror_death: asset_server.load("audio/horror_death_placeholder.ogg"),
-        survivor_hit: asset_server.load("audio/survivor_hit_placeholder.ogg"),
-        revelation: asset_server.load("audio/revelation_placeholder.ogg"),
-        soul_collect: asset_server.load("audio/soul_collect_placeholder.ogg"),
-        madness_consumes: asset_server.load("audio/madness_consumes_placeholder.ogg"),
-        omen_accepted: asset_server.load("audio/omen_accepted_placeholder.ogg"),
-        horror_projectile: asset_server.load("audio/horror_projectile_placeholder.ogg"), 
-        background_music: asset_server.load("audio/cyclopean_ruins_ambience_placeholder.ogg"),
+        sounds: manifest.sounds.iter().map(|entry| (entry.sound, entry.samples().filter_map(load).collect())).collect(),
+        background_music: load(&manifest.music).unwrap_or_default(),
+        music_layers: manifest.music_layers.iter().filter_map(|layer| load(&layer.path).map(|handle| (layer.phase, handle))).collect(),
     });
 }
 
+// The camera is the listener, since it is what the player sees the world from
+fn attach_spatial_listener(mut commands: Commands, camera_query: Query<Entity, Added<MainCamera>>) {
+    for camera in camera_query.iter() { commands.entity(camera).insert(SpatialListener::new(LISTENER_EAR_GAP)); }
+}
+
+// Gameplay systems only announce what happened; which of those moments are audible is decided here
+pub fn sound_for_game_event(event: &GameEvent) -> Option<SoundEffect> {
+    match event {
+        GameEvent::HorrorKilled(_) => Some(SoundEffect::HorrorDeath),
+        GameEvent::SkillCast { skill_id, .. } => Some(SoundEffect::SkillCast(*skill_id)),
+        GameEvent::DamageDealt { origin: DamageOrigin::Skill(skill_id), .. } => Some(SoundEffect::SkillImpact(*skill_id)),
+        GameEvent::DamageDealt { origin: DamageOrigin::IchorBlast, .. } => Some(SoundEffect::HorrorHit),
+        // Auto-weapons tick too often to give every hit a sound
+        GameEvent::DamageDealt { origin: DamageOrigin::Weapon(_), .. } => None,
+        // Summons already play their own hit sounds where the damage lands
+        GameEvent::DamageDealt { origin: DamageOrigin::Summon(_), .. } => None,
+        // Relic and bomb blasts play a hit sound per horror where they land
+        GameEvent::DamageDealt { origin: DamageOrigin::Relic | DamageOrigin::Consumable(_), .. } => None,
+        GameEvent::SurvivorHit { .. } => Some(SoundEffect::SurvivorHit),
+        GameEvent::LevelUp { .. } => Some(SoundEffect::Revelation),
+        GameEvent::ReliquaryOpened { .. } => None,
+        GameEvent::EliteSpawned { horror_type, .. } => Some(SoundEffect::BossStinger(*horror_type)),
+        // Heard through the music layers instead
+        GameEvent::ElitePhaseChanged { .. } => None,
+        // The rift plays its own opening sound where it stands, and its beacon pings
+        GameEvent::ExtractionRiftOpened { .. } => None,
+        GameEvent::EscortFinished { delivered, .. } => Some(if *delivered { SoundEffect::Revelation } else { SoundEffect::MadnessConsumes }),
+    }
+}
+
+pub fn sound_event_for_game_event(event: &GameEvent) -> Option<PlaySoundEvent> {
+    let effect = sound_for_game_event(event)?;
+    let position = match event {
+        GameEvent::HorrorKilled(kill) => Some(kill.position),
+        GameEvent::SkillCast { position, .. } | GameEvent::DamageDealt { position, .. } => Some(*position),
+        // A stinger announces the elite rather than placing it, so it plays centred
+        _ => None,
+    };
+    Some(PlaySoundEvent { effect, position: position.map(|position| position.truncate()) })
+}
+
+fn game_event_sound_system(mut game_events: EventReader<GameEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>) {
+    for sound_event in game_events.read().filter_map(sound_event_for_game_event) { sound_event_writer.send(sound_event); }
+}
+
 fn play_sound_system(
     mut commands: Commands,
     mut sound_events: EventReader<PlaySoundEvent>,
     audio_handles: Res<GameAudioHandles>,
+    mut sample_rotation: ResMut<SampleRotation>,
+    listener_query: Query<&GlobalTransform, With<SpatialListener>>,
 ) {
+    let listener_position = listener_query.get_single().ok().map(|transform| transform.translation().truncate());
+    // Purely cosmetic, so this stays off the seeded gameplay rng
+    let mut rng = rand::thread_rng();
     for event in sound_events.read() {
-        let source = match event.0 {
-            SoundEffect::RitualCast => audio_handles.ritual_cast.clone(),
-            SoundEffect::HorrorHit => audio_handles.horror_hit.clone(),
-            SoundEffect::HorrorDeath => audio_handles.horror_death.clone(),
-            SoundEffect::SurvivorHit => audio_handles.survivor_hit.clone(),
-            SoundEffect::Revelation => audio_handles.revelation.clone(),
-            SoundEffect::SoulCollect => audio_handles.soul_collect.clone(),
-            SoundEffect::MadnessConsumes => audio_handles.madness_consumes.clone(),
-            SoundEffect::OmenAccepted => audio_handles.omen_accepted.clone(),
-            SoundEffect::HorrorProjectile => audio_handles.horror_projectile.clone(),
-        };
-        commands.spawn(AudioBundle {
-            source,
-            settings: PlaybackSettings::DESPAWN, 
-        });
+        let Some((sound, samples)) = audio_handles.samples_for(event.effect) else { continue; };
+        let source = samples[sample_rotation.next_index(sound, samples.len())].clone();
+        let (speed, volume) = sound_variation(event.effect, rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+        let settings = PlaybackSettings::DESPAWN.with_speed(speed).with_volume(bevy::audio::Volume::new(volume));
+        // Without a listener there is nothing to pan against, so everything plays centred
+        match (event.position, listener_position) {
+            (Some(position), Some(listener_position)) => {
+                if !is_audible(position, listener_position) { continue; }
+                commands.spawn((
+                    AudioBundle { source, settings: settings.with_spatial(true).with_spatial_scale(SpatialScale::new_2d(1.0 / FULL_VOLUME_RADIUS)) },
+                    TransformBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+                ));
+            }
+            _ => {
+                commands.spawn(AudioBundle {
+                    source,
+                    settings, 
+                });
+            }
+        }
     }
 }
 
 fn start_background_music(
     mut commands: Commands,
     audio_handles: Res<GameAudioHandles>,
+    music_intensity: Res<MusicIntensity>,
     music_controller_query: Query<Entity, With<BackgroundMusicController>>, 
 ) {
     if !music_controller_query.is_empty() {
         return;
     }
-    commands.spawn((
-        AudioBundle {
-            source: audio_handles.background_music.clone(),
-            settings: PlaybackSettings {
-                mode: bevy::audio::PlaybackMode::Loop,
-                volume: bevy::audio::Volume::new(0.3), 
-                ..default()
+    // All layers start together and loop silently until needed, so they stay in step with the base track
+    let layers = std::iter::once((ElitePhase::Opening, audio_handles.background_music.clone())).chain(audio_handles.music_layers.iter().cloned());
+    for (phase, source) in layers {
+        let fade = music_intensity.layer_target(phase);
+        commands.spawn((
+            AudioBundle {
+                source,
+                settings: PlaybackSettings {
+                    mode: bevy::audio::PlaybackMode::Loop,
+                    volume: bevy::audio::Volume::new(MUSIC_VOLUME * fade), 
+                    ..default()
+                },
             },
-        },
-        BackgroundMusicController,
-    ));
+            BackgroundMusicController,
+            MusicLayer { phase, fade },
+        ));
+    }
 }
 
 fn stop_background_music(
@@ -114,4 +401,50 @@ fn stop_background_music(
     for entity in music_controller_query.iter() {
         commands.entity(entity).despawn_recursive();
     }
-}
\ No newline at end of file
+}
+// Runs every frame rather than on change: sinks only appear a frame after their audio is spawned
+fn low_health_audio_system(
+    mut commands: Commands,
+    low_health: Res<LowHealth>,
+    audio_handles: Res<GameAudioHandles>,
+    heartbeat_query: Query<(Entity, Option<&AudioSink>), With<HeartbeatLoop>>,
+) {
+    match (heartbeat_playback(&low_health), heartbeat_query.get_single()) {
+        (Some((speed, volume)), Ok((_, Some(sink)))) => {
+            sink.set_speed(speed);
+            sink.set_volume(volume);
+        }
+        (Some(_), Ok((_, None))) => {}
+        (Some((speed, volume)), Err(_)) => {
+            let Some((_, samples)) = audio_handles.samples_for(SoundEffect::Heartbeat) else { return; };
+            commands.spawn((
+                AudioBundle { source: samples[0].clone(), settings: PlaybackSettings::LOOP.with_speed(speed).with_volume(bevy::audio::Volume::new(volume)) },
+                HeartbeatLoop,
+            ));
+        }
+        (None, _) => {
+            for (entity, _) in heartbeat_query.iter() { commands.entity(entity).despawn_recursive(); }
+        }
+    }
+}
+
+fn music_intensity_system(mut game_events: EventReader<GameEvent>, mut music_intensity: ResMut<MusicIntensity>, elite_query: Query<&Health, With<ElitePhase>>) {
+    let any_elite_alive = elite_query.iter().any(|health| health.0 > 0);
+    let mut next = *music_intensity;
+    for event in game_events.read() { next.apply(event, any_elite_alive); }
+    if !any_elite_alive { next = MusicIntensity::default(); }
+    if *music_intensity != next { *music_intensity = next; }
+}
+
+// Also applies the low-health muffle, since both end up as the same sink volume
+fn update_music_layers(time: Res<Time>, music_intensity: Res<MusicIntensity>, low_health: Res<LowHealth>, mut layer_query: Query<(&mut MusicLayer, Option<&AudioSink>)>) {
+    let (music_volume, music_speed) = music_playback(&low_health);
+    let fade_step = time.delta_seconds() / MUSIC_LAYER_FADE_SECS;
+    for (mut layer, sink) in layer_query.iter_mut() {
+        let target = music_intensity.layer_target(layer.phase);
+        if layer.fade != target { layer.fade = if layer.fade < target { (layer.fade + fade_step).min(target) } else { (layer.fade - fade_step).max(target) }; }
+        let Some(sink) = sink else { continue; };
+        sink.set_volume(music_volume * layer.fade);
+        sink.set_speed(music_speed);
+    }
+}
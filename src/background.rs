@@ -1,21 +1,104 @@
 use bevy::prelude::*;
-use crate::camera_systems::MainCamera;
-use crate::game::AppState;
+use rand::Rng;
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::camera_systems::{MainCamera, view_half_extents};
+use crate::components::Velocity;
+use crate::survivor::Survivor;
+use crate::game::{AppState, GameConfig, SCREEN_WIDTH, SCREEN_HEIGHT};
+use crate::floating_origin::OriginShifted;
+use crate::void_boundary::VoidBoundary;
 
 pub const BACKGROUND_TILE_SIZE: f32 = 2048.0;
 const BACKGROUND_Z: f32 = -10.0;
-const GRID_DIMENSION: i32 = 5; 
-const NUM_TILES: usize = (GRID_DIMENSION * GRID_DIMENSION) as usize;
-// Shift the grid when camera moves this fraction of a tile size past the center tile's edge
-const GRID_SHIFT_THRESHOLD_FACTOR: f32 = 0.45; // Previously effectively 0.5
+// Chunks are loaded this many rings past what the camera can see, so roaming never reveals an empty edge
+const CHUNK_LOAD_MARGIN: i32 = 1;
+// A chunk is only dropped this many rings past the load range, so pacing along a border doesn't churn entities
+const CHUNK_UNLOAD_HYSTERESIS: i32 = 1;
+// Parallax motes and ambient particles live in a box around the camera and wrap at its edges.
+// Twice the screen size, so they still fill the view when the camera zooms out.
+pub const AMBIENT_FIELD_HALF_EXTENTS: Vec2 = Vec2::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+// Scroll factor of each parallax layer, farthest first; 1.0 would be locked to the ground
+const PARALLAX_LAYER_FACTORS: [f32; 3] = [0.15, 0.35, 0.6];
+const PARALLAX_LAYER_Z: f32 = -9.5;
+const AMBIENT_PARTICLE_Z: f32 = -8.0;
+// Fraction of the survivor's velocity that pushes ambient particles the opposite way
+const PARTICLE_VELOCITY_RESPONSE: f32 = 0.25;
+pub const BACKGROUND_QUALITY_KEY: KeyCode = KeyCode::F6;
+// How far the void's darkness reaches outward past its edge; enough to fill the view at the widest zoom
+pub const VOID_WALL_REACH: f32 = 4000.0;
+const VOID_WALL_Z: f32 = -7.0;
+const VOID_EDGE_WIDTH: f32 = 12.0;
+const VOID_EDGE_PULSE_SPEED: f32 = 3.0;
+const VOID_WALL_COLOR: Color = Color::rgba(0.12, 0.02, 0.2, 0.75);
+const VOID_EDGE_COLOR: Color = Color::rgb(0.7, 0.35, 1.0);
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackgroundQuality { Low, #[default] Medium, High, }
+
+impl BackgroundQuality {
+    pub fn parallax_layers(self) -> usize {
+        match self { BackgroundQuality::Low => 1, BackgroundQuality::Medium => 2, BackgroundQuality::High => PARALLAX_LAYER_FACTORS.len(), }
+    }
+    pub fn motes_per_layer(self) -> usize {
+        match self { BackgroundQuality::Low => 30, BackgroundQuality::Medium => 50, BackgroundQuality::High => 80, }
+    }
+    pub fn ambient_particles(self) -> usize {
+        match self { BackgroundQuality::Low => 0, BackgroundQuality::Medium => 40, BackgroundQuality::High => 100, }
+    }
+    pub fn next(self) -> Self {
+        match self { BackgroundQuality::Low => BackgroundQuality::Medium, BackgroundQuality::Medium => BackgroundQuality::High, BackgroundQuality::High => BackgroundQuality::Low, }
+    }
+}
+
+// Distant star; drawn at anchor + camera * (1 - factor), so lower factors appear farther away
+#[derive(Component)]
+struct ParallaxMote {
+    factor: f32,
+    anchor: Vec2,
+}
+
+// Drifting spore in world space, nudged by the survivor's movement
+#[derive(Component)]
+pub(crate) struct AmbientParticle {
+    drift: Vec2,
+}
 
 #[derive(Component)]
 struct BackgroundTile;
 
-#[derive(Resource)]
-struct BackgroundGrid {
-    tiles: [Entity; NUM_TILES],
-    grid_logical_center: Vec2,
+// One side of the closing void, or the glowing seam along its edge; sides go right, left, top, bottom
+#[derive(Component)]
+struct VoidWall {
+    side: usize,
+    edge: bool,
+}
+
+// Every ground chunk currently spawned, by chunk coordinate; chunk (0, 0) is centered on the world origin
+#[derive(Resource, Default, Debug)]
+pub struct BackgroundChunks {
+    pub loaded: HashMap<IVec2, Entity>,
+}
+
+pub fn chunk_coord(position: Vec2) -> IVec2 {
+    (position / BACKGROUND_TILE_SIZE).round().as_ivec2()
+}
+
+pub fn chunk_center(coord: IVec2) -> Vec2 {
+    coord.as_vec2() * BACKGROUND_TILE_SIZE
+}
+
+// Rings of chunks around the camera's chunk needed to cover a view of this size
+pub fn chunk_load_radius(view_half_extents: Vec2) -> i32 {
+    (view_half_extents.max_element() / BACKGROUND_TILE_SIZE).ceil() as i32 + CHUNK_LOAD_MARGIN
+}
+
+pub fn chunks_to_load(center: IVec2, radius: i32) -> impl Iterator<Item = IVec2> {
+    (-radius..=radius).flat_map(move |y| (-radius..=radius).map(move |x| center + IVec2::new(x, y)))
+}
+
+pub fn should_unload_chunk(coord: IVec2, center: IVec2, radius: i32) -> bool {
+    (coord - center).abs().max_element() > radius + CHUNK_UNLOAD_HYSTERESIS
 }
 
 pub struct BackgroundPlugin;
@@ -23,111 +106,241 @@ pub struct BackgroundPlugin;
 impl Plugin for BackgroundPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(OnEnter(AppState::InGame), setup_background)
-            .add_systems(Update, infinite_scroll_background.run_if(in_state(AppState::InGame)))
-            .add_systems(OnExit(AppState::InGame), cleanup_background);
+            .init_resource::<BackgroundQuality>()
+            .init_resource::<BackgroundChunks>()
+            .add_systems(OnEnter(AppState::InGame), (setup_ambient_layers, setup_void_walls))
+            .add_systems(Update, (
+                follow_origin_shift,
+                stream_background_chunks,
+                cycle_background_quality,
+                rebuild_ambient_layers.run_if(resource_changed::<BackgroundQuality>),
+                parallax_mote_system,
+                ambient_particle_system,
+                void_wall_system,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), (cleanup_background, cleanup_ambient_layers, cleanup_void_walls));
+    }
+}
+
+// Tiles and spores were moved with everything else; chunk keys and mote anchors are positions kept outside a Transform
+fn follow_origin_shift(mut shifted_events: EventReader<OriginShifted>, mut chunks: ResMut<BackgroundChunks>, mut mote_query: Query<&mut ParallaxMote>) {
+    for shifted in shifted_events.read() {
+        chunks.loaded = chunks.loaded.drain().map(|(coord, entity)| (coord - shifted.chunks, entity)).collect();
+        // Keeps each mote's apparent position moving with the world, since the camera it is drawn against just jumped back
+        for mut mote in mote_query.iter_mut() { let factor = mote.factor; mote.anchor -= shifted.shift * factor; }
+    }
+}
+
+fn stream_background_chunks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    mut chunks: ResMut<BackgroundChunks>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else { return; };
+    let center = chunk_coord(camera_transform.translation.truncate());
+    let radius = chunk_load_radius(view_half_extents(projection));
+
+    chunks.loaded.retain(|coord, entity| {
+        if !should_unload_chunk(*coord, center, radius) { return true; }
+        commands.entity(*entity).despawn_recursive();
+        false
+    });
+    for coord in chunks_to_load(center, radius) {
+        if chunks.loaded.contains_key(&coord) { continue; }
+        let tile_entity = commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load("sprites/cyclopean_ruins_tile_placeholder.png"),
+                sprite: Sprite { custom_size: Some(Vec2::splat(BACKGROUND_TILE_SIZE)), ..default() },
+                transform: Transform::from_translation(chunk_center(coord).extend(BACKGROUND_Z)),
+                ..default()
+            },
+            BackgroundTile,
+            Name::new(format!("BackgroundChunk_{}_{}", coord.x, coord.y)),
+        )).id();
+        chunks.loaded.insert(coord, tile_entity);
+    }
+}
+
+fn cleanup_background(mut commands: Commands, query: Query<Entity, With<BackgroundTile>>, mut chunks: ResMut<BackgroundChunks>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
+    chunks.loaded.clear();
+}
+// Moves a position by whole field widths until it lies inside the field around the center
+pub fn wrap_into_field(position: Vec2, center: Vec2, half_extents: Vec2) -> Vec2 {
+    let size = half_extents * 2.0;
+    let offset = position - center + half_extents;
+    center - half_extents + Vec2::new(offset.x.rem_euclid(size.x), offset.y.rem_euclid(size.y))
 }
 
-fn setup_background(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut tiles = [Entity::PLACEHOLDER; NUM_TILES];
-    let grid_half_span_offset = (GRID_DIMENSION as f32 - 1.0) / 2.0; 
+fn random_field_offset<R: Rng>(rng: &mut R) -> Vec2 {
+    Vec2::new(rng.gen_range(-AMBIENT_FIELD_HALF_EXTENTS.x..AMBIENT_FIELD_HALF_EXTENTS.x), rng.gen_range(-AMBIENT_FIELD_HALF_EXTENTS.y..AMBIENT_FIELD_HALF_EXTENTS.y))
+}
 
-    for i in 0..GRID_DIMENSION { 
-        for j in 0..GRID_DIMENSION { 
-            let x_pos = (j as f32 - grid_half_span_offset) * BACKGROUND_TILE_SIZE;
-            let y_pos = (i as f32 - grid_half_span_offset) * BACKGROUND_TILE_SIZE;
-            
-            let tile_entity = commands.spawn((
+fn spawn_ambient_layers(commands: &mut Commands, quality: BackgroundQuality, camera_pos: Vec2) {
+    let mut rng = rand::thread_rng();
+    let first_layer = PARALLAX_LAYER_FACTORS.len() - quality.parallax_layers();
+    for (layer, factor) in PARALLAX_LAYER_FACTORS.iter().enumerate().skip(first_layer) {
+        // Nearer layers are bigger and brighter
+        let size = 1.5 + layer as f32 * 1.5;
+        let brightness = 0.35 + layer as f32 * 0.2;
+        for _ in 0..quality.motes_per_layer() {
+            commands.spawn((
                 SpriteBundle {
-                    texture: asset_server.load("sprites/cyclopean_ruins_tile_placeholder.png"),
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::splat(BACKGROUND_TILE_SIZE)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(x_pos, y_pos, BACKGROUND_Z),
+                    sprite: Sprite { custom_size: Some(Vec2::splat(size)), color: Color::rgba(0.8, 0.85, 1.0, brightness), ..default() },
+                    transform: Transform::from_translation((camera_pos + random_field_offset(&mut rng)).extend(PARALLAX_LAYER_Z + layer as f32 * 0.1)),
                     ..default()
                 },
-                BackgroundTile,
-                Name::new(format!("BackgroundTile_{}_{}", i, j)),
-            )).id();
-            tiles[(i * GRID_DIMENSION + j) as usize] = tile_entity;
+                ParallaxMote { factor: *factor, anchor: random_field_offset(&mut rng) },
+                Name::new(format!("ParallaxMote_{}", layer)),
+            ));
         }
     }
-    commands.insert_resource(BackgroundGrid { tiles, grid_logical_center: Vec2::ZERO });
+    for _ in 0..quality.ambient_particles() {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite { custom_size: Some(Vec2::splat(rng.gen_range(3.0..6.0))), color: Color::rgba(0.55, 0.8, 0.45, rng.gen_range(0.2..0.45)), ..default() },
+                transform: Transform::from_translation((camera_pos + random_field_offset(&mut rng)).extend(AMBIENT_PARTICLE_Z)),
+                ..default()
+            },
+            AmbientParticle { drift: Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(5.0..20.0) },
+            Name::new("AmbientSpore"),
+        ));
+    }
+}
+
+fn setup_ambient_layers(mut commands: Commands, quality: Res<BackgroundQuality>, camera_query: Query<&Transform, With<MainCamera>>) {
+    let camera_pos = camera_query.get_single().map(|transform| transform.translation.truncate()).unwrap_or(Vec2::ZERO);
+    spawn_ambient_layers(&mut commands, *quality, camera_pos);
+}
+
+fn cycle_background_quality(keyboard_input: Res<ButtonInput<KeyCode>>, mut quality: ResMut<BackgroundQuality>) {
+    if keyboard_input.just_pressed(BACKGROUND_QUALITY_KEY) {
+        *quality = quality.next();
+        info!("Background quality: {:?}", *quality);
+    }
+}
+
+fn rebuild_ambient_layers(
+    mut commands: Commands,
+    quality: Res<BackgroundQuality>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+    ambient_query: Query<Entity, Or<(With<ParallaxMote>, With<AmbientParticle>)>>,
+) {
+    // The setup on entering the game already built the current quality
+    if quality.is_added() { return; }
+    for entity in ambient_query.iter() { commands.entity(entity).despawn_recursive(); }
+    let camera_pos = camera_query.get_single().map(|transform| transform.translation.truncate()).unwrap_or(Vec2::ZERO);
+    spawn_ambient_layers(&mut commands, *quality, camera_pos);
+}
+
+fn parallax_mote_system(
+    camera_query: Query<&Transform, With<MainCamera>>,
+    mut mote_query: Query<(&mut Transform, &ParallaxMote), Without<MainCamera>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else { return; };
+    let camera_pos = camera_transform.translation.truncate();
+    for (mut transform, mote) in mote_query.iter_mut() {
+        let apparent = mote.anchor + camera_pos * (1.0 - mote.factor);
+        transform.translation = wrap_into_field(apparent, camera_pos, AMBIENT_FIELD_HALF_EXTENTS).extend(transform.translation.z);
+    }
 }
 
-fn infinite_scroll_background(
+fn ambient_particle_system(
+    time: Res<Time>,
     camera_query: Query<&Transform, With<MainCamera>>,
-    mut background_grid: ResMut<BackgroundGrid>,
-    mut tile_transforms: Query<&mut Transform, (With<BackgroundTile>, Without<MainCamera>)>,
+    survivor_query: Query<&Velocity, With<Survivor>>,
+    mut particle_query: Query<(&mut Transform, &AmbientParticle), Without<MainCamera>>,
 ) {
     let Ok(camera_transform) = camera_query.get_single() else { return; };
     let camera_pos = camera_transform.translation.truncate();
+    let survivor_velocity = survivor_query.get_single().map(|velocity| velocity.0).unwrap_or(Vec2::ZERO);
+    for (mut transform, particle) in particle_query.iter_mut() {
+        let moved = transform.translation.truncate() + (particle.drift - survivor_velocity * PARTICLE_VELOCITY_RESPONSE) * time.delta_seconds();
+        transform.translation = wrap_into_field(moved, camera_pos, AMBIENT_FIELD_HALF_EXTENTS).extend(transform.translation.z);
+    }
+}
+
+fn cleanup_ambient_layers(mut commands: Commands, query: Query<Entity, Or<(With<ParallaxMote>, With<AmbientParticle>)>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Centre and size of the four slabs covering everything past the boundary, reaching `reach` outward.
+// The top and bottom slabs span the corners too, so the sides never overlap
+pub fn void_wall_rects(center: Vec2, half_extent: f32, reach: f32) -> [(Vec2, Vec2); 4] {
+    let offset = half_extent + reach / 2.0;
+    let side = Vec2::new(reach, half_extent * 2.0);
+    let cap = Vec2::new((half_extent + reach) * 2.0, reach);
+    [
+        (center + Vec2::new(offset, 0.0), side),
+        (center - Vec2::new(offset, 0.0), side),
+        (center + Vec2::new(0.0, offset), cap),
+        (center - Vec2::new(0.0, offset), cap),
+    ]
+}
 
-    let dx = camera_pos.x - background_grid.grid_logical_center.x;
-    let dy = camera_pos.y - background_grid.grid_logical_center.y;
-
-    let threshold = BACKGROUND_TILE_SIZE * GRID_SHIFT_THRESHOLD_FACTOR;
-    
-    let mut shift_x_tiles_count = 0i32;
-    let mut shift_y_tiles_count = 0i32;
-
-    if dx > threshold {
-        shift_x_tiles_count = ((dx - threshold) / BACKGROUND_TILE_SIZE).ceil() as i32;
-         if shift_x_tiles_count == 0 && dx > threshold { shift_x_tiles_count = 1; } // Ensure at least one tile shift if over threshold
-    } else if dx < -threshold {
-        shift_x_tiles_count = ((dx + threshold) / BACKGROUND_TILE_SIZE).floor() as i32;
-        if shift_x_tiles_count == 0 && dx < -threshold { shift_x_tiles_count = -1; }
-    }
-
-    if dy > threshold {
-        shift_y_tiles_count = ((dy - threshold) / BACKGROUND_TILE_SIZE).ceil() as i32;
-        if shift_y_tiles_count == 0 && dy > threshold { shift_y_tiles_count = 1; }
-    } else if dy < -threshold {
-        shift_y_tiles_count = ((dy + threshold) / BACKGROUND_TILE_SIZE).floor() as i32;
-        if shift_y_tiles_count == 0 && dy < -threshold { shift_y_tiles_count = -1; }
-    }
-    
-    if shift_x_tiles_count != 0 || shift_y_tiles_count != 0 {
-        let grid_total_span = GRID_DIMENSION as f32 * BACKGROUND_TILE_SIZE;
-        let half_grid_span_from_center = (GRID_DIMENSION as f32 / 2.0) * BACKGROUND_TILE_SIZE;
-
-        // Update the logical center of the grid first
-        let new_grid_center_x = background_grid.grid_logical_center.x + shift_x_tiles_count as f32 * BACKGROUND_TILE_SIZE;
-        let new_grid_center_y = background_grid.grid_logical_center.y + shift_y_tiles_count as f32 * BACKGROUND_TILE_SIZE;
-
-        for tile_entity_id in background_grid.tiles.iter() {
-            if let Ok(mut tile_transform) = tile_transforms.get_mut(*tile_entity_id) {
-                // Adjust tile position based on how much the logical center shifted
-                tile_transform.translation.x += shift_x_tiles_count as f32 * BACKGROUND_TILE_SIZE;
-                tile_transform.translation.y += shift_y_tiles_count as f32 * BACKGROUND_TILE_SIZE;
-
-                // Wrap tiles that are now too far from the NEW logical center
-                if tile_transform.translation.x < new_grid_center_x - half_grid_span_from_center {
-                    tile_transform.translation.x += grid_total_span;
-                } else if tile_transform.translation.x >= new_grid_center_x + half_grid_span_from_center { // Use >= for upper bound
-                    tile_transform.translation.x -= grid_total_span;
-                }
-                
-                if tile_transform.translation.y < new_grid_center_y - half_grid_span_from_center {
-                    tile_transform.translation.y += grid_total_span;
-                } else if tile_transform.translation.y >= new_grid_center_y + half_grid_span_from_center {
-                    tile_transform.translation.y -= grid_total_span;
-                }
-            }
+// The seams sit on the boundary itself and meet at the corners
+pub fn void_edge_rects(center: Vec2, half_extent: f32, width: f32) -> [(Vec2, Vec2); 4] {
+    let vertical = Vec2::new(width, half_extent * 2.0 + width);
+    let horizontal = Vec2::new(half_extent * 2.0 + width, width);
+    [
+        (center + Vec2::new(half_extent, 0.0), vertical),
+        (center - Vec2::new(half_extent, 0.0), vertical),
+        (center + Vec2::new(0.0, half_extent), horizontal),
+        (center - Vec2::new(0.0, half_extent), horizontal),
+    ]
+}
+
+// Spawned hidden every time; the system below shows and places them only when the run has a closing void
+fn setup_void_walls(mut commands: Commands) {
+    for side in 0..4 {
+        for edge in [false, true] {
+            let color = if edge { VOID_EDGE_COLOR } else { VOID_WALL_COLOR };
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite { color, custom_size: Some(Vec2::ONE), ..default() },
+                    transform: Transform::from_xyz(0.0, 0.0, VOID_WALL_Z + if edge { 0.1 } else { 0.0 }),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                VoidWall { side, edge },
+                Name::new(if edge { "VoidEdge" } else { "VoidWall" }),
+            ));
         }
-        // Officially update the grid's logical center
-        background_grid.grid_logical_center.x = new_grid_center_x;
-        background_grid.grid_logical_center.y = new_grid_center_y;
     }
 }
 
+fn void_wall_system(
+    time: Res<Time>,
+    boundary: Res<VoidBoundary>,
+    game_config: Res<GameConfig>,
+    mut wall_query: Query<(&mut Transform, &mut Sprite, &mut Visibility, &VoidWall)>,
+) {
+    let Some(half_extent) = boundary.half_extent else {
+        for (_, _, mut visibility, _) in wall_query.iter_mut() { *visibility = Visibility::Hidden; }
+        return;
+    };
+    let walls = void_wall_rects(game_config.arena_center, half_extent, VOID_WALL_REACH);
+    let edges = void_edge_rects(game_config.arena_center, half_extent, VOID_EDGE_WIDTH);
+    let pulse = 0.6 + 0.4 * (time.elapsed_seconds() * VOID_EDGE_PULSE_SPEED).sin().abs();
+    for (mut transform, mut sprite, mut visibility, wall) in wall_query.iter_mut() {
+        let (position, size) = if wall.edge { edges[wall.side] } else { walls[wall.side] };
+        transform.translation = position.extend(transform.translation.z);
+        sprite.custom_size = Some(size);
+        if wall.edge { sprite.color.set_a(pulse); }
+        *visibility = Visibility::Visible;
+    }
+}
 
-fn cleanup_background(mut commands: Commands, query: Query<Entity, With<BackgroundTile>>) {
+fn cleanup_void_walls(mut commands: Commands, query: Query<Entity, With<VoidWall>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
+
 //Placeholder for fleshy_landscape_tile_placeholder.png if used
 //The current code only uses one background tile, so background_tile2.png is not used.
\ No newline at end of file
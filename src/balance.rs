@@ -0,0 +1,226 @@
+use bevy::{
+    prelude::*,
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+use crate::{
+    horror_stats::DATA_RELOAD_KEY,
+    survivor::DamageSource,
+};
+
+pub const BALANCE_CONFIG_PATH: &str = "data/balance.ron";
+// Compiled-in copy so the first frame already plays with the shipped values
+const EMBEDDED_BALANCE_CONFIG: &str = include_str!("../assets/data/balance.ron");
+
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BalanceConfig>()
+            .register_asset_loader(BalanceConfigLoader)
+            .init_resource::<BalanceConfig>()
+            .init_resource::<BalanceConfigHandle>()
+            .add_systems(Startup, load_balance_config)
+            .add_systems(Update, (apply_loaded_balance_config, reload_balance_config_on_key));
+    }
+}
+
+// Loose tuning numbers gathered in one place; gameplay reads the resource each frame, so debug menu edits apply immediately
+#[derive(Resource, Asset, TypePath, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BalanceConfig {
+    pub ichor_blast_spread_degrees: f32,
+    pub soul_value_multiplier: f32,
+    pub spawn_invincibility_secs: f32,
+    pub contact_grace_secs: f32,
+    pub projectile_grace_secs: f32,
+    pub spawn_view_padding: f32,
+    pub spawn_clearance: f32,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self { Self::from_ron_str(EMBEDDED_BALANCE_CONFIG).expect("embedded balance config is invalid") }
+}
+
+impl BalanceConfig {
+    pub fn from_ron_str(source: &str) -> Result<Self, BalanceConfigError> {
+        let config: BalanceConfig = ron::de::from_str(source)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), BalanceConfigError> {
+        match BalanceField::ALL.iter().find(|field| !field.range().contains(&self.get(**field))) {
+            Some(field) => Err(BalanceConfigError::OutOfRange(*field)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, field: BalanceField) -> f32 {
+        match field {
+            BalanceField::IchorBlastSpread => self.ichor_blast_spread_degrees,
+            BalanceField::SoulValue => self.soul_value_multiplier,
+            BalanceField::SpawnInvincibility => self.spawn_invincibility_secs,
+            BalanceField::ContactGrace => self.contact_grace_secs,
+            BalanceField::ProjectileGrace => self.projectile_grace_secs,
+            BalanceField::SpawnViewPadding => self.spawn_view_padding,
+            BalanceField::SpawnClearance => self.spawn_clearance,
+        }
+    }
+
+    // Clamped to the field's range, so a slider can never push a value the file would be rejected for
+    pub fn set(&mut self, field: BalanceField, value: f32) {
+        let value = value.clamp(*field.range().start(), *field.range().end());
+        let slot = match field {
+            BalanceField::IchorBlastSpread => &mut self.ichor_blast_spread_degrees,
+            BalanceField::SoulValue => &mut self.soul_value_multiplier,
+            BalanceField::SpawnInvincibility => &mut self.spawn_invincibility_secs,
+            BalanceField::ContactGrace => &mut self.contact_grace_secs,
+            BalanceField::ProjectileGrace => &mut self.projectile_grace_secs,
+            BalanceField::SpawnViewPadding => &mut self.spawn_view_padding,
+            BalanceField::SpawnClearance => &mut self.spawn_clearance,
+        };
+        *slot = value;
+    }
+
+    // Hazards keep their fixed window until something deals hazard damage
+    pub fn grace_secs(&self, source: DamageSource) -> f32 {
+        match source {
+            DamageSource::Contact => self.contact_grace_secs,
+            DamageSource::Projectile => self.projectile_grace_secs,
+            DamageSource::Hazard => source.grace_secs(),
+        }
+    }
+
+    pub fn scaled_soul_value(&self, xp_value: u32) -> u32 { (xp_value as f32 * self.soul_value_multiplier).round() as u32 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceField { IchorBlastSpread, SoulValue, SpawnInvincibility, ContactGrace, ProjectileGrace, SpawnViewPadding, SpawnClearance, }
+
+impl BalanceField {
+    pub const ALL: [BalanceField; 7] = [
+        BalanceField::IchorBlastSpread, BalanceField::SoulValue, BalanceField::SpawnInvincibility, BalanceField::ContactGrace,
+        BalanceField::ProjectileGrace, BalanceField::SpawnViewPadding, BalanceField::SpawnClearance,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BalanceField::IchorBlastSpread => "Blast spread (deg)",
+            BalanceField::SoulValue => "Soul value x",
+            BalanceField::SpawnInvincibility => "Spawn invincibility (s)",
+            BalanceField::ContactGrace => "Contact grace (s)",
+            BalanceField::ProjectileGrace => "Projectile grace (s)",
+            BalanceField::SpawnViewPadding => "Spawn view padding",
+            BalanceField::SpawnClearance => "Spawn wall clearance",
+        }
+    }
+
+    pub fn range(&self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            BalanceField::IchorBlastSpread => 0.0..=45.0,
+            BalanceField::SoulValue => 0.0..=5.0,
+            BalanceField::SpawnInvincibility => 0.0..=5.0,
+            BalanceField::ContactGrace | BalanceField::ProjectileGrace => 0.0..=2.0,
+            BalanceField::SpawnViewPadding => 0.0..=400.0,
+            BalanceField::SpawnClearance => 0.0..=200.0,
+        }
+    }
+
+    // Where the value sits along its range, 0 to 1, for drawing a slider
+    pub fn fraction(&self, value: f32) -> f32 {
+        let range = self.range();
+        ((value - range.start()) / (range.end() - range.start())).clamp(0.0, 1.0)
+    }
+
+    pub fn value_at(&self, fraction: f32) -> f32 {
+        let range = self.range();
+        range.start() + (range.end() - range.start()) * fraction.clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug)]
+pub enum BalanceConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    OutOfRange(BalanceField),
+}
+
+impl std::fmt::Display for BalanceConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceConfigError::Io(error) => write!(f, "could not read balance config: {}", error),
+            BalanceConfigError::Parse(error) => write!(f, "could not parse balance config: {}", error),
+            BalanceConfigError::OutOfRange(field) => {
+                let range = field.range();
+                write!(f, "{} must be between {} and {}", field.label(), range.start(), range.end())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BalanceConfigError {}
+
+impl From<std::io::Error> for BalanceConfigError {
+    fn from(error: std::io::Error) -> Self { BalanceConfigError::Io(error) }
+}
+
+impl From<ron::error::SpannedError> for BalanceConfigError {
+    fn from(error: ron::error::SpannedError) -> Self { BalanceConfigError::Parse(error) }
+}
+
+#[derive(Default)]
+pub struct BalanceConfigLoader;
+
+impl AssetLoader for BalanceConfigLoader {
+    type Asset = BalanceConfig;
+    type Settings = ();
+    type Error = BalanceConfigError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut source = String::new();
+            reader.read_to_string(&mut source).await?;
+            BalanceConfig::from_ron_str(&source)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["balance.ron"]
+    }
+}
+
+#[derive(Resource, Default)]
+struct BalanceConfigHandle(Option<Handle<BalanceConfig>>);
+
+fn load_balance_config(asset_server: Res<AssetServer>, mut handle: ResMut<BalanceConfigHandle>) {
+    handle.0 = Some(asset_server.load(BALANCE_CONFIG_PATH));
+}
+
+// A reload replaces any slider edits; a file that fails validation is rejected by the loader and the current values stay
+fn apply_loaded_balance_config(
+    mut asset_events: EventReader<AssetEvent<BalanceConfig>>,
+    configs: Res<Assets<BalanceConfig>>,
+    handle: Res<BalanceConfigHandle>,
+    mut balance: ResMut<BalanceConfig>,
+) {
+    for event in asset_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else { continue; };
+        if handle.0.as_ref().map(|handle| handle.id()) != Some(*id) { continue; }
+        if let Some(config) = configs.get(*id) {
+            *balance = *config;
+            info!("Loaded balance config from {}", BALANCE_CONFIG_PATH);
+        }
+    }
+}
+
+fn reload_balance_config_on_key(keyboard_input: Res<ButtonInput<KeyCode>>, asset_server: Res<AssetServer>) {
+    if keyboard_input.just_pressed(DATA_RELOAD_KEY) {
+        asset_server.reload(BALANCE_CONFIG_PATH);
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{
+    game::AppState,
+    game_events::GameEvent,
+    horror::{Horror, HorrorType},
+    horror_stats::HorrorStatsEntry,
+};
+
+pub struct BestiaryPlugin;
+
+impl Plugin for BestiaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Bestiary>()
+            .init_resource::<RunKills>()
+            .add_systems(Update, (record_sightings, record_bestiary_events).run_if(in_state(AppState::InGame)))
+            // Practice fights leave for the menu instead of the game over screen
+            .add_systems(OnEnter(AppState::GameOver), bank_run_kills)
+            .add_systems(OnEnter(AppState::MainMenu), bank_run_kills);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestiaryEntry {
+    pub horror_type: HorrorType,
+    pub kills: u32,
+    // Elites stand in for bosses; once one has been met it can be fought again in boss practice
+    #[serde(default)]
+    pub elite_encountered: bool,
+}
+
+// Horror types the player has met, in the order they were first seen; kept in the profile across runs
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bestiary {
+    pub entries: Vec<BestiaryEntry>,
+}
+
+impl Bestiary {
+    pub fn entry(&self, horror_type: HorrorType) -> Option<&BestiaryEntry> {
+        self.entries.iter().find(|entry| entry.horror_type == horror_type)
+    }
+
+    pub fn is_discovered(&self, horror_type: HorrorType) -> bool { self.entry(horror_type).is_some() }
+
+    // Returns true the first time a type is seen
+    pub fn record_sighting(&mut self, horror_type: HorrorType) -> bool {
+        if self.is_discovered(horror_type) { return false; }
+        self.entries.push(BestiaryEntry { horror_type, kills: 0, elite_encountered: false });
+        true
+    }
+
+    pub fn record_elite(&mut self, horror_type: HorrorType) {
+        self.record_sighting(horror_type);
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.horror_type == horror_type) { entry.elite_encountered = true; }
+    }
+
+    pub fn encountered_elites(&self) -> Vec<HorrorType> {
+        self.entries.iter().filter(|entry| entry.elite_encountered).map(|entry| entry.horror_type).collect()
+    }
+
+    // A kill counts as a sighting too, in case the horror died on the frame it appeared
+    pub fn record_kill(&mut self, horror_type: HorrorType) { self.add_kills(horror_type, 1); }
+
+    pub fn add_kills(&mut self, horror_type: HorrorType, kills: u32) {
+        self.record_sighting(horror_type);
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.horror_type == horror_type) { entry.kills = entry.kills.saturating_add(kills); }
+    }
+
+    pub fn bank(&mut self, run_kills: &mut RunKills) {
+        for (horror_type, kills) in run_kills.0.drain(..) { self.add_kills(horror_type, kills); }
+    }
+}
+
+// Kills from the run in progress. The Bestiary is saved whenever it changes, so kills wait here
+// and are banked once the run ends instead of rewriting the profile on every kill
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunKills(pub Vec<(HorrorType, u32)>);
+
+impl RunKills {
+    pub fn record(&mut self, horror_type: HorrorType) {
+        match self.0.iter_mut().find(|(killed, _)| *killed == horror_type) {
+            Some((_, kills)) => *kills += 1,
+            None => self.0.push((horror_type, 1)),
+        }
+    }
+
+    pub fn kills(&self, horror_type: HorrorType) -> u32 {
+        self.0.iter().find(|(killed, _)| *killed == horror_type).map_or(0, |(_, kills)| *kills)
+    }
+}
+
+pub fn horror_name(horror_type: HorrorType) -> &'static str {
+    match horror_type {
+        HorrorType::SkitteringShadowling => "Skittering Shadowling",
+        HorrorType::FloatingEyeball => "Floating Eyeball",
+        HorrorType::AmorphousFleshbeast => "Amorphous Fleshbeast",
+        HorrorType::VoidBlinker => "Void Blinker",
+        HorrorType::FleshWeaver => "Flesh Weaver",
+        HorrorType::CrawlingTorment => "Crawling Torment",
+        HorrorType::FrenziedBehemoth => "Frenzied Behemoth",
+    }
+}
+
+pub fn horror_behavior(horror_type: HorrorType) -> &'static str {
+    match horror_type {
+        HorrorType::SkitteringShadowling => "Rushes straight at its prey in numbers.",
+        HorrorType::FloatingEyeball => "Keeps its distance and spits ichor, then drifts aside before the next volley.",
+        HorrorType::AmorphousFleshbeast => "Slow and heavy; soaks up punishment on its way in.",
+        HorrorType::VoidBlinker => "Fades out of sight and reappears somewhere near its prey.",
+        HorrorType::FleshWeaver => "Hangs back from its prey and knits Crawling Torments out of its own flesh.",
+        HorrorType::CrawlingTorment => "A weaver's spawn; weak alone but rarely alone.",
+        HorrorType::FrenziedBehemoth => "Flushes red, then charges in a straight line.",
+    }
+}
+
+// Stats as they are at the first cycle; later cycles make every horror tougher and faster
+pub fn stat_line(entry: &HorrorStatsEntry) -> String {
+    let mut line = format!("Health {:.0}   Contact damage {}   Speed {:.0}", entry.base_health * entry.health_scaling, entry.damage_on_collision, entry.base_speed);
+    if let Some(ranged) = &entry.ranged { line.push_str(&format!("   Ranged {} dmg at {:.0}", ranged.projectile_damage, ranged.range)); }
+    line
+}
+
+fn record_sightings(mut bestiary: ResMut<Bestiary>, new_horrors: Query<&Horror, Added<Horror>>) {
+    for horror in new_horrors.iter() {
+        // Only touch the resource on a discovery, so the profile isn't rewritten for every spawn
+        if !bestiary.is_discovered(horror.horror_type) { bestiary.record_sighting(horror.horror_type); }
+    }
+}
+
+fn record_bestiary_events(mut bestiary: ResMut<Bestiary>, mut run_kills: ResMut<RunKills>, mut game_events: EventReader<GameEvent>) {
+    for event in game_events.read() {
+        match event {
+            GameEvent::HorrorKilled(kill) => {
+                // A horror that died on the frame it appeared is still a discovery, and that is worth saving right away
+                if !bestiary.is_discovered(kill.horror_type) { bestiary.record_sighting(kill.horror_type); }
+                run_kills.record(kill.horror_type);
+            }
+            GameEvent::EliteSpawned { horror_type, .. } if !bestiary.entry(*horror_type).is_some_and(|entry| entry.elite_encountered) => bestiary.record_elite(*horror_type),
+            _ => {}
+        }
+    }
+}
+
+fn bank_run_kills(mut bestiary: ResMut<Bestiary>, mut run_kills: ResMut<RunKills>) {
+    if run_kills.0.is_empty() { return; }
+    bestiary.bank(&mut run_kills);
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use crate::{
+    game::{AppState, GameState},
+    game_events::GameEvent,
+    horror::{spawn_horror_type, HorrorSpawnTimer, HorrorType},
+    horror_stats::HorrorStatsLibrary,
+    platform::{GameStorage, SystemClipboard},
+    bestiary::{horror_name, Bestiary},
+    challenge::ActiveChallenge,
+    debug_menu::DebugImportBuildEvent,
+    run_history::BuildCode,
+    survivor::Survivor,
+};
+
+pub const BOSS_PRACTICE_KEY: KeyCode = KeyCode::KeyP;
+const PREVIOUS_BOSS_KEY: KeyCode = KeyCode::Comma;
+const NEXT_BOSS_KEY: KeyCode = KeyCode::Period;
+const PRACTICE_BUILD_TOGGLE_KEY: KeyCode = KeyCode::KeyB;
+// Far enough that the fight opens with the boss on screen but not on top of the survivor
+const PRACTICE_BOSS_DISTANCE: f32 = 400.0;
+const PRACTICE_MENU_COLOR: Color = Color::rgb(0.95, 0.45, 0.35);
+
+pub struct BossPracticePlugin;
+
+impl Plugin for BossPracticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BossPractice>()
+            .add_systems(OnEnter(AppState::MainMenu), (end_practice_fight, setup_practice_menu_text).chain())
+            .add_systems(Update, (choose_practice_fight, update_practice_menu_text).chain().run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), (despawn_practice_menu_text, isolate_practice_arena))
+            .add_systems(Update, (spawn_practice_boss, finish_practice_on_boss_kill).chain().run_if(in_state(AppState::InGame)))
+            // Runs after every Update system has had its say, so no death or extraction slips through to the results screen
+            .add_systems(PostUpdate, leave_practice_instead_of_game_over);
+    }
+}
+
+// A fight in progress: which elite is being practiced against, and whether it has been put in the arena yet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PracticeFight {
+    pub boss: HorrorType,
+    pub boss_spawned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PracticeResult {
+    pub boss: HorrorType,
+    pub defeated: bool,
+    pub secs: f32,
+}
+
+// Elites stand in for bosses until there are real ones; any elite met on a run can be fought here
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct BossPractice {
+    pub selected: usize,
+    pub use_exported_build: bool,
+    pub fight: Option<PracticeFight>,
+    pub last_result: Option<PracticeResult>,
+}
+
+impl BossPractice {
+    pub fn selected_boss(&self, bestiary: &Bestiary) -> Option<HorrorType> {
+        let bosses = bestiary.encountered_elites();
+        if bosses.is_empty() { return None; }
+        Some(bosses[self.selected % bosses.len()])
+    }
+
+    // Wraps around both ways, so the list can be stepped through from either end
+    pub fn cycle(&mut self, step: isize, boss_count: usize) {
+        if boss_count == 0 { self.selected = 0; return; }
+        self.selected = (self.selected as isize + step).rem_euclid(boss_count as isize) as usize;
+    }
+
+    pub fn is_active(&self) -> bool { self.fight.is_some() }
+}
+
+pub fn practice_menu_label(practice: &BossPractice, bestiary: &Bestiary) -> String {
+    let Some(boss) = practice.selected_boss(bestiary) else { return "Boss Practice: meet an elite on a run to unlock".to_string(); };
+    let build = if practice.use_exported_build { "exported build" } else { "fresh survivor" };
+    let mut label = format!("Boss Practice (P): {} (< / >)   B: {}", horror_name(boss), build);
+    if let Some(result) = practice.last_result {
+        let verdict = if result.defeated { "Defeated" } else { "Fell to" };
+        label.push_str(&format!("\nLast: {} {} in {:.1}s", verdict, horror_name(result.boss), result.secs));
+    }
+    label
+}
+
+#[derive(Component)]
+struct PracticeMenuText;
+
+// The run itself is started by the main menu; this only decides that it is a practice fight
+fn choose_practice_fight(keyboard_input: Res<ButtonInput<KeyCode>>, bestiary: Res<Bestiary>, mut practice: ResMut<BossPractice>, mut active_challenge: ResMut<ActiveChallenge>) {
+    let boss_count = bestiary.encountered_elites().len();
+    if keyboard_input.just_pressed(PREVIOUS_BOSS_KEY) { practice.cycle(-1, boss_count); }
+    if keyboard_input.just_pressed(NEXT_BOSS_KEY) { practice.cycle(1, boss_count); }
+    if keyboard_input.just_pressed(PRACTICE_BUILD_TOGGLE_KEY) { practice.use_exported_build = !practice.use_exported_build; }
+    if keyboard_input.just_pressed(BOSS_PRACTICE_KEY) {
+        let Some(boss) = practice.selected_boss(&bestiary) else { return; };
+        active_challenge.0 = None;
+        practice.fight = Some(PracticeFight { boss, boss_spawned: false });
+    }
+}
+
+fn setup_practice_menu_text(mut commands: Commands, asset_server: Res<AssetServer>, practice: Res<BossPractice>, bestiary: Res<Bestiary>) {
+    commands.spawn((
+        TextBundle::from_section(practice_menu_label(&practice, &bestiary), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: PRACTICE_MENU_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(80.0), right: Val::Px(20.0), ..default() })
+            .with_text_justify(JustifyText::Right),
+        PracticeMenuText,
+    ));
+}
+
+fn update_practice_menu_text(practice: Res<BossPractice>, bestiary: Res<Bestiary>, mut text_query: Query<&mut Text, With<PracticeMenuText>>) {
+    if !practice.is_changed() { return; }
+    for mut text in text_query.iter_mut() { text.sections[0].value = practice_menu_label(&practice, &bestiary); }
+}
+
+fn despawn_practice_menu_text(mut commands: Commands, text_query: Query<Entity, With<PracticeMenuText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+// Nothing else spawns while the boss is being practiced against
+fn isolate_practice_arena(practice: Res<BossPractice>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>) {
+    if practice.is_active() { horror_spawn_timer.timer.pause(); }
+}
+
+fn spawn_practice_boss(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    horror_stats: Res<HorrorStatsLibrary>,
+    mut practice: ResMut<BossPractice>,
+    player_query: Query<&Transform, With<Survivor>>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    mut import_writer: EventWriter<DebugImportBuildEvent>,
+    storage: Res<GameStorage>,
+    mut clipboard: NonSendMut<SystemClipboard>,
+) {
+    let Some(fight) = practice.fight.filter(|fight| !fight.boss_spawned) else { return; };
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let position = (player_transform.translation.truncate() + Vec2::Y * PRACTICE_BOSS_DISTANCE).extend(0.5);
+    spawn_horror_type(&mut commands, &asset_server, &horror_stats, fight.boss, position, 1.0, true);
+    game_event_writer.send(GameEvent::EliteSpawned { horror_type: fight.boss, position });
+    // Same clipboard and import path as the sandbox, so a build exported from any run can be brought in
+    if practice.use_exported_build {
+        match BuildCode::from_paste(clipboard.paste(), storage.0.as_ref()) {
+            Ok(build) => { import_writer.send(DebugImportBuildEvent(build)); }
+            Err(error) => warn!("Boss practice could not import the exported build: {}", error),
+        }
+    }
+    practice.fight = Some(PracticeFight { boss_spawned: true, ..fight });
+}
+
+fn finish_practice_on_boss_kill(
+    mut game_events: EventReader<GameEvent>,
+    mut practice: ResMut<BossPractice>,
+    game_state: Res<GameState>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let Some(fight) = practice.fight else { return; };
+    if !game_events.read().filter_map(GameEvent::as_horror_kill).any(|kill| kill.is_elite && kill.horror_type == fight.boss) { return; }
+    practice.last_result = Some(PracticeResult { boss: fight.boss, defeated: true, secs: game_state.game_timer.elapsed_secs() });
+    next_app_state.set(AppState::MainMenu);
+}
+
+// Practice never grades the run or pays out remnants; a death sends the survivor straight back to the menu
+fn leave_practice_instead_of_game_over(mut practice: ResMut<BossPractice>, game_state: Res<GameState>, mut next_app_state: ResMut<NextState<AppState>>) {
+    let Some(fight) = practice.fight else { return; };
+    if next_app_state.0 != Some(AppState::GameOver) { return; }
+    practice.last_result = Some(PracticeResult { boss: fight.boss, defeated: false, secs: game_state.game_timer.elapsed_secs() });
+    next_app_state.set(AppState::MainMenu);
+}
+
+fn end_practice_fight(mut practice: ResMut<BossPractice>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>) {
+    if practice.fight.take().is_some() { horror_spawn_timer.timer.unpause(); }
+}
@@ -1,35 +1,122 @@
 use bevy::prelude::*;
-use crate::player::Player;
-use crate::game::AppState;
+use crate::survivor::Survivor;
+use crate::horror::Horror;
+use crate::game::{AppState, SCREEN_WIDTH, SCREEN_HEIGHT};
+use crate::kill_cam::kill_cam_inactive;
 
-const CAMERA_LERP_FACTOR: f32 = 0.05; // Adjust for more or less "softness" (lower is softer)
+pub const ADAPTIVE_ZOOM_TOGGLE_KEY: KeyCode = KeyCode::KeyZ;
+const ELITE_THREAT_WEIGHT: f32 = 15.0; // An elite counts as this many ordinary horrors when judging threat
 
 #[derive(Component)]
 pub struct MainCamera; // Marker component for the main game camera
 
+// Player-facing camera options; zoom values are orthographic scales, so larger shows more of the arena
+#[derive(Resource, Debug, Clone)]
+pub struct CameraSettings {
+    pub adaptive_zoom: bool,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub threat_radius: f32,
+    pub horrors_for_max_zoom: f32,
+    pub zoom_speed: f32,
+    pub deadzone_half_extents: Vec2, // The survivor can move this far from the camera's focus before it follows
+    pub follow_smoothing: f32, // Higher catches up faster; 0 snaps straight to the target
+    pub look_ahead_distance: f32, // How far ahead of the survivor, along the aim direction, the camera centres
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self { adaptive_zoom: true, min_zoom: 1.0, max_zoom: 1.6, threat_radius: 450.0, horrors_for_max_zoom: 40.0, zoom_speed: 1.5, deadzone_half_extents: Vec2::new(60.0, 40.0), follow_smoothing: 3.0, look_ahead_distance: 80.0 }
+    }
+}
+
+impl CameraSettings {
+    pub fn target_zoom(&self, nearby_horrors: usize, nearby_elites: usize) -> f32 {
+        if !self.adaptive_zoom { return self.min_zoom; }
+        let threat = (nearby_horrors as f32 + nearby_elites as f32 * ELITE_THREAT_WEIGHT) / self.horrors_for_max_zoom.max(1.0);
+        self.min_zoom + (self.max_zoom - self.min_zoom) * threat.clamp(0.0, 1.0)
+    }
+
+    // Where the camera wants to be: it only moves once the look-ahead point leaves the deadzone, and then just far enough to bring it back to the edge
+    pub fn follow_target(&self, camera_pos: Vec2, survivor_pos: Vec2, aim_direction: Vec2) -> Vec2 {
+        let focus = survivor_pos + aim_direction.normalize_or_zero() * self.look_ahead_distance;
+        let offset = focus - camera_pos;
+        let excess = offset - offset.clamp(-self.deadzone_half_extents, self.deadzone_half_extents);
+        camera_pos + excess
+    }
+}
+
+// Fraction of the remaining distance to cover this frame, independent of frame rate
+pub fn smoothing_fraction(rate: f32, delta_secs: f32) -> f32 {
+    if rate <= 0.0 { 1.0 } else { 1.0 - (-rate * delta_secs).exp() }
+}
+
+// Half the size of the world-space rectangle the camera shows at its current zoom. The projection's area follows
+// the canvas shape once the camera has run; until then the design resolution stands in
+pub fn view_half_extents(projection: &OrthographicProjection) -> Vec2 {
+    let half_size = projection.area.half_size();
+    if half_size.cmpgt(Vec2::ONE).all() { half_size } else { Vec2::new(SCREEN_WIDTH, SCREEN_HEIGHT) / 2.0 * projection.scale }
+}
+
+// Eases the current zoom towards the target
+pub fn approach_zoom(current: f32, target: f32, zoom_speed: f32, delta_secs: f32) -> f32 {
+    current + (target - current) * smoothing_fraction(zoom_speed, delta_secs)
+}
+
 pub struct CameraSystemsPlugin;
 
 impl Plugin for CameraSystemsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, 
-            soft_camera_follow_system.run_if(in_state(AppState::InGame))
-        );
+        app.init_resource::<CameraSettings>()
+            // The kill cam takes the camera over while it runs, and both ease back from wherever it left off
+            .add_systems(Update, (
+                soft_camera_follow_system,
+                adaptive_camera_zoom_system,
+            ).run_if(in_state(AppState::InGame)).run_if(kill_cam_inactive))
+            .add_systems(Update, toggle_adaptive_zoom_system.run_if(in_state(AppState::InGame)));
     }
 }
 
 fn soft_camera_follow_system(
-    player_query: Query<&Transform, (With<Player>, Without<MainCamera>)>,
-    mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<Player>)>,
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    survivor_query: Query<(&Transform, &Survivor), Without<MainCamera>>,
+    mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<Survivor>)>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-            let target_position = player_transform.translation;
-            
-            // Interpolate camera position towards player position
-            // Only interpolate X and Y, keep Z fixed unless desired.
-            camera_transform.translation = camera_transform.translation.lerp(target_position, CAMERA_LERP_FACTOR);
-            // Ensure camera Z remains constant if it was set specifically
-            // camera_transform.translation.z = desired_camera_z_value; // e.g. 10.0 or what was set at spawn
+    let Ok((survivor_transform, survivor)) = survivor_query.get_single() else { return; };
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else { return; };
+    let camera_pos = camera_transform.translation.truncate();
+    let target = settings.follow_target(camera_pos, survivor_transform.translation.truncate(), survivor.aim_direction);
+    // Only X and Y move; the camera keeps the depth it was spawned with
+    let new_pos = camera_pos.lerp(target, smoothing_fraction(settings.follow_smoothing, time.delta_seconds()));
+    camera_transform.translation = new_pos.extend(camera_transform.translation.z);
+}
+
+fn adaptive_camera_zoom_system(
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    survivor_query: Query<&Transform, With<Survivor>>,
+    horror_query: Query<(&Transform, &Horror)>,
+    mut projection_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    let Ok(mut projection) = projection_query.get_single_mut() else { return; };
+    let (nearby_horrors, nearby_elites) = match survivor_query.get_single() {
+        Ok(survivor_transform) => {
+            let survivor_pos = survivor_transform.translation.truncate();
+            let radius_sq = settings.threat_radius * settings.threat_radius;
+            horror_query.iter()
+                .filter(|(transform, _)| transform.translation.truncate().distance_squared(survivor_pos) <= radius_sq)
+                .fold((0, 0), |(horrors, elites), (_, horror)| if horror.is_elite { (horrors, elites + 1) } else { (horrors + 1, elites) })
         }
+        Err(_) => (0, 0),
+    };
+    let target = settings.target_zoom(nearby_horrors, nearby_elites);
+    projection.scale = approach_zoom(projection.scale, target, settings.zoom_speed, time.delta_seconds());
+}
+
+fn toggle_adaptive_zoom_system(keyboard_input: Res<ButtonInput<KeyCode>>, mut settings: ResMut<CameraSettings>) {
+    if keyboard_input.just_pressed(ADAPTIVE_ZOOM_TOGGLE_KEY) {
+        settings.adaptive_zoom = !settings.adaptive_zoom;
+        info!("Adaptive camera zoom {}", if settings.adaptive_zoom { "enabled" } else { "disabled" });
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,321 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use crate::{
+    game::{AppState, GameRng, GameState},
+    game_events::GameEvent,
+    horror::HorrorType,
+    platform::unix_time_secs,
+    score::{ScoreAwardEvent, ScoreSource},
+    ranking::RunStats,
+    skills::{SkillId, SkillLibrary},
+    survivor::Survivor,
+    upgrades::{UpgradeCard, UpgradeType},
+};
+
+pub const WEEKLY_CHALLENGE_KEY: KeyCode = KeyCode::KeyW;
+pub const CHALLENGE_OBJECTIVE_BONUS: u32 = 5000;
+const BANNED_SKILL_COUNT: usize = 2;
+const BANNED_HORROR_COUNT: usize = 2;
+// Offensive skills only, so the fixed loadout can always fight back
+const CHALLENGE_STARTING_SKILLS: [SkillId; 6] = [SkillId(1), SkillId(2), SkillId(3), SkillId(5), SkillId(7), SkillId(12)];
+const CHALLENGE_SKILLS: [SkillId; 12] = [SkillId(1), SkillId(2), SkillId(3), SkillId(4), SkillId(5), SkillId(6), SkillId(7), SkillId(8), SkillId(9), SkillId(10), SkillId(11), SkillId(12)];
+// Shadowlings carry the opening waves and torments only come from weavers, so neither can be struck from the roster
+const BANNABLE_HORRORS: [HorrorType; 5] = [HorrorType::FloatingEyeball, HorrorType::AmorphousFleshbeast, HorrorType::VoidBlinker, HorrorType::FleshWeaver, HorrorType::FrenziedBehemoth];
+
+pub struct ChallengePlugin;
+
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveChallenge>()
+            .init_resource::<WeeklyBestScore>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_weekly_challenge_menu)
+            .add_systems(Update, choose_run_mode.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), despawn_weekly_challenge_menu)
+            .add_systems(OnEnter(AppState::InGame), setup_challenge_hud)
+            .add_systems(Update, (
+                challenge_objective_system,
+                update_challenge_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_challenge_hud)
+            .add_systems(OnEnter(AppState::GameOver), (record_weekly_best, setup_weekly_result_ui).chain())
+            .add_systems(OnExit(AppState::GameOver), despawn_weekly_result_ui);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoWeek {
+    pub year: i32,
+    pub week: u32,
+}
+
+impl IsoWeek {
+    // A week runs Monday to Sunday and belongs to the year its Thursday falls in
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // 1970-01-01 was a Thursday
+        let days_since_monday = (days + 3).rem_euclid(7);
+        let thursday = days - days_since_monday + 3;
+        let year = civil_year(thursday);
+        let week = ((thursday - days_from_civil(year, 1, 1)) / 7 + 1) as u32;
+        Self { year, week }
+    }
+
+    pub fn current() -> Self {
+        Self::from_days_since_epoch((unix_time_secs() / 86_400) as i64)
+    }
+}
+
+// Days from 1970-01-01 to the given proleptic Gregorian date
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_year(days: i64) -> i32 {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400 + if month_from_march >= 10 { 1 } else { 0 };
+    year as i32
+}
+
+// Everyone playing the same week gets the same seed, and so the same rules and the same run
+pub fn weekly_seed(week: IsoWeek) -> u64 {
+    (week.year as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (week.week as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeObjective {
+    SlayHorrors(u32),
+    SlayElites(u32),
+    EndureCycles(u32),
+}
+
+impl ChallengeObjective {
+    pub fn target(&self) -> u32 {
+        match *self { ChallengeObjective::SlayHorrors(count) | ChallengeObjective::SlayElites(count) | ChallengeObjective::EndureCycles(count) => count }
+    }
+
+    pub fn describe(&self) -> String {
+        match *self {
+            ChallengeObjective::SlayHorrors(count) => format!("slay {} horrors", count),
+            ChallengeObjective::SlayElites(count) => format!("slay {} elites", count),
+            ChallengeObjective::EndureCycles(count) => format!("endure {} cycles", count),
+        }
+    }
+}
+
+// The special rules of a challenge run; anything keyed by a seed can use these, the weekly mode keys them by ISO week
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeRules {
+    pub seed: u64,
+    pub starting_skill: SkillId,
+    pub banned_skills: Vec<SkillId>,
+    pub banned_horrors: Vec<HorrorType>,
+    pub objective: ChallengeObjective,
+}
+
+impl ChallengeRules {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let starting_skill = *CHALLENGE_STARTING_SKILLS.choose(&mut rng).expect("starting skills are listed");
+        let bannable: Vec<SkillId> = CHALLENGE_SKILLS.iter().copied().filter(|skill| *skill != starting_skill).collect();
+        let banned_skills = bannable.choose_multiple(&mut rng, BANNED_SKILL_COUNT).copied().collect();
+        let banned_horrors = BANNABLE_HORRORS.choose_multiple(&mut rng, BANNED_HORROR_COUNT).copied().collect();
+        let objective = match rng.gen_range(0..3) {
+            0 => ChallengeObjective::SlayHorrors(1500),
+            1 => ChallengeObjective::SlayElites(12),
+            _ => ChallengeObjective::EndureCycles(8),
+        };
+        Self { seed, starting_skill, banned_skills, banned_horrors, objective }
+    }
+
+    pub fn allows_upgrade(&self, card: &UpgradeCard) -> bool {
+        !matches!(card.upgrade_type, UpgradeType::GrantSkill(skill) if self.banned_skills.contains(&skill))
+    }
+
+    // Banned horrors are swapped for one of the types still on the roster; the rng is only touched when a swap happens
+    pub fn substitute_horror<R: Rng>(&self, chosen: HorrorType, rng: &mut R) -> HorrorType {
+        if !self.banned_horrors.contains(&chosen) { return chosen; }
+        let allowed: Vec<HorrorType> = BANNABLE_HORRORS.iter().copied().filter(|horror| !self.banned_horrors.contains(horror)).collect();
+        allowed.choose(rng).copied().unwrap_or(HorrorType::SkitteringShadowling)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub week: IsoWeek,
+    pub rules: ChallengeRules,
+    pub progress: u32,
+    pub completed: bool,
+}
+
+impl Challenge {
+    pub fn weekly(week: IsoWeek) -> Self {
+        Self { week, rules: ChallengeRules::from_seed(weekly_seed(week)), progress: 0, completed: false }
+    }
+
+    // True only on the call that completes the objective
+    pub fn record_progress(&mut self, amount: u32) -> bool {
+        if self.completed { return false; }
+        self.progress = (self.progress + amount).min(self.rules.objective.target());
+        self.completed = self.progress >= self.rules.objective.target();
+        self.completed
+    }
+}
+
+// The challenge this run is playing, if any; None is a standard run
+#[derive(Resource, Debug, Default)]
+pub struct ActiveChallenge(pub Option<Challenge>);
+
+impl ActiveChallenge {
+    pub fn rules(&self) -> Option<&ChallengeRules> { self.0.as_ref().map(|challenge| &challenge.rules) }
+    pub fn allows_upgrade(&self, card: &UpgradeCard) -> bool { self.rules().is_none_or(|rules| rules.allows_upgrade(card)) }
+    pub fn substitute_horror<R: Rng>(&self, chosen: HorrorType, rng: &mut R) -> HorrorType { self.rules().map_or(chosen, |rules| rules.substitute_horror(chosen, rng)) }
+    pub fn starting_skill(&self) -> SkillId { self.rules().map_or(SkillId(1), |rules| rules.starting_skill) }
+}
+
+// Best score for one week only; a new week starts its slot over
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeeklyBest {
+    pub week: IsoWeek,
+    pub score: u32,
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct WeeklyBestScore(pub Option<WeeklyBest>);
+
+impl WeeklyBestScore {
+    pub fn for_week(&self, week: IsoWeek) -> Option<u32> {
+        self.0.filter(|best| best.week == week).map(|best| best.score)
+    }
+
+    // Returns whether the score became the week's best
+    pub fn record(&mut self, week: IsoWeek, score: u32) -> bool {
+        if self.for_week(week).is_some_and(|best| best >= score) { return false; }
+        self.0 = Some(WeeklyBest { week, score });
+        true
+    }
+}
+
+#[derive(Component)]
+struct WeeklyChallengeMenuUI;
+
+#[derive(Component)]
+struct ChallengeHud;
+
+#[derive(Component)]
+struct WeeklyResultUI;
+
+// The new run is started by the main menu itself; this only decides which rules it plays by
+fn choose_run_mode(keyboard_input: Res<ButtonInput<KeyCode>>, mut active_challenge: ResMut<ActiveChallenge>, mut game_rng: ResMut<GameRng>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        active_challenge.0 = None;
+    } else if keyboard_input.just_pressed(WEEKLY_CHALLENGE_KEY) {
+        let challenge = Challenge::weekly(IsoWeek::current());
+        *game_rng = GameRng::seeded(challenge.rules.seed);
+        info!("Starting the weekly challenge for {}-W{:02}: {:?}", challenge.week.year, challenge.week.week, challenge.rules);
+        active_challenge.0 = Some(challenge);
+    }
+}
+
+fn skill_names(skills: &[SkillId], skill_library: &SkillLibrary) -> String {
+    skills.iter().map(|skill| skill_library.get_skill_definition(*skill).map_or_else(|| format!("Skill {}", skill.0), |definition| definition.name.clone())).collect::<Vec<_>>().join(", ")
+}
+
+fn setup_weekly_challenge_menu(mut commands: Commands, asset_server: Res<AssetServer>, skill_library: Res<SkillLibrary>, weekly_best: Res<WeeklyBestScore>) {
+    let week = IsoWeek::current();
+    let rules = ChallengeRules::from_seed(weekly_seed(week));
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let banned_horrors = rules.banned_horrors.iter().map(|horror| format!("{:?}", horror)).collect::<Vec<_>>().join(", ");
+    let best = weekly_best.for_week(week).map_or_else(|| "none yet".to_string(), |score| score.to_string());
+    commands.spawn((
+        NodeBundle { style: Style { position_type: PositionType::Absolute, bottom: Val::Px(40.0), width: Val::Percent(100.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Center, row_gap: Val::Px(6.0), ..default() }, ..default() },
+        WeeklyChallengeMenuUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(format!("Weekly Challenge {}-W{:02} (W)", week.year, week.week), TextStyle { font: font.clone(), font_size: 30.0, color: Color::rgb(1.0, 0.75, 0.4) }));
+        for line in [
+            format!("Start with {} - banned: {} - absent: {}", skill_names(&[rules.starting_skill], &skill_library), skill_names(&rules.banned_skills, &skill_library), banned_horrors),
+            format!("Objective: {}   Best this week: {}", rules.objective.describe(), best),
+        ] {
+            parent.spawn(TextBundle::from_section(line, TextStyle { font: font.clone(), font_size: 20.0, color: Color::rgb(0.85, 0.85, 0.85) }));
+        }
+    });
+}
+
+fn despawn_weekly_challenge_menu(mut commands: Commands, ui_query: Query<Entity, With<WeeklyChallengeMenuUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn setup_challenge_hud(mut commands: Commands, asset_server: Res<AssetServer>, active_challenge: Res<ActiveChallenge>) {
+    if active_challenge.0.is_none() { return; }
+    commands.spawn((
+        TextBundle::from_section("", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::rgb(1.0, 0.75, 0.4) })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(85.0), right: Val::Px(15.0), ..default() }),
+        ChallengeHud,
+        Name::new("ChallengeHud"),
+    ));
+}
+
+fn challenge_objective_system(
+    mut game_events: EventReader<GameEvent>,
+    game_state: Res<GameState>,
+    mut active_challenge: ResMut<ActiveChallenge>,
+    player_query: Query<&Transform, With<Survivor>>,
+    mut award_writer: EventWriter<ScoreAwardEvent>,
+) {
+    let kills: Vec<bool> = game_events.read().filter_map(GameEvent::as_horror_kill).map(|kill| kill.is_elite).collect();
+    let Some(challenge) = active_challenge.0.as_mut() else { return; };
+    let just_completed = match challenge.rules.objective {
+        ChallengeObjective::SlayHorrors(_) => challenge.record_progress(kills.len() as u32),
+        ChallengeObjective::SlayElites(_) => challenge.record_progress(kills.iter().filter(|is_elite| **is_elite).count() as u32),
+        // Cycles count from one, so the cycle in progress hasn't been endured yet
+        ChallengeObjective::EndureCycles(_) => { let endured = game_state.cycle_number.saturating_sub(1); let gained = endured.saturating_sub(challenge.progress); challenge.record_progress(gained) }
+    };
+    if !just_completed { return; }
+    let position = player_query.get_single().map_or(Vec3::ZERO, |transform| transform.translation);
+    award_writer.send(ScoreAwardEvent { source: ScoreSource::ChallengeObjective, points: CHALLENGE_OBJECTIVE_BONUS, position });
+}
+
+fn update_challenge_hud(active_challenge: Res<ActiveChallenge>, mut text_query: Query<&mut Text, With<ChallengeHud>>) {
+    let (Ok(mut text), Some(challenge)) = (text_query.get_single_mut(), active_challenge.0.as_ref()) else { return; };
+    let objective = challenge.rules.objective;
+    text.sections[0].value = if challenge.completed {
+        format!("Weekly: {} - done!", objective.describe())
+    } else {
+        format!("Weekly: {} ({}/{})", objective.describe(), challenge.progress, objective.target())
+    };
+}
+
+fn despawn_challenge_hud(mut commands: Commands, hud_query: Query<Entity, With<ChallengeHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn record_weekly_best(active_challenge: Res<ActiveChallenge>, game_state: Res<GameState>, run_stats: Res<RunStats>, mut weekly_best: ResMut<WeeklyBestScore>) {
+    let Some(challenge) = active_challenge.0.as_ref() else { return; };
+    if !run_stats.counts_for_records() { return; }
+    weekly_best.record(challenge.week, game_state.score);
+}
+
+fn setup_weekly_result_ui(mut commands: Commands, asset_server: Res<AssetServer>, active_challenge: Res<ActiveChallenge>, game_state: Res<GameState>, run_stats: Res<RunStats>, weekly_best: Res<WeeklyBestScore>) {
+    let Some(challenge) = active_challenge.0.as_ref() else { return; };
+    let best = weekly_best.for_week(challenge.week).unwrap_or(0);
+    let headline = if !run_stats.counts_for_records() { format!("Weekly best: {} (debug run, not counted)", best) } else if best == game_state.score { format!("New weekly best: {}", best) } else { format!("Weekly best: {}", best) };
+    commands.spawn((
+        TextBundle::from_section(headline, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 26.0, color: Color::rgb(1.0, 0.75, 0.4) })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(40.0), right: Val::Px(40.0), ..default() }),
+        WeeklyResultUI,
+    ));
+}
+
+fn despawn_weekly_result_ui(mut commands: Commands, ui_query: Query<Entity, With<WeeklyResultUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
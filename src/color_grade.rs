@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::{
+    game::{AppState, GameState},
+    audio::MusicIntensity,
+    horror::ElitePhase,
+};
+
+const EMBEDDED_COLOR_GRADES: &str = include_str!("../assets/data/color_grades.ron");
+
+pub struct ColorGradePlugin;
+
+impl Plugin for ColorGradePlugin {
+    fn build(&self, app: &mut App) {
+        let table = ColorGradeTable::from_ron_str(EMBEDDED_COLOR_GRADES).expect("embedded color grades are invalid");
+        app.insert_resource(ColorGrade::new(table))
+            .add_systems(OnExit(AppState::MainMenu), reset_color_grade)
+            .add_systems(OnEnter(AppState::InGame), setup_color_grade_overlay)
+            .add_systems(Update, update_color_grade.run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_color_grade_overlay);
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradeEvent {
+    BloodMoon,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeOfDayGrade {
+    pub name: String,
+    pub from_secs: f32,
+    pub tint: (f32, f32, f32, f32),
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EventGrade {
+    pub event: GradeEvent,
+    pub tint: (f32, f32, f32, f32),
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ColorGradeTable {
+    pub blend_secs: f32,
+    pub time_of_day: Vec<TimeOfDayGrade>,
+    #[serde(default)]
+    pub events: Vec<EventGrade>,
+}
+
+impl ColorGradeTable {
+    pub fn from_ron_str(source: &str) -> Result<Self, ColorGradeError> {
+        let table: ColorGradeTable = ron::de::from_str(source)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    pub fn validate(&self) -> Result<(), ColorGradeError> {
+        if self.blend_secs <= 0.0 { return Err(ColorGradeError::NonPositiveBlend); }
+        if self.time_of_day.first().is_none_or(|first| first.from_secs != 0.0) { return Err(ColorGradeError::MissingStartOfRun); }
+        if let Some(pair) = self.time_of_day.windows(2).find(|pair| pair[1].from_secs <= pair[0].from_secs) { return Err(ColorGradeError::OutOfOrder(pair[1].name.clone())); }
+        for (index, grade) in self.events.iter().enumerate() {
+            if self.events[..index].iter().any(|earlier| earlier.event == grade.event) { return Err(ColorGradeError::DuplicateEvent(grade.event)); }
+        }
+        Ok(())
+    }
+
+    pub fn time_of_day_at(&self, run_secs: f32) -> &TimeOfDayGrade {
+        self.time_of_day.iter().rev().find(|grade| grade.from_secs <= run_secs).unwrap_or(&self.time_of_day[0])
+    }
+
+    // An event with no grade of its own leaves the time of day showing
+    pub fn target_tint(&self, run_secs: f32, event: Option<GradeEvent>) -> Vec4 {
+        let event_tint = event.and_then(|event| self.events.iter().find(|grade| grade.event == event)).map(|grade| grade.tint);
+        let (r, g, b, a) = event_tint.unwrap_or(self.time_of_day_at(run_secs).tint);
+        Vec4::new(r, g, b, a)
+    }
+}
+
+#[derive(Debug)]
+pub enum ColorGradeError {
+    Parse(ron::error::SpannedError),
+    NonPositiveBlend,
+    MissingStartOfRun,
+    OutOfOrder(String),
+    DuplicateEvent(GradeEvent),
+}
+
+impl std::fmt::Display for ColorGradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorGradeError::Parse(error) => write!(f, "could not parse color grades: {}", error),
+            ColorGradeError::NonPositiveBlend => write!(f, "blend_secs must be above zero"),
+            ColorGradeError::MissingStartOfRun => write!(f, "the first time of day must start at 0 seconds"),
+            ColorGradeError::OutOfOrder(name) => write!(f, "{} does not start after the time of day before it", name),
+            ColorGradeError::DuplicateEvent(event) => write!(f, "{:?} is listed more than once", event),
+        }
+    }
+}
+
+impl std::error::Error for ColorGradeError {}
+
+impl From<ron::error::SpannedError> for ColorGradeError {
+    fn from(error: ron::error::SpannedError) -> Self { ColorGradeError::Parse(error) }
+}
+
+// Eases toward the target so about 95% of a change has landed after blend_secs, whatever the frame rate
+pub fn blend_tint(current: Vec4, target: Vec4, delta_secs: f32, blend_secs: f32) -> Vec4 {
+    current.lerp(target, 1.0 - (-3.0 * delta_secs / blend_secs).exp())
+}
+
+#[derive(Resource, Debug)]
+pub struct ColorGrade {
+    pub table: ColorGradeTable,
+    pub tint: Vec4,
+}
+
+impl ColorGrade {
+    pub fn new(table: ColorGradeTable) -> Self {
+        let tint = table.target_tint(0.0, None);
+        Self { table, tint }
+    }
+}
+
+#[derive(Component)]
+struct ColorGradeOverlay;
+
+fn reset_color_grade(mut grade: ResMut<ColorGrade>) {
+    grade.tint = grade.table.target_tint(0.0, None);
+}
+
+fn setup_color_grade_overlay(mut commands: Commands, grade: Res<ColorGrade>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, ..default() },
+            background_color: Color::rgba(grade.tint.x, grade.tint.y, grade.tint.z, grade.tint.w).into(),
+            // Beneath every other UI node, so only the world is graded
+            z_index: ZIndex::Global(-1),
+            focus_policy: bevy::ui::FocusPolicy::Pass,
+            ..default()
+        },
+        ColorGradeOverlay,
+    ));
+}
+
+fn update_color_grade(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    music_intensity: Res<MusicIntensity>,
+    mut grade: ResMut<ColorGrade>,
+    mut overlay_query: Query<&mut BackgroundColor, With<ColorGradeOverlay>>,
+) {
+    let event = (music_intensity.phase == ElitePhase::Final).then_some(GradeEvent::BloodMoon);
+    let target = grade.table.target_tint(game_state.game_timer.elapsed_secs(), event);
+    grade.tint = blend_tint(grade.tint, target, time.delta_seconds(), grade.table.blend_secs);
+    let color = Color::rgba(grade.tint.x, grade.tint.y, grade.tint.z, grade.tint.w);
+    for mut background in overlay_query.iter_mut() {
+        if background.0 != color { background.0 = color; }
+    }
+}
+
+fn despawn_color_grade_overlay(mut commands: Commands, overlay_query: Query<Entity, With<ColorGradeOverlay>>) {
+    for entity in overlay_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
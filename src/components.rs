@@ -20,4 +20,52 @@ pub struct Target(pub Option<Entity>); // Currently unused
 #[derive(Component)]
 pub struct Lifetime {
     pub timer: Timer,
-}
\ No newline at end of file
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileOwner { Survivor, Horror, }
+
+#[derive(Component)]
+pub struct Reflectable;
+
+#[derive(Component)]
+pub struct TauntTarget { pub radius: f32, }
+
+// Horrors are never spawned inside this radius, so traps and hazards aren't sprung the moment a wave arrives
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SpawnExclusion { pub radius: f32, }
+
+// Local time multiplier for a single entity; entities without one run at normal speed
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TimeScale(pub f32);
+impl TimeScale { pub fn of(time_scale: Option<&TimeScale>) -> f32 { time_scale.map_or(1.0, |scale| scale.0) } }
+
+// Elemental flavour of a hit; each type can carry its own on-hit effect, see horror::apply_damage_type_on_hit
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum DamageType { #[default] Eldritch, Frost, Chaos, }
+
+// Health a horror spawned with; execute thresholds are measured against it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MaxHealth(pub i32);
+
+// Damage beyond what a kill needed, stored on a projectile and added to its next hit
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct OverkillCarry { pub fraction: f32, pub stored: i32, }
+impl OverkillCarry {
+    pub fn new(fraction: f32) -> Self { Self { fraction, stored: 0 } }
+    pub fn take(&mut self) -> i32 { std::mem::take(&mut self.stored) }
+    pub fn store(&mut self, overkill: i32) { self.stored = (overkill as f32 * self.fraction).round() as i32; }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HitResult { pub dealt: i32, pub overkill: i32, pub executed: bool, }
+
+// Applies a hit, then executes the target outright if what is left is at or below execute_fraction of its max health
+pub fn resolve_hit(health: &mut i32, max_health: Option<i32>, damage: i32, execute_fraction: f32) -> HitResult {
+    *health -= damage;
+    let overkill = (-*health).max(0);
+    let executed = *health > 0 && max_health.is_some_and(|max| *health as f32 <= max as f32 * execute_fraction);
+    let mut dealt = damage;
+    if executed { dealt += *health; *health = 0; }
+    HitResult { dealt, overkill, executed }
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use rand::{Rng, seq::SliceRandom};
+use crate::{
+    survivor::{Survivor, SURVIVOR_SIZE},
+    components::Health,
+    game::{AppState, GameRng},
+    game_events::{GameEvent, DamageOrigin},
+    items::ExplosionEffect,
+    audio::{PlaySoundEvent, SoundEffect},
+    corruption::RunDifficulty,
+};
+
+pub const MAX_CONSUMABLE_SLOTS: usize = 3;
+pub const CONSUMABLE_DROP_SIZE: Vec2 = Vec2::new(20.0, 20.0);
+const ELITE_CONSUMABLE_DROP_CHANCE: f64 = 0.5;
+const CONSUMABLE_PICKUP_RADIUS: f32 = SURVIVOR_SIZE.x / 2.0 + CONSUMABLE_DROP_SIZE.x / 2.0;
+const HEALING_DRAUGHT_AMOUNT: i32 = 40;
+const BOMB_DAMAGE: i32 = 60;
+const BOMB_RADIUS: f32 = 180.0;
+
+pub struct ConsumablesPlugin;
+
+impl Plugin for ConsumablesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::InGame), setup_consumable_hud)
+            .add_systems(Update, (
+                consumable_drop_on_elite_death_system,
+                consumable_pickup_system,
+                consumable_input_system,
+                update_consumable_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_consumable_hud)
+            .add_systems(OnEnter(AppState::GameOver), despawn_consumable_drops)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_consumable_drops);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsumableKind { HealingDraught, CooldownFlask, Bomb, }
+
+impl ConsumableKind {
+    pub const ALL: [ConsumableKind; 3] = [ConsumableKind::HealingDraught, ConsumableKind::CooldownFlask, ConsumableKind::Bomb];
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsumableKind::HealingDraught => "Healing Draught",
+            ConsumableKind::CooldownFlask => "Flask of Haste",
+            ConsumableKind::Bomb => "Pitch Bomb",
+        }
+    }
+    pub fn max_stack(&self) -> u32 {
+        match self {
+            ConsumableKind::HealingDraught => 3,
+            ConsumableKind::CooldownFlask => 2,
+            ConsumableKind::Bomb => 5,
+        }
+    }
+    fn color(&self) -> Color {
+        match self {
+            ConsumableKind::HealingDraught => Color::rgb(0.8, 0.1, 0.2),
+            ConsumableKind::CooldownFlask => Color::rgb(0.2, 0.6, 1.0),
+            ConsumableKind::Bomb => Color::rgb(0.3, 0.3, 0.3),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsumableStack { pub kind: ConsumableKind, pub count: u32, }
+
+// Carried by the Survivor; each kind takes one slot and stacks up to its own limit
+#[derive(Component, Debug, Default)]
+pub struct ConsumableInventory {
+    pub stacks: Vec<ConsumableStack>,
+    pub selected: usize,
+}
+
+impl ConsumableInventory {
+    pub fn try_add(&mut self, kind: ConsumableKind) -> bool {
+        if let Some(stack) = self.stacks.iter_mut().find(|stack| stack.kind == kind) {
+            if stack.count >= kind.max_stack() { return false; }
+            stack.count += 1;
+            return true;
+        }
+        if self.stacks.len() >= MAX_CONSUMABLE_SLOTS { return false; }
+        self.stacks.push(ConsumableStack { kind, count: 1 });
+        true
+    }
+    pub fn take_selected(&mut self) -> Option<ConsumableKind> {
+        let stack = self.stacks.get_mut(self.selected)?;
+        let kind = stack.kind;
+        stack.count -= 1;
+        if stack.count == 0 {
+            self.stacks.remove(self.selected);
+            if self.selected >= self.stacks.len() { self.selected = 0; }
+        }
+        Some(kind)
+    }
+    pub fn cycle_selection(&mut self) {
+        if !self.stacks.is_empty() { self.selected = (self.selected + 1) % self.stacks.len(); }
+    }
+}
+
+#[derive(Component)]
+pub struct ConsumableDrop { pub kind: ConsumableKind, }
+
+#[derive(Component)]
+struct ConsumableHudText;
+
+pub fn spawn_consumable_drop(commands: &mut Commands, asset_server: &Res<AssetServer>, position: Vec3, kind: ConsumableKind) {
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/eldritch_relic_placeholder.png"),
+            sprite: Sprite { custom_size: Some(CONSUMABLE_DROP_SIZE), color: kind.color(), ..default() },
+            transform: Transform::from_translation(position.truncate().extend(0.4)),
+            ..default()
+        },
+        ConsumableDrop { kind },
+        Name::new(format!("ConsumableDrop_{}", kind.name())),
+    ));
+}
+
+fn consumable_drop_on_elite_death_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_events: EventReader<GameEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = &mut game_rng.0;
+    for event in game_events.read().filter_map(GameEvent::as_horror_kill) {
+        if !event.is_elite || !rng.gen_bool(ELITE_CONSUMABLE_DROP_CHANCE) { continue; }
+        if let Some(kind) = ConsumableKind::ALL.choose(rng) {
+            spawn_consumable_drop(&mut commands, &asset_server, event.position, *kind);
+        }
+    }
+}
+
+// Drops stay on the ground while there is no room for them
+fn consumable_pickup_system(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut ConsumableInventory), With<Survivor>>,
+    drop_query: Query<(Entity, &Transform, &ConsumableDrop)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let Ok((player_transform, mut inventory)) = player_query.get_single_mut() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    for (drop_entity, drop_transform, consumable_drop) in drop_query.iter() {
+        if player_pos.distance(drop_transform.translation.truncate()) >= CONSUMABLE_PICKUP_RADIUS { continue; }
+        if inventory.try_add(consumable_drop.kind) {
+            sound_event_writer.send(PlaySoundEvent::global(SoundEffect::SoulCollect));
+            commands.entity(drop_entity).despawn_recursive();
+        }
+    }
+}
+
+fn consumable_input_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    run_difficulty: Res<RunDifficulty>,
+    mut player_query: Query<(&Transform, &mut Survivor, &mut Health, &mut ConsumableInventory)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let Ok((player_transform, mut survivor, mut health, mut inventory)) = player_query.get_single_mut() else { return; };
+    if keyboard_input.just_pressed(KeyCode::Tab) { inventory.cycle_selection(); }
+    if !keyboard_input.just_pressed(KeyCode::KeyQ) { return; }
+    let Some(kind) = inventory.take_selected() else { return; };
+
+    match kind {
+        ConsumableKind::HealingDraught => {
+            health.0 = (health.0 + run_difficulty.scale_healing(HEALING_DRAUGHT_AMOUNT as f32).round() as i32).min(survivor.max_health);
+        }
+        ConsumableKind::CooldownFlask => {
+            for skill_instance in survivor.equipped_skills.iter_mut() { skill_instance.current_cooldown = std::time::Duration::ZERO; }
+        }
+        ConsumableKind::Bomb => {
+            commands.spawn((
+                SpriteBundle {
+                    texture: asset_server.load("sprites/eldritch_nova_effect_placeholder.png"),
+                    sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: Color::rgba(1.0, 0.6, 0.2, 0.7), ..default() },
+                    transform: Transform::from_translation(player_transform.translation.truncate().extend(0.3)),
+                    ..default()
+                },
+                ExplosionEffect { damage: BOMB_DAMAGE, radius_sq: (BOMB_RADIUS * survivor.area_multiplier).powi(2), timer: Timer::from_seconds(0.3, TimerMode::Once), already_hit_entities: Vec::new(), origin: DamageOrigin::Consumable(ConsumableKind::Bomb) },
+                Name::new("PitchBombExplosion"),
+            ));
+        }
+    }
+    sound_event_writer.send(PlaySoundEvent::global(SoundEffect::RitualCast));
+}
+
+fn setup_consumable_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::WHITE },
+        ).with_style(Style { position_type: PositionType::Absolute, bottom: Val::Px(40.0), left: Val::Px(15.0), ..default() }),
+        ConsumableHudText,
+    ));
+}
+
+fn update_consumable_hud(
+    player_query: Query<&ConsumableInventory>,
+    mut hud_query: Query<&mut Text, With<ConsumableHudText>>,
+) {
+    let Ok(mut text) = hud_query.get_single_mut() else { return; };
+    let Ok(inventory) = player_query.get_single() else { text.sections[0].value.clear(); return; };
+    let slots: Vec<String> = (0..MAX_CONSUMABLE_SLOTS).map(|slot_index| match inventory.stacks.get(slot_index) {
+        Some(stack) if slot_index == inventory.selected => format!("[{} x{}]", stack.kind.name(), stack.count),
+        Some(stack) => format!("{} x{}", stack.kind.name(), stack.count),
+        None => "--".to_string(),
+    }).collect();
+    text.sections[0].value = format!("Q: {}   (Tab to cycle)", slots.join(" | "));
+}
+
+fn despawn_consumable_hud(mut commands: Commands, hud_query: Query<Entity, With<ConsumableHudText>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn despawn_consumable_drops(mut commands: Commands, drop_query: Query<Entity, With<ConsumableDrop>>) {
+    for entity in drop_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use rand::{Rng, seq::SliceRandom};
+use std::time::Duration;
+use crate::{
+    survivor::Survivor,
+    components::Health,
+    game_events::GameEvent,
+    items::ItemLibrary,
+    game::{AppState, GameRng, ItemCollectedEvent},
+};
+
+const FIRST_CONTRACT_DELAY_SECS: f32 = 45.0;
+const CONTRACT_COOLDOWN_SECS: f32 = 75.0;
+const SLAY_CONTRACT_KILLS: u32 = 50;
+const SLAY_CONTRACT_SECS: f32 = 30.0;
+const UNTOUCHED_CONTRACT_SECS: f32 = 60.0;
+
+pub struct ContractsPlugin;
+
+impl Plugin for ContractsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContractBoard>()
+            .add_systems(OnExit(AppState::MainMenu), reset_contract_board)
+            .add_systems(OnEnter(AppState::InGame), setup_contract_hud)
+            .add_systems(Update, (
+                contract_progress_system,
+                update_contract_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_contract_hud);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractObjective {
+    SlayHorrors { required: u32 },
+    Untouched,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractOutcome { Completed, Failed, }
+
+// An optional objective with a deadline; slaying contracts must be finished before it, untouched contracts must survive until it
+#[derive(Debug, Clone)]
+pub struct Contract {
+    pub objective: ContractObjective,
+    pub timer: Timer,
+    pub kills: u32,
+    pub was_hit: bool,
+}
+
+impl Contract {
+    pub fn slay(required: u32, limit_secs: f32) -> Self { Self::new(ContractObjective::SlayHorrors { required }, limit_secs) }
+    pub fn untouched(duration_secs: f32) -> Self { Self::new(ContractObjective::Untouched, duration_secs) }
+    fn new(objective: ContractObjective, secs: f32) -> Self { Self { objective, timer: Timer::from_seconds(secs, TimerMode::Once), kills: 0, was_hit: false } }
+
+    pub fn record_kills(&mut self, count: u32) { self.kills += count; }
+    pub fn record_hit(&mut self) { self.was_hit = true; }
+
+    pub fn advance(&mut self, delta: Duration) -> Option<ContractOutcome> {
+        self.timer.tick(delta);
+        match self.objective {
+            ContractObjective::SlayHorrors { required } if self.kills >= required => Some(ContractOutcome::Completed),
+            ContractObjective::SlayHorrors { .. } if self.timer.finished() => Some(ContractOutcome::Failed),
+            ContractObjective::Untouched if self.was_hit => Some(ContractOutcome::Failed),
+            ContractObjective::Untouched if self.timer.finished() => Some(ContractOutcome::Completed),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let remaining = self.timer.remaining_secs().ceil() as u32;
+        match self.objective {
+            ContractObjective::SlayHorrors { required } => format!("Contract: slay {} horrors ({}/{}) - {}s", required, self.kills.min(required), required, remaining),
+            ContractObjective::Untouched => format!("Contract: take no damage - {}s", remaining),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ContractBoard {
+    pub active: Option<Contract>,
+    pub next_offer_timer: Timer,
+    pub completed: u32,
+}
+
+impl Default for ContractBoard {
+    fn default() -> Self {
+        Self { active: None, next_offer_timer: Timer::from_seconds(FIRST_CONTRACT_DELAY_SECS, TimerMode::Once), completed: 0 }
+    }
+}
+
+impl ContractBoard {
+    pub fn offer<R: Rng>(&mut self, rng: &mut R) {
+        self.active = Some(if rng.gen_bool(0.5) { Contract::slay(SLAY_CONTRACT_KILLS, SLAY_CONTRACT_SECS) } else { Contract::untouched(UNTOUCHED_CONTRACT_SECS) });
+    }
+    pub fn finish(&mut self, outcome: ContractOutcome) {
+        self.active = None;
+        if outcome == ContractOutcome::Completed { self.completed += 1; }
+        self.next_offer_timer = Timer::from_seconds(CONTRACT_COOLDOWN_SECS, TimerMode::Once);
+    }
+}
+
+#[derive(Component)]
+struct ContractHud;
+
+fn reset_contract_board(mut board: ResMut<ContractBoard>) {
+    *board = ContractBoard::default();
+}
+
+fn contract_progress_system(
+    time: Res<Time>,
+    mut board: ResMut<ContractBoard>,
+    mut game_events: EventReader<GameEvent>,
+    player_query: Query<&Health, With<Survivor>>,
+    mut last_player_health: Local<Option<i32>>,
+    item_library: Res<ItemLibrary>,
+    mut item_collected_writer: EventWriter<ItemCollectedEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let kills = game_events.read().filter_map(GameEvent::as_horror_kill).count() as u32;
+    let current_health = player_query.get_single().ok().map(|health| health.0);
+    let was_hit = matches!((*last_player_health, current_health), (Some(previous), Some(current)) if current < previous);
+    *last_player_health = current_health;
+
+    let Some(contract) = board.active.as_mut() else {
+        board.next_offer_timer.tick(time.delta());
+        if board.next_offer_timer.finished() { board.offer(&mut game_rng.0); }
+        return;
+    };
+    contract.record_kills(kills);
+    if was_hit { contract.record_hit(); }
+    let Some(outcome) = contract.advance(time.delta()) else { return; };
+    // There are no chests yet, so a fulfilled contract pays out a random relic
+    if outcome == ContractOutcome::Completed {
+        if let Some(relic) = item_library.items.choose(&mut game_rng.0) { item_collected_writer.send(ItemCollectedEvent(relic.id)); }
+    }
+    board.finish(outcome);
+}
+
+fn setup_contract_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section("", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::rgb(1.0, 0.85, 0.4) })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(60.0), right: Val::Px(15.0), ..default() }),
+        ContractHud,
+        Name::new("ContractHud"),
+    ));
+}
+
+fn update_contract_hud(board: Res<ContractBoard>, mut text_query: Query<&mut Text, With<ContractHud>>) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    text.sections[0].value = board.active.as_ref().map(Contract::description).unwrap_or_default();
+}
+
+fn despawn_contract_hud(mut commands: Commands, hud_query: Query<Entity, With<ContractHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
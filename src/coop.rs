@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use crate::{game::AppState, input_actions::InputAction};
+
+pub const MAX_LOCAL_PLAYERS: usize = 2;
+// Player one keeps the menu choices; player two has their own, bound to the numpad so neither reaches across the other
+pub const CHOICE_ACTIONS: [[InputAction; 3]; MAX_LOCAL_PLAYERS] = [
+    [InputAction::Choice1, InputAction::Choice2, InputAction::Choice3],
+    [InputAction::PartnerChoice1, InputAction::PartnerChoice2, InputAction::PartnerChoice3],
+];
+
+// Which local player a survivor, or a level-up panel and its buttons, belongs to
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct PlayerSlot(pub usize);
+
+impl PlayerSlot {
+    pub fn label(&self) -> String { format!("Player {}", self.0 + 1) }
+}
+
+// The party shares one pool: a soul picked up by anyone is banked once, at the best gain multiplier in the party,
+// and every survivor receives it, so levels stay in step
+pub fn shared_experience(amount: u32, multipliers: impl IntoIterator<Item = f32>) -> u32 {
+    let best = multipliers.into_iter().fold(None, |best: Option<f32>, multiplier| Some(best.map_or(multiplier, |best| best.max(multiplier))));
+    (amount as f32 * best.unwrap_or(1.0)).round() as u32
+}
+
+// One level-up screen: every player owes one pick from their own offer. The run stays paused until the last of
+// them has chosen; whoever finishes first waits on the others
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct LevelUpChoices {
+    choosing: Vec<PlayerSlot>,
+    follow_up: Option<AppState>,
+}
+
+impl LevelUpChoices {
+    pub fn open(slots: impl IntoIterator<Item = PlayerSlot>) -> Self {
+        let mut choosing: Vec<PlayerSlot> = slots.into_iter().collect();
+        choosing.sort();
+        choosing.dedup();
+        Self { choosing, follow_up: None }
+    }
+
+    pub fn is_choosing(&self, slot: PlayerSlot) -> bool { self.choosing.contains(&slot) }
+
+    pub fn waiting_on(&self) -> &[PlayerSlot] { &self.choosing }
+
+    // Records the pick and returns the state to move to once nobody is left choosing. A card that needs a
+    // follow-up screen holds on to it until then; the first such card wins
+    pub fn complete(&mut self, slot: PlayerSlot, after: AppState) -> Option<AppState> {
+        let index = self.choosing.iter().position(|choosing| *choosing == slot)?;
+        self.choosing.remove(index);
+        if after != AppState::InGame && self.follow_up.is_none() { self.follow_up = Some(after); }
+        self.choosing.is_empty().then(|| self.follow_up.clone().unwrap_or(AppState::InGame))
+    }
+}
+
+pub fn waiting_label(choices: &LevelUpChoices) -> String {
+    let names: Vec<String> = choices.waiting_on().iter().map(PlayerSlot::label).collect();
+    format!("Waiting for {}", names.join(" and "))
+}
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use crate::{
+    game::AppState,
+    challenge::ActiveChallenge,
+    extraction::RunOutcome,
+    horror::ELITE_SPAWN_CHANCE,
+    ranking::RunStats,
+};
+
+pub const MAX_CORRUPTION_LEVEL: u32 = 10;
+pub const CORRUPTION_ELITE_CHANCE_PER_LEVEL: f64 = 0.02;
+pub const CORRUPTION_HEALING_FACTOR_PER_LEVEL: f32 = 0.85;
+const MAX_ELITE_CHANCE: f64 = 0.5;
+const CORRUPTION_LOWER_KEY: KeyCode = KeyCode::BracketLeft;
+const CORRUPTION_RAISE_KEY: KeyCode = KeyCode::BracketRight;
+const CORRUPTION_COLOR: Color = Color::rgb(0.75, 0.3, 0.9);
+
+pub struct CorruptionPlugin;
+
+impl Plugin for CorruptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Corruption>()
+            .init_resource::<RunDifficulty>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_corruption_menu_text)
+            .add_systems(Update, (choose_corruption_level, update_corruption_menu_text).chain().run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), (despawn_corruption_menu_text, apply_run_difficulty))
+            .add_systems(OnEnter(AppState::InGame), setup_corruption_hud)
+            .add_systems(OnExit(AppState::InGame), despawn_corruption_hud)
+            .add_systems(OnEnter(AppState::GameOver), unlock_corruption_on_victory)
+            .add_systems(OnExit(AppState::GameOver), despawn_corruption_unlock_text);
+    }
+}
+
+// Kept in the profile: the level the next run starts at, and the deepest one a victory has opened up
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Corruption {
+    pub level: u32,
+    pub unlocked: u32,
+}
+
+impl Corruption {
+    pub fn lower(&mut self) { self.level = self.level.saturating_sub(1); }
+    pub fn raise(&mut self) { self.level = (self.level + 1).min(self.unlocked); }
+
+    // A victory at the deepest unlocked level opens the next one and starts the next run there
+    pub fn record_victory(&mut self) -> bool {
+        if self.level < self.unlocked || self.unlocked >= MAX_CORRUPTION_LEVEL { return false; }
+        self.unlocked += 1;
+        self.level = self.unlocked;
+        true
+    }
+}
+
+// Global modifiers for the run in progress, fixed when it starts
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct RunDifficulty {
+    pub corruption_level: u32,
+    pub elite_chance: f64,
+    pub healing_multiplier: f32,
+}
+
+impl Default for RunDifficulty {
+    fn default() -> Self { Self::for_corruption(0) }
+}
+
+impl RunDifficulty {
+    // Each level stacks on the last: more elites, and every heal shrinks by the same factor again
+    pub fn for_corruption(level: u32) -> Self {
+        Self {
+            corruption_level: level,
+            elite_chance: (ELITE_SPAWN_CHANCE + CORRUPTION_ELITE_CHANCE_PER_LEVEL * level as f64).min(MAX_ELITE_CHANCE),
+            healing_multiplier: CORRUPTION_HEALING_FACTOR_PER_LEVEL.powi(level as i32),
+        }
+    }
+
+    pub fn scale_healing(&self, amount: f32) -> f32 { amount * self.healing_multiplier }
+}
+
+#[derive(Component)]
+struct CorruptionMenuText;
+
+#[derive(Component)]
+struct CorruptionHud;
+
+#[derive(Component)]
+struct CorruptionUnlockText;
+
+fn corruption_menu_label(corruption: &Corruption) -> String {
+    if corruption.unlocked == 0 { return "Corruption: escape the Abyss once to unlock".to_string(); }
+    format!("Corruption +{} of {} ([ / ])", corruption.level, corruption.unlocked)
+}
+
+fn setup_corruption_menu_text(mut commands: Commands, asset_server: Res<AssetServer>, corruption: Res<Corruption>) {
+    commands.spawn((
+        TextBundle::from_section(corruption_menu_label(&corruption), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: CORRUPTION_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(50.0), right: Val::Px(20.0), ..default() }),
+        CorruptionMenuText,
+    ));
+}
+
+fn choose_corruption_level(keyboard_input: Res<ButtonInput<KeyCode>>, mut corruption: ResMut<Corruption>) {
+    if keyboard_input.just_pressed(CORRUPTION_LOWER_KEY) { corruption.lower(); }
+    if keyboard_input.just_pressed(CORRUPTION_RAISE_KEY) { corruption.raise(); }
+}
+
+fn update_corruption_menu_text(corruption: Res<Corruption>, mut text_query: Query<&mut Text, With<CorruptionMenuText>>) {
+    if !corruption.is_changed() { return; }
+    for mut text in text_query.iter_mut() { text.sections[0].value = corruption_menu_label(&corruption); }
+}
+
+fn despawn_corruption_menu_text(mut commands: Commands, text_query: Query<Entity, With<CorruptionMenuText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+// Challenge runs are compared against everyone else's, so they always play uncorrupted
+fn apply_run_difficulty(corruption: Res<Corruption>, active_challenge: Res<ActiveChallenge>, mut run_difficulty: ResMut<RunDifficulty>) {
+    let level = if active_challenge.0.is_some() { 0 } else { corruption.level };
+    *run_difficulty = RunDifficulty::for_corruption(level);
+}
+
+fn setup_corruption_hud(mut commands: Commands, asset_server: Res<AssetServer>, run_difficulty: Res<RunDifficulty>) {
+    if run_difficulty.corruption_level == 0 { return; }
+    commands.spawn((
+        TextBundle::from_section(
+            format!("Corruption +{}  (elites {:.0}%, healing {:.0}%)", run_difficulty.corruption_level, run_difficulty.elite_chance * 100.0, run_difficulty.healing_multiplier * 100.0),
+            TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: CORRUPTION_COLOR },
+        ).with_style(Style { position_type: PositionType::Absolute, top: Val::Px(110.0), right: Val::Px(15.0), ..default() }),
+        CorruptionHud,
+        Name::new("CorruptionHud"),
+    ));
+}
+
+fn despawn_corruption_hud(mut commands: Commands, hud_query: Query<Entity, With<CorruptionHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn unlock_corruption_on_victory(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    outcome: Res<RunOutcome>,
+    run_difficulty: Res<RunDifficulty>,
+    active_challenge: Res<ActiveChallenge>,
+    run_stats: Res<RunStats>,
+    mut corruption: ResMut<Corruption>,
+) {
+    if *outcome != RunOutcome::Extracted || active_challenge.0.is_some() || !run_stats.counts_for_records() || run_difficulty.corruption_level != corruption.level { return; }
+    if !corruption.record_victory() { return; }
+    commands.spawn((
+        TextBundle::from_section(format!("Corruption +{} unlocked - your next descent starts there", corruption.level), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 26.0, color: CORRUPTION_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(20.0), width: Val::Percent(100.0), justify_content: JustifyContent::Center, ..default() })
+            .with_text_justify(JustifyText::Center),
+        CorruptionUnlockText,
+    ));
+}
+
+fn despawn_corruption_unlock_text(mut commands: Commands, text_query: Query<Entity, With<CorruptionUnlockText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
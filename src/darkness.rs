@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::Survivor,
+    camera_systems::MainCamera,
+    items::{ItemId, ItemLibrary, ItemEffect},
+    skills::{DecoyEffect, ActiveSkillAoEEffect},
+    weapons::HexSnare,
+    game::{AppState, SCREEN_WIDTH, SCREEN_HEIGHT},
+};
+
+pub const BASE_VISION_RADIUS: f32 = 340.0;
+// Width of the soft edge at the rim of every light
+pub const LIGHT_FALLOFF: f32 = 110.0;
+pub const MAX_DARKNESS_ALPHA: f32 = 0.92;
+const DARKNESS_CELL_SIZE: f32 = 48.0;
+// The overlay covers twice the screen so it still fills the view when the camera zooms out
+const DARKNESS_FIELD_HALF_EXTENTS: Vec2 = Vec2::new(SCREEN_WIDTH, SCREEN_HEIGHT);
+const DARKNESS_Z: f32 = 50.0;
+const DECOY_LIGHT_RADIUS: f32 = 200.0;
+const SNARE_LIGHT_RADIUS: f32 = 110.0;
+const AOE_LIGHT_MARGIN: f32 = 40.0;
+
+pub struct DarknessPlugin;
+
+impl Plugin for DarknessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::InGame), setup_darkness_overlay)
+            .add_systems(Update, (
+                attach_light_sources,
+                survivor_light_system,
+                darkness_overlay_system,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_darkness_overlay);
+    }
+}
+
+// Carves a circle of visibility out of the darkness
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LightSource { pub radius: f32 }
+
+#[derive(Component)]
+struct DarknessCell { offset: Vec2 }
+
+// How visible a point is given the lights around it: 1 inside any light's core, fading to 0 at its rim
+pub fn visibility_at(point: Vec2, lights: &[(Vec2, f32)]) -> f32 {
+    lights.iter()
+        .map(|(position, radius)| ((radius - point.distance(*position)) / LIGHT_FALLOFF).clamp(0.0, 1.0))
+        .fold(0.0, f32::max)
+}
+
+pub fn vision_radius(item_ids: &[ItemId], item_library: &ItemLibrary) -> f32 {
+    let bonus: f32 = item_ids.iter()
+        .filter_map(|item_id| item_library.get_item_definition(*item_id))
+        .flat_map(|item_def| item_def.effects.iter())
+        .filter_map(|effect| if let ItemEffect::ExtendVision { radius_fraction } = effect { Some(*radius_fraction) } else { None })
+        .sum();
+    BASE_VISION_RADIUS * (1.0 + bonus)
+}
+
+fn setup_darkness_overlay(mut commands: Commands) {
+    let columns = (DARKNESS_FIELD_HALF_EXTENTS.x * 2.0 / DARKNESS_CELL_SIZE).ceil() as i32;
+    let rows = (DARKNESS_FIELD_HALF_EXTENTS.y * 2.0 / DARKNESS_CELL_SIZE).ceil() as i32;
+    for row in 0..rows {
+        for column in 0..columns {
+            let offset = Vec2::new(column as f32 - columns as f32 / 2.0, row as f32 - rows as f32 / 2.0) * DARKNESS_CELL_SIZE;
+            commands.spawn((
+                SpriteBundle {
+                    // Slightly oversized so neighbouring cells never leave seams
+                    sprite: Sprite { custom_size: Some(Vec2::splat(DARKNESS_CELL_SIZE + 1.0)), color: Color::rgba(0.0, 0.0, 0.02, MAX_DARKNESS_ALPHA), ..default() },
+                    transform: Transform::from_translation(offset.extend(DARKNESS_Z)),
+                    ..default()
+                },
+                DarknessCell { offset },
+            ));
+        }
+    }
+}
+
+fn attach_light_sources(
+    mut commands: Commands,
+    decoy_query: Query<Entity, Added<DecoyEffect>>,
+    snare_query: Query<Entity, Added<HexSnare>>,
+    aoe_query: Query<(Entity, &ActiveSkillAoEEffect), Added<ActiveSkillAoEEffect>>,
+) {
+    for entity in decoy_query.iter() { commands.entity(entity).insert(LightSource { radius: DECOY_LIGHT_RADIUS }); }
+    for entity in snare_query.iter() { commands.entity(entity).insert(LightSource { radius: SNARE_LIGHT_RADIUS }); }
+    for (entity, aoe) in aoe_query.iter() { commands.entity(entity).insert(LightSource { radius: aoe.actual_radius_sq.sqrt() + AOE_LIGHT_MARGIN }); }
+}
+
+fn survivor_light_system(
+    mut commands: Commands,
+    item_library: Res<ItemLibrary>,
+    mut survivor_query: Query<(Entity, &Survivor, Option<&mut LightSource>)>,
+) {
+    for (entity, survivor, light_opt) in survivor_query.iter_mut() {
+        let radius = vision_radius(&survivor.collected_item_ids, &item_library);
+        match light_opt {
+            Some(mut light) => { if light.radius != radius { light.radius = radius; } }
+            None => { commands.entity(entity).insert(LightSource { radius }); }
+        }
+    }
+}
+
+fn darkness_overlay_system(
+    camera_query: Query<&Transform, With<MainCamera>>,
+    light_query: Query<(&GlobalTransform, &LightSource)>,
+    mut cell_query: Query<(&mut Transform, &mut Sprite, &DarknessCell), Without<MainCamera>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else { return; };
+    // Snap to the cell grid so the overlay doesn't shimmer as the camera glides
+    let anchor = (camera_transform.translation.truncate() / DARKNESS_CELL_SIZE).round() * DARKNESS_CELL_SIZE;
+    let lights: Vec<(Vec2, f32)> = light_query.iter().map(|(transform, light)| (transform.translation().truncate(), light.radius)).collect();
+    for (mut transform, mut sprite, cell) in cell_query.iter_mut() {
+        let position = anchor + cell.offset;
+        transform.translation = position.extend(DARKNESS_Z);
+        sprite.color.set_a(MAX_DARKNESS_ALPHA * (1.0 - visibility_at(position, &lights)));
+    }
+}
+
+fn despawn_darkness_overlay(mut commands: Commands, cell_query: Query<Entity, With<DarknessCell>>) {
+    for entity in cell_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
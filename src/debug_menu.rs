@@ -1,13 +1,31 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ecs::system::SystemParam, ui::RelativeCursorPosition};
 use crate::{
-    upgrades::{UpgradePool, UpgradeCard},
+    upgrades::{UpgradePool, UpgradeCard, UpgradeType},
     game::{AppState, UpgradeChosenEvent, ItemCollectedEvent},
+    coop::PlayerSlot,
     audio::{PlaySoundEvent, SoundEffect},
     items::{ItemLibrary, ItemId}, // ItemDefinition removed as unused directly here
-    skills::{SkillLibrary, SkillId}, // ActiveSkillInstance, SkillDefinition removed as unused directly here
+    skills::{SkillLibrary, SkillId, ActiveSkillInstance, MAX_SKILL_SLOTS},
     glyphs::{GlyphLibrary, GlyphId}, // GlyphDefinition removed as unused directly here
     survivor::Survivor, // Changed
+    run_history::{BuildCode, BUILD_EXPORT_PATH},
+    balance::{BalanceConfig, BalanceField, BALANCE_CONFIG_PATH},
+    components::{Health, Velocity, Lifetime, ProjectileOwner},
+    horror::Horror,
+    bestiary::horror_name,
+    skills::{ActiveSkillAoEEffect, DecoyEffect, TimeBubbleEffect},
+    items::ExplosionEffect,
+    game::{GameState, GameRng, apply_cycle_scaling},
+    horror::{HorrorSpawnTimer, MaxHorrors, HorrorType, spawn_horror_type, can_be_elite, cycle_stat_multiplier},
+    horror_stats::HorrorStatsLibrary,
+    game_events::GameEvent,
+    extraction::ExtractionRiftTimer,
+    respec_shrine::RespecShrineTimer,
+    contracts::ContractBoard,
+    ranking::RunStats,
+    platform::{GameStorage, SystemClipboard},
 };
+use rand::seq::SliceRandom;
 
 #[derive(Event)]
 pub struct DebugGrantGlyphEvent(pub GlyphId);
@@ -19,6 +37,29 @@ pub struct DebugSocketGlyphEvent {
     pub glyph_id_to_socket: GlyphId,
 }
 
+#[derive(Event)]
+pub struct DebugGrantSkillEvent(pub SkillId);
+
+// Sockets straight into the skill, without needing the glyph in the inventory first
+#[derive(Event)]
+pub struct DebugEquipGlyphEvent {
+    pub player_skill_slot_idx: usize,
+    pub glyph_id: GlyphId,
+}
+
+// Sandbox import of an exported build; upgrades and relics replay through the normal events
+#[derive(Event)]
+pub struct DebugImportBuildEvent(pub BuildCode);
+
+pub const IMPORT_BUILD_KEY: KeyCode = KeyCode::KeyI;
+// Nearest first; the rest are left out so the list stays usable mid-swarm
+pub const INSPECTOR_MAX_ROWS: usize = 40;
+pub const DEBUG_FAST_FORWARD_SECS: f32 = 300.0;
+// Same distance as boss practice, so the elite opens on screen but not on top of the survivor
+const DEBUG_ELITE_DISTANCE: f32 = 400.0;
+// Frames to wait for replayed GrantSkill upgrades to land before missing skills are added directly
+const IMPORT_SETTLE_FRAMES: u32 = 5;
+
 pub struct DebugMenuPlugin;
 
 impl Plugin for DebugMenuPlugin {
@@ -26,7 +67,14 @@ impl Plugin for DebugMenuPlugin {
         app
             .add_event::<DebugGrantGlyphEvent>()
             .add_event::<DebugSocketGlyphEvent>()
-            .add_systems(OnEnter(AppState::DebugUpgradeMenu), setup_debug_menu_ui)
+            .add_event::<DebugImportBuildEvent>()
+            .add_event::<DebugGrantSkillEvent>()
+            .add_event::<DebugEquipGlyphEvent>()
+            .init_resource::<PendingBuildGlyphs>()
+            .init_resource::<DebugTab>()
+            .init_resource::<InspectorFilter>()
+            .init_resource::<DebugCycleTarget>()
+            .add_systems(OnEnter(AppState::DebugUpgradeMenu), (setup_debug_menu_ui, setup_build_import_hint, flag_run_as_cheated))
             .add_systems(Update,
                 (
                     debug_menu_button_interaction_system,
@@ -34,20 +82,211 @@ impl Plugin for DebugMenuPlugin {
                     debug_glyph_button_interaction_system,
                     debug_socket_glyph_button_interaction_system,
                     debug_menu_keyboard_scroll_system,
+                    debug_import_build_key_system,
+                    (debug_grant_skill_button_system, debug_equip_glyph_button_system, update_debug_skill_tab_texts).chain(),
+                    (debug_timeline_button_system, update_debug_timeline_text).chain(),
+                    (debug_balance_slider_system, update_debug_balance_sliders).chain(),
+                    (handle_debug_tab_buttons, handle_inspector_filter_buttons, highlight_debug_tabs, debug_inspector_edit_system, update_inspector_values).chain(),
                 )
                 .run_if(in_state(AppState::DebugUpgradeMenu))
             )
             .add_systems(Update,
                 (
                     handle_debug_grant_glyph.run_if(on_event::<DebugGrantGlyphEvent>()),
-                    handle_debug_socket_glyph.run_if(on_event::<DebugSocketGlyphEvent>())
+                    handle_debug_socket_glyph.run_if(on_event::<DebugSocketGlyphEvent>()),
+                    handle_debug_import_build.run_if(on_event::<DebugImportBuildEvent>()),
+                    handle_debug_grant_skill.run_if(on_event::<DebugGrantSkillEvent>()),
+                    handle_debug_equip_glyph.run_if(on_event::<DebugEquipGlyphEvent>()),
+                    restore_imported_glyphs,
                 )
             )
             .add_systems(OnExit(AppState::DebugUpgradeMenu), despawn_debug_menu_ui);
     }
 }
 
+// The debug menu reopens on whichever tab and inspector filter were last used
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugTab {
+    #[default]
+    Grants,
+    Skills,
+    Timeline,
+    Inspector,
+}
+
+impl DebugTab {
+    pub const ALL: [DebugTab; 4] = [DebugTab::Grants, DebugTab::Skills, DebugTab::Timeline, DebugTab::Inspector];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DebugTab::Grants => "Grants & Balance",
+            DebugTab::Skills => "Skills & Glyphs",
+            DebugTab::Timeline => "Time & Events",
+            DebugTab::Inspector => "Inspector",
+        }
+    }
+}
+
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InspectorFilter {
+    #[default]
+    Enemies,
+    Projectiles,
+    Effects,
+}
+
+impl InspectorFilter {
+    pub const ALL: [InspectorFilter; 3] = [InspectorFilter::Enemies, InspectorFilter::Projectiles, InspectorFilter::Effects];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            InspectorFilter::Enemies => "Enemies",
+            InspectorFilter::Projectiles => "Projectiles",
+            InspectorFilter::Effects => "Effects",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectorField { Health, Speed, Timer, }
+
+impl InspectorField {
+    pub const ALL: [InspectorField; 3] = [InspectorField::Health, InspectorField::Speed, InspectorField::Timer];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InspectorField::Health => "HP",
+            InspectorField::Speed => "Speed",
+            InspectorField::Timer => "Time left",
+        }
+    }
+
+    // What one press of - or + changes the field by
+    pub fn step(&self) -> f32 {
+        match self {
+            InspectorField::Health => 10.0,
+            InspectorField::Speed => 25.0,
+            InspectorField::Timer => 0.5,
+        }
+    }
+
+    pub fn format(&self, value: f32) -> String {
+        match self {
+            InspectorField::Health | InspectorField::Speed => format!("{:.0}", value),
+            InspectorField::Timer => format!("{:.1}s", value),
+        }
+    }
+}
+
+// Moves a timer's end without touching how far it has run; it can be brought forward to now but not into the past
+pub fn adjust_remaining(timer: &mut Timer, delta_secs: f32) {
+    let remaining = (timer.remaining_secs() + delta_secs).max(0.0);
+    timer.set_duration(timer.elapsed() + std::time::Duration::from_secs_f32(remaining));
+}
+
+// Timed happenings the timeline tab can bring forward; each keeps its own clock apart from the cycle timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledEvent {
+    ExtractionRift,
+    RespecShrine,
+    ContractOffer,
+}
+
+impl ScheduledEvent {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduledEvent::ExtractionRift => "Extraction Rift",
+            ScheduledEvent::RespecShrine => "Respec Shrine",
+            ScheduledEvent::ContractOffer => "Contract offer",
+        }
+    }
+}
+
+pub fn next_scheduled_event(upcoming: &[(ScheduledEvent, f32)]) -> Option<(ScheduledEvent, f32)> {
+    upcoming.iter().copied().min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+// Leaves the timer a hair short of its duration, so its owner sees it finish on the next tick without the interval changing
+pub fn finish_on_next_tick(timer: &mut Timer) {
+    timer.set_elapsed(timer.duration().saturating_sub(std::time::Duration::from_micros(1)));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum TimelineAction {
+    PreviousCycle,
+    NextCycle,
+    JumpToCycle,
+    FastForward,
+    ForceNextEvent,
+    SpawnElite,
+}
+
+impl TimelineAction {
+    const ALL: [TimelineAction; 6] = [TimelineAction::PreviousCycle, TimelineAction::NextCycle, TimelineAction::JumpToCycle, TimelineAction::FastForward, TimelineAction::ForceNextEvent, TimelineAction::SpawnElite];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TimelineAction::PreviousCycle => "Target cycle -1",
+            TimelineAction::NextCycle => "Target cycle +1",
+            TimelineAction::JumpToCycle => "Jump to target cycle",
+            TimelineAction::FastForward => "Fast-forward 5 minutes",
+            TimelineAction::ForceNextEvent => "Force next scheduled event",
+            TimelineAction::SpawnElite => "Spawn an elite",
+        }
+    }
+}
+
+#[derive(Resource)]
+struct DebugCycleTarget(u32);
+
+impl Default for DebugCycleTarget {
+    fn default() -> Self { Self(1) }
+}
+
+// A skill already learned but left out of the loadout is equipped instead; with the loadout full it takes the last slot
+pub fn grant_skill_directly(player: &mut Survivor, skill_id: SkillId, base_glyph_slots: u8) -> bool {
+    if player.equipped_skills.iter().any(|skill| skill.definition_id == skill_id) { return false; }
+    let Some(learned_idx) = player.learned_skills.iter().position(|skill| skill.definition_id == skill_id) else {
+        return player.learn_skill(ActiveSkillInstance::new(skill_id, base_glyph_slots));
+    };
+    let skill = player.learned_skills.remove(learned_idx);
+    if player.equipped_skills.len() >= MAX_SKILL_SLOTS {
+        if let Some(bumped) = player.equipped_skills.pop() { player.learned_skills.push(bumped); }
+    }
+    player.equipped_skills.push(skill);
+    true
+}
+
+// Fills the first empty socket; with every socket full the last glyph is swapped out and goes back to the inventory
+pub fn equip_glyph_directly(player: &mut Survivor, player_skill_slot_idx: usize, glyph_id: GlyphId) -> bool {
+    let Some(skill) = player.equipped_skills.get_mut(player_skill_slot_idx) else { return false; };
+    if skill.equipped_glyphs.is_empty() || skill.equipped_glyphs.contains(&Some(glyph_id)) { return false; }
+    let socket_idx = skill.equipped_glyphs.iter().position(Option::is_none).unwrap_or(skill.equipped_glyphs.len() - 1);
+    let displaced = skill.equipped_glyphs[socket_idx].replace(glyph_id);
+    player.collected_glyphs.retain(|&id| id != glyph_id);
+    if let Some(displaced) = displaced.filter(|id| !player.collected_glyphs.contains(id)) { player.collected_glyphs.push(displaced); }
+    true
+}
+
+pub fn skill_grant_status(player: &Survivor, skill_id: SkillId) -> String {
+    if let Some(slot) = player.equipped_skills.iter().position(|skill| skill.definition_id == skill_id) { return format!("slot {}", slot + 1); }
+    if player.knows_skill(skill_id) { "learned".to_string() } else { String::new() }
+}
+
+fn skill_loadout_label(player: Option<&Survivor>, skill_library: &SkillLibrary, glyph_library: &GlyphLibrary) -> String {
+    let Some(player) = player.filter(|player| !player.equipped_skills.is_empty()) else { return "No skills equipped.".to_string(); };
+    player.equipped_skills.iter().enumerate().map(|(slot, skill)| {
+        let skill_name = skill_library.get_skill_definition(skill.definition_id).map_or("?", |def| def.name.as_str());
+        let glyphs: Vec<&str> = skill.equipped_glyphs.iter().map(|glyph| glyph.and_then(|id| glyph_library.get_glyph_definition(id)).map_or("-", |def| def.name.as_str())).collect();
+        format!("{}. {} [{}]", slot + 1, skill_name, glyphs.join(", "))
+    }).collect::<Vec<_>>().join("\n")
+}
+
 #[derive(Component)] struct DebugMenuUIRoot;
+#[derive(Component)] struct DebugTimelineText;
+#[derive(Component)] struct DebugTabPanel;
+#[derive(Component)] struct DebugInspectorValue { entity: Entity, field: InspectorField }
+#[derive(Component)] struct DebugInspectorEdit { entity: Entity, field: InspectorField, direction: f32 }
 #[derive(Component)] struct DebugUpgradeButton(UpgradeCard);
 #[derive(Component)] struct DebugItemButton(ItemId);
 #[derive(Component)] struct DebugGlyphButton(GlyphId);
@@ -57,6 +296,13 @@ struct DebugSocketGlyphButton {
     glyph_slot_idx: usize,
     glyph_id_to_socket: GlyphId,
 }
+#[derive(Component)] struct DebugGrantSkillButton(SkillId);
+#[derive(Component)] struct DebugEquipGlyphButton { player_skill_slot_idx: usize, glyph_id: GlyphId }
+#[derive(Component)] struct DebugSkillStatusText(SkillId);
+#[derive(Component)] struct DebugSkillLoadoutText;
+#[derive(Component)] struct DebugBalanceSlider(BalanceField);
+#[derive(Component)] struct DebugBalanceSliderFill(BalanceField);
+#[derive(Component)] struct DebugBalanceValueText(BalanceField);
 #[derive(Component)] struct DebugMenuScrollView;
 #[derive(Component)] struct DebugMenuScrollableContent;
 #[derive(Component)] struct ScrollOffset(f32);
@@ -69,34 +315,621 @@ const DEBUG_BUTTON_HOVER_BG_COLOR: Color = Color::rgb(0.35, 0.35, 0.35);
 const DEBUG_BUTTON_PRESSED_BG_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
 const DEBUG_SCROLL_AREA_BG_COLOR: Color = Color::rgba(0.12, 0.12, 0.12, 1.0);
 const KEYBOARD_SCROLL_SPEED: f32 = 30.0;
+const DEBUG_COLUMN_WIDTH_PERCENT: f32 = 19.0;
+const DEBUG_SLIDER_HEIGHT: Val = Val::Px(12.0);
+const DEBUG_SLIDER_FILL_COLOR: Color = Color::rgb(0.85, 0.45, 0.75);
+const DEBUG_ACTIVE_TAB_COLOR: Color = Color::ORANGE_RED;
+
+// What the grants tab lists, besides the balance sliders' own resource
+#[derive(SystemParam)]
+struct DebugGrantSources<'w, 's> {
+    upgrade_pool: Res<'w, UpgradePool>,
+    item_library: Res<'w, ItemLibrary>,
+    glyph_library: Res<'w, GlyphLibrary>,
+    skill_library: Res<'w, SkillLibrary>,
+    balance: Res<'w, BalanceConfig>,
+    player_query: Query<'w, 's, &'static Survivor>,
+}
+
+// Live entities the inspector can list, and the components behind each editable field
+#[derive(SystemParam)]
+struct InspectorQueries<'w, 's> {
+    player_query: Query<'w, 's, &'static Transform, With<Survivor>>,
+    enemy_query: Query<'w, 's, (Entity, &'static Transform), With<Horror>>,
+    projectile_query: Query<'w, 's, (Entity, &'static Transform, Option<&'static Name>), With<ProjectileOwner>>,
+    effect_query: Query<'w, 's, (Entity, &'static Transform, Option<&'static Name>), Or<(With<ActiveSkillAoEEffect>, With<DecoyEffect>, With<TimeBubbleEffect>, With<ExplosionEffect>)>>,
+    health: Query<'w, 's, &'static mut Health>,
+    horror: Query<'w, 's, &'static mut Horror>,
+    velocity: Query<'w, 's, &'static mut Velocity>,
+    lifetime: Query<'w, 's, &'static mut Lifetime>,
+    aoe: Query<'w, 's, &'static mut ActiveSkillAoEEffect>,
+    decoy: Query<'w, 's, &'static mut DecoyEffect>,
+    time_bubble: Query<'w, 's, &'static mut TimeBubbleEffect>,
+    explosion: Query<'w, 's, &'static mut ExplosionEffect>,
+}
+
+impl InspectorQueries<'_, '_> {
+    fn rows(&self, filter: InspectorFilter) -> Vec<(Entity, String)> {
+        let origin = self.player_query.get_single().map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+        let mut rows: Vec<(f32, Entity, String)> = match filter {
+            InspectorFilter::Enemies => self.enemy_query.iter().map(|(entity, transform)| {
+                let label = self.horror.get(entity).map_or_else(|_| "Horror".to_string(), |horror| format!("{}{}", horror_name(horror.horror_type), if horror.is_elite { " (elite)" } else { "" }));
+                (transform.translation.truncate().distance(origin), entity, label)
+            }).collect(),
+            InspectorFilter::Projectiles => self.projectile_query.iter().map(|(entity, transform, name)| (transform.translation.truncate().distance(origin), entity, name.map_or("Projectile", |name| name.as_str()).to_string())).collect(),
+            InspectorFilter::Effects => self.effect_query.iter().map(|(entity, transform, name)| (transform.translation.truncate().distance(origin), entity, name.map_or("Effect", |name| name.as_str()).to_string())).collect(),
+        };
+        rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+        rows.into_iter().take(INSPECTOR_MAX_ROWS).map(|(_, entity, label)| (entity, format!("{} #{}", label, entity.index()))).collect()
+    }
+
+    // None when the entity has nothing behind that field
+    fn read(&self, entity: Entity, field: InspectorField) -> Option<f32> {
+        match field {
+            InspectorField::Health => self.health.get(entity).ok().map(|health| health.0 as f32),
+            InspectorField::Speed => self.horror.get(entity).map(|horror| horror.speed).or_else(|_| self.velocity.get(entity).map(|velocity| velocity.0.length())).ok(),
+            InspectorField::Timer => {
+                if let Ok(lifetime) = self.lifetime.get(entity) { return Some(lifetime.timer.remaining_secs()); }
+                if let Ok(aoe) = self.aoe.get(entity) { return Some(aoe.lifetime_timer.remaining_secs()); }
+                if let Ok(decoy) = self.decoy.get(entity) { return Some(decoy.lifetime_timer.remaining_secs()); }
+                if let Ok(bubble) = self.time_bubble.get(entity) { return Some(bubble.lifetime_timer.remaining_secs()); }
+                self.explosion.get(entity).ok().map(|explosion| explosion.timer.remaining_secs())
+            }
+        }
+    }
+
+    fn adjust(&mut self, entity: Entity, field: InspectorField, delta: f32) {
+        match field {
+            InspectorField::Health => if let Ok(mut health) = self.health.get_mut(entity) { health.0 += delta as i32; },
+            InspectorField::Speed => {
+                if let Ok(mut horror) = self.horror.get_mut(entity) { horror.speed = (horror.speed + delta).max(0.0); return; }
+                // A stopped projectile has no direction left to speed back up along
+                if let Ok(mut velocity) = self.velocity.get_mut(entity) { let speed = (velocity.0.length() + delta).max(1.0); velocity.0 = velocity.0.normalize_or_zero() * speed; }
+            }
+            InspectorField::Timer => {
+                if let Ok(mut lifetime) = self.lifetime.get_mut(entity) { adjust_remaining(&mut lifetime.timer, delta); return; }
+                if let Ok(mut aoe) = self.aoe.get_mut(entity) { adjust_remaining(&mut aoe.lifetime_timer, delta); return; }
+                if let Ok(mut decoy) = self.decoy.get_mut(entity) { adjust_remaining(&mut decoy.lifetime_timer, delta); return; }
+                if let Ok(mut bubble) = self.time_bubble.get_mut(entity) { adjust_remaining(&mut bubble.lifetime_timer, delta); return; }
+                if let Ok(mut explosion) = self.explosion.get_mut(entity) { adjust_remaining(&mut explosion.timer, delta); }
+            }
+        }
+    }
+}
+
+// The run's clocks and the spawn scaling that follows the cycle
+#[derive(SystemParam)]
+struct RunTimeline<'w> {
+    game_state: ResMut<'w, GameState>,
+    horror_spawn_timer: ResMut<'w, HorrorSpawnTimer>,
+    max_horrors: ResMut<'w, MaxHorrors>,
+    rift_timer: ResMut<'w, ExtractionRiftTimer>,
+    shrine_timer: ResMut<'w, RespecShrineTimer>,
+    contract_board: ResMut<'w, ContractBoard>,
+}
+
+impl RunTimeline<'_> {
+    fn upcoming(&self) -> Vec<(ScheduledEvent, f32)> {
+        let mut upcoming = vec![
+            (ScheduledEvent::ExtractionRift, self.rift_timer.0.remaining_secs()),
+            (ScheduledEvent::RespecShrine, self.shrine_timer.0.remaining_secs()),
+        ];
+        // The offer clock only runs while no contract is taken
+        if self.contract_board.active.is_none() { upcoming.push((ScheduledEvent::ContractOffer, self.contract_board.next_offer_timer.remaining_secs())); }
+        upcoming
+    }
+
+    fn force(&mut self, event: ScheduledEvent) {
+        match event {
+            ScheduledEvent::ExtractionRift => finish_on_next_tick(&mut self.rift_timer.0),
+            ScheduledEvent::RespecShrine => finish_on_next_tick(&mut self.shrine_timer.0),
+            ScheduledEvent::ContractOffer => finish_on_next_tick(&mut self.contract_board.next_offer_timer),
+        }
+    }
+
+    fn jump_to_cycle(&mut self, cycle_number: u32) {
+        self.game_state.cycle_number = cycle_number.max(1);
+        self.game_state.difficulty_timer.reset();
+        apply_cycle_scaling(self.game_state.cycle_number, &mut self.horror_spawn_timer, &mut self.max_horrors);
+    }
+
+    fn fast_forward(&mut self, secs: f32) {
+        if self.game_state.fast_forward(secs) > 0 { apply_cycle_scaling(self.game_state.cycle_number, &mut self.horror_spawn_timer, &mut self.max_horrors); }
+    }
+
+    fn summary(&self, target_cycle: u32) -> String {
+        let run_secs = self.game_state.game_timer.elapsed_secs() as u32;
+        let next_event = next_scheduled_event(&self.upcoming()).map_or_else(|| "none".to_string(), |(event, secs)| format!("{} in {:.0}s", event.label(), secs));
+        format!(
+            "Cycle {} ({:.0}s to the next)   Run time {:02}:{:02}   Target cycle {}\nHorror cap {}, spawning every {:.2}s\nNext scheduled event: {}",
+            self.game_state.cycle_number, self.game_state.difficulty_timer.remaining_secs(), run_secs / 60, run_secs % 60, target_cycle,
+            self.max_horrors.0, self.horror_spawn_timer.timer.duration().as_secs_f32(), next_event,
+        )
+    }
+}
 
 fn setup_debug_menu_ui(
     mut commands: Commands, asset_server: Res<AssetServer>,
-    upgrade_pool: Res<UpgradePool>, item_library: Res<ItemLibrary>,
-    glyph_library: Res<GlyphLibrary>, skill_library: Res<SkillLibrary>,
-    player_query: Query<&Survivor>, // Changed
+    grant_sources: DebugGrantSources,
+    inspector: InspectorQueries,
+    tab: Res<DebugTab>,
+    filter: Res<InspectorFilter>,
 ) {
-    let player_skills_equipped_glyphs: Vec<(SkillId, Vec<Option<GlyphId>>)> = if let Ok(player) = player_query.get_single() {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn(( NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(8.0), ..default() }, background_color: Color::rgba(0.0, 0.0, 0.0, 0.90).into(), z_index: ZIndex::Global(50), ..default() }, DebugMenuUIRoot, Name::new("DebugMenuUIRoot"), )).with_children(|parent| {
+        parent.spawn(NodeBundle { style: Style { column_gap: Val::Px(12.0), ..default() }, ..default() }).with_children(|tab_row| {
+            for tab in DebugTab::ALL {
+                tab_row.spawn((
+                    ButtonBundle { style: Style { padding: UiRect::axes(Val::Px(14.0), Val::Px(3.0)), border: UiRect::bottom(Val::Px(2.0)), ..default() }, background_color: Color::NONE.into(), border_color: BorderColor(Color::NONE), ..default() },
+                    tab,
+                )).with_children(|button| { button.spawn(TextBundle::from_section(tab.title(), TextStyle { font: font.clone(), font_size: 18.0, color: DEBUG_TEXT_COLOR })); });
+            }
+        });
+        parent.spawn((NodeBundle { style: Style { width: Val::Percent(90.0), min_width: Val::Px(900.0), max_width: Val::Px(1400.0), height: Val::Percent(85.0), flex_direction: FlexDirection::Row, justify_content: JustifyContent::SpaceAround, border: UiRect::all(Val::Px(2.0)), padding: UiRect::all(Val::Px(10.0)), ..default() }, border_color: BorderColor(Color::DARK_GRAY), background_color: Color::rgb(0.05, 0.05, 0.07).into(), ..default()
+        }, DebugTabPanel)).with_children(|panel| spawn_debug_tab_contents(panel, *tab, *filter, &asset_server, &grant_sources, &inspector));
+    });
+}
+
+fn spawn_debug_tab_contents(panel: &mut ChildBuilder, tab: DebugTab, filter: InspectorFilter, asset_server: &AssetServer, grant_sources: &DebugGrantSources, inspector: &InspectorQueries) {
+    match tab {
+        DebugTab::Grants => spawn_grant_columns(panel, asset_server, grant_sources),
+        DebugTab::Skills => spawn_skill_grant_columns(panel, asset_server, grant_sources),
+        DebugTab::Timeline => spawn_timeline_controls(panel, asset_server),
+        DebugTab::Inspector => spawn_inspector(panel, filter, asset_server, inspector),
+    }
+}
+
+fn spawn_grant_columns(sections_container: &mut ChildBuilder, asset_server: &AssetServer, sources: &DebugGrantSources) {
+    let (upgrade_pool, item_library, glyph_library, skill_library) = (&sources.upgrade_pool, &sources.item_library, &sources.glyph_library, &sources.skill_library);
+    let player_skills_equipped_glyphs: Vec<(SkillId, Vec<Option<GlyphId>>)> = if let Ok(player) = sources.player_query.get_single() {
         player.equipped_skills.iter().map(|s| (s.definition_id, s.equipped_glyphs.clone())).collect()
     } else { Vec::new() };
-    let collected_glyphs_inventory: Vec<GlyphId> = if let Ok(player) = player_query.get_single() { player.collected_glyphs.clone() } else { Vec::new() };
-
-    commands.spawn(( NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() }, background_color: Color::rgba(0.0, 0.0, 0.0, 0.90).into(), z_index: ZIndex::Global(50), ..default() }, DebugMenuUIRoot, Name::new("DebugMenuUIRoot"), )).with_children(|parent| {
-        parent.spawn(NodeBundle { style: Style { width: Val::Percent(90.0), min_width: Val::Px(900.0), max_width: Val::Px(1400.0), height: Val::Percent(90.0), flex_direction: FlexDirection::Row, justify_content: JustifyContent::SpaceAround, border: UiRect::all(Val::Px(2.0)), padding: UiRect::all(Val::Px(10.0)), ..default() }, border_color: BorderColor(Color::DARK_GRAY).into(), background_color: Color::rgb(0.05, 0.05, 0.07).into(), ..default()
-        }).with_children(|sections_container| {
-            sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(24.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "UPGRADES", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::ORANGE_RED,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("UpgradeScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("UpgradeList"), )).with_children(|list| { for card in upgrade_pool.available_upgrades.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugUpgradeButton(card.clone()), Name::new(format!("DbgUp:{}", card.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] {}", card.id.0, card.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
-            sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(24.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "ITEMS (Grant)", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::CYAN,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("ItemScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("ItemList"), )).with_children(|list| { for item_def in item_library.items.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugItemButton(item_def.id), Name::new(format!("DbgItem:{}", item_def.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] {}", item_def.id.0, item_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
-            sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(24.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "GLYPHS (Grant to Inv)", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::LIME_GREEN,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("GlyphGrantScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("GlyphGrantList"), )).with_children(|list| { for glyph_def in glyph_library.glyphs.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugGlyphButton(glyph_def.id), Name::new(format!("DbgGlyphGrant:{}", glyph_def.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] Grant {}", glyph_def.id.0, glyph_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
-            sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(24.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "SOCKET GLYPHS", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::YELLOW,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("GlyphSocketScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("GlyphSocketList"), )).with_children(|list| { for (skill_idx, (skill_id, equipped_glyphs_in_skill)) in player_skills_equipped_glyphs.iter().enumerate() { if let Some(skill_definition) = skill_library.get_skill_definition(*skill_id) { list.spawn(TextBundle::from_section(format!("Skill: {}", skill_definition.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 13.0, color: Color::WHITE,}).with_style(Style {margin: UiRect::top(Val::Px(5.0)), ..default()})); for (glyph_slot_idx, current_glyph_opt) in equipped_glyphs_in_skill.iter().enumerate() { let slot_text = if let Some(current_glyph_id) = current_glyph_opt { glyph_library.get_glyph_definition(*current_glyph_id).map_or("Slot Filled (Unknown)".to_string(), |g| format!("Slot {}: {}", glyph_slot_idx, g.name)) } else { format!("Slot {}: EMPTY", glyph_slot_idx) }; list.spawn(TextBundle::from_section(slot_text, TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: Color::GRAY,}).with_style(Style{ margin: UiRect::left(Val::Px(10.0)), ..default()})); if current_glyph_opt.is_none() { for collected_glyph_id in collected_glyphs_inventory.iter() { if let Some(glyph_to_socket_def) = glyph_library.get_glyph_definition(*collected_glyph_id) { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::new(Val::Px(20.0), Val::Px(0.0), Val::Px(0.0),DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugSocketGlyphButton { player_skill_slot_idx: skill_idx, glyph_slot_idx, glyph_id_to_socket: *collected_glyph_id }, Name::new(format!("SocketGlyph:{}:S{}:GS{}", glyph_to_socket_def.id.0, skill_idx, glyph_slot_idx)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("Socket '{}'", glyph_to_socket_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 10.0, color: DEBUG_TEXT_COLOR,}));}); } } } } } } if collected_glyphs_inventory.is_empty() { list.spawn(TextBundle::from_section("No collected glyphs to socket.", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: Color::GRAY,}));} }); }); });
+    let collected_glyphs_inventory: Vec<GlyphId> = if let Ok(player) = sources.player_query.get_single() { player.collected_glyphs.clone() } else { Vec::new() };
+
+    sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "UPGRADES", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::ORANGE_RED,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("UpgradeScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("UpgradeList"), )).with_children(|list| { for card in upgrade_pool.available_upgrades.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugUpgradeButton(card.clone()), Name::new(format!("DbgUp:{}", card.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] {}", card.id.0, card.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
+    sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "ITEMS (Grant)", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::CYAN,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("ItemScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("ItemList"), )).with_children(|list| { for item_def in item_library.items.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugItemButton(item_def.id), Name::new(format!("DbgItem:{}", item_def.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] {}", item_def.id.0, item_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
+    sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "GLYPHS (Grant to Inv)", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::LIME_GREEN,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("GlyphGrantScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("GlyphGrantList"), )).with_children(|list| { for glyph_def in glyph_library.glyphs.iter() { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugGlyphButton(glyph_def.id), Name::new(format!("DbgGlyphGrant:{}", glyph_def.name)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("[{}] Grant {}", glyph_def.id.0, glyph_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: DEBUG_TEXT_COLOR,}));}); } }); }); });
+    sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| { panel.spawn(TextBundle::from_section( "SOCKET GLYPHS", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::YELLOW,}, ).with_style(Style {margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default()})); panel.spawn(( NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default()}, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("GlyphSocketScroll"), )).with_children(|scroll| { scroll.spawn(( NodeBundle {style: Style {position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default()}, ..default()}, DebugMenuScrollableContent, Name::new("GlyphSocketList"), )).with_children(|list| { for (skill_idx, (skill_id, equipped_glyphs_in_skill)) in player_skills_equipped_glyphs.iter().enumerate() { if let Some(skill_definition) = skill_library.get_skill_definition(*skill_id) { list.spawn(TextBundle::from_section(format!("Skill: {}", skill_definition.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 13.0, color: Color::WHITE,}).with_style(Style {margin: UiRect::top(Val::Px(5.0)), ..default()})); for (glyph_slot_idx, current_glyph_opt) in equipped_glyphs_in_skill.iter().enumerate() { let slot_text = if let Some(current_glyph_id) = current_glyph_opt { glyph_library.get_glyph_definition(*current_glyph_id).map_or("Slot Filled (Unknown)".to_string(), |g| format!("Slot {}: {}", glyph_slot_idx, g.name)) } else { format!("Slot {}: EMPTY", glyph_slot_idx) }; list.spawn(TextBundle::from_section(slot_text, TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: Color::GRAY,}).with_style(Style{ margin: UiRect::left(Val::Px(10.0)), ..default()})); if current_glyph_opt.is_none() { for collected_glyph_id in collected_glyphs_inventory.iter() { if let Some(glyph_to_socket_def) = glyph_library.get_glyph_definition(*collected_glyph_id) { list.spawn(( ButtonBundle { style: Style {height: DEBUG_BUTTON_HEIGHT, margin: UiRect::new(Val::Px(20.0), Val::Px(0.0), Val::Px(0.0),DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::FlexStart, align_items: AlignItems::Center, ..default()}, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default()}, DebugSocketGlyphButton { player_skill_slot_idx: skill_idx, glyph_slot_idx, glyph_id_to_socket: *collected_glyph_id }, Name::new(format!("SocketGlyph:{}:S{}:GS{}", glyph_to_socket_def.id.0, skill_idx, glyph_slot_idx)), )).with_children(|btn| { btn.spawn(TextBundle::from_section(format!("Socket '{}'", glyph_to_socket_def.name), TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 10.0, color: DEBUG_TEXT_COLOR,}));}); } } } } } } if collected_glyphs_inventory.is_empty() { list.spawn(TextBundle::from_section("No collected glyphs to socket.", TextStyle {font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 11.0, color: Color::GRAY,}));} }); }); });
+    spawn_balance_sliders(sections_container, asset_server.load("fonts/FiraSans-Bold.ttf"), &sources.balance);
+}
+
+fn spawn_debug_scroll_list(column: &mut ChildBuilder, name: &str, fill: impl FnOnce(&mut ChildBuilder)) {
+    column.spawn((
+        NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, ..default() }, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() },
+        DebugMenuScrollView,
+        ScrollOffset(0.0),
+        Name::new(format!("{}Scroll", name)),
+    )).with_children(|scroll| {
+        scroll.spawn((
+            NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Stretch, ..default() }, ..default() },
+            DebugMenuScrollableContent,
+            Name::new(format!("{}List", name)),
+        )).with_children(fill);
+    });
+}
+
+fn spawn_skill_grant_columns(panel: &mut ChildBuilder, asset_server: &AssetServer, sources: &DebugGrantSources) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let player = sources.player_query.get_single().ok();
+    let column_style = Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(45.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() };
+    let button_style = Style { height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), padding: UiRect::horizontal(Val::Px(5.0)), justify_content: JustifyContent::SpaceBetween, align_items: AlignItems::Center, ..default() };
+    let text_style = TextStyle { font: font.clone(), font_size: 11.0, color: DEBUG_TEXT_COLOR };
+
+    panel.spawn(NodeBundle { style: column_style.clone(), ..default() }).with_children(|column| {
+        column.spawn(TextBundle::from_section("SKILLS (Grant / Equip)", TextStyle { font: font.clone(), font_size: 16.0, color: Color::VIOLET })
+            .with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default() }));
+        spawn_debug_scroll_list(column, "SkillGrant", |list| {
+            for skill_def in sources.skill_library.skills.iter() {
+                list.spawn((
+                    ButtonBundle { style: button_style.clone(), background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default() },
+                    DebugGrantSkillButton(skill_def.id),
+                    Name::new(format!("DbgSkill:{}", skill_def.name)),
+                )).with_children(|button| {
+                    button.spawn(TextBundle::from_section(format!("[{}] {}", skill_def.id.0, skill_def.name), text_style.clone()));
+                    button.spawn((
+                        TextBundle::from_section(player.map_or_else(String::new, |player| skill_grant_status(player, skill_def.id)), TextStyle { color: Color::GRAY, ..text_style.clone() }),
+                        DebugSkillStatusText(skill_def.id),
+                    ));
+                });
+            }
+        });
+    });
+
+    panel.spawn(NodeBundle { style: column_style, ..default() }).with_children(|column| {
+        column.spawn(TextBundle::from_section("GLYPHS (Equip to skill slot)", TextStyle { font: font.clone(), font_size: 16.0, color: Color::LIME_GREEN })
+            .with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default() }));
+        column.spawn((
+            TextBundle::from_section(skill_loadout_label(player, &sources.skill_library, &sources.glyph_library), TextStyle { font_size: 12.0, ..text_style.clone() })
+                .with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), ..default() }),
+            DebugSkillLoadoutText,
+        ));
+        spawn_debug_scroll_list(column, "GlyphEquip", |list| {
+            for glyph_def in sources.glyph_library.glyphs.iter() {
+                list.spawn(NodeBundle { style: Style { margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), align_items: AlignItems::Center, column_gap: Val::Px(3.0), ..default() }, ..default() }).with_children(|row| {
+                    row.spawn(TextBundle::from_section(format!("[{}] {}", glyph_def.id.0, glyph_def.name), text_style.clone()).with_style(Style { flex_grow: 1.0, ..default() }));
+                    for player_skill_slot_idx in 0..MAX_SKILL_SLOTS {
+                        row.spawn((
+                            ButtonBundle { style: Style { width: Val::Px(20.0), height: DEBUG_BUTTON_HEIGHT, justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() }, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default() },
+                            DebugEquipGlyphButton { player_skill_slot_idx, glyph_id: glyph_def.id },
+                        )).with_children(|button| { button.spawn(TextBundle::from_section((player_skill_slot_idx + 1).to_string(), text_style.clone())); });
+                    }
+                });
+            }
+        });
+    });
+}
+
+fn spawn_timeline_controls(panel: &mut ChildBuilder, asset_server: &AssetServer) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, width: Val::Px(420.0), row_gap: Val::Px(6.0), ..default() }, ..default() }).with_children(|column| {
+        column.spawn(TextBundle::from_section("TIME & EVENTS", TextStyle { font: font.clone(), font_size: 16.0, color: Color::GOLD })
+            .with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default() }));
+        column.spawn((TextBundle::from_section("", TextStyle { font: font.clone(), font_size: 13.0, color: DEBUG_TEXT_COLOR }), DebugTimelineText));
+        for action in TimelineAction::ALL {
+            column.spawn((
+                ButtonBundle { style: Style { height: Val::Px(28.0), padding: UiRect::horizontal(Val::Px(8.0)), align_items: AlignItems::Center, ..default() }, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default() },
+                action,
+            )).with_children(|button| { button.spawn(TextBundle::from_section(action.label(), TextStyle { font: font.clone(), font_size: 13.0, color: DEBUG_TEXT_COLOR })); });
+        }
+    });
+}
+
+fn balance_slider_label(field: BalanceField, value: f32) -> String { format!("{}: {:.2}", field.label(), value) }
+
+// Click or drag along a bar to set the value anywhere in the field's range
+fn spawn_balance_sliders(sections_container: &mut ChildBuilder, font: Handle<Font>, balance: &BalanceConfig) {
+    sections_container.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|panel| {
+        panel.spawn(TextBundle::from_section("BALANCE (Live)", TextStyle { font: font.clone(), font_size: 16.0, color: DEBUG_SLIDER_FILL_COLOR }).with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default() }));
+        for field in BalanceField::ALL {
+            let value = balance.get(field);
+            panel.spawn((
+                TextBundle::from_section(balance_slider_label(field, value), TextStyle { font: font.clone(), font_size: 11.0, color: DEBUG_TEXT_COLOR }).with_style(Style { margin: UiRect::top(Val::Px(6.0)), ..default() }),
+                DebugBalanceValueText(field),
+            ));
+            panel.spawn((
+                ButtonBundle { style: Style { width: Val::Percent(100.0), height: DEBUG_SLIDER_HEIGHT, margin: UiRect::top(DEBUG_BUTTON_MARGIN), ..default() }, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default() },
+                RelativeCursorPosition::default(),
+                DebugBalanceSlider(field),
+                Name::new(format!("DbgBalance:{:?}", field)),
+            )).with_children(|track| {
+                track.spawn((
+                    NodeBundle { style: Style { width: Val::Percent(field.fraction(value) * 100.0), height: Val::Percent(100.0), ..default() }, background_color: DEBUG_SLIDER_FILL_COLOR.into(), ..default() },
+                    DebugBalanceSliderFill(field),
+                ));
+            });
+        }
+        panel.spawn(TextBundle::from_section(format!("F5 in game reloads {}", BALANCE_CONFIG_PATH), TextStyle { font, font_size: 10.0, color: Color::GRAY }).with_style(Style { margin: UiRect::top(Val::Px(10.0)), ..default() }));
+    });
+}
+
+// Interaction stays Pressed while the button is held, so this follows the cursor for as long as the drag lasts
+fn debug_balance_slider_system(slider_query: Query<(&Interaction, &RelativeCursorPosition, &DebugBalanceSlider)>, mut balance: ResMut<BalanceConfig>) {
+    for (interaction, cursor, slider) in slider_query.iter() {
+        if *interaction != Interaction::Pressed { continue; }
+        let Some(position) = cursor.normalized else { continue; };
+        let value = slider.0.value_at(position.x);
+        if balance.get(slider.0) != value { balance.set(slider.0, value); }
+    }
+}
+
+fn update_debug_balance_sliders(
+    balance: Res<BalanceConfig>,
+    mut fill_query: Query<(&DebugBalanceSliderFill, &mut Style)>,
+    mut text_query: Query<(&DebugBalanceValueText, &mut Text)>,
+) {
+    if !balance.is_changed() { return; }
+    for (fill, mut style) in fill_query.iter_mut() { style.width = Val::Percent(fill.0.fraction(balance.get(fill.0)) * 100.0); }
+    for (label, mut text) in text_query.iter_mut() { text.sections[0].value = balance_slider_label(label.0, balance.get(label.0)); }
+}
+
+// The state is frozen while the menu is open, so the list is a snapshot of the moment it was built
+fn spawn_inspector(panel: &mut ChildBuilder, filter: InspectorFilter, asset_server: &AssetServer, inspector: &InspectorQueries) {
+    let font: Handle<Font> = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text = |value: String, font_size: f32, color: Color| TextBundle::from_section(value, TextStyle { font: font.clone(), font_size, color });
+    panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_basis: Val::Percent(DEBUG_COLUMN_WIDTH_PERCENT), row_gap: Val::Px(6.0), margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|filters| {
+        filters.spawn(text("SHOW".to_string(), 16.0, DEBUG_ACTIVE_TAB_COLOR).with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), align_self: AlignSelf::Center, ..default() }));
+        for option in InspectorFilter::ALL {
+            let background = if option == filter { DEBUG_BUTTON_PRESSED_BG_COLOR } else { DEBUG_BUTTON_BG_COLOR };
+            filters.spawn((
+                ButtonBundle { style: Style { height: DEBUG_BUTTON_HEIGHT, padding: UiRect::horizontal(Val::Px(5.0)), align_items: AlignItems::Center, border: UiRect::left(Val::Px(3.0)), ..default() }, background_color: background.into(), border_color: BorderColor(if option == filter { DEBUG_ACTIVE_TAB_COLOR } else { Color::NONE }), ..default() },
+                option,
+            )).with_children(|button| { button.spawn(text(option.title().to_string(), 12.0, DEBUG_TEXT_COLOR)); });
+        }
+        filters.spawn(text(format!("Nearest {} to the survivor; - and + edit the live entity", INSPECTOR_MAX_ROWS), 10.0, Color::GRAY).with_style(Style { margin: UiRect::top(Val::Px(10.0)), ..default() }));
+    });
+    panel.spawn((NodeBundle { style: Style { overflow: Overflow { y: OverflowAxis::Clip, ..default() }, flex_grow: 1.0, margin: UiRect::horizontal(Val::Px(5.0)), ..default() }, background_color: DEBUG_SCROLL_AREA_BG_COLOR.into(), ..default() }, DebugMenuScrollView, ScrollOffset(0.0), Name::new("InspectorScroll"))).with_children(|scroll| {
+        scroll.spawn((NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Percent(100.0), top: Val::Px(0.0), left: Val::Px(0.0), flex_direction: FlexDirection::Column, ..default() }, ..default() }, DebugMenuScrollableContent, Name::new("InspectorList"))).with_children(|list| {
+            let rows = inspector.rows(filter);
+            if rows.is_empty() { list.spawn(text(format!("No {} alive.", filter.title().to_lowercase()), 11.0, Color::GRAY)); }
+            for (entity, label) in rows {
+                list.spawn(NodeBundle { style: Style { height: DEBUG_BUTTON_HEIGHT, margin: UiRect::bottom(DEBUG_BUTTON_MARGIN), column_gap: Val::Px(6.0), align_items: AlignItems::Center, padding: UiRect::horizontal(Val::Px(5.0)), ..default() }, ..default() }).with_children(|row| {
+                    row.spawn(text(label, 11.0, DEBUG_TEXT_COLOR).with_style(Style { width: Val::Px(220.0), ..default() }));
+                    for field in InspectorField::ALL.into_iter().filter(|field| inspector.read(entity, *field).is_some()) {
+                        row.spawn(text(format!("{}:", field.label()), 11.0, Color::GRAY));
+                        row.spawn((text(String::new(), 11.0, Color::WHITE).with_style(Style { width: Val::Px(44.0), ..default() }), DebugInspectorValue { entity, field }));
+                        for (symbol, direction) in [("-", -1.0), ("+", 1.0)] {
+                            row.spawn((
+                                ButtonBundle { style: Style { width: Val::Px(18.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() }, background_color: DEBUG_BUTTON_BG_COLOR.into(), ..default() },
+                                DebugInspectorEdit { entity, field, direction },
+                            )).with_children(|button| { button.spawn(text(symbol.to_string(), 12.0, DEBUG_TEXT_COLOR)); });
+                        }
+                    }
+                });
+            }
         });
     });
 }
 
+fn rebuild_debug_tab_panel(commands: &mut Commands, panel_query: &Query<Entity, With<DebugTabPanel>>, tab: DebugTab, filter: InspectorFilter, asset_server: &AssetServer, grant_sources: &DebugGrantSources, inspector: &InspectorQueries) {
+    for panel in panel_query.iter() {
+        commands.entity(panel).despawn_descendants().with_children(|panel| spawn_debug_tab_contents(panel, tab, filter, asset_server, grant_sources, inspector));
+    }
+}
+
+fn handle_debug_tab_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    interaction_query: Query<(&Interaction, &DebugTab), (Changed<Interaction>, With<Button>)>,
+    grant_sources: DebugGrantSources,
+    inspector: InspectorQueries,
+    mut tab: ResMut<DebugTab>,
+    filter: Res<InspectorFilter>,
+    panel_query: Query<Entity, With<DebugTabPanel>>,
+) {
+    let Some(pressed) = interaction_query.iter().find(|(interaction, _)| **interaction == Interaction::Pressed).map(|(_, tab)| *tab) else { return; };
+    if *tab == pressed { return; }
+    *tab = pressed;
+    rebuild_debug_tab_panel(&mut commands, &panel_query, pressed, *filter, &asset_server, &grant_sources, &inspector);
+}
+
+fn handle_inspector_filter_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    interaction_query: Query<(&Interaction, &InspectorFilter), (Changed<Interaction>, With<Button>)>,
+    grant_sources: DebugGrantSources,
+    inspector: InspectorQueries,
+    tab: Res<DebugTab>,
+    mut filter: ResMut<InspectorFilter>,
+    panel_query: Query<Entity, With<DebugTabPanel>>,
+) {
+    let Some(pressed) = interaction_query.iter().find(|(interaction, _)| **interaction == Interaction::Pressed).map(|(_, filter)| *filter) else { return; };
+    if *filter == pressed { return; }
+    *filter = pressed;
+    rebuild_debug_tab_panel(&mut commands, &panel_query, *tab, pressed, &asset_server, &grant_sources, &inspector);
+}
+
+fn highlight_debug_tabs(tab: Res<DebugTab>, mut button_query: Query<(&DebugTab, &mut BorderColor)>) {
+    for (button_tab, mut border) in button_query.iter_mut() {
+        let color = if *button_tab == *tab { DEBUG_ACTIVE_TAB_COLOR } else { Color::NONE };
+        if border.0 != color { border.0 = color; }
+    }
+}
+
+fn debug_inspector_edit_system(
+    mut interaction_query: Query<(&Interaction, &DebugInspectorEdit, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    mut inspector: InspectorQueries,
+) {
+    for (interaction, edit, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); inspector.adjust(edit.entity, edit.field, edit.direction * edit.field.step()); }
+            Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); }
+            Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); }
+        }
+    }
+}
+
+// An entity that died from an edit shows a dash rather than its last value
+fn update_inspector_values(inspector: InspectorQueries, mut text_query: Query<(&DebugInspectorValue, &mut Text)>) {
+    for (value, mut text) in text_query.iter_mut() {
+        let shown = inspector.read(value.entity, value.field).map_or_else(|| "-".to_string(), |current| value.field.format(current));
+        if text.sections[0].value != shown { text.sections[0].value = shown; }
+    }
+}
+
+fn debug_grant_skill_button_system(
+    mut interaction_query: Query<(&Interaction, &DebugGrantSkillButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    mut grant_skill_event_writer: EventWriter<DebugGrantSkillEvent>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted)); grant_skill_event_writer.send(DebugGrantSkillEvent(button.0)); }
+            Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); }
+            Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); }
+        }
+    }
+}
+
+fn debug_equip_glyph_button_system(
+    mut interaction_query: Query<(&Interaction, &DebugEquipGlyphButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    mut equip_glyph_event_writer: EventWriter<DebugEquipGlyphEvent>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    for (interaction, button, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::SoulCollect)); equip_glyph_event_writer.send(DebugEquipGlyphEvent { player_skill_slot_idx: button.player_skill_slot_idx, glyph_id: button.glyph_id }); }
+            Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); }
+            Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); }
+        }
+    }
+}
+
+// Grants land a frame after the click, so the tab's texts follow the survivor rather than being rebuilt
+fn update_debug_skill_tab_texts(
+    player_query: Query<&Survivor, Changed<Survivor>>,
+    skill_library: Res<SkillLibrary>,
+    glyph_library: Res<GlyphLibrary>,
+    mut status_query: Query<(&DebugSkillStatusText, &mut Text)>,
+    mut loadout_query: Query<&mut Text, (With<DebugSkillLoadoutText>, Without<DebugSkillStatusText>)>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    for (status, mut text) in status_query.iter_mut() {
+        let shown = skill_grant_status(player, status.0);
+        if text.sections[0].value != shown { text.sections[0].value = shown; }
+    }
+    for mut text in loadout_query.iter_mut() { text.sections[0].value = skill_loadout_label(Some(player), &skill_library, &glyph_library); }
+}
+
+fn debug_timeline_button_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut interaction_query: Query<(&Interaction, &TimelineAction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+    mut timeline: RunTimeline,
+    mut target_cycle: ResMut<DebugCycleTarget>,
+    horror_stats: Res<HorrorStatsLibrary>,
+    mut game_rng: ResMut<GameRng>,
+    player_query: Query<&Transform, With<Survivor>>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    for (interaction, action, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); }
+            Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); continue; }
+            Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); continue; }
+        }
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted));
+        match action {
+            TimelineAction::PreviousCycle => target_cycle.0 = target_cycle.0.saturating_sub(1).max(1),
+            TimelineAction::NextCycle => target_cycle.0 += 1,
+            TimelineAction::JumpToCycle => timeline.jump_to_cycle(target_cycle.0),
+            TimelineAction::FastForward => timeline.fast_forward(DEBUG_FAST_FORWARD_SECS),
+            TimelineAction::ForceNextEvent => if let Some((event, _)) = next_scheduled_event(&timeline.upcoming()) { timeline.force(event); },
+            TimelineAction::SpawnElite => {
+                let Ok(player_transform) = player_query.get_single() else { continue; };
+                let candidates: Vec<HorrorType> = HorrorType::ALL.into_iter().filter(|horror_type| can_be_elite(*horror_type)).collect();
+                let Some(&horror_type) = candidates.choose(&mut game_rng.0) else { continue; };
+                let position = (player_transform.translation.truncate() + Vec2::Y * DEBUG_ELITE_DISTANCE).extend(0.5);
+                spawn_horror_type(&mut commands, &asset_server, &horror_stats, horror_type, position, cycle_stat_multiplier(timeline.game_state.cycle_number), true);
+                game_event_writer.send(GameEvent::EliteSpawned { horror_type, position });
+            }
+        }
+    }
+}
+
+fn update_debug_timeline_text(timeline: RunTimeline, target_cycle: Res<DebugCycleTarget>, mut text_query: Query<&mut Text, With<DebugTimelineText>>) {
+    for mut text in text_query.iter_mut() {
+        let shown = timeline.summary(target_cycle.0);
+        if text.sections[0].value != shown { text.sections[0].value = shown; }
+    }
+}
+
+fn handle_debug_grant_skill(mut events: EventReader<DebugGrantSkillEvent>, mut player_query: Query<&mut Survivor>, skill_library: Res<SkillLibrary>) {
+    let Ok(mut player) = player_query.get_single_mut() else { return; };
+    for event in events.read() {
+        let Some(skill_def) = skill_library.get_skill_definition(event.0) else { continue; };
+        grant_skill_directly(&mut player, event.0, skill_def.base_glyph_slots);
+    }
+}
+
+fn handle_debug_equip_glyph(mut events: EventReader<DebugEquipGlyphEvent>, mut player_query: Query<&mut Survivor>) {
+    let Ok(mut player) = player_query.get_single_mut() else { return; };
+    for event in events.read() { equip_glyph_directly(&mut player, event.player_skill_slot_idx, event.glyph_id); }
+}
+
+#[allow(clippy::possible_missing_else)]
 fn debug_menu_keyboard_scroll_system( keyboard_input: Res<ButtonInput<KeyCode>>, mut scroll_view_query: Query<(&mut ScrollOffset, &Node, &Children, &GlobalTransform), With<DebugMenuScrollView>>, mut content_query: Query<(&Node, &mut Style), With<DebugMenuScrollableContent>>, window_query: Query<&Window, With<bevy::window::PrimaryWindow>>, ) { let Ok(_primary_window) = window_query.get_single() else { return }; let _cursor_pos_option = _primary_window.cursor_position(); for (mut scroll_offset, scroll_view_node, scroll_view_children, _scroll_view_gtransform) in scroll_view_query.iter_mut() { let mut content_entity = None; for &child in scroll_view_children.iter() { if content_query.get(child).is_ok() { content_entity = Some(child); break; } } if let Some(content_e) = content_entity { if let Ok((content_node, mut content_style)) = content_query.get_mut(content_e) { let scroll_view_height = scroll_view_node.size().y; let content_height = content_node.size().y; let mut new_offset = scroll_offset.0; let mut scrolled = false; if keyboard_input.pressed(KeyCode::ArrowUp) { new_offset -= KEYBOARD_SCROLL_SPEED; scrolled = true; } if keyboard_input.pressed(KeyCode::ArrowDown) { new_offset += KEYBOARD_SCROLL_SPEED; scrolled = true; } if scrolled { let max_scroll = (content_height - scroll_view_height).max(0.0); new_offset = new_offset.clamp(0.0, max_scroll); if (scroll_offset.0 - new_offset).abs() > f32::EPSILON { scroll_offset.0 = new_offset; content_style.top = Val::Px(-new_offset); } } } } } }
-fn debug_menu_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugUpgradeButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut upgrade_chosen_event: EventWriter<UpgradeChosenEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_button_data, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent(SoundEffect::OmenAccepted)); upgrade_chosen_event.send(UpgradeChosenEvent(debug_button_data.0.clone())); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
-fn debug_item_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugItemButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut item_collected_event: EventWriter<ItemCollectedEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_item_button, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent(SoundEffect::OmenAccepted)); item_collected_event.send(ItemCollectedEvent(debug_item_button.0)); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
-fn debug_glyph_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugGlyphButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut grant_glyph_event_writer: EventWriter<DebugGrantGlyphEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_glyph_button, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent(SoundEffect::SoulCollect)); grant_glyph_event_writer.send(DebugGrantGlyphEvent(debug_glyph_button.0)); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
-fn debug_socket_glyph_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugSocketGlyphButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut socket_glyph_event_writer: EventWriter<DebugSocketGlyphEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, button_data, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent(SoundEffect::OmenAccepted)); socket_glyph_event_writer.send(DebugSocketGlyphEvent { player_skill_slot_idx: button_data.player_skill_slot_idx, glyph_slot_idx: button_data.glyph_slot_idx, glyph_id_to_socket: button_data.glyph_id_to_socket, }); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
+fn debug_menu_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugUpgradeButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut upgrade_chosen_event: EventWriter<UpgradeChosenEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_button_data, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted)); upgrade_chosen_event.send(UpgradeChosenEvent(debug_button_data.0.clone(), PlayerSlot::default())); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
+fn debug_item_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugItemButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut item_collected_event: EventWriter<ItemCollectedEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_item_button, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted)); item_collected_event.send(ItemCollectedEvent(debug_item_button.0)); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
+fn debug_glyph_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugGlyphButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut grant_glyph_event_writer: EventWriter<DebugGrantGlyphEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, debug_glyph_button, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::SoulCollect)); grant_glyph_event_writer.send(DebugGrantGlyphEvent(debug_glyph_button.0)); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
+fn debug_socket_glyph_button_interaction_system( mut interaction_query: Query<(&Interaction, &DebugSocketGlyphButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, mut socket_glyph_event_writer: EventWriter<DebugSocketGlyphEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, button_data, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { *bg_color = DEBUG_BUTTON_PRESSED_BG_COLOR.into(); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted)); socket_glyph_event_writer.send(DebugSocketGlyphEvent { player_skill_slot_idx: button_data.player_skill_slot_idx, glyph_slot_idx: button_data.glyph_slot_idx, glyph_id_to_socket: button_data.glyph_id_to_socket, }); } Interaction::Hovered => { *bg_color = DEBUG_BUTTON_HOVER_BG_COLOR.into(); } Interaction::None => { *bg_color = DEBUG_BUTTON_BG_COLOR.into(); } } } }
 fn handle_debug_grant_glyph( mut events: EventReader<DebugGrantGlyphEvent>, mut player_query: Query<&mut Survivor>,) { if let Ok(mut player) = player_query.get_single_mut() { for event in events.read() { if !player.collected_glyphs.contains(&event.0) { player.collected_glyphs.push(event.0); } } } } // Changed
 fn handle_debug_socket_glyph( mut events: EventReader<DebugSocketGlyphEvent>, mut player_query: Query<&mut Survivor>,) { if let Ok(mut player) = player_query.get_single_mut() { for event in events.read() { if let Some(collected_glyph_index) = player.collected_glyphs.iter().position(|&id| id == event.glyph_id_to_socket) { if let Some(skill_instance) = player.equipped_skills.get_mut(event.player_skill_slot_idx) { if event.glyph_slot_idx < skill_instance.equipped_glyphs.len() && skill_instance.equipped_glyphs[event.glyph_slot_idx].is_none() { skill_instance.equipped_glyphs[event.glyph_slot_idx] = Some(event.glyph_id_to_socket); player.collected_glyphs.remove(collected_glyph_index); } } } } } } // Changed
-fn despawn_debug_menu_ui(mut commands: Commands, query: Query<Entity, With<DebugMenuUIRoot>>) { for entity in query.iter() { commands.entity(entity).despawn_recursive(); } }
\ No newline at end of file
+// Just opening the menu is enough; nothing in it is checked for whether it was actually used
+fn flag_run_as_cheated(mut run_stats: ResMut<RunStats>) { run_stats.cheats_used = true; }
+fn despawn_debug_menu_ui(mut commands: Commands, query: Query<Entity, With<DebugMenuUIRoot>>) { for entity in query.iter() { commands.entity(entity).despawn_recursive(); } }
+#[derive(Component)] struct BuildImportStatusText;
+
+// Glyph sockets are restored once the replayed upgrades have granted their skills
+#[derive(Resource, Default)]
+struct PendingBuildGlyphs {
+    build: Option<BuildCode>,
+    frames_waited: u32,
+}
+
+// Random relic cards would roll a second relic on top of the recorded ones, and glyph slots come from the recorded sockets
+pub fn upgrades_to_replay(build: &BuildCode, pool: &[UpgradeCard]) -> (Vec<UpgradeCard>, Vec<u32>) {
+    let mut replayed = Vec::new();
+    let mut missing = Vec::new();
+    for upgrade_id in build.upgrades.iter() {
+        match pool.iter().find(|card| card.id.0 == *upgrade_id) {
+            Some(card) if matches!(card.upgrade_type, UpgradeType::GrantRandomRelic | UpgradeType::AddGlyphSlot) => {}
+            Some(card) => replayed.push(card.clone()),
+            None => missing.push(*upgrade_id),
+        }
+    }
+    (replayed, missing)
+}
+
+fn setup_build_import_hint(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle {
+            z_index: ZIndex::Global(51),
+            ..TextBundle::from_section(
+                format!("Press I to import the build on the clipboard (or in {})", BUILD_EXPORT_PATH),
+                TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: DEBUG_TEXT_COLOR },
+            ).with_style(Style { position_type: PositionType::Absolute, bottom: Val::Px(8.0), left: Val::Px(12.0), ..default() })
+        },
+        DebugMenuUIRoot,
+        BuildImportStatusText,
+    ));
+}
+
+fn debug_import_build_key_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut import_writer: EventWriter<DebugImportBuildEvent>,
+    mut status_query: Query<&mut Text, With<BuildImportStatusText>>,
+    storage: Res<GameStorage>,
+    mut clipboard: NonSendMut<SystemClipboard>,
+) {
+    if !keyboard_input.just_pressed(IMPORT_BUILD_KEY) { return; }
+    let status = match BuildCode::from_paste(clipboard.paste(), storage.0.as_ref()) {
+        Ok(build) => { import_writer.send(DebugImportBuildEvent(build)); "Build imported".to_string() }
+        Err(error) => format!("Could not import build: {}", error),
+    };
+    if let Ok(mut text) = status_query.get_single_mut() {
+        if let Some(section) = text.sections.get_mut(0) { section.value = status; }
+    }
+}
+
+fn handle_debug_import_build(
+    mut events: EventReader<DebugImportBuildEvent>,
+    upgrade_pool: Res<UpgradePool>,
+    mut upgrade_chosen_event: EventWriter<UpgradeChosenEvent>,
+    mut item_collected_event: EventWriter<ItemCollectedEvent>,
+    mut pending_glyphs: ResMut<PendingBuildGlyphs>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    for event in events.read() {
+        run_stats.cheats_used = true;
+        let (replayed, missing) = upgrades_to_replay(&event.0, &upgrade_pool.available_upgrades);
+        if !missing.is_empty() { warn!("Imported build references unknown upgrades {:?}", missing); }
+        for card in replayed { upgrade_chosen_event.send(UpgradeChosenEvent(card, PlayerSlot::default())); }
+        for item_id in event.0.items.iter() { item_collected_event.send(ItemCollectedEvent(ItemId(*item_id))); }
+        *pending_glyphs = PendingBuildGlyphs { build: Some(event.0.clone()), frames_waited: 0 };
+    }
+}
+
+fn restore_imported_glyphs(mut pending_glyphs: ResMut<PendingBuildGlyphs>, mut player_query: Query<&mut Survivor>, skill_library: Res<SkillLibrary>) {
+    let Some(build) = pending_glyphs.build.as_ref() else { return; };
+    let Ok(mut player) = player_query.get_single_mut() else { return; };
+    let all_skills_granted = build.skills.iter().all(|loadout| player.equipped_skills.iter().any(|skill| skill.definition_id.0 == loadout.skill_id));
+    if !all_skills_granted && pending_glyphs.frames_waited < IMPORT_SETTLE_FRAMES {
+        pending_glyphs.frames_waited += 1;
+        return;
+    }
+
+    for loadout in build.skills.iter() {
+        let skill_id = SkillId(loadout.skill_id);
+        if !player.equipped_skills.iter().any(|skill| skill.definition_id == skill_id) && player.equipped_skills.len() < MAX_SKILL_SLOTS {
+            if let Some(skill_def) = skill_library.get_skill_definition(skill_id) { player.equipped_skills.push(ActiveSkillInstance::new(skill_id, skill_def.base_glyph_slots)); }
+        }
+        let Some(skill) = player.equipped_skills.iter_mut().find(|skill| skill.definition_id == skill_id) else { continue; };
+        while skill.equipped_glyphs.len() < loadout.glyphs.len() && skill.add_glyph_slot() {}
+        for (socket, glyph) in skill.equipped_glyphs.iter_mut().zip(loadout.glyphs.iter()) { *socket = glyph.map(GlyphId); }
+    }
+    for glyph_id in build.glyphs.iter() {
+        if !player.collected_glyphs.contains(&GlyphId(*glyph_id)) { player.collected_glyphs.push(GlyphId(*glyph_id)); }
+    }
+    *pending_glyphs = PendingBuildGlyphs::default();
+}
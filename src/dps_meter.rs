@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::{
+    game::AppState,
+    game_events::{GameEvent, DamageOrigin},
+    skills::SkillLibrary,
+};
+
+pub const DPS_WINDOW_SECS: f32 = 10.0;
+pub const DPS_METER_TOGGLE_KEY: KeyCode = KeyCode::KeyV;
+
+pub struct DpsMeterPlugin;
+
+impl Plugin for DpsMeterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DpsMeter>()
+            .init_resource::<DpsMeterDisplay>()
+            .add_systems(Startup, setup_dps_meter_ui)
+            .add_systems(OnExit(AppState::MainMenu), reset_dps_meter)
+            // Hits can land on the frame play pauses for a level up, so they are recorded in any state
+            .add_systems(Update, record_damage_system)
+            .add_systems(Update, (toggle_dps_meter, update_dps_meter_text).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::InGame), show_dps_meter)
+            .add_systems(OnExit(AppState::InGame), hide_dps_meter);
+    }
+}
+
+// Every hit from the last DPS_WINDOW_SECS, oldest first
+#[derive(Resource, Debug, Default)]
+pub struct DpsMeter {
+    hits: VecDeque<(f32, DamageOrigin, i32)>,
+    run_started_at: f32,
+}
+
+impl DpsMeter {
+    pub fn start(&mut self, now: f32) {
+        self.hits.clear();
+        self.run_started_at = now;
+    }
+
+    pub fn record(&mut self, now: f32, origin: DamageOrigin, amount: i32) {
+        self.hits.push_back((now, origin, amount.max(0)));
+        while self.hits.front().is_some_and(|(hit_at, _, _)| *hit_at <= now - DPS_WINDOW_SECS) { self.hits.pop_front(); }
+    }
+
+    // Early in a run the window is only as long as the run, so the first seconds aren't diluted by time that never happened
+    fn window_secs(&self, now: f32) -> f32 {
+        (now - self.run_started_at).clamp(1.0, DPS_WINDOW_SECS)
+    }
+
+    // Damage per second for each source that hit within the window, highest first
+    pub fn per_source(&self, now: f32) -> Vec<(DamageOrigin, f32)> {
+        let mut totals: Vec<(DamageOrigin, i32)> = Vec::new();
+        for (_, origin, amount) in self.hits.iter().filter(|(hit_at, _, _)| *hit_at > now - DPS_WINDOW_SECS) {
+            match totals.iter_mut().find(|(total_origin, _)| total_origin == origin) {
+                Some((_, total)) => *total += amount,
+                None => totals.push((*origin, *amount)),
+            }
+        }
+        let window = self.window_secs(now);
+        let mut rates: Vec<(DamageOrigin, f32)> = totals.into_iter().map(|(origin, total)| (origin, total as f32 / window)).collect();
+        rates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        rates
+    }
+
+    pub fn total(&self, now: f32) -> f32 {
+        self.per_source(now).iter().map(|(_, rate)| rate).sum()
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct DpsMeterDisplay {
+    pub expanded: bool,
+}
+
+pub fn origin_label(origin: DamageOrigin, skill_library: &SkillLibrary) -> String {
+    match origin {
+        DamageOrigin::IchorBlast => "Ichor Blast".to_string(),
+        DamageOrigin::Skill(skill_id) => skill_library.get_skill_definition(skill_id).map_or_else(|| format!("Skill {}", skill_id.0), |definition| definition.name.clone()),
+        DamageOrigin::Weapon(weapon) => weapon.name().to_string(),
+        DamageOrigin::Summon(kind) => format!("{} (summon)", kind.name()),
+        DamageOrigin::Relic => "Relics".to_string(),
+        DamageOrigin::Consumable(kind) => kind.name().to_string(),
+    }
+}
+
+#[derive(Component)]
+struct DpsMeterUI;
+
+#[derive(Component)]
+struct DpsMeterText;
+
+fn setup_dps_meter_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style { display: Display::None, position_type: PositionType::Absolute, bottom: Val::Px(50.0), right: Val::Px(15.0), padding: UiRect::all(Val::Px(6.0)), ..default() },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+            z_index: ZIndex::Global(2),
+            ..default()
+        },
+        DpsMeterUI,
+    )).with_children(|parent| {
+        parent.spawn((TextBundle::from_section("", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::rgb(1.0, 0.85, 0.7) }), DpsMeterText));
+    });
+}
+
+fn reset_dps_meter(time: Res<Time>, mut meter: ResMut<DpsMeter>) {
+    meter.start(time.elapsed_seconds());
+}
+
+fn record_damage_system(time: Res<Time>, mut game_events: EventReader<GameEvent>, mut meter: ResMut<DpsMeter>) {
+    let now = time.elapsed_seconds();
+    for event in game_events.read() {
+        if let GameEvent::DamageDealt { amount, origin, .. } = event { meter.record(now, *origin, *amount); }
+    }
+}
+
+fn toggle_dps_meter(keyboard_input: Res<ButtonInput<KeyCode>>, mut display: ResMut<DpsMeterDisplay>) {
+    if keyboard_input.just_pressed(DPS_METER_TOGGLE_KEY) { display.expanded = !display.expanded; }
+}
+
+// The headline always reads the meter's total, so collapsing the per-source list never changes it
+pub fn dps_meter_lines(meter: &DpsMeter, now: f32, expanded: bool, skill_library: &SkillLibrary) -> Vec<String> {
+    let total = meter.total(now);
+    let mut lines = vec![format!("DPS {:.0}  [{}]", total, if expanded { "V: less" } else { "V: more" })];
+    if expanded {
+        for (origin, rate) in meter.per_source(now).iter() {
+            let share = if total > 0.0 { rate / total * 100.0 } else { 0.0 };
+            lines.push(format!("{}  {:.1}  ({:.0}%)", origin_label(*origin, skill_library), rate, share));
+        }
+    }
+    lines
+}
+
+fn update_dps_meter_text(time: Res<Time>, meter: Res<DpsMeter>, display: Res<DpsMeterDisplay>, skill_library: Res<SkillLibrary>, mut text_query: Query<&mut Text, With<DpsMeterText>>) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    text.sections[0].value = dps_meter_lines(&meter, time.elapsed_seconds(), display.expanded, &skill_library).join("\n");
+}
+
+fn show_dps_meter(mut ui_query: Query<&mut Style, With<DpsMeterUI>>) {
+    for mut style in ui_query.iter_mut() { style.display = Display::Flex; }
+}
+
+fn hide_dps_meter(mut ui_query: Query<&mut Style, With<DpsMeterUI>>) {
+    for mut style in ui_query.iter_mut() { style.display = Display::None; }
+}
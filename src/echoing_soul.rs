@@ -2,15 +2,26 @@ use bevy::prelude::*;
 use crate::{
     survivor::{Survivor, SURVIVOR_SIZE}, // Updated
     components::Velocity,
-    game::AppState,
+    game::{AppState, PendingLevelUps},
     audio::{PlaySoundEvent, SoundEffect},
+    game_events::GameEvent,
+    horror::SpawnThrottle,
+    taunt::{Taunting, pickup_radius_multiplier},
+    coop::shared_experience,
 };
 
 pub const ECHOING_SOUL_SIZE: Vec2 = Vec2::new(10.0, 10.0);
 pub const ECHOING_SOUL_VALUE: u32 = 25; 
-const SOUL_GRAVITATE_SPEED: f32 = 300.0;
+const SOUL_PULL_START_SPEED: f32 = 60.0;
+const SOUL_PULL_MAX_SPEED: f32 = 650.0;
+const SOUL_PULL_RAMP_SECS: f32 = 0.6;
+// Vacuumed souls start this far off the direct line and straighten out as they close in
+const SOUL_SPIRAL_MAX_ANGLE: f32 = 1.2;
+const SOUL_SPIRAL_FULL_DISTANCE: f32 = 600.0;
 // Updated to use SURVIVOR_SIZE
 const SOUL_PICKUP_RADIUS_COLLISION: f32 = SURVIVOR_SIZE.x / 2.0 + ECHOING_SOUL_SIZE.x / 2.0 - 5.0; 
+const THROTTLED_SOUL_MERGE_RADIUS: f32 = 120.0;
+const THROTTLED_SOUL_MERGE_INTERVAL_SECS: f32 = 0.5;
 
 
 pub struct EchoingSoulPlugin; // Renamed
@@ -18,7 +29,10 @@ pub struct EchoingSoulPlugin; // Renamed
 impl Plugin for EchoingSoulPlugin { // Renamed
     fn build(&self, app: &mut App) {
         app
+            .add_event::<SoulVacuumEvent>()
             .add_systems(Update, (
+                throttled_soul_merge_system,
+                soul_vacuum_system,
                 echoing_soul_gravitation_and_movement_system,
                 echoing_soul_collection_system,
             ).chain().run_if(in_state(AppState::InGame)));
@@ -30,6 +44,34 @@ pub struct EchoingSoul {
     pub value: u32,
 }
 
+// Pull phase: once a soul is caught it keeps accelerating toward the survivor until collected
+#[derive(Component, Debug, Default)]
+pub struct MovingToSurvivor {
+    pub elapsed_secs: f32,
+    pub spiral: bool,
+}
+
+// Sweeps every soul on the field into a spiralling pull
+#[derive(Event)]
+pub struct SoulVacuumEvent;
+
+// Ease-in so the pull starts gently and snaps shut at the end
+pub fn soul_pull_speed(elapsed_secs: f32) -> f32 {
+    let progress = (elapsed_secs / SOUL_PULL_RAMP_SECS).clamp(0.0, 1.0);
+    SOUL_PULL_START_SPEED + (SOUL_PULL_MAX_SPEED - SOUL_PULL_START_SPEED) * progress * progress
+}
+
+pub fn soul_pull_velocity(to_survivor: Vec2, pull: &MovingToSurvivor) -> Vec2 {
+    let direction = to_survivor.normalize_or_zero();
+    let direction = if pull.spiral {
+        let swirl = SOUL_SPIRAL_MAX_ANGLE * (to_survivor.length() / SOUL_SPIRAL_FULL_DISTANCE).min(1.0);
+        Vec2::from_angle(swirl).rotate(direction)
+    } else {
+        direction
+    };
+    direction * soul_pull_speed(pull.elapsed_secs)
+}
+
 pub fn spawn_echoing_soul(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
@@ -52,33 +94,81 @@ pub fn spawn_echoing_soul(
     ));
 }
 
+// While spawning is throttled, nearby souls fold into one to keep the entity count down
+fn throttled_soul_merge_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    spawn_throttle: Res<SpawnThrottle>,
+    mut merge_timer: Local<Timer>,
+    mut soul_query: Query<(Entity, &Transform, &mut EchoingSoul)>,
+) {
+    if !spawn_throttle.is_active() { return; }
+    if merge_timer.duration().is_zero() { *merge_timer = Timer::from_seconds(THROTTLED_SOUL_MERGE_INTERVAL_SECS, TimerMode::Repeating); }
+    merge_timer.tick(time.delta());
+    if !merge_timer.just_finished() { return; }
+
+    let merge_radius_sq = THROTTLED_SOUL_MERGE_RADIUS.powi(2);
+    let mut absorbing_souls: Vec<(Entity, Vec2, u32)> = Vec::new();
+    for (soul_entity, soul_transform, soul) in soul_query.iter() {
+        let soul_pos = soul_transform.translation.truncate();
+        if let Some(absorber) = absorbing_souls.iter_mut().find(|(_, absorber_pos, _)| absorber_pos.distance_squared(soul_pos) < merge_radius_sq) {
+            absorber.2 += soul.value;
+            commands.entity(soul_entity).despawn();
+        } else {
+            absorbing_souls.push((soul_entity, soul_pos, soul.value));
+        }
+    }
+    for (soul_entity, _, merged_value) in absorbing_souls {
+        if let Ok((_, _, mut soul)) = soul_query.get_mut(soul_entity) { soul.value = merged_value; }
+    }
+}
+
+fn soul_vacuum_system(
+    mut commands: Commands,
+    mut vacuum_events: EventReader<SoulVacuumEvent>,
+    soul_query: Query<Entity, With<EchoingSoul>>,
+) {
+    if vacuum_events.read().count() == 0 { return; }
+    for soul_entity in soul_query.iter() {
+        commands.entity(soul_entity).insert(MovingToSurvivor { elapsed_secs: 0.0, spiral: true });
+    }
+}
+
 fn echoing_soul_gravitation_and_movement_system(
-    mut soul_query: Query<(&mut Transform, &mut Velocity), With<EchoingSoul>>,
-    player_query: Query<(&Transform, &Survivor), (With<Survivor>, Without<EchoingSoul>)>,
+    mut commands: Commands,
+    mut soul_query: Query<(Entity, &mut Transform, &mut Velocity, Option<&mut MovingToSurvivor>), With<EchoingSoul>>,
+    player_query: Query<(&Transform, &Survivor, Option<&Taunting>), (With<Survivor>, Without<EchoingSoul>)>,
     time: Res<Time>,
 ) {
-    if let Ok((player_transform, player_stats)) = player_query.get_single() {
+    if let Ok((player_transform, player_stats, taunting)) = player_query.get_single() {
         let player_pos = player_transform.translation.truncate();
-        let effective_gravitate_radius = player_stats.get_effective_pickup_radius();
+        let effective_gravitate_radius = player_stats.get_effective_pickup_radius() * pickup_radius_multiplier(taunting);
 
-        for (mut soul_transform, mut soul_velocity) in soul_query.iter_mut() {
+        for (soul_entity, mut soul_transform, mut soul_velocity, moving_to_survivor) in soul_query.iter_mut() {
             let soul_pos = soul_transform.translation.truncate();
-            let distance_to_player = player_pos.distance(soul_pos);
+            let to_player = player_pos - soul_pos;
 
-            if distance_to_player < effective_gravitate_radius {
-                let direction_to_player = (player_pos - soul_pos).normalize_or_zero();
-                soul_velocity.0 = direction_to_player * SOUL_GRAVITATE_SPEED;
-            } else {
-                 if soul_velocity.0 != Vec2::ZERO && distance_to_player > effective_gravitate_radius + 20.0 {
-                     soul_velocity.0 = Vec2::ZERO;
-                 }
+            match moving_to_survivor {
+                Some(mut pull) => {
+                    pull.elapsed_secs += time.delta_seconds();
+                    soul_velocity.0 = soul_pull_velocity(to_player, &pull);
+                }
+                None => {
+                    if to_player.length() < effective_gravitate_radius {
+                        commands.entity(soul_entity).insert(MovingToSurvivor::default());
+                    }
+                    soul_velocity.0 = Vec2::ZERO;
+                }
             }
-            
-            soul_transform.translation.x += soul_velocity.0.x * time.delta_seconds();
-            soul_transform.translation.y += soul_velocity.0.y * time.delta_seconds();
+
+            // Never step past the survivor, or fast souls would orbit instead of landing
+            let step = soul_velocity.0 * time.delta_seconds();
+            let step = if step.length_squared() > to_player.length_squared() { to_player } else { step };
+            soul_transform.translation.x += step.x;
+            soul_transform.translation.y += step.y;
         }
     } else {
-        for (mut soul_transform, mut soul_velocity) in soul_query.iter_mut() {
+        for (_, mut soul_transform, mut soul_velocity, _) in soul_query.iter_mut() {
             if soul_velocity.0 != Vec2::ZERO {
                  soul_velocity.0 *= 0.9; 
                  if soul_velocity.0.length_squared() < 0.1 {
@@ -91,22 +181,32 @@ fn echoing_soul_gravitation_and_movement_system(
     }
 }
 
+// Local co-op shares one pool: whoever touches a soul, every survivor banks it, so the party levels together
+// and each level owes every player one upgrade choice
 fn echoing_soul_collection_system(
     mut commands: Commands,
     soul_query: Query<(Entity, &Transform, &EchoingSoul)>,
     mut player_query: Query<(&Transform, &mut Survivor), With<Survivor>>,
     mut next_app_state: ResMut<NextState<AppState>>,
+    mut pending_level_ups: ResMut<PendingLevelUps>,
     mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
 ) {
-    if let Ok((player_transform, mut player_stats)) = player_query.get_single_mut() {
-        let player_pos = player_transform.translation.truncate();
-        for (soul_entity, soul_transform, soul_data) in soul_query.iter() {
-            let soul_pos = soul_transform.translation.truncate();
-            if player_pos.distance(soul_pos) < SOUL_PICKUP_RADIUS_COLLISION { 
-                commands.entity(soul_entity).despawn();
-                sound_event_writer.send(PlaySoundEvent(SoundEffect::SoulCollect));
-                player_stats.add_experience(soul_data.value, &mut next_app_state, &mut sound_event_writer);
-            }
+    let player_positions: Vec<Vec2> = player_query.iter().map(|(player_transform, _)| player_transform.translation.truncate()).collect();
+    for (soul_entity, soul_transform, soul_data) in soul_query.iter() {
+        let soul_pos = soul_transform.translation.truncate();
+        if !player_positions.iter().any(|player_pos| player_pos.distance(soul_pos) < SOUL_PICKUP_RADIUS_COLLISION) { continue; }
+        commands.entity(soul_entity).despawn();
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::SoulCollect));
+        let amount = shared_experience(soul_data.value, player_query.iter().map(|(_, player_stats)| player_stats.xp_gain_multiplier));
+        let (mut levels_gained, mut new_level) = (0, 0);
+        for (_, mut player_stats) in player_query.iter_mut() {
+            levels_gained = levels_gained.max(player_stats.bank_experience(amount));
+            new_level = new_level.max(player_stats.level);
         }
+        if levels_gained == 0 { continue; }
+        pending_level_ups.0 += levels_gained;
+        game_event_writer.send(GameEvent::LevelUp { new_level, levels_gained });
+        next_app_state.set(AppState::LevelUp);
     }
 }
@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::{
+    survivor::Survivor,
+    horror::{Horror, is_valid_spawn_point},
+    components::Health,
+    game::{AppState, GameConfig, GameRng},
+    game_events::GameEvent,
+    aggro::Threat,
+    gold::spawn_gold_coin,
+    score::{ScoreAwardEvent, ScoreSource},
+    audio::{PlaySoundEvent, SoundEffect},
+};
+
+pub const ESCORT_INTERVAL_SECS: f32 = 240.0;
+pub const ESCORT_DURATION_SECS: f32 = 90.0;
+pub const CARAVAN_MAX_HEALTH: i32 = 400;
+pub const CARAVAN_SPEED: f32 = 35.0;
+// Horrors weigh the caravan a little below the survivor, so only the ones nearer to it peel off
+pub const CARAVAN_THREAT_WEIGHT: f32 = 0.75;
+pub const ESCORT_SCORE_BONUS: u32 = 1500;
+pub const ESCORT_GOLD_COINS: u32 = 12;
+pub const ESCORT_GOLD_COIN_VALUE: u32 = 5;
+const CARAVAN_SIZE: Vec2 = Vec2::new(90.0, 60.0);
+const CARAVAN_CONTACT_TICK_SECS: f32 = 0.5;
+// Close enough to be noticed, far enough that walking over to it is a choice
+const CARAVAN_MIN_DISTANCE: f32 = 350.0;
+const CARAVAN_MAX_DISTANCE: f32 = 550.0;
+const CARAVAN_PLACEMENT_ATTEMPTS: usize = 8;
+const CARAVAN_COLOR: Color = Color::rgb(0.95, 0.8, 0.45);
+const ESCORT_HUD_BAR_WIDTH: f32 = 240.0;
+
+pub struct EscortPlugin;
+
+impl Plugin for EscortPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EscortTimer>()
+            .add_systems(OnExit(AppState::MainMenu), reset_escort)
+            .add_systems(OnEnter(AppState::InGame), setup_escort_hud)
+            .add_systems(Update, (
+                spawn_caravan_system,
+                caravan_movement_system,
+                caravan_contact_damage_system,
+                finish_escort_system,
+                update_escort_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_escort_hud)
+            .add_systems(OnEnter(AppState::GameOver), despawn_caravans)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_caravans);
+    }
+}
+
+#[derive(Resource)]
+pub struct EscortTimer(pub Timer);
+
+impl Default for EscortTimer {
+    fn default() -> Self { Self(Timer::from_seconds(ESCORT_INTERVAL_SECS, TimerMode::Repeating)) }
+}
+
+// Crawls across the arena drawing horrors away from the survivor; keeping it standing until the journey
+// timer runs out pays a large bonus, and ignoring it costs nothing but the reward
+#[derive(Component, Debug)]
+pub struct Caravan {
+    pub heading: Vec2,
+    pub journey: Timer,
+    pub contact_tick: Timer,
+}
+
+impl Caravan {
+    pub fn new(heading: Vec2) -> Self {
+        Self {
+            heading: heading.normalize_or_zero(),
+            journey: Timer::from_seconds(ESCORT_DURATION_SECS, TimerMode::Once),
+            contact_tick: Timer::from_seconds(CARAVAN_CONTACT_TICK_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscortOutcome {
+    Delivered,
+    Lost,
+}
+
+// A caravan destroyed on the very frame its journey ends still counts as lost
+pub fn escort_outcome(health: i32, journey_finished: bool) -> Option<EscortOutcome> {
+    if health <= 0 { return Some(EscortOutcome::Lost); }
+    journey_finished.then_some(EscortOutcome::Delivered)
+}
+
+pub fn caravan_health_fraction(health: i32) -> f32 {
+    (health.max(0) as f32 / CARAVAN_MAX_HEALTH as f32).min(1.0)
+}
+
+// Turns back from an arena wall instead of grinding along it
+pub fn advance_caravan(position: Vec2, heading: Vec2, distance: f32, game_config: &GameConfig) -> (Vec2, Vec2) {
+    let moved = position + heading * distance;
+    let clamped = game_config.clamp_to_arena(moved, CARAVAN_SIZE.max_element() / 2.0);
+    if clamped == moved { return (moved, heading); }
+    let mut turned = heading;
+    if clamped.x != moved.x { turned.x = -turned.x; }
+    if clamped.y != moved.y { turned.y = -turned.y; }
+    (clamped, turned)
+}
+
+#[derive(Component)]
+struct EscortHud;
+
+#[derive(Component)]
+struct EscortHudText;
+
+#[derive(Component)]
+struct EscortHudFill;
+
+fn reset_escort(mut escort_timer: ResMut<EscortTimer>) {
+    escort_timer.0.reset();
+}
+
+fn spawn_caravan_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut escort_timer: ResMut<EscortTimer>,
+    caravan_query: Query<(), With<Caravan>>,
+    player_query: Query<&Transform, With<Survivor>>,
+    game_config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    if !escort_timer.0.tick(time.delta()).just_finished() || !caravan_query.is_empty() { return; }
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let rng = &mut game_rng.0;
+    let Some(position) = (0..CARAVAN_PLACEMENT_ATTEMPTS)
+        .map(|_| player_pos + Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(CARAVAN_MIN_DISTANCE..CARAVAN_MAX_DISTANCE))
+        .find(|point| is_valid_spawn_point(*point, CARAVAN_SIZE.max_element() / 2.0, &game_config, &[])) else { return; };
+    let heading = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/guardian_seed.png"),
+            sprite: Sprite { custom_size: Some(CARAVAN_SIZE), color: CARAVAN_COLOR, ..default() },
+            transform: Transform::from_translation(position.extend(0.45)),
+            ..default()
+        },
+        Caravan::new(heading),
+        Health(CARAVAN_MAX_HEALTH),
+        Threat { weight: CARAVAN_THREAT_WEIGHT },
+        Name::new("Caravan"),
+    ));
+    sound_event_writer.send(PlaySoundEvent::at(SoundEffect::OmenAccepted, position));
+}
+
+fn caravan_movement_system(time: Res<Time>, game_config: Res<GameConfig>, mut caravan_query: Query<(&mut Transform, &mut Caravan)>) {
+    for (mut transform, mut caravan) in caravan_query.iter_mut() {
+        caravan.journey.tick(time.delta());
+        let (position, heading) = advance_caravan(transform.translation.truncate(), caravan.heading, CARAVAN_SPEED * time.delta_seconds(), &game_config);
+        caravan.heading = heading;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+// Same contact rule as the decoy: every horror touching it lands its collision damage once per tick
+fn caravan_contact_damage_system(
+    time: Res<Time>,
+    mut caravan_query: Query<(&Transform, &mut Caravan, &mut Health, &mut Sprite), Without<Horror>>,
+    horror_query: Query<(&Transform, &Horror)>,
+) {
+    for (transform, mut caravan, mut health, mut sprite) in caravan_query.iter_mut() {
+        if !caravan.contact_tick.tick(time.delta()).just_finished() { continue; }
+        let caravan_pos = transform.translation.truncate();
+        for (horror_transform, horror) in horror_query.iter() {
+            if horror_transform.translation.truncate().distance(caravan_pos) < CARAVAN_SIZE.max_element() / 2.0 + horror.size.x / 2.0 {
+                health.0 -= horror.damage_on_collision;
+            }
+        }
+        sprite.color.set_a(0.5 + 0.5 * caravan_health_fraction(health.0));
+    }
+}
+
+fn finish_escort_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    caravan_query: Query<(Entity, &Transform, &Caravan, &Health)>,
+    mut game_rng: ResMut<GameRng>,
+    mut award_writer: EventWriter<ScoreAwardEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    for (entity, transform, caravan, health) in caravan_query.iter() {
+        let Some(outcome) = escort_outcome(health.0, caravan.journey.finished()) else { continue; };
+        commands.entity(entity).despawn_recursive();
+        let position = transform.translation;
+        if outcome == EscortOutcome::Delivered {
+            award_writer.send(ScoreAwardEvent { source: ScoreSource::CaravanEscorted, points: ESCORT_SCORE_BONUS, position });
+            for _ in 0..ESCORT_GOLD_COINS {
+                let scatter = Vec2::from_angle(game_rng.0.gen_range(0.0..std::f32::consts::TAU)) * game_rng.0.gen_range(10.0..70.0);
+                spawn_gold_coin(&mut commands, &asset_server, position + scatter.extend(0.0), ESCORT_GOLD_COIN_VALUE);
+            }
+        }
+        game_event_writer.send(GameEvent::EscortFinished { delivered: outcome == EscortOutcome::Delivered, position });
+    }
+}
+
+fn setup_escort_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style { position_type: PositionType::Absolute, top: Val::Px(60.0), left: Val::Percent(50.0), margin: UiRect::left(Val::Px(-ESCORT_HUD_BAR_WIDTH / 2.0)), flex_direction: FlexDirection::Column, align_items: AlignItems::Center, display: Display::None, ..default() },
+            ..default()
+        },
+        EscortHud,
+        Name::new("EscortHud"),
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: CARAVAN_COLOR }),
+            EscortHudText,
+        ));
+        parent.spawn(NodeBundle {
+            style: Style { width: Val::Px(ESCORT_HUD_BAR_WIDTH), height: Val::Px(10.0), ..default() },
+            background_color: Color::rgba(0.2, 0.15, 0.05, 0.8).into(),
+            ..default()
+        }).with_children(|bar| {
+            bar.spawn((
+                NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), ..default() }, background_color: CARAVAN_COLOR.into(), ..default() },
+                EscortHudFill,
+            ));
+        });
+    });
+}
+
+fn update_escort_hud(
+    caravan_query: Query<(&Caravan, &Health)>,
+    mut hud_query: Query<&mut Style, (With<EscortHud>, Without<EscortHudFill>)>,
+    mut fill_query: Query<&mut Style, (With<EscortHudFill>, Without<EscortHud>)>,
+    mut text_query: Query<&mut Text, With<EscortHudText>>,
+) {
+    let Ok(mut hud_style) = hud_query.get_single_mut() else { return; };
+    let Some((caravan, health)) = caravan_query.iter().next() else {
+        hud_style.display = Display::None;
+        return;
+    };
+    hud_style.display = Display::Flex;
+    if let Ok(mut fill_style) = fill_query.get_single_mut() { fill_style.width = Val::Percent(caravan_health_fraction(health.0) * 100.0); }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format!("Protect the caravan - {:.0}s", caravan.journey.remaining_secs().ceil());
+    }
+}
+
+fn despawn_escort_hud(mut commands: Commands, hud_query: Query<Entity, With<EscortHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn despawn_caravans(mut commands: Commands, caravan_query: Query<Entity, With<Caravan>>) {
+    for entity in caravan_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
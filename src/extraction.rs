@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::{
+    survivor::Survivor,
+    game::{AppState, GameConfig, GameRng},
+    game_events::GameEvent,
+    horror::is_valid_spawn_point,
+    audio::{PlaySoundEvent, SoundEffect},
+};
+
+pub const EXTRACTION_RIFT_INTERVAL_SECS: f32 = 600.0;
+pub const EXTRACTION_RIFT_LIFETIME_SECS: f32 = 90.0;
+pub const EXTRACTION_CHANNEL_SECS: f32 = 10.0;
+pub const EXTRACTION_RIFT_RADIUS: f32 = 60.0;
+// Well off screen, so reaching it is a trek through the horde
+const EXTRACTION_RIFT_MIN_DISTANCE: f32 = 900.0;
+const EXTRACTION_RIFT_MAX_DISTANCE: f32 = 1300.0;
+const EXTRACTION_RIFT_PLACEMENT_ATTEMPTS: usize = 8;
+const EXTRACTION_RING_PIPS: usize = 24;
+const EXTRACTION_RING_PIP_SIZE: Vec2 = Vec2::new(8.0, 8.0);
+const RIFT_COLOR: Color = Color::rgb(0.3, 0.9, 0.8);
+const PIP_UNLIT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.2);
+const PIP_LIT_COLOR: Color = Color::rgb(0.5, 1.0, 0.9);
+
+pub struct ExtractionPlugin;
+
+impl Plugin for ExtractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExtractionRiftTimer>()
+            .init_resource::<RunOutcome>()
+            .add_systems(OnExit(AppState::MainMenu), reset_extraction)
+            .add_systems(OnEnter(AppState::InGame), setup_extraction_hud)
+            .add_systems(Update, (
+                spawn_extraction_rift_system,
+                extraction_channel_system,
+                extraction_rift_lifetime_system,
+                update_extraction_ring,
+                update_extraction_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_extraction_hud)
+            .add_systems(OnEnter(AppState::GameOver), despawn_extraction_rifts)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_extraction_rifts);
+    }
+}
+
+// How the run ended; anything but an extraction is a death
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    #[default]
+    Died,
+    Extracted,
+}
+
+#[derive(Resource)]
+pub struct ExtractionRiftTimer(pub Timer);
+
+impl Default for ExtractionRiftTimer {
+    fn default() -> Self { Self(Timer::from_seconds(EXTRACTION_RIFT_INTERVAL_SECS, TimerMode::Repeating)) }
+}
+
+// Standing inside for EXTRACTION_CHANNEL_SECS ends the run in victory; leaving or taking a hit starts the channel over.
+// It closes again if ignored, but never while someone is channelling in it
+#[derive(Component, Debug)]
+pub struct ExtractionRift {
+    pub lifetime: Timer,
+    pub channel_secs: f32,
+}
+
+impl Default for ExtractionRift {
+    fn default() -> Self { Self { lifetime: Timer::from_seconds(EXTRACTION_RIFT_LIFETIME_SECS, TimerMode::Once), channel_secs: 0.0 } }
+}
+
+impl ExtractionRift {
+    pub fn is_channelling(&self) -> bool { self.channel_secs > 0.0 }
+    pub fn channel_fraction(&self) -> f32 { (self.channel_secs / EXTRACTION_CHANNEL_SECS).min(1.0) }
+
+    // Returns true once the channel completes
+    pub fn advance_channel(&mut self, delta_secs: f32, inside: bool, was_hit: bool) -> bool {
+        if !inside || was_hit { self.channel_secs = 0.0; return false; }
+        self.channel_secs += delta_secs;
+        self.channel_secs >= EXTRACTION_CHANNEL_SECS
+    }
+}
+
+#[derive(Component)]
+struct ExtractionRingPip(usize);
+
+#[derive(Component)]
+struct ExtractionHud;
+
+fn reset_extraction(mut rift_timer: ResMut<ExtractionRiftTimer>, mut outcome: ResMut<RunOutcome>) {
+    rift_timer.0.reset();
+    *outcome = RunOutcome::Died;
+}
+
+fn spawn_extraction_rift_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut rift_timer: ResMut<ExtractionRiftTimer>,
+    rift_query: Query<(), With<ExtractionRift>>,
+    player_query: Query<&Transform, With<Survivor>>,
+    game_config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    if !rift_timer.0.tick(time.delta()).just_finished() || !rift_query.is_empty() { return; }
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let rng = &mut game_rng.0;
+    // Near an arena wall some directions are out of bounds; if every try misses, this opening is skipped
+    let Some(position) = (0..EXTRACTION_RIFT_PLACEMENT_ATTEMPTS)
+        .map(|_| player_pos + Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(EXTRACTION_RIFT_MIN_DISTANCE..EXTRACTION_RIFT_MAX_DISTANCE))
+        .find(|point| is_valid_spawn_point(*point, EXTRACTION_RIFT_RADIUS, &game_config, &[])) else { return; };
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/revelation_wave_placeholder.png"),
+            sprite: Sprite { custom_size: Some(Vec2::splat(EXTRACTION_RIFT_RADIUS * 2.0)), color: RIFT_COLOR, ..default() },
+            transform: Transform::from_translation(position.extend(0.4)),
+            ..default()
+        },
+        ExtractionRift::default(),
+        Name::new("ExtractionRift"),
+    )).with_children(|parent| {
+        for index in 0..EXTRACTION_RING_PIPS {
+            let angle = std::f32::consts::FRAC_PI_2 - index as f32 / EXTRACTION_RING_PIPS as f32 * std::f32::consts::TAU;
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite { custom_size: Some(EXTRACTION_RING_PIP_SIZE), color: PIP_UNLIT_COLOR, ..default() },
+                    transform: Transform::from_translation((Vec2::from_angle(angle) * (EXTRACTION_RIFT_RADIUS + 12.0)).extend(0.1)),
+                    ..default()
+                },
+                ExtractionRingPip(index),
+            ));
+        }
+    });
+    sound_event_writer.send(PlaySoundEvent::at(SoundEffect::Revelation, position));
+    game_event_writer.send(GameEvent::ExtractionRiftOpened { position: position.extend(0.4) });
+}
+
+fn extraction_channel_system(
+    time: Res<Time>,
+    mut game_events: EventReader<GameEvent>,
+    player_query: Query<&Transform, With<Survivor>>,
+    mut rift_query: Query<(&Transform, &mut ExtractionRift)>,
+    mut outcome: ResMut<RunOutcome>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let was_hit = game_events.read().any(|event| matches!(event, GameEvent::SurvivorHit { .. }));
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    for (rift_transform, mut rift) in rift_query.iter_mut() {
+        let inside = player_pos.distance(rift_transform.translation.truncate()) < EXTRACTION_RIFT_RADIUS;
+        if !rift.advance_channel(time.delta_seconds(), inside, was_hit) { continue; }
+        *outcome = RunOutcome::Extracted;
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::Revelation));
+        next_app_state.set(AppState::GameOver);
+        return;
+    }
+}
+
+fn extraction_rift_lifetime_system(mut commands: Commands, time: Res<Time>, mut rift_query: Query<(Entity, &mut ExtractionRift, &mut Sprite)>) {
+    for (entity, mut rift, mut sprite) in rift_query.iter_mut() {
+        if rift.is_channelling() { continue; }
+        if rift.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        sprite.color.set_a((rift.lifetime.remaining_secs() / (EXTRACTION_RIFT_LIFETIME_SECS * 0.25)).clamp(0.2, 1.0));
+    }
+}
+
+// Pips light clockwise from the top as the channel fills
+fn update_extraction_ring(rift_query: Query<(&ExtractionRift, &Children)>, mut pip_query: Query<(&ExtractionRingPip, &mut Sprite)>) {
+    for (rift, children) in rift_query.iter() {
+        let lit = (rift.channel_fraction() * EXTRACTION_RING_PIPS as f32).floor() as usize;
+        for child in children.iter() {
+            let Ok((pip, mut sprite)) = pip_query.get_mut(*child) else { continue; };
+            sprite.color = if pip.0 < lit { PIP_LIT_COLOR } else { PIP_UNLIT_COLOR };
+        }
+    }
+}
+
+fn setup_extraction_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section("", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: RIFT_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, bottom: Val::Px(80.0), width: Val::Percent(100.0), justify_content: JustifyContent::Center, ..default() })
+            .with_text_justify(JustifyText::Center),
+        ExtractionHud,
+        Name::new("ExtractionHud"),
+    ));
+}
+
+fn update_extraction_hud(player_query: Query<&Transform, With<Survivor>>, rift_query: Query<(&Transform, &ExtractionRift)>, mut text_query: Query<&mut Text, With<ExtractionHud>>) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    let (Ok(player_transform), Some((rift_transform, rift))) = (player_query.get_single(), rift_query.iter().next()) else {
+        text.sections[0].value.clear();
+        return;
+    };
+    text.sections[0].value = if rift.is_channelling() {
+        format!("Extracting... {:.1}s - don't get hit", EXTRACTION_CHANNEL_SECS - rift.channel_secs)
+    } else {
+        let distance = player_transform.translation.truncate().distance(rift_transform.translation.truncate());
+        format!("Extraction rift open {:.0} px away - closes in {:.0}s", distance, rift.lifetime.remaining_secs())
+    };
+}
+
+fn despawn_extraction_hud(mut commands: Commands, hud_query: Query<Entity, With<ExtractionHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn despawn_extraction_rifts(mut commands: Commands, rift_query: Query<Entity, With<ExtractionRift>>) {
+    for entity in rift_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,83 @@
+use bevy::{prelude::*, transform::TransformSystem};
+use crate::{
+    survivor::Survivor,
+    game::{AppState, GameConfig},
+    background::{chunk_coord, chunk_center, BACKGROUND_TILE_SIZE},
+    horror::{RangedAttackerBehavior, VoidBlinkerBehavior, FrenziedBehemothBehavior},
+    weapons::SnareLayer,
+};
+
+// f32 positions keep sub-pixel precision comfortably within this distance of the origin
+pub const ORIGIN_RECENTER_DISTANCE: f32 = BACKGROUND_TILE_SIZE * 8.0;
+
+pub struct FloatingOriginPlugin;
+
+impl Plugin for FloatingOriginPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FloatingOrigin>()
+            .add_event::<OriginShifted>()
+            .add_systems(OnExit(AppState::MainMenu), reset_floating_origin)
+            // After gameplay has moved everything this frame, before global transforms are worked out from it
+            .add_systems(PostUpdate, recenter_world_system.before(TransformSystem::TransformPropagate).run_if(in_state(AppState::InGame)));
+    }
+}
+
+// Where the local origin sits in the world, in whole background chunks so the ground grid stays aligned
+#[derive(Resource, Default, Debug)]
+pub struct FloatingOrigin {
+    pub chunk_offset: IVec2,
+}
+
+impl FloatingOrigin {
+    pub fn to_world(&self, local: Vec2) -> Vec2 {
+        local + chunk_center(self.chunk_offset)
+    }
+}
+
+// Sent once everything in the world has been moved back by `shift`; systems holding positions outside a Transform listen for it
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OriginShifted {
+    pub shift: Vec2,
+    pub chunks: IVec2,
+}
+
+// How many chunks to move the origin by once the focus has strayed too far; the focus ends up within half a chunk of the new origin
+pub fn recenter_chunks(focus: Vec2) -> Option<IVec2> {
+    if focus.abs().max_element() < ORIGIN_RECENTER_DISTANCE { return None; }
+    Some(chunk_coord(focus))
+}
+
+fn reset_floating_origin(mut origin: ResMut<FloatingOrigin>, mut game_config: ResMut<GameConfig>) {
+    origin.chunk_offset = IVec2::ZERO;
+    game_config.arena_center = Vec2::ZERO;
+}
+
+// Events already in flight keep their old positions; at worst a sound or hit effect lands one chunk-multiple off for a frame
+fn recenter_world_system(
+    mut origin: ResMut<FloatingOrigin>,
+    mut game_config: ResMut<GameConfig>,
+    mut survivor_query: Query<(&mut Transform, &mut Survivor), Without<Parent>>,
+    mut root_query: Query<&mut Transform, (Without<Parent>, Without<Node>, Without<Survivor>)>,
+    mut ranged_query: Query<&mut RangedAttackerBehavior>,
+    mut blinker_query: Query<&mut VoidBlinkerBehavior>,
+    mut behemoth_query: Query<&mut FrenziedBehemothBehavior>,
+    mut snare_layer_query: Query<&mut SnareLayer>,
+    mut shifted_writer: EventWriter<OriginShifted>,
+) {
+    let Ok((mut survivor_transform, mut survivor)) = survivor_query.get_single_mut() else { return; };
+    let Some(chunks) = recenter_chunks(survivor_transform.translation.truncate()) else { return; };
+    let shift = chunk_center(chunks);
+
+    survivor_transform.translation -= shift.extend(0.0);
+    survivor.aim_world_position -= shift;
+    for mut transform in root_query.iter_mut() { transform.translation -= shift.extend(0.0); }
+    for mut behavior in ranged_query.iter_mut() { if let Some(target) = behavior.reposition_target.as_mut() { *target -= shift; } }
+    for mut behavior in blinker_query.iter_mut() { if let Some(destination) = behavior.next_teleport_destination.as_mut() { *destination -= shift; } }
+    for mut behavior in behemoth_query.iter_mut() { if let Some(target) = behavior.charge_target_pos.as_mut() { *target -= shift; } }
+    for mut snare_layer in snare_layer_query.iter_mut() { if let Some(position) = snare_layer.last_position.as_mut() { *position -= shift; } }
+
+    origin.chunk_offset += chunks;
+    game_config.arena_center -= shift;
+    shifted_writer.send(OriginShifted { shift, chunks });
+    info!("Floating origin moved by {} chunks; the local origin is now at {} in the world", chunks, origin.to_world(Vec2::ZERO));
+}
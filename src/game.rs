@@ -1,17 +1,27 @@
 use bevy::prelude::*;
-use rand::seq::SliceRandom;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
 use crate::{
-    enemy::{HorrorSpawnTimer, MaxHorrors}, // Renamed
+    horror::{HorrorSpawnTimer, MaxHorrors}, // Renamed
     echoing_soul::{EchoingSoul, EchoingSoulPlugin}, // Changed
-    player::Survivor, // Renamed
-    components::Health,
+    survivor::Survivor, // Renamed
+    components::{Health, DamageType},
+    glyphs::GlyphLibrary,
+    visual_effects::DamageTypeStyle,
     upgrades::{UpgradePlugin, UpgradePool, OfferedUpgrades, UpgradeCard, UpgradeType},
-    weapons::{CircleOfWarding, SwarmOfNightmares}, // Renamed
+    weapons::{CircleOfWarding, SwarmOfNightmares, SearingGaze, SporeCenser, RearGuard, SnareLayer, HookedCrescent}, // Renamed
     audio::{PlaySoundEvent, SoundEffect},
     debug_menu::DebugMenuPlugin,
+    upgrade_preview::{UpgradePreviewPlugin, UpgradePreviewText},
     items::{ItemId, ItemLibrary},
-    skills::{ActiveSkillInstance, SkillId, SkillProjectile, ActiveSkillAoEEffect},
-    thought_fragment::IchorBlast, // Renamed
+    gold::Gold,
+    challenge::{ActiveChallenge, WEEKLY_CHALLENGE_KEY},
+    boss_practice::BOSS_PRACTICE_KEY,
+    input_actions::{ActionState, InputAction, InputMap},
+    bestiary::Bestiary,
+    extraction::RunOutcome,
+    skills::{ActiveSkillInstance, SkillLibrary, MAX_GLYPH_SLOTS},
+    ichor_blast::IchorBlast, // Renamed
+    coop::{PlayerSlot, LevelUpChoices, CHOICE_ACTIONS, waiting_label},
 };
 
 pub const SCREEN_WIDTH: f32 = 1280.0;
@@ -22,21 +32,37 @@ const DIFFICULTY_INCREASE_INTERVAL_SECONDS: f32 = 30.0;
 const MAX_HORRORS_INCREMENT: u32 = 10; // Renamed
 const SPAWN_INTERVAL_DECREMENT_FACTOR: f32 = 0.9;
 const MIN_SPAWN_INTERVAL_SECONDS: f32 = 0.3;
+const ARENA_HALF_EXTENT: f32 = 50_000.0;
 
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
-pub enum AppState { #[default] MainMenu, InGame, LevelUp, GameOver, DebugUpgradeMenu, }
+pub enum AppState { #[default] MainMenu, InGame, LevelUp, GlyphSlotChoice, GameOver, DebugUpgradeMenu, RewardReveal, Paused, RespecShrine, }
+// The arena stays put in the world, so it drifts in local coordinates whenever the floating origin recenters
 #[derive(Resource)]
-pub struct GameConfig { pub width: f32, pub height: f32, pub spawn_area_padding: f32, }
-impl Default for GameConfig { fn default() -> Self { Self { width: SCREEN_WIDTH, height: SCREEN_HEIGHT, spawn_area_padding: 50.0 } } }
+pub struct GameConfig { pub width: f32, pub height: f32, pub spawn_area_padding: f32, pub arena_half_extents: Vec2, pub arena_center: Vec2, }
+impl Default for GameConfig { fn default() -> Self { Self { width: SCREEN_WIDTH, height: SCREEN_HEIGHT, spawn_area_padding: 50.0, arena_half_extents: Vec2::splat(ARENA_HALF_EXTENT), arena_center: Vec2::ZERO } } }
+impl GameConfig { pub fn clamp_to_arena(&self, position: Vec2, margin: f32) -> Vec2 { let limit = (self.arena_half_extents - Vec2::splat(margin)).max(Vec2::ZERO); position.clamp(self.arena_center - limit, self.arena_center + limit) } }
 pub struct GamePlugin;
 #[derive(Resource, Default)]
 pub struct GameState { pub score: u32, pub cycle_number: u32, pub horror_count: u32, pub game_over_timer: Timer, pub game_timer: Timer, pub difficulty_timer: Timer, } // Renamed wave/enemy
-#[derive(Event)] pub struct UpgradeChosenEvent(pub UpgradeCard);
+// Moves the clocks even while they are paused, and returns how many cycles were passed on the way; the caller applies their scaling
+impl GameState { pub fn fast_forward(&mut self, secs: f32) -> u32 { let secs = secs.max(0.0); let run_elapsed = (self.game_timer.elapsed_secs() + secs).min(self.game_timer.duration().as_secs_f32()); self.game_timer.set_elapsed(std::time::Duration::from_secs_f32(run_elapsed)); let interval = self.difficulty_timer.duration().as_secs_f32(); if interval <= 0.0 { return 0; } let into_cycle = self.difficulty_timer.elapsed_secs() + secs; let cycles = (into_cycle / interval) as u32; self.difficulty_timer.set_elapsed(std::time::Duration::from_secs_f32(into_cycle % interval)); self.cycle_number += cycles; cycles } }
+// Levels earned but not yet chosen; each one opens the level-up screen in turn
+#[derive(Resource, Default, Debug)]
+pub struct PendingLevelUps(pub u32);
+// Every gameplay roll draws from here, so a fixed seed replays a run exactly; visual-only jitter keeps using thread_rng
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+impl Default for GameRng { fn default() -> Self { Self(StdRng::from_entropy()) } }
+impl GameRng { pub fn seeded(seed: u64) -> Self { Self(StdRng::seed_from_u64(seed)) } }
+// Carries the player who picked the card, so it lands on their survivor
+#[derive(Event)] pub struct UpgradeChosenEvent(pub UpgradeCard, pub PlayerSlot);
 #[derive(Event)] pub struct ItemCollectedEvent(pub ItemId);
 
 #[derive(Component)] struct MainMenuUI;
 #[derive(Component)] struct LevelUpUI;
-#[derive(Component)] struct UpgradeButton(UpgradeCard);
+#[derive(Component)] pub struct UpgradeButton(pub UpgradeCard);
+#[derive(Component)] struct GlyphSlotChoiceUI;
+#[derive(Component)] struct GlyphSlotSkillButton(usize);
 #[derive(Component)] struct GameOverUI;
 #[derive(Component)] struct InGameUI;
 #[derive(Component)] struct EnduranceText; // Renamed
@@ -45,18 +71,21 @@ pub struct GameState { pub score: u32, pub cycle_number: u32, pub horror_count:
 #[derive(Component)] struct ScoreText;
 #[derive(Component)] struct TimerText;
 #[derive(Component)] struct CycleText; // Renamed
+#[derive(Component)] struct GoldText;
 
-fn reset_for_new_game_session(mut game_state: ResMut<GameState>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>, mut max_horrors: ResMut<MaxHorrors>,) { game_state.score = 0; game_state.cycle_number = 1; game_state.horror_count = 0; game_state.game_timer = Timer::from_seconds(3600.0, TimerMode::Once); game_state.game_timer.reset(); game_state.game_timer.unpause(); game_state.difficulty_timer = Timer::from_seconds(DIFFICULTY_INCREASE_INTERVAL_SECONDS, TimerMode::Repeating); game_state.difficulty_timer.reset(); horror_spawn_timer.timer.set_duration(std::time::Duration::from_secs_f32(INITIAL_SPAWN_INTERVAL_SECONDS)); horror_spawn_timer.timer.reset(); max_horrors.0 = INITIAL_MAX_HORRORS; } // Renamed variables
-fn on_enter_ingame_state_actions(mut game_state: ResMut<GameState>) { if game_state.game_timer.paused() { game_state.game_timer.unpause(); } if game_state.difficulty_timer.paused() { game_state.difficulty_timer.unpause(); } }
-fn on_enter_pause_like_state_actions(mut game_state: ResMut<GameState>, _current_app_state: Res<State<AppState>>) { if !game_state.game_timer.paused() { game_state.game_timer.pause(); } if !game_state.difficulty_timer.paused() { game_state.difficulty_timer.pause(); } }
+fn reset_for_new_game_session(mut game_state: ResMut<GameState>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>, mut max_horrors: ResMut<MaxHorrors>, mut pending_level_ups: ResMut<PendingLevelUps>,) { pending_level_ups.0 = 0; game_state.score = 0; game_state.cycle_number = 1; game_state.horror_count = 0; game_state.game_timer = Timer::from_seconds(3600.0, TimerMode::Once); game_state.game_timer.reset(); game_state.game_timer.unpause(); game_state.difficulty_timer = Timer::from_seconds(DIFFICULTY_INCREASE_INTERVAL_SECONDS, TimerMode::Repeating); game_state.difficulty_timer.reset(); horror_spawn_timer.timer.set_duration(std::time::Duration::from_secs_f32(INITIAL_SPAWN_INTERVAL_SECONDS)); horror_spawn_timer.timer.reset(); max_horrors.0 = INITIAL_MAX_HORRORS; } // Renamed variables
+#[allow(clippy::possible_missing_else)]
+pub(crate) fn on_enter_ingame_state_actions(mut game_state: ResMut<GameState>) { if game_state.game_timer.paused() { game_state.game_timer.unpause(); } if game_state.difficulty_timer.paused() { game_state.difficulty_timer.unpause(); } }
+#[allow(clippy::possible_missing_else)]
+pub(crate) fn on_enter_pause_like_state_actions(mut game_state: ResMut<GameState>, _current_app_state: Res<State<AppState>>) { if !game_state.game_timer.paused() { game_state.game_timer.pause(); } if !game_state.difficulty_timer.paused() { game_state.difficulty_timer.pause(); } }
 fn log_entering_debug_menu_state() {}
 fn log_exiting_debug_menu_state() {}
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app .add_event::<UpgradeChosenEvent>() .add_event::<ItemCollectedEvent>()
-            .add_plugins((UpgradePlugin, DebugMenuPlugin)) .init_state::<AppState>()
-            .init_resource::<GameConfig>() .init_resource::<GameState>()
+            .add_plugins((UpgradePlugin, DebugMenuPlugin, UpgradePreviewPlugin)) .init_state::<AppState>()
+            .init_resource::<GameConfig>() .init_resource::<GameState>() .init_resource::<PendingLevelUps>() .init_resource::<LevelUpChoices>() .init_resource::<GameRng>()
             .insert_resource(HorrorSpawnTimer {timer: Timer::from_seconds(INITIAL_SPAWN_INTERVAL_SECONDS, TimerMode::Repeating)}) // Renamed
             .insert_resource(MaxHorrors(INITIAL_MAX_HORRORS)) .add_plugins(EchoingSoulPlugin) // Changed
             .add_systems(OnEnter(AppState::MainMenu), setup_main_menu_ui)
@@ -64,11 +93,15 @@ impl Plugin for GamePlugin {
             .add_systems(OnExit(AppState::MainMenu), despawn_ui_by_marker::<MainMenuUI>)
             .add_systems(OnEnter(AppState::InGame), (on_enter_ingame_state_actions, setup_ingame_ui,))
             .add_systems(Update, (update_ingame_ui, update_game_timer, difficulty_scaling_system, global_debug_key_listener,).chain().run_if(in_state(AppState::InGame).or_else(in_state(AppState::DebugUpgradeMenu))))
+            .add_systems(Update, open_pending_level_up.run_if(in_state(AppState::InGame)))
             .add_systems(OnExit(AppState::InGame), (cleanup_session_entities, despawn_ui_by_marker::<InGameUI>))
             .add_systems(OnEnter(AppState::LevelUp), (setup_level_up_ui, on_enter_pause_like_state_actions))
             .add_systems(Update, handle_upgrade_choice_interaction.run_if(in_state(AppState::LevelUp)))
             .add_systems(Update, apply_chosen_upgrade.run_if(on_event::<UpgradeChosenEvent>()))
             .add_systems(OnExit(AppState::LevelUp), (despawn_ui_by_marker::<LevelUpUI>, on_enter_ingame_state_actions))
+            .add_systems(OnEnter(AppState::GlyphSlotChoice), (setup_glyph_slot_choice_ui, on_enter_pause_like_state_actions))
+            .add_systems(Update, handle_glyph_slot_choice_interaction.run_if(in_state(AppState::GlyphSlotChoice)))
+            .add_systems(OnExit(AppState::GlyphSlotChoice), (despawn_ui_by_marker::<GlyphSlotChoiceUI>, on_enter_ingame_state_actions))
             .add_systems(OnEnter(AppState::DebugUpgradeMenu), (on_enter_pause_like_state_actions, log_entering_debug_menu_state))
             .add_systems(OnExit(AppState::DebugUpgradeMenu), (on_enter_ingame_state_actions, log_exiting_debug_menu_state))
             .add_systems(OnEnter(AppState::GameOver), setup_game_over_ui)
@@ -79,16 +112,92 @@ impl Plugin for GamePlugin {
 fn global_debug_key_listener(keyboard_input: Res<ButtonInput<KeyCode>>, current_app_state: Res<State<AppState>>, mut next_app_state: ResMut<NextState<AppState>>,) { if keyboard_input.just_pressed(KeyCode::Backquote) { match current_app_state.get() { AppState::InGame => { next_app_state.set(AppState::DebugUpgradeMenu); } AppState::DebugUpgradeMenu => { next_app_state.set(AppState::InGame); } _ => {} } } }
 fn despawn_ui_by_marker<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) { for entity in query.iter() { commands.entity(entity).despawn_recursive(); } }
 fn setup_main_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) { commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(20.0), ..default() }, ..default() }, MainMenuUI, )).with_children(|parent| { parent.spawn( TextBundle::from_section( "Echoes of the Abyss", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 70.0, color: Color::WHITE, }, ).with_text_justify(JustifyText::Center) ); parent.spawn( TextBundle::from_section( "Embrace the Madness (SPACE)", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 40.0, color: Color::rgba(0.8, 0.8, 0.8, 1.0), }, ).with_text_justify(JustifyText::Center) ); }); }
-fn main_menu_input_system(mut commands: Commands, keyboard_input: Res<ButtonInput<KeyCode>>, mut next_app_state: ResMut<NextState<AppState>>, game_state: ResMut<GameState>, horror_spawn_timer: ResMut<HorrorSpawnTimer>, max_horrors: ResMut<MaxHorrors>, player_entity_query: Query<Entity, With<Survivor>>,) { if keyboard_input.just_pressed(KeyCode::Space) { for entity in player_entity_query.iter() { commands.entity(entity).despawn_recursive(); } reset_for_new_game_session(game_state, horror_spawn_timer, max_horrors); next_app_state.set(AppState::InGame); } } // Renamed variables
-fn setup_ingame_ui(mut commands: Commands, asset_server: Res<AssetServer>) { commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), flex_direction: FlexDirection::Column, justify_content: JustifyContent::SpaceBetween, padding: UiRect::all(Val::Px(10.0)), position_type: PositionType::Absolute, ..default() }, z_index: ZIndex::Global(1), ..default() }, InGameUI, )).with_children(|parent| { parent.spawn(NodeBundle { style: Style { width: Val::Percent(100.0), justify_content: JustifyContent::SpaceAround, align_items: AlignItems::Center, padding: UiRect::all(Val::Px(5.0)), ..default() }, background_color: Color::rgba(0.0, 0.0, 0.0, 0.3).into(), ..default() }).with_children(|top_bar| { top_bar.spawn((TextBundle::from_section( "Endurance: 100", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::GREEN, }, ), EnduranceText)); top_bar.spawn((TextBundle::from_section( "Insight: 1", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::CYAN, }, ), InsightText)); top_bar.spawn((TextBundle::from_section( "Echoes: 0/100", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::YELLOW, }, ), EchoesText)); top_bar.spawn((TextBundle::from_section( "Cycle: 1", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::ORANGE_RED, }, ), CycleText)); }); parent.spawn(NodeBundle { style: Style { width: Val::Percent(100.0), justify_content: JustifyContent::SpaceBetween, align_items: AlignItems::FlexEnd, padding: UiRect::all(Val::Px(5.0)), ..default() }, ..default() }).with_children(|bottom_bar| { bottom_bar.spawn((TextBundle::from_section( "Score: 0", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::WHITE, }, ), ScoreText)); bottom_bar.spawn((TextBundle::from_section( "Time: 00:00", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::WHITE, }, ), TimerText)); }); }); }
+fn main_menu_input_system(mut commands: Commands, keyboard_input: Res<ButtonInput<KeyCode>>, actions: Res<ActionState>, mut next_app_state: ResMut<NextState<AppState>>, game_state: ResMut<GameState>, horror_spawn_timer: ResMut<HorrorSpawnTimer>, max_horrors: ResMut<MaxHorrors>, pending_level_ups: ResMut<PendingLevelUps>, player_entity_query: Query<Entity, With<Survivor>>, bestiary: Res<Bestiary>,) { let practice_requested = keyboard_input.just_pressed(BOSS_PRACTICE_KEY) && !bestiary.encountered_elites().is_empty(); if actions.just_pressed(InputAction::Confirm) || keyboard_input.just_pressed(WEEKLY_CHALLENGE_KEY) || practice_requested { for entity in player_entity_query.iter() { commands.entity(entity).despawn_recursive(); } reset_for_new_game_session(game_state, horror_spawn_timer, max_horrors, pending_level_ups); next_app_state.set(AppState::InGame); } } // Renamed variables
+fn setup_ingame_ui(mut commands: Commands, asset_server: Res<AssetServer>) { commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), flex_direction: FlexDirection::Column, justify_content: JustifyContent::SpaceBetween, padding: UiRect::all(Val::Px(10.0)), position_type: PositionType::Absolute, ..default() }, z_index: ZIndex::Global(1), ..default() }, InGameUI, )).with_children(|parent| { parent.spawn(NodeBundle { style: Style { width: Val::Percent(100.0), justify_content: JustifyContent::SpaceAround, align_items: AlignItems::Center, padding: UiRect::all(Val::Px(5.0)), ..default() }, background_color: Color::rgba(0.0, 0.0, 0.0, 0.3).into(), ..default() }).with_children(|top_bar| { top_bar.spawn((TextBundle::from_section( "Endurance: 100", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::GREEN, }, ), EnduranceText)); top_bar.spawn((TextBundle::from_section( "Insight: 1", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::CYAN, }, ), InsightText)); top_bar.spawn((TextBundle::from_section( "Echoes: 0/100", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::YELLOW, }, ), EchoesText)); top_bar.spawn((TextBundle::from_section( "Cycle: 1", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::ORANGE_RED, }, ), CycleText)); top_bar.spawn((TextBundle::from_section( "Gold: 0", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::GOLD, }, ), GoldText)); }); parent.spawn(NodeBundle { style: Style { width: Val::Percent(100.0), justify_content: JustifyContent::SpaceBetween, align_items: AlignItems::FlexEnd, padding: UiRect::all(Val::Px(5.0)), ..default() }, ..default() }).with_children(|bottom_bar| { bottom_bar.spawn((TextBundle::from_section( "Score: 0", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::WHITE, }, ), ScoreText)); bottom_bar.spawn((TextBundle::from_section( "Time: 00:00", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::WHITE, }, ), TimerText)); }); }); }
+fn open_pending_level_up(pending_level_ups: Res<PendingLevelUps>, mut next_app_state: ResMut<NextState<AppState>>) { if pending_level_ups.0 > 0 { next_app_state.set(AppState::LevelUp); } }
 fn update_game_timer(mut game_state: ResMut<GameState>, time: Res<Time>) { if !game_state.game_timer.paused() { game_state.game_timer.tick(time.delta()); } }
-fn difficulty_scaling_system(time: Res<Time>, mut game_state: ResMut<GameState>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>, mut max_horrors: ResMut<MaxHorrors>,) { if game_state.difficulty_timer.paused() { return; } game_state.difficulty_timer.tick(time.delta()); if game_state.difficulty_timer.just_finished() { game_state.cycle_number += 1; max_horrors.0 = (INITIAL_MAX_HORRORS + (game_state.cycle_number -1) * MAX_HORRORS_INCREMENT).min(200); let current_duration = horror_spawn_timer.timer.duration().as_secs_f32(); let new_duration = (current_duration * SPAWN_INTERVAL_DECREMENT_FACTOR).max(MIN_SPAWN_INTERVAL_SECONDS); horror_spawn_timer.timer.set_duration(std::time::Duration::from_secs_f32(new_duration)); } } // Renamed variables
-fn update_ingame_ui(player_query: Query<(&Survivor, &Health)>, game_state: Res<GameState>, mut ui_texts: ParamSet< ( Query<&mut Text, With<EnduranceText>>, Query<&mut Text, With<InsightText>>, Query<&mut Text, With<EchoesText>>, Query<&mut Text, With<ScoreText>>, Query<&mut Text, With<TimerText>>, Query<&mut Text, With<CycleText>>, )>,) { if let Ok((player_stats, player_health)) = player_query.get_single() { if let Ok(mut text) = ui_texts.p0().get_single_mut() { text.sections[0].value = format!("Endurance: {}/{}", player_health.0, player_stats.max_health); if player_health.0 < player_stats.max_health / 3 { text.sections[0].style.color = Color::RED; } else if player_health.0 < player_stats.max_health * 2 / 3 { text.sections[0].style.color = Color::YELLOW; } else { text.sections[0].style.color = Color::GREEN; } } if let Ok(mut text) = ui_texts.p1().get_single_mut() { text.sections[0].value = format!("Insight: {}", player_stats.level); } if let Ok(mut text) = ui_texts.p2().get_single_mut() { text.sections[0].value = format!("Echoes: {}/{}", player_stats.current_level_xp, player_stats.experience_to_next_level()); } } else { if let Ok(mut text) = ui_texts.p0().get_single_mut() { text.sections[0].value = "Endurance: --/--".to_string(); } if let Ok(mut text) = ui_texts.p1().get_single_mut() { text.sections[0].value = "Insight: --".to_string(); } if let Ok(mut text) = ui_texts.p2().get_single_mut() { text.sections[0].value = "Echoes: --/--".to_string(); } } if let Ok(mut text) = ui_texts.p3().get_single_mut() { text.sections[0].value = format!("Score: {}", game_state.score); } if let Ok(mut text) = ui_texts.p4().get_single_mut() { let elapsed_seconds = game_state.game_timer.elapsed().as_secs(); let minutes = elapsed_seconds / 60; let seconds = elapsed_seconds % 60; text.sections[0].value = format!("Time: {:02}:{:02}", minutes, seconds); } if let Ok(mut text) = ui_texts.p5().get_single_mut() { text.sections[0].value = format!("Cycle: {}", game_state.cycle_number); } }
-fn setup_level_up_ui(mut commands: Commands, asset_server: Res<AssetServer>, player_query: Query<&Survivor>, upgrade_pool: Res<UpgradePool>,) { let player_level = if let Ok(player) = player_query.get_single() { player.level } else { 0 }; let current_offered_upgrades = OfferedUpgrades { choices: upgrade_pool.get_random_upgrades(3) }; commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(30.0), ..default() }, background_color: Color::rgba(0.1, 0.1, 0.2, 0.9).into(), z_index: ZIndex::Global(10), ..default() }, LevelUpUI, current_offered_upgrades.clone(), )).with_children(|parent| { parent.spawn( TextBundle::from_section( format!("Revelation! Insight: {}", player_level), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 50.0, color: Color::GOLD, }, ).with_style(Style { margin: UiRect::bottom(Val::Px(20.0)), ..default()}) ); for (index, card) in current_offered_upgrades.choices.iter().enumerate() { parent.spawn(( ButtonBundle { style: Style { width: Val::Px(400.0), height: Val::Px(120.0), padding: UiRect::all(Val::Px(10.0)), justify_content: JustifyContent::Center, align_items: AlignItems::FlexStart, flex_direction: FlexDirection::Column, border: UiRect::all(Val::Px(2.0)), margin: UiRect::bottom(Val::Px(10.0)), ..default() }, border_color: BorderColor(Color::DARK_GRAY), background_color: Color::GRAY.into(), ..default() }, UpgradeButton(card.clone()), Name::new(format!("Upgrade Button {}", index + 1)), )).with_children(|button_parent| { button_parent.spawn(TextBundle::from_section( &card.name, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: Color::WHITE, }, ).with_style(Style { margin: UiRect::bottom(Val::Px(5.0)), ..default() })); button_parent.spawn(TextBundle::from_section( &card.description, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::rgb(0.9, 0.9, 0.9), }, )); }); } }); }
-fn handle_upgrade_choice_interaction(mut interaction_query: Query< (&Interaction, &UpgradeButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>), >, mut upgrade_chosen_event: EventWriter<UpgradeChosenEvent>, mut next_app_state: ResMut<NextState<AppState>>, keyboard_input: Res<ButtonInput<KeyCode>>, level_up_ui_query: Query<&OfferedUpgrades, With<LevelUpUI>>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (interaction, upgrade_button_data, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { sound_event_writer.send(PlaySoundEvent(SoundEffect::OmenAccepted)); upgrade_chosen_event.send(UpgradeChosenEvent(upgrade_button_data.0.clone())); next_app_state.set(AppState::InGame); return; } Interaction::Hovered => { *bg_color = Color::DARK_GREEN.into(); } Interaction::None => { *bg_color = Color::GRAY.into(); } } } if let Ok(offered) = level_up_ui_query.get_single() { let choice_made = if keyboard_input.just_pressed(KeyCode::Digit1) && offered.choices.len() > 0 { Some(offered.choices[0].clone()) } else if keyboard_input.just_pressed(KeyCode::Digit2) && offered.choices.len() > 1 { Some(offered.choices[1].clone()) } else if keyboard_input.just_pressed(KeyCode::Digit3) && offered.choices.len() > 2 { Some(offered.choices[2].clone()) } else { None }; if let Some(chosen_card) = choice_made { sound_event_writer.send(PlaySoundEvent(SoundEffect::OmenAccepted)); upgrade_chosen_event.send(UpgradeChosenEvent(chosen_card)); next_app_state.set(AppState::InGame); } } }
-fn apply_chosen_upgrade( mut events: EventReader<UpgradeChosenEvent>, mut player_query: Query<(&mut Survivor, &mut crate::player::SanityStrain, &mut Health, &mut CircleOfWarding, &mut SwarmOfNightmares)>, item_library: Res<ItemLibrary>, mut item_collected_writer: EventWriter<ItemCollectedEvent>, skill_library: Res<crate::skills::SkillLibrary>,) { for event in events.read() { let Ok((mut player_stats, mut sanity_strain, mut health_stats, mut circle_aura, mut nightmare_swarm)) = player_query.get_single_mut() else { continue; }; match &event.0.upgrade_type { UpgradeType::SurvivorSpeed(percentage) => { player_stats.speed *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::MaxEndurance(amount) => { player_stats.max_health += *amount; health_stats.0 += *amount; health_stats.0 = health_stats.0.min(player_stats.max_health); } UpgradeType::IchorBlastIntensity(bonus_amount) => { player_stats.ichor_blast_damage_bonus += *bonus_amount; } UpgradeType::IchorBlastSpeed(percentage) => { let reduction_factor = *percentage as f32 / 100.0; let new_base_fire_rate_secs = sanity_strain.base_fire_rate_secs * (1.0 - reduction_factor); sanity_strain.base_fire_rate_secs = new_base_fire_rate_secs.max(0.05); let timer_duration_val = sanity_strain.base_fire_rate_secs; sanity_strain.fire_timer.set_duration(std::time::Duration::from_secs_f32(timer_duration_val));} UpgradeType::IchorBlastVelocity(percentage_increase) => { player_stats.ichor_blast_speed_multiplier *= 1.0 + (*percentage_increase as f32 / 100.0); } UpgradeType::IchorBlastPiercing(amount) => { player_stats.ichor_blast_piercing += *amount; } UpgradeType::EchoesGainMultiplier(percentage) => { player_stats.xp_gain_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::SoulAttractionRadius(percentage) => { player_stats.pickup_radius_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::AdditionalIchorBlasts(amount) => { player_stats.additional_ichor_blasts += *amount; } UpgradeType::InscribeCircleOfWarding => { if !circle_aura.is_active { circle_aura.is_active = true; } else { circle_aura.base_damage_per_tick += 1; circle_aura.current_radius *= 1.1; }} UpgradeType::IncreaseCircleRadius(percentage) => { if circle_aura.is_active { circle_aura.current_radius *= 1.0 + (*percentage as f32 / 100.0); }} UpgradeType::IncreaseCircleDamage(amount) => { if circle_aura.is_active { circle_aura.base_damage_per_tick += *amount; }} UpgradeType::DecreaseCircleTickRate(percentage) => { if circle_aura.is_active { let reduction_factor = *percentage as f32 / 100.0; let current_tick_duration = circle_aura.damage_tick_timer.duration().as_secs_f32(); let new_tick_duration = (current_tick_duration * (1.0 - reduction_factor)).max(0.1); circle_aura.damage_tick_timer.set_duration(std::time::Duration::from_secs_f32(new_tick_duration)); } } UpgradeType::EnduranceRegeneration(amount) => { player_stats.health_regen_rate += *amount; } UpgradeType::ManifestSwarmOfNightmares => { if !nightmare_swarm.is_active { nightmare_swarm.is_active = true; nightmare_swarm.num_larvae = nightmare_swarm.num_larvae.max(2); } else { nightmare_swarm.num_larvae += 1; nightmare_swarm.damage_per_hit += 1; }} UpgradeType::IncreaseNightmareCount(count) => { if nightmare_swarm.is_active { nightmare_swarm.num_larvae += *count; }} UpgradeType::IncreaseNightmareDamage(damage) => { if nightmare_swarm.is_active { nightmare_swarm.damage_per_hit += *damage; }} UpgradeType::IncreaseNightmareRadius(radius_increase) => { if nightmare_swarm.is_active { nightmare_swarm.orbit_radius += *radius_increase; }} UpgradeType::IncreaseNightmareRotationSpeed(speed_increase) => { if nightmare_swarm.is_active { nightmare_swarm.rotation_speed += *speed_increase; }} UpgradeType::IncreaseSkillDamage { slot_index, amount } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.flat_damage_bonus += *amount; skill_instance.current_level += 1; } } UpgradeType::GrantRandomRelic => { if !item_library.items.is_empty() { let mut rng = rand::thread_rng(); if let Some(random_item_def) = item_library.items.choose(&mut rng) { item_collected_writer.send(ItemCollectedEvent(random_item_def.id)); } } } UpgradeType::GrantSkill(skill_id_to_grant) => { let already_has_skill = player_stats.equipped_skills.iter().any(|s| s.definition_id == *skill_id_to_grant); if !already_has_skill { if player_stats.equipped_skills.len() < 5 { if let Some(skill_def) = skill_library.get_skill_definition(*skill_id_to_grant) { player_stats.equipped_skills.push(ActiveSkillInstance::new(*skill_id_to_grant, skill_def.base_glyph_slots)); } } } } UpgradeType::ReduceSkillCooldown { slot_index, percent_reduction } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.cooldown_multiplier *= 1.0 - percent_reduction; skill_instance.cooldown_multiplier = skill_instance.cooldown_multiplier.max(0.1); skill_instance.current_level +=1; } } UpgradeType::IncreaseSkillAoERadius { slot_index, percent_increase } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.aoe_radius_multiplier *= 1.0 + percent_increase; skill_instance.current_level +=1; } } } } }
-fn setup_game_over_ui(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>) { commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(20.0), ..default() }, ..default() }, GameOverUI, )).with_children(|parent| { parent.spawn( TextBundle::from_section( "Consumed by Madness!", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 80.0, color: Color::RED, }, ).with_text_justify(JustifyText::Center) ); parent.spawn( TextBundle::from_section( format!("Score: {}", game_state.score), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 50.0, color: Color::WHITE, }, ).with_text_justify(JustifyText::Center) ); parent.spawn( TextBundle::from_section( "Succumb Again? (R)", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 40.0, color: Color::rgba(0.8,0.8,0.8,1.0), }, ).with_text_justify(JustifyText::Center) ); }); }
-fn game_over_input_system(mut commands: Commands, keyboard_input: Res<ButtonInput<KeyCode>>, mut next_app_state: ResMut<NextState<AppState>>, game_state: ResMut<GameState>, horror_spawn_timer: ResMut<HorrorSpawnTimer>, max_horrors: ResMut<MaxHorrors>, player_entity_query: Query<Entity, With<Survivor>>,) { if keyboard_input.just_pressed(KeyCode::KeyR) { for entity in player_entity_query.iter() { commands.entity(entity).despawn_recursive(); } reset_for_new_game_session(game_state, horror_spawn_timer, max_horrors); next_app_state.set(AppState::MainMenu); } } // Renamed variables
+fn difficulty_scaling_system(time: Res<Time>, mut game_state: ResMut<GameState>, mut horror_spawn_timer: ResMut<HorrorSpawnTimer>, mut max_horrors: ResMut<MaxHorrors>,) { if game_state.difficulty_timer.paused() { return; } game_state.difficulty_timer.tick(time.delta()); if game_state.difficulty_timer.just_finished() { game_state.cycle_number += 1; apply_cycle_scaling(game_state.cycle_number, &mut horror_spawn_timer, &mut max_horrors); } }
+// Horror cap and spawn interval a cycle has reached: every cycle raises the cap and shortens the interval, down to a floor
+pub fn cycle_spawn_scaling(cycle_number: u32) -> (u32, f32) { let cycles_endured = cycle_number.saturating_sub(1); ((INITIAL_MAX_HORRORS + cycles_endured * MAX_HORRORS_INCREMENT).min(200), (INITIAL_SPAWN_INTERVAL_SECONDS * SPAWN_INTERVAL_DECREMENT_FACTOR.powi(cycles_endured as i32)).max(MIN_SPAWN_INTERVAL_SECONDS)) }
+pub fn apply_cycle_scaling(cycle_number: u32, horror_spawn_timer: &mut HorrorSpawnTimer, max_horrors: &mut MaxHorrors) { let (cap, interval) = cycle_spawn_scaling(cycle_number); max_horrors.0 = cap; horror_spawn_timer.timer.set_duration(std::time::Duration::from_secs_f32(interval)); }
+#[allow(clippy::possible_missing_else)]
+fn update_ingame_ui(player_query: Query<(&Survivor, &Health)>, game_state: Res<GameState>, gold: Res<Gold>, mut ui_texts: ParamSet< ( Query<&mut Text, With<EnduranceText>>, Query<&mut Text, With<InsightText>>, Query<&mut Text, With<EchoesText>>, Query<&mut Text, With<ScoreText>>, Query<&mut Text, With<TimerText>>, Query<&mut Text, With<CycleText>>, Query<&mut Text, With<GoldText>>, )>,) { if let Ok((player_stats, player_health)) = player_query.get_single() { if let Ok(mut text) = ui_texts.p0().get_single_mut() { text.sections[0].value = format!("Endurance: {}/{}", player_health.0, player_stats.max_health); if player_health.0 < player_stats.max_health / 3 { text.sections[0].style.color = Color::RED; } else if player_health.0 < player_stats.max_health * 2 / 3 { text.sections[0].style.color = Color::YELLOW; } else { text.sections[0].style.color = Color::GREEN; } } if let Ok(mut text) = ui_texts.p1().get_single_mut() { text.sections[0].value = format!("Insight: {}", player_stats.level); } if let Ok(mut text) = ui_texts.p2().get_single_mut() { text.sections[0].value = format!("Echoes: {}/{}", player_stats.current_level_xp, player_stats.experience_to_next_level()); } } else { if let Ok(mut text) = ui_texts.p0().get_single_mut() { text.sections[0].value = "Endurance: --/--".to_string(); } if let Ok(mut text) = ui_texts.p1().get_single_mut() { text.sections[0].value = "Insight: --".to_string(); } if let Ok(mut text) = ui_texts.p2().get_single_mut() { text.sections[0].value = "Echoes: --/--".to_string(); } } if let Ok(mut text) = ui_texts.p3().get_single_mut() { text.sections[0].value = format!("Score: {}", game_state.score); } if let Ok(mut text) = ui_texts.p4().get_single_mut() { let elapsed_seconds = game_state.game_timer.elapsed().as_secs(); let minutes = elapsed_seconds / 60; let seconds = elapsed_seconds % 60; text.sections[0].value = format!("Time: {:02}:{:02}", minutes, seconds); } if let Ok(mut text) = ui_texts.p5().get_single_mut() { text.sections[0].value = format!("Cycle: {}", game_state.cycle_number); } if let Ok(mut text) = ui_texts.p6().get_single_mut() { text.sections[0].value = format!("Gold: {}", gold.0); } }
+fn setup_level_up_ui(mut commands: Commands, asset_server: Res<AssetServer>, player_query: Query<(&Survivor, &PlayerSlot)>, upgrade_pool: Res<UpgradePool>, mut pending_level_ups: ResMut<PendingLevelUps>, mut game_rng: ResMut<GameRng>, active_challenge: Res<ActiveChallenge>, mut choices: ResMut<LevelUpChoices>,) {
+    pending_level_ups.0 = pending_level_ups.0.saturating_sub(1);
+    let queued_level_ups = pending_level_ups.0;
+    let mut players: Vec<(PlayerSlot, u32, f32, bool)> = player_query.iter().map(|(player, slot)| (*slot, player.level, player.luck, player.can_add_glyph_slot())).collect();
+    players.sort_by_key(|(slot, ..)| *slot);
+    if players.is_empty() { players.push((PlayerSlot::default(), 0, 0.0, false)); }
+    // Every player gets an offer of their own, rolled with their own luck, and picks from it independently
+    *choices = LevelUpChoices::open(players.iter().map(|(slot, ..)| *slot));
+    let party_level = players.iter().map(|(_, level, ..)| *level).max().unwrap_or(0);
+    let shows_owner = players.len() > 1;
+    commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(30.0), ..default() }, background_color: Color::rgba(0.1, 0.1, 0.2, 0.9).into(), z_index: ZIndex::Global(10), ..default() }, LevelUpUI, )).with_children(|parent| {
+        parent.spawn( TextBundle::from_section( format!("Revelation! Insight: {}", party_level.saturating_sub(queued_level_ups)), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 50.0, color: Color::GOLD, }, ).with_style(Style { margin: UiRect::bottom(Val::Px(20.0)), ..default()}) );
+        if queued_level_ups > 0 { parent.spawn( TextBundle::from_section( format!("{} more revelation{} await", queued_level_ups, if queued_level_ups == 1 { "" } else { "s" }), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: Color::rgb(0.8, 0.8, 1.0), }, ) ); }
+        parent.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Row, column_gap: Val::Px(40.0), ..default() }, ..default() }).with_children(|row| {
+            for (slot, _, luck, can_add_glyph_slot) in players.iter() {
+                let offered = OfferedUpgrades { choices: upgrade_pool.get_random_allowed_upgrades(&mut game_rng.0, 3, *luck, |card| active_challenge.allows_upgrade(card) && (*can_add_glyph_slot || !matches!(card.upgrade_type, UpgradeType::AddGlyphSlot))) };
+                row.spawn(( NodeBundle { style: Style { flex_direction: FlexDirection::Column, align_items: AlignItems::Center, ..default() }, ..default() }, *slot, offered.clone(), )).with_children(|panel| {
+                    if shows_owner { panel.spawn( TextBundle::from_section( slot.label(), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 28.0, color: Color::rgb(0.8, 0.8, 1.0), }, ).with_style(Style { margin: UiRect::bottom(Val::Px(10.0)), ..default()}) ); }
+                    for (index, card) in offered.choices.iter().enumerate() { panel.spawn(( ButtonBundle { style: Style { width: Val::Px(400.0), height: Val::Px(120.0), padding: UiRect::all(Val::Px(10.0)), justify_content: JustifyContent::Center, align_items: AlignItems::FlexStart, flex_direction: FlexDirection::Column, border: UiRect::all(Val::Px(2.0)), margin: UiRect::bottom(Val::Px(10.0)), ..default() }, border_color: BorderColor(Color::DARK_GRAY), background_color: Color::GRAY.into(), ..default() }, UpgradeButton(card.clone()), *slot, Name::new(format!("Upgrade Button {}", index + 1)), )).with_children(|button_parent| { button_parent.spawn(TextBundle::from_section( &card.name, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: Color::WHITE, }, ).with_style(Style { margin: UiRect::bottom(Val::Px(5.0)), ..default() })); button_parent.spawn(TextBundle::from_section( &card.description, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::rgb(0.9, 0.9, 0.9), }, )); }); }
+                    panel.spawn(( TextBundle::from_section( "", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 20.0, color: Color::rgb(0.7, 1.0, 0.7), }, ).with_text_justify(JustifyText::Center), UpgradePreviewText, *slot, ));
+                });
+            }
+        });
+    });
+}
+// The run stays paused until every player has picked; a player who is done sees who the party is waiting on
+fn handle_upgrade_choice_interaction(mut commands: Commands, asset_server: Res<AssetServer>, mut interaction_query: Query< (&Interaction, &UpgradeButton, &PlayerSlot, &mut BackgroundColor), (Changed<Interaction>, With<Button>), >, panel_query: Query<(Entity, &OfferedUpgrades, &PlayerSlot)>, mut choices: ResMut<LevelUpChoices>, mut upgrade_chosen_event: EventWriter<UpgradeChosenEvent>, mut next_app_state: ResMut<NextState<AppState>>, actions: Res<ActionState>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) {
+    let mut picks: Vec<(PlayerSlot, UpgradeCard)> = Vec::new();
+    for (interaction, upgrade_button_data, slot, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { picks.push((*slot, upgrade_button_data.0.clone())); } Interaction::Hovered => { *bg_color = Color::DARK_GREEN.into(); } Interaction::None => { *bg_color = Color::GRAY.into(); } } }
+    for (_, offered, slot) in panel_query.iter() {
+        let Some(slot_actions) = CHOICE_ACTIONS.get(slot.0) else { continue; };
+        if let Some(card) = slot_actions.iter().zip(offered.choices.iter()).find(|(action, _)| actions.just_pressed(**action)).map(|(_, card)| card.clone()) { picks.push((*slot, card)); }
+    }
+    for (slot, chosen_card) in picks {
+        if !choices.is_choosing(slot) { continue; }
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted));
+        let after = state_after_upgrade_choice(&chosen_card);
+        upgrade_chosen_event.send(UpgradeChosenEvent(chosen_card, slot));
+        if let Some(next_state) = choices.complete(slot, after) { next_app_state.set(next_state); return; }
+        for (panel, _, _) in panel_query.iter().filter(|(_, _, panel_slot)| **panel_slot == slot) {
+            commands.entity(panel).despawn_descendants().with_children(|waiting| { waiting.spawn(TextBundle::from_section( waiting_label(&choices), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: Color::rgb(0.8, 0.8, 1.0), }, )); });
+        }
+    }
+}
+// Some cards need a follow-up choice before play resumes
+fn state_after_upgrade_choice(card: &UpgradeCard) -> AppState { match card.upgrade_type { UpgradeType::AddGlyphSlot => AppState::GlyphSlotChoice, _ => AppState::InGame, } }
+fn apply_chosen_upgrade( mut events: EventReader<UpgradeChosenEvent>, mut player_query: Query<(&mut Survivor, &mut crate::survivor::SanityStrain, &mut Health, &mut CircleOfWarding, &mut SwarmOfNightmares, &mut SearingGaze, &mut SporeCenser, &mut RearGuard, &mut SnareLayer, &mut HookedCrescent, &PlayerSlot)>, item_library: Res<ItemLibrary>, mut item_collected_writer: EventWriter<ItemCollectedEvent>, skill_library: Res<crate::skills::SkillLibrary>, mut game_rng: ResMut<GameRng>,) { for event in events.read() { let Some((mut player_stats, mut sanity_strain, mut health_stats, mut circle_aura, mut nightmare_swarm, mut searing_gaze, mut spore_censer, mut rear_guard, mut snare_layer, mut hooked_crescent, _)) = player_query.iter_mut().find(|(.., slot)| **slot == event.1) else { continue; }; match &event.0.upgrade_type { UpgradeType::SurvivorSpeed(percentage) => { player_stats.speed *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::MaxEndurance(amount) => { player_stats.max_health += *amount; health_stats.0 += *amount; health_stats.0 = health_stats.0.min(player_stats.max_health); } UpgradeType::IchorBlastIntensity(bonus_amount) => { player_stats.ichor_blast_damage_bonus += *bonus_amount; } UpgradeType::IchorBlastSpeed(percentage) => { let reduction_factor = *percentage as f32 / 100.0; let new_base_fire_rate_secs = sanity_strain.base_fire_rate_secs * (1.0 - reduction_factor); sanity_strain.base_fire_rate_secs = new_base_fire_rate_secs.max(0.05); let timer_duration_val = sanity_strain.base_fire_rate_secs; sanity_strain.fire_timer.set_duration(std::time::Duration::from_secs_f32(timer_duration_val));} UpgradeType::IchorBlastVelocity(percentage_increase) => { player_stats.ichor_blast_speed_multiplier *= 1.0 + (*percentage_increase as f32 / 100.0); } UpgradeType::IchorBlastPiercing(amount) => { player_stats.ichor_blast_piercing += *amount; } UpgradeType::EchoesGainMultiplier(percentage) => { player_stats.xp_gain_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::SoulAttractionRadius(percentage) => { player_stats.pickup_radius_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::AdditionalIchorBlasts(amount) => { player_stats.additional_ichor_blasts += *amount; } UpgradeType::AreaMultiplier(percentage) => { player_stats.area_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::CooldownReduction(percentage) => { player_stats.cooldown_reduction = (player_stats.cooldown_reduction + *percentage as f32 / 100.0).min(crate::survivor::MAX_COOLDOWN_REDUCTION); } UpgradeType::Luck(percentage) => { player_stats.luck += *percentage as f32 / 100.0; } UpgradeType::DurationMultiplier(percentage) => { player_stats.duration_multiplier *= 1.0 + (*percentage as f32 / 100.0); } UpgradeType::InscribeCircleOfWarding => { if !circle_aura.is_active { circle_aura.is_active = true; } else { circle_aura.base_damage_per_tick += 1; circle_aura.current_radius *= 1.1; }} UpgradeType::IncreaseCircleRadius(percentage) => { if circle_aura.is_active { circle_aura.current_radius *= 1.0 + (*percentage as f32 / 100.0); }} UpgradeType::IncreaseCircleDamage(amount) => { if circle_aura.is_active { circle_aura.base_damage_per_tick += *amount; }} UpgradeType::DecreaseCircleTickRate(percentage) => { if circle_aura.is_active { let reduction_factor = *percentage as f32 / 100.0; let current_tick_duration = circle_aura.damage_tick_timer.duration().as_secs_f32(); let new_tick_duration = (current_tick_duration * (1.0 - reduction_factor)).max(0.1); circle_aura.damage_tick_timer.set_duration(std::time::Duration::from_secs_f32(new_tick_duration)); } } UpgradeType::EnduranceRegeneration(amount) => { player_stats.health_regen_rate += *amount; } UpgradeType::ManifestSwarmOfNightmares => { if !nightmare_swarm.is_active { nightmare_swarm.is_active = true; nightmare_swarm.num_larvae = nightmare_swarm.num_larvae.max(2); } else { nightmare_swarm.num_larvae += 1; nightmare_swarm.damage_per_hit += 1; }} UpgradeType::IncreaseNightmareCount(count) => { if nightmare_swarm.is_active { nightmare_swarm.num_larvae += *count; }} UpgradeType::IncreaseNightmareDamage(damage) => { if nightmare_swarm.is_active { nightmare_swarm.damage_per_hit += *damage; }} UpgradeType::IncreaseNightmareRadius(radius_increase) => { if nightmare_swarm.is_active { nightmare_swarm.orbit_radius += *radius_increase; }} UpgradeType::IncreaseNightmareRotationSpeed(speed_increase) => { if nightmare_swarm.is_active { nightmare_swarm.rotation_speed += *speed_increase; }} UpgradeType::IncreaseSkillDamage { slot_index, amount } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.flat_damage_bonus += *amount; skill_instance.current_level += 1; } } UpgradeType::GrantRandomRelic => { if !item_library.items.is_empty() { let rng = &mut game_rng.0; if let Some(random_item_def) = item_library.items.choose(rng) { item_collected_writer.send(ItemCollectedEvent(random_item_def.id)); } } } UpgradeType::GrantSkill(skill_id_to_grant) => { if let Some(skill_def) = skill_library.get_skill_definition(*skill_id_to_grant) { player_stats.learn_skill(ActiveSkillInstance::new(*skill_id_to_grant, skill_def.base_glyph_slots)); } } UpgradeType::ReduceSkillCooldown { slot_index, percent_reduction } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.cooldown_multiplier *= 1.0 - percent_reduction; skill_instance.cooldown_multiplier = skill_instance.cooldown_multiplier.max(0.1); skill_instance.current_level +=1; } } UpgradeType::IncreaseSkillAoERadius { slot_index, percent_increase } => { if let Some(skill_instance) = player_stats.equipped_skills.get_mut(*slot_index) { skill_instance.aoe_radius_multiplier *= 1.0 + percent_increase; skill_instance.current_level +=1; } } UpgradeType::AddGlyphSlot => {} /* Applied by the GlyphSlotChoice screen */ UpgradeType::ManifestSearingGaze => { if !searing_gaze.is_active { searing_gaze.is_active = true; } else { searing_gaze.damage_per_sweep += 4; searing_gaze.range *= 1.1; }} UpgradeType::IncreaseGazeArc(degrees) => { if searing_gaze.is_active { searing_gaze.arc_degrees = (searing_gaze.arc_degrees + *degrees).min(360.0); }} UpgradeType::IncreaseGazeSweepSpeed(percentage) => { if searing_gaze.is_active { searing_gaze.sweep_speed_degrees *= 1.0 + (*percentage as f32 / 100.0); }} UpgradeType::ManifestSporeCenser => { if !spore_censer.is_active { spore_censer.is_active = true; } else { spore_censer.max_spores += 1; spore_censer.burst_damage += 3; }} UpgradeType::IncreaseSporeCount(count) => { if spore_censer.is_active { spore_censer.max_spores += *count; }} UpgradeType::IncreaseSporeBurstRadius(percentage) => { if spore_censer.is_active { spore_censer.burst_radius *= 1.0 + (*percentage as f32 / 100.0); }} UpgradeType::ManifestRearGuard => { if !rear_guard.is_active { rear_guard.is_active = true; } else { rear_guard.base_shots += 1; }} UpgradeType::IncreaseRearGuardDamage(amount) => { if rear_guard.is_active { rear_guard.damage_per_shot += *amount; }} UpgradeType::IncreaseRearGuardFireRate(percentage) => { if rear_guard.is_active { let new_duration = (rear_guard.fire_timer.duration().as_secs_f32() / (1.0 + *percentage as f32 / 100.0)).max(0.2); rear_guard.fire_timer.set_duration(std::time::Duration::from_secs_f32(new_duration)); }} UpgradeType::ManifestSnareLayer => { if !snare_layer.is_active { snare_layer.is_active = true; } else { snare_layer.damage += 5; snare_layer.max_snares += 1; }} UpgradeType::IncreaseSnareCount(count) => { if snare_layer.is_active { snare_layer.max_snares += *count; }} UpgradeType::IncreaseSnareBlastRadius(percentage) => { if snare_layer.is_active { snare_layer.blast_radius *= 1.0 + (*percentage as f32 / 100.0); }} UpgradeType::ManifestHookedCrescent => { if !hooked_crescent.is_active { hooked_crescent.is_active = true; } else { hooked_crescent.damage += 4; hooked_crescent.speed *= 1.1; }} UpgradeType::IncreaseCrescentDamage(amount) => { if hooked_crescent.is_active { hooked_crescent.damage += *amount; }} UpgradeType::IncreaseCrescentReach(percentage) => { if hooked_crescent.is_active { hooked_crescent.outbound_secs *= 1.0 + (*percentage as f32 / 100.0); }} } } }
+fn setup_glyph_slot_choice_ui(mut commands: Commands, asset_server: Res<AssetServer>, player_query: Query<&Survivor>, skill_library: Res<SkillLibrary>, glyph_library: Res<GlyphLibrary>, damage_type_style: Res<DamageTypeStyle>, mut next_app_state: ResMut<NextState<AppState>>,) {
+    let Ok(player) = player_query.get_single() else { next_app_state.set(AppState::InGame); return; };
+    if !player.can_add_glyph_slot() { next_app_state.set(AppState::InGame); return; }
+    commands.spawn((
+        NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(20.0), ..default() }, background_color: Color::rgba(0.1, 0.1, 0.2, 0.9).into(), z_index: ZIndex::Global(10), ..default() },
+        GlyphSlotChoiceUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Which skill receives the new socket?", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 40.0, color: Color::GOLD }));
+        for (index, skill_instance) in player.equipped_skills.iter().enumerate() {
+            let skill_def = skill_library.get_skill_definition(skill_instance.definition_id);
+            let skill_name = skill_def.map_or("Unknown Skill", |def| def.name.as_str());
+            let damage_type = skill_def.map_or(DamageType::default(), |def| skill_instance.damage_type(def, &glyph_library));
+            let has_room = skill_instance.equipped_glyphs.len() < MAX_GLYPH_SLOTS;
+            let label = format!("{}. {} [{}] ({}/{} sockets)", index + 1, skill_name, damage_type_style.label(damage_type), skill_instance.equipped_glyphs.len(), MAX_GLYPH_SLOTS);
+            let mut button = parent.spawn(ButtonBundle { style: Style { width: Val::Px(400.0), height: Val::Px(50.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() }, background_color: if has_room { Color::GRAY.into() } else { Color::rgb(0.2, 0.2, 0.2).into() }, ..default() });
+            button.with_children(|b| { b.spawn(TextBundle::from_section(label, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: if has_room { damage_type_style.color(damage_type) } else { Color::DARK_GRAY } })); });
+            if has_room { button.insert(GlyphSlotSkillButton(index)); }
+        }
+    });
+}
+fn handle_glyph_slot_choice_interaction(mut interaction_query: Query<(&Interaction, &GlyphSlotSkillButton, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>, actions: Res<ActionState>, mut player_query: Query<&mut Survivor>, mut next_app_state: ResMut<NextState<AppState>>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) {
+    let mut chosen_slot = None;
+    for (interaction, skill_button, mut bg_color) in interaction_query.iter_mut() { match *interaction { Interaction::Pressed => { chosen_slot = Some(skill_button.0); } Interaction::Hovered => { *bg_color = Color::DARK_GREEN.into(); } Interaction::None => { *bg_color = Color::GRAY.into(); } } }
+    if chosen_slot.is_none() { chosen_slot = actions.choice_just_pressed(); }
+    let (Some(slot_index), Ok(mut player)) = (chosen_slot, player_query.get_single_mut()) else { return; };
+    if player.equipped_skills.get_mut(slot_index).is_some_and(|skill_instance| skill_instance.add_glyph_slot()) { sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted)); next_app_state.set(AppState::InGame); }
+}
+fn setup_game_over_ui(mut commands: Commands, game_state: Res<GameState>, asset_server: Res<AssetServer>, outcome: Res<RunOutcome>, input_map: Res<InputMap>) { let (headline, headline_color, retry_question) = match *outcome { RunOutcome::Extracted => ("Victory! You Escaped the Abyss", Color::GOLD, "Descend Again?"), RunOutcome::Died => ("Consumed by Madness!", Color::RED, "Succumb Again?") }; let retry_prompt = format!("{} ({})", retry_question, input_map.bindings_label(InputAction::Confirm)); commands.spawn(( NodeBundle { style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(20.0), ..default() }, ..default() }, GameOverUI, )).with_children(|parent| { parent.spawn( TextBundle::from_section( headline, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 80.0, color: headline_color, }, ).with_text_justify(JustifyText::Center) ); parent.spawn( TextBundle::from_section( format!("Score: {}", game_state.score), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 50.0, color: Color::WHITE, }, ).with_text_justify(JustifyText::Center) ); parent.spawn( TextBundle::from_section( retry_prompt, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 40.0, color: Color::rgba(0.8,0.8,0.8,1.0), }, ).with_text_justify(JustifyText::Center) ); }); }
+fn game_over_input_system(mut commands: Commands, actions: Res<ActionState>, mut next_app_state: ResMut<NextState<AppState>>, game_state: ResMut<GameState>, horror_spawn_timer: ResMut<HorrorSpawnTimer>, max_horrors: ResMut<MaxHorrors>, pending_level_ups: ResMut<PendingLevelUps>, player_entity_query: Query<Entity, With<Survivor>>,) { if actions.just_pressed(InputAction::Confirm) { for entity in player_entity_query.iter() { commands.entity(entity).despawn_recursive(); } reset_for_new_game_session(game_state, horror_spawn_timer, max_horrors, pending_level_ups); next_app_state.set(AppState::MainMenu); } } // Renamed variables
 
 fn cleanup_session_entities(
     mut commands: Commands,
@@ -96,11 +205,17 @@ fn cleanup_session_entities(
     orbs_query: Query<Entity, With<EchoingSoul>>, // Renamed
     skill_projectiles_query: Query<Entity, With<crate::skills::SkillProjectile>>,
     skill_aoe_query: Query<Entity, With<crate::skills::ActiveSkillAoEEffect>>,
+    skill_beam_query: Query<Entity, With<crate::skills::ChanneledBeam>>,
+    lingering_skill_query: Query<Entity, Or<(With<crate::skills::DecoyEffect>, With<crate::skills::TimeBubbleEffect>, With<crate::skills::RicochetOrb>)>>,
+    weapon_effect_query: Query<Entity, Or<(With<crate::weapons::SearingGazeBeam>, With<crate::weapons::SeekingSpore>, With<crate::weapons::HexSnare>, With<crate::weapons::CrescentProjectile>)>>,
     // traps_query: Query<Entity, With<crate::skills::PlacedTrap>>, // Removed as PlacedTrap is removed
 ) {
     for entity in fragments_query.iter() { commands.entity(entity).despawn_recursive(); }
     for entity in orbs_query.iter() { commands.entity(entity).despawn_recursive(); }
     for entity in skill_projectiles_query.iter() { commands.entity(entity).despawn_recursive(); }
     for entity in skill_aoe_query.iter() { commands.entity(entity).despawn_recursive(); }
+    for entity in skill_beam_query.iter() { commands.entity(entity).despawn_recursive(); }
+    for entity in lingering_skill_query.iter() { commands.entity(entity).despawn_recursive(); }
+    for entity in weapon_effect_query.iter() { commands.entity(entity).despawn_recursive(); }
     // for entity in traps_query.iter() { commands.entity(entity).despawn_recursive(); } // Removed
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::{
+    components::DamageType,
+    horror::{HorrorType, ElitePhase},
+    skills::SkillId,
+    items::ItemId,
+    survivor::DamageSource,
+    weapons::AutoWeapon,
+    summons::SummonKind,
+    consumables::ConsumableKind,
+};
+
+pub struct GameEventsPlugin;
+
+impl Plugin for GameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GameEvent>();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HorrorKill {
+    pub horror_type: HorrorType,
+    pub position: Vec3,
+    pub is_elite: bool,
+    pub xp_value: u32,
+    // The skill that landed the killing blow, if it was a skill at all
+    pub skill: Option<SkillId>,
+}
+
+// What dealt a hit; rear guard volleys are ichor blasts, so they count as the basic weapon.
+// Anything spawned to fight on its own reports as a summon, never as the skill or weapon behind it.
+// Relic covers damage that collected items deal by themselves: on-hit explosions, retaliation novas and thorns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DamageOrigin {
+    IchorBlast,
+    Skill(SkillId),
+    Weapon(AutoWeapon),
+    Summon(SummonKind),
+    #[default]
+    Relic,
+    Consumable(ConsumableKind),
+}
+
+// One stream for everything notable that happens in a run; systems that care about an outcome read this
+// instead of reaching into the system that caused it
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    HorrorKilled(HorrorKill),
+    SkillCast { skill_id: SkillId, position: Vec3 },
+    DamageDealt { amount: i32, damage_type: DamageType, position: Vec3, origin: DamageOrigin },
+    SurvivorHit { damage: i32, source: DamageSource },
+    LevelUp { new_level: u32, levels_gained: u32 },
+    ReliquaryOpened { item_id: ItemId },
+    EliteSpawned { horror_type: HorrorType, position: Vec3 },
+    ElitePhaseChanged { horror_type: HorrorType, phase: ElitePhase, position: Vec3 },
+    ExtractionRiftOpened { position: Vec3 },
+    EscortFinished { delivered: bool, position: Vec3 },
+}
+
+impl GameEvent {
+    // Lets kill listeners keep their loops as they were: `events.read().filter_map(GameEvent::as_horror_kill)`
+    pub fn as_horror_kill(&self) -> Option<&HorrorKill> {
+        match self {
+            GameEvent::HorrorKilled(kill) => Some(kill),
+            _ => None,
+        }
+    }
+}
@@ -1,14 +1,30 @@
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use crate::{
+    components::DamageType,
+    game::{AppState, GameRng},
+    game_events::GameEvent,
+    survivor::Survivor,
+    luck::roll_with_luck,
+};
 // use crate::skills::SkillId; // Removed unused import
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
 pub struct GlyphId(pub u32);
 
+pub const ELITE_GLYPH_DROP_CHANCE: f64 = 0.08;
+
 #[derive(Debug, Clone, PartialEq, Reflect)]
 pub enum GlyphEffectType {
     ProjectileChain { bounces: u32, },
     IncreasedAoEDamage { percent_increase: f32, },
     AddedChaosDamageToProjectile { damage_amount: i32, },
+    SplitOnHit { fragments: u32, angle: f32, },
+    ConvertDamageType { damage_type: DamageType, },
+    CooldownResetOnKill { chance: f32, },
+    IncreasedProjectileSize { percent_increase: f32, },
+    IncreasedDuration { percent_increase: f32, },
+    OverkillCarryover { fraction: f32, },
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -41,7 +57,23 @@ impl Plugin for GlyphsPlugin {
             .register_type::<GlyphDefinition>()
             .register_type::<GlyphLibrary>()
             .init_resource::<GlyphLibrary>()
-            .add_systems(Startup, populate_glyph_library);
+            .add_systems(Startup, populate_glyph_library)
+            .add_systems(Update, elite_glyph_drop_system.run_if(in_state(AppState::InGame)));
+    }
+}
+
+// Glyphs found on elites go straight into the survivor's glyph inventory, ready to be socketed
+fn elite_glyph_drop_system(
+    mut game_events: EventReader<GameEvent>,
+    mut player_query: Query<&mut Survivor>,
+    glyph_library: Res<GlyphLibrary>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Ok(mut survivor) = player_query.get_single_mut() else { return; };
+    let rng = &mut game_rng.0;
+    for event in game_events.read().filter_map(GameEvent::as_horror_kill) {
+        if !event.is_elite || !roll_with_luck(rng, ELITE_GLYPH_DROP_CHANCE, survivor.luck) { continue; }
+        if let Some(glyph_def) = glyph_library.glyphs.choose(rng) { survivor.collected_glyphs.push(glyph_def.id); }
     }
 }
 
@@ -64,4 +96,40 @@ fn populate_glyph_library(mut library: ResMut<GlyphLibrary>) {
         description: "Your projectiles deal an additional 10 chaos damage.".to_string(),
         effect: GlyphEffectType::AddedChaosDamageToProjectile { damage_amount: 10 },
     });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(4),
+        name: "Glyph of Sundered Thought".to_string(),
+        description: "Your projectiles burst into 3 smaller fragments when they strike or fade.".to_string(),
+        effect: GlyphEffectType::SplitOnHit { fragments: 3, angle: 50.0f32.to_radians() },
+    });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(5),
+        name: "Frostbind".to_string(),
+        description: "Your projectiles deal Frost damage instead and chill the horrors they strike.".to_string(),
+        effect: GlyphEffectType::ConvertDamageType { damage_type: DamageType::Frost },
+    });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(6),
+        name: "Glyph of Hungering Echoes".to_string(),
+        description: "Killing blows from this skill have a 15% chance to instantly reset its cooldown.".to_string(),
+        effect: GlyphEffectType::CooldownResetOnKill { chance: 0.15 },
+    });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(7),
+        name: "Glyph of Swollen Malice".to_string(),
+        description: "Your projectiles grow 40% larger, widening what they can strike.".to_string(),
+        effect: GlyphEffectType::IncreasedProjectileSize { percent_increase: 0.40 },
+    });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(8),
+        name: "Glyph of the Lingering Hour".to_string(),
+        description: "Projectiles and ground effects from this skill last 35% longer.".to_string(),
+        effect: GlyphEffectType::IncreasedDuration { percent_increase: 0.35 },
+    });
+    library.glyphs.push(GlyphDefinition {
+        id: GlyphId(9),
+        name: "Glyph of the Gorged Wound".to_string(),
+        description: "Projectiles from this skill carry 75% of the damage wasted on a kill into their next hit.".to_string(),
+        effect: GlyphEffectType::OverkillCarryover { fraction: 0.75 },
+    });
 }
\ No newline at end of file
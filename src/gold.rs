@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::{
+    survivor::{Survivor, SURVIVOR_SIZE},
+    components::Velocity,
+    game::{AppState, GameRng},
+    game_events::GameEvent,
+    items::{ItemLibrary, ItemEffect},
+    audio::{PlaySoundEvent, SoundEffect},
+    taunt::{Taunting, pickup_radius_multiplier},
+};
+
+pub const GOLD_COIN_SIZE: Vec2 = Vec2::new(12.0, 12.0);
+const ELITE_GOLD_COINS: u32 = 3;
+const ELITE_GOLD_COIN_VALUE: u32 = 5;
+const COIN_SCATTER_RADIUS: f32 = 25.0;
+const COIN_GRAVITATE_SPEED: f32 = 350.0;
+const COIN_PICKUP_RADIUS_COLLISION: f32 = SURVIVOR_SIZE.x / 2.0 + GOLD_COIN_SIZE.x / 2.0;
+
+pub struct GoldPlugin;
+
+impl Plugin for GoldPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Gold>()
+            .add_systems(OnExit(AppState::MainMenu), reset_gold)
+            .add_systems(Update, (
+                gold_on_horror_death_system,
+                gold_coin_gravitation_system,
+                gold_coin_collection_system,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), despawn_gold_coins)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_gold_coins);
+    }
+}
+
+// Run currency, spent in the shop
+#[derive(Resource, Default, Debug)]
+pub struct Gold(pub u32);
+
+impl Gold {
+    pub fn add(&mut self, amount: u32) { self.0 = self.0.saturating_add(amount); }
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        if self.0 < amount { return false; }
+        self.0 -= amount;
+        true
+    }
+}
+
+#[derive(Component)]
+pub struct GoldCoin {
+    pub value: u32,
+}
+
+pub fn spawn_gold_coin(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    position: Vec3,
+    value: u32,
+) {
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/echoing_soul_orb_placeholder.png"),
+            sprite: Sprite {
+                custom_size: Some(GOLD_COIN_SIZE),
+                color: Color::GOLD,
+                ..default()
+            },
+            transform: Transform::from_translation(position.truncate().extend(0.35)),
+            ..default()
+        },
+        GoldCoin { value },
+        Velocity(Vec2::ZERO),
+        Name::new("GoldCoin"),
+    ));
+}
+
+fn reset_gold(mut gold: ResMut<Gold>) {
+    gold.0 = 0;
+}
+
+fn gold_on_horror_death_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_events: EventReader<GameEvent>,
+    mut gold: ResMut<Gold>,
+    player_query: Query<&Survivor>,
+    item_library: Res<ItemLibrary>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let gold_per_kill: u32 = player_query.get_single().map_or(0, |player| {
+        player.collected_item_ids.iter()
+            .filter_map(|item_id| item_library.get_item_definition(*item_id))
+            .flat_map(|item_def| item_def.effects.iter())
+            .filter_map(|effect| if let ItemEffect::GoldOnKill { amount } = effect { Some(*amount) } else { None })
+            .sum()
+    });
+    let rng = &mut game_rng.0;
+    for event in game_events.read().filter_map(GameEvent::as_horror_kill) {
+        gold.add(gold_per_kill);
+        if !event.is_elite { continue; }
+        for _ in 0..ELITE_GOLD_COINS {
+            let offset = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * COIN_SCATTER_RADIUS;
+            spawn_gold_coin(&mut commands, &asset_server, event.position + offset.extend(0.0), ELITE_GOLD_COIN_VALUE);
+        }
+    }
+}
+
+fn gold_coin_gravitation_system(
+    mut coin_query: Query<(&mut Transform, &mut Velocity), With<GoldCoin>>,
+    player_query: Query<(&Transform, &Survivor, Option<&Taunting>), Without<GoldCoin>>,
+    time: Res<Time>,
+) {
+    let Ok((player_transform, player_stats, taunting)) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let gravitate_radius = player_stats.get_effective_pickup_radius() * pickup_radius_multiplier(taunting);
+
+    for (mut coin_transform, mut coin_velocity) in coin_query.iter_mut() {
+        let coin_pos = coin_transform.translation.truncate();
+        if player_pos.distance(coin_pos) < gravitate_radius {
+            coin_velocity.0 = (player_pos - coin_pos).normalize_or_zero() * COIN_GRAVITATE_SPEED;
+        }
+        coin_transform.translation.x += coin_velocity.0.x * time.delta_seconds();
+        coin_transform.translation.y += coin_velocity.0.y * time.delta_seconds();
+    }
+}
+
+fn gold_coin_collection_system(
+    mut commands: Commands,
+    coin_query: Query<(Entity, &Transform, &GoldCoin)>,
+    player_query: Query<&Transform, With<Survivor>>,
+    mut gold: ResMut<Gold>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    for (coin_entity, coin_transform, coin) in coin_query.iter() {
+        if player_pos.distance(coin_transform.translation.truncate()) < COIN_PICKUP_RADIUS_COLLISION {
+            gold.add(coin.value);
+            sound_event_writer.send(PlaySoundEvent::global(SoundEffect::SoulCollect));
+            commands.entity(coin_entity).despawn_recursive();
+        }
+    }
+}
+
+fn despawn_gold_coins(mut commands: Commands, coin_query: Query<Entity, With<GoldCoin>>) {
+    for coin_entity in coin_query.iter() {
+        commands.entity(coin_entity).despawn_recursive();
+    }
+}
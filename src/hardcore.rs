@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use crate::{
+    game::AppState,
+    extraction::RunOutcome,
+    ranking::grade_finished_run,
+};
+
+pub const HARDCORE_TOGGLE_KEY: KeyCode = KeyCode::KeyH;
+// Share of the unbanked pool lost when a hardcore run ends in death
+pub const HARDCORE_DEATH_WIPE: f32 = 0.5;
+pub const HARDCORE_EXTRACTION_MULTIPLIER: f32 = 1.5;
+
+pub struct HardcorePlugin;
+
+impl Plugin for HardcorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HardcoreMode>()
+            .init_resource::<UnbankedRemnants>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_hardcore_menu_text)
+            .add_systems(Update, (toggle_hardcore_mode, update_hardcore_menu_text).chain().run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), (despawn_hardcore_menu_text, clear_hardcore_settlement))
+            .add_systems(OnEnter(AppState::GameOver), setup_hardcore_result_ui.after(grade_finished_run))
+            .add_systems(OnExit(AppState::GameOver), despawn_hardcore_result_ui);
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct HardcoreMode {
+    pub enabled: bool,
+}
+
+// Remnants earned on hardcore runs wait here, across runs, until an extraction banks them
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct UnbankedRemnants(pub u32);
+
+// How a finished hardcore run split the pool
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardcoreSettlement {
+    pub outcome: RunOutcome,
+    pub banked: u32,
+    pub unbanked: u32,
+    pub lost: u32,
+}
+
+// The run's earnings join the pool first, so dying costs part of this run too, and extracting multiplies everything carried
+pub fn settle_hardcore_run(unbanked: u32, earned: u32, outcome: RunOutcome) -> HardcoreSettlement {
+    let pool = unbanked.saturating_add(earned);
+    match outcome {
+        RunOutcome::Extracted => HardcoreSettlement { outcome, banked: (pool as f32 * HARDCORE_EXTRACTION_MULTIPLIER).round() as u32, unbanked: 0, lost: 0 },
+        RunOutcome::Died => {
+            let lost = (pool as f32 * HARDCORE_DEATH_WIPE).round() as u32;
+            HardcoreSettlement { outcome, banked: 0, unbanked: pool - lost, lost }
+        }
+    }
+}
+
+#[derive(Component)]
+struct HardcoreMenuText;
+
+#[derive(Component)]
+struct HardcoreResultUI;
+
+fn hardcore_menu_label(hardcore: &HardcoreMode, unbanked: &UnbankedRemnants) -> String {
+    format!("Hardcore: {} (H)   Unbanked remnants: {}", if hardcore.enabled { "ON" } else { "OFF" }, unbanked.0)
+}
+
+fn setup_hardcore_menu_text(mut commands: Commands, asset_server: Res<AssetServer>, hardcore: Res<HardcoreMode>, unbanked: Res<UnbankedRemnants>) {
+    commands.spawn((
+        TextBundle::from_section(hardcore_menu_label(&hardcore, &unbanked), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: Color::rgb(1.0, 0.45, 0.4) })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(20.0), right: Val::Px(20.0), ..default() }),
+        HardcoreMenuText,
+    ));
+}
+
+fn toggle_hardcore_mode(keyboard_input: Res<ButtonInput<KeyCode>>, mut hardcore: ResMut<HardcoreMode>) {
+    if keyboard_input.just_pressed(HARDCORE_TOGGLE_KEY) { hardcore.enabled = !hardcore.enabled; }
+}
+
+fn update_hardcore_menu_text(hardcore: Res<HardcoreMode>, unbanked: Res<UnbankedRemnants>, mut text_query: Query<&mut Text, With<HardcoreMenuText>>) {
+    if !hardcore.is_changed() { return; }
+    for mut text in text_query.iter_mut() { text.sections[0].value = hardcore_menu_label(&hardcore, &unbanked); }
+}
+
+fn despawn_hardcore_menu_text(mut commands: Commands, text_query: Query<Entity, With<HardcoreMenuText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn clear_hardcore_settlement(mut commands: Commands) {
+    commands.remove_resource::<HardcoreSettlement>();
+}
+
+fn setup_hardcore_result_ui(mut commands: Commands, asset_server: Res<AssetServer>, settlement: Option<Res<HardcoreSettlement>>) {
+    let Some(settlement) = settlement else { return; };
+    let summary = match settlement.outcome {
+        RunOutcome::Extracted => format!("Hardcore extraction: {} remnants banked (x{})", settlement.banked, HARDCORE_EXTRACTION_MULTIPLIER),
+        RunOutcome::Died => format!("Hardcore death: {} unbanked remnants lost, {} still unbanked", settlement.lost, settlement.unbanked),
+    };
+    commands.spawn((
+        TextBundle::from_section(summary, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: Color::rgb(1.0, 0.45, 0.4) })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(90.0), right: Val::Px(40.0), ..default() }),
+        HardcoreResultUI,
+    ));
+}
+
+fn despawn_hardcore_result_ui(mut commands: Commands, ui_query: Query<Entity, With<HardcoreResultUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -2,29 +2,41 @@ use bevy::prelude::*;
 use rand::{Rng, seq::SliceRandom};
 use std::time::Duration; // Ensured Duration is imported
 use crate::{
-    components::{Velocity, Health, Damage, Lifetime},
-    player::Survivor,
-    game::{AppState, GameState},
+    components::{Velocity, Health, MaxHealth, Damage, Lifetime, ProjectileOwner, Reflectable, TimeScale, DamageType, SpawnExclusion},
+    ichor_blast::IchorBlast,
+    survivor::Survivor,
+    game::{AppState, GameConfig, GameRng, GameState},
+    camera_systems::{MainCamera, view_half_extents},
     audio::{PlaySoundEvent, SoundEffect},
     items::{ItemDrop, ItemLibrary, ITEM_DROP_SIZE, ItemEffect, SurvivorTemporaryBuff, TemporaryHealthRegenBuff},
-    experience::{spawn_echoing_soul, ECHOING_SOUL_VALUE},
+    echoing_soul::spawn_echoing_soul,
+    skills::LastHitBySkill,
+    game_events::{GameEvent, HorrorKill},
+    luck::roll_with_luck,
+    projectile::{ProjectileSpec, ProjectileVisual, spawn_projectile},
+    horror_stats::HorrorStatsLibrary,
+    survivor::DamageSource,
+    challenge::ActiveChallenge,
+    corruption::RunDifficulty,
+    aggro::{ThreatMap, ThreatAssessment},
+    balance::BalanceConfig,
+    visual_effects::{spawn_impact_effect, DamageTypeStyle},
+    perf_overlay::timed_collision,
 };
+use serde::{Deserialize, Serialize};
 
 #[derive(Component, Debug)]
 pub struct Frozen { pub timer: Timer, pub speed_multiplier: f32, }
 
-pub const SKITTERING_SHADOWLIMG_SIZE: Vec2 = Vec2::new(35.0, 35.0);
-pub const FLOATING_EYEBALL_SIZE: Vec2 = Vec2::new(40.0, 40.0);
-pub const AMORPHOUS_FLESHBEAST_SIZE: Vec2 = Vec2::new(60.0, 60.0);
-pub const VOID_BLINKER_SIZE: Vec2 = Vec2::new(30.0, 45.0);
-pub const FLESH_WEAVER_SIZE: Vec2 = Vec2::new(45.0, 45.0);
-pub const CRAWLING_TORMENT_SIZE: Vec2 = Vec2::new(25.0, 25.0);
-pub const FRENZIED_BEHEMOTH_SIZE: Vec2 = Vec2::new(55.0, 50.0);
+pub const FROST_CHILL_SPEED_MULTIPLIER: f32 = 0.7;
+pub const FROST_CHILL_DURATION_SECS: f32 = 1.5;
 
-const ITEM_DROP_CHANCE: f64 = 0.05;
-const MINION_ITEM_DROP_CHANCE: f64 = 0.01;
 const ELITE_ITEM_DROP_CHANCE_BONUS: f64 = 0.10;
-const ELITE_SPAWN_CHANCE: f64 = 0.05;
+pub const ELITE_SPAWN_CHANCE: f64 = 0.05;
+const SPAWN_PLACEMENT_ATTEMPTS: usize = 8;
+// Share of max health at or below which an elite enters its wounded and final phases
+pub const ELITE_WOUNDED_HEALTH_FRACTION: f32 = 2.0 / 3.0;
+pub const ELITE_FINAL_HEALTH_FRACTION: f32 = 1.0 / 3.0;
 
 const REPOSITION_DURATION_SECONDS: f32 = 1.5;
 const REPOSITION_SPEED_MULTIPLIER: f32 = 0.7;
@@ -48,31 +60,58 @@ const CHARGER_MIN_CHARGE_RANGE: f32 = 100.0;
 #[derive(Resource)]
 pub struct MaxHorrors(pub u32);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+const SLOW_FRAME_THRESHOLD_SECS: f32 = 1.0 / 40.0;
+const SLOW_FRAMES_BEFORE_THROTTLE: u32 = 30;
+const SPAWN_THROTTLE_DURATION_SECS: f32 = 6.0;
+const THROTTLED_SPAWN_BUDGET_FRACTION: f32 = 0.5;
+pub const CONTACT_HIT_COOLDOWN_SECS: f32 = 0.8;
+pub const CONTACT_KNOCKBACK_DISTANCE: f32 = 14.0;
+
+// Adaptive spawning for weak hardware: a streak of slow frames cuts the horror budget for a while
+#[derive(Resource, Default, Debug)]
+pub struct SpawnThrottle { pub slow_frame_streak: u32, pub remaining_secs: f32, }
+
+impl SpawnThrottle {
+    pub fn record_frame(&mut self, frame_secs: f32) {
+        self.remaining_secs = (self.remaining_secs - frame_secs).max(0.0);
+        if frame_secs <= SLOW_FRAME_THRESHOLD_SECS { self.slow_frame_streak = 0; return; }
+        self.slow_frame_streak += 1;
+        if self.slow_frame_streak >= SLOW_FRAMES_BEFORE_THROTTLE { self.remaining_secs = SPAWN_THROTTLE_DURATION_SECS; self.slow_frame_streak = 0; }
+    }
+    pub fn is_active(&self) -> bool { self.remaining_secs > 0.0 }
+    pub fn spawn_budget(&self, max_horrors: u32) -> u32 { if self.is_active() { (max_horrors as f32 * THROTTLED_SPAWN_BUDGET_FRACTION) as u32 } else { max_horrors } }
+}
+
+// Damage a horror deals by touching the survivor; each horror keeps its own cooldown so a crowd isn't gated by one shared timer
+#[derive(Component, Debug)]
+pub struct ContactDamage { pub damage: i32, pub cooldown: Timer, }
+
+impl ContactDamage {
+    pub fn new(damage: i32) -> Self { let mut cooldown = Timer::from_seconds(CONTACT_HIT_COOLDOWN_SECS, TimerMode::Once); cooldown.tick(cooldown.duration()); Self { damage, cooldown } }
+    pub fn tick(&mut self, delta: Duration) { self.cooldown.tick(delta); }
+    pub fn try_hit(&mut self) -> Option<i32> { if !self.cooldown.finished() { return None; } self.cooldown.reset(); Some(self.damage) }
+}
+
+// Offset that shoves the survivor directly away from a horror it touched
+pub fn contact_knockback(survivor_pos: Vec2, horror_pos: Vec2) -> Vec2 { (survivor_pos - horror_pos).try_normalize().unwrap_or(Vec2::X) * CONTACT_KNOCKBACK_DISTANCE }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HorrorType {
     SkitteringShadowling, FloatingEyeball, AmorphousFleshbeast, VoidBlinker, FleshWeaver, CrawlingTorment, FrenziedBehemoth,
 }
+// How much tougher horrors spawned in a given cycle are than in the first
+pub fn cycle_stat_multiplier(cycle_number: u32) -> f32 { 1.0 + (cycle_number as f32 - 1.0) * 0.1 }
+// For now, summoners and chargers don't become elite
+pub fn can_be_elite(horror_type: HorrorType) -> bool { !matches!(horror_type, HorrorType::CrawlingTorment | HorrorType::FleshWeaver | HorrorType::FrenziedBehemoth) }
+impl HorrorType { pub const ALL: [HorrorType; 7] = [HorrorType::SkitteringShadowling, HorrorType::FloatingEyeball, HorrorType::AmorphousFleshbeast, HorrorType::VoidBlinker, HorrorType::FleshWeaver, HorrorType::CrawlingTorment, HorrorType::FrenziedBehemoth]; }
 
 pub struct HorrorStats {
     pub horror_type: HorrorType, pub health: i32, pub damage_on_collision: i32, pub speed: f32, pub size: Vec2,
-    pub sprite_path: &'static str, pub projectile_range: Option<f32>, pub projectile_fire_rate: Option<f32>,
+    pub sprite_path: String, pub projectile_range: Option<f32>, pub projectile_fire_rate: Option<f32>,
     pub projectile_speed: Option<f32>, pub projectile_damage: Option<i32>, pub xp_value: u32,
     pub item_drop_chance_override: Option<f64>,
 }
 
-impl HorrorStats {
-    fn get_for_type(horror_type: HorrorType, wave_multiplier: f32) -> Self {
-        match horror_type {
-            HorrorType::SkitteringShadowling => HorrorStats { horror_type, health: (20.0 * wave_multiplier).max(1.0) as i32, damage_on_collision: 10, speed: 100.0 + 20.0 * (wave_multiplier - 1.0).max(0.0), size: SKITTERING_SHADOWLIMG_SIZE, sprite_path: "sprites/skittering_shadowling_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE, item_drop_chance_override: Some(ITEM_DROP_CHANCE), },
-            HorrorType::FloatingEyeball => HorrorStats { horror_type, health: (15.0 * wave_multiplier).max(1.0) as i32, damage_on_collision: 5, speed: 70.0 + 15.0 * (wave_multiplier - 1.0).max(0.0), size: FLOATING_EYEBALL_SIZE, sprite_path: "sprites/floating_eyeball_placeholder.png", projectile_range: Some(350.0), projectile_fire_rate: Some(2.8), projectile_speed: Some(280.0), projectile_damage: Some(10), xp_value: ECHOING_SOUL_VALUE + 5, item_drop_chance_override: Some(ITEM_DROP_CHANCE + 0.02), },
-            HorrorType::AmorphousFleshbeast => HorrorStats { horror_type, health: (60.0 * wave_multiplier * 1.5).max(1.0) as i32, damage_on_collision: 20, speed: 50.0 + 10.0 * (wave_multiplier - 1.0).max(0.0), size: AMORPHOUS_FLESHBEAST_SIZE, sprite_path: "sprites/amorphous_fleshbeast_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE + 15, item_drop_chance_override: Some(ITEM_DROP_CHANCE + 0.05), },
-            HorrorType::VoidBlinker => HorrorStats { horror_type, health: (30.0 * wave_multiplier).max(1.0) as i32, damage_on_collision: 15, speed: 110.0 + 20.0 * (wave_multiplier - 1.0).max(0.0), size: VOID_BLINKER_SIZE, sprite_path: "sprites/void_blinker_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE + 10, item_drop_chance_override: Some(ITEM_DROP_CHANCE + 0.03), },
-            HorrorType::FleshWeaver => HorrorStats { horror_type, health: (40.0 * wave_multiplier * 1.2).max(1.0) as i32, damage_on_collision: 8, speed: 60.0 + 10.0 * (wave_multiplier - 1.0).max(0.0), size: FLESH_WEAVER_SIZE, sprite_path: "sprites/flesh_weaver_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE + 20, item_drop_chance_override: Some(ITEM_DROP_CHANCE + 0.07), },
-            HorrorType::CrawlingTorment => HorrorStats { horror_type, health: (5.0 * wave_multiplier).max(1.0) as i32, damage_on_collision: 5, speed: 120.0 + 10.0 * (wave_multiplier - 1.0).max(0.0), size: CRAWLING_TORMENT_SIZE, sprite_path: "sprites/crawling_torment_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE / 5, item_drop_chance_override: Some(MINION_ITEM_DROP_CHANCE), },
-            HorrorType::FrenziedBehemoth => HorrorStats { horror_type, health: (70.0 * wave_multiplier * 1.3).max(1.0) as i32, damage_on_collision: 25, speed: 80.0 + 15.0 * (wave_multiplier - 1.0).max(0.0), size: FRENZIED_BEHEMOTH_SIZE, sprite_path: "sprites/frenzied_behemoth_placeholder.png", projectile_range: None, projectile_fire_rate: None, projectile_speed: None, projectile_damage: None, xp_value: ECHOING_SOUL_VALUE + 25, item_drop_chance_override: Some(ITEM_DROP_CHANCE + 0.1), },
-        }
-    }
-}
 
 #[derive(Component)]
 pub struct Horror {
@@ -80,6 +119,17 @@ pub struct Horror {
     pub xp_value: u32, pub item_drop_chance: f64, pub is_elite: bool,
 }
 
+// Elites stand in for bosses until there are any: each third of health lost moves them on a phase, and phases never go back
+#[derive(Component, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ElitePhase { #[default] Opening, Wounded, Final, }
+impl ElitePhase {
+    pub fn for_health(health: i32, max_health: i32) -> Self { if max_health <= 0 { return ElitePhase::Opening; } let fraction = health as f32 / max_health as f32; if fraction <= ELITE_FINAL_HEALTH_FRACTION { ElitePhase::Final } else if fraction <= ELITE_WOUNDED_HEALTH_FRACTION { ElitePhase::Wounded } else { ElitePhase::Opening } }
+}
+
+// What sets an elite apart; each kind gets its own outline and aura so it reads at a glance. Only the stat boost exists so far
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EliteModifier { Empowered, }
+
 #[derive(Component)]
 pub struct RangedAttackerBehavior { pub shooting_range: f32, pub fire_timer: Timer, pub projectile_speed: f32, pub projectile_damage: i32, pub state: RangedAttackerState, pub reposition_target: Option<Vec2>, pub reposition_timer: Timer, }
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -108,31 +158,30 @@ const HORROR_PROJECTILE_COLOR: Color = Color::rgb(0.3, 0.8, 0.4);
 const HORROR_PROJECTILE_LIFETIME: f32 = 3.5;
 const HORROR_PROJECTILE_Z_POS: f32 = 0.7;
 
-fn spawn_horror_projectile( commands: &mut Commands, asset_server: &Res<AssetServer>, mut position: Vec3, direction: Vec2, speed: f32, damage: i32,) {
-    position.z = HORROR_PROJECTILE_Z_POS;
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("sprites/horror_ichor_blast_placeholder.png"),
-            sprite: Sprite { custom_size: Some(HORROR_PROJECTILE_SPRITE_SIZE), color: HORROR_PROJECTILE_COLOR, ..default() },
-            visibility: Visibility::Visible,
-            transform: Transform::from_translation(position).with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
-            ..default()
-        },
-        HorrorProjectile, Velocity(direction * speed), Damage(damage),
-        Lifetime { timer: Timer::from_seconds(HORROR_PROJECTILE_LIFETIME, TimerMode::Once)},
-        Name::new("HorrorIchorBlast"),
-    ));
+fn spawn_horror_projectile( commands: &mut Commands, asset_server: &Res<AssetServer>, position: Vec3, direction: Vec2, speed: f32, damage: i32,) {
+    let visual = ProjectileVisual { texture: asset_server.load("sprites/horror_ichor_blast_placeholder.png"), size: HORROR_PROJECTILE_SPRITE_SIZE, color: HORROR_PROJECTILE_COLOR, z: Some(HORROR_PROJECTILE_Z_POS) };
+    spawn_projectile(commands, ProjectileSpec::new(ProjectileOwner::Horror, damage, speed, HORROR_PROJECTILE_LIFETIME, visual, "HorrorIchorBlast"), position, direction);
+}
+
+// Reflected projectiles are resolved by the ichor blast collision, so they stop hurting the survivor and start hurting horrors
+pub fn transfer_projectile_ownership(commands: &mut Commands, projectile_entity: Entity, new_owner: ProjectileOwner) {
+    match new_owner {
+        ProjectileOwner::Survivor => { commands.entity(projectile_entity).remove::<(HorrorProjectile, Reflectable)>().insert((IchorBlast { piercing_left: 0 }, ProjectileOwner::Survivor)); }
+        ProjectileOwner::Horror => { commands.entity(projectile_entity).remove::<IchorBlast>().insert((HorrorProjectile, Reflectable, ProjectileOwner::Horror)); }
+    }
 }
 
 #[derive(Resource)] pub struct HorrorSpawnTimer { pub timer: Timer, }
 impl Default for HorrorSpawnTimer { fn default() -> Self { Self { timer: Timer::from_seconds(2.0, TimerMode::Repeating), } } }
 
 pub struct HorrorPlugin;
-fn should_despawn_all_entities_on_session_end(next_state: Res<NextState<AppState>>) -> bool { match next_state.0 { Some(AppState::MainMenu) | Some(AppState::GameOver) => true, _ => false, } }
+fn should_despawn_all_entities_on_session_end(next_state: Res<NextState<AppState>>) -> bool { matches!(next_state.0, Some(AppState::MainMenu | AppState::GameOver)) }
 
 impl Plugin for HorrorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (
+        app.init_resource::<SpawnThrottle>()
+            .add_systems(Update, (
+                spawn_throttle_monitor_system,
                 horror_spawn_system,
                 horror_movement_system,
                 frozen_effect_tick_system, // System for Frozen effect
@@ -140,10 +189,11 @@ impl Plugin for HorrorPlugin {
                 void_blinker_ai_system,
                 flesh_weaver_ai_system,
                 frenzied_behemoth_ai_system,
-                horror_projectile_collision_system,
+                timed_collision(horror_projectile_collision_system),
                 horror_projectile_lifetime_system,
+                elite_phase_system,
                 handle_horror_death_drops,
-            ).chain().run_if(in_state(AppState::InGame)))
+            ).chain().after(ThreatAssessment).run_if(in_state(AppState::InGame)))
             .add_systems(PostUpdate, update_horror_count_system_in_game_state.run_if(in_state(AppState::InGame)))
             .add_systems(OnExit(AppState::InGame), (
                 despawn_all_horrors.run_if(should_despawn_all_entities_on_session_end),
@@ -159,11 +209,11 @@ fn despawn_all_item_drops(mut commands: Commands, item_drop_query: Query<Entity,
     for entity in item_drop_query.iter() { commands.entity(entity).despawn_recursive(); }
 }
 
-fn spawn_horror_type(
-    commands: &mut Commands, asset_server: &Res<AssetServer>, horror_type: HorrorType,
+pub fn spawn_horror_type(
+    commands: &mut Commands, asset_server: &Res<AssetServer>, horror_stats: &HorrorStatsLibrary, horror_type: HorrorType,
     position: Vec3, wave_multiplier: f32, is_elite: bool,
 ) {
-    let base_stats = HorrorStats::get_for_type(horror_type, wave_multiplier);
+    let base_stats = horror_stats.stats_for(horror_type, wave_multiplier);
     let mut final_health = base_stats.health; let mut final_damage = base_stats.damage_on_collision;
     let mut final_speed = base_stats.speed; let mut final_size = base_stats.size;
     let mut final_xp = base_stats.xp_value; let mut final_item_chance = base_stats.item_drop_chance_override.unwrap_or(0.0);
@@ -182,7 +232,7 @@ fn spawn_horror_type(
 
     let mut horror_entity_commands = commands.spawn((
         SpriteBundle {
-            texture: asset_server.load(base_stats.sprite_path),
+            texture: asset_server.load(base_stats.sprite_path.clone()),
             sprite: Sprite { custom_size: Some(final_size), color: sprite_color, ..default() },
             transform: Transform::from_translation(position), ..default()
         },
@@ -190,9 +240,11 @@ fn spawn_horror_type(
             horror_type: base_stats.horror_type, size: final_size, damage_on_collision: final_damage,
             speed: final_speed, xp_value: final_xp, item_drop_chance: final_item_chance, is_elite,
         },
-        Health(final_health), Velocity(Vec2::ZERO), Name::new(final_name),
+        Health(final_health), MaxHealth(final_health), ContactDamage::new(final_damage), Velocity(Vec2::ZERO), Name::new(final_name),
     ));
 
+    if is_elite { horror_entity_commands.insert((ElitePhase::default(), EliteModifier::Empowered)); }
+
     match base_stats.horror_type {
         HorrorType::FloatingEyeball => { horror_entity_commands.insert(RangedAttackerBehavior { shooting_range: base_stats.projectile_range.unwrap_or(350.0), fire_timer: Timer::from_seconds(base_stats.projectile_fire_rate.unwrap_or(2.8), TimerMode::Repeating), projectile_speed: base_stats.projectile_speed.unwrap_or(280.0), projectile_damage: base_stats.projectile_damage.unwrap_or(10), state: RangedAttackerState::Idle, reposition_target: None, reposition_timer: Timer::from_seconds(REPOSITION_DURATION_SECONDS, TimerMode::Once), }); }
         HorrorType::VoidBlinker => { horror_entity_commands.insert(VoidBlinkerBehavior::default()); }
@@ -202,60 +254,98 @@ fn spawn_horror_type(
     }
 }
 
+fn elite_phase_system(mut elite_query: Query<(&Transform, &Health, &MaxHealth, &Horror, &mut ElitePhase)>, mut game_event_writer: EventWriter<GameEvent>) {
+    for (transform, health, max_health, horror, mut phase) in elite_query.iter_mut() {
+        // A dying elite is about to be despawned, so it doesn't get a last phase change
+        if health.0 <= 0 { continue; }
+        let reached = ElitePhase::for_health(health.0, max_health.0);
+        if reached <= *phase { continue; }
+        *phase = reached;
+        game_event_writer.send(GameEvent::ElitePhaseChanged { horror_type: horror.horror_type, phase: reached, position: transform.translation });
+    }
+}
+
+fn spawn_throttle_monitor_system(time: Res<Time>, mut spawn_throttle: ResMut<SpawnThrottle>) {
+    spawn_throttle.record_frame(time.delta_seconds());
+}
+
 fn horror_spawn_system(
     mut commands: Commands, time: Res<Time>, mut spawn_timer: ResMut<HorrorSpawnTimer>,
-    asset_server: Res<AssetServer>, player_query: Query<&Transform, With<Survivor>>,
-    horror_query: Query<(), With<Horror>>, max_horrors: Res<MaxHorrors>, game_state: Res<GameState>,
+    asset_server: Res<AssetServer>, horror_stats: Res<HorrorStatsLibrary>, camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    exclusion_query: Query<(&Transform, &SpawnExclusion)>, (game_config, balance): (Res<GameConfig>, Res<BalanceConfig>),
+    horror_query: Query<(), With<Horror>>, max_horrors: Res<MaxHorrors>, game_state: Res<GameState>, spawn_throttle: Res<SpawnThrottle>,
+    mut game_rng: ResMut<GameRng>, active_challenge: Res<ActiveChallenge>, run_difficulty: Res<RunDifficulty>,
+    mut game_event_writer: EventWriter<GameEvent>,
 ) {
     spawn_timer.timer.tick(time.delta());
-    if !spawn_timer.timer.just_finished() || horror_query.iter().count() >= max_horrors.0 as usize { return; }
-    let Ok(player_transform) = player_query.get_single() else { return; };
-    let player_pos = player_transform.translation.truncate();
-    let mut rng = rand::thread_rng();
-    let angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0);
-    let distance = rng.gen_range(crate::game::SCREEN_WIDTH * 0.7 .. crate::game::SCREEN_WIDTH * 1.0);
-    let relative_spawn_pos = Vec2::new(angle.cos() * distance, angle.sin() * distance);
-    let spawn_pos = player_pos + relative_spawn_pos;
+    if !spawn_timer.timer.just_finished() || horror_query.iter().count() >= spawn_throttle.spawn_budget(max_horrors.0) as usize { return; }
+    let Ok((camera_transform, projection)) = camera_query.get_single() else { return; };
+    let view_center = camera_transform.translation.truncate();
+    let view_extents = view_half_extents(projection);
+    let exclusions: Vec<(Vec2, f32)> = exclusion_query.iter().map(|(transform, exclusion)| (transform.translation.truncate(), exclusion.radius)).collect();
+    let rng = &mut game_rng.0;
+    // Against an arena wall part of the ring is out of bounds; if every try misses, the next spawn tick tries again
+    let Some(spawn_pos) = (0..SPAWN_PLACEMENT_ATTEMPTS)
+        .map(|_| spawn_point_outside_view(rng, view_center, view_extents, balance.spawn_view_padding))
+        .find(|point| is_valid_spawn_point(*point, balance.spawn_clearance, &game_config, &exclusions)) else { return; };
     let final_spawn_pos = Vec3::new(spawn_pos.x, spawn_pos.y, 0.5);
-    let wave_multiplier = 1.0 + (game_state.wave_number as f32 - 1.0) * 0.1;
+    let wave_multiplier = cycle_stat_multiplier(game_state.cycle_number);
 
-    let chosen_type = match game_state.wave_number {
+    let chosen_type = match game_state.cycle_number {
         1..=2 => HorrorType::SkitteringShadowling,
         3..=4 => { if rng.gen_bool(0.3) { HorrorType::SkitteringShadowling } else if rng.gen_bool(0.3) { HorrorType::FloatingEyeball } else { HorrorType::VoidBlinker } }
         5..=6 => { let roll = rng.gen_range(0..100); if roll < 20 { HorrorType::SkitteringShadowling } else if roll < 40 { HorrorType::FloatingEyeball } else if roll < 60 { HorrorType::VoidBlinker } else { HorrorType::FleshWeaver } }
         _ => { let roll = rng.gen_range(0..100); if roll < 15 { HorrorType::SkitteringShadowling } else if roll < 30 { HorrorType::FloatingEyeball } else if roll < 45 { HorrorType::VoidBlinker } else if roll < 60 { HorrorType::FleshWeaver } else if roll < 80 { HorrorType::FrenziedBehemoth } else { HorrorType::AmorphousFleshbeast } }
     };
-    let is_elite = rng.gen_bool(ELITE_SPAWN_CHANCE) &&
-                   chosen_type != HorrorType::CrawlingTorment &&
-                   chosen_type != HorrorType::FleshWeaver && // For now, summoners and chargers don't become elite
-                   chosen_type != HorrorType::FrenziedBehemoth;
-    spawn_horror_type(&mut commands, &asset_server, chosen_type, final_spawn_pos, wave_multiplier, is_elite);
+    let chosen_type = active_challenge.substitute_horror(chosen_type, rng);
+    let is_elite = rng.gen_bool(run_difficulty.elite_chance) && can_be_elite(chosen_type);
+    spawn_horror_type(&mut commands, &asset_server, &horror_stats, chosen_type, final_spawn_pos, wave_multiplier, is_elite);
+    if is_elite { game_event_writer.send(GameEvent::EliteSpawned { horror_type: chosen_type, position: final_spawn_pos }); }
+}
+
+// A point just outside the view rectangle, in a random direction from its center. Horrors appear at least `padding`
+// beyond the edge, plus up to as much again, so they walk in rather than pop into sight
+pub fn spawn_point_outside_view<R: Rng>(rng: &mut R, view_center: Vec2, view_half_extents: Vec2, padding: f32) -> Vec2 {
+    let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+    let padded = view_half_extents + Vec2::splat(padding);
+    // Distance along the direction to the padded rectangle's edge
+    let to_edge = (padded.x / direction.x.abs()).min(padded.y / direction.y.abs());
+    view_center + direction * (to_edge + rng.gen_range(0.0..=padding))
+}
+
+pub fn is_valid_spawn_point(point: Vec2, body_radius: f32, game_config: &GameConfig, exclusions: &[(Vec2, f32)]) -> bool {
+    game_config.clamp_to_arena(point, body_radius) == point
+        && exclusions.iter().all(|(center, radius)| point.distance(*center) > radius + body_radius)
 }
 
-fn horror_movement_system( mut query: Query<(&mut Transform, &mut Velocity, &Horror, Option<&RangedAttackerBehavior>, Option<&VoidBlinkerBehavior>, Option<&FleshWeaverBehavior>, Option<&FrenziedBehemothBehavior>, Option<&Frozen>)>, player_query: Query<&Transform, (With<Survivor>, Without<Horror>)>, time: Res<Time>,) {
-    let Ok(player_transform) = player_query.get_single() else { return; }; let player_pos = player_transform.translation.truncate();
-    for (mut transform, mut velocity, horror_data, ranged_opt, void_blinker_opt, flesh_weaver_opt, frenzied_behemoth_opt, frozen_opt) in query.iter_mut() {
+fn horror_movement_system( mut query: Query<(&mut Transform, &mut Velocity, &Horror, Option<&RangedAttackerBehavior>, Option<&VoidBlinkerBehavior>, Option<&FleshWeaverBehavior>, Option<&FrenziedBehemothBehavior>, Option<&Frozen>, Option<&TimeScale>)>, threat_map: Res<ThreatMap>, time: Res<Time>,) {
+    for (mut transform, mut velocity, horror_data, ranged_opt, void_blinker_opt, flesh_weaver_opt, frenzied_behemoth_opt, frozen_opt, time_scale_opt) in query.iter_mut() {
         let mut current_speed_multiplier = 1.0; if let Some(frozen) = frozen_opt { current_speed_multiplier = frozen.speed_multiplier; }
         if current_speed_multiplier == 0.0 { velocity.0 = Vec2::ZERO; continue; }
         let horror_pos = transform.translation.truncate(); let mut should_chase_player_normally = true;
-        if let Some(phase_behavior) = void_blinker_opt { match phase_behavior.state { VoidBlinkerState::PhasingOut | VoidBlinkerState::PhasedOut | VoidBlinkerState::PhasingIn => { should_chase_player_normally = false; velocity.0 = Vec2::ZERO; } VoidBlinkerState::Cooldown => { let direction_to_player = (player_pos - horror_pos).normalize_or_zero(); velocity.0 = direction_to_player * horror_data.speed * 0.6 * current_speed_multiplier; if direction_to_player != Vec2::ZERO {transform.rotation = Quat::from_rotation_z(direction_to_player.y.atan2(direction_to_player.x));} should_chase_player_normally = false; } VoidBlinkerState::Chasing => {} } }
+        let Some(target_pos) = threat_map.target_for(horror_pos) else { velocity.0 = Vec2::ZERO; continue; };
+        if let Some(phase_behavior) = void_blinker_opt { match phase_behavior.state { VoidBlinkerState::PhasingOut | VoidBlinkerState::PhasedOut | VoidBlinkerState::PhasingIn => { should_chase_player_normally = false; velocity.0 = Vec2::ZERO; } VoidBlinkerState::Cooldown => { let direction_to_target = (target_pos - horror_pos).normalize_or_zero(); velocity.0 = direction_to_target * horror_data.speed * 0.6 * current_speed_multiplier; if direction_to_target != Vec2::ZERO {transform.rotation = Quat::from_rotation_z(direction_to_target.y.atan2(direction_to_target.x));} should_chase_player_normally = false; } VoidBlinkerState::Chasing => {} } }
         if should_chase_player_normally && ranged_opt.is_some() { if let Some(ranged_behavior) = ranged_opt { match ranged_behavior.state { RangedAttackerState::Attacking => { should_chase_player_normally = false; velocity.0 = Vec2::ZERO; } RangedAttackerState::Repositioning => { if let Some(target_pos) = ranged_behavior.reposition_target { let dir_to_target = (target_pos - horror_pos).normalize_or_zero(); if dir_to_target != Vec2::ZERO { velocity.0 = dir_to_target * horror_data.speed * REPOSITION_SPEED_MULTIPLIER * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(dir_to_target.y.atan2(dir_to_target.x)); } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } } RangedAttackerState::Idle => {} } } }
-        if let Some(_summoner_behavior) = flesh_weaver_opt { let distance_to_player = player_pos.distance(horror_pos); if distance_to_player < 250.0 { let direction_away_from_player = (horror_pos - player_pos).normalize_or_zero(); if direction_away_from_player != Vec2::ZERO { velocity.0 = direction_away_from_player * horror_data.speed * 0.5 * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_away_from_player.y.atan2(direction_away_from_player.x)); } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } else if distance_to_player > 400.0 { let direction_to_player = (player_pos - horror_pos).normalize_or_zero(); if direction_to_player != Vec2::ZERO { velocity.0 = direction_to_player * horror_data.speed * 0.5 * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_to_player.y.atan2(direction_to_player.x)); } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } else { velocity.0 = Vec2::ZERO; should_chase_player_normally = false; } }
+        if let Some(_summoner_behavior) = flesh_weaver_opt { let distance_to_target = target_pos.distance(horror_pos); if distance_to_target < 250.0 { let direction_away_from_target = (horror_pos - target_pos).normalize_or_zero(); if direction_away_from_target != Vec2::ZERO { velocity.0 = direction_away_from_target * horror_data.speed * 0.5 * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_away_from_target.y.atan2(direction_away_from_target.x)); } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } else if distance_to_target > 400.0 { let direction_to_target = (target_pos - horror_pos).normalize_or_zero(); if direction_to_target != Vec2::ZERO { velocity.0 = direction_to_target * horror_data.speed * 0.5 * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_to_target.y.atan2(direction_to_target.x)); } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } else { velocity.0 = Vec2::ZERO; should_chase_player_normally = false; } }
         if let Some(charger_behavior) = frenzied_behemoth_opt { match charger_behavior.state { FrenziedBehemothState::Telegraphing | FrenziedBehemothState::Cooldown => { should_chase_player_normally = false; velocity.0 = Vec2::ZERO; } FrenziedBehemothState::Charging => { if let Some(charge_dir) = charger_behavior.charge_direction { velocity.0 = charge_dir * horror_data.speed * CHARGER_CHARGE_SPEED_MULTIPLIER; } else { velocity.0 = Vec2::ZERO; } should_chase_player_normally = false; } FrenziedBehemothState::Roaming => {} } }
-        if should_chase_player_normally { let direction_to_player = (player_pos - horror_pos).normalize_or_zero(); if direction_to_player != Vec2::ZERO { velocity.0 = direction_to_player * horror_data.speed * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_to_player.y.atan2(direction_to_player.x)); } else { velocity.0 = Vec2::ZERO; } }
-        transform.translation.x += velocity.0.x * time.delta_seconds(); transform.translation.y += velocity.0.y * time.delta_seconds();
+        if should_chase_player_normally { let direction_to_target = (target_pos - horror_pos).normalize_or_zero(); if direction_to_target != Vec2::ZERO { velocity.0 = direction_to_target * horror_data.speed * current_speed_multiplier; transform.rotation = Quat::from_rotation_z(direction_to_target.y.atan2(direction_to_target.x)); } else { velocity.0 = Vec2::ZERO; } }
+        let scaled_delta_seconds = time.delta_seconds() * TimeScale::of(time_scale_opt);
+        transform.translation.x += velocity.0.x * scaled_delta_seconds; transform.translation.y += velocity.0.y * scaled_delta_seconds;
     }
 }
 
 fn frozen_effect_tick_system( mut commands: Commands, time: Res<Time>, mut frozen_query: Query<(Entity, &mut Frozen)>,) { for (entity, mut frozen_effect) in frozen_query.iter_mut() { frozen_effect.timer.tick(time.delta()); if frozen_effect.timer.finished() { commands.entity(entity).remove::<Frozen>(); } } }
-fn ranged_attacker_logic(mut commands: Commands, time: Res<Time>, asset_server: Res<AssetServer>, mut attacker_query: Query<(&mut Transform, &mut RangedAttackerBehavior, &GlobalTransform, &Horror)>, player_query: Query<&Transform, (With<Survivor>, Without<Horror>)>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { let Ok(player_transform) = player_query.get_single() else { return; }; let player_position = player_transform.translation.truncate(); let mut rng = rand::thread_rng(); for (mut transform, mut behavior, attacker_gtransform, _horror_data) in attacker_query.iter_mut() { let attacker_position = attacker_gtransform.translation().truncate(); let distance_to_player = player_position.distance(attacker_position); match behavior.state { RangedAttackerState::Idle => { if distance_to_player <= behavior.shooting_range { behavior.state = RangedAttackerState::Attacking; } } RangedAttackerState::Attacking => { if distance_to_player > behavior.shooting_range * 1.1 { behavior.state = RangedAttackerState::Idle; } else { let dir = (player_position - attacker_position).normalize_or_zero(); if dir != Vec2::ZERO { transform.rotation = Quat::from_rotation_z(dir.y.atan2(dir.x)); } behavior.fire_timer.tick(time.delta()); if behavior.fire_timer.just_finished() { sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorProjectile)); spawn_horror_projectile( &mut commands, &asset_server, attacker_gtransform.translation(), dir, behavior.projectile_speed, behavior.projectile_damage, ); behavior.state = RangedAttackerState::Repositioning; behavior.reposition_timer.reset(); let perp_dir = Vec2::new(-dir.y, dir.x) * (if rng.gen_bool(0.5) { 1.0 } else { -1.0 }); let dist = rng.gen_range(50.0..150.0); behavior.reposition_target = Some(attacker_position + perp_dir * dist); } } } RangedAttackerState::Repositioning => { behavior.reposition_timer.tick(time.delta()); if behavior.reposition_timer.finished() || (behavior.reposition_target.is_some() && attacker_position.distance(behavior.reposition_target.unwrap()) < 10.0) { behavior.state = RangedAttackerState::Idle; behavior.reposition_target = None; } } } } }
-fn void_blinker_ai_system( _commands: Commands, time: Res<Time>, mut ripper_query: Query<(&mut Transform, &mut VoidBlinkerBehavior, &mut Sprite, &mut Visibility), (With<VoidBlinkerBehavior>, With<Horror>, Without<Survivor>)>, player_query: Query<&Transform, (With<Survivor>, Without<Horror>)>,) { let Ok(player_transform) = player_query.get_single() else { return; }; let player_pos = player_transform.translation.truncate(); let mut rng = rand::thread_rng(); for (mut transform, mut behavior, mut sprite, mut visibility) in ripper_query.iter_mut() { behavior.action_timer.tick(time.delta()); match behavior.state { VoidBlinkerState::Chasing => { if behavior.action_timer.finished() { behavior.state = VoidBlinkerState::PhasingOut; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_PHASE_DURATION_SECS)); behavior.action_timer.reset(); let angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0); let distance = rng.gen_range(PHASE_RIPPER_TELEPORT_RANGE_MIN..PHASE_RIPPER_TELEPORT_RANGE_MAX); behavior.next_teleport_destination = Some(player_pos + Vec2::new(angle.cos() * distance, angle.sin() * distance)); sprite.color.set_a(0.5); } } VoidBlinkerState::PhasingOut => { sprite.color.set_a(1.0 - behavior.action_timer.fraction()); if behavior.action_timer.just_finished() { *visibility = Visibility::Hidden; behavior.state = VoidBlinkerState::PhasedOut; behavior.action_timer.set_duration(Duration::from_millis(50)); behavior.action_timer.reset(); } } VoidBlinkerState::PhasedOut => { if behavior.action_timer.just_finished() { if let Some(destination) = behavior.next_teleport_destination.take() { transform.translation = destination.extend(transform.translation.z); } behavior.state = VoidBlinkerState::PhasingIn; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_PHASE_DURATION_SECS)); behavior.action_timer.reset(); *visibility = Visibility::Visible; sprite.color.set_a(0.0); } } VoidBlinkerState::PhasingIn => { sprite.color.set_a(behavior.action_timer.fraction()); if behavior.action_timer.just_finished() { sprite.color.set_a(1.0); behavior.state = VoidBlinkerState::Cooldown; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_TELEPORT_COOLDOWN_SECS)); behavior.action_timer.reset(); } } VoidBlinkerState::Cooldown => { if behavior.action_timer.finished() { behavior.state = VoidBlinkerState::Chasing; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_TELEPORT_COOLDOWN_SECS)); behavior.action_timer.reset(); } } } } }
-fn flesh_weaver_ai_system( mut commands: Commands, time: Res<Time>, mut summoner_query: Query<(&Transform, &mut FleshWeaverBehavior), (With<Horror>, With<FleshWeaverBehavior>)>, asset_server: Res<AssetServer>, game_state: Res<GameState>,) { let wave_multiplier = 1.0 + (game_state.wave_number as f32 - 1.0) * 0.1; for (summoner_transform, mut summoner_behavior) in summoner_query.iter_mut() { summoner_behavior.summon_timer.tick(time.delta()); summoner_behavior.active_minion_entities.retain(|&minion_e| commands.get_entity(minion_e).is_some()); if summoner_behavior.summon_timer.just_finished() && summoner_behavior.active_minion_entities.len() < summoner_behavior.max_minions as usize { for _ in 0..SUMMONER_MINIONS_TO_SPAWN { if summoner_behavior.active_minion_entities.len() >= summoner_behavior.max_minions as usize { break; } let mut rng = rand::thread_rng(); let offset_angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0); let offset_distance = rng.gen_range(20.0..50.0); let spawn_offset = Vec2::new(offset_angle.cos() * offset_distance, offset_angle.sin() * offset_distance); let minion_spawn_pos = (summoner_transform.translation.truncate() + spawn_offset).extend(0.5); let minion_entity = spawn_and_return_horror_entity(&mut commands, &asset_server, HorrorType::CrawlingTorment, minion_spawn_pos, wave_multiplier); summoner_behavior.active_minion_entities.push(minion_entity); } } } }
-fn spawn_and_return_horror_entity( commands: &mut Commands, asset_server: &Res<AssetServer>, horror_type: HorrorType, position: Vec3, wave_multiplier: f32,) -> Entity { let stats = HorrorStats::get_for_type(horror_type, wave_multiplier); commands.spawn(( SpriteBundle { texture: asset_server.load(stats.sprite_path), sprite: Sprite { custom_size: Some(stats.size), ..default() }, transform: Transform::from_translation(position), ..default() }, Horror { horror_type: stats.horror_type, size: stats.size, damage_on_collision: stats.damage_on_collision, speed: stats.speed, xp_value: stats.xp_value, item_drop_chance: stats.item_drop_chance_override.unwrap_or(0.0), is_elite: false }, Health(stats.health), Velocity(Vec2::ZERO), Name::new(format!("{:?}", stats.horror_type)), )).id() }
-fn frenzied_behemoth_ai_system(time: Res<Time>, mut charger_query: Query<(&Transform, &mut FrenziedBehemothBehavior, &mut Sprite, &Horror)>, player_query: Query<&Transform, With<Survivor>>,){ let Ok(player_transform) = player_query.get_single() else { return; }; let player_pos = player_transform.translation.truncate(); for (charger_transform, mut behavior, mut sprite, _horror_data) in charger_query.iter_mut() { let charger_pos = charger_transform.translation.truncate(); match behavior.state { FrenziedBehemothState::Roaming => { behavior.charge_cooldown_timer.tick(time.delta()); if behavior.charge_cooldown_timer.finished() { let distance_to_player = charger_pos.distance(player_pos); if distance_to_player < CHARGER_DETECTION_RANGE && distance_to_player > CHARGER_MIN_CHARGE_RANGE { behavior.state = FrenziedBehemothState::Telegraphing; behavior.telegraph_timer.reset(); behavior.charge_target_pos = Some(player_pos); sprite.color = Color::rgb(1.0, 0.5, 0.5); } } } FrenziedBehemothState::Telegraphing => { behavior.telegraph_timer.tick(time.delta()); if behavior.telegraph_timer.just_finished() { behavior.state = FrenziedBehemothState::Charging; behavior.charge_duration_timer.reset(); if let Some(target_pos) = behavior.charge_target_pos { behavior.charge_direction = Some((target_pos - charger_pos).normalize_or_zero()); } else { behavior.charge_direction = Some((player_pos - charger_pos).normalize_or_zero()); } sprite.color = Color::rgb(1.0, 0.2, 0.2); } } FrenziedBehemothState::Charging => { behavior.charge_duration_timer.tick(time.delta()); if behavior.charge_duration_timer.finished() { behavior.state = FrenziedBehemothState::Cooldown; behavior.charge_cooldown_timer.reset(); let telegraph_timer_duration_val = behavior.telegraph_timer.duration(); behavior.telegraph_timer.tick(telegraph_timer_duration_val); behavior.charge_direction = None; sprite.color = Color::WHITE; } } FrenziedBehemothState::Cooldown => { if behavior.charge_cooldown_timer.finished() { behavior.state = FrenziedBehemothState::Roaming; } } } } }
-fn horror_projectile_collision_system(mut commands: Commands, projectile_query: Query<(Entity, &GlobalTransform, &Damage), With<HorrorProjectile>>, mut player_query: Query<(&GlobalTransform, &mut Health, &mut Survivor), With<Survivor>>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { if let Ok((player_gtransform, mut player_health, mut player_component)) = player_query.get_single_mut() { for (projectile_entity, projectile_gtransform, projectile_damage) in projectile_query.iter() { let distance = projectile_gtransform.translation().truncate().distance(player_gtransform.translation().truncate()); let projectile_radius = HORROR_PROJECTILE_SPRITE_SIZE.x / 2.0; let player_radius = crate::player::PLAYER_SIZE.x / 2.0; if distance < projectile_radius + player_radius { if player_component.invincibility_timer.finished() { sound_event_writer.send(PlaySoundEvent(SoundEffect::SurvivorHit)); player_health.0 -= projectile_damage.0; player_component.invincibility_timer.reset(); } commands.entity(projectile_entity).despawn_recursive(); } } } }
+fn ranged_attacker_logic(mut commands: Commands, time: Res<Time>, asset_server: Res<AssetServer>, mut attacker_query: Query<(&mut Transform, &mut RangedAttackerBehavior, &GlobalTransform, &Horror, Option<&TimeScale>)>, threat_map: Res<ThreatMap>, mut sound_event_writer: EventWriter<PlaySoundEvent>, mut game_rng: ResMut<GameRng>,) { let rng = &mut game_rng.0; for (mut transform, mut behavior, attacker_gtransform, _horror_data, time_scale_opt) in attacker_query.iter_mut() { let scaled_delta = time.delta().mul_f32(TimeScale::of(time_scale_opt)); let attacker_position = attacker_gtransform.translation().truncate(); let Some(player_position) = threat_map.target_for(attacker_position) else { continue; }; let distance_to_player = player_position.distance(attacker_position); match behavior.state { RangedAttackerState::Idle => { if distance_to_player <= behavior.shooting_range { behavior.state = RangedAttackerState::Attacking; } } RangedAttackerState::Attacking => { if distance_to_player > behavior.shooting_range * 1.1 { behavior.state = RangedAttackerState::Idle; } else { let dir = (player_position - attacker_position).normalize_or_zero(); if dir != Vec2::ZERO { transform.rotation = Quat::from_rotation_z(dir.y.atan2(dir.x)); } behavior.fire_timer.tick(scaled_delta); if behavior.fire_timer.just_finished() { sound_event_writer.send(PlaySoundEvent::at(SoundEffect::HorrorProjectile, attacker_position)); spawn_horror_projectile( &mut commands, &asset_server, attacker_gtransform.translation(), dir, behavior.projectile_speed, behavior.projectile_damage, ); behavior.state = RangedAttackerState::Repositioning; behavior.reposition_timer.reset(); let perp_dir = Vec2::new(-dir.y, dir.x) * (if rng.gen_bool(0.5) { 1.0 } else { -1.0 }); let dist = rng.gen_range(50.0..150.0); behavior.reposition_target = Some(attacker_position + perp_dir * dist); } } } RangedAttackerState::Repositioning => { behavior.reposition_timer.tick(scaled_delta); if behavior.reposition_timer.finished() || (behavior.reposition_target.is_some() && attacker_position.distance(behavior.reposition_target.unwrap()) < 10.0) { behavior.state = RangedAttackerState::Idle; behavior.reposition_target = None; } } } } }
+fn void_blinker_ai_system( _commands: Commands, time: Res<Time>, mut ripper_query: Query<(&mut Transform, &mut VoidBlinkerBehavior, &mut Sprite, &mut Visibility), (With<VoidBlinkerBehavior>, With<Horror>, Without<Survivor>)>, threat_map: Res<ThreatMap>, mut game_rng: ResMut<GameRng>,) { let rng = &mut game_rng.0; for (mut transform, mut behavior, mut sprite, mut visibility) in ripper_query.iter_mut() { behavior.action_timer.tick(time.delta()); match behavior.state { VoidBlinkerState::Chasing => { if behavior.action_timer.finished() { let Some(player_pos) = threat_map.target_for(transform.translation.truncate()) else { continue; }; behavior.state = VoidBlinkerState::PhasingOut; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_PHASE_DURATION_SECS)); behavior.action_timer.reset(); let angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0); let distance = rng.gen_range(PHASE_RIPPER_TELEPORT_RANGE_MIN..PHASE_RIPPER_TELEPORT_RANGE_MAX); behavior.next_teleport_destination = Some(player_pos + Vec2::new(angle.cos() * distance, angle.sin() * distance)); sprite.color.set_a(0.5); } } VoidBlinkerState::PhasingOut => { sprite.color.set_a(1.0 - behavior.action_timer.fraction()); if behavior.action_timer.just_finished() { *visibility = Visibility::Hidden; behavior.state = VoidBlinkerState::PhasedOut; behavior.action_timer.set_duration(Duration::from_millis(50)); behavior.action_timer.reset(); } } VoidBlinkerState::PhasedOut => { if behavior.action_timer.just_finished() { if let Some(destination) = behavior.next_teleport_destination.take() { transform.translation = destination.extend(transform.translation.z); } behavior.state = VoidBlinkerState::PhasingIn; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_PHASE_DURATION_SECS)); behavior.action_timer.reset(); *visibility = Visibility::Visible; sprite.color.set_a(0.0); } } VoidBlinkerState::PhasingIn => { sprite.color.set_a(behavior.action_timer.fraction()); if behavior.action_timer.just_finished() { sprite.color.set_a(1.0); behavior.state = VoidBlinkerState::Cooldown; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_TELEPORT_COOLDOWN_SECS)); behavior.action_timer.reset(); } } VoidBlinkerState::Cooldown => { if behavior.action_timer.finished() { behavior.state = VoidBlinkerState::Chasing; behavior.action_timer.set_duration(Duration::from_secs_f32(PHASE_RIPPER_TELEPORT_COOLDOWN_SECS)); behavior.action_timer.reset(); } } } } }
+fn flesh_weaver_ai_system( mut commands: Commands, time: Res<Time>, mut summoner_query: Query<(&Transform, &mut FleshWeaverBehavior, Option<&TimeScale>), (With<Horror>, With<FleshWeaverBehavior>)>, asset_server: Res<AssetServer>, horror_stats: Res<HorrorStatsLibrary>, game_state: Res<GameState>, mut game_rng: ResMut<GameRng>,) { let wave_multiplier = 1.0 + (game_state.cycle_number as f32 - 1.0) * 0.1; for (summoner_transform, mut summoner_behavior, time_scale_opt) in summoner_query.iter_mut() { summoner_behavior.summon_timer.tick(time.delta().mul_f32(TimeScale::of(time_scale_opt))); summoner_behavior.active_minion_entities.retain(|&minion_e| commands.get_entity(minion_e).is_some()); if summoner_behavior.summon_timer.just_finished() && summoner_behavior.active_minion_entities.len() < summoner_behavior.max_minions as usize { for _ in 0..SUMMONER_MINIONS_TO_SPAWN { if summoner_behavior.active_minion_entities.len() >= summoner_behavior.max_minions as usize { break; } let rng = &mut game_rng.0; let offset_angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0); let offset_distance = rng.gen_range(20.0..50.0); let spawn_offset = Vec2::new(offset_angle.cos() * offset_distance, offset_angle.sin() * offset_distance); let minion_spawn_pos = (summoner_transform.translation.truncate() + spawn_offset).extend(0.5); let minion_entity = spawn_and_return_horror_entity(&mut commands, &asset_server, &horror_stats, HorrorType::CrawlingTorment, minion_spawn_pos, wave_multiplier); summoner_behavior.active_minion_entities.push(minion_entity); } } } }
+fn spawn_and_return_horror_entity( commands: &mut Commands, asset_server: &Res<AssetServer>, horror_stats: &HorrorStatsLibrary, horror_type: HorrorType, position: Vec3, wave_multiplier: f32,) -> Entity { let stats = horror_stats.stats_for(horror_type, wave_multiplier); commands.spawn(( SpriteBundle { texture: asset_server.load(stats.sprite_path.clone()), sprite: Sprite { custom_size: Some(stats.size), ..default() }, transform: Transform::from_translation(position), ..default() }, Horror { horror_type: stats.horror_type, size: stats.size, damage_on_collision: stats.damage_on_collision, speed: stats.speed, xp_value: stats.xp_value, item_drop_chance: stats.item_drop_chance_override.unwrap_or(0.0), is_elite: false }, Health(stats.health), MaxHealth(stats.health), ContactDamage::new(stats.damage_on_collision), Velocity(Vec2::ZERO), Name::new(format!("{:?}", stats.horror_type)), )).id() }
+fn frenzied_behemoth_ai_system(time: Res<Time>, mut charger_query: Query<(&Transform, &mut FrenziedBehemothBehavior, &mut Sprite, &Horror)>, threat_map: Res<ThreatMap>,){ for (charger_transform, mut behavior, mut sprite, _horror_data) in charger_query.iter_mut() { let charger_pos = charger_transform.translation.truncate(); let Some(player_pos) = threat_map.target_for(charger_pos) else { continue; }; match behavior.state { FrenziedBehemothState::Roaming => { behavior.charge_cooldown_timer.tick(time.delta()); if behavior.charge_cooldown_timer.finished() { let distance_to_player = charger_pos.distance(player_pos); if distance_to_player < CHARGER_DETECTION_RANGE && distance_to_player > CHARGER_MIN_CHARGE_RANGE { behavior.state = FrenziedBehemothState::Telegraphing; behavior.telegraph_timer.reset(); behavior.charge_target_pos = Some(player_pos); sprite.color = Color::rgb(1.0, 0.5, 0.5); } } } FrenziedBehemothState::Telegraphing => { behavior.telegraph_timer.tick(time.delta()); if behavior.telegraph_timer.just_finished() { behavior.state = FrenziedBehemothState::Charging; behavior.charge_duration_timer.reset(); if let Some(target_pos) = behavior.charge_target_pos { behavior.charge_direction = Some((target_pos - charger_pos).normalize_or_zero()); } else { behavior.charge_direction = Some((player_pos - charger_pos).normalize_or_zero()); } sprite.color = Color::rgb(1.0, 0.2, 0.2); } } FrenziedBehemothState::Charging => { behavior.charge_duration_timer.tick(time.delta()); if behavior.charge_duration_timer.finished() { behavior.state = FrenziedBehemothState::Cooldown; behavior.charge_cooldown_timer.reset(); let telegraph_timer_duration_val = behavior.telegraph_timer.duration(); behavior.telegraph_timer.tick(telegraph_timer_duration_val); behavior.charge_direction = None; sprite.color = Color::WHITE; } } FrenziedBehemothState::Cooldown => { if behavior.charge_cooldown_timer.finished() { behavior.state = FrenziedBehemothState::Roaming; } } } } }
+fn horror_projectile_collision_system(mut commands: Commands, projectile_query: Query<(Entity, &GlobalTransform, &Damage, Option<&DamageType>), With<HorrorProjectile>>, mut player_query: Query<(&GlobalTransform, &mut Health, &mut Survivor), With<Survivor>>, mut game_event_writer: EventWriter<GameEvent>, balance: Res<BalanceConfig>, style: Res<DamageTypeStyle>,) { if let Ok((player_gtransform, mut player_health, mut player_component)) = player_query.get_single_mut() { for (projectile_entity, projectile_gtransform, projectile_damage, projectile_damage_type) in projectile_query.iter() { let distance = projectile_gtransform.translation().truncate().distance(player_gtransform.translation().truncate()); let projectile_radius = HORROR_PROJECTILE_SPRITE_SIZE.x / 2.0; let player_radius = crate::survivor::SURVIVOR_SIZE.x / 2.0; if distance < projectile_radius + player_radius { if player_component.can_take_damage_from(DamageSource::Projectile) { game_event_writer.send(GameEvent::SurvivorHit { damage: projectile_damage.0, source: DamageSource::Projectile }); player_health.0 -= projectile_damage.0; player_component.register_hit_with_grace(DamageSource::Projectile, balance.grace_secs(DamageSource::Projectile)); } spawn_impact_effect(&mut commands, &style, projectile_gtransform.translation(), projectile_damage_type.copied().unwrap_or_default()); commands.entity(projectile_entity).despawn_recursive(); } } } }
 fn horror_projectile_lifetime_system(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut Lifetime), With<HorrorProjectile>>,) { for (entity, mut lifetime) in query.iter_mut() { lifetime.timer.tick(time.delta()); if lifetime.timer.just_finished() { commands.entity(entity).despawn_recursive(); } } }
-fn handle_horror_death_drops(mut commands: Commands, dead_horrors_query: Query<(Entity, &Transform, &Health, &Horror)>, asset_server: Res<AssetServer>, mut game_state: ResMut<GameState>, item_library: Res<ItemLibrary>, mut sound_event_writer: EventWriter<PlaySoundEvent>, player_query: Query<(Entity, &Survivor)>,) { let Ok((player_entity, player_data)) = player_query.get_single() else { return }; let mut rng = rand::thread_rng(); for (entity, transform, health, horror_data) in dead_horrors_query.iter() { if health.0 <= 0 { sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorDeath)); game_state.score += horror_data.xp_value / 2; spawn_echoing_soul(&mut commands, &asset_server, transform.translation, horror_data.xp_value); if rng.gen_bool(horror_data.item_drop_chance) { if !item_library.items.is_empty() { if let Some(item_to_drop_def) = item_library.items.choose(&mut rng) { commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/eldritch_relic_placeholder.png"), sprite: Sprite { custom_size: Some(ITEM_DROP_SIZE), ..default() }, transform: Transform::from_translation(transform.translation.truncate().extend(0.4)), ..default() }, ItemDrop { item_id: item_to_drop_def.id }, Name::new(format!("ItemDrop_{}", item_to_drop_def.name)), )); } } } for item_id in player_data.collected_item_ids.iter() { if let Some(item_def) = item_library.get_item_definition(*item_id) { for effect in &item_def.effects { if let ItemEffect::OnHorrorKillTrigger { chance, effect: kill_effect_type } = effect { if rng.gen_bool((*chance).into()) { match kill_effect_type { SurvivorTemporaryBuff::HealthRegen { rate, duration_secs } => { commands.entity(player_entity).insert(TemporaryHealthRegenBuff { regen_per_second: *rate, duration_timer: Timer::from_seconds(*duration_secs, TimerMode::Once), }); } } } } } } commands.entity(entity).despawn_recursive(); } } }
+fn handle_horror_death_drops(mut commands: Commands, dead_horrors_query: Query<(Entity, &Transform, &Health, &Horror, Option<&LastHitBySkill>)>, asset_server: Res<AssetServer>, mut game_state: ResMut<GameState>, item_library: Res<ItemLibrary>, mut game_event_writer: EventWriter<GameEvent>, player_query: Query<(Entity, &Survivor)>, mut game_rng: ResMut<GameRng>, balance: Res<BalanceConfig>,) { let Ok((player_entity, player_data)) = player_query.get_single() else { return }; let rng = &mut game_rng.0; for (entity, transform, health, horror_data, last_hit_by_skill) in dead_horrors_query.iter() { if health.0 <= 0 { game_event_writer.send(GameEvent::HorrorKilled(HorrorKill { horror_type: horror_data.horror_type, position: transform.translation, is_elite: horror_data.is_elite, xp_value: horror_data.xp_value, skill: last_hit_by_skill.map(|last_hit| last_hit.0) })); game_state.score += horror_data.xp_value / 2; spawn_echoing_soul(&mut commands, &asset_server, transform.translation, balance.scaled_soul_value(horror_data.xp_value)); if roll_with_luck(rng, horror_data.item_drop_chance, player_data.luck) && !item_library.items.is_empty() { if let Some(item_to_drop_def) = item_library.items.choose(rng) { commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/eldritch_relic_placeholder.png"), sprite: Sprite { custom_size: Some(ITEM_DROP_SIZE), ..default() }, transform: Transform::from_translation(transform.translation.truncate().extend(0.4)), ..default() }, ItemDrop { item_id: item_to_drop_def.id }, Name::new(format!("ItemDrop_{}", item_to_drop_def.name)), )); } } for item_id in player_data.collected_item_ids.iter() { if let Some(item_def) = item_library.get_item_definition(*item_id) { for effect in &item_def.effects { if let ItemEffect::OnHorrorKillTrigger { chance, effect: kill_effect_type } = effect { if rng.gen_bool((*chance).into()) { match kill_effect_type { SurvivorTemporaryBuff::HealthRegen { rate, duration_secs } => { commands.entity(player_entity).insert(TemporaryHealthRegenBuff { regen_per_second: *rate, duration_timer: Timer::from_seconds(*duration_secs, TimerMode::Once), }); } } } } } } commands.entity(entity).despawn_recursive(); } } } }
 fn update_horror_count_system_in_game_state(mut game_state: ResMut<crate::game::GameState>, horror_query: Query<(), With<Horror>>,) { game_state.horror_count = horror_query.iter().count() as u32; }
 //Placeholder for fleshy_landscape_tile_placeholder.png if used
 //The current code only uses one background tile, so background_tile2.png is not used.
+
+// A frost chill never overrides a stronger slow that is already running
+pub fn apply_damage_type_on_hit(commands: &mut Commands, horror_entity: Entity, damage_type: DamageType, current_frozen: Option<&Frozen>) { match damage_type { DamageType::Frost => { if current_frozen.is_none_or(|frozen| frozen.speed_multiplier >= FROST_CHILL_SPEED_MULTIPLIER) { commands.entity(horror_entity).insert(Frozen { timer: Timer::from_seconds(FROST_CHILL_DURATION_SECS, TimerMode::Once), speed_multiplier: FROST_CHILL_SPEED_MULTIPLIER, }); } } DamageType::Eldritch | DamageType::Chaos => {} } }
@@ -0,0 +1,223 @@
+use bevy::{
+    prelude::*,
+    asset::{AssetLoader, AsyncReadExt, LoadContext, LoadState, io::Reader},
+    utils::{BoxedFuture, HashMap},
+};
+use serde::Deserialize;
+use crate::horror::{HorrorType, HorrorStats};
+
+pub const HORROR_STATS_PATH: &str = "data/enemies.ron";
+// Re-reads the balance data files from disk while the game is running
+pub const DATA_RELOAD_KEY: KeyCode = KeyCode::F5;
+// Compiled-in copy of the data file so horrors can spawn before the asset server finishes loading it
+const EMBEDDED_HORROR_STATS: &str = include_str!("../assets/data/enemies.ron");
+
+pub struct HorrorStatsPlugin;
+
+impl Plugin for HorrorStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<HorrorStatsTable>()
+            .register_asset_loader(HorrorStatsLoader)
+            .init_resource::<HorrorStatsLibrary>()
+            .add_systems(Startup, load_horror_stats)
+            .add_systems(Update, (apply_loaded_horror_stats, reload_horror_stats_on_key));
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RangedStats {
+    pub range: f32,
+    pub fire_rate: f32,
+    pub projectile_speed: f32,
+    pub projectile_damage: i32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct HorrorStatsEntry {
+    pub horror_type: HorrorType,
+    pub base_health: f32,
+    pub health_scaling: f32,
+    pub damage_on_collision: i32,
+    pub base_speed: f32,
+    pub speed_per_cycle: f32,
+    pub size: (f32, f32),
+    pub sprite_path: String,
+    #[serde(default)]
+    pub ranged: Option<RangedStats>,
+    pub xp_value: u32,
+    pub item_drop_chance: f64,
+}
+
+impl HorrorStatsEntry {
+    pub fn stats_at(&self, wave_multiplier: f32) -> HorrorStats {
+        HorrorStats {
+            horror_type: self.horror_type,
+            health: (self.base_health * wave_multiplier * self.health_scaling).max(1.0) as i32,
+            damage_on_collision: self.damage_on_collision,
+            speed: self.base_speed + self.speed_per_cycle * (wave_multiplier - 1.0).max(0.0),
+            size: Vec2::new(self.size.0, self.size.1),
+            sprite_path: self.sprite_path.clone(),
+            projectile_range: self.ranged.as_ref().map(|ranged| ranged.range),
+            projectile_fire_rate: self.ranged.as_ref().map(|ranged| ranged.fire_rate),
+            projectile_speed: self.ranged.as_ref().map(|ranged| ranged.projectile_speed),
+            projectile_damage: self.ranged.as_ref().map(|ranged| ranged.projectile_damage),
+            xp_value: self.xp_value,
+            item_drop_chance_override: Some(self.item_drop_chance),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), HorrorStatsError> {
+        let invalid = |field: &'static str| Err(HorrorStatsError::InvalidValue { horror_type: self.horror_type, field });
+        if self.base_health <= 0.0 { return invalid("base_health"); }
+        if self.health_scaling <= 0.0 { return invalid("health_scaling"); }
+        if self.damage_on_collision < 0 { return invalid("damage_on_collision"); }
+        if self.base_speed < 0.0 || self.speed_per_cycle < 0.0 { return invalid("speed"); }
+        if self.size.0 <= 0.0 || self.size.1 <= 0.0 { return invalid("size"); }
+        if self.sprite_path.is_empty() { return invalid("sprite_path"); }
+        if !(0.0..=1.0).contains(&self.item_drop_chance) { return invalid("item_drop_chance"); }
+        if let Some(ranged) = &self.ranged {
+            if ranged.range <= 0.0 || ranged.fire_rate <= 0.0 || ranged.projectile_speed <= 0.0 || ranged.projectile_damage < 0 { return invalid("ranged"); }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct HorrorStatsTable {
+    pub horrors: Vec<HorrorStatsEntry>,
+}
+
+impl HorrorStatsTable {
+    pub fn from_ron_str(source: &str) -> Result<Self, HorrorStatsError> {
+        let table: HorrorStatsTable = ron::de::from_str(source)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    // Every horror type needs exactly one entry, since spawning looks stats up by type
+    pub fn validate(&self) -> Result<(), HorrorStatsError> {
+        for (i, entry) in self.horrors.iter().enumerate() {
+            if self.horrors[..i].iter().any(|earlier| earlier.horror_type == entry.horror_type) {
+                return Err(HorrorStatsError::DuplicateEntry(entry.horror_type));
+            }
+            entry.validate()?;
+        }
+        if let Some(missing) = HorrorType::ALL.iter().find(|horror_type| !self.horrors.iter().any(|entry| entry.horror_type == **horror_type)) {
+            return Err(HorrorStatsError::MissingEntry(*missing));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum HorrorStatsError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    DuplicateEntry(HorrorType),
+    MissingEntry(HorrorType),
+    InvalidValue { horror_type: HorrorType, field: &'static str },
+}
+
+impl std::fmt::Display for HorrorStatsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HorrorStatsError::Io(error) => write!(f, "could not read horror stats: {}", error),
+            HorrorStatsError::Parse(error) => write!(f, "could not parse horror stats: {}", error),
+            HorrorStatsError::DuplicateEntry(horror_type) => write!(f, "{:?} is listed more than once", horror_type),
+            HorrorStatsError::MissingEntry(horror_type) => write!(f, "{:?} has no stats entry", horror_type),
+            HorrorStatsError::InvalidValue { horror_type, field } => write!(f, "{:?} has an invalid {}", horror_type, field),
+        }
+    }
+}
+
+impl std::error::Error for HorrorStatsError {}
+
+impl From<std::io::Error> for HorrorStatsError {
+    fn from(error: std::io::Error) -> Self { HorrorStatsError::Io(error) }
+}
+
+impl From<ron::error::SpannedError> for HorrorStatsError {
+    fn from(error: ron::error::SpannedError) -> Self { HorrorStatsError::Parse(error) }
+}
+
+#[derive(Default)]
+pub struct HorrorStatsLoader;
+
+impl AssetLoader for HorrorStatsLoader {
+    type Asset = HorrorStatsTable;
+    type Settings = ();
+    type Error = HorrorStatsError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = String::new();
+            reader.read_to_string(&mut bytes).await?;
+            HorrorStatsTable::from_ron_str(&bytes)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["enemies.ron"]
+    }
+}
+
+// Stats currently in use; a table that fails validation is rejected by the loader, so the previous values stay in effect
+#[derive(Resource)]
+pub struct HorrorStatsLibrary {
+    pub entries: HashMap<HorrorType, HorrorStatsEntry>,
+    handle: Option<Handle<HorrorStatsTable>>,
+}
+
+impl Default for HorrorStatsLibrary {
+    fn default() -> Self {
+        let table = HorrorStatsTable::from_ron_str(EMBEDDED_HORROR_STATS).expect("embedded horror stats are invalid");
+        let mut library = Self { entries: HashMap::new(), handle: None };
+        library.replace_entries(&table);
+        library
+    }
+}
+
+impl HorrorStatsLibrary {
+    pub fn stats_for(&self, horror_type: HorrorType, wave_multiplier: f32) -> HorrorStats {
+        self.entries[&horror_type].stats_at(wave_multiplier)
+    }
+
+    // True once the data file has either arrived or failed, so nothing that waits on it can hang
+    pub fn finished_loading(&self, asset_server: &AssetServer) -> bool {
+        self.handle.as_ref().is_none_or(|handle| matches!(asset_server.get_load_state(handle), Some(LoadState::Loaded | LoadState::Failed)))
+    }
+
+    fn replace_entries(&mut self, table: &HorrorStatsTable) {
+        self.entries = table.horrors.iter().map(|entry| (entry.horror_type, entry.clone())).collect();
+    }
+}
+
+fn load_horror_stats(asset_server: Res<AssetServer>, mut library: ResMut<HorrorStatsLibrary>) {
+    library.handle = Some(asset_server.load(HORROR_STATS_PATH));
+}
+
+pub(crate) fn apply_loaded_horror_stats(
+    mut asset_events: EventReader<AssetEvent<HorrorStatsTable>>,
+    tables: Res<Assets<HorrorStatsTable>>,
+    mut library: ResMut<HorrorStatsLibrary>,
+) {
+    for event in asset_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else { continue; };
+        if library.handle.as_ref().map(|handle| handle.id()) != Some(*id) { continue; }
+        if let Some(table) = tables.get(*id) {
+            library.replace_entries(table);
+            info!("Loaded stats for {} horror types from {}", table.horrors.len(), HORROR_STATS_PATH);
+        }
+    }
+}
+
+fn reload_horror_stats_on_key(keyboard_input: Res<ButtonInput<KeyCode>>, asset_server: Res<AssetServer>) {
+    if keyboard_input.just_pressed(DATA_RELOAD_KEY) {
+        asset_server.reload(HORROR_STATS_PATH);
+    }
+}
@@ -1,19 +1,23 @@
 use bevy::prelude::*;
 use rand::Rng; // For chance
 use crate::{
-    components::{Velocity, Damage, Lifetime, Health},
-    visual_effects::spawn_damage_text,
-    audio::{PlaySoundEvent, SoundEffect},
-    skills::SkillProjectile,
-    enemy::EnemyProjectile,
+    components::{Velocity, Damage, Lifetime, Health, MaxHealth, TimeScale, ProjectileOwner, DamageType, resolve_hit},
+    visual_effects::{spawn_damage_text, spawn_impact_effect, DamageTypeStyle},
+    game_events::{GameEvent, DamageOrigin},
+    game::GameRng,
+    skills::{SkillProjectile, LastHitBySkill},
+    horror::{Horror, HorrorProjectile},
     survivor::Survivor, // Changed from player::Player
-    items::{ItemLibrary, ItemEffect, ExplosionEffect}, // For item effects & explosion component
+    items::{ItemLibrary, ItemEffect, ExplosionEffect, execute_threshold}, // For item effects & explosion component
+    projectile::{ProjectileSpec, ProjectileVisual, spawn_projectile},
+    perf_overlay::timed_collision,
 };
 
 pub const ICHOR_BLAST_SIZE: Vec2 = Vec2::new(10.0, 10.0);
 pub const BASE_FRAGMENT_SPEED: f32 = 600.0;
 pub const BASE_FRAGMENT_DAMAGE: i32 = 10;
 pub const FRAGMENT_LIFETIME_SECONDS: f32 = 2.0;
+pub const ICHOR_BLAST_COLOR: Color = Color::rgb(0.7, 0.5, 1.0);
 
 pub struct IchorBlastPlugin;
 
@@ -22,7 +26,7 @@ impl Plugin for IchorBlastPlugin {
         app
             .add_systems(Update, (
                 projectile_movement_system,
-                ichor_blast_collision_system,
+                timed_collision(ichor_blast_collision_system),
                 ichor_blast_lifetime_system,
             ).chain());
     }
@@ -33,12 +37,15 @@ pub struct IchorBlast {
     pub piercing_left: u32,
 }
 
-pub fn spawn_ichor_blast( commands: &mut Commands, asset_server: &Res<AssetServer>, position: Vec3, direction: Vec2, damage: i32, speed: f32, piercing: u32,) {
-    commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/ichor_blast_placeholder.png"), sprite: Sprite { custom_size: Some(ICHOR_BLAST_SIZE), color: Color::rgb(0.7, 0.5, 1.0), ..default() }, transform: Transform::from_translation(position).with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))), ..default() }, IchorBlast { piercing_left: piercing }, Velocity(direction * speed), Damage(damage), Lifetime { timer: Timer::from_seconds(FRAGMENT_LIFETIME_SECONDS, TimerMode::Once) }, Name::new("IchorBlast"), ));
+// Speed, piercing, lifetime and colour come from the caster's stats so every source of ichor blasts scales the same way
+pub fn spawn_ichor_blast( commands: &mut Commands, asset_server: &Res<AssetServer>, survivor: &Survivor, position: Vec3, direction: Vec2, damage: i32,) {
+    let visual = ProjectileVisual { texture: asset_server.load("sprites/ichor_blast_placeholder.png"), size: ICHOR_BLAST_SIZE, color: survivor.projectile_tint, z: None };
+    let spec = ProjectileSpec::new(ProjectileOwner::Survivor, damage, BASE_FRAGMENT_SPEED * survivor.ichor_blast_speed_multiplier, FRAGMENT_LIFETIME_SECONDS * survivor.duration_multiplier, visual, "IchorBlast").with_piercing(survivor.ichor_blast_piercing);
+    spawn_projectile(commands, spec, position, direction);
 }
 
-fn projectile_movement_system( mut query: Query<(&mut Transform, &Velocity), Or<(With<IchorBlast>, With<EnemyProjectile>, With<SkillProjectile>)>>, time: Res<Time>,) {
-    for (mut transform, velocity) in query.iter_mut() { transform.translation.x += velocity.0.x * time.delta_seconds(); transform.translation.y += velocity.0.y * time.delta_seconds(); }
+fn projectile_movement_system( mut query: Query<(&mut Transform, &Velocity, Option<&TimeScale>), Or<(With<IchorBlast>, With<HorrorProjectile>, With<SkillProjectile>)>>, time: Res<Time>,) {
+    for (mut transform, velocity, time_scale_opt) in query.iter_mut() { let scaled_delta_seconds = time.delta_seconds() * TimeScale::of(time_scale_opt); transform.translation.x += velocity.0.x * scaled_delta_seconds; transform.translation.y += velocity.0.y * scaled_delta_seconds; }
 }
 
 fn ichor_blast_lifetime_system( mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut Lifetime), With<IchorBlast>>, ) {
@@ -48,27 +55,32 @@ fn ichor_blast_lifetime_system( mut commands: Commands, time: Res<Time>, mut que
 fn ichor_blast_collision_system(
     mut commands: Commands,
     mut fragment_query: Query<(Entity, &GlobalTransform, &Damage, &mut IchorBlast)>,
-    mut enemy_query: Query<(Entity, &GlobalTransform, &mut Health, &crate::enemy::Horror)>,
+    mut enemy_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror, Option<&MaxHealth>)>,
     player_query: Query<&Survivor>, // Changed from Query<&crate::player::Survivor>
     item_library: Res<ItemLibrary>,
     asset_server: Res<AssetServer>,
     time: Res<Time>,
-    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    mut game_rng: ResMut<GameRng>,
+    style: Res<DamageTypeStyle>,
 ) {
     let Ok(player) = player_query.get_single() else { return };
+    let execute_fraction = execute_threshold(&player.collected_item_ids, &item_library);
 
     for (fragment_entity, fragment_gtransform, fragment_damage, mut fragment_stats) in fragment_query.iter_mut() {
-        for (enemy_entity, enemy_gtransform, mut enemy_health, enemy_data) in enemy_query.iter_mut() {
+        for (enemy_entity, enemy_gtransform, mut enemy_health, enemy_data, enemy_max_health) in enemy_query.iter_mut() {
             let distance = fragment_gtransform.translation().truncate().distance(enemy_gtransform.translation().truncate());
             let fragment_radius = ICHOR_BLAST_SIZE.x / 2.0;
             let enemy_radius = enemy_data.size.x / 2.0;
 
             if distance < fragment_radius + enemy_radius {
-                sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit));
-                enemy_health.0 -= fragment_damage.0;
-                spawn_damage_text(&mut commands, &asset_server, enemy_gtransform.translation(), fragment_damage.0, &time);
+                let hit = resolve_hit(&mut enemy_health.0, enemy_max_health.map(|max| max.0), fragment_damage.0, execute_fraction);
+                game_event_writer.send(GameEvent::DamageDealt { amount: hit.dealt, damage_type: DamageType::Eldritch, position: enemy_gtransform.translation(), origin: DamageOrigin::IchorBlast });
+                spawn_damage_text(&mut commands, enemy_gtransform.translation(), hit.dealt, &time);
+                spawn_impact_effect(&mut commands, &style, enemy_gtransform.translation(), DamageType::Eldritch);
+                commands.entity(enemy_entity).remove::<LastHitBySkill>();
 
-                let mut rng = rand::thread_rng();
+                let rng = &mut game_rng.0;
                 for item_id in player.collected_item_ids.iter() {
                     if let Some(item_def) = item_library.get_item_definition(*item_id) {
                         for effect in &item_def.effects {
@@ -87,9 +99,10 @@ fn ichor_blast_collision_system(
                                         },
                                         ExplosionEffect {
                                             damage: *explosion_damage,
-                                            radius_sq: explosion_radius.powi(2),
+                                            radius_sq: (explosion_radius * player.area_multiplier).powi(2),
                                             timer: Timer::from_seconds(0.3, TimerMode::Once), 
                                             already_hit_entities: vec![enemy_entity], 
+                                            origin: DamageOrigin::Relic,
                                         },
                                         Name::new("ItemHitExplosion"),
                                     ));
@@ -0,0 +1,241 @@
+use bevy::{prelude::*, input::InputSystem, window::PrimaryWindow};
+use crate::skills::MAX_SKILL_SLOTS;
+
+pub struct InputActionsPlugin;
+
+impl Plugin for InputActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<ActionState>()
+            .init_resource::<VirtualInput>()
+            .add_systems(PreUpdate, update_action_state.in_set(ActionStateSystem).after(InputSystem));
+    }
+}
+
+// Gameplay reads ActionState after this; anything that fakes raw input, like the headless simulation, runs before it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionStateSystem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Skill1,
+    Skill2,
+    Skill3,
+    Skill4,
+    Skill5,
+    Dash,
+    Pause,
+    Confirm,
+    Choice1,
+    Choice2,
+    Choice3,
+    Choice4,
+    Choice5,
+    PartnerChoice1,
+    PartnerChoice2,
+    PartnerChoice3,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 20] = [
+        InputAction::MoveUp, InputAction::MoveDown, InputAction::MoveLeft, InputAction::MoveRight,
+        InputAction::Skill1, InputAction::Skill2, InputAction::Skill3, InputAction::Skill4, InputAction::Skill5,
+        InputAction::Dash, InputAction::Pause, InputAction::Confirm,
+        InputAction::Choice1, InputAction::Choice2, InputAction::Choice3, InputAction::Choice4, InputAction::Choice5,
+        InputAction::PartnerChoice1, InputAction::PartnerChoice2, InputAction::PartnerChoice3,
+    ];
+    pub const SKILLS: [InputAction; MAX_SKILL_SLOTS] = [InputAction::Skill1, InputAction::Skill2, InputAction::Skill3, InputAction::Skill4, InputAction::Skill5];
+    // Menu options by position: upgrade cards use the first three, the glyph-slot pick one per skill slot
+    pub const CHOICES: [InputAction; MAX_SKILL_SLOTS] = [InputAction::Choice1, InputAction::Choice2, InputAction::Choice3, InputAction::Choice4, InputAction::Choice5];
+
+    pub fn skill(slot_index: usize) -> Option<InputAction> { Self::SKILLS.get(slot_index).copied() }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move up",
+            InputAction::MoveDown => "Move down",
+            InputAction::MoveLeft => "Move left",
+            InputAction::MoveRight => "Move right",
+            InputAction::Skill1 => "Skill 1",
+            InputAction::Skill2 => "Skill 2",
+            InputAction::Skill3 => "Skill 3",
+            InputAction::Skill4 => "Skill 4",
+            InputAction::Skill5 => "Skill 5",
+            InputAction::Dash => "Dash",
+            InputAction::Pause => "Pause",
+            InputAction::Confirm => "Confirm",
+            InputAction::Choice1 => "Choice 1",
+            InputAction::Choice2 => "Choice 2",
+            InputAction::Choice3 => "Choice 3",
+            InputAction::Choice4 => "Choice 4",
+            InputAction::Choice5 => "Choice 5",
+            InputAction::PartnerChoice1 => "Player 2 choice 1",
+            InputAction::PartnerChoice2 => "Player 2 choice 2",
+            InputAction::PartnerChoice3 => "Player 2 choice 3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl InputBinding {
+    pub fn is_held(&self, keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>) -> bool {
+        match self {
+            InputBinding::Key(key) => keys.pressed(*key),
+            InputBinding::Mouse(button) => mouse.pressed(*button),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => {
+                let name = format!("{:?}", key);
+                name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name).to_string()
+            }
+            InputBinding::Mouse(MouseButton::Left) => "LMB".to_string(),
+            InputBinding::Mouse(MouseButton::Right) => "RMB".to_string(),
+            InputBinding::Mouse(MouseButton::Middle) => "MMB".to_string(),
+            InputBinding::Mouse(button) => format!("{:?}", button),
+        }
+    }
+}
+
+// An action can have any number of bindings, and a binding can drive more than one action
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputMap {
+    pub bindings: Vec<(InputAction, InputBinding)>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use InputBinding::{Key, Mouse};
+        Self {
+            bindings: vec![
+                (InputAction::MoveUp, Key(KeyCode::KeyW)),
+                (InputAction::MoveDown, Key(KeyCode::KeyS)),
+                (InputAction::MoveLeft, Key(KeyCode::KeyA)),
+                (InputAction::MoveRight, Key(KeyCode::KeyD)),
+                (InputAction::Skill1, Mouse(MouseButton::Right)),
+                (InputAction::Skill1, Key(KeyCode::Digit1)),
+                (InputAction::Skill2, Key(KeyCode::Digit2)),
+                (InputAction::Skill3, Key(KeyCode::Digit3)),
+                (InputAction::Skill4, Key(KeyCode::KeyE)),
+                (InputAction::Skill5, Key(KeyCode::KeyR)),
+                (InputAction::Dash, Key(KeyCode::ShiftLeft)),
+                (InputAction::Pause, Key(KeyCode::Escape)),
+                (InputAction::Confirm, Key(KeyCode::Space)),
+                (InputAction::Confirm, Key(KeyCode::Enter)),
+                // Menus never run alongside skill casting, so the choices can share the skill number keys
+                (InputAction::Choice1, Key(KeyCode::Digit1)),
+                (InputAction::Choice2, Key(KeyCode::Digit2)),
+                (InputAction::Choice3, Key(KeyCode::Digit3)),
+                (InputAction::Choice4, Key(KeyCode::Digit4)),
+                (InputAction::Choice5, Key(KeyCode::Digit5)),
+                (InputAction::PartnerChoice1, Key(KeyCode::Numpad1)),
+                (InputAction::PartnerChoice2, Key(KeyCode::Numpad2)),
+                (InputAction::PartnerChoice3, Key(KeyCode::Numpad3)),
+            ],
+        }
+    }
+}
+
+impl InputMap {
+    pub fn bindings_for(&self, action: InputAction) -> impl Iterator<Item = InputBinding> + '_ {
+        self.bindings.iter().filter(move |(bound, _)| *bound == action).map(|(_, binding)| *binding)
+    }
+
+    pub fn bindings_label(&self, action: InputAction) -> String {
+        self.bindings_for(action).map(|binding| binding.label()).collect::<Vec<_>>().join(" / ")
+    }
+
+    pub fn bind(&mut self, action: InputAction, binding: InputBinding) {
+        if !self.bindings.contains(&(action, binding)) { self.bindings.push((action, binding)); }
+    }
+
+    pub fn unbind(&mut self, action: InputAction, binding: InputBinding) {
+        self.bindings.retain(|bound| *bound != (action, binding));
+    }
+
+    pub fn held_actions(&self, keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>) -> Vec<InputAction> {
+        InputAction::ALL.into_iter().filter(|action| self.bindings_for(*action).any(|binding| binding.is_held(keys, mouse))).collect()
+    }
+}
+
+// A cursor is a screen position still to be turned into a world direction; a stick already is a direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AimInput {
+    Cursor(Vec2),
+    Direction(Vec2),
+}
+
+// Input from devices with no bindings of their own, like the on-screen touch controls; filled in before ActionStateSystem each frame
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct VirtualInput {
+    pub held: Vec<InputAction>,
+    pub move_axis: Option<Vec2>,
+    pub aim_direction: Option<Vec2>,
+}
+
+// What the player is asking for this frame, whatever device it came from
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ActionState {
+    held: Vec<InputAction>,
+    just_pressed: Vec<InputAction>,
+    pub aim: Option<AimInput>,
+    // An analog stick replaces the four move actions outright, so a half push moves at half speed
+    pub move_override: Option<Vec2>,
+}
+
+impl ActionState {
+    // An action counts as just pressed on the first frame any of its bindings is held
+    pub fn update(&mut self, held: Vec<InputAction>) {
+        self.just_pressed = held.iter().filter(|action| !self.held.contains(action)).copied().collect();
+        self.held = held;
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool { self.held.contains(&action) }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool { self.just_pressed.contains(&action) }
+
+    // Not normalized, so opposite directions cancel out
+    pub fn move_axis(&self) -> Vec2 {
+        if let Some(axis) = self.move_override { return axis; }
+        let axis = |negative, positive| (self.pressed(positive) as i32 - self.pressed(negative) as i32) as f32;
+        Vec2::new(axis(InputAction::MoveLeft, InputAction::MoveRight), axis(InputAction::MoveDown, InputAction::MoveUp))
+    }
+
+    // The lowest slot wins when several skills are pressed on the same frame
+    pub fn skill_just_pressed(&self) -> Option<usize> {
+        InputAction::SKILLS.iter().position(|action| self.just_pressed(*action))
+    }
+
+    pub fn choice_just_pressed(&self) -> Option<usize> {
+        InputAction::CHOICES.iter().position(|action| self.just_pressed(*action))
+    }
+}
+
+fn update_action_state(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    input_map: Res<InputMap>,
+    virtual_input: Res<VirtualInput>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let mut held = input_map.held_actions(&keys, &mouse);
+    for action in virtual_input.held.iter() {
+        if !held.contains(action) { held.push(*action); }
+    }
+    action_state.update(held);
+    action_state.move_override = virtual_input.move_axis;
+    let cursor_aim = window_query.get_single().ok().and_then(Window::cursor_position).map(AimInput::Cursor);
+    action_state.aim = virtual_input.aim_direction.map(AimInput::Direction).or(cursor_aim);
+}
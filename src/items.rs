@@ -2,12 +2,15 @@ use bevy::prelude::*;
 // rand::Rng removed
 use crate::{
     survivor::Survivor, // Changed
-    components::{Health as ComponentHealth, Health},
+    components::{Health as ComponentHealth, Health, DamageType},
     game::{AppState, ItemCollectedEvent},
     horror::Horror, // Changed
     visual_effects::spawn_damage_text,
     audio::{PlaySoundEvent, SoundEffect},
-    skills::{SkillId, SkillLibrary, ActiveSkillInstance}, // Added SkillLibrary and ActiveSkillInstance
+    skills::{SkillId, SkillLibrary, ActiveSkillInstance, LastHitBySkill}, // Added SkillLibrary and ActiveSkillInstance
+    weapons::CircleOfWarding,
+    corruption::RunDifficulty,
+    game_events::{GameEvent, DamageOrigin},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
@@ -23,6 +26,12 @@ pub enum ItemEffect {
     OnSurvivorHitRetaliate { chance: f32, retaliation_damage: i32, retaliation_radius: f32, retaliation_color: Color, },
     OnHorrorKillTrigger { chance: f32, effect: SurvivorTemporaryBuff, },
     GrantSpecificSkill { skill_id: SkillId, },
+    AuraDamageFromMissingHealth { max_bonus: f32, },
+    GoldOnKill { amount: u32, },
+    ThornsReflect { damage_fraction: f32, per_attacker_cooldown_secs: f32, },
+    PreventDeathOnce { heal_fraction: f32, shockwave_damage: i32, shockwave_radius: f32, invulnerability_secs: f32, },
+    ExtendVision { radius_fraction: f32, },
+    ExecuteBelowHealth { health_fraction: f32, },
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -31,14 +40,18 @@ pub struct ItemDefinition { pub id: ItemId, pub name: String, pub description: S
 #[derive(Resource, Default, Reflect)] #[reflect(Resource)]
 pub struct ItemLibrary { pub items: Vec<ItemDefinition>, }
 impl ItemLibrary { pub fn get_item_definition(&self, id: ItemId) -> Option<&ItemDefinition> { self.items.iter().find(|def| def.id == id) } }
+// Highest execute threshold among the collected relics; execute effects don't stack
+pub fn execute_threshold(item_ids: &[ItemId], item_library: &ItemLibrary) -> f32 { item_ids.iter().filter_map(|item_id| item_library.get_item_definition(*item_id)).flat_map(|item_def| item_def.effects.iter()).filter_map(|effect| if let ItemEffect::ExecuteBelowHealth { health_fraction } = effect { Some(*health_fraction) } else { None }).fold(0.0, f32::max) }
 
 #[derive(Component, Debug)] pub struct ItemDrop { pub item_id: ItemId, }
 pub const ITEM_DROP_SIZE: Vec2 = Vec2::new(24.0, 24.0);
 
 #[derive(Component, Reflect, Default, Debug)] #[reflect(Component)]
-pub struct ExplosionEffect { pub damage: i32, pub radius_sq: f32, pub timer: Timer, pub already_hit_entities: Vec<Entity>, }
+pub struct ExplosionEffect { pub damage: i32, pub radius_sq: f32, pub timer: Timer, pub already_hit_entities: Vec<Entity>, #[reflect(ignore)] pub origin: DamageOrigin, }
 #[derive(Component, Reflect, Default, Debug)] #[reflect(Component)]
 pub struct RetaliationNovaEffect { pub damage: i32, pub radius_sq: f32, pub timer: Timer, pub already_hit_entities: Vec<Entity>, }
+// Marks a horror that thorns already answered recently
+#[derive(Component, Debug)] pub struct ThornsCooldown { pub timer: Timer, }
 #[derive(Component, Reflect, Default, Debug)] #[reflect(Component)]
 pub struct TemporaryHealthRegenBuff { pub regen_per_second: f32, pub duration_timer: Timer, }
 
@@ -47,7 +60,7 @@ impl Plugin for ItemsPlugin {
     fn build(&self, app: &mut App) {
         app .register_type::<ItemId>() .register_type::<SurvivorTemporaryBuff>() .register_type::<ItemEffect>() .register_type::<ItemLibrary>() .register_type::<ExplosionEffect>() .register_type::<RetaliationNovaEffect>() .register_type::<TemporaryHealthRegenBuff>() .init_resource::<ItemLibrary>()
             .add_systems(Startup, populate_item_library)
-            .add_systems(Update, ( apply_collected_item_effects_system.run_if(on_event::<ItemCollectedEvent>()), explosion_effect_system.run_if(in_state(AppState::InGame)), retaliation_nova_effect_system.run_if(in_state(AppState::InGame)), temporary_health_regen_buff_system.run_if(in_state(AppState::InGame)), ));
+            .add_systems(Update, ( apply_collected_item_effects_system.run_if(on_event::<ItemCollectedEvent>()), explosion_effect_system.run_if(in_state(AppState::InGame)), retaliation_nova_effect_system.run_if(in_state(AppState::InGame)), temporary_health_regen_buff_system.run_if(in_state(AppState::InGame)), missing_health_aura_modifier_system.run_if(in_state(AppState::InGame)), thorns_cooldown_tick_system.run_if(in_state(AppState::InGame)), ));
     }
 }
 
@@ -61,10 +74,16 @@ fn populate_item_library(mut library: ResMut<ItemLibrary>) {
     library.items.push(ItemDefinition { id: ItemId(7), name: "Cloak of VengefulSpirits".to_string(), description: "When struck, has a chance to unleash a damaging psychic nova.".to_string(), effects: vec![ItemEffect::OnSurvivorHitRetaliate { chance: 0.25, retaliation_damage: 30, retaliation_radius: 120.0, retaliation_color: Color::rgba(0.9, 0.1, 0.1, 0.5), }], });
     library.items.push(ItemDefinition { id: ItemId(8), name: "Soul Siphon Shard".to_string(), description: "Defeated foes have a 20% chance to grant brief, rapid health regeneration.".to_string(), effects: vec![ItemEffect::OnHorrorKillTrigger { chance: 0.20, effect: SurvivorTemporaryBuff::HealthRegen { rate: 5.0, duration_secs: 3.0 }, }], });
     library.items.push(ItemDefinition { id: ItemId(9), name: "Tome of Forbidden Rites".to_string(), description: "Grants knowledge of the 'Void Lance' skill.".to_string(), effects: vec![ItemEffect::GrantSpecificSkill { skill_id: SkillId(3) }], });
+    library.items.push(ItemDefinition { id: ItemId(10), name: "Phylactery".to_string(), description: "Once per run, refuse death: rise with half your Endurance in a blast that scatters nearby horrors.".to_string(), effects: vec![ItemEffect::PreventDeathOnce { heal_fraction: 0.5, shockwave_damage: 250, shockwave_radius: 260.0, invulnerability_secs: 3.0, }], });
+    library.items.push(ItemDefinition { id: ItemId(11), name: "Martyr's Brand".to_string(), description: "Inscribes the Circle of Warding; it burns up to 150% hotter the closer you are to death.".to_string(), effects: vec![ItemEffect::AuraDamageFromMissingHealth { max_bonus: 1.5 }], });
+    library.items.push(ItemDefinition { id: ItemId(12), name: "Gravedigger's Pouch".to_string(), description: "Every horror you fell leaves a coin behind in your pouch. +1 gold per kill.".to_string(), effects: vec![ItemEffect::GoldOnKill { amount: 1 }], });
+    library.items.push(ItemDefinition { id: ItemId(13), name: "Briar Crown".to_string(), description: "Horrors that strike you in the flesh take 60% of the blow back.".to_string(), effects: vec![ItemEffect::ThornsReflect { damage_fraction: 0.6, per_attacker_cooldown_secs: 1.0 }], });
+    library.items.push(ItemDefinition { id: ItemId(14), name: "Witchlight Lantern".to_string(), description: "A pale flame that pushes back the dark. Vision radius +35%.".to_string(), effects: vec![ItemEffect::ExtendVision { radius_fraction: 0.35 }], });
+    library.items.push(ItemDefinition { id: ItemId(15), name: "Headsman's Verdict".to_string(), description: "Horrors your projectiles leave below 12% Endurance are executed outright.".to_string(), effects: vec![ItemEffect::ExecuteBelowHealth { health_fraction: 0.12 }], });
 }
 
-fn apply_collected_item_effects_system( mut events: EventReader<ItemCollectedEvent>, mut player_query: Query<(&mut Survivor, Option<&mut ComponentHealth>)>, item_library: Res<ItemLibrary>, skill_library: Res<SkillLibrary>,) { // Added SkillLibrary
-    if let Ok((mut player, mut opt_health_component)) = player_query.get_single_mut() {
+fn apply_collected_item_effects_system( mut events: EventReader<ItemCollectedEvent>, mut player_query: Query<(&mut Survivor, Option<&mut ComponentHealth>, Option<&mut CircleOfWarding>)>, item_library: Res<ItemLibrary>, skill_library: Res<SkillLibrary>,) { // Added SkillLibrary
+    if let Ok((mut player, mut opt_health_component, mut opt_circle_aura)) = player_query.get_single_mut() {
         for event in events.read() {
             let item_id = event.0; if player.collected_item_ids.contains(&item_id) { continue; }
             if let Some(item_def) = item_library.get_item_definition(item_id) {
@@ -80,12 +99,12 @@ fn apply_collected_item_effects_system( mut events: EventReader<ItemCollectedEve
                         }
                         ItemEffect::GrantSpecificSkill { skill_id } => {
                             if let Some(skill_to_grant_def) = skill_library.get_skill_definition(*skill_id) { // Corrected: Use skill_library
-                                let already_has_skill = player.equipped_skills.iter().any(|s| s.definition_id == *skill_id);
-                                if !already_has_skill { if player.equipped_skills.len() < 4 { // Max 4 skills currently based on input
-                                    player.equipped_skills.push(ActiveSkillInstance::new(*skill_id, skill_to_grant_def.base_glyph_slots)); // Corrected: Pass base_glyph_slots
-                                } }
+                                player.learn_skill(ActiveSkillInstance::new(*skill_id, skill_to_grant_def.base_glyph_slots)); // Corrected: Pass base_glyph_slots
                             }
                         }
+                        ItemEffect::AuraDamageFromMissingHealth { .. } => {
+                            if let Some(ref mut circle_aura) = opt_circle_aura { circle_aura.is_active = true; }
+                        }
                         _ => {}
                     }
                 }
@@ -94,6 +113,10 @@ fn apply_collected_item_effects_system( mut events: EventReader<ItemCollectedEve
     }
 }
 
-fn explosion_effect_system( mut commands: Commands, time: Res<Time>, mut explosion_query: Query<(Entity, &mut ExplosionEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, asset_server: Res<AssetServer>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (explosion_entity, mut explosion, explosion_g_transform, mut sprite, mut vis_transform) in explosion_query.iter_mut() { explosion.timer.tick(time.delta()); let progress = explosion.timer.fraction(); let current_radius = explosion.radius_sq.sqrt(); vis_transform.scale = Vec3::splat(current_radius * 2.0 * progress); sprite.color.set_a(1.0 - progress); if explosion.timer.fraction() < 0.5 { let explosion_pos = explosion_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if explosion.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(explosion_pos) < explosion.radius_sq { horror_health.0 -= explosion.damage; spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), explosion.damage, &time); sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit)); explosion.already_hit_entities.push(horror_entity); } } } if explosion.timer.finished() { commands.entity(explosion_entity).despawn_recursive(); } } }
-fn retaliation_nova_effect_system( mut commands: Commands, time: Res<Time>, mut nova_query: Query<(Entity, &mut RetaliationNovaEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, asset_server: Res<AssetServer>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (nova_entity, mut nova, nova_g_transform, mut sprite, mut vis_transform) in nova_query.iter_mut() { nova.timer.tick(time.delta()); let progress = nova.timer.fraction(); let current_radius = nova.radius_sq.sqrt(); vis_transform.scale = Vec3::splat(current_radius * 2.0 * progress); sprite.color.set_a(1.0 - progress * progress); if nova.timer.fraction() < 0.3 { let nova_pos = nova_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if nova.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(nova_pos) < nova.radius_sq { horror_health.0 -= nova.damage; spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), nova.damage, &time); sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit)); nova.already_hit_entities.push(horror_entity); } } } if nova.timer.finished() { commands.entity(nova_entity).despawn_recursive(); } } }
-fn temporary_health_regen_buff_system( mut commands: Commands, time: Res<Time>, mut buff_query: Query<(Entity, &mut TemporaryHealthRegenBuff, &Survivor, &mut ComponentHealth)>,) { for (entity, mut buff, survivor_stats, mut health_component) in buff_query.iter_mut() { buff.duration_timer.tick(time.delta()); if buff.duration_timer.finished() { commands.entity(entity).remove::<TemporaryHealthRegenBuff>(); } else { let regen_amount = buff.regen_per_second * time.delta().as_secs_f32(); health_component.0 = (health_component.0 as f32 + regen_amount).round() as i32; health_component.0 = health_component.0.min(survivor_stats.max_health); } } }
\ No newline at end of file
+#[allow(clippy::possible_missing_else)]
+fn explosion_effect_system( mut commands: Commands, time: Res<Time>, mut explosion_query: Query<(Entity, &mut ExplosionEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, mut sound_event_writer: EventWriter<PlaySoundEvent>, mut game_event_writer: EventWriter<GameEvent>,) { for (explosion_entity, mut explosion, explosion_g_transform, mut sprite, mut vis_transform) in explosion_query.iter_mut() { explosion.timer.tick(time.delta()); let progress = explosion.timer.fraction(); let current_radius = explosion.radius_sq.sqrt(); vis_transform.scale = Vec3::splat(current_radius * 2.0 * progress); sprite.color.set_a(1.0 - progress); if explosion.timer.fraction() < 0.5 { let explosion_pos = explosion_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if explosion.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(explosion_pos) < explosion.radius_sq { horror_health.0 -= explosion.damage; commands.entity(horror_entity).remove::<LastHitBySkill>(); spawn_damage_text(&mut commands, horror_gtransform.translation(), explosion.damage, &time); game_event_writer.send(GameEvent::DamageDealt { amount: explosion.damage, damage_type: DamageType::Eldritch, position: horror_gtransform.translation(), origin: explosion.origin }); sound_event_writer.send(PlaySoundEvent::at(SoundEffect::HorrorHit, horror_pos)); explosion.already_hit_entities.push(horror_entity); } } } if explosion.timer.finished() { commands.entity(explosion_entity).despawn_recursive(); } } }
+#[allow(clippy::possible_missing_else)]
+fn retaliation_nova_effect_system( mut commands: Commands, time: Res<Time>, mut nova_query: Query<(Entity, &mut RetaliationNovaEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, mut sound_event_writer: EventWriter<PlaySoundEvent>, mut game_event_writer: EventWriter<GameEvent>,) { for (nova_entity, mut nova, nova_g_transform, mut sprite, mut vis_transform) in nova_query.iter_mut() { nova.timer.tick(time.delta()); let progress = nova.timer.fraction(); let current_radius = nova.radius_sq.sqrt(); vis_transform.scale = Vec3::splat(current_radius * 2.0 * progress); sprite.color.set_a(1.0 - progress * progress); if nova.timer.fraction() < 0.3 { let nova_pos = nova_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if nova.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(nova_pos) < nova.radius_sq { horror_health.0 -= nova.damage; commands.entity(horror_entity).remove::<LastHitBySkill>(); spawn_damage_text(&mut commands, horror_gtransform.translation(), nova.damage, &time); game_event_writer.send(GameEvent::DamageDealt { amount: nova.damage, damage_type: DamageType::Eldritch, position: horror_gtransform.translation(), origin: DamageOrigin::Relic }); sound_event_writer.send(PlaySoundEvent::at(SoundEffect::HorrorHit, horror_pos)); nova.already_hit_entities.push(horror_entity); } } } if nova.timer.finished() { commands.entity(nova_entity).despawn_recursive(); } } }
+fn temporary_health_regen_buff_system( mut commands: Commands, time: Res<Time>, run_difficulty: Res<RunDifficulty>, mut buff_query: Query<(Entity, &mut TemporaryHealthRegenBuff, &Survivor, &mut ComponentHealth)>,) { for (entity, mut buff, survivor_stats, mut health_component) in buff_query.iter_mut() { buff.duration_timer.tick(time.delta()); if buff.duration_timer.finished() { commands.entity(entity).remove::<TemporaryHealthRegenBuff>(); } else { let regen_amount = run_difficulty.scale_healing(buff.regen_per_second * time.delta().as_secs_f32()); health_component.0 = (health_component.0 as f32 + regen_amount).round() as i32; health_component.0 = health_component.0.min(survivor_stats.max_health); } } }
+fn missing_health_aura_modifier_system(mut player_query: Query<(&Survivor, &ComponentHealth, &mut CircleOfWarding)>, item_library: Res<ItemLibrary>,) { for (survivor, health, mut circle_aura) in player_query.iter_mut() { let max_bonus: f32 = survivor.collected_item_ids.iter().filter_map(|item_id| item_library.get_item_definition(*item_id)).flat_map(|item_def| item_def.effects.iter()).filter_map(|effect| if let ItemEffect::AuraDamageFromMissingHealth { max_bonus } = effect { Some(*max_bonus) } else { None }).sum(); let missing_fraction = 1.0 - (health.0.max(0) as f32 / survivor.max_health.max(1) as f32).min(1.0); let new_multiplier = 1.0 + max_bonus * missing_fraction; if circle_aura.damage_multiplier != new_multiplier { circle_aura.damage_multiplier = new_multiplier; } } }
+fn thorns_cooldown_tick_system(mut commands: Commands, time: Res<Time>, mut cooldown_query: Query<(Entity, &mut ThornsCooldown)>,) { for (entity, mut cooldown) in cooldown_query.iter_mut() { cooldown.timer.tick(time.delta()); if cooldown.timer.finished() { commands.entity(entity).remove::<ThornsCooldown>(); } } }
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use crate::{
+    game::{AppState, GameState},
+    game_events::GameEvent,
+    bestiary::horror_name,
+    score::ELITE_KILL_BONUS,
+    camera_systems::{MainCamera, approach_zoom, smoothing_fraction},
+    visual_effects::spawn_particle_burst,
+};
+
+pub const KILL_CAM_SECONDS: f32 = 1.5;
+pub const KILL_CAM_TIME_SCALE: f32 = 0.25;
+// Orthographic scale while the camera is on the body; below 1 is closer than the normal view
+pub const KILL_CAM_ZOOM: f32 = 0.6;
+// The last share of the shot eases time back to full speed instead of snapping
+pub const KILL_CAM_RECOVERY_FRACTION: f32 = 0.2;
+const KILL_CAM_CAMERA_SPEED: f32 = 8.0;
+const KILL_CAM_PARTICLES: u32 = 40;
+const KILL_CAM_PARTICLE_SPEED: f32 = 320.0;
+const KILL_CAM_COLOR: Color = Color::rgb(1.0, 0.85, 0.35);
+
+pub struct KillCamPlugin;
+
+impl Plugin for KillCamPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KillCam>()
+            .add_systems(Update, (start_kill_cam, run_kill_cam).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), end_kill_cam)
+            .add_systems(OnEnter(AppState::MainMenu), end_kill_cam);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KillCamShot {
+    pub focus: Vec2,
+    pub timer: Timer,
+    pub score_before: u32,
+}
+
+// Elites stand in for bosses: killing one slows the fight and swings the camera onto the body for a moment
+#[derive(Resource, Debug, Default)]
+pub struct KillCam {
+    pub shot: Option<KillCamShot>,
+}
+
+impl KillCam {
+    pub fn is_active(&self) -> bool { self.shot.is_some() }
+
+    // What every TimeScale is multiplied by right now
+    pub fn time_scale(&self) -> f32 {
+        self.shot.as_ref().map_or(1.0, |shot| kill_cam_time_scale(shot.timer.fraction()))
+    }
+}
+
+pub fn kill_cam_inactive(kill_cam: Res<KillCam>) -> bool { !kill_cam.is_active() }
+
+pub fn kill_cam_time_scale(fraction: f32) -> f32 {
+    let recovery_start = 1.0 - KILL_CAM_RECOVERY_FRACTION;
+    if fraction <= recovery_start { return KILL_CAM_TIME_SCALE; }
+    let recovered = ((fraction - recovery_start) / KILL_CAM_RECOVERY_FRACTION).min(1.0);
+    KILL_CAM_TIME_SCALE + (1.0 - KILL_CAM_TIME_SCALE) * recovered
+}
+
+// The score counts up from where it stood before the kill to the current total over the first half of the shot
+pub fn tally_score(score_before: u32, score_now: u32, fraction: f32) -> u32 {
+    let progress = (fraction * 2.0).clamp(0.0, 1.0);
+    score_before + (score_now.saturating_sub(score_before) as f32 * progress).round() as u32
+}
+
+#[derive(Component)]
+struct KillCamTally;
+
+#[derive(Component)]
+struct KillCamTallyScore;
+
+fn start_kill_cam(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_events: EventReader<GameEvent>,
+    game_state: Res<GameState>,
+    mut kill_cam: ResMut<KillCam>,
+    tally_query: Query<Entity, With<KillCamTally>>,
+) {
+    // Several elites dying together share one shot, on the last of them
+    let Some(kill) = game_events.read().filter_map(GameEvent::as_horror_kill).filter(|kill| kill.is_elite).last() else { return; };
+    let kill_points = kill.xp_value / 2;
+    kill_cam.shot = Some(KillCamShot {
+        focus: kill.position.truncate(),
+        timer: Timer::from_seconds(KILL_CAM_SECONDS, TimerMode::Once),
+        score_before: game_state.score.saturating_sub(kill_points),
+    });
+    spawn_particle_burst(&mut commands, kill.position, KILL_CAM_COLOR, KILL_CAM_PARTICLES, KILL_CAM_PARTICLE_SPEED);
+
+    for entity in tally_query.iter() { commands.entity(entity).despawn_recursive(); }
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle {
+            style: Style { position_type: PositionType::Absolute, top: Val::Percent(22.0), width: Val::Percent(100.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Center, row_gap: Val::Px(4.0), ..default() },
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+        KillCamTally,
+        Name::new("KillCamTally"),
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(format!("{} slain", horror_name(kill.horror_type)), TextStyle { font: font.clone(), font_size: 40.0, color: KILL_CAM_COLOR }));
+        parent.spawn(TextBundle::from_section(format!("Kill +{}   Elite bonus +{}", kill_points, ELITE_KILL_BONUS), TextStyle { font: font.clone(), font_size: 24.0, color: Color::WHITE }));
+        parent.spawn((
+            TextBundle::from_section(format!("Score {}", game_state.score), TextStyle { font, font_size: 30.0, color: KILL_CAM_COLOR }),
+            KillCamTallyScore,
+        ));
+    });
+}
+
+// Runs on unscaled time, so the shot lasts the same however slow the fight around it is
+fn run_kill_cam(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut kill_cam: ResMut<KillCam>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    mut score_text_query: Query<&mut Text, With<KillCamTallyScore>>,
+    tally_query: Query<Entity, With<KillCamTally>>,
+) {
+    let Some(shot) = kill_cam.shot.as_mut() else { return; };
+    if shot.timer.tick(time.delta()).finished() {
+        kill_cam.shot = None;
+        for entity in tally_query.iter() { commands.entity(entity).despawn_recursive(); }
+        return;
+    }
+    if let Ok((mut camera_transform, mut projection)) = camera_query.get_single_mut() {
+        let camera_pos = camera_transform.translation.truncate().lerp(shot.focus, smoothing_fraction(KILL_CAM_CAMERA_SPEED, time.delta_seconds()));
+        camera_transform.translation = camera_pos.extend(camera_transform.translation.z);
+        projection.scale = approach_zoom(projection.scale, KILL_CAM_ZOOM, KILL_CAM_CAMERA_SPEED, time.delta_seconds());
+    }
+    for mut text in score_text_query.iter_mut() {
+        text.sections[0].value = format!("Score {}", tally_score(shot.score_before, game_state.score, shot.timer.fraction()));
+    }
+}
+
+fn end_kill_cam(mut commands: Commands, mut kill_cam: ResMut<KillCam>, tally_query: Query<Entity, With<KillCamTally>>) {
+    kill_cam.shot = None;
+    for entity in tally_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::{
+    survivor::{Survivor, SURVIVOR_SIZE},
+    game::{AppState, GameRng},
+    game_events::GameEvent,
+    luck::roll_with_luck,
+    ranking::Remnants,
+    audio::{PlaySoundEvent, SoundEffect},
+};
+
+const EMBEDDED_LORE: &str = include_str!("../assets/data/lore.ron");
+pub const WHISPERED_PAGE_DROP_CHANCE: f64 = 0.003;
+pub const ELITE_WHISPERED_PAGE_DROP_CHANCE: f64 = 0.05;
+pub const WHISPERED_PAGE_REMNANTS: u32 = 5;
+const WHISPERED_PAGE_SIZE: Vec2 = Vec2::new(20.0, 26.0);
+const WHISPERED_PAGE_COLOR: Color = Color::rgb(0.9, 0.85, 0.6);
+const WHISPERED_PAGE_PICKUP_RADIUS: f32 = SURVIVOR_SIZE.x / 2.0 + WHISPERED_PAGE_SIZE.x / 2.0;
+const LORE_TOAST_SECS: f32 = 4.0;
+
+pub struct LorePlugin;
+
+impl Plugin for LorePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LoreLibrary::from_ron_str(EMBEDDED_LORE).expect("embedded lore is invalid"))
+            .init_resource::<LoreCollection>()
+            .add_systems(Update, (drop_whispered_pages, collect_whispered_pages).chain().run_if(in_state(AppState::InGame)))
+            // The toast keeps fading while a level-up or pause screen is up, so it never lingers behind one
+            .add_systems(Update, fade_lore_toasts)
+            .add_systems(OnEnter(AppState::GameOver), (despawn_whispered_pages, despawn_lore_toasts))
+            .add_systems(OnEnter(AppState::MainMenu), (despawn_whispered_pages, despawn_lore_toasts));
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LorePage {
+    pub id: u32,
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct LoreLibrary {
+    pub pages: Vec<LorePage>,
+}
+
+impl LoreLibrary {
+    pub fn from_ron_str(source: &str) -> Result<Self, LoreLibraryError> {
+        let library: LoreLibrary = ron::de::from_str(source)?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    pub fn validate(&self) -> Result<(), LoreLibraryError> {
+        if self.pages.is_empty() { return Err(LoreLibraryError::Empty); }
+        for (i, page) in self.pages.iter().enumerate() {
+            if self.pages[..i].iter().any(|earlier| earlier.id == page.id) { return Err(LoreLibraryError::DuplicateId(page.id)); }
+            if page.title.trim().is_empty() || page.text.trim().is_empty() { return Err(LoreLibraryError::EmptyPage(page.id)); }
+        }
+        Ok(())
+    }
+
+    // Pages are found in book order, so the story reads the same for everyone
+    pub fn next_uncollected(&self, collection: &LoreCollection) -> Option<&LorePage> {
+        self.pages.iter().find(|page| !collection.has(page.id))
+    }
+}
+
+#[derive(Debug)]
+pub enum LoreLibraryError {
+    Parse(ron::error::SpannedError),
+    Empty,
+    DuplicateId(u32),
+    EmptyPage(u32),
+}
+
+impl std::fmt::Display for LoreLibraryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoreLibraryError::Parse(error) => write!(f, "could not parse lore: {}", error),
+            LoreLibraryError::Empty => write!(f, "no lore pages are defined"),
+            LoreLibraryError::DuplicateId(id) => write!(f, "lore page {} is listed more than once", id),
+            LoreLibraryError::EmptyPage(id) => write!(f, "lore page {} has no title or text", id),
+        }
+    }
+}
+
+impl std::error::Error for LoreLibraryError {}
+
+impl From<ron::error::SpannedError> for LoreLibraryError {
+    fn from(error: ron::error::SpannedError) -> Self { LoreLibraryError::Parse(error) }
+}
+
+// Ids of the pages found so far, in the order they were found; kept in the profile
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct LoreCollection {
+    pub collected: Vec<u32>,
+}
+
+impl LoreCollection {
+    pub fn has(&self, id: u32) -> bool { self.collected.contains(&id) }
+
+    // Returns true if the page was new
+    pub fn collect(&mut self, id: u32) -> bool {
+        if self.has(id) { return false; }
+        self.collected.push(id);
+        true
+    }
+}
+
+#[derive(Component)]
+pub struct WhisperedPage;
+
+#[derive(Component)]
+struct LoreToast(Timer);
+
+// Only one page lies on the ground at a time, and none drop once the book is complete
+fn drop_whispered_pages(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_events: EventReader<GameEvent>,
+    library: Res<LoreLibrary>,
+    collection: Res<LoreCollection>,
+    page_query: Query<(), With<WhisperedPage>>,
+    player_query: Query<&Survivor>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let kills: Vec<_> = game_events.read().filter_map(GameEvent::as_horror_kill).copied().collect();
+    if !page_query.is_empty() || library.next_uncollected(&collection).is_none() { return; }
+    let luck = player_query.get_single().map_or(0.0, |player| player.luck);
+    let rng = &mut game_rng.0;
+    let Some(kill) = kills.into_iter().find(|kill| roll_with_luck(rng, if kill.is_elite { ELITE_WHISPERED_PAGE_DROP_CHANCE } else { WHISPERED_PAGE_DROP_CHANCE }, luck)) else { return; };
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/item_drop_placeholder.png"),
+            sprite: Sprite { custom_size: Some(WHISPERED_PAGE_SIZE), color: WHISPERED_PAGE_COLOR, ..default() },
+            transform: Transform::from_translation(kill.position.truncate().extend(0.4)).with_rotation(Quat::from_rotation_z(rng.gen_range(-0.4..0.4))),
+            ..default()
+        },
+        WhisperedPage,
+        Name::new("WhisperedPage"),
+    ));
+}
+
+fn collect_whispered_pages(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    page_query: Query<(Entity, &Transform), With<WhisperedPage>>,
+    player_query: Query<&Transform, With<Survivor>>,
+    library: Res<LoreLibrary>,
+    mut collection: ResMut<LoreCollection>,
+    mut remnants: ResMut<Remnants>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    for (page_entity, page_transform) in page_query.iter() {
+        if player_pos.distance(page_transform.translation.truncate()) >= WHISPERED_PAGE_PICKUP_RADIUS { continue; }
+        commands.entity(page_entity).despawn_recursive();
+        let Some(page) = library.next_uncollected(&collection).cloned() else { continue; };
+        collection.collect(page.id);
+        remnants.0 = remnants.0.saturating_add(WHISPERED_PAGE_REMNANTS);
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::ItemPickup));
+        commands.spawn((
+            TextBundle::from_section(
+                format!("Whispered Page found: {}  (+{} remnants, {} of {})", page.title, WHISPERED_PAGE_REMNANTS, collection.collected.len(), library.pages.len()),
+                TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 24.0, color: WHISPERED_PAGE_COLOR },
+            ).with_style(Style { position_type: PositionType::Absolute, top: Val::Px(140.0), width: Val::Percent(100.0), justify_content: JustifyContent::Center, ..default() })
+            .with_text_justify(JustifyText::Center),
+            LoreToast(Timer::from_seconds(LORE_TOAST_SECS, TimerMode::Once)),
+        ));
+    }
+}
+
+fn fade_lore_toasts(mut commands: Commands, time: Res<Time>, mut toast_query: Query<(Entity, &mut LoreToast, &mut Text)>) {
+    for (entity, mut toast, mut text) in toast_query.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        // Holds fully visible for the first half, then fades out
+        text.sections[0].style.color.set_a((2.0 - toast.0.fraction() * 2.0).min(1.0));
+    }
+}
+
+fn despawn_whispered_pages(mut commands: Commands, page_query: Query<Entity, With<WhisperedPage>>) {
+    for entity in page_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn despawn_lore_toasts(mut commands: Commands, toast_query: Query<Entity, With<LoreToast>>) {
+    for entity in toast_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,23 @@
+use rand::{Rng, seq::SliceRandom};
+
+// Luck-aware rolls. Callers pass their own rng so the effect of luck can be checked with a seeded generator.
+pub const MAX_LUCK_CHANCE: f64 = 0.95;
+
+// Luck scales a base chance proportionally: 0.5 luck turns a 10% roll into 15%
+pub fn luck_adjusted_chance(base_chance: f64, luck: f32) -> f64 {
+    if base_chance <= 0.0 { return 0.0; }
+    (base_chance * (1.0 + luck.max(0.0) as f64)).min(MAX_LUCK_CHANCE.max(base_chance)).min(1.0)
+}
+
+pub fn roll_with_luck<R: Rng>(rng: &mut R, base_chance: f64, luck: f32) -> bool {
+    rng.gen_bool(luck_adjusted_chance(base_chance, luck))
+}
+
+// Rare entries gain weight with luck, common entries keep a weight of 1
+pub fn choose_multiple_with_luck<'a, T, R: Rng>(rng: &mut R, options: &'a [T], count: usize, luck: f32, is_rare: impl Fn(&T) -> bool) -> Vec<&'a T> {
+    let rare_weight = 1.0 + luck.max(0.0);
+    match options.choose_multiple_weighted(rng, count, |option| if is_rare(option) { rare_weight } else { 1.0 }) {
+        Ok(chosen) => chosen.collect(),
+        Err(_) => options.choose_multiple(rng, count).collect(),
+    }
+}
@@ -1,49 +1,88 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, asset::io::AssetSourceBuilder, render::camera::ScalingMode};
 
-mod survivor; // Changed
-mod components;
-mod horror; // Changed
-mod ichor_blast; // Changed
-mod game;
-mod echoing_soul; // Changed
-mod upgrades;
-mod level_event_effects;
-mod weapons;
-mod visual_effects;
-mod audio;
-mod camera_systems;
-mod background;
-mod debug_menu;
-mod skills;
-mod items;
-mod glyphs;
-
-use survivor::SurvivorPlugin; // Changed
-use horror::HorrorPlugin; // Changed
-use ichor_blast::IchorBlastPlugin; // Changed
-use game::{GamePlugin, SCREEN_WIDTH, SCREEN_HEIGHT};
-use level_event_effects::LevelEventEffectsPlugin;
-use weapons::WeaponsPlugin;
-use visual_effects::VisualEffectsPlugin;
-use audio::GameAudioPlugin;
-use camera_systems::{CameraSystemsPlugin, MainCamera};
-use background::BackgroundPlugin;
-use skills::SkillsPlugin;
-use items::ItemsPlugin;
-use glyphs::GlyphsPlugin;
+use cosmic_gardener::survivor::SurvivorPlugin; // Changed
+use cosmic_gardener::horror::HorrorPlugin; // Changed
+use cosmic_gardener::ichor_blast::IchorBlastPlugin; // Changed
+use cosmic_gardener::game::{GamePlugin, SCREEN_WIDTH, SCREEN_HEIGHT};
+use cosmic_gardener::level_event_effects::LevelEventEffectsPlugin;
+use cosmic_gardener::weapons::WeaponsPlugin;
+use cosmic_gardener::visual_effects::VisualEffectsPlugin;
+use cosmic_gardener::audio::GameAudioPlugin;
+use cosmic_gardener::camera_systems::{CameraSystemsPlugin, MainCamera};
+use cosmic_gardener::background::BackgroundPlugin;
+use cosmic_gardener::skills::SkillsPlugin;
+use cosmic_gardener::items::ItemsPlugin;
+use cosmic_gardener::glyphs::GlyphsPlugin;
+use cosmic_gardener::gold::GoldPlugin;
+use cosmic_gardener::consumables::ConsumablesPlugin;
+use cosmic_gardener::stat_sheet::StatSheetPlugin;
+use cosmic_gardener::overwhelm::OverwhelmPlugin;
+use cosmic_gardener::projectile::ProjectilePlugin;
+use cosmic_gardener::horror_stats::HorrorStatsPlugin;
+use cosmic_gardener::contracts::ContractsPlugin;
+use cosmic_gardener::ranking::RankingPlugin;
+use cosmic_gardener::darkness::DarknessPlugin;
+use cosmic_gardener::reward_reveal::RewardRevealPlugin;
+use cosmic_gardener::score::ScorePlugin;
+use cosmic_gardener::run_history::RunHistoryPlugin;
+use cosmic_gardener::menu_focus::MenuFocusPlugin;
+use cosmic_gardener::game_events::GameEventsPlugin;
+use cosmic_gardener::mods::{ModsPlugin, MODS_ASSET_SOURCE, MODS_DIR};
+use cosmic_gardener::skill_scripts::SkillScriptsPlugin;
+use cosmic_gardener::save_data::SaveDataPlugin;
+use cosmic_gardener::perf_overlay::PerfOverlayPlugin;
+use cosmic_gardener::dps_meter::DpsMeterPlugin;
+use cosmic_gardener::pause_menu::PauseMenuPlugin;
+use cosmic_gardener::respec_shrine::RespecShrinePlugin;
+use cosmic_gardener::color_grade::ColorGradePlugin;
+use cosmic_gardener::floating_origin::FloatingOriginPlugin;
+use cosmic_gardener::taunt::TauntPlugin;
+use cosmic_gardener::challenge::ChallengePlugin;
+use cosmic_gardener::hardcore::HardcorePlugin;
+use cosmic_gardener::extraction::ExtractionPlugin;
+use cosmic_gardener::corruption::CorruptionPlugin;
+use cosmic_gardener::aggro::AggroPlugin;
+use cosmic_gardener::bestiary::BestiaryPlugin;
+use cosmic_gardener::lore::LorePlugin;
+use cosmic_gardener::boss_practice::BossPracticePlugin;
+use cosmic_gardener::balance::BalancePlugin;
+use cosmic_gardener::input_actions::InputActionsPlugin;
+use cosmic_gardener::touch_controls::TouchControlsPlugin;
+use cosmic_gardener::platform::{PlatformPlugin, primary_window};
+use cosmic_gardener::platform_services::PlatformServicesPlugin;
+use cosmic_gardener::skins::SkinsPlugin;
+use cosmic_gardener::kill_cam::KillCamPlugin;
+use cosmic_gardener::void_boundary::VoidBoundaryPlugin;
+use cosmic_gardener::escort::EscortPlugin;
+use cosmic_gardener::simulation::{SimulationConfig, SimulationPlugin, headless_plugins};
 // Remove 'use experience::ExperiencePlugin' if it exists, as it's handled by GamePlugin
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Echoes of the Abyss".into(),
-                resolution: (SCREEN_WIDTH, SCREEN_HEIGHT).into(),
-                resizable: false,
+    let simulation = match SimulationConfig::from_args(std::env::args().skip(1)) {
+        Ok(simulation) => simulation,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    };
+
+    let mut app = App::new();
+    // Asset sources have to exist before the AssetPlugin inside DefaultPlugins starts up
+    app.register_asset_source(MODS_ASSET_SOURCE, AssetSourceBuilder::platform_default(MODS_DIR, None));
+    match simulation {
+        // Simulations must not depend on what is in the mods folder or touch the player's save
+        Some(config) => {
+            app.add_plugins(headless_plugins()).add_plugins(SimulationPlugin(config));
+        }
+        None => {
+            app.add_plugins(DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(primary_window("Echoes of the Abyss")),
                 ..default()
-            }),
-            ..default()
-        }))
+            }))
+            .add_plugins((ModsPlugin, SaveDataPlugin, PlatformServicesPlugin));
+        }
+    }
+    app
         .add_plugins((
             GamePlugin, // GamePlugin adds EchoingSoulPlugin (formerly ExperiencePlugin)
             SurvivorPlugin, 
@@ -55,9 +94,53 @@ fn main() {
             GameAudioPlugin, 
             CameraSystemsPlugin, 
             BackgroundPlugin,
+        ))
+        .add_plugins((
             SkillsPlugin, 
             ItemsPlugin, 
             GlyphsPlugin,
+            GoldPlugin,
+            ConsumablesPlugin,
+            StatSheetPlugin,
+            OverwhelmPlugin,
+            ProjectilePlugin,
+            HorrorStatsPlugin,
+            ContractsPlugin,
+            RankingPlugin,
+            DarknessPlugin,
+            RewardRevealPlugin,
+            ScorePlugin,
+        ))
+        .add_plugins((
+            RunHistoryPlugin,
+            MenuFocusPlugin,
+            GameEventsPlugin,
+            SkillScriptsPlugin,
+            PerfOverlayPlugin,
+            DpsMeterPlugin,
+            PauseMenuPlugin,
+            RespecShrinePlugin,
+            ColorGradePlugin,
+            FloatingOriginPlugin,
+            TauntPlugin,
+            ChallengePlugin,
+            HardcorePlugin,
+            ExtractionPlugin,
+        ))
+        .add_plugins((
+            CorruptionPlugin,
+            AggroPlugin,
+            BestiaryPlugin,
+            LorePlugin,
+            BossPracticePlugin,
+            BalancePlugin,
+            InputActionsPlugin,
+            TouchControlsPlugin,
+            PlatformPlugin,
+            SkinsPlugin,
+            KillCamPlugin,
+            VoidBoundaryPlugin,
+            EscortPlugin,
         ))
         .add_systems(Startup, setup_global_camera)
         .run();
@@ -66,5 +149,7 @@ fn main() {
 fn setup_global_camera(mut commands: Commands) {
     let mut camera_bundle = Camera2dBundle::default();
     camera_bundle.transform.translation.z = 999.0; // Ensure camera is on top
+    // A browser canvas can be any shape; at least the design resolution's worth of arena stays in view either way
+    camera_bundle.projection.scaling_mode = ScalingMode::AutoMin { min_width: SCREEN_WIDTH, min_height: SCREEN_HEIGHT };
     commands.spawn((camera_bundle, MainCamera));
 }
\ No newline at end of file
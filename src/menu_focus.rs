@@ -0,0 +1,154 @@
+use bevy::{prelude::*, ui::UiSystem};
+
+pub const FOCUS_ANNOUNCER_TOGGLE_KEY: KeyCode = KeyCode::F8;
+const FOCUS_OUTLINE_WIDTH: f32 = 3.0;
+const FOCUS_OUTLINE_COLOR: Color = Color::rgb(1.0, 0.85, 0.2);
+// Buttons whose tops are this close count as one row when ordering focus
+const FOCUS_ROW_TOLERANCE: f32 = 8.0;
+
+// Keyboard and gamepad navigation over every Bevy UI button, so menus never need the mouse
+pub struct MenuFocusPlugin;
+
+impl Plugin for MenuFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>()
+            .init_resource::<FocusAnnouncer>()
+            .add_event::<FocusAnnouncement>()
+            .add_systems(PreUpdate, activate_focused_button.after(UiSystem::Focus))
+            .add_systems(Update, (
+                drop_despawned_focus,
+                menu_focus_navigation_system,
+                highlight_focused_button,
+                log_focus_announcements,
+            ).chain())
+            .add_systems(Update, toggle_focus_announcer);
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct MenuFocus {
+    pub focused: Option<Entity>,
+}
+
+// Optional readout of whatever gains focus, for screen readers or anyone following along in the log
+#[derive(Resource, Default, Debug)]
+pub struct FocusAnnouncer {
+    pub enabled: bool,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct FocusAnnouncement(pub String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusStep { Next, Previous, }
+
+// Reading order: top to bottom, then left to right within a row. Rows are settled before sorting, since a tolerance
+// check inside the comparator isn't transitive and would let the sort order depend on the input order
+pub fn focus_order(mut positions: Vec<(Entity, Vec2)>) -> Vec<Entity> {
+    positions.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y));
+    let mut row = 0;
+    let mut previous_y = None;
+    let mut rows: Vec<(usize, Entity, Vec2)> = positions.into_iter().map(|(entity, position)| {
+        if previous_y.is_some_and(|y: f32| position.y - y > FOCUS_ROW_TOLERANCE) { row += 1; }
+        previous_y = Some(position.y);
+        (row, entity, position)
+    }).collect();
+    rows.sort_by(|(row_a, _, a), (row_b, _, b)| row_a.cmp(row_b).then_with(|| a.x.total_cmp(&b.x)));
+    rows.into_iter().map(|(_, entity, _)| entity).collect()
+}
+
+// Wraps at both ends; with nothing focused yet, the first step lands on the first (or last) button
+pub fn step_focus(order: &[Entity], current: Option<Entity>, step: FocusStep) -> Option<Entity> {
+    if order.is_empty() { return None; }
+    let Some(index) = current.and_then(|entity| order.iter().position(|candidate| *candidate == entity)) else {
+        return Some(if step == FocusStep::Next { order[0] } else { order[order.len() - 1] });
+    };
+    let next_index = match step {
+        FocusStep::Next => (index + 1) % order.len(),
+        FocusStep::Previous => (index + order.len() - 1) % order.len(),
+    };
+    Some(order[next_index])
+}
+
+fn requested_focus_step(keyboard_input: &ButtonInput<KeyCode>, gamepads: &Gamepads, gamepad_buttons: &ButtonInput<GamepadButton>) -> Option<FocusStep> {
+    let shift_held = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let gamepad_pressed = |button_type: GamepadButtonType| gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)));
+    if keyboard_input.any_just_pressed([KeyCode::ArrowDown, KeyCode::ArrowRight]) || (keyboard_input.just_pressed(KeyCode::Tab) && !shift_held)
+        || gamepad_pressed(GamepadButtonType::DPadDown) || gamepad_pressed(GamepadButtonType::DPadRight) {
+        return Some(FocusStep::Next);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::ArrowUp, KeyCode::ArrowLeft]) || (keyboard_input.just_pressed(KeyCode::Tab) && shift_held)
+        || gamepad_pressed(GamepadButtonType::DPadUp) || gamepad_pressed(GamepadButtonType::DPadLeft) {
+        return Some(FocusStep::Previous);
+    }
+    None
+}
+
+fn button_label(entity: Entity, children_query: &Query<&Children>, text_query: &Query<&Text>) -> Option<String> {
+    if let Ok(text) = text_query.get(entity) {
+        let label: String = text.sections.iter().map(|section| section.value.as_str()).collect();
+        if !label.trim().is_empty() { return Some(label); }
+    }
+    children_query.get(entity).ok()?.iter().find_map(|child| button_label(*child, children_query, text_query))
+}
+
+fn drop_despawned_focus(mut menu_focus: ResMut<MenuFocus>, button_query: Query<(), With<Button>>) {
+    if menu_focus.focused.is_some_and(|entity| button_query.get(entity).is_err()) { menu_focus.focused = None; }
+}
+
+fn menu_focus_navigation_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut menu_focus: ResMut<MenuFocus>,
+    button_query: Query<(Entity, &GlobalTransform, &ViewVisibility), With<Button>>,
+    children_query: Query<&Children>,
+    text_query: Query<&Text>,
+    mut announcement_writer: EventWriter<FocusAnnouncement>,
+) {
+    let Some(step) = requested_focus_step(&keyboard_input, &gamepads, &gamepad_buttons) else { return; };
+    let positions = button_query.iter().filter(|(_, _, visibility)| visibility.get()).map(|(entity, transform, _)| (entity, transform.translation().truncate())).collect();
+    let order = focus_order(positions);
+    menu_focus.focused = step_focus(&order, menu_focus.focused, step);
+    if let Some(label) = menu_focus.focused.and_then(|entity| button_label(entity, &children_query, &text_query)) {
+        announcement_writer.send(FocusAnnouncement(label));
+    }
+}
+
+// Runs right after Bevy's own pointer pass, so the menus' Changed<Interaction> handlers see the press this frame
+fn activate_focused_button(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    menu_focus: Res<MenuFocus>,
+    mut interaction_query: Query<&mut Interaction, With<Button>>,
+) {
+    let Some(focused) = menu_focus.focused else { return; };
+    let confirm_pressed = keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads.iter().any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South)));
+    if !confirm_pressed { return; }
+    if let Ok(mut interaction) = interaction_query.get_mut(focused) { *interaction = Interaction::Pressed; }
+}
+
+fn highlight_focused_button(mut commands: Commands, menu_focus: Res<MenuFocus>, outlined_query: Query<Entity, (With<Button>, With<Outline>)>) {
+    if !menu_focus.is_changed() { return; }
+    for entity in outlined_query.iter() {
+        if Some(entity) != menu_focus.focused { commands.entity(entity).remove::<Outline>(); }
+    }
+    if let Some(focused) = menu_focus.focused {
+        commands.entity(focused).insert(Outline::new(Val::Px(FOCUS_OUTLINE_WIDTH), Val::Px(1.0), FOCUS_OUTLINE_COLOR));
+    }
+}
+
+fn log_focus_announcements(announcer: Res<FocusAnnouncer>, mut announcements: EventReader<FocusAnnouncement>) {
+    for announcement in announcements.read() {
+        if announcer.enabled { info!("Focused: {}", announcement.0); }
+    }
+}
+
+fn toggle_focus_announcer(keyboard_input: Res<ButtonInput<KeyCode>>, mut announcer: ResMut<FocusAnnouncer>) {
+    if keyboard_input.just_pressed(FOCUS_ANNOUNCER_TOGGLE_KEY) {
+        announcer.enabled = !announcer.enabled;
+        info!("Focus announcements {}", if announcer.enabled { "enabled" } else { "disabled" });
+    }
+}
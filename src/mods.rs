@@ -0,0 +1,397 @@
+use bevy::{prelude::*, utils::HashSet};
+use serde::Deserialize;
+use std::{collections::HashMap, path::Path, time::Duration};
+use crate::{
+    game::AppState,
+    horror::HorrorType,
+    horror_stats::{HorrorStatsEntry, HorrorStatsError, HorrorStatsLibrary, HorrorStatsTable, apply_loaded_horror_stats},
+    skills::{SkillDefinition, SkillEffectType, SkillId, SkillLibrary, MAX_GLYPH_SLOTS},
+    upgrades::{UpgradeCard, UpgradeId, UpgradePool, UpgradePoolError, UpgradePoolFile, apply_loaded_upgrade_pool},
+    skill_scripts::{SkillScript, SkillScriptError, SkillScriptLibrary},
+};
+
+// Folder next to the game that holds one sub-folder per data pack, each with a mod.ron inside
+pub const MODS_DIR: &str = "mods";
+// Asset source that lets packs ship their own sprites: "mods://<folder>/<path>"
+pub const MODS_ASSET_SOURCE: &str = "mods";
+pub const MOD_MANIFEST_FILE: &str = "mod.ron";
+pub const MODS_LIST_KEY: KeyCode = KeyCode::KeyM;
+const BASE_GAME_NAME: &str = "base game";
+const CONFLICT_COLOR: Color = Color::rgb(1.0, 0.65, 0.2);
+const FAILED_COLOR: Color = Color::rgb(0.95, 0.35, 0.35);
+
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModRegistry>()
+            // Base skills are registered during Startup, so packs are merged right after
+            .add_systems(PostStartup, load_mods)
+            .add_systems(Update, reapply_mod_data_on_reload.after(apply_loaded_upgrade_pool).after(apply_loaded_horror_stats))
+            .add_systems(OnEnter(AppState::MainMenu), setup_mods_hint_ui)
+            .add_systems(Update, toggle_mods_list.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), despawn_mods_ui);
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum ModSkillEffect {
+    Projectile { base_damage: i32, speed: f32, size: (f32, f32), color: (f32, f32, f32, f32), lifetime_secs: f32, #[serde(default)] piercing: u32 },
+    AreaOfEffect { base_damage_per_tick: i32, base_radius: f32, tick_interval_secs: f32, duration_secs: f32, color: (f32, f32, f32, f32) },
+    // Names one of the pack's own scripts
+    Scripted { script: String },
+}
+
+// The data-file form of a skill; anything beyond the plain effects is described with a script
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ModSkill {
+    pub id: SkillId,
+    pub name: String,
+    pub description: String,
+    pub cooldown_secs: f32,
+    #[serde(default)]
+    pub glyph_slots: u8,
+    pub effect: ModSkillEffect,
+}
+
+impl ModSkill {
+    pub fn to_definition(&self) -> SkillDefinition {
+        let rgba = |(r, g, b, a): (f32, f32, f32, f32)| Color::rgba(r, g, b, a);
+        let effect = match &self.effect {
+            ModSkillEffect::Projectile { base_damage, speed, size, color, lifetime_secs, piercing } => SkillEffectType::Projectile { base_damage: *base_damage, speed: *speed, size: Vec2::new(size.0, size.1), color: rgba(*color), lifetime_secs: *lifetime_secs, piercing: *piercing },
+            ModSkillEffect::AreaOfEffect { base_damage_per_tick, base_radius, tick_interval_secs, duration_secs, color } => SkillEffectType::AreaOfEffect { base_damage_per_tick: *base_damage_per_tick, base_radius: *base_radius, tick_interval_secs: *tick_interval_secs, duration_secs: *duration_secs, color: rgba(*color) },
+            ModSkillEffect::Scripted { script } => SkillEffectType::Scripted { script: script.clone() },
+        };
+        SkillDefinition { id: self.id, name: self.name.clone(), description: self.description.clone(), base_cooldown: Duration::from_secs_f32(self.cooldown_secs), effect, base_glyph_slots: self.glyph_slots }
+    }
+
+    fn validate(&self) -> Result<(), ModError> {
+        if self.name.trim().is_empty() { return Err(ModError::InvalidSkill { id: self.id, field: "name" }); }
+        if !self.cooldown_secs.is_finite() || self.cooldown_secs <= 0.0 { return Err(ModError::InvalidSkill { id: self.id, field: "cooldown_secs" }); }
+        if self.glyph_slots as usize > MAX_GLYPH_SLOTS { return Err(ModError::InvalidSkill { id: self.id, field: "glyph_slots" }); }
+        Ok(())
+    }
+}
+
+// One mod.ron: extra skills and upgrades, plus stat overrides for existing horror types
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DataPack {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub skills: Vec<ModSkill>,
+    #[serde(default)]
+    pub horrors: Vec<HorrorStatsEntry>,
+    #[serde(default)]
+    pub upgrades: Vec<UpgradeCard>,
+    #[serde(default)]
+    pub scripts: HashMap<String, SkillScript>,
+}
+
+impl DataPack {
+    pub fn from_ron_str(source: &str) -> Result<Self, ModError> {
+        let pack: DataPack = ron::de::from_str(source)?;
+        pack.validate()?;
+        Ok(pack)
+    }
+
+    pub fn validate(&self) -> Result<(), ModError> {
+        if self.name.trim().is_empty() { return Err(ModError::MissingName); }
+        let mut seen_skills = HashSet::new();
+        for skill in self.skills.iter() {
+            if !seen_skills.insert(skill.id) { return Err(ModError::DuplicateSkill(skill.id)); }
+            skill.validate()?;
+            if let ModSkillEffect::Scripted { script } = &skill.effect {
+                if !self.scripts.contains_key(script) { return Err(ModError::MissingScript(script.clone())); }
+            }
+        }
+        for (name, script) in self.scripts.iter() {
+            script.validate().map_err(|error| ModError::InvalidScript { name: name.clone(), error })?;
+        }
+        for (i, entry) in self.horrors.iter().enumerate() {
+            if self.horrors[..i].iter().any(|earlier| earlier.horror_type == entry.horror_type) { return Err(HorrorStatsError::DuplicateEntry(entry.horror_type).into()); }
+            entry.validate()?;
+        }
+        UpgradePoolFile { upgrades: self.upgrades.clone() }.validate()?;
+        Ok(())
+    }
+
+    // Sprite paths are relative to the pack's folder, and script names are prefixed with it so packs never share one
+    pub fn with_asset_folder(mut self, folder: &str) -> Self {
+        for entry in self.horrors.iter_mut() {
+            entry.sprite_path = format!("{}://{}/{}", MODS_ASSET_SOURCE, folder, entry.sprite_path);
+        }
+        for skill in self.skills.iter_mut() {
+            if let ModSkillEffect::Scripted { script } = &mut skill.effect { *script = format!("{}/{}", folder, script); }
+        }
+        self.scripts = self.scripts.into_iter().map(|(name, script)| (format!("{}/{}", folder, name), script)).collect();
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum ModError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    MissingName,
+    DuplicateSkill(SkillId),
+    InvalidSkill { id: SkillId, field: &'static str },
+    Horrors(HorrorStatsError),
+    Upgrades(UpgradePoolError),
+    MissingScript(String),
+    InvalidScript { name: String, error: SkillScriptError },
+}
+
+impl std::fmt::Display for ModError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModError::Io(error) => write!(f, "could not read {}: {}", MOD_MANIFEST_FILE, error),
+            ModError::Parse(error) => write!(f, "could not parse {}: {}", MOD_MANIFEST_FILE, error),
+            ModError::MissingName => write!(f, "the pack has no name"),
+            ModError::DuplicateSkill(id) => write!(f, "skill id {} is listed more than once", id.0),
+            ModError::InvalidSkill { id, field } => write!(f, "skill {} has an invalid {}", id.0, field),
+            ModError::Horrors(error) => write!(f, "{}", error),
+            ModError::Upgrades(error) => write!(f, "{}", error),
+            ModError::MissingScript(name) => write!(f, "script '{}' is used by a skill but not defined", name),
+            ModError::InvalidScript { name, error } => write!(f, "script '{}': {}", name, error),
+        }
+    }
+}
+
+impl std::error::Error for ModError {}
+
+impl From<std::io::Error> for ModError {
+    fn from(error: std::io::Error) -> Self { ModError::Io(error) }
+}
+
+impl From<ron::error::SpannedError> for ModError {
+    fn from(error: ron::error::SpannedError) -> Self { ModError::Parse(error) }
+}
+
+impl From<HorrorStatsError> for ModError {
+    fn from(error: HorrorStatsError) -> Self { ModError::Horrors(error) }
+}
+
+impl From<UpgradePoolError> for ModError {
+    fn from(error: UpgradePoolError) -> Self { ModError::Upgrades(error) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentKey {
+    Skill(SkillId),
+    Upgrade(UpgradeId),
+    Horror(HorrorType),
+}
+
+impl std::fmt::Display for ContentKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentKey::Skill(id) => write!(f, "skill {}", id.0),
+            ContentKey::Upgrade(id) => write!(f, "upgrade {}", id.0),
+            ContentKey::Horror(horror_type) => write!(f, "{:?} stats", horror_type),
+        }
+    }
+}
+
+// An entry a pack tried to claim that was already taken; the pack loses it and keeps the rest
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModConflict {
+    pub key: ContentKey,
+    pub mod_name: String,
+    pub claimed_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadedMod {
+    pub folder: String,
+    pub pack: DataPack,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ModRegistry {
+    pub mods: Vec<LoadedMod>,
+    pub conflicts: Vec<ModConflict>,
+    pub failed: Vec<(String, String)>,
+}
+
+impl ModRegistry {
+    // Packs are taken in folder-name order and the first claim wins; horror entries are overrides,
+    // so they only clash with each other, while skill and upgrade ids also clash with the base game
+    pub fn resolve(mut packs: Vec<(String, DataPack)>, base_skills: &[SkillId], base_upgrades: &[UpgradeId]) -> Self {
+        packs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut claims: Vec<(ContentKey, String)> = base_skills.iter().map(|id| (ContentKey::Skill(*id), BASE_GAME_NAME.to_string()))
+            .chain(base_upgrades.iter().map(|id| (ContentKey::Upgrade(*id), BASE_GAME_NAME.to_string())))
+            .collect();
+        let mut registry = Self::default();
+        for (folder, mut pack) in packs {
+            let mut claim = |key: ContentKey| match claims.iter().find(|(claimed, _)| *claimed == key) {
+                Some((_, owner)) => {
+                    registry.conflicts.push(ModConflict { key, mod_name: pack.name.clone(), claimed_by: owner.clone() });
+                    false
+                }
+                None => { claims.push((key, pack.name.clone())); true }
+            };
+            let skills = std::mem::take(&mut pack.skills).into_iter().filter(|skill| claim(ContentKey::Skill(skill.id))).collect();
+            let upgrades = std::mem::take(&mut pack.upgrades).into_iter().filter(|card| claim(ContentKey::Upgrade(card.id))).collect();
+            let horrors = std::mem::take(&mut pack.horrors).into_iter().filter(|entry| claim(ContentKey::Horror(entry.horror_type))).collect();
+            pack.skills = skills;
+            pack.upgrades = upgrades;
+            pack.horrors = horrors;
+            registry.mods.push(LoadedMod { folder, pack });
+        }
+        registry
+    }
+
+    pub fn skills(&self) -> impl Iterator<Item = &ModSkill> {
+        self.mods.iter().flat_map(|loaded| loaded.pack.skills.iter())
+    }
+
+    pub fn upgrades(&self) -> impl Iterator<Item = &UpgradeCard> {
+        self.mods.iter().flat_map(|loaded| loaded.pack.upgrades.iter())
+    }
+
+    pub fn horrors(&self) -> impl Iterator<Item = &HorrorStatsEntry> {
+        self.mods.iter().flat_map(|loaded| loaded.pack.horrors.iter())
+    }
+
+    // Base data can be reloaded from disk at any time, so this is safe to run again afterwards
+    pub fn apply_to_upgrade_pool(&self, pool: &mut UpgradePool) {
+        for card in self.upgrades() {
+            if pool.available_upgrades.iter().all(|existing| existing.id != card.id) { pool.available_upgrades.push(card.clone()); }
+        }
+    }
+
+    pub fn apply_to_horror_stats(&self, library: &mut HorrorStatsLibrary) {
+        for entry in self.horrors() { library.entries.insert(entry.horror_type, entry.clone()); }
+    }
+}
+
+// Folders without a mod.ron are ignored; one that fails to load is reported instead of stopping the others
+pub fn scan_mods_folder(mods_dir: &Path) -> Vec<(String, Result<DataPack, ModError>)> {
+    let Ok(read_dir) = std::fs::read_dir(mods_dir) else { return Vec::new(); };
+    let mut packs: Vec<(String, Result<DataPack, ModError>)> = read_dir.flatten()
+        .filter(|dir_entry| dir_entry.path().join(MOD_MANIFEST_FILE).is_file())
+        .map(|dir_entry| {
+            let folder = dir_entry.file_name().to_string_lossy().into_owned();
+            let pack = std::fs::read_to_string(dir_entry.path().join(MOD_MANIFEST_FILE)).map_err(ModError::from)
+                .and_then(|source| DataPack::from_ron_str(&source))
+                .map(|pack| pack.with_asset_folder(&folder));
+            (folder, pack)
+        })
+        .collect();
+    packs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    packs
+}
+
+#[derive(Component)]
+struct ModsHintUI;
+
+#[derive(Component)]
+struct ModsListUI;
+
+fn load_mods(
+    mut registry: ResMut<ModRegistry>,
+    mut skill_library: ResMut<SkillLibrary>,
+    mut upgrade_pool: ResMut<UpgradePool>,
+    mut horror_stats: ResMut<HorrorStatsLibrary>,
+    mut script_library: ResMut<SkillScriptLibrary>,
+) {
+    let mut packs = Vec::new();
+    let mut failed = Vec::new();
+    for (folder, pack) in scan_mods_folder(Path::new(MODS_DIR)) {
+        match pack {
+            Ok(pack) => packs.push((folder, pack)),
+            Err(error) => {
+                warn!("Skipping mod '{}': {}", folder, error);
+                failed.push((folder, error.to_string()));
+            }
+        }
+    }
+    let base_skills: Vec<SkillId> = skill_library.skills.iter().map(|skill_def| skill_def.id).collect();
+    let base_upgrades: Vec<UpgradeId> = upgrade_pool.available_upgrades.iter().map(|card| card.id).collect();
+    *registry = ModRegistry::resolve(packs, &base_skills, &base_upgrades);
+    registry.failed = failed;
+    for conflict in registry.conflicts.iter() {
+        warn!("Mod '{}' cannot claim {}: already provided by {}", conflict.mod_name, conflict.key, conflict.claimed_by);
+    }
+
+    skill_library.skills.extend(registry.skills().map(ModSkill::to_definition));
+    registry.apply_to_upgrade_pool(&mut upgrade_pool);
+    registry.apply_to_horror_stats(&mut horror_stats);
+    script_library.scripts.extend(registry.mods.iter().flat_map(|loaded| loaded.pack.scripts.clone()));
+    for loaded in registry.mods.iter() {
+        info!("Loaded mod '{}' from {}/{}: {} skills, {} horror overrides, {} upgrades", loaded.pack.name, MODS_DIR, loaded.folder, loaded.pack.skills.len(), loaded.pack.horrors.len(), loaded.pack.upgrades.len());
+    }
+}
+
+// Reloading the base data files (F5) swaps the libraries wholesale, which would drop everything merged in from packs
+fn reapply_mod_data_on_reload(
+    registry: Res<ModRegistry>,
+    mut upgrade_events: EventReader<AssetEvent<UpgradePoolFile>>,
+    mut horror_events: EventReader<AssetEvent<HorrorStatsTable>>,
+    mut upgrade_pool: ResMut<UpgradePool>,
+    mut horror_stats: ResMut<HorrorStatsLibrary>,
+) {
+    // Counting rather than stopping at the first reload drains the readers, so the same events don't fire again next frame
+    if upgrade_events.read().filter(|event| is_reload(event)).count() > 0 { registry.apply_to_upgrade_pool(&mut upgrade_pool); }
+    if horror_events.read().filter(|event| is_reload(event)).count() > 0 { registry.apply_to_horror_stats(&mut horror_stats); }
+}
+
+fn is_reload<A: Asset>(event: &AssetEvent<A>) -> bool {
+    matches!(event, AssetEvent::LoadedWithDependencies { .. } | AssetEvent::Modified { .. })
+}
+
+fn setup_mods_hint_ui(mut commands: Commands, asset_server: Res<AssetServer>, registry: Res<ModRegistry>) {
+    let mut label = format!("Mods: {} loaded (M)", registry.mods.len());
+    if !registry.conflicts.is_empty() || !registry.failed.is_empty() { label.push_str(" - see list for problems"); }
+    commands.spawn((
+        TextBundle::from_section(label, TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 18.0, color: Color::rgb(0.7, 0.7, 0.7) })
+            .with_style(Style { position_type: PositionType::Absolute, bottom: Val::Px(16.0), left: Val::Px(16.0), ..default() }),
+        ModsHintUI,
+    ));
+}
+
+fn toggle_mods_list(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    registry: Res<ModRegistry>,
+    list_query: Query<Entity, With<ModsListUI>>,
+) {
+    if !keyboard_input.just_pressed(MODS_LIST_KEY) { return; }
+    if let Ok(list_entity) = list_query.get_single() {
+        commands.entity(list_entity).despawn_recursive();
+        return;
+    }
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let line = |text: String, color: Color| TextBundle::from_section(text, TextStyle { font: font.clone(), font_size: 18.0, color });
+    commands.spawn((
+        NodeBundle {
+            style: Style { position_type: PositionType::Absolute, top: Val::Px(40.0), right: Val::Px(40.0), flex_direction: FlexDirection::Column, row_gap: Val::Px(6.0), padding: UiRect::all(Val::Px(12.0)), max_width: Val::Px(460.0), ..default() },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+            z_index: ZIndex::Global(5),
+            ..default()
+        },
+        ModsListUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Installed mods", TextStyle { font: font.clone(), font_size: 24.0, color: Color::WHITE }));
+        if registry.mods.is_empty() && registry.failed.is_empty() {
+            parent.spawn(line(format!("No data packs found in {}/", MODS_DIR), Color::GRAY));
+        }
+        for loaded in registry.mods.iter() {
+            let version = if loaded.pack.version.is_empty() { String::new() } else { format!(" v{}", loaded.pack.version) };
+            parent.spawn(line(format!("{}{} - {} skills, {} horror overrides, {} upgrades", loaded.pack.name, version, loaded.pack.skills.len(), loaded.pack.horrors.len(), loaded.pack.upgrades.len()), Color::rgb(0.85, 0.85, 0.85)));
+        }
+        for conflict in registry.conflicts.iter() {
+            parent.spawn(line(format!("{}: {} already provided by {}", conflict.mod_name, conflict.key, conflict.claimed_by), CONFLICT_COLOR));
+        }
+        for (folder, error) in registry.failed.iter() {
+            parent.spawn(line(format!("{} failed to load: {}", folder, error), FAILED_COLOR));
+        }
+    });
+}
+
+fn despawn_mods_ui(mut commands: Commands, ui_query: Query<Entity, Or<(With<ModsHintUI>, With<ModsListUI>)>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::Survivor,
+    horror::Horror,
+    game::AppState,
+};
+
+const OVERWHELM_SAMPLE_RADIUS: f32 = 250.0;
+const OVERWHELM_SAMPLE_INTERVAL_SECS: f32 = 0.25;
+const HORRORS_FOR_FULL_METER: f32 = 20.0;
+const OVERWHELM_RISE_PER_SEC: f32 = 0.5;
+const OVERWHELM_DECAY_PER_SEC: f32 = 0.2;
+const OVERWHELM_TIER_THRESHOLDS: [f32; 3] = [0.33, 0.66, 0.95];
+const OVERWHELM_FIRE_RATE_BONUSES: [f32; 4] = [0.0, 0.10, 0.25, 0.45];
+const OVERWHELM_SPEED_BONUSES: [f32; 4] = [0.0, 0.05, 0.12, 0.20];
+const OVERWHELM_BAR_WIDTH: f32 = 200.0;
+
+pub struct OverwhelmPlugin;
+
+impl Plugin for OverwhelmPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::InGame), setup_overwhelm_hud)
+            .add_systems(Update, (
+                attach_overwhelm_meter_system,
+                overwhelm_density_sampling_system,
+                update_overwhelm_hud,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_overwhelm_hud);
+    }
+}
+
+// Fills while horrors crowd the survivor and drains once they thin out; higher tiers hasten casting and movement
+#[derive(Component, Debug)]
+pub struct Overwhelm {
+    pub meter: f32,
+    pub target: f32,
+    pub sample_timer: Timer,
+}
+
+impl Default for Overwhelm {
+    fn default() -> Self {
+        Self { meter: 0.0, target: 0.0, sample_timer: Timer::from_seconds(OVERWHELM_SAMPLE_INTERVAL_SECS, TimerMode::Repeating) }
+    }
+}
+
+impl Overwhelm {
+    pub fn set_nearby_horror_count(&mut self, count: usize) { self.target = (count as f32 / HORRORS_FOR_FULL_METER).min(1.0); }
+    pub fn advance(&mut self, delta_secs: f32) {
+        if self.target > self.meter { self.meter = (self.meter + OVERWHELM_RISE_PER_SEC * delta_secs).min(self.target); }
+        else { self.meter = (self.meter - OVERWHELM_DECAY_PER_SEC * delta_secs).max(self.target); }
+    }
+    pub fn tier(&self) -> usize { OVERWHELM_TIER_THRESHOLDS.iter().filter(|threshold| self.meter >= **threshold).count() }
+    pub fn fire_rate_bonus(&self) -> f32 { OVERWHELM_FIRE_RATE_BONUSES[self.tier()] }
+    pub fn speed_bonus(&self) -> f32 { OVERWHELM_SPEED_BONUSES[self.tier()] }
+}
+
+#[derive(Component)]
+struct OverwhelmHud;
+#[derive(Component)]
+struct OverwhelmBarFill;
+#[derive(Component)]
+struct OverwhelmTierText;
+
+fn attach_overwhelm_meter_system(mut commands: Commands, player_query: Query<Entity, (With<Survivor>, Without<Overwhelm>)>) {
+    for player_entity in player_query.iter() { commands.entity(player_entity).insert(Overwhelm::default()); }
+}
+
+fn overwhelm_density_sampling_system(
+    time: Res<Time>,
+    mut player_query: Query<(&Transform, &mut Overwhelm), With<Survivor>>,
+    horror_query: Query<&Transform, (With<Horror>, Without<Survivor>)>,
+) {
+    let Ok((player_transform, mut overwhelm)) = player_query.get_single_mut() else { return; };
+    overwhelm.sample_timer.tick(time.delta());
+    if overwhelm.sample_timer.just_finished() {
+        let player_pos = player_transform.translation.truncate();
+        let sample_radius_sq = OVERWHELM_SAMPLE_RADIUS.powi(2);
+        let nearby_horrors = horror_query.iter().filter(|horror_transform| horror_transform.translation.truncate().distance_squared(player_pos) < sample_radius_sq).count();
+        overwhelm.set_nearby_horror_count(nearby_horrors);
+    }
+    overwhelm.advance(time.delta_seconds());
+}
+
+fn setup_overwhelm_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style { position_type: PositionType::Absolute, bottom: Val::Px(15.0), left: Val::Percent(50.0), margin: UiRect::left(Val::Px(-OVERWHELM_BAR_WIDTH / 2.0)), flex_direction: FlexDirection::Column, align_items: AlignItems::Center, ..default() },
+            ..default()
+        },
+        OverwhelmHud,
+        Name::new("OverwhelmHud"),
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Overwhelm", TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::rgb(1.0, 0.6, 0.3) }),
+            OverwhelmTierText,
+        ));
+        parent.spawn(NodeBundle {
+            style: Style { width: Val::Px(OVERWHELM_BAR_WIDTH), height: Val::Px(10.0), ..default() },
+            background_color: Color::rgba(0.2, 0.1, 0.1, 0.8).into(),
+            ..default()
+        }).with_children(|bar| {
+            bar.spawn((
+                NodeBundle { style: Style { width: Val::Percent(0.0), height: Val::Percent(100.0), ..default() }, background_color: Color::rgb(0.9, 0.3, 0.1).into(), ..default() },
+                OverwhelmBarFill,
+            ));
+        });
+    });
+}
+
+fn update_overwhelm_hud(
+    player_query: Query<&Overwhelm>,
+    mut fill_query: Query<&mut Style, With<OverwhelmBarFill>>,
+    mut text_query: Query<&mut Text, With<OverwhelmTierText>>,
+) {
+    let Ok(overwhelm) = player_query.get_single() else { return; };
+    if let Ok(mut fill_style) = fill_query.get_single_mut() { fill_style.width = Val::Percent(overwhelm.meter * 100.0); }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = if overwhelm.tier() > 0 { format!("Overwhelm {}", "!".repeat(overwhelm.tier())) } else { "Overwhelm".to_string() };
+    }
+}
+
+fn despawn_overwhelm_hud(mut commands: Commands, hud_query: Query<Entity, With<OverwhelmHud>>) {
+    for entity in hud_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,322 @@
+use bevy::{prelude::*, ecs::system::SystemParam};
+use crate::{
+    game::{AppState, on_enter_pause_like_state_actions, on_enter_ingame_state_actions},
+    survivor::Survivor,
+    skills::{SkillLibrary, MAX_SKILL_SLOTS},
+    audio::{PlaySoundEvent, SoundEffect},
+    run_history::UpgradeLog,
+    upgrades::{UpgradePool, UpgradeId},
+    bestiary::{Bestiary, RunKills, horror_name, horror_behavior, stat_line},
+    horror::HorrorType,
+    horror_stats::HorrorStatsLibrary,
+    lore::{LoreLibrary, LoreCollection},
+    input_actions::{ActionState, InputAction, InputMap},
+};
+const SLOT_COLOR: Color = Color::GRAY;
+const SELECTED_SLOT_COLOR: Color = Color::rgb(0.55, 0.45, 0.1);
+const HINT_COLOR: Color = Color::rgb(0.8, 0.8, 1.0);
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkillSwapSelection>()
+            .init_resource::<PauseTab>()
+            .add_systems(Update, toggle_pause.run_if(in_state(AppState::InGame).or_else(in_state(AppState::Paused))))
+            .add_systems(OnEnter(AppState::Paused), (setup_pause_menu_ui, on_enter_pause_like_state_actions))
+            .add_systems(Update, (handle_pause_tab_buttons, highlight_active_tab, handle_skill_slot_buttons, highlight_selected_slot).chain().run_if(in_state(AppState::Paused)))
+            .add_systems(OnExit(AppState::Paused), (despawn_pause_menu_ui, on_enter_ingame_state_actions));
+    }
+}
+
+// The pause menu remembers which tab was open the last time it closed
+#[derive(Resource, Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PauseTab {
+    #[default]
+    Skills,
+    Upgrades,
+    Bestiary,
+    Lore,
+}
+
+impl PauseTab {
+    pub const ALL: [PauseTab; 4] = [PauseTab::Skills, PauseTab::Upgrades, PauseTab::Bestiary, PauseTab::Lore];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            PauseTab::Skills => "Skills",
+            PauseTab::Upgrades => "Upgrades",
+            PauseTab::Bestiary => "Bestiary",
+            PauseTab::Lore => "Whispered Pages",
+        }
+    }
+}
+
+// A place a skill can sit; an index one past the end of either list is that list's free space
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillSlot {
+    Equipped(usize),
+    Learned(usize),
+}
+
+// The slot clicked first, waiting for the one it should trade places with
+#[derive(Resource, Default, Debug)]
+pub struct SkillSwapSelection(pub Option<SkillSlot>);
+
+// Skills keep their cooldowns, upgrades and glyphs wherever they move; the survivor always keeps at least one equipped
+pub fn swap_skill_slots(survivor: &mut Survivor, first: SkillSlot, second: SkillSlot) -> bool {
+    let equipped = survivor.equipped_skills.len();
+    let learned = survivor.learned_skills.len();
+    match (first, second) {
+        (SkillSlot::Equipped(a), SkillSlot::Equipped(b)) => {
+            if a == b || a.max(b) >= MAX_SKILL_SLOTS { return false; }
+            match (a < equipped, b < equipped) {
+                (true, true) => survivor.equipped_skills.swap(a, b),
+                // Moving into an empty slot just sends the skill to the back of the bar
+                (true, false) => { let skill = survivor.equipped_skills.remove(a); survivor.equipped_skills.push(skill); }
+                (false, true) => { let skill = survivor.equipped_skills.remove(b); survivor.equipped_skills.push(skill); }
+                (false, false) => return false,
+            }
+            true
+        }
+        (SkillSlot::Equipped(slot), SkillSlot::Learned(pooled)) | (SkillSlot::Learned(pooled), SkillSlot::Equipped(slot)) => {
+            if slot >= MAX_SKILL_SLOTS { return false; }
+            match (slot < equipped, pooled < learned) {
+                (true, true) => std::mem::swap(&mut survivor.equipped_skills[slot], &mut survivor.learned_skills[pooled]),
+                (false, true) => { let skill = survivor.learned_skills.remove(pooled); survivor.equipped_skills.push(skill); }
+                (true, false) if equipped > 1 => { let skill = survivor.equipped_skills.remove(slot); survivor.learned_skills.push(skill); }
+                _ => return false,
+            }
+            true
+        }
+        (SkillSlot::Learned(_), SkillSlot::Learned(_)) => false,
+    }
+}
+
+#[derive(Component)]
+struct PauseMenuUI;
+
+// Everything a tab's contents are built from, besides the survivor
+#[derive(SystemParam)]
+struct TabSources<'w> {
+    skill_library: Res<'w, SkillLibrary>,
+    upgrade_log: Res<'w, UpgradeLog>,
+    upgrade_pool: Res<'w, UpgradePool>,
+    bestiary: Res<'w, Bestiary>,
+    run_kills: Res<'w, RunKills>,
+    horror_stats: Res<'w, HorrorStatsLibrary>,
+    lore_library: Res<'w, LoreLibrary>,
+    lore: Res<'w, LoreCollection>,
+    input_map: Res<'w, InputMap>,
+}
+
+#[derive(Component)]
+struct PauseTabPanel;
+
+fn toggle_pause(actions: Res<ActionState>, current_app_state: Res<State<AppState>>, mut next_app_state: ResMut<NextState<AppState>>) {
+    if !actions.just_pressed(InputAction::Pause) { return; }
+    match current_app_state.get() {
+        AppState::InGame => next_app_state.set(AppState::Paused),
+        AppState::Paused => next_app_state.set(AppState::InGame),
+        _ => {}
+    }
+}
+
+fn setup_pause_menu_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    survivor_query: Query<&Survivor>,
+    sources: TabSources,
+    tab: Res<PauseTab>,
+    mut selection: ResMut<SkillSwapSelection>,
+) {
+    selection.0 = None;
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle {
+            style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(16.0), ..default() },
+            background_color: Color::rgba(0.1, 0.1, 0.2, 0.9).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        PauseMenuUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Paused", TextStyle { font: font.clone(), font_size: 50.0, color: Color::GOLD }));
+        parent.spawn(NodeBundle { style: Style { column_gap: Val::Px(12.0), ..default() }, ..default() }).with_children(|tab_row| {
+            for tab in PauseTab::ALL {
+                tab_row.spawn((
+                    ButtonBundle { style: Style { padding: UiRect::axes(Val::Px(16.0), Val::Px(4.0)), border: UiRect::bottom(Val::Px(2.0)), ..default() }, background_color: Color::NONE.into(), border_color: BorderColor(Color::NONE), ..default() },
+                    tab,
+                )).with_children(|button| { button.spawn(TextBundle::from_section(tab.title(), TextStyle { font: font.clone(), font_size: 26.0, color: Color::WHITE })); });
+            }
+        });
+        parent.spawn((NodeBundle { style: Style { flex_direction: FlexDirection::Column, align_items: AlignItems::Center, row_gap: Val::Px(12.0), ..default() }, ..default() }, PauseTabPanel))
+            .with_children(|panel| spawn_tab_contents(panel, *tab, survivor_query.get_single().ok(), &sources, &font));
+        parent.spawn(TextBundle::from_section("Esc: resume", TextStyle { font, font_size: 18.0, color: Color::rgb(0.7, 0.7, 0.7) }));
+    });
+}
+
+fn spawn_tab_contents(panel: &mut ChildBuilder, tab: PauseTab, survivor: Option<&Survivor>, sources: &TabSources, font: &Handle<Font>) {
+    match tab {
+        PauseTab::Skills => {
+            panel.spawn(TextBundle::from_section("Pick a skill, then the slot it should trade places with", TextStyle { font: font.clone(), font_size: 18.0, color: HINT_COLOR }));
+            let Some(survivor) = survivor else { return; };
+            panel.spawn(NodeBundle { style: Style { column_gap: Val::Px(40.0), ..default() }, ..default() })
+                .with_children(|columns| spawn_skill_columns(columns, survivor, &sources.skill_library, &sources.input_map, font));
+        }
+        PauseTab::Upgrades => spawn_upgrade_history(panel, &sources.upgrade_log, &sources.upgrade_pool, font),
+        PauseTab::Bestiary => spawn_bestiary(panel, &sources.bestiary, &sources.run_kills, &sources.horror_stats, font),
+        PauseTab::Lore => spawn_lore_pages(panel, &sources.lore_library, &sources.lore, font),
+    }
+}
+
+fn upgrade_name(upgrade_pool: &UpgradePool, upgrade_id: UpgradeId) -> String {
+    upgrade_pool.available_upgrades.iter().find(|card| card.id == upgrade_id).map_or_else(|| format!("Upgrade {}", upgrade_id.0), |card| card.name.clone())
+}
+
+fn spawn_upgrade_history(panel: &mut ChildBuilder, upgrade_log: &UpgradeLog, upgrade_pool: &UpgradePool, font: &Handle<Font>) {
+    let picks = upgrade_log.picks_with_stacks();
+    let summary = if picks.is_empty() { "No upgrades chosen yet".to_string() } else { format!("{} upgrades chosen, oldest first", picks.len()) };
+    panel.spawn(TextBundle::from_section(summary, TextStyle { font: font.clone(), font_size: 18.0, color: HINT_COLOR }));
+    // Long runs wrap into further columns instead of running off the bottom of the screen
+    panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_wrap: FlexWrap::Wrap, max_height: Val::Vh(55.0), column_gap: Val::Px(32.0), row_gap: Val::Px(4.0), ..default() }, ..default() }).with_children(|list| {
+        for (index, (upgrade_id, stacks)) in picks.into_iter().enumerate() {
+            let stack_label = if stacks > 1 { format!("  x{}", stacks) } else { String::new() };
+            list.spawn(TextBundle::from_section(format!("{}. {}{}", index + 1, upgrade_name(upgrade_pool, upgrade_id), stack_label), TextStyle { font: font.clone(), font_size: 20.0, color: Color::WHITE }));
+        }
+    });
+}
+
+// Undiscovered types keep their place in the list so the player can see how many are left
+fn spawn_bestiary(panel: &mut ChildBuilder, bestiary: &Bestiary, run_kills: &RunKills, horror_stats: &HorrorStatsLibrary, font: &Handle<Font>) {
+    panel.spawn(TextBundle::from_section(format!("{} of {} horrors discovered", bestiary.entries.len(), HorrorType::ALL.len()), TextStyle { font: font.clone(), font_size: 18.0, color: HINT_COLOR }));
+    panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_wrap: FlexWrap::Wrap, max_height: Val::Vh(55.0), column_gap: Val::Px(32.0), row_gap: Val::Px(10.0), ..default() }, ..default() }).with_children(|list| {
+        for horror_type in HorrorType::ALL {
+            let Some(entry) = bestiary.entry(horror_type) else {
+                list.spawn(TextBundle::from_section("???", TextStyle { font: font.clone(), font_size: 20.0, color: Color::GRAY }));
+                continue;
+            };
+            list.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, ..default() }, ..default() }).with_children(|card| {
+                card.spawn(TextBundle::from_section(format!("{}  - {} slain", horror_name(horror_type), entry.kills + run_kills.kills(horror_type)), TextStyle { font: font.clone(), font_size: 20.0, color: Color::GOLD }));
+                if let Some(stats) = horror_stats.entries.get(&horror_type) {
+                    card.spawn(TextBundle::from_section(stat_line(stats), TextStyle { font: font.clone(), font_size: 15.0, color: Color::WHITE }));
+                }
+                card.spawn(TextBundle::from_section(horror_behavior(horror_type), TextStyle { font: font.clone(), font_size: 15.0, color: Color::rgb(0.75, 0.75, 0.75) }));
+            });
+        }
+    });
+}
+
+fn spawn_lore_pages(panel: &mut ChildBuilder, library: &LoreLibrary, collection: &LoreCollection, font: &Handle<Font>) {
+    panel.spawn(TextBundle::from_section(format!("{} of {} pages found", collection.collected.len(), library.pages.len()), TextStyle { font: font.clone(), font_size: 18.0, color: HINT_COLOR }));
+    panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, flex_wrap: FlexWrap::Wrap, max_height: Val::Vh(55.0), max_width: Val::Vw(80.0), column_gap: Val::Px(32.0), row_gap: Val::Px(10.0), ..default() }, ..default() }).with_children(|list| {
+        for page in library.pages.iter() {
+            if !collection.has(page.id) {
+                list.spawn(TextBundle::from_section("???", TextStyle { font: font.clone(), font_size: 20.0, color: Color::GRAY }));
+                continue;
+            }
+            list.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, max_width: Val::Px(520.0), ..default() }, ..default() }).with_children(|card| {
+                card.spawn(TextBundle::from_section(page.title.clone(), TextStyle { font: font.clone(), font_size: 20.0, color: Color::GOLD }));
+                card.spawn(TextBundle::from_section(page.text.clone(), TextStyle { font: font.clone(), font_size: 15.0, color: Color::WHITE }));
+            });
+        }
+    });
+}
+
+fn skill_label(survivor: &Survivor, skill_library: &SkillLibrary, input_map: &InputMap, slot: SkillSlot) -> String {
+    let skill = match slot {
+        SkillSlot::Equipped(index) => survivor.equipped_skills.get(index),
+        SkillSlot::Learned(index) => survivor.learned_skills.get(index),
+    };
+    let name = skill.map_or_else(|| "(empty)".to_string(), |skill| {
+        let skill_name = skill_library.get_skill_definition(skill.definition_id).map_or("Unknown Skill", |definition| definition.name.as_str());
+        format!("{} (Lv {})", skill_name, skill.current_level)
+    });
+    match slot {
+        SkillSlot::Equipped(index) => format!("[{}] {}", InputAction::skill(index).map_or_else(String::new, |action| input_map.bindings_label(action)), name),
+        SkillSlot::Learned(_) => name,
+    }
+}
+
+fn spawn_skill_columns(panel: &mut ChildBuilder, survivor: &Survivor, skill_library: &SkillLibrary, input_map: &InputMap, font: &Handle<Font>) {
+    let equipped_slots = (0..MAX_SKILL_SLOTS).map(SkillSlot::Equipped).collect::<Vec<_>>();
+    // The learned column ends in a free space, so a skill can be set aside without taking another's place
+    let learned_slots = (0..=survivor.learned_skills.len()).map(SkillSlot::Learned).collect::<Vec<_>>();
+    for (title, slots) in [("Equipped", equipped_slots), ("Learned", learned_slots)] {
+        panel.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Column, row_gap: Val::Px(8.0), ..default() }, ..default() }).with_children(|column| {
+            column.spawn(TextBundle::from_section(title, TextStyle { font: font.clone(), font_size: 24.0, color: Color::GOLD }));
+            for slot in slots {
+                column.spawn((
+                    ButtonBundle { style: Style { width: Val::Px(320.0), height: Val::Px(44.0), padding: UiRect::horizontal(Val::Px(10.0)), align_items: AlignItems::Center, ..default() }, background_color: SLOT_COLOR.into(), ..default() },
+                    slot,
+                )).with_children(|button| {
+                    button.spawn(TextBundle::from_section(skill_label(survivor, skill_library, input_map, slot), TextStyle { font: font.clone(), font_size: 20.0, color: Color::WHITE }));
+                });
+            }
+        });
+    }
+}
+
+fn handle_skill_slot_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    interaction_query: Query<(&Interaction, &SkillSlot), (Changed<Interaction>, With<Button>)>,
+    mut survivor_query: Query<&mut Survivor>,
+    sources: TabSources,
+    mut selection: ResMut<SkillSwapSelection>,
+    tab: Res<PauseTab>,
+    panel_query: Query<Entity, With<PauseTabPanel>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    let Some(pressed) = interaction_query.iter().find(|(interaction, _)| **interaction == Interaction::Pressed).map(|(_, slot)| *slot) else { return; };
+    let Some(selected) = selection.0.take() else {
+        selection.0 = Some(pressed);
+        return;
+    };
+    let Ok(mut survivor) = survivor_query.get_single_mut() else { return; };
+    if !swap_skill_slots(&mut survivor, selected, pressed) { return; }
+    sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted));
+    // Labels and the learned column's length both change, so the panel is rebuilt rather than patched
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    for panel in panel_query.iter() {
+        commands.entity(panel).despawn_descendants().with_children(|panel| spawn_tab_contents(panel, *tab, Some(&survivor), &sources, &font));
+    }
+}
+
+fn handle_pause_tab_buttons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    interaction_query: Query<(&Interaction, &PauseTab), (Changed<Interaction>, With<Button>)>,
+    survivor_query: Query<&Survivor>,
+    sources: TabSources,
+    mut tab: ResMut<PauseTab>,
+    mut selection: ResMut<SkillSwapSelection>,
+    panel_query: Query<Entity, With<PauseTabPanel>>,
+) {
+    let Some(pressed) = interaction_query.iter().find(|(interaction, _)| **interaction == Interaction::Pressed).map(|(_, tab)| *tab) else { return; };
+    if *tab == pressed { return; }
+    *tab = pressed;
+    selection.0 = None;
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    for panel in panel_query.iter() {
+        commands.entity(panel).despawn_descendants().with_children(|panel| spawn_tab_contents(panel, pressed, survivor_query.get_single().ok(), &sources, &font));
+    }
+}
+
+fn highlight_active_tab(tab: Res<PauseTab>, mut button_query: Query<(&PauseTab, &mut BorderColor)>) {
+    for (button_tab, mut border) in button_query.iter_mut() {
+        let color = if *button_tab == *tab { Color::GOLD } else { Color::NONE };
+        if border.0 != color { border.0 = color; }
+    }
+}
+
+fn highlight_selected_slot(selection: Res<SkillSwapSelection>, mut button_query: Query<(&SkillSlot, &mut BackgroundColor)>) {
+    for (slot, mut background) in button_query.iter_mut() {
+        let color = if selection.0 == Some(*slot) { SELECTED_SLOT_COLOR } else { SLOT_COLOR };
+        if background.0 != color { background.0 = color; }
+    }
+}
+
+fn despawn_pause_menu_ui(mut commands: Commands, query: Query<Entity, With<PauseMenuUI>>) {
+    for entity in query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,239 @@
+use bevy::{
+    prelude::*,
+    app::{MainScheduleOrder, RunFixedMainLoop, SpawnScene},
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin, RegisterDiagnostic},
+    ecs::{schedule::ScheduleLabel, system::{Adapt, AdapterSystem}},
+    utils::Instant,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::{
+    background::AmbientParticle,
+    echoing_soul::EchoingSoul,
+    horror::{Horror, HorrorProjectile},
+    skills::{RicochetOrb, SkillProjectile},
+    visual_effects::BurstParticle,
+    weapons::CrescentProjectile,
+};
+
+pub const PERF_OVERLAY_KEY: KeyCode = KeyCode::F10;
+// CPU time from the first to the last schedule of the main frame, i.e. everything but presenting it
+pub const MAIN_SCHEDULE_TIME: DiagnosticPath = DiagnosticPath::const_new("main_schedule_time");
+pub const UPDATE_SCHEDULE_TIME: DiagnosticPath = DiagnosticPath::const_new("update_schedule_time");
+pub const POST_UPDATE_SCHEDULE_TIME: DiagnosticPath = DiagnosticPath::const_new("post_update_schedule_time");
+// Summed run time of every system wrapped in timed_collision; the sum can exceed wall time when they run in parallel
+pub const COLLISION_SYSTEMS_TIME: DiagnosticPath = DiagnosticPath::const_new("collision_systems_time");
+pub const FRAME_GRAPH_BARS: usize = 120;
+// Frame times at or above this fill the whole graph height
+pub const FRAME_GRAPH_CEILING_MS: f32 = 50.0;
+const TARGET_FRAME_MS: f32 = 1000.0 / 60.0;
+const FRAME_GRAPH_HEIGHT: f32 = 60.0;
+const FRAME_GRAPH_BAR_WIDTH: f32 = 2.0;
+
+pub struct PerfOverlayPlugin;
+
+impl Plugin for PerfOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin))
+            .register_diagnostic(Diagnostic::new(MAIN_SCHEDULE_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(UPDATE_SCHEDULE_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(POST_UPDATE_SCHEDULE_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(COLLISION_SYSTEMS_TIME).with_suffix("ms"))
+            .init_resource::<PerfOverlay>()
+            .init_resource::<MainScheduleStart>()
+            .init_resource::<ScheduleMarks>()
+            .add_systems(Startup, setup_perf_overlay)
+            .add_systems(First, mark_main_schedule_start)
+            .add_systems(Last, (measure_main_schedule, measure_schedule_spans, measure_collision_systems))
+            .add_systems(Update, (
+                toggle_perf_overlay,
+                (update_perf_text, update_frame_graph).run_if(|overlay: Res<PerfOverlay>| overlay.visible),
+            ).chain());
+
+        // Empty schedules around Update and PostUpdate stamp the time as the main loop passes them
+        let mut order = app.world.resource_mut::<MainScheduleOrder>();
+        order.insert_after(RunFixedMainLoop, ScheduleMark::UpdateStart);
+        order.insert_after(Update, ScheduleMark::UpdateEnd);
+        order.insert_after(SpawnScene, ScheduleMark::PostUpdateStart);
+        order.insert_after(PostUpdate, ScheduleMark::PostUpdateEnd);
+        for mark in ScheduleMark::ALL {
+            app.add_systems(mark, move |mut marks: ResMut<ScheduleMarks>| marks.0[mark as usize] = Some(Instant::now()));
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct PerfOverlay {
+    pub visible: bool,
+}
+
+// What is alive right now, split the way performance reports usually need it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EntityBreakdown {
+    pub enemies: usize,
+    pub projectiles: usize,
+    pub orbs: usize,
+    pub particles: usize,
+}
+
+impl EntityBreakdown {
+    // Whatever the named groups don't cover: UI, the camera, background tiles and so on
+    pub fn other(&self, total: usize) -> usize {
+        total.saturating_sub(self.enemies + self.projectiles + self.orbs + self.particles)
+    }
+}
+
+// Height as a fraction of the graph, and green/yellow/red against 60 and 30 FPS budgets
+pub fn frame_graph_bar(frame_ms: f32) -> (f32, Color) {
+    let height = (frame_ms / FRAME_GRAPH_CEILING_MS).clamp(0.0, 1.0);
+    let color = if frame_ms <= TARGET_FRAME_MS * 1.05 { Color::rgb(0.3, 0.9, 0.4) } else if frame_ms <= TARGET_FRAME_MS * 2.0 { Color::rgb(1.0, 0.8, 0.2) } else { Color::rgb(1.0, 0.3, 0.3) };
+    (height, color)
+}
+
+// Wraps a collision system so its run time counts towards COLLISION_SYSTEMS_TIME; ordering against it by name still works
+pub fn timed_collision<M>(system: impl IntoSystem<(), (), M>) -> impl System<In = (), Out = ()> {
+    let system = IntoSystem::into_system(system);
+    let name = system.name();
+    AdapterSystem::new(CollisionTimer, system, name)
+}
+
+// Collision time gathered since the last call, in milliseconds
+pub fn take_collision_time_ms() -> f64 {
+    COLLISION_NANOS.swap(0, Ordering::Relaxed) as f64 / 1_000_000.0
+}
+
+// Collision systems live in several plugins and may run on any thread, so they all add into one counter
+static COLLISION_NANOS: AtomicU64 = AtomicU64::new(0);
+
+struct CollisionTimer;
+
+impl<S: System<In = (), Out = ()>> Adapt<S> for CollisionTimer {
+    type In = ();
+    type Out = ();
+
+    fn adapt(&mut self, input: (), run_system: impl FnOnce(())) {
+        let started = Instant::now();
+        run_system(input);
+        COLLISION_NANOS.fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ScheduleMark {
+    UpdateStart,
+    UpdateEnd,
+    PostUpdateStart,
+    PostUpdateEnd,
+}
+
+impl ScheduleMark {
+    const ALL: [ScheduleMark; 4] = [ScheduleMark::UpdateStart, ScheduleMark::UpdateEnd, ScheduleMark::PostUpdateStart, ScheduleMark::PostUpdateEnd];
+}
+
+#[derive(Resource, Default)]
+struct MainScheduleStart(Option<Instant>);
+
+#[derive(Resource, Default)]
+struct ScheduleMarks([Option<Instant>; 4]);
+
+#[derive(Component)]
+struct PerfOverlayUI;
+
+#[derive(Component)]
+struct PerfOverlayText;
+
+#[derive(Component)]
+struct FrameGraphBar(usize);
+
+fn mark_main_schedule_start(mut start: ResMut<MainScheduleStart>) {
+    start.0 = Some(Instant::now());
+}
+
+fn measure_main_schedule(start: Res<MainScheduleStart>, mut diagnostics: Diagnostics) {
+    if let Some(started) = start.0 {
+        diagnostics.add_measurement(&MAIN_SCHEDULE_TIME, || started.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+fn measure_schedule_spans(marks: Res<ScheduleMarks>, mut diagnostics: Diagnostics) {
+    let span = |start: ScheduleMark, end: ScheduleMark| match (marks.0[start as usize], marks.0[end as usize]) {
+        (Some(started), Some(ended)) => Some(ended.saturating_duration_since(started).as_secs_f64() * 1000.0),
+        _ => None,
+    };
+    if let Some(update_ms) = span(ScheduleMark::UpdateStart, ScheduleMark::UpdateEnd) { diagnostics.add_measurement(&UPDATE_SCHEDULE_TIME, || update_ms); }
+    if let Some(post_update_ms) = span(ScheduleMark::PostUpdateStart, ScheduleMark::PostUpdateEnd) { diagnostics.add_measurement(&POST_UPDATE_SCHEDULE_TIME, || post_update_ms); }
+}
+
+fn measure_collision_systems(mut diagnostics: Diagnostics) {
+    let collision_ms = take_collision_time_ms();
+    diagnostics.add_measurement(&COLLISION_SYSTEMS_TIME, || collision_ms);
+}
+
+fn setup_perf_overlay(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle {
+            style: Style { display: Display::None, position_type: PositionType::Absolute, top: Val::Px(50.0), left: Val::Px(10.0), flex_direction: FlexDirection::Column, row_gap: Val::Px(6.0), padding: UiRect::all(Val::Px(8.0)), ..default() },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        PerfOverlayUI,
+    )).with_children(|parent| {
+        parent.spawn((TextBundle::from_section("", TextStyle { font, font_size: 16.0, color: Color::WHITE }), PerfOverlayText));
+        parent.spawn(NodeBundle {
+            style: Style { width: Val::Px(FRAME_GRAPH_BARS as f32 * FRAME_GRAPH_BAR_WIDTH), height: Val::Px(FRAME_GRAPH_HEIGHT), align_items: AlignItems::FlexEnd, ..default() },
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.05).into(),
+            ..default()
+        }).with_children(|graph| {
+            for index in 0..FRAME_GRAPH_BARS {
+                graph.spawn((NodeBundle { style: Style { width: Val::Px(FRAME_GRAPH_BAR_WIDTH), height: Val::Px(0.0), ..default() }, ..default() }, FrameGraphBar(index)));
+            }
+        });
+    });
+}
+
+fn toggle_perf_overlay(keyboard_input: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<PerfOverlay>, mut ui_query: Query<&mut Style, With<PerfOverlayUI>>) {
+    if !keyboard_input.just_pressed(PERF_OVERLAY_KEY) { return; }
+    overlay.visible = !overlay.visible;
+    for mut style in ui_query.iter_mut() { style.display = if overlay.visible { Display::Flex } else { Display::None }; }
+}
+
+fn update_perf_text(
+    diagnostics: Res<DiagnosticsStore>,
+    enemy_query: Query<(), With<Horror>>,
+    projectile_query: Query<(), Or<(With<SkillProjectile>, With<HorrorProjectile>, With<CrescentProjectile>, With<RicochetOrb>)>>,
+    orb_query: Query<(), With<EchoingSoul>>,
+    particle_query: Query<(), Or<(With<BurstParticle>, With<AmbientParticle>)>>,
+    mut text_query: Query<&mut Text, With<PerfOverlayText>>,
+) {
+    let Ok(mut text) = text_query.get_single_mut() else { return; };
+    let smoothed = |path: &DiagnosticPath| diagnostics.get(path).and_then(|diagnostic| diagnostic.smoothed());
+    let breakdown = EntityBreakdown { enemies: enemy_query.iter().count(), projectiles: projectile_query.iter().count(), orbs: orb_query.iter().count(), particles: particle_query.iter().count() };
+    let total = smoothed(&EntityCountDiagnosticsPlugin::ENTITY_COUNT).unwrap_or_default() as usize;
+
+    let mut lines = vec![
+        format!("FPS {:.0}   frame {:.2} ms", smoothed(&FrameTimeDiagnosticsPlugin::FPS).unwrap_or_default(), smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME).unwrap_or_default()),
+        format!("CPU {:.2} ms: Update {:.2}  PostUpdate {:.2}  collision {:.2}", smoothed(&MAIN_SCHEDULE_TIME).unwrap_or_default(), smoothed(&UPDATE_SCHEDULE_TIME).unwrap_or_default(), smoothed(&POST_UPDATE_SCHEDULE_TIME).unwrap_or_default(), smoothed(&COLLISION_SYSTEMS_TIME).unwrap_or_default()),
+        format!("Entities {}: enemies {}  projectiles {}  orbs {}  particles {}  other {}", total, breakdown.enemies, breakdown.projectiles, breakdown.orbs, breakdown.particles, breakdown.other(total)),
+    ];
+    // Everything else registered with Bevy's diagnostics, so timings added elsewhere show up without touching this
+    let known = [FrameTimeDiagnosticsPlugin::FPS, FrameTimeDiagnosticsPlugin::FRAME_TIME, FrameTimeDiagnosticsPlugin::FRAME_COUNT, EntityCountDiagnosticsPlugin::ENTITY_COUNT, MAIN_SCHEDULE_TIME, UPDATE_SCHEDULE_TIME, POST_UPDATE_SCHEDULE_TIME, COLLISION_SYSTEMS_TIME];
+    for diagnostic in diagnostics.iter().filter(|diagnostic| diagnostic.is_enabled && !known.contains(diagnostic.path())) {
+        if let Some(value) = diagnostic.smoothed() { lines.push(format!("{} {:.2}{}", diagnostic.path(), value, diagnostic.suffix)); }
+    }
+    text.sections[0].value = lines.join("\n");
+}
+
+fn update_frame_graph(diagnostics: Res<DiagnosticsStore>, mut bar_query: Query<(&FrameGraphBar, &mut Style, &mut BackgroundColor)>) {
+    let Some(frame_times) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else { return; };
+    let history: Vec<f64> = frame_times.values().copied().collect();
+    // Newest frame on the right; bars without a sample yet stay flat
+    let offset = FRAME_GRAPH_BARS.saturating_sub(history.len());
+    let skipped = history.len().saturating_sub(FRAME_GRAPH_BARS);
+    for (bar, mut style, mut background) in bar_query.iter_mut() {
+        let sample = bar.0.checked_sub(offset).and_then(|index| history.get(skipped + index));
+        let (height, color) = sample.map_or((0.0, Color::NONE), |frame_ms| frame_graph_bar(*frame_ms as f32));
+        style.height = Val::Px(height * FRAME_GRAPH_HEIGHT);
+        background.0 = color;
+    }
+}
@@ -0,0 +1,211 @@
+use bevy::{prelude::*, window::WindowResized};
+use std::{collections::HashMap, fs, io::{self, Write}, path::{Path, PathBuf}, sync::Mutex};
+use crate::game::{SCREEN_WIDTH, SCREEN_HEIGHT};
+
+const BACKUP_EXTENSION: &str = "bak";
+const TEMP_EXTENSION: &str = "tmp";
+// Keeps the game's entries apart from anything else the page's origin has stored
+#[cfg(target_arch = "wasm32")]
+const LOCAL_STORAGE_PREFIX: &str = "echoes_of_the_abyss/";
+// The canvas web/index.html provides; without one Bevy would append its own to the page body
+#[cfg(target_arch = "wasm32")]
+const CANVAS_SELECTOR: &str = "#bevy";
+
+pub struct PlatformPlugin;
+
+impl Plugin for PlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameStorage>()
+            .init_non_send_resource::<SystemClipboard>()
+            .add_systems(Update, scale_ui_to_window);
+    }
+}
+
+// Where the profile, run history and exported builds live: files beside the game on desktop, localStorage in a browser
+pub trait Storage: Send + Sync {
+    fn read(&self, key: &str) -> io::Result<String>;
+    fn write(&self, key: &str, contents: &str) -> io::Result<()>;
+
+    // Whatever was stored before is kept under backup_key, so a damaged entry can be recovered
+    fn write_keeping_backup(&self, key: &str, contents: &str) -> io::Result<()> {
+        match self.read(key) {
+            Ok(previous) => self.write(&backup_key(key), &previous)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+        self.write(key, contents)
+    }
+}
+
+pub fn backup_key(key: &str) -> String {
+    format!("{}.{}", key, BACKUP_EXTENSION)
+}
+
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    path.with_file_name(file_name)
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling_path(path, BACKUP_EXTENSION)
+}
+
+// Writes to a temp file, flushes it to disk and only then swaps it in, so a crash mid-write leaves the
+// previous file (or its backup) intact
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let temp_path = sibling_path(path, TEMP_EXTENSION);
+    {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+    if path.exists() { fs::rename(path, backup_path(path))?; }
+    fs::rename(&temp_path, path)
+}
+
+// Keys are file names inside the root folder
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    pub root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self { Self { root: root.into() } }
+}
+
+impl Default for FileStorage {
+    fn default() -> Self { Self::new(".") }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, key: &str) -> io::Result<String> { fs::read_to_string(self.root.join(key)) }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> { fs::write(self.root.join(key), contents) }
+
+    fn write_keeping_backup(&self, key: &str, contents: &str) -> io::Result<()> { write_atomically(&self.root.join(key), contents) }
+}
+
+// Gone when the app closes; headless simulations use it so they never touch the player's files
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> io::Result<String> {
+        let entries = self.entries.lock().map_err(|_| io::Error::other("memory storage is poisoned"))?;
+        entries.get(key).cloned().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        let mut entries = self.entries.lock().map_err(|_| io::Error::other("memory storage is poisoned"))?;
+        entries.insert(key.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+// The browser's per-site key-value store; it survives reloads, and a full quota surfaces as a write error
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Default)]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn store() -> io::Result<web_sys::Storage> {
+        web_sys::window().and_then(|window| window.local_storage().ok().flatten()).ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "localStorage is not available"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn read(&self, key: &str) -> io::Result<String> {
+        let item = Self::store()?.get_item(&format!("{}{}", LOCAL_STORAGE_PREFIX, key)).map_err(|error| io::Error::new(io::ErrorKind::Other, format!("{:?}", error)))?;
+        item.ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        Self::store()?.set_item(&format!("{}{}", LOCAL_STORAGE_PREFIX, key), contents).map_err(|error| io::Error::new(io::ErrorKind::Other, format!("{:?}", error)))
+    }
+}
+
+#[derive(Resource)]
+pub struct GameStorage(pub Box<dyn Storage>);
+
+impl GameStorage {
+    pub fn in_memory() -> Self { Self(Box::new(MemoryStorage::default())) }
+}
+
+impl Default for GameStorage {
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self { Self(Box::new(LocalStorage)) }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self { Self(Box::new(FileStorage::default())) }
+}
+
+// Opened on first use and then kept: on Linux copied text only stays pasteable while the handle that set it is alive.
+// Not every platform's handle can move between threads, so this is a non-send resource
+#[derive(Default)]
+pub struct SystemClipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open(&mut self) -> Result<&mut arboard::Clipboard, String> {
+        if self.clipboard.is_none() { self.clipboard = Some(arboard::Clipboard::new().map_err(|error| error.to_string())?); }
+        self.clipboard.as_mut().ok_or_else(|| "the clipboard could not be opened".to_string())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn copy(&mut self, text: &str) -> Result<(), String> {
+        self.open()?.set_text(text).map_err(|error| error.to_string())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn paste(&mut self) -> Result<String, String> {
+        self.open()?.get_text().map_err(|error| error.to_string())
+    }
+
+    // Browsers only hand out the clipboard through async page APIs, so the web build has none
+    #[cfg(target_arch = "wasm32")]
+    pub fn copy(&mut self, _text: &str) -> Result<(), String> {
+        Err("the clipboard is not available in the browser".to_string())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn paste(&mut self) -> Result<String, String> {
+        Err("the clipboard is not available in the browser".to_string())
+    }
+}
+
+// std's clock panics in the browser, so the date comes from JavaScript there
+pub fn unix_time_secs() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    { (js_sys::Date::now() / 1000.0) as u64 }
+    #[cfg(not(target_arch = "wasm32"))]
+    { std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()) }
+}
+
+// A fixed-size window on desktop; in a browser the canvas fills its parent and follows it as the page resizes
+pub fn primary_window(title: &str) -> Window {
+    let window = Window { title: title.into(), resolution: (SCREEN_WIDTH, SCREEN_HEIGHT).into(), resizable: false, ..default() };
+    #[cfg(target_arch = "wasm32")]
+    let window = Window { canvas: Some(CANVAS_SELECTOR.into()), fit_canvas_to_parent: true, resizable: true, ..window };
+    window
+}
+
+// The UI is laid out for the design resolution, so it shrinks to fit a smaller canvas rather than running off the edge
+pub fn ui_scale_for(window_size: Vec2) -> f32 {
+    if window_size.x <= 0.0 || window_size.y <= 0.0 { return 1.0; }
+    (window_size.x / SCREEN_WIDTH).min(window_size.y / SCREEN_HEIGHT)
+}
+
+fn scale_ui_to_window(mut resize_events: EventReader<WindowResized>, mut ui_scale: ResMut<UiScale>) {
+    let Some(resized) = resize_events.read().last() else { return; };
+    let scale = ui_scale_for(Vec2::new(resized.width, resized.height));
+    if ui_scale.0 != scale { ui_scale.0 = scale; }
+}
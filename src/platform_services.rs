@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use crate::{
+    game::{AppState, GameState},
+    game_events::GameEvent,
+    extraction::RunOutcome,
+    ranking::RunStats,
+    boss_practice::BossPractice,
+};
+
+pub const ACHIEVEMENT_LEVEL: u32 = 20;
+pub const ACHIEVEMENT_CYCLE: u32 = 10;
+
+pub struct PlatformServicesPlugin;
+
+impl Plugin for PlatformServicesPlugin {
+    fn build(&self, app: &mut App) {
+        // Leaves the defaults in place when Steam isn't running, so the same build still starts outside it
+        #[cfg(feature = "steam")]
+        crate::steam::install(app);
+        app.init_resource::<PlatformServices>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Update, unlock_achievements_from_events.run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), unlock_achievements_for_finished_run)
+            .add_systems(Last, update_rich_presence);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    SlayElite,
+    ReachLevel,
+    SurviveCycles,
+    Extract,
+}
+
+impl Achievement {
+    #[cfg(feature = "steam")]
+    pub const ALL: [Achievement; 4] = [Achievement::SlayElite, Achievement::ReachLevel, Achievement::SurviveCycles, Achievement::Extract];
+
+    // The names the achievements are registered under with Steam
+    #[cfg(feature = "steam")]
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            Achievement::SlayElite => "ACH_SLAY_ELITE",
+            Achievement::ReachLevel => "ACH_REACH_LEVEL_20",
+            Achievement::SurviveCycles => "ACH_SURVIVE_10_CYCLES",
+            Achievement::Extract => "ACH_EXTRACT",
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Achievement::SlayElite => "Slay an elite".to_string(),
+            Achievement::ReachLevel => format!("Reach level {}", ACHIEVEMENT_LEVEL),
+            Achievement::SurviveCycles => format!("Survive {} cycles", ACHIEVEMENT_CYCLE),
+            Achievement::Extract => "Escape through a rift".to_string(),
+        }
+    }
+}
+
+// Sent alongside every report to the storefront, so the game's own unlocks don't depend on one being there
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AchievementUnlocked(pub Achievement);
+
+pub fn achievement_for_event(event: &GameEvent) -> Option<Achievement> {
+    match event {
+        GameEvent::HorrorKilled(kill) if kill.is_elite => Some(Achievement::SlayElite),
+        GameEvent::LevelUp { new_level, .. } if *new_level >= ACHIEVEMENT_LEVEL => Some(Achievement::ReachLevel),
+        _ => None,
+    }
+}
+
+pub fn achievements_for_run(cycle_reached: u32, outcome: RunOutcome) -> Vec<Achievement> {
+    let mut achievements = Vec::new();
+    if cycle_reached >= ACHIEVEMENT_CYCLE { achievements.push(Achievement::SurviveCycles); }
+    if outcome == RunOutcome::Extracted { achievements.push(Achievement::Extract); }
+    achievements
+}
+
+// What friends see the player doing
+pub fn rich_presence(state: &AppState, cycle: u32) -> String {
+    match state {
+        AppState::MainMenu => "In the main menu".to_string(),
+        AppState::GameOver => "Reading the results of a descent".to_string(),
+        AppState::Paused => format!("Paused in cycle {}", cycle),
+        _ => format!("Surviving cycle {}", cycle),
+    }
+}
+
+// A storefront the game reports to. Unlocking an achievement twice is harmless; stores ignore repeats
+pub trait PlatformService: Send + Sync {
+    fn unlock_achievement(&mut self, achievement: Achievement);
+    fn set_rich_presence(&mut self, status: &str);
+}
+
+// Builds without a storefront have nothing to report to
+#[derive(Debug, Default)]
+pub struct NoPlatformService;
+
+impl PlatformService for NoPlatformService {
+    fn unlock_achievement(&mut self, _achievement: Achievement) {}
+    fn set_rich_presence(&mut self, _status: &str) {}
+}
+
+#[derive(Resource)]
+pub struct PlatformServices(pub Box<dyn PlatformService>);
+
+impl Default for PlatformServices {
+    fn default() -> Self { Self(Box::new(NoPlatformService)) }
+}
+
+// Runs touched by debug tools and boss practice fights earn nothing, same as they stay off the records
+fn earns_achievements(run_stats: &RunStats, practice: &BossPractice) -> bool {
+    run_stats.counts_for_records() && !practice.is_active()
+}
+
+fn unlock_achievements_from_events(
+    mut game_events: EventReader<GameEvent>,
+    run_stats: Res<RunStats>,
+    practice: Res<BossPractice>,
+    mut services: ResMut<PlatformServices>,
+    mut unlocked_writer: EventWriter<AchievementUnlocked>,
+) {
+    let achievements: Vec<Achievement> = game_events.read().filter_map(achievement_for_event).collect();
+    if !earns_achievements(&run_stats, &practice) { return; }
+    for achievement in achievements {
+        services.0.unlock_achievement(achievement);
+        unlocked_writer.send(AchievementUnlocked(achievement));
+    }
+}
+
+fn unlock_achievements_for_finished_run(
+    game_state: Res<GameState>,
+    outcome: Res<RunOutcome>,
+    run_stats: Res<RunStats>,
+    practice: Res<BossPractice>,
+    mut services: ResMut<PlatformServices>,
+    mut unlocked_writer: EventWriter<AchievementUnlocked>,
+) {
+    if !earns_achievements(&run_stats, &practice) { return; }
+    for achievement in achievements_for_run(game_state.cycle_number, *outcome) {
+        services.0.unlock_achievement(achievement);
+        unlocked_writer.send(AchievementUnlocked(achievement));
+    }
+}
+
+fn update_rich_presence(app_state: Res<State<AppState>>, game_state: Res<GameState>, mut services: ResMut<PlatformServices>, mut last_status: Local<String>) {
+    let status = rich_presence(app_state.get(), game_state.cycle_number);
+    if *last_status == status { return; }
+    services.0.set_rich_presence(&status);
+    *last_status = status;
+}
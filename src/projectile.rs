@@ -0,0 +1,162 @@
+use bevy::{prelude::*, ecs::entity::EntityHashSet};
+use crate::{
+    components::{Velocity, Damage, Lifetime, ProjectileOwner, Reflectable, DamageType, TimeScale, OverkillCarry},
+    survivor::Survivor,
+    horror::{Horror, HorrorProjectile},
+    ichor_blast::IchorBlast,
+    skills::{SkillId, SkillProjectile, ProjectileSplit},
+    game::AppState,
+    camera_systems::{MainCamera, view_half_extents},
+};
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectileCullPolicy>()
+            .add_systems(Update, (projectile_homing_system, cull_offscreen_projectiles).run_if(in_state(AppState::InGame)));
+    }
+}
+
+// Straight-flying shots this far past the edge of the view, and heading further out, can no longer matter and are despawned early.
+// Homing shots and shots that split when they expire are exempt, since they or their fragments may still come back;
+// boomerangs like the Hooked Crescent aren't owned projectiles at all.
+#[derive(Resource, Debug, Clone)]
+pub struct ProjectileCullPolicy {
+    pub enabled: bool,
+    pub margin: f32,
+}
+
+impl Default for ProjectileCullPolicy {
+    fn default() -> Self { Self { enabled: true, margin: 250.0 } }
+}
+
+// Only shots moving away count, so a horror firing from just off screen isn't culled before its shot arrives
+pub fn is_leaving_view(position: Vec2, velocity: Vec2, view_center: Vec2, view_half_extents: Vec2, margin: f32) -> bool {
+    let offset = position - view_center;
+    let beyond = offset.abs().cmpgt(view_half_extents + Vec2::splat(margin)).any();
+    beyond && velocity.dot(offset) > 0.0
+}
+
+#[derive(Clone, Debug)]
+pub struct ProjectileVisual {
+    pub texture: Handle<Image>,
+    pub size: Vec2,
+    pub color: Color,
+    pub z: Option<f32>, // Overrides the spawn position's depth when set
+}
+
+#[derive(Clone, Debug)]
+pub struct SkillProjectilePayload {
+    pub skill_id: SkillId,
+    pub bounces: u32,
+    pub split: Option<ProjectileSplit>,
+    pub already_hit: EntityHashSet,
+}
+
+// Everything needed to put a projectile in flight; the owner decides which collision system resolves it
+#[derive(Clone, Debug)]
+pub struct ProjectileSpec {
+    pub owner: ProjectileOwner,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub speed: f32,
+    pub lifetime_secs: f32,
+    pub piercing: u32,
+    pub homing_turn_rate: Option<f32>,
+    pub overkill_carry: Option<OverkillCarry>,
+    pub visual: ProjectileVisual,
+    pub skill: Option<SkillProjectilePayload>,
+    pub name: String,
+}
+
+impl ProjectileSpec {
+    pub fn new(owner: ProjectileOwner, damage: i32, speed: f32, lifetime_secs: f32, visual: ProjectileVisual, name: impl Into<String>) -> Self {
+        Self { owner, damage, damage_type: DamageType::default(), speed, lifetime_secs, piercing: 0, homing_turn_rate: None, overkill_carry: None, visual, skill: None, name: name.into() }
+    }
+    pub fn with_piercing(mut self, piercing: u32) -> Self { self.piercing = piercing; self }
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Self { self.damage_type = damage_type; self }
+    pub fn with_homing(mut self, turn_rate: f32) -> Self { self.homing_turn_rate = Some(turn_rate); self }
+    pub fn with_overkill_carry(mut self, carry: OverkillCarry) -> Self { self.overkill_carry = Some(carry); self }
+    pub fn for_skill(mut self, payload: SkillProjectilePayload) -> Self { self.skill = Some(payload); self }
+}
+
+// Turns towards the nearest opposing target at up to turn_rate radians per second
+#[derive(Component, Debug)]
+pub struct ProjectileHoming { pub turn_rate: f32 }
+
+pub fn spawn_projectile(commands: &mut Commands, spec: ProjectileSpec, mut position: Vec3, direction: Vec2) -> Entity {
+    if let Some(z) = spec.visual.z { position.z = z; }
+    let mut projectile = commands.spawn((
+        SpriteBundle {
+            texture: spec.visual.texture,
+            sprite: Sprite { custom_size: Some(spec.visual.size), color: spec.visual.color, ..default() },
+            transform: Transform::from_translation(position).with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+            ..default()
+        },
+        spec.owner,
+        Velocity(direction * spec.speed),
+        Damage(spec.damage),
+        spec.damage_type,
+        Lifetime { timer: Timer::from_seconds(spec.lifetime_secs, TimerMode::Once) },
+        Name::new(spec.name),
+    ));
+    match (spec.owner, spec.skill) {
+        (ProjectileOwner::Horror, _) => { projectile.insert((HorrorProjectile, Reflectable)); }
+        (ProjectileOwner::Survivor, Some(payload)) => {
+            projectile.insert(SkillProjectile { skill_id: payload.skill_id, piercing_left: spec.piercing, bounces_left: payload.bounces, already_hit_by_this_projectile: payload.already_hit, split: payload.split });
+        }
+        (ProjectileOwner::Survivor, None) => { projectile.insert(IchorBlast { piercing_left: spec.piercing }); }
+    }
+    if let Some(turn_rate) = spec.homing_turn_rate { projectile.insert(ProjectileHoming { turn_rate }); }
+    if let Some(carry) = spec.overkill_carry { projectile.insert(carry); }
+    projectile.id()
+}
+
+// Rotates velocity towards the target by at most max_turn radians, keeping its speed
+pub fn steer_towards(velocity: Vec2, to_target: Vec2, max_turn: f32) -> Vec2 {
+    if velocity == Vec2::ZERO || to_target == Vec2::ZERO { return velocity; }
+    let current_angle = velocity.to_angle();
+    let turn = ((to_target.to_angle() - current_angle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI).clamp(-max_turn, max_turn);
+    Vec2::from_angle(current_angle + turn) * velocity.length()
+}
+
+fn cull_offscreen_projectiles(
+    mut commands: Commands,
+    policy: Res<ProjectileCullPolicy>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    projectile_query: Query<(Entity, &Transform, &Velocity, Option<&SkillProjectile>), (With<ProjectileOwner>, Without<ProjectileHoming>)>,
+) {
+    if !policy.enabled { return; }
+    let Ok((camera_transform, projection)) = camera_query.get_single() else { return; };
+    let view_center = camera_transform.translation.truncate();
+    let view_extents = view_half_extents(projection);
+    for (entity, transform, velocity, skill_projectile) in projectile_query.iter() {
+        if skill_projectile.is_some_and(|projectile| projectile.split.is_some()) { continue; }
+        if is_leaving_view(transform.translation.truncate(), velocity.0, view_center, view_extents, policy.margin) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn projectile_homing_system(
+    time: Res<Time>,
+    mut projectile_query: Query<(&mut Transform, &mut Velocity, &ProjectileHoming, &ProjectileOwner, Option<&TimeScale>)>,
+    horror_query: Query<&GlobalTransform, With<Horror>>,
+    survivor_query: Query<&GlobalTransform, With<Survivor>>,
+) {
+    let survivor_pos = survivor_query.get_single().ok().map(|transform| transform.translation().truncate());
+    for (mut transform, mut velocity, homing, owner, time_scale_opt) in projectile_query.iter_mut() {
+        let projectile_pos = transform.translation.truncate();
+        let target_pos = match owner {
+            ProjectileOwner::Survivor => horror_query.iter()
+                .map(|horror_transform| horror_transform.translation().truncate())
+                .min_by(|a, b| a.distance_squared(projectile_pos).total_cmp(&b.distance_squared(projectile_pos))),
+            ProjectileOwner::Horror => survivor_pos,
+        };
+        let Some(target_pos) = target_pos else { continue; };
+        let max_turn = homing.turn_rate * time.delta_seconds() * TimeScale::of(time_scale_opt);
+        velocity.0 = steer_towards(velocity.0, target_pos - projectile_pos, max_turn);
+        transform.rotation = Quat::from_rotation_z(velocity.0.to_angle());
+    }
+}
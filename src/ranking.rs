@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::{
+    survivor::Survivor,
+    components::Health,
+    game_events::{GameEvent, DamageOrigin},
+    summons::SummonKind,
+    game::{AppState, GameState},
+    extraction::RunOutcome,
+    hardcore::{HardcoreMode, UnbankedRemnants, settle_hardcore_run},
+};
+
+// Kills closer together than this keep the combo going
+pub const COMBO_WINDOW_SECS: f32 = 2.0;
+// Short runs are graded as if they lasted this long, so a lucky opening burst can't inflate kills per minute
+const MIN_GRADED_MINUTES: f32 = 1.0;
+const EMBEDDED_GRADE_THRESHOLDS: &str = include_str!("../assets/data/grades.ron");
+
+pub struct RankingPlugin;
+
+impl Plugin for RankingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .init_resource::<Remnants>()
+            .insert_resource(GradeThresholds::from_ron_str(EMBEDDED_GRADE_THRESHOLDS).expect("embedded grade thresholds are invalid"))
+            .add_systems(OnExit(AppState::MainMenu), reset_run_stats)
+            .add_systems(Update, run_stats_tracking_system.run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), (grade_finished_run, setup_run_grade_ui).chain())
+            .add_systems(OnExit(AppState::GameOver), despawn_run_grade_ui);
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunGrade { S, A, B, C, }
+
+impl RunGrade {
+    fn color(self) -> Color {
+        match self { RunGrade::S => Color::GOLD, RunGrade::A => Color::rgb(0.4, 0.9, 0.4), RunGrade::B => Color::rgb(0.4, 0.7, 1.0), RunGrade::C => Color::GRAY, }
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone)]
+pub struct RunStats {
+    pub kills: u32,
+    pub damage_taken: u32,
+    pub highest_combo: u32,
+    pub current_combo: u32,
+    pub secs_since_last_kill: f32,
+    pub damage_dealt: u32,
+    // Summon hits are part of damage_dealt too; this splits them out per kind, in the order each first landed
+    pub summon_damage: Vec<(SummonKind, u32)>,
+    // Set once the debug menu or a sandbox import has touched the run
+    pub cheats_used: bool,
+}
+
+impl RunStats {
+    // Advances the combo clock, then counts this frame's kills towards the running combo
+    pub fn record(&mut self, delta_secs: f32, kills: u32, damage_taken: u32) {
+        self.secs_since_last_kill += delta_secs;
+        if self.secs_since_last_kill > COMBO_WINDOW_SECS { self.current_combo = 0; }
+        if kills > 0 {
+            self.kills += kills;
+            self.current_combo += kills;
+            self.highest_combo = self.highest_combo.max(self.current_combo);
+            self.secs_since_last_kill = 0.0;
+        }
+        self.damage_taken += damage_taken;
+    }
+
+    pub fn record_damage(&mut self, origin: DamageOrigin, amount: i32) {
+        let amount = amount.max(0) as u32;
+        self.damage_dealt += amount;
+        let DamageOrigin::Summon(kind) = origin else { return; };
+        match self.summon_damage.iter_mut().find(|(summon, _)| *summon == kind) {
+            Some((_, total)) => *total += amount,
+            None => self.summon_damage.push((kind, amount)),
+        }
+    }
+
+    pub fn total_summon_damage(&self) -> u32 {
+        self.summon_damage.iter().map(|(_, amount)| amount).sum()
+    }
+
+    // Cheated runs are still graded and paid out, but never set a weekly best or unlock corruption, and are marked in the run history
+    pub fn counts_for_records(&self) -> bool { !self.cheats_used }
+
+    pub fn kills_per_minute(&self, elapsed_secs: f32) -> f32 {
+        self.kills as f32 / (elapsed_secs / 60.0).max(MIN_GRADED_MINUTES)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct GradeThreshold {
+    pub grade: RunGrade,
+    pub min_kills_per_minute: f32,
+    pub max_damage_taken: u32,
+    pub min_highest_combo: u32,
+    pub remnant_bonus: u32,
+}
+
+impl GradeThreshold {
+    fn is_met_by(&self, stats: &RunStats, elapsed_secs: f32) -> bool {
+        stats.kills_per_minute(elapsed_secs) >= self.min_kills_per_minute && stats.damage_taken <= self.max_damage_taken && stats.highest_combo >= self.min_highest_combo
+    }
+}
+
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct GradeThresholds {
+    pub grades: Vec<GradeThreshold>,
+}
+
+impl GradeThresholds {
+    pub fn from_ron_str(source: &str) -> Result<Self, GradeThresholdsError> {
+        let thresholds: GradeThresholds = ron::de::from_str(source)?;
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    pub fn validate(&self) -> Result<(), GradeThresholdsError> {
+        if self.grades.is_empty() { return Err(GradeThresholdsError::Empty); }
+        for (i, threshold) in self.grades.iter().enumerate() {
+            if self.grades[..i].iter().any(|earlier| earlier.grade == threshold.grade) { return Err(GradeThresholdsError::DuplicateGrade(threshold.grade)); }
+            if threshold.min_kills_per_minute < 0.0 { return Err(GradeThresholdsError::InvalidValue { grade: threshold.grade, field: "min_kills_per_minute" }); }
+        }
+        Ok(())
+    }
+
+    pub fn grade_for(&self, stats: &RunStats, elapsed_secs: f32) -> &GradeThreshold {
+        self.grades.iter().find(|threshold| threshold.is_met_by(stats, elapsed_secs)).unwrap_or_else(|| self.grades.last().expect("grade thresholds are validated as non-empty"))
+    }
+}
+
+#[derive(Debug)]
+pub enum GradeThresholdsError {
+    Parse(ron::error::SpannedError),
+    Empty,
+    DuplicateGrade(RunGrade),
+    InvalidValue { grade: RunGrade, field: &'static str },
+}
+
+impl std::fmt::Display for GradeThresholdsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GradeThresholdsError::Parse(error) => write!(f, "could not parse grade thresholds: {}", error),
+            GradeThresholdsError::Empty => write!(f, "no grades are defined"),
+            GradeThresholdsError::DuplicateGrade(grade) => write!(f, "grade {:?} is listed more than once", grade),
+            GradeThresholdsError::InvalidValue { grade, field } => write!(f, "grade {:?} has an invalid {}", grade, field),
+        }
+    }
+}
+
+impl std::error::Error for GradeThresholdsError {}
+
+impl From<ron::error::SpannedError> for GradeThresholdsError {
+    fn from(error: ron::error::SpannedError) -> Self { GradeThresholdsError::Parse(error) }
+}
+
+// Meta currency; unlike Gold it carries over between runs
+#[derive(Resource, Default, Debug)]
+pub struct Remnants(pub u32);
+
+// Result of the last finished run, shown on the results screen
+#[derive(Resource, Debug, Clone)]
+pub struct LastRunGrade {
+    pub grade: RunGrade,
+    pub kills_per_minute: f32,
+    pub remnants_earned: u32,
+}
+
+#[derive(Component)]
+struct RunGradeUI;
+
+fn reset_run_stats(mut run_stats: ResMut<RunStats>) {
+    *run_stats = RunStats::default();
+}
+
+fn run_stats_tracking_system(
+    time: Res<Time>,
+    mut run_stats: ResMut<RunStats>,
+    mut game_events: EventReader<GameEvent>,
+    player_query: Query<&Health, With<Survivor>>,
+    mut last_player_health: Local<Option<i32>>,
+) {
+    let mut kills = 0;
+    for event in game_events.read() {
+        match event {
+            GameEvent::HorrorKilled(_) => kills += 1,
+            GameEvent::DamageDealt { amount, origin, .. } => run_stats.record_damage(*origin, *amount),
+            _ => {}
+        }
+    }
+    let current_health = player_query.get_single().ok().map(|health| health.0);
+    let damage_taken = match (*last_player_health, current_health) {
+        (Some(previous), Some(current)) if current < previous => (previous - current) as u32,
+        _ => 0,
+    };
+    *last_player_health = current_health;
+    run_stats.record(time.delta_seconds(), kills, damage_taken);
+}
+
+pub fn grade_finished_run(
+    mut commands: Commands,
+    run_stats: Res<RunStats>,
+    thresholds: Res<GradeThresholds>,
+    game_state: Res<GameState>,
+    hardcore: Res<HardcoreMode>,
+    outcome: Res<RunOutcome>,
+    mut remnants: ResMut<Remnants>,
+    mut unbanked: ResMut<UnbankedRemnants>,
+) {
+    let elapsed_secs = game_state.game_timer.elapsed_secs();
+    let threshold = thresholds.grade_for(&run_stats, elapsed_secs);
+    // Hardcore earnings go through the unbanked pool; only an extraction moves any of it into Remnants
+    let banked = if hardcore.enabled {
+        let settlement = settle_hardcore_run(unbanked.0, threshold.remnant_bonus, *outcome);
+        unbanked.0 = settlement.unbanked;
+        commands.insert_resource(settlement);
+        settlement.banked
+    } else {
+        threshold.remnant_bonus
+    };
+    remnants.0 = remnants.0.saturating_add(banked);
+    commands.insert_resource(LastRunGrade { grade: threshold.grade, kills_per_minute: run_stats.kills_per_minute(elapsed_secs), remnants_earned: banked });
+}
+
+fn setup_run_grade_ui(mut commands: Commands, asset_server: Res<AssetServer>, last_grade: Option<Res<LastRunGrade>>, run_stats: Res<RunStats>, remnants: Res<Remnants>) {
+    let Some(last_grade) = last_grade else { return; };
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle { style: Style { position_type: PositionType::Absolute, bottom: Val::Px(40.0), width: Val::Percent(100.0), flex_direction: FlexDirection::Column, align_items: AlignItems::Center, row_gap: Val::Px(8.0), ..default() }, ..default() },
+        RunGradeUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(format!("Grade: {:?}", last_grade.grade), TextStyle { font: font.clone(), font_size: 60.0, color: last_grade.grade.color() }));
+        parent.spawn(TextBundle::from_section(
+            format!("Kills/min: {:.1}   Damage taken: {}   Best combo: {}", last_grade.kills_per_minute, run_stats.damage_taken, run_stats.highest_combo),
+            TextStyle { font: font.clone(), font_size: 24.0, color: Color::rgb(0.85, 0.85, 0.85) },
+        ));
+        if !run_stats.summon_damage.is_empty() {
+            let breakdown = run_stats.summon_damage.iter().map(|(kind, amount)| format!("{} {}", kind.name(), amount)).collect::<Vec<_>>().join("   ");
+            parent.spawn(TextBundle::from_section(
+                format!("Summons dealt {} of {} damage: {}", run_stats.total_summon_damage(), run_stats.damage_dealt, breakdown),
+                TextStyle { font: font.clone(), font_size: 20.0, color: Color::rgb(0.75, 0.75, 0.85) },
+            ));
+        }
+        parent.spawn(TextBundle::from_section(format!("Remnants +{} (total {})", last_grade.remnants_earned, remnants.0), TextStyle { font: font.clone(), font_size: 24.0, color: Color::rgb(0.7, 0.6, 1.0) }));
+        if !run_stats.counts_for_records() {
+            parent.spawn(TextBundle::from_section("Debug tools were used - this run is kept off the records", TextStyle { font, font_size: 20.0, color: Color::ORANGE_RED }));
+        }
+    });
+}
+
+fn despawn_run_grade_ui(mut commands: Commands, ui_query: Query<Entity, With<RunGradeUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
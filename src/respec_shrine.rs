@@ -0,0 +1,295 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::{
+    survivor::{Survivor, SURVIVOR_SIZE},
+    components::Health,
+    game::{AppState, GameRng, PendingLevelUps, on_enter_pause_like_state_actions, on_enter_ingame_state_actions},
+    gold::Gold,
+    run_history::UpgradeLog,
+    upgrades::{UpgradeCard, UpgradePool, UpgradeType},
+    audio::{PlaySoundEvent, SoundEffect},
+};
+
+pub const RESPEC_SHRINE_SIZE: Vec2 = Vec2::new(36.0, 36.0);
+pub const RESPEC_SHRINE_INTERVAL_SECS: f32 = 150.0;
+pub const RESPEC_SHRINE_LIFETIME_SECS: f32 = 45.0;
+pub const MAX_RESPEC_REFUNDS: usize = 3;
+pub const RESPEC_GOLD_PER_PICK: u32 = 20;
+pub const RESPEC_LEAVE_KEY: KeyCode = KeyCode::Escape;
+const RESPEC_SHRINE_TOUCH_RADIUS: f32 = SURVIVOR_SIZE.x / 2.0 + RESPEC_SHRINE_SIZE.x / 2.0;
+const RESPEC_SHRINE_MIN_DISTANCE: f32 = 250.0;
+const RESPEC_SHRINE_MAX_DISTANCE: f32 = 400.0;
+const SHRINE_COLOR: Color = Color::rgb(0.6, 0.4, 0.9);
+const OPTION_COLOR: Color = Color::GRAY;
+const SELECTED_OPTION_COLOR: Color = Color::rgb(0.55, 0.45, 0.1);
+const UNAFFORDABLE_OPTION_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
+
+pub struct RespecShrinePlugin;
+
+impl Plugin for RespecShrinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RespecShrineTimer>()
+            .init_resource::<RespecSelection>()
+            .add_systems(OnExit(AppState::MainMenu), reset_respec_shrine_timer)
+            .add_systems(Update, (spawn_respec_shrine_system, respec_shrine_lifetime_system, respec_shrine_touch_system).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::RespecShrine), (setup_respec_shrine_ui, on_enter_pause_like_state_actions))
+            .add_systems(Update, (handle_respec_buttons, update_respec_ui).chain().run_if(in_state(AppState::RespecShrine)))
+            .add_systems(OnExit(AppState::RespecShrine), (despawn_respec_shrine_ui, on_enter_ingame_state_actions))
+            .add_systems(OnEnter(AppState::GameOver), despawn_respec_shrines)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_respec_shrines);
+    }
+}
+
+#[derive(Resource)]
+pub struct RespecShrineTimer(pub Timer);
+
+impl Default for RespecShrineTimer {
+    fn default() -> Self { Self(Timer::from_seconds(RESPEC_SHRINE_INTERVAL_SECS, TimerMode::Repeating)) }
+}
+
+// Lingers for a while, then crumbles; after the player walks off without using it, it waits for them to step away before opening again
+#[derive(Component)]
+pub struct RespecShrine {
+    pub lifetime: Timer,
+    pub armed: bool,
+}
+
+// How many of the latest picks the player means to refund; zero until one of the options is clicked
+#[derive(Resource, Default, Debug)]
+pub struct RespecSelection(pub usize);
+
+// Stats an upgrade only nudges up or down can be put back exactly; grants, weapons, capped stats and skill upgrades can't
+pub fn is_refundable(upgrade_type: &UpgradeType) -> bool {
+    matches!(upgrade_type,
+        UpgradeType::SurvivorSpeed(_) | UpgradeType::MaxEndurance(_) | UpgradeType::IchorBlastIntensity(_) | UpgradeType::IchorBlastVelocity(_)
+        | UpgradeType::IchorBlastPiercing(_) | UpgradeType::EchoesGainMultiplier(_) | UpgradeType::SoulAttractionRadius(_) | UpgradeType::AdditionalIchorBlasts(_)
+        | UpgradeType::AreaMultiplier(_) | UpgradeType::Luck(_) | UpgradeType::DurationMultiplier(_) | UpgradeType::EnduranceRegeneration(_))
+}
+
+// The inverse of what apply_chosen_upgrade did for the card; multipliers divide back out, so items bought since are left alone
+pub fn revert_upgrade(upgrade_type: &UpgradeType, survivor: &mut Survivor, health: &mut Health) -> bool {
+    let undo_percent = |value: &mut f32, percentage: u32| *value /= 1.0 + percentage as f32 / 100.0;
+    match upgrade_type {
+        UpgradeType::SurvivorSpeed(percentage) => undo_percent(&mut survivor.speed, *percentage),
+        UpgradeType::MaxEndurance(amount) => {
+            survivor.max_health -= *amount;
+            health.0 = health.0.min(survivor.max_health).max(1);
+        }
+        UpgradeType::IchorBlastIntensity(amount) => survivor.ichor_blast_damage_bonus -= *amount,
+        UpgradeType::IchorBlastVelocity(percentage) => undo_percent(&mut survivor.ichor_blast_speed_multiplier, *percentage),
+        UpgradeType::IchorBlastPiercing(amount) => survivor.ichor_blast_piercing = survivor.ichor_blast_piercing.saturating_sub(*amount),
+        UpgradeType::EchoesGainMultiplier(percentage) => undo_percent(&mut survivor.xp_gain_multiplier, *percentage),
+        UpgradeType::SoulAttractionRadius(percentage) => undo_percent(&mut survivor.pickup_radius_multiplier, *percentage),
+        UpgradeType::AdditionalIchorBlasts(amount) => survivor.additional_ichor_blasts = survivor.additional_ichor_blasts.saturating_sub(*amount),
+        UpgradeType::AreaMultiplier(percentage) => undo_percent(&mut survivor.area_multiplier, *percentage),
+        UpgradeType::Luck(percentage) => survivor.luck -= *percentage as f32 / 100.0,
+        UpgradeType::DurationMultiplier(percentage) => undo_percent(&mut survivor.duration_multiplier, *percentage),
+        UpgradeType::EnduranceRegeneration(amount) => survivor.health_regen_rate -= *amount,
+        _ => return false,
+    }
+    true
+}
+
+// The latest picks, newest first, up to the first one that can't be refunded
+pub fn refundable_picks<'a>(upgrade_log: &UpgradeLog, cards: &'a [UpgradeCard]) -> Vec<&'a UpgradeCard> {
+    upgrade_log.0.iter().rev()
+        .map(|upgrade_id| cards.iter().find(|card| card.id == *upgrade_id))
+        .take_while(|card| card.is_some_and(|card| is_refundable(&card.upgrade_type)))
+        .flatten()
+        .take(MAX_RESPEC_REFUNDS)
+        .collect()
+}
+
+pub fn respec_cost(refunds: usize) -> u32 {
+    RESPEC_GOLD_PER_PICK * refunds as u32
+}
+
+#[derive(Component)]
+struct RespecShrineUI;
+
+#[derive(Component, Clone, Copy)]
+enum RespecButton {
+    Refund(usize),
+    Confirm,
+    Leave,
+}
+
+#[derive(Component)]
+struct RespecSummaryText;
+
+fn reset_respec_shrine_timer(mut shrine_timer: ResMut<RespecShrineTimer>) {
+    shrine_timer.0.reset();
+}
+
+fn spawn_respec_shrine_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut shrine_timer: ResMut<RespecShrineTimer>,
+    shrine_query: Query<(), With<RespecShrine>>,
+    player_query: Query<&Transform, With<Survivor>>,
+    upgrade_log: Res<UpgradeLog>,
+    upgrade_pool: Res<UpgradePool>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !shrine_timer.0.tick(time.delta()).just_finished() || !shrine_query.is_empty() { return; }
+    // A shrine with nothing to refund would only be a detour
+    if refundable_picks(&upgrade_log, &upgrade_pool.available_upgrades).is_empty() { return; }
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let rng = &mut game_rng.0;
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let distance = rng.gen_range(RESPEC_SHRINE_MIN_DISTANCE..RESPEC_SHRINE_MAX_DISTANCE);
+    let position = player_transform.translation.truncate() + Vec2::from_angle(angle) * distance;
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/respec_shrine_placeholder.png"),
+            sprite: Sprite { custom_size: Some(RESPEC_SHRINE_SIZE), color: SHRINE_COLOR, ..default() },
+            transform: Transform::from_translation(position.extend(0.4)),
+            ..default()
+        },
+        RespecShrine { lifetime: Timer::from_seconds(RESPEC_SHRINE_LIFETIME_SECS, TimerMode::Once), armed: true },
+        Name::new("RespecShrine"),
+    ));
+}
+
+fn respec_shrine_lifetime_system(mut commands: Commands, time: Res<Time>, mut shrine_query: Query<(Entity, &mut RespecShrine, &mut Sprite)>) {
+    for (entity, mut shrine, mut sprite) in shrine_query.iter_mut() {
+        if shrine.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        // Fades over its last quarter so the player can tell it's about to go
+        sprite.color.set_a((shrine.lifetime.remaining_secs() / (RESPEC_SHRINE_LIFETIME_SECS * 0.25)).clamp(0.2, 1.0));
+    }
+}
+
+fn respec_shrine_touch_system(
+    player_query: Query<&Transform, With<Survivor>>,
+    mut shrine_query: Query<(&Transform, &mut RespecShrine)>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    for (shrine_transform, mut shrine) in shrine_query.iter_mut() {
+        let touching = player_pos.distance(shrine_transform.translation.truncate()) < RESPEC_SHRINE_TOUCH_RADIUS;
+        if !touching { shrine.armed = true; continue; }
+        if !shrine.armed { continue; }
+        shrine.armed = false;
+        next_app_state.set(AppState::RespecShrine);
+    }
+}
+
+fn setup_respec_shrine_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    upgrade_log: Res<UpgradeLog>,
+    upgrade_pool: Res<UpgradePool>,
+    mut selection: ResMut<RespecSelection>,
+) {
+    selection.0 = 0;
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let picks = refundable_picks(&upgrade_log, &upgrade_pool.available_upgrades);
+    commands.spawn((
+        NodeBundle {
+            style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), position_type: PositionType::Absolute, justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(14.0), ..default() },
+            background_color: Color::rgba(0.1, 0.1, 0.2, 0.9).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        RespecShrineUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Shrine of Unmaking", TextStyle { font: font.clone(), font_size: 44.0, color: SHRINE_COLOR }));
+        parent.spawn(TextBundle::from_section("Give up your latest revelations and choose them anew", TextStyle { font: font.clone(), font_size: 18.0, color: Color::rgb(0.8, 0.8, 1.0) }));
+        if picks.is_empty() {
+            parent.spawn(TextBundle::from_section("Nothing here can be unmade", TextStyle { font: font.clone(), font_size: 22.0, color: Color::WHITE }));
+        }
+        for refunds in 1..=picks.len() {
+            let names: Vec<&str> = picks[..refunds].iter().map(|card| card.name.as_str()).collect();
+            spawn_respec_button(parent, &font, RespecButton::Refund(refunds), format!("Unmake {} ({} gold)", names.join(", "), respec_cost(refunds)));
+        }
+        parent.spawn((TextBundle::from_section("", TextStyle { font: font.clone(), font_size: 20.0, color: Color::rgb(0.7, 1.0, 0.7) }), RespecSummaryText));
+        parent.spawn(NodeBundle { style: Style { column_gap: Val::Px(20.0), ..default() }, ..default() }).with_children(|row| {
+            spawn_respec_button(row, &font, RespecButton::Confirm, "Confirm".to_string());
+            spawn_respec_button(row, &font, RespecButton::Leave, "Leave (Esc)".to_string());
+        });
+    });
+}
+
+fn spawn_respec_button(parent: &mut ChildBuilder, font: &Handle<Font>, button: RespecButton, label: String) {
+    let width = if matches!(button, RespecButton::Refund(_)) { Val::Px(560.0) } else { Val::Px(180.0) };
+    parent.spawn((
+        ButtonBundle { style: Style { width, min_height: Val::Px(44.0), padding: UiRect::axes(Val::Px(10.0), Val::Px(6.0)), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() }, background_color: OPTION_COLOR.into(), ..default() },
+        button,
+    )).with_children(|button| {
+        button.spawn(TextBundle::from_section(label, TextStyle { font: font.clone(), font_size: 20.0, color: Color::WHITE }));
+    });
+}
+
+fn handle_respec_buttons(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    interaction_query: Query<(&Interaction, &RespecButton), (Changed<Interaction>, With<Button>)>,
+    mut selection: ResMut<RespecSelection>,
+    mut player_query: Query<(&mut Survivor, &mut Health)>,
+    mut upgrade_log: ResMut<UpgradeLog>,
+    upgrade_pool: Res<UpgradePool>,
+    mut gold: ResMut<Gold>,
+    mut pending_level_ups: ResMut<PendingLevelUps>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    shrine_query: Query<Entity, With<RespecShrine>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    if keyboard_input.just_pressed(RESPEC_LEAVE_KEY) { next_app_state.set(AppState::InGame); return; }
+    let Some(pressed) = interaction_query.iter().find(|(interaction, _)| **interaction == Interaction::Pressed).map(|(_, button)| *button) else { return; };
+    match pressed {
+        RespecButton::Refund(refunds) => selection.0 = refunds,
+        RespecButton::Leave => next_app_state.set(AppState::InGame),
+        RespecButton::Confirm => {
+            let refunds = selection.0;
+            if refunds == 0 || !gold.try_spend(respec_cost(refunds)) { return; }
+            let Ok((mut survivor, mut health)) = player_query.get_single_mut() else { return; };
+            let picks: Vec<UpgradeCard> = refundable_picks(&upgrade_log, &upgrade_pool.available_upgrades).into_iter().take(refunds).cloned().collect();
+            for card in picks.iter() { revert_upgrade(&card.upgrade_type, &mut survivor, &mut health); }
+            let kept = upgrade_log.0.len() - picks.len();
+            upgrade_log.0.truncate(kept);
+            // Every refunded pick comes back as a level-up, which opens as soon as play resumes
+            pending_level_ups.0 += picks.len() as u32;
+            // A shrine answers once
+            for shrine in shrine_query.iter() { commands.entity(shrine).despawn_recursive(); }
+            sound_event_writer.send(PlaySoundEvent::global(SoundEffect::OmenAccepted));
+            next_app_state.set(AppState::InGame);
+        }
+    }
+}
+
+fn update_respec_ui(
+    selection: Res<RespecSelection>,
+    gold: Res<Gold>,
+    mut button_query: Query<(&RespecButton, &mut BackgroundColor)>,
+    mut summary_query: Query<&mut Text, With<RespecSummaryText>>,
+) {
+    for (button, mut background) in button_query.iter_mut() {
+        let color = match button {
+            RespecButton::Refund(refunds) if respec_cost(*refunds) > gold.0 => UNAFFORDABLE_OPTION_COLOR,
+            RespecButton::Refund(refunds) if *refunds == selection.0 => SELECTED_OPTION_COLOR,
+            RespecButton::Confirm if selection.0 == 0 || respec_cost(selection.0) > gold.0 => UNAFFORDABLE_OPTION_COLOR,
+            _ => OPTION_COLOR,
+        };
+        if background.0 != color { background.0 = color; }
+    }
+    let Ok(mut text) = summary_query.get_single_mut() else { return; };
+    let summary = match selection.0 {
+        0 => format!("You carry {} gold", gold.0),
+        refunds if respec_cost(refunds) > gold.0 => format!("You carry {} gold; that needs {}", gold.0, respec_cost(refunds)),
+        refunds => format!("Pay {} of your {} gold and choose {} upgrade{} again", respec_cost(refunds), gold.0, refunds, if refunds == 1 { "" } else { "s" }),
+    };
+    if text.sections[0].value != summary { text.sections[0].value = summary; }
+}
+
+fn despawn_respec_shrine_ui(mut commands: Commands, ui_query: Query<Entity, With<RespecShrineUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn despawn_respec_shrines(mut commands: Commands, shrine_query: Query<Entity, With<RespecShrine>>) {
+    for entity in shrine_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
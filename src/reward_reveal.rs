@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use crate::{
+    survivor::{Survivor, SURVIVOR_SIZE},
+    game::{AppState, GameRng, ItemCollectedEvent, on_enter_pause_like_state_actions, on_enter_ingame_state_actions},
+    game_events::GameEvent,
+    items::{ItemId, ItemLibrary},
+    luck::roll_with_luck,
+    audio::{PlaySoundEvent, SoundEffect},
+    score::{ScoreAwardEvent, ScoreSource, RELIQUARY_FOUND_BONUS},
+};
+
+pub const RELIQUARY_SIZE: Vec2 = Vec2::new(28.0, 28.0);
+pub const REVEAL_SPIN_SECS: f32 = 2.2;
+pub const REVEAL_SKIP_KEY: KeyCode = KeyCode::Space;
+const RELIQUARY_ELITE_DROP_CHANCE: f64 = 0.3;
+const RELIQUARY_PICKUP_RADIUS: f32 = SURVIVOR_SIZE.x / 2.0 + RELIQUARY_SIZE.x / 2.0;
+const REVEAL_SPIN_LAPS: usize = 4;
+const REVEAL_HOLD_SECS: f32 = 1.5;
+const REVEAL_MIN_CANDIDATES: usize = 2;
+const REVEAL_MAX_CANDIDATES: usize = 3;
+const HIGHLIGHT_COLOR: Color = Color::rgb(0.9, 0.75, 0.3);
+const DIM_COLOR: Color = Color::rgb(0.35, 0.35, 0.4);
+
+pub struct RewardRevealPlugin;
+
+impl Plugin for RewardRevealPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RewardRng>()
+            .add_systems(OnExit(AppState::MainMenu), reseed_reward_rng)
+            .add_systems(Update, (
+                reliquary_drop_on_elite_death_system,
+                reliquary_pickup_system,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::RewardReveal), (setup_reward_reveal_ui, on_enter_pause_like_state_actions))
+            .add_systems(Update, (advance_reward_reveal, update_reward_reveal_ui).chain().run_if(in_state(AppState::RewardReveal)))
+            .add_systems(OnExit(AppState::RewardReveal), (despawn_reward_reveal_ui, on_enter_ingame_state_actions))
+            .add_systems(OnEnter(AppState::GameOver), despawn_reliquaries)
+            .add_systems(OnEnter(AppState::MainMenu), despawn_reliquaries);
+    }
+}
+
+// Stand-in for a rare chest: elites sometimes leave one behind
+#[derive(Component)]
+pub struct Reliquary;
+
+// Every reliquary of a run draws from one seed, so the same seed always reveals the same rewards in the same order
+#[derive(Resource, Debug, Default)]
+pub struct RewardRng {
+    pub seed: u64,
+    pub opened: u64,
+}
+
+impl RewardRng {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, opened: 0 }
+    }
+
+    pub fn next_rng(&mut self) -> StdRng {
+        let rng = StdRng::seed_from_u64(self.seed.wrapping_add(self.opened.wrapping_mul(0x9E37_79B9_7F4A_7C15)));
+        self.opened += 1;
+        rng
+    }
+}
+
+// The outcome is fixed when the reliquary opens; the spin only decides how long it takes to show it
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct RewardReveal {
+    pub candidates: Vec<ItemId>,
+    pub landed: usize,
+    pub elapsed_secs: f32,
+}
+
+impl RewardReveal {
+    pub fn roll<R: Rng>(rng: &mut R, pool: &[ItemId]) -> Option<Self> {
+        if pool.is_empty() { return None; }
+        let count = rng.gen_range(REVEAL_MIN_CANDIDATES..=REVEAL_MAX_CANDIDATES).min(pool.len());
+        let candidates: Vec<ItemId> = pool.choose_multiple(rng, count).copied().collect();
+        let landed = rng.gen_range(0..candidates.len());
+        Some(Self { candidates, landed, elapsed_secs: 0.0 })
+    }
+
+    // Steps through the candidates with ever longer pauses, ending exactly on the landed one
+    pub fn highlighted(&self) -> usize {
+        let total_steps = self.candidates.len() * REVEAL_SPIN_LAPS + self.landed;
+        let progress = (self.elapsed_secs / REVEAL_SPIN_SECS).clamp(0.0, 1.0);
+        let step = ((total_steps as f32 * progress.sqrt()) as usize).min(total_steps);
+        step % self.candidates.len()
+    }
+
+    pub fn has_landed(&self) -> bool {
+        self.elapsed_secs >= REVEAL_SPIN_SECS
+    }
+
+    pub fn skip(&mut self) {
+        self.elapsed_secs = self.elapsed_secs.max(REVEAL_SPIN_SECS);
+    }
+
+    pub fn landed_item(&self) -> ItemId {
+        self.candidates[self.landed]
+    }
+}
+
+#[derive(Component)]
+struct RewardRevealUI;
+
+#[derive(Component)]
+struct RevealCandidateText(usize);
+
+#[derive(Component)]
+struct RevealPromptText;
+
+fn reseed_reward_rng(mut reward_rng: ResMut<RewardRng>, mut game_rng: ResMut<GameRng>) {
+    *reward_rng = RewardRng::new(game_rng.0.gen());
+}
+
+fn reliquary_drop_on_elite_death_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut game_events: EventReader<GameEvent>,
+    player_query: Query<&Survivor>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let luck = player_query.get_single().map_or(0.0, |survivor| survivor.luck);
+    let rng = &mut game_rng.0;
+    for event in game_events.read().filter_map(GameEvent::as_horror_kill) {
+        if !event.is_elite || !roll_with_luck(rng, RELIQUARY_ELITE_DROP_CHANCE, luck) { continue; }
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load("sprites/eldritch_relic_placeholder.png"),
+                sprite: Sprite { custom_size: Some(RELIQUARY_SIZE), color: HIGHLIGHT_COLOR, ..default() },
+                transform: Transform::from_translation(event.position.truncate().extend(0.4)),
+                ..default()
+            },
+            Reliquary,
+            Name::new("Reliquary"),
+        ));
+    }
+}
+
+fn reliquary_pickup_system(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Survivor>>,
+    reliquary_query: Query<(Entity, &Transform), With<Reliquary>>,
+    item_library: Res<ItemLibrary>,
+    mut reward_rng: ResMut<RewardRng>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut award_writer: EventWriter<ScoreAwardEvent>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let Some((reliquary_entity, _)) = reliquary_query.iter().find(|(_, transform)| player_pos.distance(transform.translation.truncate()) < RELIQUARY_PICKUP_RADIUS) else { return; };
+    commands.entity(reliquary_entity).despawn_recursive();
+    award_writer.send(ScoreAwardEvent { source: ScoreSource::ReliquaryFound, points: RELIQUARY_FOUND_BONUS, position: player_transform.translation });
+
+    let pool: Vec<ItemId> = item_library.items.iter().map(|item_def| item_def.id).collect();
+    let Some(reveal) = RewardReveal::roll(&mut reward_rng.next_rng(), &pool) else { return; };
+    sound_event_writer.send(PlaySoundEvent::global(SoundEffect::ReliquaryOpen));
+    commands.insert_resource(reveal);
+    next_app_state.set(AppState::RewardReveal);
+}
+
+fn setup_reward_reveal_ui(mut commands: Commands, asset_server: Res<AssetServer>, reveal: Res<RewardReveal>, item_library: Res<ItemLibrary>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle {
+            style: Style { width: Val::Percent(100.0), height: Val::Percent(100.0), justify_content: JustifyContent::Center, align_items: AlignItems::Center, flex_direction: FlexDirection::Column, row_gap: Val::Px(24.0), position_type: PositionType::Absolute, ..default() },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.75).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        RewardRevealUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("The reliquary creaks open...", TextStyle { font: font.clone(), font_size: 40.0, color: Color::WHITE }));
+        parent.spawn(NodeBundle { style: Style { flex_direction: FlexDirection::Row, column_gap: Val::Px(40.0), ..default() }, ..default() }).with_children(|row| {
+            for (index, item_id) in reveal.candidates.iter().enumerate() {
+                let name = item_library.get_item_definition(*item_id).map_or_else(|| "Unknown relic".to_string(), |item_def| item_def.name.clone());
+                row.spawn((TextBundle::from_section(name, TextStyle { font: font.clone(), font_size: 26.0, color: DIM_COLOR }), RevealCandidateText(index)));
+            }
+        });
+        parent.spawn((TextBundle::from_section("", TextStyle { font, font_size: 20.0, color: Color::GRAY }), RevealPromptText));
+    });
+}
+
+fn advance_reward_reveal(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut reveal: ResMut<RewardReveal>,
+    mut item_collected_writer: EventWriter<ItemCollectedEvent>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let was_landed = reveal.has_landed();
+    reveal.elapsed_secs += time.delta_seconds();
+    if keyboard_input.just_pressed(REVEAL_SKIP_KEY) {
+        if !was_landed { reveal.skip(); } else { reveal.elapsed_secs = REVEAL_SPIN_SECS + REVEAL_HOLD_SECS; }
+    }
+    if !was_landed && reveal.has_landed() { sound_event_writer.send(PlaySoundEvent::global(SoundEffect::ItemPickup)); }
+    if reveal.elapsed_secs >= REVEAL_SPIN_SECS + REVEAL_HOLD_SECS {
+        item_collected_writer.send(ItemCollectedEvent(reveal.landed_item()));
+        game_event_writer.send(GameEvent::ReliquaryOpened { item_id: reveal.landed_item() });
+        next_app_state.set(AppState::InGame);
+    }
+}
+
+fn update_reward_reveal_ui(
+    reveal: Res<RewardReveal>,
+    mut candidate_query: Query<(&RevealCandidateText, &mut Text), Without<RevealPromptText>>,
+    mut prompt_query: Query<&mut Text, With<RevealPromptText>>,
+) {
+    let highlighted = reveal.highlighted();
+    for (candidate, mut text) in candidate_query.iter_mut() {
+        if let Some(section) = text.sections.get_mut(0) {
+            section.style.color = if candidate.0 == highlighted { HIGHLIGHT_COLOR } else { DIM_COLOR };
+        }
+    }
+    if let Ok(mut prompt) = prompt_query.get_single_mut() {
+        if let Some(section) = prompt.sections.get_mut(0) {
+            section.value = if reveal.has_landed() { "Press Space to claim".to_string() } else { "Press Space to skip".to_string() };
+        }
+    }
+}
+
+fn despawn_reward_reveal_ui(mut commands: Commands, ui_query: Query<Entity, With<RewardRevealUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+    commands.remove_resource::<RewardReveal>();
+}
+
+fn despawn_reliquaries(mut commands: Commands, reliquary_query: Query<Entity, With<Reliquary>>) {
+    for entity in reliquary_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{
+    survivor::Survivor,
+    game::{AppState, GameState, UpgradeChosenEvent},
+    upgrades::UpgradeId,
+    platform::{backup_key, GameStorage, Storage, SystemClipboard},
+    simulation::SimulationConfig,
+    ranking::RunStats,
+};
+
+pub const RUN_HISTORY_PATH: &str = "run_history.ron";
+pub const BUILD_EXPORT_PATH: &str = "build_export.txt";
+pub const MAX_RUN_HISTORY: usize = 50;
+pub const EXPORT_BUILD_KEY: KeyCode = KeyCode::KeyE;
+const BUILD_CODE_PREFIX: &str = "CG1";
+const EMPTY_GLYPH_SOCKET: &str = "_";
+
+pub struct RunHistoryPlugin;
+
+impl Plugin for RunHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UpgradeLog>()
+            .init_resource::<CurrentBuild>()
+            .add_systems(OnExit(AppState::MainMenu), reset_build_tracking)
+            .add_systems(Update, record_chosen_upgrades.run_if(on_event::<UpgradeChosenEvent>()))
+            .add_systems(Update, snapshot_current_build.run_if(in_state(AppState::InGame)))
+            // Headless simulations reach the results screen too, but they are not the player's runs
+            .add_systems(OnEnter(AppState::GameOver), (save_finished_run.run_if(not(resource_exists::<SimulationConfig>)), setup_build_export_ui).chain())
+            .add_systems(Update, build_export_interaction.run_if(in_state(AppState::GameOver)))
+            .add_systems(OnExit(AppState::GameOver), despawn_build_export_ui);
+    }
+}
+
+// Every upgrade card taken this run, in the order it was picked
+#[derive(Resource, Default, Debug)]
+pub struct UpgradeLog(pub Vec<UpgradeId>);
+
+impl UpgradeLog {
+    // Each pick in order, alongside how many copies of that card the run held once it was taken
+    pub fn picks_with_stacks(&self) -> Vec<(UpgradeId, usize)> {
+        self.0.iter().enumerate().map(|(index, upgrade_id)| (*upgrade_id, self.0[..=index].iter().filter(|earlier| *earlier == upgrade_id).count())).collect()
+    }
+}
+
+// The survivor is despawned before the results screen opens, so its build is kept here while the run is live
+#[derive(Resource, Default, Debug)]
+pub struct CurrentBuild(pub BuildCode);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SkillLoadout {
+    pub skill_id: u32,
+    pub glyphs: Vec<Option<u32>>,
+}
+
+// A whole build as plain ids, small enough to share as a single line of text
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BuildCode {
+    pub skills: Vec<SkillLoadout>,
+    pub items: Vec<u32>,
+    pub glyphs: Vec<u32>,
+    pub upgrades: Vec<u32>,
+}
+
+impl BuildCode {
+    pub fn from_survivor(survivor: &Survivor, upgrade_log: &UpgradeLog) -> Self {
+        Self {
+            skills: survivor.equipped_skills.iter().map(|skill| SkillLoadout {
+                skill_id: skill.definition_id.0,
+                glyphs: skill.equipped_glyphs.iter().map(|glyph| glyph.map(|glyph_id| glyph_id.0)).collect(),
+            }).collect(),
+            items: survivor.collected_item_ids.iter().map(|item_id| item_id.0).collect(),
+            glyphs: survivor.collected_glyphs.iter().map(|glyph_id| glyph_id.0).collect(),
+            upgrades: upgrade_log.0.iter().map(|upgrade_id| upgrade_id.0).collect(),
+        }
+    }
+
+    // CG1/<skill[:glyph-glyph]>.../<items>/<glyphs>/<upgrades>, lists separated by '.'
+    pub fn encode(&self) -> String {
+        let skills: Vec<String> = self.skills.iter().map(|loadout| {
+            if loadout.glyphs.is_empty() { return loadout.skill_id.to_string(); }
+            let sockets: Vec<String> = loadout.glyphs.iter().map(|glyph| glyph.map_or_else(|| EMPTY_GLYPH_SOCKET.to_string(), |glyph_id| glyph_id.to_string())).collect();
+            format!("{}:{}", loadout.skill_id, sockets.join("-"))
+        }).collect();
+        format!("{}/{}/{}/{}/{}", BUILD_CODE_PREFIX, skills.join("."), join_ids(&self.items), join_ids(&self.glyphs), join_ids(&self.upgrades))
+    }
+
+    pub fn decode(code: &str) -> Result<Self, BuildCodeError> {
+        let mut sections = code.trim().split('/');
+        if sections.next() != Some(BUILD_CODE_PREFIX) { return Err(BuildCodeError::UnknownFormat); }
+        let mut next_section = |name: &'static str| sections.next().ok_or(BuildCodeError::MissingSection(name));
+        let skills_section = next_section("skills")?;
+        let items = parse_ids(next_section("items")?)?;
+        let glyphs = parse_ids(next_section("glyphs")?)?;
+        let upgrades = parse_ids(next_section("upgrades")?)?;
+        let skills = split_list(skills_section).map(|entry| {
+            let (skill_id, sockets) = entry.split_once(':').unwrap_or((entry, ""));
+            let glyphs = if sockets.is_empty() { Vec::new() } else {
+                sockets.split('-').map(|socket| if socket == EMPTY_GLYPH_SOCKET { Ok(None) } else { parse_id(socket).map(Some) }).collect::<Result<_, _>>()?
+            };
+            Ok(SkillLoadout { skill_id: parse_id(skill_id)?, glyphs })
+        }).collect::<Result<_, BuildCodeError>>()?;
+        Ok(Self { skills, items, glyphs, upgrades })
+    }
+
+    // Whatever the results screen exported last; any run or sandbox can import it
+    pub fn read_exported(storage: &dyn Storage) -> Result<Self, String> {
+        storage.read(BUILD_EXPORT_PATH).map_err(|error| error.to_string()).and_then(|code| Self::decode(&code).map_err(|error| error.to_string()))
+    }
+
+    // A code pasted from the clipboard wins; only when there is no clipboard to read does the exported file stand in
+    pub fn from_paste(pasted: Result<String, String>, storage: &dyn Storage) -> Result<Self, String> {
+        match pasted {
+            Ok(code) => Self::decode(code.trim()).map_err(|error| error.to_string()),
+            Err(_) => Self::read_exported(storage),
+        }
+    }
+}
+
+fn join_ids(ids: &[u32]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(".")
+}
+
+fn split_list(section: &str) -> impl Iterator<Item = &str> {
+    section.split('.').filter(|entry| !entry.is_empty())
+}
+
+fn parse_id(value: &str) -> Result<u32, BuildCodeError> {
+    value.parse().map_err(|_| BuildCodeError::InvalidId(value.to_string()))
+}
+
+fn parse_ids(section: &str) -> Result<Vec<u32>, BuildCodeError> {
+    split_list(section).map(parse_id).collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BuildCodeError {
+    UnknownFormat,
+    MissingSection(&'static str),
+    InvalidId(String),
+}
+
+impl std::fmt::Display for BuildCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildCodeError::UnknownFormat => write!(f, "not a {} build code", BUILD_CODE_PREFIX),
+            BuildCodeError::MissingSection(section) => write!(f, "build code is missing its {} section", section),
+            BuildCodeError::InvalidId(value) => write!(f, "'{}' is not a valid id", value),
+        }
+    }
+}
+
+impl std::error::Error for BuildCodeError {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    pub score: u32,
+    pub cycle_reached: u32,
+    pub survived_secs: f32,
+    pub build: BuildCode,
+    // Runs from before the flag existed load as clean
+    #[serde(default)]
+    pub cheated: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct RunHistory {
+    pub runs: Vec<RunRecord>,
+}
+
+impl RunHistory {
+    pub fn from_ron_str(source: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_str(source)
+    }
+
+    pub fn to_ron_string(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    // Oldest runs fall off once the history is full
+    pub fn push(&mut self, record: RunRecord) {
+        self.runs.push(record);
+        let overflow = self.runs.len().saturating_sub(MAX_RUN_HISTORY);
+        self.runs.drain(..overflow);
+    }
+}
+
+#[derive(Component)]
+struct BuildExportUI;
+
+#[derive(Component)]
+struct ExportBuildButton;
+
+#[derive(Component)]
+struct ExportStatusText;
+
+fn reset_build_tracking(mut upgrade_log: ResMut<UpgradeLog>, mut current_build: ResMut<CurrentBuild>) {
+    upgrade_log.0.clear();
+    current_build.0 = BuildCode::default();
+}
+
+fn record_chosen_upgrades(mut events: EventReader<UpgradeChosenEvent>, mut upgrade_log: ResMut<UpgradeLog>) {
+    upgrade_log.0.extend(events.read().map(|event| event.0.id));
+}
+
+fn snapshot_current_build(player_query: Query<&Survivor, Changed<Survivor>>, upgrade_log: Res<UpgradeLog>, mut current_build: ResMut<CurrentBuild>) {
+    if let Ok(survivor) = player_query.get_single() { current_build.0 = BuildCode::from_survivor(survivor, &upgrade_log); }
+}
+
+// A damaged history falls back to its backup, and starts fresh rather than blocking the results screen
+fn save_finished_run(game_state: Res<GameState>, run_stats: Res<RunStats>, upgrade_log: Res<UpgradeLog>, storage: Res<GameStorage>, mut current_build: ResMut<CurrentBuild>) {
+    current_build.0.upgrades = upgrade_log.0.iter().map(|upgrade_id| upgrade_id.0).collect();
+    let read = |key: &str| storage.0.read(key).ok().and_then(|source| RunHistory::from_ron_str(&source).ok());
+    let mut history = read(RUN_HISTORY_PATH).or_else(|| read(&backup_key(RUN_HISTORY_PATH))).unwrap_or_default();
+    history.push(RunRecord { score: game_state.score, cycle_reached: game_state.cycle_number, survived_secs: game_state.game_timer.elapsed_secs(), build: current_build.0.clone(), cheated: !run_stats.counts_for_records() });
+    match history.to_ron_string() {
+        Ok(serialized) => if let Err(error) = storage.0.write_keeping_backup(RUN_HISTORY_PATH, &serialized) { warn!("Could not save run history to {}: {}", RUN_HISTORY_PATH, error); },
+        Err(error) => warn!("Could not serialize run history: {}", error),
+    }
+}
+
+fn setup_build_export_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle { style: Style { position_type: PositionType::Absolute, top: Val::Px(40.0), right: Val::Px(40.0), flex_direction: FlexDirection::Column, align_items: AlignItems::FlexEnd, row_gap: Val::Px(8.0), max_width: Val::Px(420.0), ..default() }, ..default() },
+        BuildExportUI,
+    )).with_children(|parent| {
+        parent.spawn((
+            ButtonBundle { style: Style { padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)), ..default() }, background_color: Color::GRAY.into(), ..default() },
+            ExportBuildButton,
+        )).with_children(|button| {
+            button.spawn(TextBundle::from_section("Export build (E)", TextStyle { font: font.clone(), font_size: 22.0, color: Color::WHITE }));
+        });
+        parent.spawn((TextBundle::from_section("", TextStyle { font, font_size: 16.0, color: Color::rgb(0.8, 0.8, 0.8) }), ExportStatusText));
+    });
+}
+
+// The code goes to the clipboard; if there is none to reach, it is written next to the run history instead. Either way
+// it is shown on screen
+fn build_export_interaction(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<ExportBuildButton>)>,
+    mut status_query: Query<&mut Text, With<ExportStatusText>>,
+    current_build: Res<CurrentBuild>,
+    storage: Res<GameStorage>,
+    mut clipboard: NonSendMut<SystemClipboard>,
+) {
+    let mut export_requested = keyboard_input.just_pressed(EXPORT_BUILD_KEY);
+    for (interaction, mut bg_color) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => export_requested = true,
+            Interaction::Hovered => *bg_color = Color::DARK_GREEN.into(),
+            Interaction::None => *bg_color = Color::GRAY.into(),
+        }
+    }
+    if !export_requested { return; }
+    let code = current_build.0.encode();
+    let status = match clipboard.copy(&code) {
+        Ok(()) => format!("Copied to clipboard:\n{}", code),
+        Err(clipboard_error) => {
+            warn!("Could not copy the build to the clipboard: {}", clipboard_error);
+            match storage.0.write(BUILD_EXPORT_PATH, &code) {
+                Ok(()) => format!("Saved to {}:\n{}", BUILD_EXPORT_PATH, code),
+                Err(error) => format!("Could not save build: {}\n{}", error, code),
+            }
+        }
+    };
+    if let Ok(mut text) = status_query.get_single_mut() {
+        if let Some(section) = text.sections.get_mut(0) { section.value = status; }
+    }
+}
+
+fn despawn_build_export_ui(mut commands: Commands, ui_query: Query<Entity, With<BuildExportUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,238 @@
+use bevy::{prelude::*, app::AppExit, ecs::system::SystemParam};
+use serde::{Deserialize, Serialize};
+use crate::{
+    background::BackgroundQuality,
+    bestiary::Bestiary,
+    lore::LoreCollection,
+    challenge::{WeeklyBest, WeeklyBestScore},
+    hardcore::{HardcoreMode, UnbankedRemnants},
+    corruption::{Corruption, MAX_CORRUPTION_LEVEL},
+    menu_focus::FocusAnnouncer,
+    platform::{backup_key, GameStorage, Storage},
+    ranking::Remnants,
+    skins::SkinCollection,
+    touch_controls::{TouchControlMode, TouchControls},
+    visual_effects::AfterimageSettings,
+    void_boundary::VoidClosesIn,
+};
+
+pub const SAVE_DATA_PATH: &str = "save_data.ron";
+const CHECKSUM_PREFIX: &str = "// checksum ";
+
+pub struct SaveDataPlugin;
+
+impl Plugin for SaveDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastSavedData>()
+            .add_systems(PreStartup, load_save_data)
+            .add_systems(Last, (autosave_on_change, save_on_exit));
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct SavedSettings {
+    pub background_quality: BackgroundQuality,
+    pub afterimages_enabled: bool,
+    pub focus_announcer_enabled: bool,
+    #[serde(default)]
+    pub hardcore_enabled: bool,
+    #[serde(default)]
+    pub touch_controls: TouchControlMode,
+    #[serde(default)]
+    pub void_closes_in: bool,
+}
+
+// Everything that has to outlive a run: meta currency, discoveries and the player's toggles
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SaveData {
+    pub remnants: u32,
+    #[serde(default)]
+    pub unbanked_remnants: u32,
+    #[serde(default)]
+    pub corruption_level: u32,
+    #[serde(default)]
+    pub corruption_unlocked: u32,
+    pub settings: SavedSettings,
+    // Saves from before weekly challenges have no slot; they load as never having played one
+    #[serde(default)]
+    pub weekly_best: Option<WeeklyBest>,
+    #[serde(default)]
+    pub bestiary: Bestiary,
+    #[serde(default)]
+    pub lore: LoreCollection,
+    #[serde(default)]
+    pub skins: SkinCollection,
+}
+
+impl SaveData {
+    // The body is prefixed with a checksum comment so a torn or hand-mangled file is caught before it is trusted
+    pub fn to_save_string(&self) -> Result<String, SaveError> {
+        let body = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        Ok(format!("{}{:016x}\n{}", CHECKSUM_PREFIX, checksum(&body), body))
+    }
+
+    pub fn from_save_str(source: &str) -> Result<Self, SaveError> {
+        let (header, body) = source.split_once('\n').ok_or(SaveError::Corrupted)?;
+        let expected = header.strip_prefix(CHECKSUM_PREFIX).and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok()).ok_or(SaveError::Corrupted)?;
+        if checksum(body) != expected { return Err(SaveError::Corrupted); }
+        Ok(ron::de::from_str(body)?)
+    }
+}
+
+// FNV-1a; only has to notice damage, not resist tampering
+fn checksum(body: &str) -> u64 {
+    body.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    Corrupted,
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::Parse(error) => write!(f, "could not parse save data: {}", error),
+            SaveError::Serialize(error) => write!(f, "could not serialize save data: {}", error),
+            SaveError::Corrupted => write!(f, "save data failed its checksum"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl SaveError {
+    pub fn is_missing(&self) -> bool { matches!(self, SaveError::Io(error) if error.kind() == std::io::ErrorKind::NotFound) }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(error: std::io::Error) -> Self { SaveError::Io(error) }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(error: ron::error::SpannedError) -> Self { SaveError::Parse(error) }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(error: ron::Error) -> Self { SaveError::Serialize(error) }
+}
+
+pub fn write_save(storage: &dyn Storage, key: &str, data: &SaveData) -> Result<(), SaveError> {
+    storage.write_keeping_backup(key, &data.to_save_string()?)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSource { Primary, Backup, Fresh, }
+
+// Falls back to the backup when the main entry is missing or damaged, and to defaults when both are
+pub fn read_save(storage: &dyn Storage, key: &str) -> (SaveData, SaveSource) {
+    let read = |key: &str| storage.read(key).map_err(SaveError::from).and_then(|source| SaveData::from_save_str(&source));
+    match read(key) {
+        Ok(data) => (data, SaveSource::Primary),
+        Err(primary_error) => {
+            if !primary_error.is_missing() { warn!("Save data at {} is unusable: {}", key, primary_error); }
+            match read(&backup_key(key)) {
+                Ok(data) => (data, SaveSource::Backup),
+                Err(_) => (SaveData::default(), SaveSource::Fresh),
+            }
+        }
+    }
+}
+
+// What is on disk right now; autosave only writes when the live data drifts from it
+#[derive(Resource, Default, Debug)]
+struct LastSavedData(Option<SaveData>);
+
+// Every resource that ends up in the save file
+#[derive(SystemParam)]
+struct SavedResources<'w> {
+    remnants: Res<'w, Remnants>,
+    unbanked_remnants: Res<'w, UnbankedRemnants>,
+    weekly_best: Res<'w, WeeklyBestScore>,
+    hardcore: Res<'w, HardcoreMode>,
+    corruption: Res<'w, Corruption>,
+    bestiary: Res<'w, Bestiary>,
+    lore: Res<'w, LoreCollection>,
+    skins: Res<'w, SkinCollection>,
+    background_quality: Res<'w, BackgroundQuality>,
+    afterimages: Res<'w, AfterimageSettings>,
+    focus_announcer: Res<'w, FocusAnnouncer>,
+    touch_controls: Res<'w, TouchControls>,
+    void_closes_in: Res<'w, VoidClosesIn>,
+}
+
+impl SavedResources<'_> {
+    fn is_changed(&self) -> bool {
+        self.remnants.is_changed() || self.unbanked_remnants.is_changed() || self.weekly_best.is_changed() || self.hardcore.is_changed() || self.corruption.is_changed() || self.bestiary.is_changed() || self.lore.is_changed() || self.skins.is_changed()
+            || self.background_quality.is_changed() || self.afterimages.is_changed() || self.focus_announcer.is_changed() || self.touch_controls.is_changed() || self.void_closes_in.is_changed()
+    }
+
+    fn current(&self) -> SaveData {
+        SaveData {
+            remnants: self.remnants.0,
+            unbanked_remnants: self.unbanked_remnants.0,
+            corruption_level: self.corruption.level,
+            corruption_unlocked: self.corruption.unlocked,
+            settings: SavedSettings { background_quality: *self.background_quality, afterimages_enabled: self.afterimages.enabled, focus_announcer_enabled: self.focus_announcer.enabled, hardcore_enabled: self.hardcore.enabled, touch_controls: self.touch_controls.mode, void_closes_in: self.void_closes_in.enabled },
+            weekly_best: self.weekly_best.0,
+            bestiary: self.bestiary.clone(),
+            lore: self.lore.clone(),
+            skins: self.skins.clone(),
+        }
+    }
+}
+
+fn load_save_data(mut commands: Commands, storage: Res<GameStorage>, mut last_saved: ResMut<LastSavedData>) {
+    let (data, source) = read_save(storage.0.as_ref(), SAVE_DATA_PATH);
+    match source {
+        SaveSource::Primary => {}
+        SaveSource::Backup => {
+            warn!("Restored save data from {}", backup_key(SAVE_DATA_PATH));
+            // Put the good copy back in place straight away instead of waiting for the next change
+            if let Err(error) = write_save(storage.0.as_ref(), SAVE_DATA_PATH, &data) { warn!("Could not restore {}: {}", SAVE_DATA_PATH, error); }
+        }
+        SaveSource::Fresh => info!("No save data found; starting fresh"),
+    }
+    commands.insert_resource(Remnants(data.remnants));
+    commands.insert_resource(UnbankedRemnants(data.unbanked_remnants));
+    commands.insert_resource(HardcoreMode { enabled: data.settings.hardcore_enabled });
+    commands.insert_resource(VoidClosesIn { enabled: data.settings.void_closes_in });
+    // A hand-edited level past what has been unlocked is pulled back down
+    let corruption_unlocked = data.corruption_unlocked.min(MAX_CORRUPTION_LEVEL);
+    commands.insert_resource(Corruption { level: data.corruption_level.min(corruption_unlocked), unlocked: corruption_unlocked });
+    commands.insert_resource(WeeklyBestScore(data.weekly_best));
+    commands.insert_resource(data.bestiary.clone());
+    commands.insert_resource(data.lore.clone());
+    commands.insert_resource(data.skins.clone().sanitized());
+    commands.insert_resource(data.settings.background_quality);
+    commands.insert_resource(AfterimageSettings { enabled: data.settings.afterimages_enabled });
+    commands.insert_resource(FocusAnnouncer { enabled: data.settings.focus_announcer_enabled });
+    commands.insert_resource(TouchControls { mode: data.settings.touch_controls, touch_detected: false });
+    last_saved.0 = (source != SaveSource::Fresh).then_some(data);
+}
+
+fn save_if_changed(data: SaveData, storage: &dyn Storage, last_saved: &mut LastSavedData) {
+    if last_saved.0.as_ref() == Some(&data) { return; }
+    match write_save(storage, SAVE_DATA_PATH, &data) {
+        Ok(()) => last_saved.0 = Some(data),
+        Err(error) => warn!("Could not save to {}: {}", SAVE_DATA_PATH, error),
+    }
+}
+
+fn autosave_on_change(saved: SavedResources, storage: Res<GameStorage>, mut last_saved: ResMut<LastSavedData>) {
+    if !saved.is_changed() { return; }
+    save_if_changed(saved.current(), storage.0.as_ref(), &mut last_saved);
+}
+
+// Browsers close the tab without an AppExit, so on the web the autosave above is what keeps the profile
+fn save_on_exit(mut exit_events: EventReader<AppExit>, saved: SavedResources, storage: Res<GameStorage>, mut last_saved: ResMut<LastSavedData>) {
+    if exit_events.read().last().is_none() { return; }
+    // Forget what was saved so the exit write always happens, even if nothing changed
+    last_saved.0 = None;
+    save_if_changed(saved.current(), storage.0.as_ref(), &mut last_saved);
+}
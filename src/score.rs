@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::Survivor,
+    components::Health,
+    game::{AppState, GameState},
+    game_events::GameEvent,
+};
+
+pub const ELITE_KILL_BONUS: u32 = 250;
+pub const CYCLE_CLEAR_BONUS_PER_CYCLE: u32 = 100;
+pub const NO_DAMAGE_CYCLE_BONUS: u32 = 500;
+pub const RELIQUARY_FOUND_BONUS: u32 = 300;
+const SCORE_POPUP_LIFETIME_SECS: f32 = 1.4;
+const SCORE_POPUP_RISE_SPEED: f32 = 45.0;
+const SCORE_POPUP_Z_POS: f32 = 6.0;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ScoreBreakdown>()
+            .add_event::<ScoreAwardEvent>()
+            .add_systems(OnExit(AppState::MainMenu), reset_score_breakdown)
+            .add_systems(Update, (
+                kill_score_system,
+                cycle_clear_bonus_system,
+            ).run_if(in_state(AppState::InGame)))
+            // Awards can be sent on the frame play pauses for a reveal, so they are applied in any state
+            .add_systems(Update, apply_score_awards_system)
+            .add_systems(Update, animate_score_popups_system.run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::GameOver), (despawn_score_popups, setup_score_breakdown_ui))
+            .add_systems(OnExit(AppState::GameOver), despawn_score_breakdown_ui);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreSource { Kills, EliteKills, CycleClear, NoDamageCycle, ReliquaryFound, ChallengeObjective, CaravanEscorted, }
+
+impl ScoreSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoreSource::Kills => "Horrors slain",
+            ScoreSource::EliteKills => "Elites slain",
+            ScoreSource::CycleClear => "Cycles endured",
+            ScoreSource::NoDamageCycle => "Untouched cycles",
+            ScoreSource::ReliquaryFound => "Reliquaries found",
+            ScoreSource::ChallengeObjective => "Challenge objective",
+            ScoreSource::CaravanEscorted => "Caravans escorted",
+        }
+    }
+    // Plain kills happen constantly, so they count toward the breakdown without a popup
+    fn shows_popup(&self) -> bool {
+        !matches!(self, ScoreSource::Kills)
+    }
+}
+
+// Anything that earns score beyond the kill itself; position is where its popup appears
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScoreAwardEvent {
+    pub source: ScoreSource,
+    pub points: u32,
+    pub position: Vec3,
+}
+
+// Running totals per source, kept in the order each source was first earned
+#[derive(Resource, Debug, Default)]
+pub struct ScoreBreakdown {
+    entries: Vec<(ScoreSource, u32)>,
+}
+
+impl ScoreBreakdown {
+    pub fn add(&mut self, source: ScoreSource, points: u32) {
+        match self.entries.iter_mut().find(|(entry_source, _)| *entry_source == source) {
+            Some((_, total)) => *total += points,
+            None => self.entries.push((source, points)),
+        }
+    }
+    pub fn total(&self) -> u32 {
+        self.entries.iter().map(|(_, total)| total).sum()
+    }
+    pub fn entries(&self) -> &[(ScoreSource, u32)] {
+        &self.entries
+    }
+}
+
+pub fn cycle_clear_bonus(cleared_cycle: u32, untouched: bool) -> Vec<(ScoreSource, u32)> {
+    let mut bonuses = vec![(ScoreSource::CycleClear, CYCLE_CLEAR_BONUS_PER_CYCLE * cleared_cycle)];
+    if untouched { bonuses.push((ScoreSource::NoDamageCycle, NO_DAMAGE_CYCLE_BONUS)); }
+    bonuses
+}
+
+#[derive(Component)]
+struct ScorePopup {
+    lifetime: Timer,
+}
+
+#[derive(Component)]
+struct ScoreBreakdownUI;
+
+fn reset_score_breakdown(mut breakdown: ResMut<ScoreBreakdown>) {
+    *breakdown = ScoreBreakdown::default();
+}
+
+// Horrors already add their own kill score to GameState; only the breakdown and the elite bonus are added here
+fn kill_score_system(
+    mut game_events: EventReader<GameEvent>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut award_writer: EventWriter<ScoreAwardEvent>,
+) {
+    for event in game_events.read().filter_map(GameEvent::as_horror_kill) {
+        breakdown.add(ScoreSource::Kills, event.xp_value / 2);
+        if event.is_elite {
+            award_writer.send(ScoreAwardEvent { source: ScoreSource::EliteKills, points: ELITE_KILL_BONUS, position: event.position });
+        }
+    }
+}
+
+// Cycles stand in for waves: each time the difficulty cycle advances the one just survived pays out
+fn cycle_clear_bonus_system(
+    game_state: Res<GameState>,
+    player_query: Query<(&Transform, &Health), With<Survivor>>,
+    mut last_cycle: Local<Option<u32>>,
+    mut last_health: Local<Option<i32>>,
+    mut hit_this_cycle: Local<bool>,
+    mut award_writer: EventWriter<ScoreAwardEvent>,
+) {
+    let Ok((player_transform, health)) = player_query.get_single() else { return; };
+    if matches!(*last_health, Some(previous) if health.0 < previous) { *hit_this_cycle = true; }
+    *last_health = Some(health.0);
+
+    let previous_cycle = last_cycle.get_or_insert(game_state.cycle_number);
+    // A new run starts back at cycle one
+    if game_state.cycle_number < *previous_cycle { *previous_cycle = game_state.cycle_number; *hit_this_cycle = false; }
+    if game_state.cycle_number == *previous_cycle { return; }
+
+    for (source, points) in cycle_clear_bonus(*previous_cycle, !*hit_this_cycle) {
+        award_writer.send(ScoreAwardEvent { source, points, position: player_transform.translation });
+    }
+    *previous_cycle = game_state.cycle_number;
+    *hit_this_cycle = false;
+}
+
+fn apply_score_awards_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut award_events: EventReader<ScoreAwardEvent>,
+    mut game_state: ResMut<GameState>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+) {
+    for award in award_events.read() {
+        game_state.score += award.points;
+        breakdown.add(award.source, award.points);
+        if !award.source.shows_popup() { continue; }
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    format!("+{} {}", award.points, award.source.label()),
+                    TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: Color::rgb(1.0, 0.9, 0.4) },
+                ),
+                transform: Transform::from_translation(award.position.truncate().extend(SCORE_POPUP_Z_POS) + Vec3::Y * 30.0),
+                ..default()
+            },
+            ScorePopup { lifetime: Timer::from_seconds(SCORE_POPUP_LIFETIME_SECS, TimerMode::Once) },
+            Name::new("ScorePopup"),
+        ));
+    }
+}
+
+fn animate_score_popups_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut popup, mut transform, mut text) in popup_query.iter_mut() {
+        popup.lifetime.tick(time.delta());
+        if popup.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation.y += SCORE_POPUP_RISE_SPEED * time.delta_seconds();
+        if let Some(section) = text.sections.get_mut(0) {
+            section.style.color.set_a(1.0 - popup.lifetime.fraction().powi(2));
+        }
+    }
+}
+
+fn despawn_score_popups(mut commands: Commands, popup_query: Query<Entity, With<ScorePopup>>) {
+    for entity in popup_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn setup_score_breakdown_ui(mut commands: Commands, asset_server: Res<AssetServer>, breakdown: Res<ScoreBreakdown>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle { style: Style { position_type: PositionType::Absolute, top: Val::Px(40.0), left: Val::Px(40.0), flex_direction: FlexDirection::Column, row_gap: Val::Px(6.0), ..default() }, ..default() },
+        ScoreBreakdownUI,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Score sources", TextStyle { font: font.clone(), font_size: 28.0, color: Color::WHITE }));
+        for (source, points) in breakdown.entries() {
+            parent.spawn(TextBundle::from_section(format!("{}: {}", source.label(), points), TextStyle { font: font.clone(), font_size: 22.0, color: Color::rgb(0.85, 0.85, 0.85) }));
+        }
+        parent.spawn(TextBundle::from_section(format!("Total: {}", breakdown.total()), TextStyle { font: font.clone(), font_size: 24.0, color: Color::WHITE }));
+    });
+}
+
+fn despawn_score_breakdown_ui(mut commands: Commands, ui_query: Query<Entity, With<ScoreBreakdownUI>>) {
+    for entity in ui_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
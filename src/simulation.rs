@@ -0,0 +1,276 @@
+use bevy::{
+    prelude::*,
+    app::{AppExit, PluginGroupBuilder, ScheduleRunnerPlugin, StateTransition},
+    ecs::schedule::ExecutorKind,
+    input::InputSystem,
+    render::{RenderPlugin, settings::WgpuSettings},
+    time::TimeUpdateStrategy,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use serde::Deserialize;
+use std::time::Duration;
+use crate::{
+    components::Health,
+    game::{AppState, GameRng, GameState},
+    horror::Horror,
+    horror_stats::HorrorStatsLibrary,
+    input_actions::ActionStateSystem,
+    platform::GameStorage,
+    survivor::Survivor,
+    upgrades::UpgradePool,
+};
+
+pub const SIMULATE_FLAG: &str = "--simulate";
+pub const SIMULATION_STEPS_PER_SEC: u32 = 60;
+pub const DEFAULT_SIMULATION_SEED: u64 = 0x5EED;
+// Positions are hashed at this resolution so harmless float noise in the last bits doesn't count as a change
+const POSITION_HASH_SCALE: f32 = 100.0;
+
+// Runs the game without a window or GPU on a fixed clock with seeded randomness, then prints a hash of the final state
+pub struct SimulationPlugin(pub SimulationConfig);
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.0.clone())
+            .insert_resource(GameRng::seeded(self.0.seed))
+            // Whatever a simulated run writes is thrown away with it
+            .insert_resource(GameStorage::in_memory())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / SIMULATION_STEPS_PER_SEC as f64)))
+            .init_resource::<SimulationClock>()
+            .add_systems(PreUpdate, drive_simulated_input.after(InputSystem).before(ActionStateSystem))
+            .add_systems(Last, finish_simulation);
+        // Systems with no ordering between them may otherwise run in a different order from one run to the next
+        app.edit_schedule(First, single_threaded)
+            .edit_schedule(PreUpdate, single_threaded)
+            .edit_schedule(StateTransition, single_threaded)
+            .edit_schedule(FixedUpdate, single_threaded)
+            .edit_schedule(Update, single_threaded)
+            .edit_schedule(PostUpdate, single_threaded)
+            .edit_schedule(Last, single_threaded);
+    }
+}
+
+fn single_threaded(schedule: &mut Schedule) {
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+}
+
+// DefaultPlugins minus the window and the renderer, looping as fast as the CPU allows
+pub fn headless_plugins() -> PluginGroupBuilder {
+    DefaultPlugins
+        .set(WindowPlugin { primary_window: None, exit_condition: ExitCondition::DontExit, close_when_requested: false })
+        .set(RenderPlugin { render_creation: WgpuSettings { backends: None, ..default() }.into(), ..default() })
+        .disable::<WinitPlugin>()
+        .add(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+}
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    pub minutes: f32,
+    pub seed: u64,
+    pub inputs: InputScript,
+    pub expected_hash: Option<u64>,
+}
+
+impl SimulationConfig {
+    // `--simulate <minutes> [--seed <n>] [--inputs <file.ron>] [--expect <hash>]`; Ok(None) means a normal windowed run
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, SimulationError> {
+        let mut args = args.into_iter();
+        let mut config: Option<SimulationConfig> = None;
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &'static str| args.next().ok_or(SimulationError::MissingValue(flag));
+            match arg.as_str() {
+                SIMULATE_FLAG => {
+                    let minutes: f32 = value(SIMULATE_FLAG)?.parse().map_err(|_| SimulationError::InvalidValue(SIMULATE_FLAG))?;
+                    if minutes <= 0.0 { return Err(SimulationError::InvalidValue(SIMULATE_FLAG)); }
+                    config.get_or_insert_with(SimulationConfig::default).minutes = minutes;
+                }
+                "--seed" => config.get_or_insert_with(SimulationConfig::default).seed = value("--seed")?.parse().map_err(|_| SimulationError::InvalidValue("--seed"))?,
+                "--inputs" => {
+                    let source = std::fs::read_to_string(value("--inputs")?).map_err(SimulationError::Io)?;
+                    config.get_or_insert_with(SimulationConfig::default).inputs = InputScript::from_ron_str(&source)?;
+                }
+                "--expect" => {
+                    let hash = u64::from_str_radix(value("--expect")?.trim_start_matches("0x"), 16).map_err(|_| SimulationError::InvalidValue("--expect"))?;
+                    config.get_or_insert_with(SimulationConfig::default).expected_hash = Some(hash);
+                }
+                _ => {}
+            }
+        }
+        // The other flags only make sense alongside --simulate
+        match config {
+            Some(config) if config.minutes > 0.0 => Ok(Some(config)),
+            Some(_) => Err(SimulationError::MissingValue(SIMULATE_FLAG)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn total_steps(&self) -> u64 {
+        (self.minutes as f64 * 60.0 * SIMULATION_STEPS_PER_SEC as f64).round() as u64
+    }
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self { minutes: 0.0, seed: DEFAULT_SIMULATION_SEED, inputs: InputScript::default(), expected_hash: None }
+    }
+}
+
+#[derive(Debug)]
+pub enum SimulationError {
+    MissingValue(&'static str),
+    InvalidValue(&'static str),
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulationError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            SimulationError::InvalidValue(flag) => write!(f, "{} has an invalid value", flag),
+            SimulationError::Io(error) => write!(f, "could not read the input script: {}", error),
+            SimulationError::Parse(error) => write!(f, "could not parse the input script: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+impl From<ron::error::SpannedError> for SimulationError {
+    fn from(error: ron::error::SpannedError) -> Self { SimulationError::Parse(error) }
+}
+
+// The controls a script can hold down; menus are answered automatically
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulatedKey { Up, Down, Left, Right, Skill1, Skill2, Skill3, Skill4, Skill5, }
+
+impl SimulatedKey {
+    pub fn key_code(self) -> KeyCode {
+        match self {
+            SimulatedKey::Up => KeyCode::KeyW, SimulatedKey::Down => KeyCode::KeyS, SimulatedKey::Left => KeyCode::KeyA, SimulatedKey::Right => KeyCode::KeyD,
+            SimulatedKey::Skill1 => KeyCode::Digit1, SimulatedKey::Skill2 => KeyCode::Digit2, SimulatedKey::Skill3 => KeyCode::Digit3,
+            SimulatedKey::Skill4 => KeyCode::KeyE, SimulatedKey::Skill5 => KeyCode::KeyR,
+        }
+    }
+}
+
+// Holds `key` for `hold_secs` starting `from_secs` into the run
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ScriptedInput {
+    pub key: SimulatedKey,
+    pub from_secs: f32,
+    pub hold_secs: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct InputScript {
+    pub inputs: Vec<ScriptedInput>,
+}
+
+impl InputScript {
+    pub fn from_ron_str(source: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_str(source)
+    }
+
+    pub fn keys_held_at(&self, run_secs: f32) -> Vec<KeyCode> {
+        self.inputs.iter()
+            .filter(|input| run_secs >= input.from_secs && run_secs < input.from_secs + input.hold_secs)
+            .map(|input| input.key.key_code())
+            .collect()
+    }
+}
+
+// Everything the hash covers; horrors are sorted so it doesn't depend on query order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationSnapshot {
+    pub steps: u64,
+    pub score: u32,
+    pub cycle_number: u32,
+    pub survivor: Option<(Vec2, i32, u32, u32)>,
+    pub horrors: Vec<(Vec2, i32)>,
+}
+
+impl SimulationSnapshot {
+    pub fn state_hash(&self) -> u64 {
+        let quantize = |position: Vec2| ((position.x * POSITION_HASH_SCALE).round() as i64, (position.y * POSITION_HASH_SCALE).round() as i64);
+        let mut horrors: Vec<((i64, i64), i32)> = self.horrors.iter().map(|(position, health)| (quantize(*position), *health)).collect();
+        horrors.sort_unstable();
+        let mut words: Vec<i64> = vec![self.steps as i64, self.score as i64, self.cycle_number as i64];
+        if let Some((position, health, level, experience)) = self.survivor {
+            let (x, y) = quantize(position);
+            words.extend([x, y, health as i64, level as i64, experience as i64]);
+        }
+        words.push(horrors.len() as i64);
+        words.extend(horrors.into_iter().flat_map(|((x, y), health)| [x, y, health as i64]));
+        // FNV-1a, so the hash is the same on every platform and Rust version
+        words.iter().flat_map(|word| word.to_le_bytes()).fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+    }
+}
+
+// Steps since the run left the main menu; asset loading before that varies from machine to machine
+#[derive(Resource, Default, Debug)]
+struct SimulationClock {
+    run_steps: Option<u64>,
+}
+
+fn drive_simulated_input(
+    config: Res<SimulationConfig>,
+    clock: Res<SimulationClock>,
+    app_state: Res<State<AppState>>,
+    asset_server: Res<AssetServer>,
+    horror_stats: Res<HorrorStatsLibrary>,
+    upgrade_pool: Res<UpgradePool>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    let run_secs = clock.run_steps.unwrap_or(0) as f32 / SIMULATION_STEPS_PER_SEC as f32;
+    let held = config.inputs.keys_held_at(run_secs);
+    for input in config.inputs.inputs.iter() {
+        let key = input.key.key_code();
+        if held.contains(&key) { keyboard_input.press(key); } else { keyboard_input.release(key); }
+    }
+    // Menus take the first option; pressing on alternate frames makes every press a fresh just_pressed
+    let menu_key = match app_state.get() {
+        AppState::MainMenu if horror_stats.finished_loading(&asset_server) && upgrade_pool.finished_loading(&asset_server) => Some(KeyCode::Space),
+        AppState::LevelUp | AppState::GlyphSlotChoice => Some(KeyCode::Digit1),
+        AppState::RewardReveal => Some(KeyCode::Space),
+        AppState::RespecShrine => Some(crate::respec_shrine::RESPEC_LEAVE_KEY),
+        _ => None,
+    };
+    if let Some(key) = menu_key {
+        if keyboard_input.pressed(key) { keyboard_input.release(key); } else { keyboard_input.press(key); }
+    }
+}
+
+fn finish_simulation(
+    config: Res<SimulationConfig>,
+    mut clock: ResMut<SimulationClock>,
+    app_state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    survivor_query: Query<(&Transform, &Health, &Survivor)>,
+    horror_query: Query<(&Transform, &Health), With<Horror>>,
+    mut exit_writer: EventWriter<AppExit>,
+) {
+    if *app_state.get() == AppState::MainMenu && clock.run_steps.is_none() { return; }
+    let steps = clock.run_steps.get_or_insert(0);
+    *steps += 1;
+    let steps = *steps;
+    if steps < config.total_steps() && *app_state.get() != AppState::GameOver { return; }
+
+    let snapshot = SimulationSnapshot {
+        steps,
+        score: game_state.score,
+        cycle_number: game_state.cycle_number,
+        survivor: survivor_query.get_single().ok().map(|(transform, health, survivor)| (transform.translation.truncate(), health.0, survivor.level, survivor.experience)),
+        horrors: horror_query.iter().map(|(transform, health)| (transform.translation.truncate(), health.0)).collect(),
+    };
+    let hash = snapshot.state_hash();
+    println!("Simulated {} steps (seed {}): score {}, cycle {}, {} horrors alive, state hash {:016x}", steps, config.seed, snapshot.score, snapshot.cycle_number, snapshot.horrors.len(), hash);
+    if let Some(expected) = config.expected_hash {
+        if expected != hash {
+            eprintln!("State hash mismatch: expected {:016x}, got {:016x}", expected, hash);
+            std::process::exit(1);
+        }
+    }
+    exit_writer.send(AppExit);
+}
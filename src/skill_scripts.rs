@@ -0,0 +1,300 @@
+use bevy::{prelude::*, ecs::{entity::EntityHashSet, system::SystemParam}};
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::{
+    game::AppState,
+    components::{Health, ProjectileOwner, DamageType},
+    horror::{Horror, Frozen},
+    skills::{SkillId, LastHitBySkill},
+    projectile::{ProjectileSpec, ProjectileVisual, SkillProjectilePayload, spawn_projectile},
+    visual_effects::spawn_damage_text,
+    game_events::{GameEvent, DamageOrigin},
+};
+
+// A single hook can never issue more than this many commands, however many enemies are in range
+pub const MAX_SCRIPT_COMMANDS: usize = 64;
+// Hooks only ever see enemies this close to where they run
+pub const MAX_SCRIPT_QUERY_RADIUS: f32 = 1200.0;
+const MIN_SCRIPT_TICK_SECS: f32 = 0.05;
+
+pub struct SkillScriptsPlugin;
+
+impl Plugin for SkillScriptsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkillScriptLibrary>()
+            .add_event::<SkillScriptCastEvent>()
+            .add_event::<SkillScriptHitEvent>()
+            .add_systems(Update, (run_cast_hooks, run_hit_hooks, run_tick_hooks).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_scripted_effects);
+    }
+}
+
+// Everything a script can do. There is deliberately no access to the survivor, items or game state
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum ScriptAction {
+    SpawnProjectile { damage: i32, speed: f32, #[serde(default)] angle_offset_deg: f32, size: (f32, f32), color: (f32, f32, f32, f32), lifetime_secs: f32, #[serde(default)] piercing: u32, #[serde(default)] homing_turn_rate: Option<f32> },
+    DamageEnemiesInRadius { radius: f32, damage: i32, #[serde(default)] max_targets: Option<usize> },
+    ChillEnemiesInRadius { radius: f32, speed_multiplier: f32, duration_secs: f32 },
+    // Only meaningful in on_hit, where there is a struck enemy
+    DamageTarget { damage: i32 },
+    ChillTarget { speed_multiplier: f32, duration_secs: f32 },
+}
+
+impl ScriptAction {
+    fn needs_target(&self) -> bool {
+        matches!(self, ScriptAction::DamageTarget { .. } | ScriptAction::ChillTarget { .. })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ScriptTick {
+    pub interval_secs: f32,
+    pub duration_secs: f32,
+    pub actions: Vec<ScriptAction>,
+}
+
+// Hooks a scripted skill runs: once when cast, whenever one of its projectiles hits, and on a timer after casting
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SkillScript {
+    #[serde(default)]
+    pub on_cast: Vec<ScriptAction>,
+    #[serde(default)]
+    pub on_hit: Vec<ScriptAction>,
+    #[serde(default)]
+    pub on_tick: Option<ScriptTick>,
+}
+
+impl SkillScript {
+    pub fn validate(&self) -> Result<(), SkillScriptError> {
+        if self.on_cast.iter().chain(self.on_tick.iter().flat_map(|tick| tick.actions.iter())).any(ScriptAction::needs_target) {
+            return Err(SkillScriptError::TargetOutsideHit);
+        }
+        if let Some(tick) = &self.on_tick {
+            let valid_tick = tick.interval_secs >= MIN_SCRIPT_TICK_SECS && tick.duration_secs > 0.0;
+            if !valid_tick { return Err(SkillScriptError::InvalidTick); }
+        }
+        for action in self.on_cast.iter().chain(self.on_hit.iter()).chain(self.on_tick.iter().flat_map(|tick| tick.actions.iter())) {
+            let valid = match action {
+                ScriptAction::SpawnProjectile { speed, size, lifetime_secs, homing_turn_rate, .. } => *speed > 0.0 && size.0 > 0.0 && size.1 > 0.0 && *lifetime_secs > 0.0 && homing_turn_rate.is_none_or(|turn_rate| turn_rate > 0.0),
+                ScriptAction::DamageEnemiesInRadius { radius, .. } => *radius > 0.0 && *radius <= MAX_SCRIPT_QUERY_RADIUS,
+                ScriptAction::ChillEnemiesInRadius { radius, speed_multiplier, duration_secs } => *radius > 0.0 && *radius <= MAX_SCRIPT_QUERY_RADIUS && (0.0..=1.0).contains(speed_multiplier) && *duration_secs > 0.0,
+                ScriptAction::ChillTarget { speed_multiplier, duration_secs } => (0.0..=1.0).contains(speed_multiplier) && *duration_secs > 0.0,
+                ScriptAction::DamageTarget { .. } => true,
+            };
+            if !valid { return Err(SkillScriptError::InvalidAction); }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SkillScriptError {
+    TargetOutsideHit,
+    InvalidTick,
+    InvalidAction,
+}
+
+impl std::fmt::Display for SkillScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkillScriptError::TargetOutsideHit => write!(f, "target actions can only be used in on_hit"),
+            SkillScriptError::InvalidTick => write!(f, "on_tick needs an interval of at least {}s and a positive duration", MIN_SCRIPT_TICK_SECS),
+            SkillScriptError::InvalidAction => write!(f, "an action has an out-of-range value"),
+        }
+    }
+}
+
+impl std::error::Error for SkillScriptError {}
+
+#[derive(Resource, Debug, Default)]
+pub struct SkillScriptLibrary {
+    pub scripts: HashMap<String, SkillScript>,
+}
+
+impl SkillScriptLibrary {
+    pub fn get(&self, name: &str) -> Option<&SkillScript> {
+        self.scripts.get(name)
+    }
+}
+
+// Where a hook runs and what it may aim at; damage_bonus carries the skill's flat damage upgrades
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScriptContext {
+    pub origin: Vec2,
+    pub aim: Vec2,
+    pub target: Option<Entity>,
+    pub damage_bonus: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    SpawnProjectile { position: Vec2, direction: Vec2, damage: i32, speed: f32, size: Vec2, color: Color, lifetime_secs: f32, piercing: u32, homing_turn_rate: Option<f32> },
+    Damage { target: Entity, amount: i32 },
+    Chill { target: Entity, speed_multiplier: f32, duration_secs: f32 },
+}
+
+// Scripts never touch the world directly: they are turned into a bounded list of commands first
+pub fn run_script_actions(actions: &[ScriptAction], context: &ScriptContext, enemies: &[(Entity, Vec2)]) -> Vec<ScriptCommand> {
+    let in_radius = |radius: f32| {
+        let mut nearby: Vec<&(Entity, Vec2)> = enemies.iter().filter(|(_, position)| position.distance(context.origin) <= radius).collect();
+        nearby.sort_by(|(_, a), (_, b)| a.distance_squared(context.origin).total_cmp(&b.distance_squared(context.origin)));
+        nearby.into_iter().map(|(entity, _)| *entity).collect::<Vec<_>>()
+    };
+    let mut commands = Vec::new();
+    for action in actions {
+        match action {
+            ScriptAction::SpawnProjectile { damage, speed, angle_offset_deg, size, color, lifetime_secs, piercing, homing_turn_rate } => {
+                let direction = Vec2::from_angle(angle_offset_deg.to_radians()).rotate(context.aim.normalize_or_zero());
+                if direction == Vec2::ZERO { continue; }
+                let (r, g, b, a) = *color;
+                commands.push(ScriptCommand::SpawnProjectile { position: context.origin, direction, damage: damage + context.damage_bonus, speed: *speed, size: Vec2::new(size.0, size.1), color: Color::rgba(r, g, b, a), lifetime_secs: *lifetime_secs, piercing: *piercing, homing_turn_rate: *homing_turn_rate });
+            }
+            ScriptAction::DamageEnemiesInRadius { radius, damage, max_targets } => {
+                let targets = in_radius(radius.min(MAX_SCRIPT_QUERY_RADIUS));
+                commands.extend(targets.into_iter().take(max_targets.unwrap_or(usize::MAX)).map(|target| ScriptCommand::Damage { target, amount: damage + context.damage_bonus }));
+            }
+            ScriptAction::ChillEnemiesInRadius { radius, speed_multiplier, duration_secs } => {
+                commands.extend(in_radius(radius.min(MAX_SCRIPT_QUERY_RADIUS)).into_iter().map(|target| ScriptCommand::Chill { target, speed_multiplier: *speed_multiplier, duration_secs: *duration_secs }));
+            }
+            ScriptAction::DamageTarget { damage } => {
+                if let Some(target) = context.target { commands.push(ScriptCommand::Damage { target, amount: damage + context.damage_bonus }); }
+            }
+            ScriptAction::ChillTarget { speed_multiplier, duration_secs } => {
+                if let Some(target) = context.target { commands.push(ScriptCommand::Chill { target, speed_multiplier: *speed_multiplier, duration_secs: *duration_secs }); }
+            }
+        }
+    }
+    commands.truncate(MAX_SCRIPT_COMMANDS);
+    commands
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SkillScriptCastEvent {
+    pub skill_id: SkillId,
+    pub script: String,
+    pub origin: Vec3,
+    pub aim: Vec2,
+    pub damage_bonus: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SkillScriptHitEvent {
+    pub skill_id: SkillId,
+    pub script: String,
+    pub target: Entity,
+    pub position: Vec3,
+    pub direction: Vec2,
+    pub damage_bonus: i32,
+}
+
+// Marks projectiles fired by a script so their hits run its on_hit hook
+#[derive(Component, Debug, Clone)]
+pub struct ScriptedProjectile {
+    pub script: String,
+    pub damage_bonus: i32,
+}
+
+#[derive(Component, Debug)]
+pub struct ScriptedEffect {
+    pub skill_id: SkillId,
+    pub script: String,
+    pub damage_bonus: i32,
+    pub aim: Vec2,
+    pub tick_timer: Timer,
+    pub lifetime_timer: Timer,
+}
+
+// The only world access a script ever gets
+#[derive(SystemParam)]
+pub struct ScriptApi<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+    time: Res<'w, Time>,
+    horror_query: Query<'w, 's, (Entity, &'static GlobalTransform, &'static mut Health, Option<&'static Frozen>), With<Horror>>,
+    game_event_writer: EventWriter<'w, GameEvent>,
+}
+
+impl ScriptApi<'_, '_> {
+    fn enemies(&self) -> Vec<(Entity, Vec2)> {
+        self.horror_query.iter().map(|(entity, transform, _, _)| (entity, transform.translation().truncate())).collect()
+    }
+
+    fn run(&mut self, actions: &[ScriptAction], context: ScriptContext, skill_id: SkillId, script: &str) {
+        let enemies = self.enemies();
+        for command in run_script_actions(actions, &context, &enemies) {
+            match command {
+                ScriptCommand::SpawnProjectile { position, direction, damage, speed, size, color, lifetime_secs, piercing, homing_turn_rate } => {
+                    let visual = ProjectileVisual { texture: self.asset_server.load("sprites/eldritch_bolt_placeholder.png"), size, color, z: None };
+                    let payload = SkillProjectilePayload { skill_id, bounces: 0, split: None, already_hit: EntityHashSet::default() };
+                    let mut spec = ProjectileSpec::new(ProjectileOwner::Survivor, damage, speed, lifetime_secs, visual, format!("ScriptedProjectile_{}", script)).with_piercing(piercing).for_skill(payload);
+                    spec.homing_turn_rate = homing_turn_rate;
+                    let projectile = spawn_projectile(&mut self.commands, spec, position.extend(1.0), direction);
+                    self.commands.entity(projectile).insert(ScriptedProjectile { script: script.to_string(), damage_bonus: context.damage_bonus });
+                }
+                ScriptCommand::Damage { target, amount } => {
+                    let Ok((_, transform, mut health, _)) = self.horror_query.get_mut(target) else { continue; };
+                    health.0 -= amount;
+                    let position = transform.translation();
+                    spawn_damage_text(&mut self.commands, position, amount, &self.time);
+                    self.commands.entity(target).insert(LastHitBySkill(skill_id));
+                    self.game_event_writer.send(GameEvent::DamageDealt { amount, damage_type: DamageType::Eldritch, position, origin: DamageOrigin::Skill(skill_id) });
+                }
+                ScriptCommand::Chill { target, speed_multiplier, duration_secs } => {
+                    let Ok((_, _, _, current_frozen)) = self.horror_query.get(target) else { continue; };
+                    // Like frost hits, a chill never overrides a stronger slow
+                    if current_frozen.is_none_or(|frozen| frozen.speed_multiplier >= speed_multiplier) {
+                        self.commands.entity(target).insert(Frozen { timer: Timer::from_seconds(duration_secs, TimerMode::Once), speed_multiplier });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn run_cast_hooks(mut cast_events: EventReader<SkillScriptCastEvent>, library: Res<SkillScriptLibrary>, mut api: ScriptApi) {
+    for event in cast_events.read() {
+        let Some(script) = library.get(&event.script) else { continue; };
+        let context = ScriptContext { origin: event.origin.truncate(), aim: event.aim, target: None, damage_bonus: event.damage_bonus };
+        api.run(&script.on_cast, context, event.skill_id, &event.script);
+        if let Some(tick) = &script.on_tick {
+            api.commands.spawn((
+                SpatialBundle::from_transform(Transform::from_translation(event.origin)),
+                ScriptedEffect {
+                    skill_id: event.skill_id,
+                    script: event.script.clone(),
+                    damage_bonus: event.damage_bonus,
+                    aim: event.aim,
+                    tick_timer: Timer::from_seconds(tick.interval_secs, TimerMode::Repeating),
+                    lifetime_timer: Timer::from_seconds(tick.duration_secs, TimerMode::Once),
+                },
+                Name::new(format!("ScriptedEffect_{}", event.script)),
+            ));
+        }
+    }
+}
+
+fn run_hit_hooks(mut hit_events: EventReader<SkillScriptHitEvent>, library: Res<SkillScriptLibrary>, mut api: ScriptApi) {
+    for event in hit_events.read() {
+        let Some(script) = library.get(&event.script) else { continue; };
+        let context = ScriptContext { origin: event.position.truncate(), aim: event.direction, target: Some(event.target), damage_bonus: event.damage_bonus };
+        api.run(&script.on_hit, context, event.skill_id, &event.script);
+    }
+}
+
+fn run_tick_hooks(mut effect_query: Query<(Entity, &mut ScriptedEffect, &Transform)>, library: Res<SkillScriptLibrary>, mut api: ScriptApi) {
+    let delta = api.time.delta();
+    for (entity, mut effect, transform) in effect_query.iter_mut() {
+        effect.lifetime_timer.tick(delta);
+        effect.tick_timer.tick(delta);
+        let Some(tick) = library.get(&effect.script).and_then(|script| script.on_tick.as_ref()) else { api.commands.entity(entity).despawn_recursive(); continue; };
+        let context = ScriptContext { origin: transform.translation.truncate(), aim: effect.aim, target: None, damage_bonus: effect.damage_bonus };
+        for _ in 0..effect.tick_timer.times_finished_this_tick() {
+            api.run(&tick.actions, context, effect.skill_id, &effect.script);
+        }
+        if effect.lifetime_timer.finished() { api.commands.entity(entity).despawn_recursive(); }
+    }
+}
+
+fn despawn_scripted_effects(mut commands: Commands, effect_query: Query<Entity, With<ScriptedEffect>>) {
+    for entity in effect_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
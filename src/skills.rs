@@ -1,17 +1,39 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ecs::entity::EntityHashSet};
+use rand::Rng;
+use serde::Deserialize;
 use std::time::Duration;
 use crate::{
     survivor::{Survivor, SURVIVOR_SIZE}, // Changed
-    game::AppState,
-    components::{Velocity, Damage, Lifetime, Health},
-    horror::Horror, // Changed
-    visual_effects::spawn_damage_text,
+    game::{AppState, GameConfig, GameRng},
+    components::{Velocity, Damage, Lifetime, Health, MaxHealth, Reflectable, ProjectileOwner, TauntTarget, TimeScale, DamageType, OverkillCarry, resolve_hit},
+    horror::{Horror, HorrorProjectile, Frozen, transfer_projectile_ownership, apply_damage_type_on_hit}, // Changed
+    visual_effects::{spawn_damage_text, spawn_typed_damage_text, spawn_beam_visual, spawn_particle_burst, spawn_impact_effect, DamageTypeStyle},
     audio::{PlaySoundEvent, SoundEffect},
+    game_events::{GameEvent, DamageOrigin},
     glyphs::{GlyphId, GlyphLibrary, GlyphEffectType},
+    items::{ExplosionEffect, ItemLibrary, execute_threshold},
+    projectile::{ProjectileSpec, ProjectileVisual, SkillProjectilePayload, spawn_projectile},
+    skill_scripts::{SkillScriptLibrary, SkillScriptCastEvent, SkillScriptHitEvent, ScriptedProjectile},
+    summons::{Summoned, SummonKind},
+    aggro::{Threat, DECOY_THREAT_WEIGHT, SENTRY_THREAT_WEIGHT},
+    input_actions::{ActionState, InputAction},
+    kill_cam::KillCam,
+    perf_overlay::timed_collision,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+// Hit sets are bounded so a long-lived piercing projectile can't grow without limit in a large wave
+pub const MAX_PROJECTILE_HIT_SET_SIZE: usize = 64;
+// Fragments from a split can split again, but only this many generations deep
+const MAX_SPLIT_DEPTH: u32 = 2;
+const SPLIT_FRAGMENT_SIZE_SCALE: f32 = 0.6;
+const SPLIT_FRAGMENT_DAMAGE_SCALE: f32 = 0.5;
+const SPLIT_FRAGMENT_LIFETIME_SECS: f32 = 0.6;
+pub const MAX_GLYPH_SLOTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default, Deserialize)] #[serde(transparent)]
 pub struct SkillId(pub u32);
+// Skill slots are bound to right click/1, 2, 3, E and R
+pub const MAX_SKILL_SLOTS: usize = 5;
 
 #[derive(Debug, Clone, PartialEq, Reflect)]
 pub enum SkillEffectType {
@@ -50,6 +72,55 @@ pub enum SkillEffectType {
         slow_duration_secs: f32,
         color: Color,
     },
+    Beam {
+        damage_per_tick: i32,
+        tick_interval_secs: f32,
+        range: f32,
+        width: f32,
+        max_channel_secs: f32,
+        color: Color,
+    },
+    Blink {
+        max_distance: f32,
+        min_distance: f32,
+        color: Color,
+    },
+    ReflectiveWard {
+        radius: f32,
+        duration_secs: f32,
+        color: Color,
+    },
+    Decoy {
+        health: i32,
+        duration_secs: f32,
+        taunt_radius: f32,
+        explosion_damage: i32,
+        explosion_radius: f32,
+        color: Color,
+    },
+    TimeBubble {
+        radius: f32,
+        duration_secs: f32,
+        horror_time_scale: f32,
+        survivor_time_scale: f32,
+        color: Color,
+    },
+    RicochetOrbs {
+        damage: i32,
+        orb_count: u32,
+        speed: f32,
+        orb_size: f32,
+        lifetime_secs: f32,
+        bounce_damage_falloff: f32,
+        min_damage: i32,
+        retarget_radius: f32,
+        max_active_orbs: u32,
+        color: Color,
+    },
+    // Behaviour comes from a SkillScript in the SkillScriptLibrary rather than from code
+    Scripted {
+        script: String,
+    },
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -77,7 +148,11 @@ impl ActiveSkillInstance {
     pub fn new(definition_id: SkillId, base_glyph_slots: u8) -> Self { Self { definition_id, current_cooldown: Duration::ZERO, current_level: 1, flat_damage_bonus: 0, cooldown_multiplier: 1.0, aoe_radius_multiplier: 1.0, equipped_glyphs: vec![None; base_glyph_slots as usize], } }
     pub fn tick_cooldown(&mut self, delta: Duration) { if self.current_cooldown > Duration::ZERO { self.current_cooldown = self.current_cooldown.saturating_sub(delta); } }
     pub fn is_ready(&self) -> bool { self.current_cooldown == Duration::ZERO }
-    pub fn trigger(&mut self, base_cooldown: Duration) { let modified_cooldown_secs = base_cooldown.as_secs_f32() * self.cooldown_multiplier; self.current_cooldown = Duration::from_secs_f32(modified_cooldown_secs.max(0.1)); }
+    // Conversion glyphs only affect projectile skills and are applied in socket order when casting, so the last one decides
+    pub fn damage_type(&self, skill_def: &SkillDefinition, glyph_library: &GlyphLibrary) -> DamageType { if !matches!(skill_def.effect, SkillEffectType::Projectile {..}) { return DamageType::default(); } self.equipped_glyphs.iter().flatten().filter_map(|glyph_id| glyph_library.get_glyph_definition(*glyph_id)).fold(DamageType::default(), |damage_type, glyph_def| if let GlyphEffectType::ConvertDamageType { damage_type: converted } = glyph_def.effect { converted } else { damage_type }) }
+    pub fn add_glyph_slot(&mut self) -> bool { if self.equipped_glyphs.len() >= MAX_GLYPH_SLOTS { return false; } self.equipped_glyphs.push(None); true }
+    pub fn trigger(&mut self, base_cooldown: Duration) { self.trigger_with_global_multiplier(base_cooldown, 1.0); }
+    pub fn trigger_with_global_multiplier(&mut self, base_cooldown: Duration, global_cooldown_multiplier: f32) { let modified_cooldown_secs = base_cooldown.as_secs_f32() * self.cooldown_multiplier * global_cooldown_multiplier; self.current_cooldown = Duration::from_secs_f32(modified_cooldown_secs.max(0.1)); }
 }
 
 #[derive(Component)]
@@ -85,14 +160,42 @@ pub struct SkillProjectile {
     pub skill_id: SkillId,
     pub piercing_left: u32,
     pub bounces_left: u32,
-    pub already_hit_by_this_projectile: Vec<Entity>, // Tracks entities hit by this specific projectile instance
+    pub already_hit_by_this_projectile: EntityHashSet, // Tracks entities hit by this specific projectile instance
+    pub split: Option<ProjectileSplit>,
 }
 
-#[derive(Component)] pub struct ActiveSkillAoEEffect { pub skill_id: SkillId, pub actual_damage_per_tick: i32, pub actual_radius_sq: f32, pub tick_timer: Timer, pub lifetime_timer: Timer, pub already_hit_this_tick: Vec<Entity>, }
+impl SkillProjectile {
+    pub fn hit_set_limit(&self) -> usize { ((self.piercing_left + self.bounces_left + 5) as usize).min(MAX_PROJECTILE_HIT_SET_SIZE) }
+}
+
+// Kill attribution: the skill that last damaged a horror, cleared again when something else hits it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LastHitBySkill(pub SkillId);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectileSplit { pub fragments: u32, pub angle: f32, pub depth_left: u32, }
+
+#[derive(Component)] pub struct ActiveSkillAoEEffect { pub skill_id: SkillId, pub actual_damage_per_tick: i32, pub actual_radius_sq: f32, pub tick_timer: Timer, pub lifetime_timer: Timer, pub already_hit_this_tick: EntityHashSet, }
 #[derive(Component, Debug)] pub struct SurvivorBuffEffect { pub speed_multiplier_bonus: f32, pub fire_rate_multiplier_bonus: f32, pub duration_timer: Timer, }
 
+#[derive(Component, Debug)]
+pub struct ChanneledBeam { pub skill_id: SkillId, pub damage_per_tick: i32, pub range: f32, pub width: f32, pub tick_timer: Timer, pub channel_timer: Timer, }
+
+#[derive(Component, Debug)]
+pub struct ReflectiveWardEffect { pub radius_sq: f32, pub lifetime_timer: Timer, }
+
+#[derive(Component, Debug)]
+pub struct DecoyEffect { pub max_health: i32, pub lifetime_timer: Timer, pub contact_tick_timer: Timer, pub explosion_damage: i32, pub explosion_radius: f32, }
+
+#[derive(Component, Debug)]
+pub struct TimeBubbleEffect { pub radius_sq: f32, pub lifetime_timer: Timer, pub horror_time_scale: f32, pub survivor_time_scale: f32, }
+
+#[derive(Component, Debug)]
+pub struct RicochetOrb { pub skill_id: SkillId, pub base_damage: i32, pub bounces: u32, pub bounce_damage_falloff: f32, pub min_damage: i32, pub radius: f32, pub retarget_radius_sq: f32, pub last_hit: Option<Entity>, pub lifetime_timer: Timer, }
+impl RicochetOrb { pub fn current_damage(&self) -> i32 { ((self.base_damage as f32 * self.bounce_damage_falloff.powi(self.bounces as i32)).round() as i32).max(self.min_damage) } }
+
 #[derive(Component, Debug, Reflect, Default)] #[reflect(Component)]
-pub struct FreezingNovaEffect { pub damage: i32, pub radius_sq: f32, pub lifetime_timer: Timer, pub slow_multiplier: f32, pub slow_duration_secs: f32, pub already_hit_entities: Vec<Entity>, }
+pub struct FreezingNovaEffect { pub skill_id: SkillId, pub damage: i32, pub radius_sq: f32, pub lifetime_timer: Timer, pub slow_multiplier: f32, pub slow_duration_secs: f32, #[reflect(ignore)] pub already_hit_entities: EntityHashSet, }
 
 #[derive(Resource, Default, Reflect)] #[reflect(Resource)]
 pub struct SkillLibrary { pub skills: Vec<SkillDefinition>, }
@@ -105,7 +208,7 @@ impl Plugin for SkillsPlugin {
             .register_type::<FreezingNovaEffect>()
             .init_resource::<SkillLibrary>()
             .add_systems(Startup, populate_skill_library)
-            .add_systems(Update, ( active_skill_cooldown_recharge_system, survivor_skill_input_system, skill_projectile_lifetime_system, skill_projectile_collision_system, active_skill_aoe_system, survivor_buff_management_system, freezing_nova_effect_damage_system, // Renamed systems
+            .add_systems(Update, ( active_skill_cooldown_recharge_system, survivor_skill_input_system, skill_projectile_lifetime_system, timed_collision(skill_projectile_collision_system), active_skill_aoe_system, survivor_buff_management_system, freezing_nova_effect_damage_system, channeled_beam_system, reflective_ward_system, decoy_system, time_bubble_system, ricochet_orb_system, skill_kill_cooldown_reset_system, // Renamed systems
             ).chain().run_if(in_state(AppState::InGame)) );
     }
 }
@@ -117,28 +220,33 @@ fn populate_skill_library(mut library: ResMut<SkillLibrary>) {
     library.skills.push(SkillDefinition { id: SkillId(4), name: "Fleeting Agility".to_string(), description: "Briefly enhance your speed and reflexes.".to_string(), base_cooldown: Duration::from_secs(20), effect: SkillEffectType::SurvivorBuff { speed_multiplier_bonus: 0.30, fire_rate_multiplier_bonus: 0.25, duration_secs: 5.0, }, base_glyph_slots: 0 }); // Changed
     library.skills.push(SkillDefinition { id: SkillId(5), name: "Glacial Nova".to_string(), description: "Emits a chilling nova, damaging and slowing nearby foes.".to_string(), base_cooldown: Duration::from_secs(10), effect: SkillEffectType::FreezingNova { damage: 20, radius: 200.0, nova_duration_secs: 0.5, slow_multiplier: 0.5, slow_duration_secs: 3.0, color: Color::rgba(0.5, 0.8, 1.0, 0.6), }, base_glyph_slots: 1, });
     library.skills.push(SkillDefinition { id: SkillId(6), name: "Psychic Sentry".to_string(), description: "Summons a stationary sentry that pulses with psychic energy.".to_string(), base_cooldown: Duration::from_secs(18), effect: SkillEffectType::SummonSentry { sentry_damage_per_tick: 15, sentry_radius: 100.0, sentry_tick_interval_secs: 0.75, sentry_duration_secs: 8.0, sentry_color: Color::rgba(0.2, 0.7, 0.9, 0.5), }, base_glyph_slots: 1 });
+    library.skills.push(SkillDefinition { id: SkillId(7), name: "Abyssal Gaze".to_string(), description: "Channel a searing beam of void-light toward your cursor while the key is held.".to_string(), base_cooldown: Duration::from_secs(6), effect: SkillEffectType::Beam { damage_per_tick: 6, tick_interval_secs: 0.15, range: 450.0, width: 24.0, max_channel_secs: 3.0, color: Color::rgba(0.6, 0.3, 1.0, 0.8), }, base_glyph_slots: 1 });
+    library.skills.push(SkillDefinition { id: SkillId(8), name: "Void Step".to_string(), description: "Tear through the veil and reappear toward your cursor.".to_string(), base_cooldown: Duration::from_secs(5), effect: SkillEffectType::Blink { max_distance: 300.0, min_distance: 20.0, color: Color::rgba(0.5, 0.2, 0.9, 0.9), }, base_glyph_slots: 0 });
+    library.skills.push(SkillDefinition { id: SkillId(9), name: "Warding Mirror".to_string(), description: "Raise a mirrored bubble that hurls horror projectiles back at their makers.".to_string(), base_cooldown: Duration::from_secs(12), effect: SkillEffectType::ReflectiveWard { radius: 110.0, duration_secs: 4.0, color: Color::rgba(0.7, 0.9, 1.0, 0.35), }, base_glyph_slots: 0 });
+    library.skills.push(SkillDefinition { id: SkillId(10), name: "Hollow Effigy".to_string(), description: "Leave behind a hollow copy of yourself that draws nearby horrors and bursts when destroyed.".to_string(), base_cooldown: Duration::from_secs(16), effect: SkillEffectType::Decoy { health: 80, duration_secs: 6.0, taunt_radius: 400.0, explosion_damage: 40, explosion_radius: 130.0, color: Color::rgba(0.6, 0.6, 0.8, 0.7), }, base_glyph_slots: 1 });
+    library.skills.push(SkillDefinition { id: SkillId(11), name: "Stilled Hour".to_string(), description: "Warp time in a sphere around you; horrors inside crawl while you are only slightly hindered.".to_string(), base_cooldown: Duration::from_secs(20), effect: SkillEffectType::TimeBubble { radius: 180.0, duration_secs: 5.0, horror_time_scale: 0.3, survivor_time_scale: 0.8, color: Color::rgba(0.9, 0.8, 0.4, 0.25), }, base_glyph_slots: 0 });
+    library.skills.push(SkillDefinition { id: SkillId(12), name: "Wandering Eyes".to_string(), description: "Loose a clutch of ricocheting orbs that leap from horror to horror, weakening with every bounce.".to_string(), base_cooldown: Duration::from_secs(7), effect: SkillEffectType::RicochetOrbs { damage: 18, orb_count: 3, speed: 420.0, orb_size: 16.0, lifetime_secs: 5.0, bounce_damage_falloff: 0.85, min_damage: 4, retarget_radius: 300.0, max_active_orbs: 9, color: Color::rgb(0.9, 0.4, 0.6), }, base_glyph_slots: 1 });
 }
 
-fn active_skill_cooldown_recharge_system(time: Res<Time>, mut player_query: Query<&mut Survivor>,) { if let Ok(mut player) = player_query.get_single_mut() { for skill_instance in player.equipped_skills.iter_mut() { skill_instance.tick_cooldown(time.delta()); } } }
+fn skill_slot_input_held(slot_index: usize, actions: &ActionState) -> bool {
+    InputAction::skill(slot_index).is_some_and(|action| actions.pressed(action))
+}
 
-fn survivor_skill_input_system( mut commands: Commands, asset_server: Res<AssetServer>, mouse_button_input: Res<ButtonInput<MouseButton>>, keyboard_input: Res<ButtonInput<KeyCode>>, mut player_query: Query<(Entity, &mut Survivor, &Transform)>, skill_library: Res<SkillLibrary>, glyph_library: Res<GlyphLibrary>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { // Renamed
-    if let Ok((player_entity, mut player, player_transform)) = player_query.get_single_mut() {
-        let mut skill_to_trigger_idx: Option<usize> = None;
-        if mouse_button_input.just_pressed(MouseButton::Right) { skill_to_trigger_idx = Some(0); }
-        else if keyboard_input.just_pressed(KeyCode::Digit1) { skill_to_trigger_idx = Some(0); }
-        else if keyboard_input.just_pressed(KeyCode::Digit2) { skill_to_trigger_idx = Some(1); }
-        else if keyboard_input.just_pressed(KeyCode::Digit3) { skill_to_trigger_idx = Some(2); }
-        else if keyboard_input.just_pressed(KeyCode::KeyE) { skill_to_trigger_idx = Some(3); } 
-        else if keyboard_input.just_pressed(KeyCode::KeyR) { skill_to_trigger_idx = Some(4); } 
+fn active_skill_cooldown_recharge_system(time: Res<Time>, mut player_query: Query<(&mut Survivor, Option<&TimeScale>)>,) { if let Ok((mut player, time_scale_opt)) = player_query.get_single_mut() { let scaled_delta = time.delta().mul_f32(TimeScale::of(time_scale_opt)); for skill_instance in player.equipped_skills.iter_mut() { skill_instance.tick_cooldown(scaled_delta); } } }
 
-        if let Some(idx) = skill_to_trigger_idx { if idx >= player.equipped_skills.len() { return; } let current_aim_direction = player.aim_direction; let skill_instance_snapshot = player.equipped_skills[idx].clone();
+#[allow(clippy::possible_missing_else)]
+fn survivor_skill_input_system( mut commands: Commands, asset_server: Res<AssetServer>, actions: Res<ActionState>, mut player_query: Query<(Entity, &mut Survivor, &mut Transform)>, skill_library: Res<SkillLibrary>, glyph_library: Res<GlyphLibrary>, mut game_event_writer: EventWriter<GameEvent>, active_beams_query: Query<&ChanneledBeam>, active_orbs_query: Query<&RicochetOrb>, game_config: Res<GameConfig>, script_library: Res<SkillScriptLibrary>, mut script_cast_writer: EventWriter<SkillScriptCastEvent>,) { // Renamed
+    if let Ok((player_entity, mut player, mut player_transform)) = player_query.get_single_mut() {
+        let skill_to_trigger_idx = actions.skill_just_pressed();
+
+        if let Some(idx) = skill_to_trigger_idx { if idx >= player.equipped_skills.len() { return; } let current_aim_direction = player.aim_direction; let area_multiplier = player.area_multiplier; let mut duration_multiplier = player.duration_multiplier; let skill_instance_snapshot = player.equipped_skills[idx].clone();
             if skill_instance_snapshot.is_ready() { if let Some(skill_def) = skill_library.get_skill_definition(skill_instance_snapshot.definition_id) {
-                let mut effect_was_triggered = false; let mut projectile_damage = 0; let mut projectile_piercing = 0; let mut projectile_bounces = 0; let mut aoe_damage_per_tick = 0; let mut aoe_radius = 0.0; let mut sentry_damage_val = 0; let mut sentry_radius_val = 0.0; let mut nova_damage_val = 0; let mut nova_radius_val = 0.0;
-                match &skill_def.effect { SkillEffectType::Projectile { base_damage, piercing: base_piercing, .. } => { projectile_damage = base_damage + skill_instance_snapshot.flat_damage_bonus; projectile_piercing = *base_piercing; } SkillEffectType::AreaOfEffect { base_damage_per_tick, base_radius, .. } => { aoe_damage_per_tick = base_damage_per_tick + skill_instance_snapshot.flat_damage_bonus; aoe_radius = base_radius * skill_instance_snapshot.aoe_radius_multiplier; }, SkillEffectType::SummonSentry { sentry_damage_per_tick: sdpt, sentry_radius: sr, ..} => { sentry_damage_val = sdpt + skill_instance_snapshot.flat_damage_bonus; sentry_radius_val = sr * skill_instance_snapshot.aoe_radius_multiplier; } SkillEffectType::FreezingNova { damage, radius, .. } => { nova_damage_val = damage + skill_instance_snapshot.flat_damage_bonus; nova_radius_val = radius * skill_instance_snapshot.aoe_radius_multiplier; } _ => {} }
-                for glyph_opt in skill_instance_snapshot.equipped_glyphs.iter() { if let Some(glyph_id) = glyph_opt { if let Some(glyph_def) = glyph_library.get_glyph_definition(*glyph_id) { match &glyph_def.effect { GlyphEffectType::AddedChaosDamageToProjectile { damage_amount } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_damage += *damage_amount; } } GlyphEffectType::IncreasedAoEDamage { percent_increase } => { if matches!(skill_def.effect, SkillEffectType::AreaOfEffect {..}) { aoe_damage_per_tick = (aoe_damage_per_tick as f32 * (1.0 + percent_increase)).round() as i32; } if matches!(skill_def.effect, SkillEffectType::SummonSentry {..}) { sentry_damage_val = (sentry_damage_val as f32 * (1.0 + percent_increase)).round() as i32; } if matches!(skill_def.effect, SkillEffectType::FreezingNova {..}) { nova_damage_val = (nova_damage_val as f32 * (1.0 + percent_increase)).round() as i32; } } GlyphEffectType::ProjectileChain { bounces } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_bounces += bounces; } } } } } }
+                let mut effect_was_triggered = false; let mut projectile_damage = 0; let mut projectile_piercing = 0; let mut projectile_bounces = 0; let mut aoe_damage_per_tick = 0; let mut aoe_radius = 0.0; let mut sentry_damage_val = 0; let mut sentry_radius_val = 0.0; let mut nova_damage_val = 0; let mut nova_radius_val = 0.0; let mut beam_damage_val = 0; let mut decoy_explosion_damage_val = 0; let mut decoy_explosion_radius_val = 0.0; let mut orb_damage_val = 0; let mut projectile_split = None; let mut projectile_damage_type = DamageType::default(); let mut projectile_size_multiplier = 1.0; let mut projectile_overkill_carry = None;
+                match &skill_def.effect { SkillEffectType::Projectile { base_damage, piercing: base_piercing, .. } => { projectile_damage = base_damage + skill_instance_snapshot.flat_damage_bonus; projectile_piercing = *base_piercing; } SkillEffectType::AreaOfEffect { base_damage_per_tick, base_radius, .. } => { aoe_damage_per_tick = base_damage_per_tick + skill_instance_snapshot.flat_damage_bonus; aoe_radius = base_radius * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; }, SkillEffectType::SummonSentry { sentry_damage_per_tick: sdpt, sentry_radius: sr, ..} => { sentry_damage_val = sdpt + skill_instance_snapshot.flat_damage_bonus; sentry_radius_val = sr * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; } SkillEffectType::FreezingNova { damage, radius, .. } => { nova_damage_val = damage + skill_instance_snapshot.flat_damage_bonus; nova_radius_val = radius * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; } SkillEffectType::Beam { damage_per_tick, .. } => { beam_damage_val = damage_per_tick + skill_instance_snapshot.flat_damage_bonus; } SkillEffectType::Decoy { explosion_damage, explosion_radius, .. } => { decoy_explosion_damage_val = explosion_damage + skill_instance_snapshot.flat_damage_bonus; decoy_explosion_radius_val = explosion_radius * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; } SkillEffectType::RicochetOrbs { damage, .. } => { orb_damage_val = damage + skill_instance_snapshot.flat_damage_bonus; } _ => {} }
+                for glyph_id in skill_instance_snapshot.equipped_glyphs.iter().flatten() { if let Some(glyph_def) = glyph_library.get_glyph_definition(*glyph_id) { match &glyph_def.effect { GlyphEffectType::AddedChaosDamageToProjectile { damage_amount } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_damage += *damage_amount; } } GlyphEffectType::IncreasedAoEDamage { percent_increase } => { if matches!(skill_def.effect, SkillEffectType::AreaOfEffect {..}) { aoe_damage_per_tick = (aoe_damage_per_tick as f32 * (1.0 + percent_increase)).round() as i32; } if matches!(skill_def.effect, SkillEffectType::SummonSentry {..}) { sentry_damage_val = (sentry_damage_val as f32 * (1.0 + percent_increase)).round() as i32; } if matches!(skill_def.effect, SkillEffectType::FreezingNova {..}) { nova_damage_val = (nova_damage_val as f32 * (1.0 + percent_increase)).round() as i32; } if matches!(skill_def.effect, SkillEffectType::Decoy {..}) { decoy_explosion_damage_val = (decoy_explosion_damage_val as f32 * (1.0 + percent_increase)).round() as i32; } } GlyphEffectType::ProjectileChain { bounces } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_bounces += bounces; } } GlyphEffectType::SplitOnHit { fragments, angle } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_split = Some(ProjectileSplit { fragments: *fragments, angle: *angle, depth_left: MAX_SPLIT_DEPTH }); } } GlyphEffectType::ConvertDamageType { damage_type } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_damage_type = *damage_type; } } GlyphEffectType::CooldownResetOnKill { .. } => {} GlyphEffectType::IncreasedProjectileSize { percent_increase } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..} | SkillEffectType::RicochetOrbs {..}) { projectile_size_multiplier *= 1.0 + percent_increase; } } GlyphEffectType::IncreasedDuration { percent_increase } => { duration_multiplier *= 1.0 + percent_increase; } GlyphEffectType::OverkillCarryover { fraction } => { if matches!(skill_def.effect, SkillEffectType::Projectile {..}) { projectile_overkill_carry = Some(OverkillCarry::new(*fraction)); } } } } }
                 match &skill_def.effect {
-                    SkillEffectType::Projectile { speed, size, color, lifetime_secs, .. } => { if current_aim_direction != Vec2::ZERO { let projectile_spawn_position = player_transform.translation + current_aim_direction.extend(0.0) * (SURVIVOR_SIZE.y / 2.0 + size.y / 2.0); commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/eldritch_bolt_placeholder.png"), sprite: Sprite { custom_size: Some(*size), color: *color, ..default()}, transform: Transform::from_translation(projectile_spawn_position) .with_rotation(Quat::from_rotation_z(current_aim_direction.y.atan2(current_aim_direction.x))), ..default() }, SkillProjectile { skill_id: skill_def.id, piercing_left: projectile_piercing, bounces_left: projectile_bounces, already_hit_by_this_projectile: Vec::new()}, Velocity(current_aim_direction * *speed), Damage(projectile_damage), Lifetime { timer: Timer::from_seconds(*lifetime_secs, TimerMode::Once) }, Name::new(format!("SkillProjectile_{}", skill_def.name)), )); effect_was_triggered = true; } }
-                    SkillEffectType::AreaOfEffect { base_damage_per_tick, .. } => { // Modified for Mind Shatter (SkillId(2))
+                    SkillEffectType::Projectile { speed, size, color, lifetime_secs, .. } => { if current_aim_direction != Vec2::ZERO { let scaled_size = *size * projectile_size_multiplier; let projectile_spawn_position = player_transform.translation + current_aim_direction.extend(0.0) * (SURVIVOR_SIZE.y / 2.0 + scaled_size.y / 2.0); let visual = ProjectileVisual { texture: asset_server.load("sprites/eldritch_bolt_placeholder.png"), size: scaled_size, color: *color, z: None }; let payload = SkillProjectilePayload { skill_id: skill_def.id, bounces: projectile_bounces, split: projectile_split, already_hit: EntityHashSet::default() }; let mut spec = ProjectileSpec::new(ProjectileOwner::Survivor, projectile_damage, *speed, *lifetime_secs * duration_multiplier, visual, format!("SkillProjectile_{}", skill_def.name)).with_piercing(projectile_piercing).with_damage_type(projectile_damage_type).for_skill(payload); if let Some(carry) = projectile_overkill_carry { spec = spec.with_overkill_carry(carry); } spawn_projectile(&mut commands, spec, projectile_spawn_position, current_aim_direction); effect_was_triggered = true; } }
+                    SkillEffectType::AreaOfEffect { .. } => { // Modified for Mind Shatter (SkillId(2))
                         if skill_def.id == SkillId(2) { // Mind Shatter
                             let num_projectiles = 5;
                             let spread_angle_rad = 60.0f32.to_radians(); // Total arc for projectiles
@@ -151,59 +259,98 @@ fn survivor_skill_input_system( mut commands: Commands, asset_server: Res<AssetS
                                 let projectile_spawn_position = player_transform.translation + direction.extend(0.0) * (SURVIVOR_SIZE.y / 2.0 + 10.0 / 2.0); // Using 10.0 as size.y for fragment
                                 
                                 let mind_shatter_damage = 15 + skill_instance_snapshot.flat_damage_bonus; // Using 15 as base, adjusted from AoE base_damage_per_tick
-                                commands.spawn((
-                                    SpriteBundle {
-                                        texture: asset_server.load("sprites/mind_shatter_fragment_placeholder.png"),
-                                        sprite: Sprite { custom_size: Some(Vec2::new(10.0, 10.0)), color: Color::rgba(0.8, 0.2, 1.0, 0.9), ..default()},
-                                        transform: Transform::from_translation(projectile_spawn_position)
-                                            .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
-                                        ..default()
-                                    },
-                                    SkillProjectile {
-                                        skill_id: skill_def.id,
-                                        piercing_left: 0, // Or 1 if desired
-                                        bounces_left: 0, // Mind Shatter fragments don't bounce by default
-                                        already_hit_by_this_projectile: Vec::new(),
-                                    },
-                                    Velocity(direction * 400.0),
-                                    Damage(mind_shatter_damage), // Use calculated damage
-                                    Lifetime { timer: Timer::from_seconds(0.4, TimerMode::Once) }, // Short lifetime
-                                    Name::new(format!("MindShatterFragment_{}", i)),
-                                ));
+                                let visual = ProjectileVisual { texture: asset_server.load("sprites/mind_shatter_fragment_placeholder.png"), size: Vec2::new(10.0, 10.0), color: Color::rgba(0.8, 0.2, 1.0, 0.9), z: None };
+                                // Mind Shatter fragments don't pierce or bounce, and live only briefly
+                                let payload = SkillProjectilePayload { skill_id: skill_def.id, bounces: 0, split: None, already_hit: EntityHashSet::default() };
+                                spawn_projectile(&mut commands, ProjectileSpec::new(ProjectileOwner::Survivor, mind_shatter_damage, 400.0, 0.4 * duration_multiplier, visual, format!("MindShatterFragment_{}", i)).for_skill(payload), projectile_spawn_position, direction);
                             }
                             effect_was_triggered = true;
                         } else { // Original AoE logic for other skills if any
-                            let aoe_spawn_position = player_transform.translation; commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/mind_shatter_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(aoe_radius * 2.0)), color: Color::rgba(0.8, 0.2, 1.0, 0.7), ..default()}, transform: Transform::from_translation(aoe_spawn_position.truncate().extend(0.2)), ..default() }, ActiveSkillAoEEffect { skill_id: skill_def.id, actual_damage_per_tick: aoe_damage_per_tick, actual_radius_sq: aoe_radius.powi(2), tick_timer: Timer::from_seconds(0.1/*tick_interval_secs*/, TimerMode::Repeating), lifetime_timer: Timer::from_seconds(0.2/*duration_secs*/, TimerMode::Once), already_hit_this_tick: Vec::new(), }, Name::new(format!("SkillAoE_{}", skill_def.name)), )); effect_was_triggered = true;
+                            let aoe_spawn_position = player_transform.translation; commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/mind_shatter_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(aoe_radius * 2.0)), color: Color::rgba(0.8, 0.2, 1.0, 0.7), ..default()}, transform: Transform::from_translation(aoe_spawn_position.truncate().extend(0.2)), ..default() }, ActiveSkillAoEEffect { skill_id: skill_def.id, actual_damage_per_tick: aoe_damage_per_tick, actual_radius_sq: aoe_radius.powi(2), tick_timer: Timer::from_seconds(0.1/*tick_interval_secs*/, TimerMode::Repeating), lifetime_timer: Timer::from_seconds(0.2/*duration_secs*/ * duration_multiplier, TimerMode::Once), already_hit_this_tick: EntityHashSet::default(), }, Name::new(format!("SkillAoE_{}", skill_def.name)), )); effect_was_triggered = true;
                         }
                     }
                     SkillEffectType::SurvivorBuff { speed_multiplier_bonus, fire_rate_multiplier_bonus, duration_secs } => { commands.entity(player_entity).insert(SurvivorBuffEffect { speed_multiplier_bonus: *speed_multiplier_bonus, fire_rate_multiplier_bonus: *fire_rate_multiplier_bonus, duration_timer: Timer::from_seconds(*duration_secs, TimerMode::Once), }); effect_was_triggered = true; }
-                    SkillEffectType::SummonSentry { sentry_tick_interval_secs, sentry_duration_secs, sentry_color, .. } => { let sentry_spawn_position = player_transform.translation.truncate().extend(0.15); commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/psychic_sentry_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(sentry_radius_val * 0.5)), color: *sentry_color, ..default() }, transform: Transform::from_translation(sentry_spawn_position), ..default() }, ActiveSkillAoEEffect { skill_id: skill_def.id, actual_damage_per_tick: sentry_damage_val, actual_radius_sq: sentry_radius_val.powi(2), tick_timer: Timer::from_seconds(*sentry_tick_interval_secs, TimerMode::Repeating), lifetime_timer: Timer::from_seconds(*sentry_duration_secs, TimerMode::Once), already_hit_this_tick: Vec::new(), }, Name::new("PsychicSentry"), )); effect_was_triggered = true; }
-                    SkillEffectType::FreezingNova { nova_duration_secs, slow_multiplier, slow_duration_secs, color, .. } => { let nova_spawn_position = player_transform.translation; commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/frost_nova_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: *color, ..default() }, transform: Transform::from_translation(nova_spawn_position.truncate().extend(0.25)), ..default() }, FreezingNovaEffect { damage: nova_damage_val, radius_sq: nova_radius_val.powi(2), lifetime_timer: Timer::from_seconds(*nova_duration_secs, TimerMode::Once), slow_multiplier: *slow_multiplier, slow_duration_secs: *slow_duration_secs, already_hit_entities: Vec::new(), }, Name::new("GlacialNovaEffect"), )); effect_was_triggered = true; sound_event_writer.send(PlaySoundEvent(SoundEffect::RitualCast)); }
+                    SkillEffectType::SummonSentry { sentry_tick_interval_secs, sentry_duration_secs, sentry_color, .. } => { let sentry_spawn_position = player_transform.translation.truncate().extend(0.15); commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/psychic_sentry_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(sentry_radius_val * 0.5)), color: *sentry_color, ..default() }, transform: Transform::from_translation(sentry_spawn_position), ..default() }, ActiveSkillAoEEffect { skill_id: skill_def.id, actual_damage_per_tick: sentry_damage_val, actual_radius_sq: sentry_radius_val.powi(2), tick_timer: Timer::from_seconds(*sentry_tick_interval_secs, TimerMode::Repeating), lifetime_timer: Timer::from_seconds(*sentry_duration_secs * duration_multiplier, TimerMode::Once), already_hit_this_tick: EntityHashSet::default(), }, Summoned(SummonKind::PsychicSentry), Threat { weight: SENTRY_THREAT_WEIGHT }, Name::new("PsychicSentry"), )); effect_was_triggered = true; }
+                    SkillEffectType::FreezingNova { nova_duration_secs, slow_multiplier, slow_duration_secs, color, .. } => { let nova_spawn_position = player_transform.translation; commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/frost_nova_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: *color, ..default() }, transform: Transform::from_translation(nova_spawn_position.truncate().extend(0.25)), ..default() }, FreezingNovaEffect { skill_id: skill_def.id, damage: nova_damage_val, radius_sq: nova_radius_val.powi(2), lifetime_timer: Timer::from_seconds(*nova_duration_secs, TimerMode::Once), slow_multiplier: *slow_multiplier, slow_duration_secs: *slow_duration_secs, already_hit_entities: EntityHashSet::default(), }, Name::new("GlacialNovaEffect"), )); effect_was_triggered = true; }
+                    SkillEffectType::Beam { tick_interval_secs, range, width, max_channel_secs, color, .. } => { // Cooldown starts when the channel ends, see channeled_beam_system
+                        if current_aim_direction != Vec2::ZERO && !active_beams_query.iter().any(|beam| beam.skill_id == skill_def.id) {
+                            let beam_entity = spawn_beam_visual(&mut commands, &asset_server, player_transform.translation, current_aim_direction, *range, *width, *color);
+                            commands.entity(beam_entity).insert(( ChanneledBeam { skill_id: skill_def.id, damage_per_tick: beam_damage_val, range: *range, width: *width, tick_timer: Timer::from_seconds(*tick_interval_secs, TimerMode::Repeating), channel_timer: Timer::from_seconds(*max_channel_secs, TimerMode::Once), }, Name::new(format!("SkillBeam_{}", skill_def.name)), ));
+                            game_event_writer.send(GameEvent::SkillCast { skill_id: skill_def.id, position: player_transform.translation });
+                        }
+                    }
+                    SkillEffectType::Blink { max_distance, min_distance, color } => { // Blinks that would land (almost) in place, e.g. pressed against the arena edge, are refused without spending the cooldown
+                        let origin = player_transform.translation.truncate();
+                        let desired_offset = (player.aim_world_position - origin).clamp_length_max(*max_distance);
+                        let destination = game_config.clamp_to_arena(origin + desired_offset, SURVIVOR_SIZE.x / 2.0);
+                        if destination.distance(origin) >= *min_distance {
+                            spawn_particle_burst(&mut commands, player_transform.translation, *color, 12, 180.0);
+                            player_transform.translation = destination.extend(player_transform.translation.z);
+                            spawn_particle_burst(&mut commands, player_transform.translation, *color, 12, 120.0);
+                            effect_was_triggered = true;
+                        }
+                    }
+                    SkillEffectType::ReflectiveWard { radius, duration_secs, color } => { let ward_radius = radius * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; commands.entity(player_entity).with_children(|parent| { parent.spawn(( SpriteBundle { texture: asset_server.load("sprites/warding_whispers_effect.png"), sprite: Sprite { custom_size: Some(Vec2::splat(ward_radius * 2.0)), color: *color, ..default() }, transform: Transform::from_xyz(0.0, 0.0, 0.35), ..default() }, ReflectiveWardEffect { radius_sq: ward_radius.powi(2), lifetime_timer: Timer::from_seconds(*duration_secs, TimerMode::Once), }, Name::new("WardingMirror"), )); }); effect_was_triggered = true; }
+                    SkillEffectType::Decoy { health, duration_secs, taunt_radius, color, .. } => { let decoy_spawn_position = player_transform.translation.truncate().extend(0.9); commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/survivor_placeholder.png"), sprite: Sprite { custom_size: Some(SURVIVOR_SIZE), color: *color, ..default() }, transform: Transform::from_translation(decoy_spawn_position).with_rotation(Quat::from_rotation_z(current_aim_direction.to_angle())), ..default() }, DecoyEffect { max_health: *health, lifetime_timer: Timer::from_seconds(*duration_secs, TimerMode::Once), contact_tick_timer: Timer::from_seconds(0.5, TimerMode::Repeating), explosion_damage: decoy_explosion_damage_val, explosion_radius: decoy_explosion_radius_val, }, Health(*health), TauntTarget { radius: *taunt_radius }, Threat { weight: DECOY_THREAT_WEIGHT }, Name::new("HollowEffigy"), )); effect_was_triggered = true; }
+                    SkillEffectType::TimeBubble { radius, duration_secs, horror_time_scale, survivor_time_scale, color } => { let bubble_radius = radius * skill_instance_snapshot.aoe_radius_multiplier * area_multiplier; commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/aoe_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(bubble_radius * 2.0)), color: *color, ..default() }, transform: Transform::from_translation(player_transform.translation.truncate().extend(0.15)), ..default() }, TimeBubbleEffect { radius_sq: bubble_radius.powi(2), lifetime_timer: Timer::from_seconds(*duration_secs * duration_multiplier, TimerMode::Once), horror_time_scale: *horror_time_scale, survivor_time_scale: *survivor_time_scale, }, Name::new("StilledHourBubble"), )); effect_was_triggered = true; }
+                    SkillEffectType::RicochetOrbs { orb_count, speed, orb_size, lifetime_secs, bounce_damage_falloff, min_damage, retarget_radius, max_active_orbs, color, .. } => { // Only as many orbs as fit under the cap are loosed; a cast with no room left is refused
+                        let active_orbs = active_orbs_query.iter().filter(|orb| orb.skill_id == skill_def.id).count() as u32;
+                        let orbs_to_spawn = (*orb_count).min(max_active_orbs.saturating_sub(active_orbs));
+                        let orb_size = orb_size * projectile_size_multiplier;
+                        if current_aim_direction != Vec2::ZERO && orbs_to_spawn > 0 {
+                            let spread_angle_rad = 30.0f32.to_radians();
+                            let angle_step = if orbs_to_spawn > 1 { spread_angle_rad / (orbs_to_spawn - 1) as f32 } else { 0.0 };
+                            let base_angle = current_aim_direction.to_angle() - angle_step * (orbs_to_spawn - 1) as f32 / 2.0;
+                            for i in 0..orbs_to_spawn {
+                                let direction = Vec2::from_angle(base_angle + angle_step * i as f32);
+                                let orb_spawn_position = player_transform.translation + direction.extend(0.0) * (SURVIVOR_SIZE.y / 2.0 + orb_size / 2.0);
+                                commands.spawn((
+                                    SpriteBundle { texture: asset_server.load("sprites/ichor_blast_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(orb_size)), color: *color, ..default() }, transform: Transform::from_translation(orb_spawn_position), ..default() },
+                                    RicochetOrb { skill_id: skill_def.id, base_damage: orb_damage_val, bounces: 0, bounce_damage_falloff: *bounce_damage_falloff, min_damage: *min_damage, radius: orb_size / 2.0, retarget_radius_sq: retarget_radius.powi(2), last_hit: None, lifetime_timer: Timer::from_seconds(*lifetime_secs * duration_multiplier, TimerMode::Once), },
+                                    Velocity(direction * *speed),
+                                    Summoned(SummonKind::WanderingEye),
+                                    Name::new(format!("RicochetOrb_{}", i)),
+                                ));
+                            }
+                            effect_was_triggered = true;
+                        }
+                    }
+                    SkillEffectType::Scripted { script } => { // A skill whose script is missing stays ready rather than silently burning its cooldown
+                        if script_library.get(script).is_some() {
+                            script_cast_writer.send(SkillScriptCastEvent { skill_id: skill_def.id, script: script.clone(), origin: player_transform.translation, aim: current_aim_direction, damage_bonus: skill_instance_snapshot.flat_damage_bonus });
+                            effect_was_triggered = true;
+                        }
+                    }
                 }
-                if effect_was_triggered { if let Some(skill_instance_mut) = player.equipped_skills.get_mut(idx) { skill_instance_mut.trigger(skill_def.base_cooldown); } } } }
+                if effect_was_triggered { game_event_writer.send(GameEvent::SkillCast { skill_id: skill_def.id, position: player_transform.translation }); let global_cooldown_multiplier = player.get_effective_cooldown_multiplier(); if let Some(skill_instance_mut) = player.equipped_skills.get_mut(idx) { skill_instance_mut.trigger_with_global_multiplier(skill_def.base_cooldown, global_cooldown_multiplier); } } } }
         }
     }
 }
 
 fn survivor_buff_management_system(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut SurvivorBuffEffect)>,) { for (entity, mut buff) in query.iter_mut() { buff.duration_timer.tick(time.delta()); if buff.duration_timer.finished() { commands.entity(entity).remove::<SurvivorBuffEffect>(); } } } // Renamed
-fn skill_projectile_lifetime_system(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut Lifetime), With<SkillProjectile>>,) { for (entity, mut lifetime) in query.iter_mut() { lifetime.timer.tick(time.delta()); if lifetime.timer.just_finished() { commands.entity(entity).despawn_recursive(); } } }
+fn skill_projectile_lifetime_system(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut Lifetime, &SkillProjectile, &GlobalTransform, &Velocity, &Damage, &Sprite, &Handle<Image>, Option<&DamageType>)>,) { for (entity, mut lifetime, skill_projectile, g_transform, velocity, damage, sprite, texture, damage_type_opt) in query.iter_mut() { lifetime.timer.tick(time.delta()); if lifetime.timer.just_finished() { spawn_split_fragments(&mut commands, texture.clone(), skill_projectile, g_transform.translation(), velocity.0, sprite, (damage.0, damage_type_opt.copied().unwrap_or_default())); commands.entity(entity).despawn_recursive(); } } }
 
 fn skill_projectile_collision_system(
     mut commands: Commands,
-    mut skill_projectile_query: Query<(Entity, &GlobalTransform, &Damage, &mut SkillProjectile, &Sprite)>, // Removed Velocity & Lifetime from here
-    mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror)>, 
+    mut skill_projectile_query: Query<(Entity, &GlobalTransform, &Damage, &mut SkillProjectile, &Sprite, &Velocity, &Handle<Image>, Option<&DamageType>, Option<&mut OverkillCarry>, Option<&ScriptedProjectile>)>, // Removed Lifetime from here
+    mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror, Option<&MaxHealth>)>, 
+    item_library: Res<ItemLibrary>,
     asset_server: Res<AssetServer>,
     time: Res<Time>,
-    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    mut script_hit_writer: EventWriter<SkillScriptHitEvent>,
     skill_library: Res<SkillLibrary>,
     player_query: Query<&Survivor>,
-    glyph_library: Res<GlyphLibrary>,
+    frozen_query: Query<&Frozen>,
+    style: Res<DamageTypeStyle>,
 ) {
     let Ok(player) = player_query.get_single() else { return };
+    let execute_fraction = execute_threshold(&player.collected_item_ids, &item_library);
 
-    for (proj_entity, proj_g_transform, proj_damage, mut skill_projectile_data, proj_sprite) in skill_projectile_query.iter_mut() {
+    for (proj_entity, proj_g_transform, proj_damage, mut skill_projectile_data, proj_sprite, proj_velocity, proj_texture, proj_damage_type_opt, mut overkill_carry, scripted) in skill_projectile_query.iter_mut() {
+        let proj_damage_type = proj_damage_type_opt.copied().unwrap_or_default();
         // Safety to prevent infinite loops if something goes wrong with despawning
-        if skill_projectile_data.already_hit_by_this_projectile.len() > (skill_projectile_data.piercing_left + skill_projectile_data.bounces_left + 5) as usize { // Increased safety margin
+        if skill_projectile_data.already_hit_by_this_projectile.len() > skill_projectile_data.hit_set_limit() {
              commands.entity(proj_entity).despawn_recursive();
              continue;
         }
@@ -211,7 +358,7 @@ fn skill_projectile_collision_system(
         let proj_pos = proj_g_transform.translation().truncate();
         let proj_radius = proj_sprite.custom_size.map_or(5.0, |s| (s.x.max(s.y)) / 2.0); // Use max(s.x, s.y) for non-circular projectiles
 
-        for (horror_entity, horror_gtransform, mut horror_health, horror_data) in horror_query.iter_mut() {
+        for (horror_entity, horror_gtransform, mut horror_health, horror_data, horror_max_health) in horror_query.iter_mut() {
             if skill_projectile_data.already_hit_by_this_projectile.contains(&horror_entity) {
                 continue;
             }
@@ -219,10 +366,18 @@ fn skill_projectile_collision_system(
             let horror_radius = horror_data.size.x / 2.0; // Assuming circular collision for horror for now
 
             if proj_pos.distance(horror_pos) < proj_radius + horror_radius {
-                sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit));
-                horror_health.0 -= proj_damage.0;
-                spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), proj_damage.0, &time);
-                skill_projectile_data.already_hit_by_this_projectile.push(horror_entity);
+                let carried = overkill_carry.as_mut().map_or(0, |carry| carry.take());
+                let hit = resolve_hit(&mut horror_health.0, horror_max_health.map(|max| max.0), proj_damage.0 + carried, execute_fraction);
+                if let Some(carry) = overkill_carry.as_mut() { carry.store(hit.overkill); }
+                game_event_writer.send(GameEvent::DamageDealt { amount: hit.dealt, damage_type: proj_damage_type, position: horror_gtransform.translation(), origin: DamageOrigin::Skill(skill_projectile_data.skill_id) });
+                spawn_typed_damage_text(&mut commands, horror_gtransform.translation(), hit.dealt, proj_damage_type, &time);
+                spawn_impact_effect(&mut commands, &style, horror_gtransform.translation(), proj_damage_type);
+                apply_damage_type_on_hit(&mut commands, horror_entity, proj_damage_type, frozen_query.get(horror_entity).ok());
+                commands.entity(horror_entity).insert(LastHitBySkill(skill_projectile_data.skill_id));
+                skill_projectile_data.already_hit_by_this_projectile.insert(horror_entity);
+                if let Some(scripted) = scripted {
+                    script_hit_writer.send(SkillScriptHitEvent { skill_id: skill_projectile_data.skill_id, script: scripted.script.clone(), target: horror_entity, position: horror_gtransform.translation(), direction: proj_velocity.0.normalize_or_zero(), damage_bonus: scripted.damage_bonus });
+                }
 
                 if skill_projectile_data.piercing_left > 0 {
                     skill_projectile_data.piercing_left -= 1;
@@ -233,49 +388,43 @@ fn skill_projectile_collision_system(
                     let mut closest_new_target: Option<(Entity, f32)> = None;
                     let chain_search_radius_sq = 250.0 * 250.0; // Example chain search radius
 
-                    for (potential_target_entity, potential_target_gtransform, _health) in horror_query.iter() {
+                    for (potential_target_entity, potential_target_gtransform, ..) in horror_query.iter() {
                         // Ensure not chaining to the same horror or one already hit by this specific projectile's chain sequence
                         if potential_target_entity == horror_entity || skill_projectile_data.already_hit_by_this_projectile.contains(&potential_target_entity) {
                             continue;
                         }
                         let distance_sq = potential_target_gtransform.translation().truncate().distance_squared(horror_pos); // Chain from hit horror's position
-                        if distance_sq < chain_search_radius_sq {
-                            if closest_new_target.is_none() || distance_sq < closest_new_target.unwrap().1 {
-                                closest_new_target = Some((potential_target_entity, distance_sq));
-                            }
+                        if distance_sq < chain_search_radius_sq && (closest_new_target.is_none() || distance_sq < closest_new_target.unwrap().1) {
+                            closest_new_target = Some((potential_target_entity, distance_sq));
                         }
                     }
 
                     if let Some((target_entity, _)) = closest_new_target {
-                        if let Ok((_t_ent, target_transform, _h)) = horror_query.get(target_entity) { // Use get() for read-only access
+                        if let Ok((_t_ent, target_transform, ..)) = horror_query.get(target_entity) { // Use get() for read-only access
                             let direction_to_new_target = (target_transform.translation().truncate() - horror_pos).normalize_or_zero();
                             
-                            if let Some(active_skill_instance) = player.equipped_skills.iter().find(|s| s.definition_id == skill_projectile_data.skill_id) {
+                            if player.equipped_skills.iter().any(|s| s.definition_id == skill_projectile_data.skill_id) {
                                 if let Some(skill_def) = skill_library.get_skill_definition(skill_projectile_data.skill_id) {
                                     if let SkillEffectType::Projectile { speed, size, color, lifetime_secs, piercing, .. } = skill_def.effect {
-                                        let mut chained_damage = proj_damage.0; // Pass original damage or re-calculate with glyphs
+                                        let chained_damage = proj_damage.0; // Pass original damage or re-calculate with glyphs
                                         // Re-apply relevant glyphs if necessary, or assume they are part of proj_damage.0
                                         // For simplicity, let's assume proj_damage.0 already includes glyph effects from the initial cast.
                                         
-                                        commands.spawn((
-                                            SpriteBundle {
-                                                texture: asset_server.load("sprites/eldritch_bolt_placeholder.png"),
-                                                sprite: Sprite { custom_size: Some(size), color, ..default()},
-                                                transform: Transform::from_translation(horror_pos.extend(proj_g_transform.translation().z))
-                                                            .with_rotation(Quat::from_rotation_z(direction_to_new_target.y.atan2(direction_to_new_target.x))),
-                                                ..default()
-                                            },
-                                            SkillProjectile {
-                                                skill_id: skill_projectile_data.skill_id,
-                                                piercing_left: piercing, // Reset piercing for the new chain, or use a different logic
-                                                bounces_left: skill_projectile_data.bounces_left, // Pass remaining bounces
-                                                already_hit_by_this_projectile: vec![target_entity], // Initialize with the new target
-                                            },
-                                            Velocity(direction_to_new_target * speed),
-                                            Damage(chained_damage),
-                                            Lifetime { timer: Timer::from_seconds(lifetime_secs, TimerMode::Once) }, // Reset lifetime for chain
-                                            Name::new(format!("ChainedProjectile_{}", skill_def.name)),
-                                        ));
+                                        let visual = ProjectileVisual { texture: asset_server.load("sprites/eldritch_bolt_placeholder.png"), size: proj_sprite.custom_size.unwrap_or(size), color, z: None }; // Keep any glyph scaling of the original
+                                        let payload = SkillProjectilePayload {
+                                            skill_id: skill_projectile_data.skill_id,
+                                            bounces: skill_projectile_data.bounces_left, // Pass remaining bounces
+                                            split: skill_projectile_data.split,
+                                            already_hit: EntityHashSet::from_iter([target_entity]), // Initialize with the new target
+                                        };
+                                        // Piercing and lifetime reset for the new chain
+                                        let mut spec = ProjectileSpec::new(ProjectileOwner::Survivor, chained_damage, speed, lifetime_secs, visual, format!("ChainedProjectile_{}", skill_def.name))
+                                            .with_piercing(piercing)
+                                            .with_damage_type(proj_damage_type)
+                                            .for_skill(payload);
+                                        // The chain continues the same projectile, so any stored overkill travels with it
+                                        if let Some(carry) = overkill_carry.as_deref() { spec = spec.with_overkill_carry(*carry); }
+                                        spawn_projectile(&mut commands, spec, horror_pos.extend(proj_g_transform.translation().z), direction_to_new_target);
                                     }
                                 }
                             }
@@ -284,6 +433,7 @@ fn skill_projectile_collision_system(
                     commands.entity(proj_entity).despawn_recursive(); // Despawn original after chaining attempt
                     break; 
                 } else {
+                    spawn_split_fragments(&mut commands, proj_texture.clone(), &skill_projectile_data, proj_g_transform.translation(), proj_velocity.0, proj_sprite, (proj_damage.0, proj_damage_type));
                     commands.entity(proj_entity).despawn_recursive();
                     break; 
                 }
@@ -292,54 +442,280 @@ fn skill_projectile_collision_system(
     }
 }
 
-fn active_skill_aoe_system(mut commands: Commands, time: Res<Time>, mut aoe_query: Query<(Entity, &mut ActiveSkillAoEEffect, &GlobalTransform, Option<&mut Sprite>)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, asset_server: Res<AssetServer>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (aoe_entity, mut aoe_effect, aoe_g_transform, opt_sprite) in aoe_query.iter_mut() { aoe_effect.lifetime_timer.tick(time.delta()); if let Some(mut sprite) = opt_sprite { let lifetime_remaining_fraction = 1.0 - aoe_effect.lifetime_timer.fraction(); let initial_alpha = sprite.color.a(); sprite.color.set_a((initial_alpha * lifetime_remaining_fraction).clamp(0.0, initial_alpha)); } if aoe_effect.lifetime_timer.finished() { commands.entity(aoe_entity).despawn_recursive(); continue; } aoe_effect.tick_timer.tick(time.delta()); if aoe_effect.tick_timer.just_finished() { aoe_effect.already_hit_this_tick.clear(); let aoe_pos = aoe_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if aoe_effect.already_hit_this_tick.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(aoe_pos) < aoe_effect.actual_radius_sq { sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit)); horror_health.0 -= aoe_effect.actual_damage_per_tick; spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), aoe_effect.actual_damage_per_tick, &time); aoe_effect.already_hit_this_tick.push(horror_entity); } } } } }
-fn freezing_nova_effect_damage_system( mut commands: Commands, time: Res<Time>, mut nova_query: Query<(Entity, &mut FreezingNovaEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &mut Velocity), (With<Horror>, Without<crate::horror::Frozen>)>, asset_server: Res<AssetServer>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (nova_entity, mut nova, nova_g_transform, mut sprite, mut vis_transform) in nova_query.iter_mut() { nova.lifetime_timer.tick(time.delta()); let progress = nova.lifetime_timer.fraction(); let current_visual_radius = nova.radius_sq.sqrt() * 2.0 * progress; vis_transform.scale = Vec3::splat(current_visual_radius); sprite.color.set_a((1.0 - progress * progress).max(0.0)); if nova.lifetime_timer.fraction() < 0.5 && !nova.already_hit_entities.contains(&nova_entity) { let nova_pos = nova_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health, _horror_velocity) in horror_query.iter_mut() { if nova.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(nova_pos) < nova.radius_sq { horror_health.0 -= nova.damage; spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), nova.damage, &time); sound_event_writer.send(PlaySoundEvent(SoundEffect::RitualCast)); commands.entity(horror_entity).insert(crate::horror::Frozen { timer: Timer::from_seconds(nova.slow_duration_secs, TimerMode::Once), speed_multiplier: nova.slow_multiplier, }); nova.already_hit_entities.push(horror_entity); } } if !nova.already_hit_entities.contains(&nova_entity) { nova.already_hit_entities.push(nova_entity); } } if nova.lifetime_timer.finished() { commands.entity(nova_entity).despawn_recursive(); } } }
+// Fragments fan out around the parent's heading and skip whatever the parent already struck
+fn spawn_split_fragments(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    parent: &SkillProjectile,
+    origin: Vec3,
+    parent_velocity: Vec2,
+    parent_sprite: &Sprite,
+    (parent_damage, damage_type): (i32, DamageType),
+) {
+    let Some(split) = parent.split else { return; };
+    if split.fragments == 0 || parent_velocity == Vec2::ZERO { return; }
+    let fragment_damage = ((parent_damage as f32 * SPLIT_FRAGMENT_DAMAGE_SCALE).round() as i32).max(1);
+    let fragment_size = parent_sprite.custom_size.unwrap_or(Vec2::splat(10.0)) * SPLIT_FRAGMENT_SIZE_SCALE;
+    let child_split = if split.depth_left > 1 { Some(ProjectileSplit { depth_left: split.depth_left - 1, ..split }) } else { None };
+    let speed = parent_velocity.length();
+    let angle_step = if split.fragments > 1 { split.angle / (split.fragments - 1) as f32 } else { 0.0 };
+    let base_angle = parent_velocity.to_angle() - angle_step * (split.fragments - 1) as f32 / 2.0;
+
+    let visual = ProjectileVisual { texture, size: fragment_size, color: parent_sprite.color, z: None };
+    let payload = SkillProjectilePayload { skill_id: parent.skill_id, bounces: 0, split: child_split, already_hit: parent.already_hit_by_this_projectile.clone() };
+    let spec = ProjectileSpec::new(ProjectileOwner::Survivor, fragment_damage, speed, SPLIT_FRAGMENT_LIFETIME_SECS, visual, "SplitFragment").with_damage_type(damage_type).for_skill(payload);
+
+    for i in 0..split.fragments {
+        spawn_projectile(commands, spec.clone(), origin, Vec2::from_angle(base_angle + angle_step * i as f32));
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::Duration;
+#[allow(clippy::possible_missing_else)]
+fn active_skill_aoe_system(mut commands: Commands, time: Res<Time>, mut aoe_query: Query<(Entity, &mut ActiveSkillAoEEffect, &GlobalTransform, Option<&mut Sprite>, Option<&Summoned>)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health), With<Horror>>, mut sound_event_writer: EventWriter<PlaySoundEvent>, mut game_event_writer: EventWriter<GameEvent>,) { for (aoe_entity, mut aoe_effect, aoe_g_transform, opt_sprite, summoned) in aoe_query.iter_mut() { aoe_effect.lifetime_timer.tick(time.delta()); if let Some(mut sprite) = opt_sprite { let lifetime_remaining_fraction = 1.0 - aoe_effect.lifetime_timer.fraction(); let initial_alpha = sprite.color.a(); sprite.color.set_a((initial_alpha * lifetime_remaining_fraction).clamp(0.0, initial_alpha)); } if aoe_effect.lifetime_timer.finished() { commands.entity(aoe_entity).despawn_recursive(); continue; } aoe_effect.tick_timer.tick(time.delta()); if aoe_effect.tick_timer.just_finished() { aoe_effect.already_hit_this_tick.clear(); let aoe_pos = aoe_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health) in horror_query.iter_mut() { if aoe_effect.already_hit_this_tick.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(aoe_pos) < aoe_effect.actual_radius_sq { sound_event_writer.send(PlaySoundEvent::at(SoundEffect::HorrorHit, horror_pos)); horror_health.0 -= aoe_effect.actual_damage_per_tick; spawn_damage_text(&mut commands, horror_gtransform.translation(), aoe_effect.actual_damage_per_tick, &time); let origin = summoned.map_or(DamageOrigin::Skill(aoe_effect.skill_id), |summoned| DamageOrigin::Summon(summoned.0)); game_event_writer.send(GameEvent::DamageDealt { amount: aoe_effect.actual_damage_per_tick, damage_type: DamageType::Eldritch, position: horror_gtransform.translation(), origin }); commands.entity(horror_entity).insert(LastHitBySkill(aoe_effect.skill_id)); aoe_effect.already_hit_this_tick.insert(horror_entity); } } } } }
+#[allow(clippy::possible_missing_else)]
+fn freezing_nova_effect_damage_system( mut commands: Commands, time: Res<Time>, mut nova_query: Query<(Entity, &mut FreezingNovaEffect, &GlobalTransform, &mut Sprite, &mut Transform)>, mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &mut Velocity), (With<Horror>, Without<crate::horror::Frozen>)>, mut sound_event_writer: EventWriter<PlaySoundEvent>, mut game_event_writer: EventWriter<GameEvent>,) { for (nova_entity, mut nova, nova_g_transform, mut sprite, mut vis_transform) in nova_query.iter_mut() { nova.lifetime_timer.tick(time.delta()); let progress = nova.lifetime_timer.fraction(); let current_visual_radius = nova.radius_sq.sqrt() * 2.0 * progress; vis_transform.scale = Vec3::splat(current_visual_radius); sprite.color.set_a((1.0 - progress * progress).max(0.0)); if nova.lifetime_timer.fraction() < 0.5 && !nova.already_hit_entities.contains(&nova_entity) { let nova_pos = nova_g_transform.translation().truncate(); for (horror_entity, horror_gtransform, mut horror_health, _horror_velocity) in horror_query.iter_mut() { if nova.already_hit_entities.contains(&horror_entity) { continue; } let horror_pos = horror_gtransform.translation().truncate(); if horror_pos.distance_squared(nova_pos) < nova.radius_sq { horror_health.0 -= nova.damage; spawn_typed_damage_text(&mut commands, horror_gtransform.translation(), nova.damage, DamageType::Frost, &time); game_event_writer.send(GameEvent::DamageDealt { amount: nova.damage, damage_type: DamageType::Frost, position: horror_gtransform.translation(), origin: DamageOrigin::Skill(nova.skill_id) }); sound_event_writer.send(PlaySoundEvent::at(SoundEffect::RitualCast, horror_pos)); commands.entity(horror_entity).insert(crate::horror::Frozen { timer: Timer::from_seconds(nova.slow_duration_secs, TimerMode::Once), speed_multiplier: nova.slow_multiplier, }); nova.already_hit_entities.insert(horror_entity); } } nova.already_hit_entities.insert(nova_entity); } if nova.lifetime_timer.finished() { commands.entity(nova_entity).despawn_recursive(); } } }
 
-    #[test]
-    fn test_active_skill_instance_new() {
-        let skill_instance = ActiveSkillInstance::new(SkillId(1), 2);
-        assert_eq!(skill_instance.definition_id, SkillId(1));
-        assert_eq!(skill_instance.current_cooldown, Duration::ZERO);
-        assert_eq!(skill_instance.current_level, 1);
-        assert_eq!(skill_instance.flat_damage_bonus, 0);
-        assert_eq!(skill_instance.cooldown_multiplier, 1.0);
-        assert_eq!(skill_instance.aoe_radius_multiplier, 1.0);
-        assert_eq!(skill_instance.equipped_glyphs.len(), 2);
-        assert!(skill_instance.equipped_glyphs.iter().all(|g| g.is_none()));
+fn channeled_beam_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut player_query: Query<(&mut Survivor, &Transform), Without<ChanneledBeam>>,
+    mut beam_query: Query<(Entity, &mut ChanneledBeam, &mut Transform, &mut Sprite), Without<Survivor>>,
+    mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror)>,
+    skill_library: Res<SkillLibrary>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let Ok((mut player, player_transform)) = player_query.get_single_mut() else { return; };
+    let aim_direction = player.aim_direction;
+
+    for (beam_entity, mut beam, mut beam_transform, mut beam_sprite) in beam_query.iter_mut() {
+        let slot_index = player.equipped_skills.iter().position(|s| s.definition_id == beam.skill_id);
+        beam.channel_timer.tick(time.delta());
+        let still_held = slot_index.is_some_and(|idx| skill_slot_input_held(idx, &actions));
+
+        if !still_held || beam.channel_timer.finished() {
+            if let (Some(idx), Some(skill_def)) = (slot_index, skill_library.get_skill_definition(beam.skill_id)) {
+                let global_cooldown_multiplier = player.get_effective_cooldown_multiplier();
+                player.equipped_skills[idx].trigger_with_global_multiplier(skill_def.base_cooldown, global_cooldown_multiplier);
+            }
+            commands.entity(beam_entity).despawn_recursive();
+            continue;
+        }
+
+        // The beam follows the survivor and rotates with the current aim
+        let origin = player_transform.translation.truncate() + aim_direction * (SURVIVOR_SIZE.y / 2.0);
+        beam_transform.translation = origin.extend(beam_transform.translation.z);
+        beam_transform.rotation = Quat::from_rotation_z(aim_direction.y.atan2(aim_direction.x));
+        if let Some(size) = beam_sprite.custom_size.as_mut() { size.x = beam.range; }
+
+        beam.tick_timer.tick(time.delta());
+        if !beam.tick_timer.just_finished() { continue; }
+        let segment = aim_direction * beam.range;
+        for (horror_entity, horror_gtransform, mut horror_health, horror_data) in horror_query.iter_mut() {
+            let horror_pos = horror_gtransform.translation().truncate();
+            let t = ((horror_pos - origin).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let closest_point = origin + segment * t;
+            if closest_point.distance(horror_pos) < beam.width / 2.0 + horror_data.size.x / 2.0 {
+                horror_health.0 -= beam.damage_per_tick;
+                spawn_damage_text(&mut commands, horror_gtransform.translation(), beam.damage_per_tick, &time);
+                game_event_writer.send(GameEvent::DamageDealt { amount: beam.damage_per_tick, damage_type: DamageType::Eldritch, position: horror_gtransform.translation(), origin: DamageOrigin::Skill(beam.skill_id) });
+                commands.entity(horror_entity).insert(LastHitBySkill(beam.skill_id));
+                sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_active_skill_instance_trigger() {
-        let mut skill_instance = ActiveSkillInstance::new(SkillId(1), 0);
-        let base_cooldown = Duration::from_secs_f32(2.0);
-        
-        assert!(skill_instance.is_ready());
-        skill_instance.trigger(base_cooldown);
-        assert!(!skill_instance.is_ready());
-        assert_eq!(skill_instance.current_cooldown, base_cooldown);
+fn reflective_ward_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ward_query: Query<(Entity, &mut ReflectiveWardEffect, &GlobalTransform, &mut Sprite)>,
+    mut projectile_query: Query<(Entity, &GlobalTransform, &mut Transform, &mut Velocity, &mut Damage, &mut Lifetime), (With<HorrorProjectile>, With<Reflectable>)>,
+    player_query: Query<&Survivor>,
+) {
+    let damage_bonus = player_query.get_single().map_or(0, |player| player.ichor_blast_damage_bonus);
+    // The ownership transfer only lands once commands apply, so overlapping wards would otherwise each flip the same projectile
+    let mut reflected = EntityHashSet::default();
+    for (ward_entity, mut ward, ward_g_transform, mut sprite) in ward_query.iter_mut() {
+        ward.lifetime_timer.tick(time.delta());
+        if ward.lifetime_timer.finished() {
+            commands.entity(ward_entity).despawn_recursive();
+            continue;
+        }
+        sprite.color.set_a(0.35 * (1.0 - ward.lifetime_timer.fraction() * 0.5));
+        let ward_pos = ward_g_transform.translation().truncate();
+        for (projectile_entity, projectile_g_transform, mut projectile_transform, mut velocity, mut damage, mut lifetime) in projectile_query.iter_mut() {
+            if reflected.contains(&projectile_entity) || projectile_g_transform.translation().truncate().distance_squared(ward_pos) > ward.radius_sq { continue; }
+            reflected.insert(projectile_entity);
+            velocity.0 = -velocity.0;
+            projectile_transform.rotation = Quat::from_rotation_z(velocity.0.y.atan2(velocity.0.x));
+            damage.0 += damage_bonus;
+            lifetime.timer.reset();
+            transfer_projectile_ownership(&mut commands, projectile_entity, ProjectileOwner::Survivor);
+        }
+    }
+}
 
-        // Test with cooldown multiplier
-        skill_instance.current_cooldown = Duration::ZERO; // Reset cooldown
-        skill_instance.cooldown_multiplier = 0.5;
-        skill_instance.trigger(base_cooldown);
-        assert_eq!(skill_instance.current_cooldown, Duration::from_secs_f32(1.0));
+fn decoy_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut decoy_query: Query<(Entity, &mut DecoyEffect, &mut Health, &GlobalTransform, &mut Sprite), Without<Horror>>,
+    horror_query: Query<(&GlobalTransform, &Horror)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    for (decoy_entity, mut decoy, mut decoy_health, decoy_g_transform, mut sprite) in decoy_query.iter_mut() {
+        let decoy_pos = decoy_g_transform.translation().truncate();
+        decoy.lifetime_timer.tick(time.delta());
+        decoy.contact_tick_timer.tick(time.delta());
+        if decoy.contact_tick_timer.just_finished() {
+            for (horror_gtransform, horror_data) in horror_query.iter() {
+                if horror_gtransform.translation().truncate().distance(decoy_pos) < SURVIVOR_SIZE.x / 2.0 + horror_data.size.x / 2.0 {
+                    decoy_health.0 -= horror_data.damage_on_collision;
+                }
+            }
+            sprite.color.set_a(0.4 + 0.3 * (decoy_health.0.max(0) as f32 / decoy.max_health.max(1) as f32).min(1.0));
+        }
+        if decoy_health.0 > 0 && !decoy.lifetime_timer.finished() { continue; }
+
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load("sprites/eldritch_nova_effect_placeholder.png"),
+                sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: Color::rgba(0.6, 0.6, 0.9, 0.7), ..default() },
+                transform: Transform::from_translation(decoy_pos.extend(0.3)),
+                ..default()
+            },
+            ExplosionEffect {
+                damage: decoy.explosion_damage,
+                radius_sq: decoy.explosion_radius.powi(2),
+                timer: Timer::from_seconds(0.3, TimerMode::Once),
+                already_hit_entities: Vec::new(),
+                origin: DamageOrigin::Summon(SummonKind::HollowEffigy),
+            },
+            Summoned(SummonKind::HollowEffigy),
+            Name::new("HollowEffigyBurst"),
+        ));
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorDeath));
+        commands.entity(decoy_entity).despawn_recursive();
     }
+}
 
-    #[test]
-    fn test_active_skill_instance_tick_cooldown() {
-        let mut skill_instance = ActiveSkillInstance::new(SkillId(1), 0);
-        skill_instance.current_cooldown = Duration::from_secs(5);
-        
-        skill_instance.tick_cooldown(Duration::from_secs(1));
-        assert_eq!(skill_instance.current_cooldown, Duration::from_secs(4));
+// Owns the TimeScale component: every frame it is recomputed from the bubbles an entity stands in and the kill cam,
+// and removed once neither slows it. Bubbles leave the survivor's own shots alone; the kill cam slows everything
+fn time_bubble_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    kill_cam: Res<KillCam>,
+    mut bubble_query: Query<(Entity, &mut TimeBubbleEffect, &GlobalTransform, &mut Sprite)>,
+    affected_query: Query<(Entity, &GlobalTransform, Option<&TimeScale>, Has<Survivor>, Option<&ProjectileOwner>), Or<(With<Horror>, With<HorrorProjectile>, With<Survivor>, With<ProjectileOwner>)>>,
+) {
+    let mut active_bubbles = Vec::new();
+    for (bubble_entity, mut bubble, bubble_g_transform, mut sprite) in bubble_query.iter_mut() {
+        bubble.lifetime_timer.tick(time.delta());
+        if bubble.lifetime_timer.finished() {
+            commands.entity(bubble_entity).despawn_recursive();
+            continue;
+        }
+        sprite.color.set_a(0.25 * (1.0 - bubble.lifetime_timer.fraction()).sqrt());
+        active_bubbles.push((bubble_g_transform.translation().truncate(), bubble.radius_sq, bubble.horror_time_scale, bubble.survivor_time_scale));
+    }
 
-        skill_instance.tick_cooldown(Duration::from_secs(5)); // Tick past zero
-        assert_eq!(skill_instance.current_cooldown, Duration::ZERO);
+    let kill_cam_scale = kill_cam.time_scale();
+    for (entity, g_transform, current_scale, is_survivor, owner) in affected_query.iter() {
+        let pos = g_transform.translation().truncate();
+        let bubble_scale = if owner == Some(&ProjectileOwner::Survivor) { None } else {
+            active_bubbles.iter()
+                .filter(|(bubble_pos, radius_sq, _, _)| bubble_pos.distance_squared(pos) < *radius_sq)
+                .map(|(_, _, horror_scale, survivor_scale)| if is_survivor { *survivor_scale } else { *horror_scale })
+                .reduce(f32::min)
+        };
+        let scale = match bubble_scale {
+            Some(scale) => Some(scale * kill_cam_scale),
+            None if kill_cam_scale < 1.0 => Some(kill_cam_scale),
+            None => None,
+        };
+        match (scale, current_scale) {
+            (Some(scale), Some(current)) if current.0 == scale => {}
+            (Some(scale), _) => { commands.entity(entity).insert(TimeScale(scale)); }
+            (None, Some(_)) => { commands.entity(entity).remove::<TimeScale>(); }
+            (None, None) => {}
+        }
+    }
+}
+
+fn ricochet_orb_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_config: Res<GameConfig>,
+    mut orb_query: Query<(Entity, &mut RicochetOrb, &mut Transform, &mut Velocity, &mut Sprite, &Summoned), Without<Horror>>,
+    mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    for (orb_entity, mut orb, mut orb_transform, mut velocity, mut sprite, summoned) in orb_query.iter_mut() {
+        orb.lifetime_timer.tick(time.delta());
+        if orb.lifetime_timer.finished() {
+            commands.entity(orb_entity).despawn_recursive();
+            continue;
+        }
+        sprite.color.set_a(1.0 - orb.lifetime_timer.fraction().powi(4));
+
+        // Orbs stay inside the arena by bouncing off its edges
+        let mut orb_pos = orb_transform.translation.truncate() + velocity.0 * time.delta_seconds();
+        let clamped_pos = game_config.clamp_to_arena(orb_pos, orb.radius);
+        if clamped_pos.x != orb_pos.x { velocity.0.x = -velocity.0.x; }
+        if clamped_pos.y != orb_pos.y { velocity.0.y = -velocity.0.y; }
+        orb_pos = clamped_pos;
+        orb_transform.translation = orb_pos.extend(orb_transform.translation.z);
+
+        let hit = horror_query.iter_mut().find(|(horror_entity, horror_gtransform, _, horror_data)| {
+            Some(*horror_entity) != orb.last_hit && horror_gtransform.translation().truncate().distance(orb_pos) < orb.radius + horror_data.size.x / 2.0
+        });
+        let Some((horror_entity, horror_gtransform, mut horror_health, _)) = hit else { continue; };
+        let damage = orb.current_damage();
+        horror_health.0 -= damage;
+        spawn_damage_text(&mut commands, horror_gtransform.translation(), damage, &time);
+        game_event_writer.send(summoned.hit(damage, DamageType::Eldritch, horror_gtransform.translation()));
+        commands.entity(horror_entity).insert(LastHitBySkill(orb.skill_id));
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+        let hit_pos = horror_gtransform.translation().truncate();
+        orb.last_hit = Some(horror_entity);
+        orb.bounces += 1;
+
+        // Leap to the nearest other horror in range, or glance off the one just hit
+        let speed = velocity.0.length();
+        let next_target = horror_query.iter()
+            .filter(|(entity, ..)| *entity != horror_entity)
+            .map(|(_, gtransform, ..)| gtransform.translation().truncate())
+            .filter(|pos| pos.distance_squared(hit_pos) < orb.retarget_radius_sq)
+            .min_by(|a, b| a.distance_squared(orb_pos).total_cmp(&b.distance_squared(orb_pos)));
+        let new_direction = match next_target {
+            Some(target_pos) => (target_pos - orb_pos).normalize_or_zero(),
+            None => (orb_pos - hit_pos).normalize_or_zero(),
+        };
+        if new_direction != Vec2::ZERO { velocity.0 = new_direction * speed; }
+    }
+}
+
+fn skill_kill_cooldown_reset_system(
+    mut game_events: EventReader<GameEvent>,
+    mut player_query: Query<&mut Survivor>,
+    glyph_library: Res<GlyphLibrary>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Ok(mut player) = player_query.get_single_mut() else { game_events.clear(); return; };
+    let rng = &mut game_rng.0;
+    for skill_id in game_events.read().filter_map(|event| event.as_horror_kill()?.skill) {
+        let Some(skill_instance) = player.equipped_skills.iter_mut().find(|s| s.definition_id == skill_id) else { continue; };
+        if skill_instance.is_ready() { continue; }
+        let reset_chance: f32 = skill_instance.equipped_glyphs.iter().flatten()
+            .filter_map(|glyph_id| glyph_library.get_glyph_definition(*glyph_id))
+            .filter_map(|glyph_def| match glyph_def.effect { GlyphEffectType::CooldownResetOnKill { chance } => Some(chance), _ => None })
+            .sum();
+        if reset_chance > 0.0 && rng.gen_bool(reset_chance.min(1.0) as f64) {
+            skill_instance.current_cooldown = Duration::ZERO;
+        }
     }
 }
 
@@ -389,4 +765,4 @@ mod tests {
         skill_instance.tick_cooldown(Duration::from_secs(5)); // Tick past zero
         assert_eq!(skill_instance.current_cooldown, Duration::ZERO);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{
+    game::AppState,
+    ichor_blast::ICHOR_BLAST_COLOR,
+    platform_services::{Achievement, AchievementUnlocked},
+    ranking::Remnants,
+};
+
+const NEXT_SKIN_KEY: KeyCode = KeyCode::KeyK;
+const BUY_SKIN_KEY: KeyCode = KeyCode::KeyU;
+const SKINS_MENU_COLOR: Color = Color::rgb(0.85, 0.75, 0.5);
+
+pub struct SkinsPlugin;
+
+impl Plugin for SkinsPlugin {
+    fn build(&self, app: &mut App) {
+        // The storefront plugin isn't there in headless runs, so the event is registered here as well
+        app.init_resource::<SkinCollection>()
+            .init_resource::<Wardrobe>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_wardrobe_menu)
+            .add_systems(Update, (browse_wardrobe, update_wardrobe_menu).chain().run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), despawn_wardrobe_menu)
+            .add_systems(Update, unlock_skins_from_achievements);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SkinId {
+    #[default]
+    Wanderer,
+    Voidborn,
+    AshenPilgrim,
+    CrimsonHunter,
+    RiftWalker,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkinUnlock {
+    Free,
+    Remnants(u32),
+    Achievement(Achievement),
+}
+
+// Purely cosmetic: a sprite and tint for the survivor, and the colour of its ichor blasts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkinDefinition {
+    pub name: &'static str,
+    pub sprite: &'static str,
+    pub sprite_tint: Color,
+    pub projectile_tint: Color,
+    pub unlock: SkinUnlock,
+}
+
+impl SkinId {
+    pub const ALL: [SkinId; 5] = [SkinId::Wanderer, SkinId::Voidborn, SkinId::AshenPilgrim, SkinId::CrimsonHunter, SkinId::RiftWalker];
+
+    pub fn definition(&self) -> SkinDefinition {
+        match self {
+            SkinId::Wanderer => SkinDefinition { name: "Wanderer", sprite: "sprites/survivor_placeholder.png", sprite_tint: Color::WHITE, projectile_tint: ICHOR_BLAST_COLOR, unlock: SkinUnlock::Free },
+            SkinId::Voidborn => SkinDefinition { name: "Voidborn", sprite: "sprites/player_ship_eldritch.png", sprite_tint: Color::WHITE, projectile_tint: Color::rgb(0.3, 0.9, 0.8), unlock: SkinUnlock::Remnants(150) },
+            SkinId::AshenPilgrim => SkinDefinition { name: "Ashen Pilgrim", sprite: "sprites/survivor_placeholder.png", sprite_tint: Color::rgb(0.75, 0.7, 0.65), projectile_tint: Color::rgb(1.0, 0.55, 0.25), unlock: SkinUnlock::Remnants(300) },
+            SkinId::CrimsonHunter => SkinDefinition { name: "Crimson Hunter", sprite: "sprites/survivor_placeholder.png", sprite_tint: Color::rgb(1.0, 0.5, 0.5), projectile_tint: Color::rgb(1.0, 0.2, 0.2), unlock: SkinUnlock::Achievement(Achievement::SlayElite) },
+            SkinId::RiftWalker => SkinDefinition { name: "Rift Walker", sprite: "sprites/player_ship.png", sprite_tint: Color::rgb(0.6, 0.9, 1.0), projectile_tint: Color::rgb(0.4, 0.9, 1.0), unlock: SkinUnlock::Achievement(Achievement::Extract) },
+        }
+    }
+
+    pub fn next(&self) -> SkinId {
+        let index = Self::ALL.iter().position(|skin| skin == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// Kept in the profile: every skin the player owns and the one the next survivor wears
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkinCollection {
+    pub owned: Vec<SkinId>,
+    pub selected: SkinId,
+}
+
+impl Default for SkinCollection {
+    fn default() -> Self { Self { owned: vec![SkinId::Wanderer], selected: SkinId::Wanderer } }
+}
+
+impl SkinCollection {
+    pub fn owns(&self, skin: SkinId) -> bool {
+        skin.definition().unlock == SkinUnlock::Free || self.owned.contains(&skin)
+    }
+
+    pub fn select(&mut self, skin: SkinId) -> bool {
+        if !self.owns(skin) { return false; }
+        self.selected = skin;
+        true
+    }
+
+    fn grant(&mut self, skin: SkinId) {
+        if !self.owned.contains(&skin) { self.owned.push(skin); }
+    }
+
+    // Spends the remnants and wears the skin straight away; achievement skins can't be bought
+    pub fn buy(&mut self, skin: SkinId, remnants: &mut u32) -> bool {
+        let SkinUnlock::Remnants(cost) = skin.definition().unlock else { return false; };
+        if self.owns(skin) || *remnants < cost { return false; }
+        *remnants -= cost;
+        self.grant(skin);
+        self.selected = skin;
+        true
+    }
+
+    // Returns the skins the achievement newly unlocked
+    pub fn unlock_for_achievement(&mut self, achievement: Achievement) -> Vec<SkinId> {
+        let unlocked: Vec<SkinId> = SkinId::ALL.into_iter().filter(|skin| skin.definition().unlock == SkinUnlock::Achievement(achievement) && !self.owns(*skin)).collect();
+        for skin in unlocked.iter() { self.grant(*skin); }
+        unlocked
+    }
+
+    // A hand-edited profile wearing a skin it doesn't own falls back to the default
+    pub fn sanitized(mut self) -> Self {
+        if !self.owns(self.selected) { self.selected = SkinId::default(); }
+        self
+    }
+
+    pub fn selected_definition(&self) -> SkinDefinition { self.selected.definition() }
+}
+
+// The skin shown on the main menu; it can be one the player doesn't own yet
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Wardrobe {
+    pub shown: SkinId,
+}
+
+pub fn wardrobe_label(collection: &SkinCollection, shown: SkinId) -> String {
+    let definition = shown.definition();
+    let status = if collection.selected == shown {
+        "wearing".to_string()
+    } else if collection.owns(shown) {
+        "owned".to_string()
+    } else {
+        match definition.unlock {
+            SkinUnlock::Free => "owned".to_string(),
+            SkinUnlock::Remnants(cost) => format!("{} remnants to unlock (U)", cost),
+            SkinUnlock::Achievement(achievement) => format!("unlocked by: {}", achievement.title()),
+        }
+    };
+    format!("Skin: {} - {} (K)", definition.name, status)
+}
+
+#[derive(Component)]
+struct WardrobeMenuText;
+
+fn setup_wardrobe_menu(mut commands: Commands, asset_server: Res<AssetServer>, collection: Res<SkinCollection>, mut wardrobe: ResMut<Wardrobe>) {
+    wardrobe.shown = collection.selected;
+    commands.spawn((
+        TextBundle::from_section(wardrobe_label(&collection, wardrobe.shown), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: SKINS_MENU_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(20.0), left: Val::Px(20.0), ..default() }),
+        WardrobeMenuText,
+    ));
+}
+
+// Browsing onto an owned skin puts it on; a locked one is only previewed until it is bought
+fn browse_wardrobe(keyboard_input: Res<ButtonInput<KeyCode>>, mut wardrobe: ResMut<Wardrobe>, mut collection: ResMut<SkinCollection>, mut remnants: ResMut<Remnants>) {
+    if keyboard_input.just_pressed(NEXT_SKIN_KEY) {
+        wardrobe.shown = wardrobe.shown.next();
+        if collection.owns(wardrobe.shown) { collection.select(wardrobe.shown); }
+    }
+    if keyboard_input.just_pressed(BUY_SKIN_KEY) && !collection.owns(wardrobe.shown) {
+        let mut balance = remnants.0;
+        if collection.buy(wardrobe.shown, &mut balance) { remnants.0 = balance; }
+    }
+}
+
+fn update_wardrobe_menu(wardrobe: Res<Wardrobe>, collection: Res<SkinCollection>, mut text_query: Query<&mut Text, With<WardrobeMenuText>>) {
+    if !wardrobe.is_changed() && !collection.is_changed() { return; }
+    for mut text in text_query.iter_mut() { text.sections[0].value = wardrobe_label(&collection, wardrobe.shown); }
+}
+
+fn despawn_wardrobe_menu(mut commands: Commands, text_query: Query<Entity, With<WardrobeMenuText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+fn unlock_skins_from_achievements(mut unlocked_events: EventReader<AchievementUnlocked>, mut collection: ResMut<SkinCollection>) {
+    for AchievementUnlocked(achievement) in unlocked_events.read() {
+        for skin in collection.unlock_for_achievement(*achievement) { info!("Unlocked the {} skin", skin.definition().name); }
+    }
+}
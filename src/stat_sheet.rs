@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::{Survivor, MAX_COOLDOWN_REDUCTION},
+    game::AppState,
+};
+
+pub struct StatSheetPlugin;
+
+impl Plugin for StatSheetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::InGame), setup_stat_sheet)
+            .add_systems(Update, (
+                toggle_stat_sheet_system,
+                update_stat_sheet_system,
+            ).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(OnExit(AppState::InGame), despawn_stat_sheet);
+    }
+}
+
+#[derive(Component)]
+struct StatSheetText;
+
+// One line per survivor-wide stat; hidden until toggled with C
+pub fn stat_sheet_lines(survivor: &Survivor) -> Vec<String> {
+    vec![
+        format!("Level: {}", survivor.level),
+        format!("Max Endurance: {}", survivor.max_health),
+        format!("Regeneration: {:.1}/s", survivor.health_regen_rate),
+        format!("Speed: {:.0}", survivor.speed),
+        format!("Ichor Blast Damage: +{}", survivor.ichor_blast_damage_bonus),
+        format!("Additional Ichor Blasts: {}", survivor.additional_ichor_blasts),
+        format!("Area of Effect: {:.0}%", survivor.area_multiplier * 100.0),
+        format!("Duration: {:.0}%", survivor.duration_multiplier * 100.0),
+        format!("Cooldown Reduction: {:.0}% (max {:.0}%)", (1.0 - survivor.get_effective_cooldown_multiplier()) * 100.0, MAX_COOLDOWN_REDUCTION * 100.0),
+        format!("Luck: {:.0}%", survivor.luck * 100.0),
+        format!("Pickup Radius: {:.0}", survivor.get_effective_pickup_radius()),
+        format!("Echoes Gain: {:.0}%", survivor.xp_gain_multiplier * 100.0),
+    ]
+}
+
+fn setup_stat_sheet(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 16.0, color: Color::rgba(0.9, 0.9, 0.9, 0.9) },
+        ).with_style(Style { position_type: PositionType::Absolute, top: Val::Px(120.0), right: Val::Px(15.0), ..default() }),
+        Visibility::Hidden,
+        StatSheetText,
+        Name::new("StatSheet"),
+    ));
+}
+
+fn toggle_stat_sheet_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut sheet_query: Query<&mut Visibility, With<StatSheetText>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyC) { return; }
+    for mut visibility in sheet_query.iter_mut() {
+        *visibility = if *visibility == Visibility::Hidden { Visibility::Visible } else { Visibility::Hidden };
+    }
+}
+
+fn update_stat_sheet_system(
+    player_query: Query<&Survivor>,
+    mut sheet_query: Query<(&mut Text, &Visibility), With<StatSheetText>>,
+) {
+    let Ok((mut text, visibility)) = sheet_query.get_single_mut() else { return; };
+    if *visibility == Visibility::Hidden { return; }
+    let Ok(survivor) = player_query.get_single() else { return; };
+    text.sections[0].value = stat_sheet_lines(survivor).join("\n");
+}
+
+fn despawn_stat_sheet(mut commands: Commands, sheet_query: Query<Entity, With<StatSheetText>>) {
+    for entity in sheet_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use std::io::{self, Read, Write};
+use steamworks::{Client, SingleClient};
+use crate::{
+    platform::{FileStorage, GameStorage, Storage},
+    platform_services::{Achievement, PlatformService, PlatformServices},
+    save_data::SAVE_DATA_PATH,
+};
+
+// Only the profile follows the player between machines; run history and exported builds stay local
+const CLOUD_SYNCED_KEYS: [&str; 1] = [SAVE_DATA_PATH];
+const RICH_PRESENCE_KEY: &str = "status";
+
+pub fn install(app: &mut App) {
+    let (client, single) = match Client::init() {
+        Ok(clients) => clients,
+        Err(error) => {
+            warn!("Steam is unavailable, running without it: {}", error);
+            return;
+        }
+    };
+    app.insert_resource(PlatformServices(Box::new(SteamService { client: client.clone() })))
+        .insert_resource(GameStorage(Box::new(SteamCloudStorage { local: FileStorage::default(), client })))
+        .insert_non_send_resource(single)
+        .add_systems(First, run_steam_callbacks);
+}
+
+fn run_steam_callbacks(single: NonSend<SingleClient>) {
+    single.run_callbacks();
+}
+
+struct SteamService {
+    client: Client,
+}
+
+impl PlatformService for SteamService {
+    fn unlock_achievement(&mut self, achievement: Achievement) {
+        let stats = self.client.user_stats();
+        if stats.achievement(achievement.api_name()).set().is_err() {
+            warn!("Steam would not unlock {}", achievement.api_name());
+            return;
+        }
+        // The unlock only reaches Steam, and its overlay, once the stats are stored
+        if stats.store_stats().is_err() { warn!("Steam would not store the unlock of {}", achievement.api_name()); }
+    }
+
+    fn set_rich_presence(&mut self, status: &str) {
+        if !self.client.friends().set_rich_presence(RICH_PRESENCE_KEY, Some(status)) { warn!("Steam rejected rich presence \"{}\"", status); }
+    }
+}
+
+// Files are still written locally, with their backups, and the synced ones are mirrored to Steam Cloud.
+// A machine with no local copy of a synced file starts from the cloud one
+struct SteamCloudStorage {
+    local: FileStorage,
+    client: Client,
+}
+
+impl SteamCloudStorage {
+    fn is_synced(key: &str) -> bool { CLOUD_SYNCED_KEYS.contains(&key) }
+
+    fn upload(&self, key: &str, contents: &str) {
+        if !Self::is_synced(key) { return; }
+        let mut writer = self.client.remote_storage().file(key).write();
+        if let Err(error) = writer.write_all(contents.as_bytes()) { warn!("Could not upload {} to Steam Cloud: {}", key, error); }
+    }
+}
+
+impl Storage for SteamCloudStorage {
+    fn read(&self, key: &str) -> io::Result<String> {
+        match self.local.read(key) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound && Self::is_synced(key) => {
+                let file = self.client.remote_storage().file(key);
+                if !file.exists() { return Err(error); }
+                let mut contents = String::new();
+                file.read().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+            result => result,
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> io::Result<()> {
+        self.local.write(key, contents)?;
+        self.upload(key, contents);
+        Ok(())
+    }
+
+    fn write_keeping_backup(&self, key: &str, contents: &str) -> io::Result<()> {
+        self.local.write_keeping_backup(key, contents)?;
+        self.upload(key, contents);
+        Ok(())
+    }
+}
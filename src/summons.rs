@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use crate::{
+    components::DamageType,
+    game_events::{GameEvent, DamageOrigin},
+};
+
+// Everything the survivor leaves in the world to deal damage on its own. New pets get a kind here,
+// so their hits are broken out in the run stats and the DPS meter from the first frame they exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SummonKind { PsychicSentry, HollowEffigy, WanderingEye, NightmareLarva, }
+
+impl SummonKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SummonKind::PsychicSentry => "Psychic Sentry",
+            SummonKind::HollowEffigy => "Hollow Effigy",
+            SummonKind::WanderingEye => "Wandering Eye",
+            SummonKind::NightmareLarva => "Nightmare Larva",
+        }
+    }
+}
+
+// Owner tag for spawned damagers; whatever system applies their damage reports it under this kind
+// instead of the skill or weapon that spawned them
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summoned(pub SummonKind);
+
+impl Summoned {
+    pub fn hit(&self, amount: i32, damage_type: DamageType, position: Vec3) -> GameEvent {
+        GameEvent::DamageDealt { amount, damage_type, position, origin: DamageOrigin::Summon(self.0) }
+    }
+}
@@ -1,66 +1,191 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 use std::time::Duration;
 use rand::Rng;
 use crate::{
-    components::{Velocity, Health as ComponentHealth},
-    game::{AppState, ItemCollectedEvent},
-    ichor_blast::{spawn_ichor_blast, BASE_FRAGMENT_DAMAGE, BASE_FRAGMENT_SPEED}, // Renamed
-    horror::Horror, // Renamed
-    weapons::{CircleOfWarding, SwarmOfNightmares}, // Renamed
+    components::{Velocity, Health as ComponentHealth, TimeScale, DamageType},
+    game::{AppState, ItemCollectedEvent, GameConfig, GameRng},
+    ichor_blast::{spawn_ichor_blast, BASE_FRAGMENT_DAMAGE, ICHOR_BLAST_COLOR}, // Renamed
+    horror::{Horror, ContactDamage, contact_knockback}, // Renamed
+    weapons::{CircleOfWarding, SwarmOfNightmares, SearingGaze, SporeCenser, RearGuard, SnareLayer, HookedCrescent}, // Renamed
     audio::{PlaySoundEvent, SoundEffect},
-    skills::{ActiveSkillInstance, SkillLibrary, SkillId, SurvivorBuffEffect}, // Renamed
-    items::{ItemId, ItemDrop, ItemLibrary, ItemEffect, RetaliationNovaEffect}, // ItemEffect will be updated
+    game_events::{GameEvent, DamageOrigin},
+    skills::{ActiveSkillInstance, SkillLibrary, SkillId, SurvivorBuffEffect, LastHitBySkill, MAX_SKILL_SLOTS, MAX_GLYPH_SLOTS}, // Renamed
+    items::{ItemId, ItemDrop, ItemLibrary, ItemEffect, RetaliationNovaEffect, ThornsCooldown}, // ItemEffect will be updated
+    visual_effects::spawn_damage_text,
     glyphs::GlyphId,
+    consumables::ConsumableInventory,
+    overwhelm::Overwhelm,
+    challenge::ActiveChallenge,
+    corruption::RunDifficulty,
+    aggro::{Threat, SURVIVOR_THREAT_WEIGHT},
+    coop::PlayerSlot,
+    input_actions::{ActionState, AimInput},
+    balance::BalanceConfig,
+    skins::SkinCollection,
+    perf_overlay::timed_collision,
 };
 
 pub const SURVIVOR_SIZE: Vec2 = Vec2::new(50.0, 50.0); // Renamed
 const XP_FOR_LEVEL: [u32; 10] = [100, 150, 250, 400, 600, 850, 1100, 1400, 1800, 2500];
 pub const BASE_PICKUP_RADIUS: f32 = 100.0;
-const PROJECTILE_SPREAD_ANGLE_DEGREES: f32 = 10.0;
+const REAR_GUARD_SHOTS_PER_ADDITIONAL_BLAST: u32 = 2;
+pub const MAX_COOLDOWN_REDUCTION: f32 = 0.6;
 pub const INITIAL_SURVIVOR_MAX_HEALTH: i32 = 100; // Renamed
+pub const SURVIVOR_HIT_INVINCIBILITY_SECS: f32 = 1.0;
+pub const CONTACT_GRACE_SECS: f32 = 0.3;
+pub const PROJECTILE_GRACE_SECS: f32 = 0.5;
+pub const HAZARD_GRACE_SECS: f32 = 0.75;
+// With a stick there is no cursor, so anything targeting the aim point gets a spot this far ahead instead
+const AIM_DIRECTION_REACH: f32 = 250.0;
+// Below this share of max health the heartbeat, vignette and muffled music kick in
+pub const LOW_HEALTH_THRESHOLD: f32 = 0.25;
+// Feedback starts this strong at the threshold and climbs to full strength at zero health
+const LOW_HEALTH_MIN_INTENSITY: f32 = 0.3;
+const LOW_HEALTH_CALM_BPM: f32 = 70.0;
+const LOW_HEALTH_PANIC_BPM: f32 = 140.0;
 const BASE_SURVIVOR_SPEED: f32 = 250.0; // Renamed (assuming this should also be survivor speed)
 const ITEM_COLLECTION_RADIUS: f32 = SURVIVOR_SIZE.x / 2.0 + crate::items::ITEM_DROP_SIZE.x / 2.0; // Renamed
 
 #[derive(Component)] pub struct SanityStrain { pub base_fire_rate_secs: f32, pub fire_timer: Timer, }
 impl Default for SanityStrain { fn default() -> Self { let base = 0.5; Self { base_fire_rate_secs: base, fire_timer: Timer::from_seconds(base, TimerMode::Repeating), } } }
 pub struct SurvivorPlugin; // Renamed
+
+// Each kind of harm runs its own grace window, so being grazed by a projectile doesn't shield against contact or hazards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource { Contact, Projectile, Hazard, }
+impl DamageSource { pub fn grace_secs(self) -> f32 { match self { DamageSource::Contact => CONTACT_GRACE_SECS, DamageSource::Projectile => PROJECTILE_GRACE_SECS, DamageSource::Hazard => HAZARD_GRACE_SECS, } } }
+
+#[derive(Debug, Clone)]
+pub struct DamageGrace { contact: Timer, projectile: Timer, hazard: Timer, }
+impl Default for DamageGrace { fn default() -> Self { let finished = |secs: f32| { let mut timer = Timer::from_seconds(secs, TimerMode::Once); timer.tick(timer.duration()); timer }; Self { contact: finished(CONTACT_GRACE_SECS), projectile: finished(PROJECTILE_GRACE_SECS), hazard: finished(HAZARD_GRACE_SECS), } } }
+impl DamageGrace {
+    fn timer(&self, source: DamageSource) -> &Timer { match source { DamageSource::Contact => &self.contact, DamageSource::Projectile => &self.projectile, DamageSource::Hazard => &self.hazard, } }
+    pub fn is_immune(&self, source: DamageSource) -> bool { !self.timer(source).finished() }
+    pub fn any_active(&self) -> bool { [DamageSource::Contact, DamageSource::Projectile, DamageSource::Hazard].into_iter().any(|source| self.is_immune(source)) }
+    pub fn start(&mut self, source: DamageSource) { self.start_for(source, source.grace_secs()); }
+    pub fn start_for(&mut self, source: DamageSource, grace_secs: f32) { let timer = Timer::from_seconds(grace_secs, TimerMode::Once); match source { DamageSource::Contact => self.contact = timer, DamageSource::Projectile => self.projectile = timer, DamageSource::Hazard => self.hazard = timer, } }
+    pub fn tick(&mut self, delta: Duration) { self.contact.tick(delta); self.projectile.tick(delta); self.hazard.tick(delta); }
+}
 #[derive(Component)]
 pub struct Survivor {
     pub speed: f32, pub experience: u32, pub current_level_xp: u32, pub level: u32,
-    pub aim_direction: Vec2, pub invincibility_timer: Timer,
+    pub aim_direction: Vec2, pub aim_world_position: Vec2, pub invincibility_timer: Timer, pub damage_grace: DamageGrace,
     pub ichor_blast_damage_bonus: i32, pub ichor_blast_speed_multiplier: f32, pub ichor_blast_piercing: u32, // Renamed fields
     pub xp_gain_multiplier: f32, pub pickup_radius_multiplier: f32, pub additional_ichor_blasts: u32, // Renamed field
+    pub area_multiplier: f32, pub cooldown_reduction: f32, pub luck: f32, pub duration_multiplier: f32,
     pub max_health: i32, pub health_regen_rate: f32,
     pub equipped_skills: Vec<ActiveSkillInstance>,
+    // Learned while every slot was full, or swapped out from the pause menu; they keep their upgrades and glyphs
+    pub learned_skills: Vec<ActiveSkillInstance>,
     pub collected_item_ids: Vec<ItemId>,
+    pub spent_revival_item_ids: Vec<ItemId>,
     pub collected_glyphs: Vec<GlyphId>,
+    // Set from the chosen skin when the survivor spawns
+    pub projectile_tint: Color,
 }
 
 impl Survivor {
+    #[allow(clippy::possible_missing_else)]
     pub fn experience_to_next_level(&self) -> u32 { if self.level == 0 { return 0; } if (self.level as usize -1) < XP_FOR_LEVEL.len() { XP_FOR_LEVEL[self.level as usize - 1] } else { XP_FOR_LEVEL.last().unwrap_or(&2500) + (self.level - XP_FOR_LEVEL.len() as u32) * 500 } }
-    pub fn add_experience( &mut self, amount: u32, next_state_value: &mut NextState<AppState>, sound_event_writer: &mut EventWriter<PlaySoundEvent>,) { let actual_xp_gained = (amount as f32 * self.xp_gain_multiplier).round() as u32; self.current_level_xp += actual_xp_gained; self.experience += actual_xp_gained; while self.current_level_xp >= self.experience_to_next_level() && self.level > 0 { let needed = self.experience_to_next_level(); self.current_level_xp -= needed; self.level += 1; sound_event_writer.send(PlaySoundEvent(SoundEffect::Revelation)); next_state_value.set(AppState::LevelUp); if next_state_value.0 == Some(AppState::LevelUp) { break; } } } // SoundEffect::LevelUp to SoundEffect::Revelation
+    // Banks the XP and returns how many levels it was worth; each of them gets its own upgrade choice
+    pub fn gain_experience(&mut self, amount: u32) -> u32 { self.bank_experience((amount as f32 * self.xp_gain_multiplier).round() as u32) }
+    // Adds XP that already had its gain multiplier applied, as the shared co-op pool hands it out
+    pub fn bank_experience(&mut self, actual_xp_gained: u32) -> u32 { self.current_level_xp += actual_xp_gained; self.experience += actual_xp_gained; let mut levels_gained = 0; while self.current_level_xp >= self.experience_to_next_level() && self.level > 0 { let needed = self.experience_to_next_level(); self.current_level_xp -= needed; self.level += 1; levels_gained += 1; } levels_gained }
+    pub fn start_invincibility(&mut self, duration_secs: f32) { self.invincibility_timer = Timer::from_seconds(duration_secs, TimerMode::Once); }
+    // Full invincibility (spawn, revival) blocks everything; otherwise only the source's own grace window matters
+    pub fn can_take_damage_from(&self, source: DamageSource) -> bool { self.invincibility_timer.finished() && !self.damage_grace.is_immune(source) }
+    pub fn register_hit(&mut self, source: DamageSource) { self.damage_grace.start(source); }
+    pub fn register_hit_with_grace(&mut self, source: DamageSource, grace_secs: f32) { self.damage_grace.start_for(source, grace_secs); }
+    // A glyph slot card is only worth offering while some equipped skill can still take another socket
+    pub fn can_add_glyph_slot(&self) -> bool { self.equipped_skills.iter().any(|skill| skill.equipped_glyphs.len() < MAX_GLYPH_SLOTS) }
+    pub fn knows_skill(&self, skill_id: SkillId) -> bool { self.equipped_skills.iter().chain(self.learned_skills.iter()).any(|skill| skill.definition_id == skill_id) }
+    // Equips a new skill in the next free slot, or sets it aside once every slot is taken; false if it was already known
+    #[allow(clippy::possible_missing_else)]
+    pub fn learn_skill(&mut self, skill: ActiveSkillInstance) -> bool { if self.knows_skill(skill.definition_id) { return false; } if self.equipped_skills.len() < MAX_SKILL_SLOTS { self.equipped_skills.push(skill); } else { self.learned_skills.push(skill); } true }
     pub fn get_effective_pickup_radius(&self) -> f32 { BASE_PICKUP_RADIUS * self.pickup_radius_multiplier }
-    pub fn new_with_skills_and_items(initial_skills: Vec<ActiveSkillInstance>, initial_items: Vec<ItemId>) -> Self { Self { speed: BASE_SURVIVOR_SPEED, experience: 0, current_level_xp: 0, level: 1, aim_direction: Vec2::X, invincibility_timer: Timer::from_seconds(1.0, TimerMode::Once), ichor_blast_damage_bonus: 0, ichor_blast_speed_multiplier: 1.0, ichor_blast_piercing: 0, xp_gain_multiplier: 1.0, pickup_radius_multiplier: 1.0, additional_ichor_blasts: 0, max_health: INITIAL_SURVIVOR_MAX_HEALTH, health_regen_rate: 0.0, equipped_skills: initial_skills, collected_item_ids: initial_items, collected_glyphs: Vec::new(), } } // Renamed fields
+    // The rear guard gains two shots for every additional ichor blast, so projectile upgrades pay off twice as hard behind you
+    // Global reduction stacks multiplicatively on top of each skill's own cooldown_multiplier
+    pub fn get_effective_cooldown_multiplier(&self) -> f32 { 1.0 - self.cooldown_reduction.clamp(0.0, MAX_COOLDOWN_REDUCTION) }
+    pub fn get_effective_rear_guard_shots(&self, base_shots: u32) -> u32 { base_shots + self.additional_ichor_blasts * REAR_GUARD_SHOTS_PER_ADDITIONAL_BLAST }
+    pub fn new_with_skills_and_items(initial_skills: Vec<ActiveSkillInstance>, initial_items: Vec<ItemId>) -> Self { Self { speed: BASE_SURVIVOR_SPEED, experience: 0, current_level_xp: 0, level: 1, aim_direction: Vec2::X, aim_world_position: Vec2::X, invincibility_timer: Timer::from_seconds(SURVIVOR_HIT_INVINCIBILITY_SECS, TimerMode::Once), damage_grace: DamageGrace::default(), ichor_blast_damage_bonus: 0, ichor_blast_speed_multiplier: 1.0, ichor_blast_piercing: 0, xp_gain_multiplier: 1.0, pickup_radius_multiplier: 1.0, additional_ichor_blasts: 0, area_multiplier: 1.0, cooldown_reduction: 0.0, luck: 0.0, duration_multiplier: 1.0, max_health: INITIAL_SURVIVOR_MAX_HEALTH, health_regen_rate: 0.0, equipped_skills: initial_skills, learned_skills: Vec::new(), collected_item_ids: initial_items, spent_revival_item_ids: Vec::new(), collected_glyphs: Vec::new(), projectile_tint: ICHOR_BLAST_COLOR, } } // Renamed fields
+}
+
+// Shared by the audio and visual warnings so they escalate together and clear together once healed
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct LowHealth { pub intensity: f32, }
+impl LowHealth {
+    pub fn from_health(health: i32, max_health: i32) -> Self { if max_health <= 0 { return Self::default(); } let fraction = (health as f32 / max_health as f32).max(0.0); if fraction >= LOW_HEALTH_THRESHOLD { return Self::default(); } Self { intensity: LOW_HEALTH_MIN_INTENSITY + (1.0 - LOW_HEALTH_MIN_INTENSITY) * (1.0 - fraction / LOW_HEALTH_THRESHOLD) } }
+    pub fn is_active(&self) -> bool { self.intensity > 0.0 }
+    pub fn heartbeat_bpm(&self) -> f32 { LOW_HEALTH_CALM_BPM + (LOW_HEALTH_PANIC_BPM - LOW_HEALTH_CALM_BPM) * self.intensity }
 }
 
-fn should_despawn_survivor(next_state: Res<NextState<AppState>>) -> bool { match next_state.0 { Some(AppState::GameOver) | Some(AppState::MainMenu) => true, _ => false, } } // Renamed
+fn should_despawn_survivor(next_state: Res<NextState<AppState>>) -> bool { matches!(next_state.0, Some(AppState::GameOver | AppState::MainMenu)) } // Renamed
 fn no_survivor_exists(survivor_query: Query<(), With<Survivor>>) -> bool { survivor_query.is_empty() } // Renamed
-impl Plugin for SurvivorPlugin { fn build(&self, app: &mut App) { app .add_systems(OnEnter(AppState::InGame), spawn_survivor.run_if(no_survivor_exists)) .add_systems(Update, ( survivor_movement, survivor_aiming, survivor_casting_system, survivor_health_regeneration_system, survivor_horror_collision_system.before(check_survivor_death_system), survivor_invincibility_system, check_survivor_death_system, survivor_item_drop_collection_system, ).chain().run_if(in_state(AppState::InGame))) .add_systems(OnExit(AppState::InGame), despawn_survivor.run_if(should_despawn_survivor)); } } // Renamed
+impl Plugin for SurvivorPlugin { fn build(&self, app: &mut App) { app .add_systems(OnEnter(AppState::InGame), spawn_survivor.run_if(no_survivor_exists)) .add_systems(Update, ( survivor_movement, survivor_aiming, survivor_casting_system, survivor_health_regeneration_system, timed_collision(survivor_horror_collision_system).before(check_survivor_death_system), survivor_invincibility_system, check_survivor_death_system, survivor_item_drop_collection_system, ).chain().run_if(in_state(AppState::InGame))) .add_systems(OnExit(AppState::InGame), despawn_survivor.run_if(should_despawn_survivor)) .init_resource::<LowHealth>() .add_systems(Update, update_low_health.after(check_survivor_death_system).run_if(in_state(AppState::InGame))) .add_systems(OnExit(AppState::InGame), clear_low_health); } } // Renamed
 
-fn spawn_survivor( mut commands: Commands, asset_server: Res<AssetServer>, skill_library: Res<SkillLibrary>,) { // Renamed
+fn spawn_survivor( mut commands: Commands, asset_server: Res<AssetServer>, skill_library: Res<SkillLibrary>, active_challenge: Res<ActiveChallenge>, balance: Res<BalanceConfig>, skins: Res<SkinCollection>,) { // Renamed
     let mut initial_skills = Vec::new();
-    if let Some(skill_def_bolt) = skill_library.get_skill_definition(SkillId(1)) {
-        let bolt_instance = ActiveSkillInstance::new(SkillId(1), skill_def_bolt.base_glyph_slots);
-        initial_skills.push(bolt_instance);
+    // Challenge runs fix the starting skill; standard runs open with Eldritch Bolt
+    let starting_skill = active_challenge.starting_skill();
+    if let Some(starting_skill_def) = skill_library.get_skill_definition(starting_skill) {
+        initial_skills.push(ActiveSkillInstance::new(starting_skill, starting_skill_def.base_glyph_slots));
     }
-    commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/survivor_placeholder.png"), sprite: Sprite { custom_size: Some(SURVIVOR_SIZE), ..default() }, transform: Transform::from_xyz(0.0, 0.0, 1.0), ..default() }, Survivor::new_with_skills_and_items(initial_skills, Vec::new()), ComponentHealth(INITIAL_SURVIVOR_MAX_HEALTH), Velocity(Vec2::ZERO), SanityStrain::default(), CircleOfWarding::default(), SwarmOfNightmares::default(), Name::new("Survivor"), )); // Renamed, Name simplified
+    let mut survivor = Survivor::new_with_skills_and_items(initial_skills, Vec::new());
+    survivor.start_invincibility(balance.spawn_invincibility_secs);
+    let skin = skins.selected_definition();
+    survivor.projectile_tint = skin.projectile_tint;
+    commands.spawn(( SpriteBundle { texture: asset_server.load(skin.sprite), sprite: Sprite { custom_size: Some(SURVIVOR_SIZE), color: skin.sprite_tint, ..default() }, transform: Transform::from_xyz(0.0, 0.0, 1.0), ..default() }, survivor, ComponentHealth(INITIAL_SURVIVOR_MAX_HEALTH), Velocity(Vec2::ZERO), SanityStrain::default(), CircleOfWarding::default(), SwarmOfNightmares::default(), SearingGaze::default(), SporeCenser::default(), RearGuard::default(), SnareLayer::default(), HookedCrescent::default(), ConsumableInventory::default(), (Threat { weight: SURVIVOR_THREAT_WEIGHT }, PlayerSlot(0)), Name::new("Survivor"), )); // Renamed, Name simplified
 }
 fn despawn_survivor(mut commands: Commands, survivor_query: Query<Entity, With<Survivor>>) { if let Ok(survivor_entity) = survivor_query.get_single() { commands.entity(survivor_entity).despawn_recursive(); } } // Renamed
-fn survivor_health_regeneration_system(time: Res<Time>, mut query: Query<(&Survivor, &mut ComponentHealth)>,) { for (survivor_stats, mut current_health) in query.iter_mut() { if survivor_stats.health_regen_rate > 0.0 && current_health.0 > 0 && current_health.0 < survivor_stats.max_health { let regen_amount = survivor_stats.health_regen_rate * time.delta_seconds(); current_health.0 = (current_health.0 as f32 + regen_amount).round() as i32; current_health.0 = current_health.0.min(survivor_stats.max_health); } } } // Renamed
-fn survivor_movement( keyboard_input: Res<ButtonInput<KeyCode>>, mut query: Query<(&Survivor, &mut Transform, &mut Velocity, Option<&SurvivorBuffEffect>)>, time: Res<Time>,) { for (survivor, mut transform, mut velocity, buff_effect_opt) in query.iter_mut() { let mut direction = Vec2::ZERO; if keyboard_input.pressed(KeyCode::KeyA) { direction.x -= 1.0; } if keyboard_input.pressed(KeyCode::KeyD) { direction.x += 1.0; } if keyboard_input.pressed(KeyCode::KeyW) { direction.y += 1.0; } if keyboard_input.pressed(KeyCode::KeyS) { direction.y -= 1.0; } let mut current_speed = survivor.speed; if let Some(buff) = buff_effect_opt { current_speed *= 1.0 + buff.speed_multiplier_bonus; } velocity.0 = if direction != Vec2::ZERO { direction.normalize() * current_speed } else { Vec2::ZERO }; transform.translation.x += velocity.0.x * time.delta_seconds(); transform.translation.y += velocity.0.y * time.delta_seconds(); } } // Renamed
-fn survivor_aiming(mut survivor_query: Query<(&mut Survivor, &Transform)>, window_query: Query<&Window, With<PrimaryWindow>>, camera_query: Query<(&Camera, &GlobalTransform)>,) { if let Ok((mut survivor, survivor_transform)) = survivor_query.get_single_mut() { if let Ok(primary_window) = window_query.get_single() { if let Ok((camera, camera_transform)) = camera_query.get_single() { if let Some(cursor_position) = primary_window.cursor_position() { if let Some(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) { let direction_to_mouse = (world_position - survivor_transform.translation.truncate()).normalize_or_zero(); if direction_to_mouse != Vec2::ZERO { survivor.aim_direction = direction_to_mouse; } } } } } } } // Renamed
-fn survivor_casting_system( mut commands: Commands, asset_server: Res<AssetServer>, time: Res<Time>, mut query: Query<(&Transform, &Survivor, &mut SanityStrain, Option<&SurvivorBuffEffect>)>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { for (survivor_transform, survivor_stats, mut sanity_strain, buff_effect_opt) in query.iter_mut() { let mut current_fire_rate_secs = sanity_strain.base_fire_rate_secs; if let Some(buff) = buff_effect_opt { current_fire_rate_secs /= 1.0 + buff.fire_rate_multiplier_bonus; } let new_duration = Duration::from_secs_f32(current_fire_rate_secs.max(0.05)); if sanity_strain.fire_timer.duration() != new_duration { sanity_strain.fire_timer.set_duration(new_duration); } sanity_strain.fire_timer.tick(time.delta()); if sanity_strain.fire_timer.just_finished() { if survivor_stats.aim_direction != Vec2::ZERO { sound_event_writer.send(PlaySoundEvent(SoundEffect::RitualCast)); let current_damage = BASE_FRAGMENT_DAMAGE + survivor_stats.ichor_blast_damage_bonus; let current_speed = BASE_FRAGMENT_SPEED * survivor_stats.ichor_blast_speed_multiplier; let current_piercing = survivor_stats.ichor_blast_piercing; let total_fragments = 1 + survivor_stats.additional_ichor_blasts; let base_angle = survivor_stats.aim_direction.to_angle(); for i in 0..total_fragments { let angle_offset_rad = if total_fragments > 1 { let total_spread_angle_rad = (total_fragments as f32 - 1.0) * PROJECTILE_SPREAD_ANGLE_DEGREES.to_radians(); let start_angle_rad = base_angle - total_spread_angle_rad / 2.0; start_angle_rad + (i as f32 * PROJECTILE_SPREAD_ANGLE_DEGREES.to_radians()) } else { base_angle }; let fragment_direction = Vec2::from_angle(angle_offset_rad); spawn_ichor_blast( &mut commands, &asset_server, survivor_transform.translation, fragment_direction, current_damage, current_speed, current_piercing, ); } } } } } // Renamed, SoundEffect, spawn_thought_fragment
-fn survivor_horror_collision_system( mut commands: Commands, asset_server: Res<AssetServer>, mut survivor_query: Query<(Entity, &Transform, &mut ComponentHealth, &mut Survivor)>, horror_query: Query<(&Transform, &Horror)>, item_library: Res<ItemLibrary>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { if let Ok((survivor_entity, survivor_transform, mut survivor_health, mut survivor_component)) = survivor_query.get_single_mut() { if !survivor_component.invincibility_timer.finished() { return; } for (horror_transform, horror_stats) in horror_query.iter() { let distance = survivor_transform.translation.truncate().distance(horror_transform.translation.truncate()); let survivor_radius = SURVIVOR_SIZE.x / 2.0; let horror_radius = horror_stats.size.x / 2.0; if distance < survivor_radius + horror_radius { if survivor_component.invincibility_timer.finished() { sound_event_writer.send(PlaySoundEvent(SoundEffect::SurvivorHit)); survivor_health.0 -= horror_stats.damage_on_collision; survivor_component.invincibility_timer.reset(); let mut rng = rand::thread_rng(); for item_id in survivor_component.collected_item_ids.iter() { if let Some(item_def) = item_library.get_item_definition(*item_id) { for effect in &item_def.effects { if let ItemEffect::OnSurvivorHitRetaliate { chance, retaliation_damage, retaliation_radius, retaliation_color } = effect { if rng.gen_bool((*chance).into()) { commands.entity(survivor_entity).with_children(|parent| { parent.spawn(( SpriteBundle { texture: asset_server.load("sprites/eldritch_nova_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: *retaliation_color, ..default() }, transform: Transform::from_xyz(0.0, 0.0, 0.3), ..default() }, RetaliationNovaEffect { damage: *retaliation_damage, radius_sq: retaliation_radius.powi(2), timer: Timer::from_seconds(0.4, TimerMode::Once), already_hit_entities: Vec::new(), }, Name::new("RetaliationNova"), )); }); } } } } } } } } } } // Renamed, ItemEffect, SoundEffect, Asset path
-fn survivor_invincibility_system(time: Res<Time>, mut query: Query<(&mut Survivor, &mut Sprite, &ComponentHealth)>,) { for (mut survivor, mut sprite, health) in query.iter_mut() { if health.0 <= 0 { if sprite.color.a() != 1.0 { sprite.color.set_a(1.0); } continue; } if !survivor.invincibility_timer.finished() { survivor.invincibility_timer.tick(time.delta()); let alpha = (time.elapsed_seconds() * 20.0).sin() / 2.0 + 0.7; sprite.color.set_a(alpha.clamp(0.3, 1.0) as f32); } else { if sprite.color.a() != 1.0 { sprite.color.set_a(1.0); } } } } // Renamed
-fn check_survivor_death_system(survivor_query: Query<&ComponentHealth, With<Survivor>>, mut app_state_next: ResMut<NextState<AppState>>, mut sound_event_writer: EventWriter<PlaySoundEvent>, current_app_state: Res<State<AppState>>,) { if let Ok(survivor_health) = survivor_query.get_single() { if survivor_health.0 <= 0 && *current_app_state.get() == AppState::InGame { sound_event_writer.send(PlaySoundEvent(SoundEffect::MadnessConsumes)); app_state_next.set(AppState::GameOver); } } } // Renamed, SoundEffect
-fn survivor_item_drop_collection_system(mut commands: Commands, survivor_query: Query<&Transform, With<Survivor>>, item_drop_query: Query<(Entity, &Transform, &ItemDrop)>, mut item_collected_event_writer: EventWriter<ItemCollectedEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { if let Ok(survivor_transform) = survivor_query.get_single() { let survivor_pos = survivor_transform.translation.truncate(); for (item_drop_entity, item_drop_transform, item_drop_data) in item_drop_query.iter() { let item_drop_pos = item_drop_transform.translation.truncate(); if survivor_pos.distance(item_drop_pos) < ITEM_COLLECTION_RADIUS { item_collected_event_writer.send(ItemCollectedEvent(item_drop_data.item_id)); sound_event_writer.send(PlaySoundEvent(SoundEffect::SoulCollect)); commands.entity(item_drop_entity).despawn_recursive(); } } } } // Renamed, SoundEffect
\ No newline at end of file
+fn update_low_health(survivor_query: Query<(&Survivor, &ComponentHealth)>, mut low_health: ResMut<LowHealth>) { let current = survivor_query.get_single().map_or(LowHealth::default(), |(survivor, health)| LowHealth::from_health(health.0, survivor.max_health)); if *low_health != current { *low_health = current; } }
+// Menus and the game over screen get no heartbeat; it returns with the next frame back in game if still low
+fn clear_low_health(mut low_health: ResMut<LowHealth>) { *low_health = LowHealth::default(); }
+fn survivor_health_regeneration_system(time: Res<Time>, run_difficulty: Res<RunDifficulty>, mut query: Query<(&Survivor, &mut ComponentHealth)>,) { for (survivor_stats, mut current_health) in query.iter_mut() { if survivor_stats.health_regen_rate > 0.0 && current_health.0 > 0 && current_health.0 < survivor_stats.max_health { let regen_amount = run_difficulty.scale_healing(survivor_stats.health_regen_rate * time.delta_seconds()); current_health.0 = (current_health.0 as f32 + regen_amount).round() as i32; current_health.0 = current_health.0.min(survivor_stats.max_health); } } } // Renamed
+#[allow(clippy::possible_missing_else)]
+fn survivor_movement( actions: Res<ActionState>, mut query: Query<(&Survivor, &mut Transform, &mut Velocity, Option<&SurvivorBuffEffect>, Option<&TimeScale>, Option<&Overwhelm>)>, time: Res<Time>, game_config: Res<GameConfig>,) { for (survivor, mut transform, mut velocity, buff_effect_opt, time_scale_opt, overwhelm_opt) in query.iter_mut() { let direction = actions.move_axis(); let mut current_speed = survivor.speed; if let Some(buff) = buff_effect_opt { current_speed *= 1.0 + buff.speed_multiplier_bonus; } if let Some(overwhelm) = overwhelm_opt { current_speed *= 1.0 + overwhelm.speed_bonus(); } velocity.0 = direction.clamp_length_max(1.0) * current_speed; let scaled_delta_seconds = time.delta_seconds() * TimeScale::of(time_scale_opt); transform.translation.x += velocity.0.x * scaled_delta_seconds; transform.translation.y += velocity.0.y * scaled_delta_seconds; let clamped = game_config.clamp_to_arena(transform.translation.truncate(), SURVIVOR_SIZE.x / 2.0); transform.translation = clamped.extend(transform.translation.z); } } // Renamed
+fn survivor_aiming(mut survivor_query: Query<(&mut Survivor, &Transform)>, actions: Res<ActionState>, camera_query: Query<(&Camera, &GlobalTransform)>,) { if let Ok((mut survivor, survivor_transform)) = survivor_query.get_single_mut() { let survivor_pos = survivor_transform.translation.truncate(); match actions.aim { Some(AimInput::Cursor(cursor_position)) => { if let Ok((camera, camera_transform)) = camera_query.get_single() { if let Some(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) { survivor.aim_world_position = world_position; let direction_to_mouse = (world_position - survivor_pos).normalize_or_zero(); if direction_to_mouse != Vec2::ZERO { survivor.aim_direction = direction_to_mouse; } } } } Some(AimInput::Direction(direction)) => { let direction = direction.normalize_or_zero(); if direction != Vec2::ZERO { survivor.aim_direction = direction; survivor.aim_world_position = survivor_pos + direction * AIM_DIRECTION_REACH; } } None => {} } } } // Renamed
+#[allow(clippy::possible_missing_else)]
+fn survivor_casting_system( mut commands: Commands, asset_server: Res<AssetServer>, time: Res<Time>, mut query: Query<(&Transform, &Survivor, &mut SanityStrain, Option<&SurvivorBuffEffect>, Option<&TimeScale>, Option<&Overwhelm>)>, mut sound_event_writer: EventWriter<PlaySoundEvent>, balance: Res<BalanceConfig>,) { let spread_angle_rad = balance.ichor_blast_spread_degrees.to_radians(); for (survivor_transform, survivor_stats, mut sanity_strain, buff_effect_opt, time_scale_opt, overwhelm_opt) in query.iter_mut() { let mut current_fire_rate_secs = sanity_strain.base_fire_rate_secs; if let Some(buff) = buff_effect_opt { current_fire_rate_secs /= 1.0 + buff.fire_rate_multiplier_bonus; } if let Some(overwhelm) = overwhelm_opt { current_fire_rate_secs /= 1.0 + overwhelm.fire_rate_bonus(); } let new_duration = Duration::from_secs_f32(current_fire_rate_secs.max(0.05)); if sanity_strain.fire_timer.duration() != new_duration { sanity_strain.fire_timer.set_duration(new_duration); } sanity_strain.fire_timer.tick(time.delta().mul_f32(TimeScale::of(time_scale_opt))); if sanity_strain.fire_timer.just_finished() && survivor_stats.aim_direction != Vec2::ZERO { sound_event_writer.send(PlaySoundEvent::global(SoundEffect::RitualCast)); let current_damage = BASE_FRAGMENT_DAMAGE + survivor_stats.ichor_blast_damage_bonus; let total_fragments = 1 + survivor_stats.additional_ichor_blasts; let base_angle = survivor_stats.aim_direction.to_angle(); for i in 0..total_fragments { let angle_offset_rad = if total_fragments > 1 { let total_spread_angle_rad = (total_fragments as f32 - 1.0) * spread_angle_rad; let start_angle_rad = base_angle - total_spread_angle_rad / 2.0; start_angle_rad + (i as f32 * spread_angle_rad) } else { base_angle }; let fragment_direction = Vec2::from_angle(angle_offset_rad); spawn_ichor_blast( &mut commands, &asset_server, survivor_stats, survivor_transform.translation, fragment_direction, current_damage, ); } } } } // Renamed, SoundEffect, spawn_thought_fragment
+fn survivor_horror_collision_system( mut commands: Commands, asset_server: Res<AssetServer>, time: Res<Time>, game_config: Res<GameConfig>, mut survivor_query: Query<(Entity, &mut Transform, &mut ComponentHealth, &mut Survivor)>, mut horror_query: Query<(Entity, &Transform, &Horror, &mut ContactDamage, &mut ComponentHealth, Option<&ThornsCooldown>), Without<Survivor>>, item_library: Res<ItemLibrary>, mut game_event_writer: EventWriter<GameEvent>, mut game_rng: ResMut<GameRng>, balance: Res<BalanceConfig>,) { for (_, _, _, mut contact_damage, _, _) in horror_query.iter_mut() { contact_damage.tick(time.delta()); } if let Ok((survivor_entity, mut survivor_transform, mut survivor_health, mut survivor_component)) = survivor_query.get_single_mut() { for (horror_entity, horror_transform, horror_stats, mut contact_damage, mut horror_health, thorns_cooldown) in horror_query.iter_mut() { let distance = survivor_transform.translation.truncate().distance(horror_transform.translation.truncate()); let survivor_radius = SURVIVOR_SIZE.x / 2.0; let horror_radius = horror_stats.size.x / 2.0; if distance < survivor_radius + horror_radius { if !survivor_component.can_take_damage_from(DamageSource::Contact) { break; } let Some(contact_damage_amount) = contact_damage.try_hit() else { continue; }; game_event_writer.send(GameEvent::SurvivorHit { damage: contact_damage_amount, source: DamageSource::Contact }); survivor_health.0 -= contact_damage_amount; survivor_component.register_hit_with_grace(DamageSource::Contact, balance.grace_secs(DamageSource::Contact)); let knocked_back = survivor_transform.translation.truncate() + contact_knockback(survivor_transform.translation.truncate(), horror_transform.translation.truncate()); survivor_transform.translation = game_config.clamp_to_arena(knocked_back, SURVIVOR_SIZE.x / 2.0).extend(survivor_transform.translation.z); if thorns_cooldown.is_none() { if let Some((damage_fraction, cooldown_secs)) = thorns_from_items(&survivor_component.collected_item_ids, &item_library) { let reflected_damage = ((horror_stats.damage_on_collision as f32 * damage_fraction).round() as i32).max(1); horror_health.0 -= reflected_damage; commands.entity(horror_entity).remove::<LastHitBySkill>(); spawn_damage_text(&mut commands, horror_transform.translation, reflected_damage, &time); game_event_writer.send(GameEvent::DamageDealt { amount: reflected_damage, damage_type: DamageType::Eldritch, position: horror_transform.translation, origin: DamageOrigin::Relic }); commands.entity(horror_entity).insert(ThornsCooldown { timer: Timer::from_seconds(cooldown_secs, TimerMode::Once) }); } } let rng = &mut game_rng.0; for item_id in survivor_component.collected_item_ids.iter() { if let Some(item_def) = item_library.get_item_definition(*item_id) { for effect in &item_def.effects { if let ItemEffect::OnSurvivorHitRetaliate { chance, retaliation_damage, retaliation_radius, retaliation_color } = effect { if rng.gen_bool((*chance).into()) { commands.entity(survivor_entity).with_children(|parent| { parent.spawn(( SpriteBundle { texture: asset_server.load("sprites/eldritch_nova_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: *retaliation_color, ..default() }, transform: Transform::from_xyz(0.0, 0.0, 0.3), ..default() }, RetaliationNovaEffect { damage: *retaliation_damage, radius_sq: (retaliation_radius * survivor_component.area_multiplier).powi(2), timer: Timer::from_seconds(0.4, TimerMode::Once), already_hit_entities: Vec::new(), }, Name::new("RetaliationNova"), )); }); } } } } } } } } } // Renamed, ItemEffect, SoundEffect, Asset path
+// Thorns from several relics stack their reflected fraction; the longest per-attacker cooldown wins
+fn thorns_from_items(item_ids: &[ItemId], item_library: &ItemLibrary) -> Option<(f32, f32)> { item_ids.iter().filter_map(|item_id| item_library.get_item_definition(*item_id)).flat_map(|item_def| item_def.effects.iter()).filter_map(|effect| if let ItemEffect::ThornsReflect { damage_fraction, per_attacker_cooldown_secs } = effect { Some((*damage_fraction, *per_attacker_cooldown_secs)) } else { None }).reduce(|(fraction_a, cooldown_a), (fraction_b, cooldown_b)| (fraction_a + fraction_b, cooldown_a.max(cooldown_b))) }
+#[allow(clippy::possible_missing_else)]
+fn survivor_invincibility_system(time: Res<Time>, mut query: Query<(&mut Survivor, &mut Sprite, &ComponentHealth)>,) { for (mut survivor, mut sprite, health) in query.iter_mut() { survivor.damage_grace.tick(time.delta()); if health.0 <= 0 { if sprite.color.a() != 1.0 { sprite.color.set_a(1.0); } continue; } if !survivor.invincibility_timer.finished() || survivor.damage_grace.any_active() { survivor.invincibility_timer.tick(time.delta()); let alpha = (time.elapsed_seconds() * 20.0).sin() / 2.0 + 0.7; sprite.color.set_a(alpha.clamp(0.3, 1.0)); } else { if sprite.color.a() != 1.0 { sprite.color.set_a(1.0); } } } } // Renamed
+fn check_survivor_death_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut survivor_query: Query<(Entity, &mut ComponentHealth, &mut Survivor)>,
+    item_library: Res<ItemLibrary>,
+    mut app_state_next: ResMut<NextState<AppState>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    current_app_state: Res<State<AppState>>,
+) {
+    let Ok((survivor_entity, mut survivor_health, mut survivor)) = survivor_query.get_single_mut() else { return; };
+    if survivor_health.0 > 0 || *current_app_state.get() != AppState::InGame { return; }
+
+    // The first unspent revival relic takes the killing blow instead
+    let revival = survivor.collected_item_ids.iter()
+        .filter(|item_id| !survivor.spent_revival_item_ids.contains(item_id))
+        .find_map(|item_id| item_library.get_item_definition(*item_id)?.effects.iter().find_map(|effect| match effect {
+            ItemEffect::PreventDeathOnce { heal_fraction, shockwave_damage, shockwave_radius, invulnerability_secs } => Some((*item_id, *heal_fraction, *shockwave_damage, *shockwave_radius, *invulnerability_secs)),
+            _ => None,
+        }));
+    let Some((item_id, heal_fraction, shockwave_damage, shockwave_radius, invulnerability_secs)) = revival else {
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::MadnessConsumes));
+        app_state_next.set(AppState::GameOver);
+        return;
+    };
+
+    survivor.spent_revival_item_ids.push(item_id);
+    survivor_health.0 = ((survivor.max_health as f32 * heal_fraction).round() as i32).max(1);
+    survivor.start_invincibility(invulnerability_secs);
+    commands.entity(survivor_entity).with_children(|parent| {
+        parent.spawn((
+            SpriteBundle { texture: asset_server.load("sprites/eldritch_nova_effect_placeholder.png"), sprite: Sprite { custom_size: Some(Vec2::splat(0.1)), color: Color::rgba(0.9, 0.85, 0.5, 0.6), ..default() }, transform: Transform::from_xyz(0.0, 0.0, 0.3), ..default() },
+            RetaliationNovaEffect { damage: shockwave_damage, radius_sq: (shockwave_radius * survivor.area_multiplier).powi(2), timer: Timer::from_seconds(0.5, TimerMode::Once), already_hit_entities: Vec::new(), },
+            Name::new("PhylacteryShockwave"),
+        ));
+    });
+    sound_event_writer.send(PlaySoundEvent::global(SoundEffect::Revelation));
+}
+fn survivor_item_drop_collection_system(mut commands: Commands, survivor_query: Query<&Transform, With<Survivor>>, item_drop_query: Query<(Entity, &Transform, &ItemDrop)>, mut item_collected_event_writer: EventWriter<ItemCollectedEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>,) { if let Ok(survivor_transform) = survivor_query.get_single() { let survivor_pos = survivor_transform.translation.truncate(); for (item_drop_entity, item_drop_transform, item_drop_data) in item_drop_query.iter() { let item_drop_pos = item_drop_transform.translation.truncate(); if survivor_pos.distance(item_drop_pos) < ITEM_COLLECTION_RADIUS { item_collected_event_writer.send(ItemCollectedEvent(item_drop_data.item_id)); sound_event_writer.send(PlaySoundEvent::global(SoundEffect::ItemPickup)); commands.entity(item_drop_entity).despawn_recursive(); } } } } // Renamed, SoundEffect
\ No newline at end of file
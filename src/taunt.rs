@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::Survivor,
+    horror::Horror,
+    game::AppState,
+    audio::{PlaySoundEvent, SoundEffect},
+    components::{TauntTarget, TimeScale},
+};
+
+pub const TAUNT_KEY: KeyCode = KeyCode::KeyT;
+pub const TAUNT_DURATION_SECS: f32 = 3.0;
+pub const TAUNT_COOLDOWN_SECS: f32 = 12.0;
+pub const TAUNT_AGGRO_RADIUS: f32 = 400.0;
+pub const TAUNT_PULL_SPEED: f32 = 60.0;
+pub const TAUNT_PICKUP_RADIUS_MULTIPLIER: f32 = 2.0;
+const TAUNT_RING_SECS: f32 = 0.5;
+const TAUNT_RING_COLOR: Color = Color::rgba(1.0, 0.35, 0.25, 0.6);
+
+pub struct TauntPlugin;
+
+impl Plugin for TauntPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TauntCooldown>()
+            .add_systems(OnExit(AppState::MainMenu), reset_taunt_cooldown)
+            .add_systems(Update, (
+                start_taunt_system,
+                taunt_pull_system,
+                tick_taunting_system,
+                animate_taunt_ring_system,
+            ).chain().run_if(in_state(AppState::InGame)));
+    }
+}
+
+// Status on the survivor while the taunt lasts: horrors nearby are drawn in and souls and gold come from further away
+#[derive(Component, Debug)]
+pub struct Taunting {
+    pub timer: Timer,
+}
+
+impl Default for Taunting {
+    fn default() -> Self {
+        Self { timer: Timer::from_seconds(TAUNT_DURATION_SECS, TimerMode::Once) }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct TauntCooldown {
+    pub timer: Timer,
+}
+
+impl Default for TauntCooldown {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(TAUNT_COOLDOWN_SECS, TimerMode::Once);
+        timer.tick(timer.duration());
+        Self { timer }
+    }
+}
+
+impl TauntCooldown {
+    pub fn is_ready(&self) -> bool { self.timer.finished() }
+    pub fn trigger(&mut self) { self.timer.reset(); }
+}
+
+pub fn pickup_radius_multiplier(taunting: Option<&Taunting>) -> f32 {
+    if taunting.is_some() { TAUNT_PICKUP_RADIUS_MULTIPLIER } else { 1.0 }
+}
+
+// How far a horror is dragged toward the survivor this frame; never past the survivor, and nothing outside the aggro radius
+pub fn taunt_pull(horror_pos: Vec2, survivor_pos: Vec2, max_step: f32) -> Vec2 {
+    let to_survivor = survivor_pos - horror_pos;
+    let distance = to_survivor.length();
+    if distance >= TAUNT_AGGRO_RADIUS || distance <= f32::EPSILON { return Vec2::ZERO; }
+    to_survivor / distance * max_step.min(distance)
+}
+
+#[derive(Component)]
+struct TauntRing {
+    timer: Timer,
+}
+
+fn reset_taunt_cooldown(mut cooldown: ResMut<TauntCooldown>) {
+    *cooldown = TauntCooldown::default();
+}
+
+fn start_taunt_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut cooldown: ResMut<TauntCooldown>,
+    survivor_query: Query<Entity, (With<Survivor>, Without<Taunting>)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+) {
+    cooldown.timer.tick(time.delta());
+    if !keyboard_input.just_pressed(TAUNT_KEY) || !cooldown.is_ready() { return; }
+    let Ok(survivor_entity) = survivor_query.get_single() else { return; };
+    cooldown.trigger();
+    // Horrors in range pick the taunting survivor over any decoy that is further away
+    commands.entity(survivor_entity).insert((Taunting::default(), TauntTarget { radius: TAUNT_AGGRO_RADIUS })).with_children(|parent| {
+        parent.spawn((
+            SpriteBundle {
+                texture: asset_server.load("sprites/revelation_wave_placeholder.png"),
+                sprite: Sprite { custom_size: Some(Vec2::ZERO), color: TAUNT_RING_COLOR, ..default() },
+                transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                ..default()
+            },
+            TauntRing { timer: Timer::from_seconds(TAUNT_RING_SECS, TimerMode::Once) },
+            Name::new("TauntRing"),
+        ));
+    });
+    sound_event_writer.send(PlaySoundEvent::global(SoundEffect::Taunt));
+}
+
+fn taunt_pull_system(
+    time: Res<Time>,
+    survivor_query: Query<&Transform, (With<Survivor>, With<Taunting>)>,
+    mut horror_query: Query<(&mut Transform, Option<&TimeScale>), (With<Horror>, Without<Survivor>)>,
+) {
+    let Ok(survivor_transform) = survivor_query.get_single() else { return; };
+    let survivor_pos = survivor_transform.translation.truncate();
+    for (mut horror_transform, time_scale) in horror_query.iter_mut() {
+        let pull = taunt_pull(horror_transform.translation.truncate(), survivor_pos, TAUNT_PULL_SPEED * time.delta_seconds() * TimeScale::of(time_scale));
+        horror_transform.translation += pull.extend(0.0);
+    }
+}
+
+fn tick_taunting_system(mut commands: Commands, time: Res<Time>, mut taunting_query: Query<(Entity, &mut Taunting)>) {
+    for (entity, mut taunting) in taunting_query.iter_mut() {
+        taunting.timer.tick(time.delta());
+        if taunting.timer.finished() { commands.entity(entity).remove::<(Taunting, TauntTarget)>(); }
+    }
+}
+
+fn animate_taunt_ring_system(mut commands: Commands, time: Res<Time>, mut ring_query: Query<(Entity, &mut TauntRing, &mut Sprite)>) {
+    for (entity, mut ring, mut sprite) in ring_query.iter_mut() {
+        ring.timer.tick(time.delta());
+        let progress = ring.timer.fraction();
+        sprite.custom_size = Some(Vec2::splat(TAUNT_AGGRO_RADIUS * 2.0 * progress));
+        sprite.color.set_a(TAUNT_RING_COLOR.a() * (1.0 - progress));
+        if ring.timer.finished() { commands.entity(entity).despawn_recursive(); }
+    }
+}
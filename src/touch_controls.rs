@@ -0,0 +1,293 @@
+use bevy::{prelude::*, input::InputSystem, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+use crate::{
+    game::AppState,
+    horror::Horror,
+    input_actions::{ActionStateSystem, InputAction, VirtualInput},
+    skills::MAX_SKILL_SLOTS,
+    survivor::Survivor,
+};
+
+pub const TOUCH_CONTROLS_TOGGLE_KEY: KeyCode = KeyCode::F9;
+pub const STICK_RADIUS: f32 = 70.0;
+pub const STICK_DEAD_ZONE: f32 = 0.15;
+pub const SKILL_BUTTON_SIZE: f32 = 64.0;
+pub const PAUSE_BUTTON_SIZE: f32 = 48.0;
+pub const AUTO_AIM_RANGE: f32 = 600.0;
+// Bottom-right corner of each skill button, measured in from the bottom-right of the screen; slot 1 sits under the thumb
+pub const SKILL_BUTTON_OFFSETS: [Vec2; MAX_SKILL_SLOTS] = [
+    Vec2::new(30.0, 30.0),
+    Vec2::new(110.0, 30.0),
+    Vec2::new(30.0, 110.0),
+    Vec2::new(190.0, 30.0),
+    Vec2::new(30.0, 190.0),
+];
+// Top-right corner of the pause button, measured in from the top-right of the screen
+pub const PAUSE_BUTTON_OFFSET: Vec2 = Vec2::new(20.0, 20.0);
+// Where the stick waits, measured from the bottom-left, until a thumb lands somewhere else
+const STICK_REST_OFFSET: Vec2 = Vec2::new(60.0, 60.0);
+const STICK_KNOB_SIZE: f32 = 56.0;
+const STICK_BASE_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.15);
+const STICK_KNOB_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.4);
+const TOUCH_BUTTON_COLOR: Color = Color::rgba(0.2, 0.2, 0.3, 0.5);
+const TOUCH_BUTTON_PRESSED_COLOR: Color = Color::rgba(0.5, 0.5, 0.7, 0.7);
+
+pub struct TouchControlsPlugin;
+
+impl Plugin for TouchControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchControls>()
+            .init_resource::<TouchStick>()
+            .add_systems(Startup, setup_touch_overlay)
+            .add_systems(PreUpdate, read_touch_controls.after(InputSystem).before(ActionStateSystem))
+            .add_systems(Update, (toggle_touch_controls, update_touch_overlay).chain());
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TouchControlMode {
+    // Shown from the first time the screen is touched
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl TouchControlMode {
+    pub fn next(self) -> Self {
+        match self {
+            TouchControlMode::Auto => TouchControlMode::On,
+            TouchControlMode::On => TouchControlMode::Off,
+            TouchControlMode::Off => TouchControlMode::Auto,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TouchControlMode::Auto => "auto",
+            TouchControlMode::On => "on",
+            TouchControlMode::Off => "off",
+        }
+    }
+}
+
+// The mode is a setting kept in the profile; whether a touch has been seen is only for this session
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TouchControls {
+    pub mode: TouchControlMode,
+    pub touch_detected: bool,
+}
+
+impl TouchControls {
+    pub fn is_active(&self) -> bool {
+        match self.mode {
+            TouchControlMode::Auto => self.touch_detected,
+            TouchControlMode::On => true,
+            TouchControlMode::Off => false,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchButton {
+    Skill(usize),
+    Pause,
+}
+
+impl TouchButton {
+    pub fn action(&self) -> InputAction {
+        match self {
+            TouchButton::Skill(slot_index) => InputAction::SKILLS[*slot_index],
+            TouchButton::Pause => InputAction::Pause,
+        }
+    }
+}
+
+// Screen positions are in UI pixels from the top-left: window pixels divided by the UI scale
+pub fn touch_button_center(window_size: Vec2, button: TouchButton) -> Vec2 {
+    match button {
+        TouchButton::Skill(slot_index) => window_size - SKILL_BUTTON_OFFSETS[slot_index] - Vec2::splat(SKILL_BUTTON_SIZE / 2.0),
+        TouchButton::Pause => Vec2::new(window_size.x - PAUSE_BUTTON_OFFSET.x - PAUSE_BUTTON_SIZE / 2.0, PAUSE_BUTTON_OFFSET.y + PAUSE_BUTTON_SIZE / 2.0),
+    }
+}
+
+pub fn touched_button(window_size: Vec2, position: Vec2) -> Option<TouchButton> {
+    let skills = (0..MAX_SKILL_SLOTS).map(|slot_index| (TouchButton::Skill(slot_index), SKILL_BUTTON_SIZE));
+    skills.chain([(TouchButton::Pause, PAUSE_BUTTON_SIZE)])
+        .find(|(button, size)| position.distance(touch_button_center(window_size, *button)) <= size / 2.0)
+        .map(|(button, _)| button)
+}
+
+// Screen y grows downwards, so it is flipped into the world's up; the result never leaves the unit circle
+pub fn stick_vector(origin: Vec2, touch: Vec2, radius: f32) -> Vec2 {
+    if radius <= 0.0 { return Vec2::ZERO; }
+    let offset = touch - origin;
+    let vector = (Vec2::new(offset.x, -offset.y) / radius).clamp_length_max(1.0);
+    if vector.length() < STICK_DEAD_ZONE { Vec2::ZERO } else { vector }
+}
+
+// Points at the nearest target within range, if there is one
+pub fn auto_aim_direction(from: Vec2, targets: impl IntoIterator<Item = Vec2>, range: f32) -> Option<Vec2> {
+    targets.into_iter()
+        .filter(|target| target.distance(from) <= range)
+        .min_by(|a, b| a.distance_squared(from).total_cmp(&b.distance_squared(from)))
+        .map(|target| (target - from).normalize_or_zero())
+        .filter(|direction| *direction != Vec2::ZERO)
+}
+
+// The touch steering the survivor; it floats to wherever the thumb first landed on the left half of the screen
+#[derive(Resource, Debug, Default)]
+struct TouchStick {
+    touch_id: Option<u64>,
+    origin: Vec2,
+    position: Vec2,
+}
+
+#[derive(Component)]
+struct TouchPlayOverlay;
+
+#[derive(Component)]
+struct StickBase;
+
+#[derive(Component)]
+struct StickKnob;
+
+fn read_touch_controls(
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    app_state: Res<State<AppState>>,
+    mut controls: ResMut<TouchControls>,
+    mut stick: ResMut<TouchStick>,
+    mut virtual_input: ResMut<VirtualInput>,
+    survivor_query: Query<&Transform, With<Survivor>>,
+    horror_query: Query<&Transform, With<Horror>>,
+    ui_scale: Res<UiScale>,
+) {
+    if touches.any_just_pressed() && !controls.touch_detected { controls.touch_detected = true; }
+    *virtual_input = VirtualInput::default();
+    let Ok(window) = window_query.get_single() else { return; };
+    let in_game = *app_state.get() == AppState::InGame;
+    if !controls.is_active() || !in_game { stick.touch_id = None; }
+    if !controls.is_active() { return; }
+    // Touches arrive in window pixels; the overlay is laid out in UI pixels, which shrink with the UI scale
+    let ui_scale = ui_scale.0.max(f32::EPSILON);
+    let window_size = Vec2::new(window.width(), window.height()) / ui_scale;
+
+    match app_state.get() {
+        // Everything else outside play is already a UI button, which Bevy presses on touch by itself
+        AppState::MainMenu => {
+            if touches.any_just_pressed() { virtual_input.held.push(InputAction::Confirm); }
+            return;
+        }
+        AppState::InGame | AppState::Paused => {}
+        _ => return,
+    }
+
+    for touch in touches.iter() {
+        match touched_button(window_size, touch.start_position() / ui_scale) {
+            Some(TouchButton::Pause) => virtual_input.held.push(InputAction::Pause),
+            Some(button) if in_game => virtual_input.held.push(button.action()),
+            _ => {}
+        }
+    }
+    if !in_game { return; }
+
+    if stick.touch_id.is_some_and(|id| touches.get_pressed(id).is_none()) { stick.touch_id = None; }
+    if stick.touch_id.is_none() {
+        let new_stick_touch = touches.iter_just_pressed().map(|touch| (touch.id(), touch.position() / ui_scale))
+            .find(|(_, position)| position.x < window_size.x / 2.0 && touched_button(window_size, *position).is_none());
+        if let Some((id, position)) = new_stick_touch { *stick = TouchStick { touch_id: Some(id), origin: position, position }; }
+    }
+    if let Some(touch) = stick.touch_id.and_then(|id| touches.get_pressed(id)) { stick.position = touch.position() / ui_scale; }
+    // Keys still steer whenever no thumb is on the stick
+    let move_axis = stick.touch_id.map(|_| stick_vector(stick.origin, stick.position, STICK_RADIUS));
+    virtual_input.move_axis = move_axis;
+
+    // Aims itself at the nearest horror, or along the stick when nothing is close
+    let Ok(survivor_transform) = survivor_query.get_single() else { return; };
+    let survivor_pos = survivor_transform.translation.truncate();
+    let nearest = auto_aim_direction(survivor_pos, horror_query.iter().map(|transform| transform.translation.truncate()), AUTO_AIM_RANGE);
+    virtual_input.aim_direction = nearest.or(move_axis.filter(|axis| *axis != Vec2::ZERO));
+}
+
+fn toggle_touch_controls(keyboard_input: Res<ButtonInput<KeyCode>>, mut controls: ResMut<TouchControls>) {
+    if keyboard_input.just_pressed(TOUCH_CONTROLS_TOGGLE_KEY) {
+        controls.mode = controls.mode.next();
+        info!("Touch controls {}", controls.mode.label());
+    }
+}
+
+fn setup_touch_overlay(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        NodeBundle {
+            style: Style { display: Display::None, position_type: PositionType::Absolute, width: Val::Percent(100.0), height: Val::Percent(100.0), ..default() },
+            z_index: ZIndex::Global(3),
+            ..default()
+        },
+        TouchPlayOverlay,
+    )).with_children(|overlay| {
+        overlay.spawn((
+            NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Px(STICK_RADIUS * 2.0), height: Val::Px(STICK_RADIUS * 2.0), ..default() }, background_color: STICK_BASE_COLOR.into(), ..default() },
+            StickBase,
+        ));
+        overlay.spawn((
+            NodeBundle { style: Style { position_type: PositionType::Absolute, width: Val::Px(STICK_KNOB_SIZE), height: Val::Px(STICK_KNOB_SIZE), ..default() }, background_color: STICK_KNOB_COLOR.into(), ..default() },
+            StickKnob,
+        ));
+        for (slot_index, offset) in SKILL_BUTTON_OFFSETS.iter().enumerate() {
+            overlay.spawn((
+                NodeBundle {
+                    style: Style { position_type: PositionType::Absolute, right: Val::Px(offset.x), bottom: Val::Px(offset.y), width: Val::Px(SKILL_BUTTON_SIZE), height: Val::Px(SKILL_BUTTON_SIZE), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() },
+                    background_color: TOUCH_BUTTON_COLOR.into(),
+                    ..default()
+                },
+                TouchButton::Skill(slot_index),
+            )).with_children(|button| { button.spawn(TextBundle::from_section((slot_index + 1).to_string(), TextStyle { font: font.clone(), font_size: 24.0, color: Color::WHITE })); });
+        }
+    });
+    // Kept apart from the rest so it still shows over the pause menu, where it resumes play
+    commands.spawn((
+        NodeBundle {
+            style: Style { display: Display::None, position_type: PositionType::Absolute, right: Val::Px(PAUSE_BUTTON_OFFSET.x), top: Val::Px(PAUSE_BUTTON_OFFSET.y), width: Val::Px(PAUSE_BUTTON_SIZE), height: Val::Px(PAUSE_BUTTON_SIZE), justify_content: JustifyContent::Center, align_items: AlignItems::Center, ..default() },
+            background_color: TOUCH_BUTTON_COLOR.into(),
+            z_index: ZIndex::Global(11),
+            ..default()
+        },
+        TouchButton::Pause,
+    )).with_children(|button| { button.spawn(TextBundle::from_section("II", TextStyle { font, font_size: 22.0, color: Color::WHITE })); });
+}
+
+fn update_touch_overlay(
+    controls: Res<TouchControls>,
+    stick: Res<TouchStick>,
+    virtual_input: Res<VirtualInput>,
+    app_state: Res<State<AppState>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut overlay_query: Query<&mut Style, (With<TouchPlayOverlay>, Without<TouchButton>, Without<StickBase>, Without<StickKnob>)>,
+    mut base_query: Query<&mut Style, (With<StickBase>, Without<StickKnob>, Without<TouchButton>)>,
+    mut knob_query: Query<&mut Style, (With<StickKnob>, Without<TouchButton>)>,
+    mut button_query: Query<(&TouchButton, &mut Style, &mut BackgroundColor)>,
+    ui_scale: Res<UiScale>,
+) {
+    let in_game = *app_state.get() == AppState::InGame;
+    let shown = |visible: bool| if controls.is_active() && visible { Display::Flex } else { Display::None };
+    for mut style in overlay_query.iter_mut() { style.display = shown(in_game); }
+    for (button, mut style, mut background) in button_query.iter_mut() {
+        if *button == TouchButton::Pause { style.display = shown(in_game || *app_state.get() == AppState::Paused); }
+        *background = if virtual_input.held.contains(&button.action()) { TOUCH_BUTTON_PRESSED_COLOR } else { TOUCH_BUTTON_COLOR }.into();
+    }
+    if !controls.is_active() || !in_game { return; }
+
+    let Ok(window) = window_query.get_single() else { return; };
+    let rest = Vec2::new(STICK_REST_OFFSET.x + STICK_RADIUS, window.height() / ui_scale.0.max(f32::EPSILON) - STICK_REST_OFFSET.y - STICK_RADIUS);
+    let (origin, knob) = if stick.touch_id.is_some() { (stick.origin, stick.origin + (stick.position - stick.origin).clamp_length_max(STICK_RADIUS)) } else { (rest, rest) };
+    for mut style in base_query.iter_mut() {
+        style.left = Val::Px(origin.x - STICK_RADIUS);
+        style.top = Val::Px(origin.y - STICK_RADIUS);
+    }
+    for mut style in knob_query.iter_mut() {
+        style.left = Val::Px(knob.x - STICK_KNOB_SIZE / 2.0);
+        style.top = Val::Px(knob.y - STICK_KNOB_SIZE / 2.0);
+    }
+}
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use crate::{
+    survivor::{Survivor, SanityStrain, MAX_COOLDOWN_REDUCTION},
+    weapons::{CircleOfWarding, SwarmOfNightmares, SearingGaze, SporeCenser, RearGuard, SnareLayer, HookedCrescent},
+    upgrades::UpgradeType,
+    game::{AppState, UpgradeButton},
+    coop::PlayerSlot,
+};
+
+pub struct UpgradePreviewPlugin;
+
+impl Plugin for UpgradePreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_upgrade_preview.run_if(in_state(AppState::LevelUp)));
+    }
+}
+
+// Text node on each player's level-up panel that shows what the card they hover would change
+#[derive(Component)]
+pub struct UpgradePreviewText;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatPreview {
+    pub label: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl std::fmt::Display for StatPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} → {}", self.label, self.before, self.after)
+    }
+}
+
+// The survivor's current state, as read by the preview
+pub struct PreviewSources<'a> {
+    pub survivor: &'a Survivor,
+    pub sanity_strain: &'a SanityStrain,
+    pub circle: &'a CircleOfWarding,
+    pub swarm: &'a SwarmOfNightmares,
+    pub gaze: &'a SearingGaze,
+    pub spores: &'a SporeCenser,
+    pub rear_guard: &'a RearGuard,
+    pub snares: &'a SnareLayer,
+    pub crescent: &'a HookedCrescent,
+}
+
+fn stat(label: impl Into<String>, before: impl ToString, after: impl ToString) -> StatPreview {
+    StatPreview { label: label.into(), before: before.to_string(), after: after.to_string() }
+}
+fn percent(value: f32) -> String { format!("{:.0}%", value * 100.0) }
+fn seconds(value: f32) -> String { format!("{:.2}s", value) }
+fn one_decimal(value: f32) -> String { format!("{:.1}", value) }
+fn manifested(name: &str) -> StatPreview { stat(name, "Dormant", "Manifested") }
+fn scaled(percentage: u32) -> f32 { 1.0 + percentage as f32 / 100.0 }
+
+// Mirrors the arithmetic in game::apply_chosen_upgrade; cards that only unlock something new preview nothing
+pub fn preview_upgrade(upgrade_type: &UpgradeType, sources: &PreviewSources) -> Vec<StatPreview> {
+    let survivor = sources.survivor;
+    match upgrade_type {
+        UpgradeType::SurvivorSpeed(p) => vec![stat("Move speed", one_decimal(survivor.speed), one_decimal(survivor.speed * scaled(*p)))],
+        UpgradeType::MaxEndurance(amount) => vec![stat("Max endurance", survivor.max_health, survivor.max_health + amount)],
+        UpgradeType::IchorBlastIntensity(amount) => vec![stat("Ichor blast damage bonus", survivor.ichor_blast_damage_bonus, survivor.ichor_blast_damage_bonus + amount)],
+        UpgradeType::IchorBlastSpeed(p) => {
+            let before = sources.sanity_strain.base_fire_rate_secs;
+            vec![stat("Fire rate", seconds(before), seconds((before * (1.0 - *p as f32 / 100.0)).max(0.05)))]
+        }
+        UpgradeType::IchorBlastVelocity(p) => vec![stat("Ichor blast velocity", percent(survivor.ichor_blast_speed_multiplier), percent(survivor.ichor_blast_speed_multiplier * scaled(*p)))],
+        UpgradeType::IchorBlastPiercing(amount) => vec![stat("Piercing", survivor.ichor_blast_piercing, survivor.ichor_blast_piercing + amount)],
+        UpgradeType::EchoesGainMultiplier(p) => vec![stat("Echo gain", percent(survivor.xp_gain_multiplier), percent(survivor.xp_gain_multiplier * scaled(*p)))],
+        UpgradeType::SoulAttractionRadius(p) => vec![stat("Pickup radius", percent(survivor.pickup_radius_multiplier), percent(survivor.pickup_radius_multiplier * scaled(*p)))],
+        UpgradeType::AdditionalIchorBlasts(amount) => vec![stat("Extra ichor blasts", survivor.additional_ichor_blasts, survivor.additional_ichor_blasts + amount)],
+        UpgradeType::AreaMultiplier(p) => vec![stat("Area", percent(survivor.area_multiplier), percent(survivor.area_multiplier * scaled(*p)))],
+        UpgradeType::DurationMultiplier(p) => vec![stat("Duration", percent(survivor.duration_multiplier), percent(survivor.duration_multiplier * scaled(*p)))],
+        UpgradeType::CooldownReduction(p) => vec![stat("Cooldown reduction", percent(survivor.cooldown_reduction), percent((survivor.cooldown_reduction + *p as f32 / 100.0).min(MAX_COOLDOWN_REDUCTION)))],
+        UpgradeType::Luck(p) => vec![stat("Luck", percent(survivor.luck), percent(survivor.luck + *p as f32 / 100.0))],
+        UpgradeType::EnduranceRegeneration(amount) => vec![stat("Endurance regen", format!("{:.1}/s", survivor.health_regen_rate), format!("{:.1}/s", survivor.health_regen_rate + amount))],
+        UpgradeType::IncreaseSkillDamage { slot_index, amount } => survivor.equipped_skills.get(*slot_index)
+            .map(|skill| vec![stat(format!("Skill {} damage bonus", slot_index + 1), skill.flat_damage_bonus, skill.flat_damage_bonus + amount)]).unwrap_or_default(),
+        UpgradeType::ReduceSkillCooldown { slot_index, percent_reduction } => survivor.equipped_skills.get(*slot_index)
+            .map(|skill| vec![stat(format!("Skill {} cooldown", slot_index + 1), percent(skill.cooldown_multiplier), percent((skill.cooldown_multiplier * (1.0 - percent_reduction)).max(0.1)))]).unwrap_or_default(),
+        UpgradeType::IncreaseSkillAoERadius { slot_index, percent_increase } => survivor.equipped_skills.get(*slot_index)
+            .map(|skill| vec![stat(format!("Skill {} area", slot_index + 1), percent(skill.aoe_radius_multiplier), percent(skill.aoe_radius_multiplier * (1.0 + percent_increase)))]).unwrap_or_default(),
+        _ => preview_weapon_upgrade(upgrade_type, sources),
+    }
+}
+
+fn preview_weapon_upgrade(upgrade_type: &UpgradeType, sources: &PreviewSources) -> Vec<StatPreview> {
+    let (circle, swarm, gaze, spores, rear_guard, snares, crescent) = (sources.circle, sources.swarm, sources.gaze, sources.spores, sources.rear_guard, sources.snares, sources.crescent);
+    let circle_tick = circle.damage_tick_timer.duration().as_secs_f32();
+    let rear_guard_interval = rear_guard.fire_timer.duration().as_secs_f32();
+    match upgrade_type {
+        UpgradeType::InscribeCircleOfWarding if !circle.is_active => vec![manifested("Circle of Warding")],
+        UpgradeType::InscribeCircleOfWarding => vec![stat("Circle damage", circle.base_damage_per_tick, circle.base_damage_per_tick + 1), stat("Circle radius", one_decimal(circle.current_radius), one_decimal(circle.current_radius * 1.1))],
+        UpgradeType::IncreaseCircleRadius(p) if circle.is_active => vec![stat("Circle radius", one_decimal(circle.current_radius), one_decimal(circle.current_radius * scaled(*p)))],
+        UpgradeType::IncreaseCircleDamage(amount) if circle.is_active => vec![stat("Circle damage", circle.base_damage_per_tick, circle.base_damage_per_tick + amount)],
+        UpgradeType::DecreaseCircleTickRate(p) if circle.is_active => vec![stat("Circle tick", seconds(circle_tick), seconds((circle_tick * (1.0 - *p as f32 / 100.0)).max(0.1)))],
+        UpgradeType::ManifestSwarmOfNightmares if !swarm.is_active => vec![manifested("Swarm of Nightmares")],
+        UpgradeType::ManifestSwarmOfNightmares => vec![stat("Larvae", swarm.num_larvae, swarm.num_larvae + 1), stat("Larva damage", swarm.damage_per_hit, swarm.damage_per_hit + 1)],
+        UpgradeType::IncreaseNightmareCount(count) if swarm.is_active => vec![stat("Larvae", swarm.num_larvae, swarm.num_larvae + count)],
+        UpgradeType::IncreaseNightmareDamage(amount) if swarm.is_active => vec![stat("Larva damage", swarm.damage_per_hit, swarm.damage_per_hit + amount)],
+        UpgradeType::IncreaseNightmareRadius(amount) if swarm.is_active => vec![stat("Orbit radius", one_decimal(swarm.orbit_radius), one_decimal(swarm.orbit_radius + amount))],
+        UpgradeType::IncreaseNightmareRotationSpeed(amount) if swarm.is_active => vec![stat("Orbit speed", one_decimal(swarm.rotation_speed), one_decimal(swarm.rotation_speed + amount))],
+        UpgradeType::ManifestSearingGaze if !gaze.is_active => vec![manifested("Searing Gaze")],
+        UpgradeType::ManifestSearingGaze => vec![stat("Gaze damage", gaze.damage_per_sweep, gaze.damage_per_sweep + 4), stat("Gaze range", one_decimal(gaze.range), one_decimal(gaze.range * 1.1))],
+        UpgradeType::IncreaseGazeArc(degrees) if gaze.is_active => vec![stat("Gaze arc", format!("{:.0}°", gaze.arc_degrees), format!("{:.0}°", (gaze.arc_degrees + degrees).min(360.0)))],
+        UpgradeType::IncreaseGazeSweepSpeed(p) if gaze.is_active => vec![stat("Sweep speed", format!("{:.0}°/s", gaze.sweep_speed_degrees), format!("{:.0}°/s", gaze.sweep_speed_degrees * scaled(*p)))],
+        UpgradeType::ManifestSporeCenser if !spores.is_active => vec![manifested("Spore Censer")],
+        UpgradeType::ManifestSporeCenser => vec![stat("Spores", spores.max_spores, spores.max_spores + 1), stat("Spore burst damage", spores.burst_damage, spores.burst_damage + 3)],
+        UpgradeType::IncreaseSporeCount(count) if spores.is_active => vec![stat("Spores", spores.max_spores, spores.max_spores + count)],
+        UpgradeType::IncreaseSporeBurstRadius(p) if spores.is_active => vec![stat("Spore burst radius", one_decimal(spores.burst_radius), one_decimal(spores.burst_radius * scaled(*p)))],
+        UpgradeType::ManifestRearGuard if !rear_guard.is_active => vec![manifested("Rear Guard")],
+        UpgradeType::ManifestRearGuard => vec![stat("Rear guard shots", rear_guard.base_shots, rear_guard.base_shots + 1)],
+        UpgradeType::IncreaseRearGuardDamage(amount) if rear_guard.is_active => vec![stat("Rear guard damage", rear_guard.damage_per_shot, rear_guard.damage_per_shot + amount)],
+        UpgradeType::IncreaseRearGuardFireRate(p) if rear_guard.is_active => vec![stat("Rear guard interval", seconds(rear_guard_interval), seconds((rear_guard_interval / scaled(*p)).max(0.2)))],
+        UpgradeType::ManifestSnareLayer if !snares.is_active => vec![manifested("Snare Layer")],
+        UpgradeType::ManifestSnareLayer => vec![stat("Snare damage", snares.damage, snares.damage + 5), stat("Snares", snares.max_snares, snares.max_snares + 1)],
+        UpgradeType::IncreaseSnareCount(count) if snares.is_active => vec![stat("Snares", snares.max_snares, snares.max_snares + count)],
+        UpgradeType::IncreaseSnareBlastRadius(p) if snares.is_active => vec![stat("Snare blast radius", one_decimal(snares.blast_radius), one_decimal(snares.blast_radius * scaled(*p)))],
+        UpgradeType::ManifestHookedCrescent if !crescent.is_active => vec![manifested("Hooked Crescent")],
+        UpgradeType::ManifestHookedCrescent => vec![stat("Crescent damage", crescent.damage, crescent.damage + 4), stat("Crescent speed", one_decimal(crescent.speed), one_decimal(crescent.speed * 1.1))],
+        UpgradeType::IncreaseCrescentDamage(amount) if crescent.is_active => vec![stat("Crescent damage", crescent.damage, crescent.damage + amount)],
+        UpgradeType::IncreaseCrescentReach(p) if crescent.is_active => vec![stat("Crescent reach", seconds(crescent.outbound_secs), seconds(crescent.outbound_secs * scaled(*p)))],
+        _ => Vec::new(),
+    }
+}
+
+fn update_upgrade_preview(
+    button_query: Query<(&Interaction, &UpgradeButton, &PlayerSlot)>,
+    player_query: Query<(&Survivor, &SanityStrain, &CircleOfWarding, &SwarmOfNightmares, &SearingGaze, &SporeCenser, &RearGuard, &SnareLayer, &HookedCrescent, &PlayerSlot)>,
+    mut text_query: Query<(&mut Text, &PlayerSlot), With<UpgradePreviewText>>,
+) {
+    for (mut text, slot) in text_query.iter_mut() {
+        let hovered_card = button_query.iter().find(|(interaction, _, button_slot)| **interaction != Interaction::None && *button_slot == slot).map(|(_, button, _)| &button.0);
+        let player = player_query.iter().find(|(.., player_slot)| *player_slot == slot);
+        let lines = match (hovered_card, player) {
+            (Some(card), Some((survivor, sanity_strain, circle, swarm, gaze, spores, rear_guard, snares, crescent, _))) => {
+                let sources = PreviewSources { survivor, sanity_strain, circle, swarm, gaze, spores, rear_guard, snares, crescent };
+                preview_upgrade(&card.upgrade_type, &sources).iter().map(StatPreview::to_string).collect::<Vec<_>>().join("\n")
+            }
+            _ => String::new(),
+        };
+        if text.sections[0].value != lines { text.sections[0].value = lines; }
+    }
+}
@@ -1,17 +1,64 @@
-use bevy::prelude::*;
-use crate::game::AppState;
+use bevy::{prelude::*, sprite::Anchor};
+use crate::{game::AppState, survivor::{Survivor, LowHealth}, components::{DamageType, ProjectileOwner}, horror::EliteModifier, game_events::GameEvent, camera_systems::MainCamera, audio::{PlaySoundEvent, SoundEffect}};
 
 const DAMAGE_TEXT_LIFETIME_SECONDS: f32 = 0.75;
 const DAMAGE_TEXT_SPEED: f32 = 60.0;
+pub const MAX_DAMAGE_NUMBERS: usize = 96;
+const BEAM_Z_POS: f32 = 0.6;
+const BEAM_FLICKER_SPEED: f32 = 30.0;
+const BEAM_FLICKER_WIDTH_FACTOR: f32 = 0.15;
+const BURST_PARTICLE_SIZE: f32 = 6.0;
+const BURST_PARTICLE_LIFETIME_SECONDS: f32 = 0.45;
+const BURST_PARTICLE_Z_POS: f32 = 1.2;
+// Trail motes sit just under the projectiles that shed them
+const TRAIL_MOTE_Z_OFFSET: f32 = -0.1;
+pub const MAX_AFTERIMAGES: usize = 12;
+pub const AFTERIMAGE_SPEED_THRESHOLD: f32 = 320.0;
+pub const AFTERIMAGE_TOGGLE_KEY: KeyCode = KeyCode::F7;
+const AFTERIMAGE_INTERVAL_SECONDS: f32 = 0.05;
+const AFTERIMAGE_LIFETIME_SECONDS: f32 = 0.3;
+const AFTERIMAGE_START_ALPHA: f32 = 0.45;
+const AFTERIMAGE_Z_OFFSET: f32 = -0.05;
 // Removed unused DAMAGE_TEXT_FADE_SPEED
+// Nested screen-edge borders, outermost first, fake a soft red vignette since UI has no gradients
+const VIGNETTE_LAYERS: usize = 4;
+const VIGNETTE_LAYER_WIDTH: f32 = 28.0;
+const VIGNETTE_RESTING_ALPHA: f32 = 0.25;
+const VIGNETTE_BEAT_ALPHA: f32 = 0.35;
+// The outline is a tinted copy of the elite's own sprite drawn just behind it, this much larger
+const ELITE_OUTLINE_SCALE: f32 = 1.2;
+const ELITE_OUTLINE_Z_OFFSET: f32 = -0.01;
+const ELITE_AURA_PULSE_SPEED: f32 = 4.0;
+// A beacon is a tall column rising from whatever raised it, drawn over every sprite, plus a ping pinned to the screen edge
+pub const BEACON_LIFETIME_SECONDS: f32 = 3.0;
+pub const BEACON_MAX_ALPHA: f32 = 0.7;
+const BEACON_FLASHES: f32 = 6.0;
+const BEACON_WIDTH: f32 = 48.0;
+const BEACON_HEIGHT: f32 = 3000.0;
+const BEACON_Z_POS: f32 = 500.0;
+const BEACON_PING_SIZE: f32 = 18.0;
+pub const BEACON_PING_EDGE_MARGIN: f32 = 24.0;
 
 pub struct VisualEffectsPlugin;
 
 impl Plugin for VisualEffectsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, 
-            animate_damage_text_system.run_if(in_state(AppState::InGame))
-        );
+        app.init_resource::<DamageNumberPool>()
+            .init_resource::<DamageTypeStyle>()
+            .init_resource::<AfterimagePool>()
+            .init_resource::<AfterimageSettings>()
+            .add_systems(Startup, (spawn_damage_number_pool, spawn_afterimage_pool, spawn_low_health_vignette))
+            .add_systems(Update, pulse_low_health_vignette)
+            .add_systems(Update, 
+                (assign_damage_numbers_system, animate_damage_text_system, animate_beam_visual_system, burst_particle_system).chain().run_if(in_state(AppState::InGame))
+            )
+            .add_systems(Update, (style_new_projectiles, emit_projectile_trails).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (emit_survivor_afterimages, fade_afterimages_system).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (attach_elite_auras, detach_elite_auras, pulse_elite_auras).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (raise_beacons, animate_beacons, animate_beacon_pings).chain().run_if(in_state(AppState::InGame)))
+            .add_systems(Update, toggle_afterimages)
+            .add_systems(OnEnter(AppState::GameOver), (hide_damage_numbers, hide_afterimages, despawn_beacons))
+            .add_systems(OnEnter(AppState::MainMenu), (hide_damage_numbers, hide_afterimages, despawn_beacons));
     }
 }
 
@@ -21,47 +68,202 @@ pub struct DamageTextEffect {
     pub velocity: Vec2,
 }
 
+pub struct DamageNumberRequest {
+    pub position: Vec3,
+    pub damage_amount: i32,
+    pub damage_type: DamageType,
+    pub spawn_time: f32,
+}
+
+// One place that decides how each damage type reads on screen, so numbers and menus never disagree
+#[derive(Resource, Debug, Clone)]
+pub struct DamageTypeStyle {
+    pub eldritch: (Color, &'static str),
+    pub frost: (Color, &'static str),
+    pub chaos: (Color, &'static str),
+}
+
+impl Default for DamageTypeStyle {
+    fn default() -> Self {
+        Self {
+            eldritch: (Color::rgb(1.0, 0.8, 0.8), ""),
+            frost: (Color::rgb(0.55, 0.85, 1.0), "*"),
+            chaos: (Color::rgb(0.75, 1.0, 0.3), "!"),
+        }
+    }
+}
+
+impl DamageTypeStyle {
+    fn entry(&self, damage_type: DamageType) -> (Color, &'static str) {
+        match damage_type { DamageType::Eldritch => self.eldritch, DamageType::Frost => self.frost, DamageType::Chaos => self.chaos, }
+    }
+    pub fn color(&self, damage_type: DamageType) -> Color { self.entry(damage_type).0 }
+    // Plain eldritch damage is the baseline, so it carries no icon
+    pub fn icon(&self, damage_type: DamageType) -> &'static str { self.entry(damage_type).1 }
+    pub fn label(&self, damage_type: DamageType) -> String {
+        let name = match damage_type { DamageType::Eldritch => "Eldritch", DamageType::Frost => "Frost", DamageType::Chaos => "Chaos", };
+        format!("{}{}", self.icon(damage_type), name)
+    }
+    pub fn damage_number_text(&self, damage_type: DamageType, amount: i32) -> String {
+        format!("{}{}", self.icon(damage_type), amount)
+    }
+    // Eldritch shots keep their own colour; converted ones all take their type's, so an all-Frost build reads as one
+    pub fn projectile_color(&self, damage_type: DamageType, own_color: Color) -> Color {
+        match damage_type {
+            DamageType::Eldritch => own_color,
+            _ => self.color(damage_type).with_a(own_color.a()),
+        }
+    }
+    pub fn projectile_fx(&self, damage_type: DamageType) -> ProjectileFx {
+        match damage_type {
+            DamageType::Eldritch => ProjectileFx { trail_interval_secs: 0.06, trail_lifetime_secs: 0.2, trail_size: 4.0, impact_particles: 6, impact_speed: 120.0 },
+            // Frost leaves a long, lingering wake and shatters softly
+            DamageType::Frost => ProjectileFx { trail_interval_secs: 0.03, trail_lifetime_secs: 0.5, trail_size: 5.0, impact_particles: 10, impact_speed: 80.0 },
+            // Chaos spits short-lived sparks and bursts violently
+            DamageType::Chaos => ProjectileFx { trail_interval_secs: 0.02, trail_lifetime_secs: 0.15, trail_size: 3.0, impact_particles: 14, impact_speed: 220.0 },
+        }
+    }
+}
+
+// How a damage type's projectiles look in flight and where they land
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectileFx {
+    pub trail_interval_secs: f32,
+    pub trail_lifetime_secs: f32,
+    pub trail_size: f32,
+    pub impact_particles: u32,
+    pub impact_speed: f32,
+}
+
+// Sheds a mote every trail interval while the projectile flies; only the survivor's shots get one, so horror fire stays easy to read
+#[derive(Component)]
+pub struct ProjectileTrail {
+    pub timer: Timer,
+}
+
+// A fixed set of hidden text entities; hits claim slots round-robin so the oldest number is recycled once the pool is full
+#[derive(Resource, Default)]
+pub struct DamageNumberPool {
+    slots: Vec<Entity>,
+    next_slot: usize,
+    pending: Vec<DamageNumberRequest>,
+}
+
+impl DamageNumberPool {
+    pub fn with_slots(slots: Vec<Entity>) -> Self {
+        Self { slots, ..default() }
+    }
+
+    pub fn claim_slot(&mut self) -> Option<Entity> {
+        if self.slots.is_empty() { return None; }
+        let slot = self.slots[self.next_slot % self.slots.len()];
+        self.next_slot = (self.next_slot + 1) % self.slots.len();
+        Some(slot)
+    }
+
+    pub fn queue(&mut self, request: DamageNumberRequest) {
+        self.pending.push(request);
+    }
+
+    // Only the newest requests that fit in the pool are kept; anything older would be overwritten this frame anyway
+    pub fn take_pending(&mut self) -> Vec<DamageNumberRequest> {
+        let mut pending = std::mem::take(&mut self.pending);
+        let overflow = pending.len().saturating_sub(self.slots.len());
+        pending.drain(..overflow);
+        pending
+    }
+}
+
+#[derive(Component)]
+pub struct BeamVisual {
+    pub base_width: f32,
+    pub base_alpha: f32,
+}
+
+#[derive(Component)]
+pub struct BurstParticle {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+}
+
 pub fn spawn_damage_text(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
     position: Vec3, 
     damage_amount: i32,
     time: &Res<Time>, 
 ) {
-    let random_offset_x = (rand::random::<f32>() - 0.5) * 20.0; 
+    spawn_typed_damage_text(commands, position, damage_amount, DamageType::default(), time);
+}
 
-    commands.spawn((
-        Text2dBundle {
-            text: Text::from_section(
-                damage_amount.to_string(),
-                TextStyle {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 20.0, 
-                    color: Color::rgb(1.0, 0.8, 0.8), 
-                },
-            ),
-            transform: Transform::from_translation(position + Vec3::new(random_offset_x, 10.0, 5.0)),
-            ..default()
-        },
-        DamageTextEffect {
-            spawn_time: time.elapsed_seconds(),
-            velocity: Vec2::new(random_offset_x * 0.5, DAMAGE_TEXT_SPEED), 
-        },
-        Name::new("DamageText"),
-    ));
+pub fn spawn_typed_damage_text(
+    commands: &mut Commands,
+    position: Vec3,
+    damage_amount: i32,
+    damage_type: DamageType,
+    time: &Res<Time>,
+) {
+    let request = DamageNumberRequest { position, damage_amount, damage_type, spawn_time: time.elapsed_seconds() };
+    commands.add(move |world: &mut World| world.resource_mut::<DamageNumberPool>().queue(request));
 }
 
-fn animate_damage_text_system(
+fn spawn_damage_number_pool(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pool: ResMut<DamageNumberPool>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let slots = (0..MAX_DAMAGE_NUMBERS).map(|_| {
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0, 
+                        color: Color::rgb(1.0, 0.8, 0.8), 
+                    },
+                ),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            DamageTextEffect { spawn_time: 0.0, velocity: Vec2::ZERO },
+            Name::new("DamageText"),
+        )).id()
+    }).collect();
+    *pool = DamageNumberPool::with_slots(slots);
+}
+
+fn assign_damage_numbers_system(
+    mut pool: ResMut<DamageNumberPool>,
+    style: Res<DamageTypeStyle>,
+    mut query: Query<(&mut DamageTextEffect, &mut Transform, &mut Text, &mut Visibility)>,
+) {
+    for request in pool.take_pending() {
+        let Some(slot) = pool.claim_slot() else { return; };
+        let Ok((mut effect_data, mut transform, mut text_component, mut visibility)) = query.get_mut(slot) else { continue; };
+        let random_offset_x = (rand::random::<f32>() - 0.5) * 20.0; 
+        effect_data.spawn_time = request.spawn_time;
+        effect_data.velocity = Vec2::new(random_offset_x * 0.5, DAMAGE_TEXT_SPEED);
+        transform.translation = request.position + Vec3::new(random_offset_x, 10.0, 5.0);
+        if let Some(section) = text_component.sections.get_mut(0) {
+            section.value = style.damage_number_text(request.damage_type, request.damage_amount);
+            section.style.color = style.color(request.damage_type);
+        }
+        *visibility = Visibility::Visible;
+    }
+}
+
+fn animate_damage_text_system(
     time: Res<Time>,
-    mut query: Query<(Entity, &DamageTextEffect, &mut Transform, &mut Text)>,
+    mut query: Query<(&DamageTextEffect, &mut Transform, &mut Text, &mut Visibility)>,
 ) {
     let current_time = time.elapsed_seconds();
-    for (entity, effect_data, mut transform, mut text_component) in query.iter_mut() {
+    for (effect_data, mut transform, mut text_component, mut visibility) in query.iter_mut() {
+        if *visibility == Visibility::Hidden { continue; }
         let time_alive = current_time - effect_data.spawn_time;
 
         if time_alive > DAMAGE_TEXT_LIFETIME_SECONDS {
-            commands.entity(entity).despawn_recursive(); // Use despawn_recursive for safety
+            *visibility = Visibility::Hidden;
             continue;
         }
 
@@ -73,4 +275,456 @@ fn animate_damage_text_system(
             section.style.color.set_a((1.0 - alpha_progress).max(0.0));
         }
     }
-}
\ No newline at end of file
+}
+
+fn hide_damage_numbers(
+    mut pool: ResMut<DamageNumberPool>,
+    mut query: Query<&mut Visibility, With<DamageTextEffect>>,
+) {
+    pool.pending.clear();
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+// Beams are anchored at their origin so the sprite can be stretched along the aim direction
+pub fn spawn_beam_visual(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    origin: Vec3,
+    direction: Vec2,
+    length: f32,
+    width: f32,
+    color: Color,
+) -> Entity {
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/abyssal_beam_placeholder.png"),
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(length, width)),
+                color,
+                anchor: Anchor::CenterLeft,
+                ..default()
+            },
+            transform: Transform::from_translation(origin.truncate().extend(BEAM_Z_POS))
+                .with_rotation(Quat::from_rotation_z(direction.y.atan2(direction.x))),
+            ..default()
+        },
+        BeamVisual { base_width: width, base_alpha: color.a() },
+    )).id()
+}
+
+fn animate_beam_visual_system(
+    time: Res<Time>,
+    mut query: Query<(&BeamVisual, &mut Sprite)>,
+) {
+    let flicker = (time.elapsed_seconds() * BEAM_FLICKER_SPEED).sin();
+    for (beam_visual, mut sprite) in query.iter_mut() {
+        if let Some(size) = sprite.custom_size.as_mut() {
+            size.y = beam_visual.base_width * (1.0 + flicker * BEAM_FLICKER_WIDTH_FACTOR);
+        }
+        sprite.color.set_a((beam_visual.base_alpha * (0.85 + flicker * 0.15)).clamp(0.0, 1.0));
+    }
+}
+
+pub fn spawn_particle_burst(
+    commands: &mut Commands,
+    position: Vec3,
+    color: Color,
+    count: u32,
+    speed: f32,
+) {
+    for i in 0..count {
+        let angle = i as f32 / count.max(1) as f32 * std::f32::consts::TAU + rand::random::<f32>() * 0.5;
+        let particle_speed = speed * (0.5 + rand::random::<f32>() * 0.5);
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite { custom_size: Some(Vec2::splat(BURST_PARTICLE_SIZE)), color, ..default() },
+                transform: Transform::from_translation(position.truncate().extend(BURST_PARTICLE_Z_POS)),
+                ..default()
+            },
+            BurstParticle {
+                velocity: Vec2::from_angle(angle) * particle_speed,
+                lifetime: Timer::from_seconds(BURST_PARTICLE_LIFETIME_SECONDS, TimerMode::Once),
+            },
+            Name::new("BurstParticle"),
+        ));
+    }
+}
+
+// Every collision system calls this where a projectile lands
+pub fn spawn_impact_effect(commands: &mut Commands, style: &DamageTypeStyle, position: Vec3, damage_type: DamageType) {
+    let fx = style.projectile_fx(damage_type);
+    spawn_particle_burst(commands, position, style.color(damage_type), fx.impact_particles, fx.impact_speed);
+}
+
+fn style_new_projectiles(
+    mut commands: Commands,
+    style: Res<DamageTypeStyle>,
+    mut projectile_query: Query<(Entity, &ProjectileOwner, Option<&DamageType>, &mut Sprite), Added<ProjectileOwner>>,
+) {
+    for (entity, owner, damage_type, mut sprite) in projectile_query.iter_mut() {
+        let damage_type = damage_type.copied().unwrap_or_default();
+        sprite.color = style.projectile_color(damage_type, sprite.color);
+        if *owner == ProjectileOwner::Survivor {
+            commands.entity(entity).insert(ProjectileTrail { timer: Timer::from_seconds(style.projectile_fx(damage_type).trail_interval_secs, TimerMode::Repeating) });
+        }
+    }
+}
+
+fn emit_projectile_trails(
+    mut commands: Commands,
+    time: Res<Time>,
+    style: Res<DamageTypeStyle>,
+    mut projectile_query: Query<(&GlobalTransform, &Sprite, Option<&DamageType>, &mut ProjectileTrail)>,
+) {
+    for (transform, sprite, damage_type, mut trail) in projectile_query.iter_mut() {
+        if !trail.timer.tick(time.delta()).just_finished() { continue; }
+        let fx = style.projectile_fx(damage_type.copied().unwrap_or_default());
+        let position = transform.translation();
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite { custom_size: Some(Vec2::splat(fx.trail_size)), color: sprite.color, ..default() },
+                transform: Transform::from_translation(position.truncate().extend(position.z + TRAIL_MOTE_Z_OFFSET)),
+                ..default()
+            },
+            BurstParticle { velocity: Vec2::ZERO, lifetime: Timer::from_seconds(fx.trail_lifetime_secs, TimerMode::Once) },
+            Name::new("TrailMote"),
+        ));
+    }
+}
+
+fn burst_particle_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut BurstParticle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+        let remaining = 1.0 - particle.lifetime.fraction();
+        sprite.color.set_a(remaining);
+        transform.scale = Vec3::splat(remaining.max(0.2));
+    }
+}
+
+// Graphics toggle for the survivor's motion ghosts; off keeps the trail pool idle
+#[derive(Resource, Debug)]
+pub struct AfterimageSettings {
+    pub enabled: bool,
+}
+
+impl Default for AfterimageSettings {
+    fn default() -> Self { Self { enabled: true } }
+}
+
+#[derive(Component)]
+pub struct Afterimage {
+    pub lifetime: Timer,
+}
+
+// Same round-robin recycling as the damage numbers, so a long sprint never grows the entity count
+#[derive(Resource, Default)]
+pub struct AfterimagePool {
+    slots: Vec<Entity>,
+    next_slot: usize,
+}
+
+impl AfterimagePool {
+    pub fn with_slots(slots: Vec<Entity>) -> Self {
+        Self { slots, next_slot: 0 }
+    }
+
+    pub fn claim_slot(&mut self) -> Option<Entity> {
+        if self.slots.is_empty() { return None; }
+        let slot = self.slots[self.next_slot % self.slots.len()];
+        self.next_slot = (self.next_slot + 1) % self.slots.len();
+        Some(slot)
+    }
+}
+
+// Uses how far the survivor actually travelled, so speed buffs and knockback shoves both leave a trail
+pub fn should_emit_afterimage(displacement: Vec2, delta_seconds: f32) -> bool {
+    delta_seconds > 0.0 && displacement.length() / delta_seconds >= AFTERIMAGE_SPEED_THRESHOLD
+}
+
+fn spawn_afterimage_pool(mut commands: Commands, mut pool: ResMut<AfterimagePool>) {
+    let slots = (0..MAX_AFTERIMAGES).map(|_| {
+        commands.spawn((
+            SpriteBundle { visibility: Visibility::Hidden, ..default() },
+            Afterimage { lifetime: Timer::from_seconds(AFTERIMAGE_LIFETIME_SECONDS, TimerMode::Once) },
+            Name::new("Afterimage"),
+        )).id()
+    }).collect();
+    *pool = AfterimagePool::with_slots(slots);
+}
+
+fn emit_survivor_afterimages(
+    time: Res<Time>,
+    settings: Res<AfterimageSettings>,
+    mut pool: ResMut<AfterimagePool>,
+    mut last_position: Local<Option<Vec2>>,
+    mut emit_cooldown: Local<f32>,
+    survivor_query: Query<(&Transform, &Sprite, &Handle<Image>), With<Survivor>>,
+    mut afterimage_query: Query<(&mut Afterimage, &mut Transform, &mut Sprite, &mut Handle<Image>, &mut Visibility), Without<Survivor>>,
+) {
+    let Ok((survivor_transform, survivor_sprite, survivor_texture)) = survivor_query.get_single() else {
+        *last_position = None;
+        return;
+    };
+    let position = survivor_transform.translation.truncate();
+    let displacement = last_position.map_or(Vec2::ZERO, |previous| position - previous);
+    *last_position = Some(position);
+    *emit_cooldown -= time.delta_seconds();
+
+    if !settings.enabled || *emit_cooldown > 0.0 || !should_emit_afterimage(displacement, time.delta_seconds()) { return; }
+    let Some(slot) = pool.claim_slot() else { return; };
+    let Ok((mut afterimage, mut transform, mut sprite, mut texture, mut visibility)) = afterimage_query.get_mut(slot) else { return; };
+    *emit_cooldown = AFTERIMAGE_INTERVAL_SECONDS;
+    afterimage.lifetime.reset();
+    *transform = *survivor_transform;
+    transform.translation.z += AFTERIMAGE_Z_OFFSET;
+    *sprite = survivor_sprite.clone();
+    sprite.color.set_a(AFTERIMAGE_START_ALPHA);
+    *texture = survivor_texture.clone();
+    *visibility = Visibility::Visible;
+}
+
+fn fade_afterimages_system(
+    time: Res<Time>,
+    mut query: Query<(&mut Afterimage, &mut Sprite, &mut Visibility)>,
+) {
+    for (mut afterimage, mut sprite, mut visibility) in query.iter_mut() {
+        if *visibility == Visibility::Hidden { continue; }
+        afterimage.lifetime.tick(time.delta());
+        if afterimage.lifetime.finished() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        sprite.color.set_a(AFTERIMAGE_START_ALPHA * (1.0 - afterimage.lifetime.fraction()));
+    }
+}
+
+fn toggle_afterimages(keyboard_input: Res<ButtonInput<KeyCode>>, mut settings: ResMut<AfterimageSettings>) {
+    if keyboard_input.just_pressed(AFTERIMAGE_TOGGLE_KEY) {
+        settings.enabled = !settings.enabled;
+        info!("Survivor afterimages {}", if settings.enabled { "enabled" } else { "disabled" });
+    }
+}
+
+fn hide_afterimages(mut query: Query<&mut Visibility, With<Afterimage>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+#[derive(Component)]
+struct LowHealthVignette {
+    layer: usize,
+}
+
+// Outer-edge opacity at a given moment: a resting tint plus a flash on each heartbeat that decays before the next
+pub fn vignette_alpha(low_health: &LowHealth, elapsed_seconds: f32) -> f32 {
+    if !low_health.is_active() { return 0.0; }
+    let beat_phase = (elapsed_seconds * low_health.heartbeat_bpm() / 60.0).fract();
+    low_health.intensity * (VIGNETTE_RESTING_ALPHA + VIGNETTE_BEAT_ALPHA * (1.0 - beat_phase).powi(3))
+}
+
+fn spawn_low_health_vignette(mut commands: Commands) {
+    let layer_bundle = |fills_screen: bool| NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: if fills_screen { PositionType::Absolute } else { PositionType::Relative },
+            border: UiRect::all(Val::Px(VIGNETTE_LAYER_WIDTH)),
+            ..default()
+        },
+        border_color: BorderColor(Color::NONE),
+        z_index: ZIndex::Global(2),
+        ..default()
+    };
+    let mut parent = commands.spawn((layer_bundle(true), LowHealthVignette { layer: 0 }, Name::new("LowHealthVignette"))).id();
+    for layer in 1..VIGNETTE_LAYERS {
+        let child = commands.spawn((layer_bundle(false), LowHealthVignette { layer })).id();
+        commands.entity(parent).add_child(child);
+        parent = child;
+    }
+}
+
+fn pulse_low_health_vignette(time: Res<Time>, low_health: Res<LowHealth>, mut vignette_query: Query<(&LowHealthVignette, &mut BorderColor)>) {
+    let alpha = vignette_alpha(&low_health, time.elapsed_seconds());
+    for (vignette, mut border_color) in vignette_query.iter_mut() {
+        let layer_alpha = alpha * (1.0 - vignette.layer as f32 / VIGNETTE_LAYERS as f32);
+        let color = if layer_alpha > 0.0 { Color::rgba(0.8, 0.0, 0.05, layer_alpha) } else { Color::NONE };
+        if border_color.0 != color { border_color.0 = color; }
+    }
+}
+
+#[derive(Component)]
+pub struct EliteAura;
+
+pub fn elite_aura_color(modifier: EliteModifier) -> Color {
+    match modifier {
+        EliteModifier::Empowered => Color::rgb(1.0, 0.25, 0.2),
+    }
+}
+
+// Breathes between half and full strength so the outline reads as alive rather than a static border
+pub fn elite_aura_alpha(elapsed_seconds: f32) -> f32 {
+    0.75 + 0.25 * (elapsed_seconds * ELITE_AURA_PULSE_SPEED).sin()
+}
+
+fn attach_elite_auras(mut commands: Commands, elite_query: Query<(Entity, &EliteModifier, &Handle<Image>, &Sprite), Added<EliteModifier>>) {
+    for (entity, modifier, texture, sprite) in elite_query.iter() {
+        let aura = commands.spawn((
+            SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite { custom_size: sprite.custom_size.map(|size| size * ELITE_OUTLINE_SCALE), color: elite_aura_color(*modifier), ..default() },
+                transform: Transform::from_xyz(0.0, 0.0, ELITE_OUTLINE_Z_OFFSET),
+                ..default()
+            },
+            EliteAura,
+            Name::new("EliteAura"),
+        )).id();
+        commands.entity(entity).add_child(aura);
+    }
+}
+
+fn detach_elite_auras(mut commands: Commands, mut removed_modifiers: RemovedComponents<EliteModifier>, aura_query: Query<(Entity, &Parent), With<EliteAura>>) {
+    for entity in removed_modifiers.read() {
+        for (aura, _) in aura_query.iter().filter(|(_, parent)| parent.get() == entity) {
+            commands.entity(aura).despawn_recursive();
+        }
+    }
+}
+
+// Colour follows the parent's current modifier, so swapping one modifier for another needs no respawn
+fn pulse_elite_auras(time: Res<Time>, modifier_query: Query<&EliteModifier>, mut aura_query: Query<(&Parent, &mut Sprite), With<EliteAura>>) {
+    let alpha = elite_aura_alpha(time.elapsed_seconds());
+    for (parent, mut sprite) in aura_query.iter_mut() {
+        let Ok(modifier) = modifier_query.get(parent.get()) else { continue; };
+        sprite.color = elite_aura_color(*modifier).with_a(alpha);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeaconKind {
+    Boss,
+    ExtractionRift,
+}
+
+impl BeaconKind {
+    pub fn color(&self) -> Color {
+        match self {
+            BeaconKind::Boss => Color::rgb(1.0, 0.25, 0.2),
+            BeaconKind::ExtractionRift => Color::rgb(0.4, 0.9, 1.0),
+        }
+    }
+}
+
+// Elites stand in for bosses; every one that spawns gets a beacon, as does every rift
+pub fn beacon_for_event(event: &GameEvent) -> Option<(BeaconKind, Vec3)> {
+    match event {
+        GameEvent::EliteSpawned { position, .. } => Some((BeaconKind::Boss, *position)),
+        GameEvent::ExtractionRiftOpened { position } => Some((BeaconKind::ExtractionRift, *position)),
+        _ => None,
+    }
+}
+
+// Flashes a few times while fading out over the beacon's life
+pub fn beacon_alpha(life_fraction: f32) -> f32 {
+    let life_fraction = life_fraction.clamp(0.0, 1.0);
+    let flash = 0.5 + 0.5 * (life_fraction * BEACON_FLASHES * std::f32::consts::TAU).cos();
+    BEACON_MAX_ALPHA * (1.0 - life_fraction) * (0.4 + 0.6 * flash)
+}
+
+// On screen the ping sits on the target itself; off screen it slides along the line from the centre until it meets the inset edge
+pub fn beacon_ping_position(viewport_size: Vec2, target: Vec2, margin: f32) -> Vec2 {
+    let center = viewport_size / 2.0;
+    let half_extents = (center - Vec2::splat(margin)).max(Vec2::ZERO);
+    let offset = target - center;
+    if offset.x.abs() <= half_extents.x && offset.y.abs() <= half_extents.y { return target; }
+    let scale_to_edge = |half: f32, along: f32| if along == 0.0 { f32::INFINITY } else { half / along.abs() };
+    center + offset * scale_to_edge(half_extents.x, offset.x).min(scale_to_edge(half_extents.y, offset.y))
+}
+
+#[derive(Component)]
+pub struct Beacon {
+    pub kind: BeaconKind,
+    pub lifetime: Timer,
+}
+
+#[derive(Component)]
+struct BeaconPing {
+    target: Vec3,
+    kind: BeaconKind,
+    lifetime: Timer,
+}
+
+fn raise_beacons(mut commands: Commands, mut game_events: EventReader<GameEvent>, mut sound_event_writer: EventWriter<PlaySoundEvent>) {
+    for (kind, position) in game_events.read().filter_map(beacon_for_event) {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite { custom_size: Some(Vec2::new(BEACON_WIDTH, BEACON_HEIGHT)), color: kind.color().with_a(BEACON_MAX_ALPHA), anchor: Anchor::BottomCenter, ..default() },
+                transform: Transform::from_translation(position.truncate().extend(BEACON_Z_POS)),
+                ..default()
+            },
+            Beacon { kind, lifetime: Timer::from_seconds(BEACON_LIFETIME_SECONDS, TimerMode::Once) },
+            Name::new("Beacon"),
+        ));
+        commands.spawn((
+            NodeBundle {
+                style: Style { position_type: PositionType::Absolute, width: Val::Px(BEACON_PING_SIZE), height: Val::Px(BEACON_PING_SIZE), ..default() },
+                background_color: kind.color().into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(4),
+                ..default()
+            },
+            BeaconPing { target: position, kind, lifetime: Timer::from_seconds(BEACON_LIFETIME_SECONDS, TimerMode::Once) },
+        ));
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::BeaconPing));
+    }
+}
+
+fn animate_beacons(mut commands: Commands, time: Res<Time>, mut beacon_query: Query<(Entity, &mut Beacon, &mut Sprite)>) {
+    for (entity, mut beacon, mut sprite) in beacon_query.iter_mut() {
+        if beacon.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        sprite.color = beacon.kind.color().with_a(beacon_alpha(beacon.lifetime.fraction()));
+    }
+}
+
+fn animate_beacon_pings(
+    mut commands: Commands,
+    time: Res<Time>,
+    ui_scale: Res<UiScale>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut ping_query: Query<(Entity, &mut BeaconPing, &mut Style, &mut BackgroundColor, &mut Visibility)>,
+) {
+    let camera = camera_query.get_single().ok();
+    let ui_scale = ui_scale.0.max(f32::EPSILON);
+    for (entity, mut ping, mut style, mut background, mut visibility) in ping_query.iter_mut() {
+        if ping.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let Some((camera, camera_transform)) = camera else { continue; };
+        let (Some(viewport_size), Some(target)) = (camera.logical_viewport_size(), camera.world_to_viewport(camera_transform, ping.target)) else { continue; };
+        // The viewport is in window pixels and the UI in UI pixels, which shrink with the UI scale
+        let position = beacon_ping_position(viewport_size / ui_scale, target / ui_scale, BEACON_PING_EDGE_MARGIN);
+        style.left = Val::Px(position.x - BEACON_PING_SIZE / 2.0);
+        style.top = Val::Px(position.y - BEACON_PING_SIZE / 2.0);
+        *background = ping.kind.color().with_a(beacon_alpha(ping.lifetime.fraction()) / BEACON_MAX_ALPHA).into();
+        *visibility = Visibility::Visible;
+    }
+}
+
+fn despawn_beacons(mut commands: Commands, beacon_query: Query<Entity, Or<(With<Beacon>, With<BeaconPing>)>>) {
+    for entity in beacon_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use crate::{
+    game::{AppState, GameConfig, GameState},
+    components::Health,
+    survivor::{Survivor, DamageSource, SURVIVOR_SIZE},
+    game_events::GameEvent,
+    challenge::ActiveChallenge,
+    boss_practice::BossPractice,
+    balance::BalanceConfig,
+};
+
+pub const VOID_TOGGLE_KEY: KeyCode = KeyCode::KeyN;
+// Half the side of the square still safe from the void, at the start of the run and once it stops closing
+pub const VOID_START_HALF_EXTENT: f32 = 2400.0;
+pub const VOID_MIN_HALF_EXTENT: f32 = 400.0;
+pub const VOID_CONTRACTION_SECONDS: f32 = 900.0;
+pub const VOID_DAMAGE: i32 = 6;
+const VOID_MENU_COLOR: Color = Color::rgb(0.65, 0.5, 1.0);
+
+pub struct VoidBoundaryPlugin;
+
+impl Plugin for VoidBoundaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VoidClosesIn>()
+            .init_resource::<VoidBoundary>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_void_menu_text)
+            .add_systems(Update, (toggle_void_closes_in, update_void_menu_text).chain().run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnExit(AppState::MainMenu), (despawn_void_menu_text, arm_void_boundary))
+            .add_systems(Update, (contract_void_boundary, void_boundary_damage_system).chain().run_if(in_state(AppState::InGame)));
+    }
+}
+
+// The player's choice for upcoming runs; kept in the profile
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct VoidClosesIn {
+    pub enabled: bool,
+}
+
+// The boundary for the run in progress, centred on the arena; None when the run has no closing void
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct VoidBoundary {
+    pub half_extent: Option<f32>,
+}
+
+impl VoidBoundary {
+    pub fn is_active(&self) -> bool { self.half_extent.is_some() }
+}
+
+// Closes at a steady pace, then holds at the smallest size so a long run stays survivable
+pub fn void_half_extent(elapsed_secs: f32) -> f32 {
+    let progress = (elapsed_secs / VOID_CONTRACTION_SECONDS).clamp(0.0, 1.0);
+    VOID_START_HALF_EXTENT + (VOID_MIN_HALF_EXTENT - VOID_START_HALF_EXTENT) * progress
+}
+
+// How far past the boundary a circle of this radius reaches; zero or less means it is clear of the void
+pub fn void_depth(position: Vec2, radius: f32, center: Vec2, half_extent: f32) -> f32 {
+    ((position - center).abs() + Vec2::splat(radius) - Vec2::splat(half_extent)).max_element()
+}
+
+#[derive(Component)]
+struct VoidMenuText;
+
+fn void_menu_label(void_closes_in: &VoidClosesIn) -> String {
+    format!("Void closes in: {} (N)", if void_closes_in.enabled { "ON" } else { "OFF" })
+}
+
+fn setup_void_menu_text(mut commands: Commands, asset_server: Res<AssetServer>, void_closes_in: Res<VoidClosesIn>) {
+    commands.spawn((
+        TextBundle::from_section(void_menu_label(&void_closes_in), TextStyle { font: asset_server.load("fonts/FiraSans-Bold.ttf"), font_size: 22.0, color: VOID_MENU_COLOR })
+            .with_style(Style { position_type: PositionType::Absolute, top: Val::Px(50.0), left: Val::Px(20.0), ..default() }),
+        VoidMenuText,
+    ));
+}
+
+fn toggle_void_closes_in(keyboard_input: Res<ButtonInput<KeyCode>>, mut void_closes_in: ResMut<VoidClosesIn>) {
+    if keyboard_input.just_pressed(VOID_TOGGLE_KEY) { void_closes_in.enabled = !void_closes_in.enabled; }
+}
+
+fn update_void_menu_text(void_closes_in: Res<VoidClosesIn>, mut text_query: Query<&mut Text, With<VoidMenuText>>) {
+    if !void_closes_in.is_changed() { return; }
+    for mut text in text_query.iter_mut() { text.sections[0].value = void_menu_label(&void_closes_in); }
+}
+
+fn despawn_void_menu_text(mut commands: Commands, text_query: Query<Entity, With<VoidMenuText>>) {
+    for entity in text_query.iter() { commands.entity(entity).despawn_recursive(); }
+}
+
+// Challenge runs are compared against everyone else's and practice fights are about the boss, so neither gets the void
+fn arm_void_boundary(void_closes_in: Res<VoidClosesIn>, active_challenge: Res<ActiveChallenge>, practice: Res<BossPractice>, mut boundary: ResMut<VoidBoundary>) {
+    let armed = void_closes_in.enabled && active_challenge.0.is_none() && !practice.is_active();
+    boundary.half_extent = armed.then_some(VOID_START_HALF_EXTENT);
+}
+
+fn contract_void_boundary(game_state: Res<GameState>, mut boundary: ResMut<VoidBoundary>) {
+    if !boundary.is_active() { return; }
+    let half_extent = void_half_extent(game_state.game_timer.elapsed_secs());
+    if boundary.half_extent != Some(half_extent) { boundary.half_extent = Some(half_extent); }
+}
+
+// Standing in the void burns on the hazard grace, so the damage comes in steady pulses rather than every frame
+fn void_boundary_damage_system(
+    boundary: Res<VoidBoundary>,
+    game_config: Res<GameConfig>,
+    balance: Res<BalanceConfig>,
+    mut survivor_query: Query<(&Transform, &mut Health, &mut Survivor)>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let Some(half_extent) = boundary.half_extent else { return; };
+    let Ok((transform, mut health, mut survivor)) = survivor_query.get_single_mut() else { return; };
+    if health.0 <= 0 || void_depth(transform.translation.truncate(), SURVIVOR_SIZE.x / 2.0, game_config.arena_center, half_extent) <= 0.0 { return; }
+    if !survivor.can_take_damage_from(DamageSource::Hazard) { return; }
+    health.0 -= VOID_DAMAGE;
+    survivor.register_hit_with_grace(DamageSource::Hazard, balance.grace_secs(DamageSource::Hazard));
+    game_event_writer.send(GameEvent::SurvivorHit { damage: VOID_DAMAGE, source: DamageSource::Hazard });
+}
@@ -1,19 +1,47 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use rand::Rng;
 use crate::{
-    survivor::Survivor, // Changed
+    survivor::{Survivor, SURVIVOR_SIZE}, // Changed
     horror::Horror,   // Changed
-    components::{Health, Damage},
-    game::AppState, // GameState import removed as it was unused
+    components::{Health, Damage, Velocity, DamageType, SpawnExclusion},
+    game::{AppState, GameRng}, // GameState import removed as it was unused
     audio::{PlaySoundEvent, SoundEffect},
-    visual_effects::spawn_damage_text,
+    visual_effects::{spawn_damage_text, spawn_beam_visual, spawn_particle_burst, spawn_impact_effect, DamageTypeStyle},
+    ichor_blast::spawn_ichor_blast,
+    projectile::steer_towards,
+    game_events::{GameEvent, DamageOrigin},
+    skills::LastHitBySkill,
+    summons::{Summoned, SummonKind},
+    perf_overlay::timed_collision,
 };
 
+// The weapons that fight on their own once manifested; the rear guard fires ichor blasts, so it isn't listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutoWeapon { CircleOfWarding, SwarmOfNightmares, SearingGaze, SporeCenser, SnareLayer, HookedCrescent, }
+
+impl AutoWeapon {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AutoWeapon::CircleOfWarding => "Circle of Warding",
+            AutoWeapon::SwarmOfNightmares => "Swarm of Nightmares",
+            AutoWeapon::SearingGaze => "Searing Gaze",
+            AutoWeapon::SporeCenser => "Spore Censer",
+            AutoWeapon::SnareLayer => "Hex Snares",
+            AutoWeapon::HookedCrescent => "Hooked Crescent",
+        }
+    }
+    pub fn hit(self, amount: i32, position: Vec3) -> GameEvent {
+        GameEvent::DamageDealt { amount, damage_type: DamageType::Eldritch, position, origin: DamageOrigin::Weapon(self) }
+    }
+}
+
 // --- Circle of Warding Aura Weapon ---
 #[derive(Component, Debug)]
 pub struct CircleOfWarding {
     pub damage_tick_timer: Timer,
     pub current_radius: f32,
     pub base_damage_per_tick: i32,
+    pub damage_multiplier: f32, // Fed each tick by item effects, see items::missing_health_aura_modifier_system
     pub is_active: bool,
     pub visual_entity: Option<Entity>,
 }
@@ -24,6 +52,7 @@ impl Default for CircleOfWarding {
             damage_tick_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
             current_radius: 75.0,
             base_damage_per_tick: 3,
+            damage_multiplier: 1.0,
             is_active: false,
             visual_entity: None,
         }
@@ -62,10 +91,227 @@ impl Default for SwarmOfNightmares {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Default)]
 pub struct NightmareLarva {
     pub angle: f32,
-    pub enemies_on_cooldown: Vec<(Entity, Timer)>,
+    pub enemies_on_cooldown: HashMap<Entity, Timer>,
+}
+
+impl NightmareLarva {
+    // Expired entries are dropped here, which also clears out horrors that died while on cooldown
+    pub fn tick_cooldowns(&mut self, delta: std::time::Duration) {
+        self.enemies_on_cooldown.retain(|_enemy_id, timer| { timer.tick(delta); !timer.finished() });
+    }
+    pub fn is_on_cooldown(&self, enemy: Entity) -> bool { self.enemies_on_cooldown.contains_key(&enemy) }
+    pub fn start_cooldown(&mut self, enemy: Entity, duration_secs: f32) {
+        self.enemies_on_cooldown.insert(enemy, Timer::from_seconds(duration_secs, TimerMode::Once));
+    }
+}
+
+
+// --- Searing Gaze (Sweeping Laser Weapon) ---
+const SEARING_GAZE_BEAM_WIDTH: f32 = 14.0;
+const SEARING_GAZE_COLOR: Color = Color::rgba(1.0, 0.35, 0.2, 0.8);
+
+#[derive(Component, Debug)]
+pub struct SearingGaze {
+    pub is_active: bool,
+    pub sweep_interval_timer: Timer,
+    pub arc_degrees: f32,
+    pub sweep_speed_degrees: f32, // Degrees per second
+    pub range: f32,
+    pub damage_per_sweep: i32,
+    pub sweep_start_angle: f32,
+    pub swept_degrees: Option<f32>, // Some while a sweep is in progress
+    pub already_hit_this_sweep: Vec<Entity>,
+    pub visual_entity: Option<Entity>,
+}
+
+impl Default for SearingGaze {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            sweep_interval_timer: Timer::from_seconds(2.5, TimerMode::Repeating),
+            arc_degrees: 90.0,
+            sweep_speed_degrees: 180.0,
+            range: 220.0,
+            damage_per_sweep: 12,
+            sweep_start_angle: 0.0,
+            swept_degrees: None,
+            already_hit_this_sweep: Vec::new(),
+            visual_entity: None,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SearingGazeBeam;
+
+
+// --- Spore Censer (Seeking Spore Weapon) ---
+const SEEKING_SPORE_SIZE: Vec2 = Vec2::new(18.0, 18.0);
+const SEEKING_SPORE_COLOR: Color = Color::rgb(0.6, 0.9, 0.4);
+const SEEKING_SPORE_SPEED: f32 = 90.0;
+const SEEKING_SPORE_TURN_RATE: f32 = 2.5; // Radians per second
+const SEEKING_SPORE_Z_POS: f32 = 0.45;
+
+#[derive(Component, Debug)]
+pub struct SporeCenser {
+    pub is_active: bool,
+    pub emit_timer: Timer,
+    pub max_spores: u32,
+    pub burst_damage: i32,
+    pub burst_radius: f32,
+}
+
+impl Default for SporeCenser {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            emit_timer: Timer::from_seconds(1.2, TimerMode::Repeating),
+            max_spores: 4,
+            burst_damage: 10,
+            burst_radius: 60.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SeekingSpore {
+    pub spawned_at: f32,
+    pub burst_damage: i32,
+    pub burst_radius: f32,
+}
+
+
+// --- Rear Guard (Backwards Firing Weapon) ---
+const REAR_GUARD_SPREAD_DEGREES: f32 = 12.0;
+
+#[derive(Component, Debug)]
+pub struct RearGuard {
+    pub is_active: bool,
+    pub fire_timer: Timer,
+    pub base_shots: u32,
+    pub damage_per_shot: i32,
+}
+
+impl Default for RearGuard {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            fire_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            base_shots: 1,
+            damage_per_shot: 8,
+        }
+    }
+}
+
+
+// --- Hex Snares (Dropped Mine Weapon) ---
+const HEX_SNARE_SIZE: Vec2 = Vec2::new(22.0, 22.0);
+const HEX_SNARE_UNARMED_COLOR: Color = Color::rgba(0.5, 0.2, 0.2, 0.5);
+const HEX_SNARE_ARMED_COLOR: Color = Color::rgb(0.9, 0.15, 0.15);
+const HEX_SNARE_Z_POS: f32 = 0.2;
+
+#[derive(Component, Debug)]
+pub struct SnareLayer {
+    pub is_active: bool,
+    pub drop_distance: f32, // Distance walked between snares
+    pub distance_since_last_drop: f32,
+    pub last_position: Option<Vec2>,
+    pub max_snares: u32,
+    pub arming_secs: f32,
+    pub trigger_radius: f32,
+    pub blast_radius: f32,
+    pub damage: i32,
+}
+
+impl Default for SnareLayer {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            drop_distance: 120.0,
+            distance_since_last_drop: 0.0,
+            last_position: None,
+            max_snares: 5,
+            arming_secs: 0.75,
+            trigger_radius: 40.0,
+            blast_radius: 90.0,
+            damage: 20,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HexSnare {
+    pub spawned_at: f32,
+    pub arming_timer: Timer,
+    pub trigger_radius: f32,
+    pub blast_radius: f32,
+    pub damage: i32,
+}
+
+
+// --- Hooked Crescent (Returning Boomerang Weapon) ---
+const HOOKED_CRESCENT_SIZE: Vec2 = Vec2::new(30.0, 30.0);
+const HOOKED_CRESCENT_COLOR: Color = Color::rgb(0.85, 0.85, 0.7);
+const HOOKED_CRESCENT_SPIN_SPEED: f32 = 12.0; // Radians per second, purely visual
+const HOOKED_CRESCENT_Z_POS: f32 = 0.5;
+
+#[derive(Component, Debug)]
+pub struct HookedCrescent {
+    pub is_active: bool,
+    pub throw_timer: Timer,
+    pub damage: i32,
+    pub speed: f32,
+    pub outbound_secs: f32,
+}
+
+impl Default for HookedCrescent {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            throw_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+            damage: 14,
+            speed: 420.0,
+            outbound_secs: 0.6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrescentPhase { Outbound { elapsed_secs: f32 }, Returning, }
+
+// Outbound the crescent decelerates to a standstill at the end of its reach, then homes back on the thrower at full speed
+#[derive(Component, Debug)]
+pub struct CrescentProjectile {
+    pub phase: CrescentPhase,
+    pub throw_direction: Vec2,
+    pub speed: f32,
+    pub outbound_secs: f32,
+    pub damage: i32,
+    pub hit_this_phase: Vec<Entity>,
+}
+
+impl CrescentProjectile {
+    pub fn velocity(&self, to_thrower: Vec2) -> Vec2 {
+        match self.phase {
+            CrescentPhase::Outbound { elapsed_secs } => self.throw_direction * self.speed * (1.0 - elapsed_secs / self.outbound_secs).max(0.0),
+            CrescentPhase::Returning => to_thrower.normalize_or_zero() * self.speed,
+        }
+    }
+    // Returns true on the frame the crescent turns around
+    pub fn advance_phase(&mut self, delta_secs: f32) -> bool {
+        let CrescentPhase::Outbound { elapsed_secs } = self.phase else { return false; };
+        let elapsed_secs = elapsed_secs + delta_secs;
+        if elapsed_secs < self.outbound_secs {
+            self.phase = CrescentPhase::Outbound { elapsed_secs };
+            return false;
+        }
+        self.phase = CrescentPhase::Returning;
+        self.hit_this_phase.clear();
+        true
+    }
 }
 
 
@@ -79,7 +325,17 @@ impl Plugin for WeaponsPlugin {
                 update_circle_of_warding_visual_system,
                 manage_nightmare_larvae_system,
                 nightmare_larva_movement_system,
-                nightmare_larva_collision_system,
+                timed_collision(nightmare_larva_collision_system),
+                searing_gaze_sweep_system,
+                spore_censer_emit_system,
+                seeking_spore_movement_system,
+                seeking_spore_burst_system,
+                rear_guard_fire_system,
+                snare_layer_drop_system,
+                hex_snare_detonation_system,
+                hooked_crescent_throw_system,
+                crescent_projectile_movement_system,
+                timed_collision(crescent_projectile_collision_system),
             )
             .chain()
             .run_if(in_state(AppState::InGame))
@@ -89,21 +345,25 @@ impl Plugin for WeaponsPlugin {
 }
 
 fn circle_of_warding_aura_system(
-    _commands: Commands,
+    mut commands: Commands,
     time: Res<Time>,
-    mut player_query: Query<(&Transform, &mut CircleOfWarding), With<Survivor>>,
-    mut horror_query: Query<(&Transform, &mut Health, &Horror), With<Horror>>,
+    mut player_query: Query<(&Transform, &Survivor, &mut CircleOfWarding)>,
+    mut horror_query: Query<(Entity, &Transform, &mut Health, &Horror), With<Horror>>,
+    mut game_event_writer: EventWriter<GameEvent>,
 ) {
-    for (player_transform, mut aura_weapon) in player_query.iter_mut() {
+    for (player_transform, survivor, mut aura_weapon) in player_query.iter_mut() {
         if !aura_weapon.is_active { continue; }
         aura_weapon.damage_tick_timer.tick(time.delta());
         if aura_weapon.damage_tick_timer.just_finished() {
             let player_position = player_transform.translation.truncate();
-            let aura_radius_sq = aura_weapon.current_radius.powi(2);
-            for (horror_transform, mut horror_health, _horror_data) in horror_query.iter_mut() {
+            let aura_radius_sq = (aura_weapon.current_radius * survivor.area_multiplier).powi(2);
+            let damage_per_tick = (aura_weapon.base_damage_per_tick as f32 * aura_weapon.damage_multiplier).round() as i32;
+            for (horror_entity, horror_transform, mut horror_health, _horror_data) in horror_query.iter_mut() {
                 let horror_position = horror_transform.translation.truncate();
                 if player_position.distance_squared(horror_position) < aura_radius_sq {
-                    horror_health.0 -= aura_weapon.base_damage_per_tick;
+                    horror_health.0 -= damage_per_tick;
+                    commands.entity(horror_entity).remove::<LastHitBySkill>();
+                    game_event_writer.send(AutoWeapon::CircleOfWarding.hit(damage_per_tick, horror_transform.translation));
                 }
             }
         }
@@ -113,12 +373,12 @@ fn circle_of_warding_aura_system(
 fn update_circle_of_warding_visual_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut player_query: Query<(Entity, &mut CircleOfWarding), With<Survivor>>,
+    mut player_query: Query<(Entity, &Survivor, &mut CircleOfWarding)>,
     mut visual_query: Query<(Entity, &mut Transform, &mut Sprite), With<CircleOfWardingVisual>>,
 ) {
-    if let Ok((player_entity, mut aura_weapon)) = player_query.get_single_mut() {
+    if let Ok((player_entity, survivor, mut aura_weapon)) = player_query.get_single_mut() {
         if aura_weapon.is_active {
-            let diameter = aura_weapon.current_radius * 2.0;
+            let diameter = aura_weapon.current_radius * survivor.area_multiplier * 2.0;
             let target_scale = diameter;
             if let Some(visual_entity) = aura_weapon.visual_entity {
                 if let Ok((_v_ent, mut visual_transform, _visual_sprite)) = visual_query.get_mut(visual_entity) {
@@ -174,7 +434,7 @@ fn manage_nightmare_larvae_system(
             for i in 0..num_to_spawn {
                 let angle_offset = (current_larva_count + i) as f32 * (2.0 * std::f32::consts::PI / weapon_stats.num_larvae.max(1) as f32);
                 let initial_local_pos = Vec3::new( weapon_stats.orbit_radius * angle_offset.cos(), weapon_stats.orbit_radius * angle_offset.sin(), NIGHTMARE_LARVA_LOCAL_Z );
-                let larva_entity = commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/nightmare_larva_placeholder.png"), sprite: Sprite { custom_size: Some(NIGHTMARE_LARVA_SPRITE_SIZE), color: NIGHTMARE_LARVA_DEBUG_COLOR, ..default() }, transform: Transform::from_translation(initial_local_pos), visibility: Visibility::Visible, ..default() }, NightmareLarva { angle: angle_offset, enemies_on_cooldown: Vec::new(), }, Damage(weapon_stats.damage_per_hit), Name::new(format!("NightmareLarva_{}", i)), )).id();
+                let larva_entity = commands.spawn(( SpriteBundle { texture: asset_server.load("sprites/nightmare_larva_placeholder.png"), sprite: Sprite { custom_size: Some(NIGHTMARE_LARVA_SPRITE_SIZE), color: NIGHTMARE_LARVA_DEBUG_COLOR, ..default() }, transform: Transform::from_translation(initial_local_pos), visibility: Visibility::Visible, ..default() }, NightmareLarva { angle: angle_offset, ..default() }, Damage(weapon_stats.damage_per_hit), Summoned(SummonKind::NightmareLarva), Name::new(format!("NightmareLarva_{}", i)), )).id();
                 commands.entity(player_entity).add_child(larva_entity);
             }
         } else if current_larva_count > weapon_stats.num_larvae {
@@ -210,32 +470,368 @@ fn nightmare_larva_movement_system(
 fn nightmare_larva_collision_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut larva_query: Query<(Entity, &GlobalTransform, &Damage, &mut NightmareLarva)>,
+    mut larva_query: Query<(Entity, &GlobalTransform, &Damage, &mut NightmareLarva, &Summoned)>,
     mut horror_query: Query<(Entity, &GlobalTransform, &mut Health, &Horror)>, // Added &Horror
-    asset_server: Res<AssetServer>,
     mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
     player_weapon_query: Query<&SwarmOfNightmares, With<Survivor>>,
 ) {
     let Ok(weapon_stats) = player_weapon_query.get_single() else { return; };
     if !weapon_stats.is_active { return; }
 
-    for (_larva_entity, larva_g_transform, larva_damage, mut larva_data) in larva_query.iter_mut() {
-        larva_data.enemies_on_cooldown.retain_mut(|(_enemy_id, timer)| {
-            timer.tick(time.delta()); !timer.finished()
-        });
+    for (_larva_entity, larva_g_transform, larva_damage, mut larva_data, summoned) in larva_query.iter_mut() {
+        larva_data.tick_cooldowns(time.delta());
         let larva_pos = larva_g_transform.translation().truncate();
         let larva_radius = NIGHTMARE_LARVA_SPRITE_SIZE.x / 2.0;
 
         for (horror_entity, horror_gtransform, mut horror_health, horror_data) in horror_query.iter_mut() { // Added horror_data
-            if larva_data.enemies_on_cooldown.iter().any(|(e_id, _)| *e_id == horror_entity) { continue; }
+            if larva_data.is_on_cooldown(horror_entity) { continue; }
             let horror_pos = horror_gtransform.translation().truncate();
             let horror_radius = horror_data.size.x / 2.0; // Use horror_data
             if larva_pos.distance(horror_pos) < larva_radius + horror_radius {
-                sound_event_writer.send(PlaySoundEvent(SoundEffect::HorrorHit));
+                sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
                 horror_health.0 -= larva_damage.0;
-                spawn_damage_text(&mut commands, &asset_server, horror_gtransform.translation(), larva_damage.0, &time);
-                larva_data.enemies_on_cooldown.push((horror_entity, Timer::from_seconds(weapon_stats.hit_cooldown_duration, TimerMode::Once)));
+                commands.entity(horror_entity).remove::<LastHitBySkill>();
+                spawn_damage_text(&mut commands, horror_gtransform.translation(), larva_damage.0, &time);
+                game_event_writer.send(summoned.hit(larva_damage.0, DamageType::Eldritch, horror_gtransform.translation()));
+                larva_data.start_cooldown(horror_entity, weapon_stats.hit_cooldown_duration);
             }
         }
     }
-}
\ No newline at end of file
+}
+// The beam rotates through its arc centred on the aim direction; anything inside the swept wedge is hit once per sweep,
+// so fast sweeps cannot skip over horrors between frames
+fn searing_gaze_sweep_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &Survivor, &mut SearingGaze)>,
+    mut horror_query: Query<(Entity, &Transform, &mut Health, &Horror), Without<Survivor>>,
+    mut beam_transform_query: Query<&mut Transform, (With<SearingGazeBeam>, Without<Survivor>, Without<Horror>)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let Ok((player_transform, survivor, mut gaze)) = player_query.get_single_mut() else { return; };
+    if !gaze.is_active {
+        if let Some(visual_entity) = gaze.visual_entity.take() { commands.entity(visual_entity).despawn_recursive(); }
+        gaze.swept_degrees = None;
+        return;
+    }
+    let player_pos = player_transform.translation.truncate();
+
+    if gaze.swept_degrees.is_none() {
+        gaze.sweep_interval_timer.tick(time.delta());
+        if !gaze.sweep_interval_timer.just_finished() { return; }
+        let aim_direction = if survivor.aim_direction != Vec2::ZERO { survivor.aim_direction } else { Vec2::X };
+        gaze.sweep_start_angle = aim_direction.to_angle() - gaze.arc_degrees.to_radians() / 2.0;
+        gaze.swept_degrees = Some(0.0);
+        gaze.already_hit_this_sweep.clear();
+        let start_direction = Vec2::from_angle(gaze.sweep_start_angle);
+        let range = gaze.range;
+        let beam_entity = spawn_beam_visual(&mut commands, &asset_server, player_transform.translation, start_direction, range, SEARING_GAZE_BEAM_WIDTH, SEARING_GAZE_COLOR);
+        commands.entity(beam_entity).insert((SearingGazeBeam, Name::new("SearingGazeBeam")));
+        gaze.visual_entity = Some(beam_entity);
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::RitualCast));
+    }
+
+    let Some(previous_swept) = gaze.swept_degrees else { return; };
+    let swept = (previous_swept + gaze.sweep_speed_degrees * time.delta_seconds()).min(gaze.arc_degrees);
+    let swept_radians = swept.to_radians();
+    let range_sq = gaze.range.powi(2);
+    let start_angle = gaze.sweep_start_angle;
+
+    for (horror_entity, horror_transform, mut horror_health, _horror) in horror_query.iter_mut() {
+        if gaze.already_hit_this_sweep.contains(&horror_entity) { continue; }
+        let offset = horror_transform.translation.truncate() - player_pos;
+        if offset.length_squared() > range_sq { continue; }
+        let angle_into_sweep = (offset.to_angle() - start_angle).rem_euclid(std::f32::consts::TAU);
+        if angle_into_sweep > swept_radians { continue; }
+        horror_health.0 -= gaze.damage_per_sweep;
+        commands.entity(horror_entity).remove::<LastHitBySkill>();
+        spawn_damage_text(&mut commands, horror_transform.translation, gaze.damage_per_sweep, &time);
+        game_event_writer.send(AutoWeapon::SearingGaze.hit(gaze.damage_per_sweep, horror_transform.translation));
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+        gaze.already_hit_this_sweep.push(horror_entity);
+    }
+
+    if let Some(visual_entity) = gaze.visual_entity {
+        if let Ok(mut beam_transform) = beam_transform_query.get_mut(visual_entity) {
+            beam_transform.translation.x = player_pos.x;
+            beam_transform.translation.y = player_pos.y;
+            beam_transform.rotation = Quat::from_rotation_z(start_angle + swept_radians);
+        }
+    }
+
+    if swept >= gaze.arc_degrees {
+        gaze.swept_degrees = None;
+        if let Some(visual_entity) = gaze.visual_entity.take() { commands.entity(visual_entity).despawn_recursive(); }
+    } else {
+        gaze.swept_degrees = Some(swept);
+    }
+}
+
+// Once the cap is reached the oldest spore is recycled so the censer keeps emitting fresh ones near the player
+fn spore_censer_emit_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &Survivor, &mut SporeCenser)>,
+    spore_query: Query<(Entity, &SeekingSpore)>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Ok((player_transform, survivor, mut censer)) = player_query.get_single_mut() else { return; };
+    if !censer.is_active { return; }
+    censer.emit_timer.tick(time.delta());
+    if !censer.emit_timer.just_finished() { return; }
+
+    let mut live_spores: Vec<(Entity, f32)> = spore_query.iter().map(|(entity, spore)| (entity, spore.spawned_at)).collect();
+    live_spores.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let excess = (live_spores.len() + 1).saturating_sub(censer.max_spores.max(1) as usize);
+    for (oldest_entity, _) in live_spores.iter().take(excess) { commands.entity(*oldest_entity).despawn_recursive(); }
+
+    let drift_direction = Vec2::from_angle(game_rng.0.gen::<f32>() * std::f32::consts::TAU);
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/echoing_soul_orb_placeholder.png"),
+            sprite: Sprite { custom_size: Some(SEEKING_SPORE_SIZE), color: SEEKING_SPORE_COLOR, ..default() },
+            transform: Transform::from_translation(player_transform.translation.truncate().extend(SEEKING_SPORE_Z_POS)),
+            ..default()
+        },
+        SeekingSpore { spawned_at: time.elapsed_seconds(), burst_damage: censer.burst_damage, burst_radius: censer.burst_radius * survivor.area_multiplier },
+        Velocity(drift_direction * SEEKING_SPORE_SPEED),
+        Name::new("SeekingSpore"),
+    ));
+}
+
+fn seeking_spore_movement_system(
+    time: Res<Time>,
+    mut spore_query: Query<(&mut Transform, &mut Velocity), With<SeekingSpore>>,
+    horror_query: Query<&Transform, (With<Horror>, Without<SeekingSpore>)>,
+) {
+    let max_turn = SEEKING_SPORE_TURN_RATE * time.delta_seconds();
+    for (mut spore_transform, mut velocity) in spore_query.iter_mut() {
+        let spore_pos = spore_transform.translation.truncate();
+        let nearest_horror = horror_query.iter()
+            .map(|horror_transform| horror_transform.translation.truncate())
+            .min_by(|a, b| a.distance_squared(spore_pos).total_cmp(&b.distance_squared(spore_pos)));
+        if let Some(target_pos) = nearest_horror {
+            velocity.0 = steer_towards(velocity.0, target_pos - spore_pos, max_turn);
+        }
+        spore_transform.translation += (velocity.0 * time.delta_seconds()).extend(0.0);
+    }
+}
+
+fn seeking_spore_burst_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    spore_query: Query<(Entity, &Transform, &SeekingSpore)>,
+    mut horror_query: Query<(Entity, &Transform, &mut Health, &Horror), Without<SeekingSpore>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    for (spore_entity, spore_transform, spore) in spore_query.iter() {
+        let spore_pos = spore_transform.translation.truncate();
+        let touched_horror = horror_query.iter().any(|(_, horror_transform, _, horror)| {
+            spore_pos.distance(horror_transform.translation.truncate()) < SEEKING_SPORE_SIZE.x / 2.0 + horror.size.x / 2.0
+        });
+        if !touched_horror { continue; }
+
+        let burst_radius_sq = spore.burst_radius.powi(2);
+        for (horror_entity, horror_transform, mut horror_health, _horror) in horror_query.iter_mut() {
+            if spore_pos.distance_squared(horror_transform.translation.truncate()) > burst_radius_sq { continue; }
+            horror_health.0 -= spore.burst_damage;
+            commands.entity(horror_entity).remove::<LastHitBySkill>();
+            spawn_damage_text(&mut commands, horror_transform.translation, spore.burst_damage, &time);
+            game_event_writer.send(AutoWeapon::SporeCenser.hit(spore.burst_damage, horror_transform.translation));
+        }
+        spawn_particle_burst(&mut commands, spore_transform.translation, SEEKING_SPORE_COLOR, 10, 140.0);
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+        commands.entity(spore_entity).despawn_recursive();
+    }
+}
+
+fn rear_guard_fire_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &Survivor, &mut RearGuard)>,
+) {
+    let Ok((player_transform, survivor, mut rear_guard)) = player_query.get_single_mut() else { return; };
+    if !rear_guard.is_active || survivor.aim_direction == Vec2::ZERO { return; }
+    rear_guard.fire_timer.tick(time.delta());
+    if !rear_guard.fire_timer.just_finished() { return; }
+
+    let total_shots = survivor.get_effective_rear_guard_shots(rear_guard.base_shots);
+    let spread_rad = REAR_GUARD_SPREAD_DEGREES.to_radians();
+    let start_angle = (-survivor.aim_direction).to_angle() - spread_rad * (total_shots - 1) as f32 / 2.0;
+    for shot_index in 0..total_shots {
+        let direction = Vec2::from_angle(start_angle + spread_rad * shot_index as f32);
+        spawn_ichor_blast(&mut commands, &asset_server, survivor, player_transform.translation, direction, rear_guard.damage_per_shot);
+    }
+}
+
+// Snares are only laid while walking; past the cap the oldest snare fizzles out to make room
+fn snare_layer_drop_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &Survivor, &mut SnareLayer)>,
+    snare_query: Query<(Entity, &HexSnare)>,
+) {
+    let Ok((player_transform, survivor, mut snare_layer)) = player_query.get_single_mut() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let previous_pos = snare_layer.last_position.replace(player_pos).unwrap_or(player_pos);
+    if !snare_layer.is_active { return; }
+    snare_layer.distance_since_last_drop += player_pos.distance(previous_pos);
+    if snare_layer.distance_since_last_drop < snare_layer.drop_distance { return; }
+    snare_layer.distance_since_last_drop = 0.0;
+
+    let mut live_snares: Vec<(Entity, f32)> = snare_query.iter().map(|(entity, snare)| (entity, snare.spawned_at)).collect();
+    live_snares.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let excess = (live_snares.len() + 1).saturating_sub(snare_layer.max_snares.max(1) as usize);
+    for (oldest_entity, _) in live_snares.iter().take(excess) { commands.entity(*oldest_entity).despawn_recursive(); }
+
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/circle_of_warding_effect_placeholder.png"),
+            sprite: Sprite { custom_size: Some(HEX_SNARE_SIZE), color: HEX_SNARE_UNARMED_COLOR, ..default() },
+            transform: Transform::from_translation(player_pos.extend(HEX_SNARE_Z_POS)),
+            ..default()
+        },
+        HexSnare {
+            spawned_at: time.elapsed_seconds(),
+            arming_timer: Timer::from_seconds(snare_layer.arming_secs, TimerMode::Once),
+            trigger_radius: snare_layer.trigger_radius,
+            blast_radius: snare_layer.blast_radius * survivor.area_multiplier,
+            damage: snare_layer.damage,
+        },
+        SpawnExclusion { radius: snare_layer.trigger_radius },
+        Name::new("HexSnare"),
+    ));
+}
+
+// A detonating snare sets off every other armed snare caught in its blast, which can cascade down a whole trail
+fn hex_snare_detonation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut snare_query: Query<(Entity, &Transform, &mut HexSnare, &mut Sprite)>,
+    mut horror_query: Query<(Entity, &Transform, &mut Health), (With<Horror>, Without<HexSnare>)>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let mut armed_snares: Vec<(Entity, Vec2, f32, i32)> = Vec::new();
+    let mut detonation_queue: Vec<usize> = Vec::new();
+    for (snare_entity, snare_transform, mut snare, mut sprite) in snare_query.iter_mut() {
+        snare.arming_timer.tick(time.delta());
+        if !snare.arming_timer.finished() { continue; }
+        sprite.color = HEX_SNARE_ARMED_COLOR;
+        let snare_pos = snare_transform.translation.truncate();
+        let trigger_radius_sq = snare.trigger_radius.powi(2);
+        if horror_query.iter().any(|(_, horror_transform, _)| snare_pos.distance_squared(horror_transform.translation.truncate()) < trigger_radius_sq) {
+            detonation_queue.push(armed_snares.len());
+        }
+        armed_snares.push((snare_entity, snare_pos, snare.blast_radius, snare.damage));
+    }
+    if detonation_queue.is_empty() { return; }
+
+    let mut detonated = vec![false; armed_snares.len()];
+    for &index in detonation_queue.iter() { detonated[index] = true; }
+    while let Some(index) = detonation_queue.pop() {
+        let (snare_entity, snare_pos, blast_radius, damage) = armed_snares[index];
+        let blast_radius_sq = blast_radius.powi(2);
+        for (other_index, (_, other_pos, _, _)) in armed_snares.iter().enumerate() {
+            if !detonated[other_index] && snare_pos.distance_squared(*other_pos) < blast_radius_sq {
+                detonated[other_index] = true;
+                detonation_queue.push(other_index);
+            }
+        }
+        for (horror_entity, horror_transform, mut horror_health) in horror_query.iter_mut() {
+            if snare_pos.distance_squared(horror_transform.translation.truncate()) > blast_radius_sq { continue; }
+            horror_health.0 -= damage;
+            commands.entity(horror_entity).remove::<LastHitBySkill>();
+            spawn_damage_text(&mut commands, horror_transform.translation, damage, &time);
+            game_event_writer.send(AutoWeapon::SnareLayer.hit(damage, horror_transform.translation));
+        }
+        spawn_particle_burst(&mut commands, snare_pos.extend(HEX_SNARE_Z_POS), HEX_SNARE_ARMED_COLOR, 14, 200.0);
+        sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+        commands.entity(snare_entity).despawn_recursive();
+    }
+}
+
+fn hooked_crescent_throw_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_query: Query<(&Transform, &Survivor, &mut HookedCrescent)>,
+) {
+    let Ok((player_transform, survivor, mut crescent_weapon)) = player_query.get_single_mut() else { return; };
+    if !crescent_weapon.is_active || survivor.aim_direction == Vec2::ZERO { return; }
+    crescent_weapon.throw_timer.tick(time.delta());
+    if !crescent_weapon.throw_timer.just_finished() { return; }
+
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("sprites/nightmare_larva_placeholder.png"),
+            sprite: Sprite { custom_size: Some(HOOKED_CRESCENT_SIZE), color: HOOKED_CRESCENT_COLOR, ..default() },
+            transform: Transform::from_translation(player_transform.translation.truncate().extend(HOOKED_CRESCENT_Z_POS)),
+            ..default()
+        },
+        CrescentProjectile {
+            phase: CrescentPhase::Outbound { elapsed_secs: 0.0 },
+            throw_direction: survivor.aim_direction.normalize_or_zero(),
+            speed: crescent_weapon.speed,
+            outbound_secs: crescent_weapon.outbound_secs,
+            damage: crescent_weapon.damage,
+            hit_this_phase: Vec::new(),
+        },
+        Name::new("HookedCrescent"),
+    ));
+}
+
+fn crescent_projectile_movement_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<Survivor>, Without<CrescentProjectile>)>,
+    mut crescent_query: Query<(Entity, &mut Transform, &mut CrescentProjectile)>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+    let player_pos = player_transform.translation.truncate();
+    let delta_secs = time.delta_seconds();
+    for (crescent_entity, mut crescent_transform, mut crescent) in crescent_query.iter_mut() {
+        crescent.advance_phase(delta_secs);
+        let crescent_pos = crescent_transform.translation.truncate();
+        if crescent.phase == CrescentPhase::Returning && crescent_pos.distance(player_pos) < SURVIVOR_SIZE.x / 2.0 {
+            commands.entity(crescent_entity).despawn_recursive();
+            continue;
+        }
+        let velocity = crescent.velocity(player_pos - crescent_pos);
+        crescent_transform.translation += (velocity * delta_secs).extend(0.0);
+        crescent_transform.rotate_z(HOOKED_CRESCENT_SPIN_SPEED * delta_secs);
+    }
+}
+
+// Each horror can be struck once on the way out and once more on the way back
+fn crescent_projectile_collision_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut crescent_query: Query<(&Transform, &mut CrescentProjectile)>,
+    mut horror_query: Query<(Entity, &Transform, &mut Health, &Horror), Without<CrescentProjectile>>,
+    mut sound_event_writer: EventWriter<PlaySoundEvent>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    style: Res<DamageTypeStyle>,
+) {
+    for (crescent_transform, mut crescent) in crescent_query.iter_mut() {
+        let crescent_pos = crescent_transform.translation.truncate();
+        for (horror_entity, horror_transform, mut horror_health, horror) in horror_query.iter_mut() {
+            if crescent.hit_this_phase.contains(&horror_entity) { continue; }
+            if crescent_pos.distance(horror_transform.translation.truncate()) >= HOOKED_CRESCENT_SIZE.x / 2.0 + horror.size.x / 2.0 { continue; }
+            horror_health.0 -= crescent.damage;
+            commands.entity(horror_entity).remove::<LastHitBySkill>();
+            spawn_damage_text(&mut commands, horror_transform.translation, crescent.damage, &time);
+            spawn_impact_effect(&mut commands, &style, horror_transform.translation, DamageType::Eldritch);
+            game_event_writer.send(AutoWeapon::HookedCrescent.hit(crescent.damage, horror_transform.translation));
+            sound_event_writer.send(PlaySoundEvent::global(SoundEffect::HorrorHit));
+            crescent.hit_this_phase.push(horror_entity);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::Vec2;
+use cosmic_gardener::aggro::{select_target, ThreatCandidate, SENTRY_THREAT_WEIGHT, SURVIVOR_THREAT_WEIGHT};
+
+fn threat(x: f32, weight: f32, taunt_radius: Option<f32>) -> ThreatCandidate {
+    ThreatCandidate { position: Vec2::new(x, 0.0), weight, taunt_radius }
+}
+
+#[test]
+fn test_threats_compete_on_distance_scaled_by_weight() {
+    let survivor = threat(100.0, SURVIVOR_THREAT_WEIGHT, None);
+    let sentry = threat(-60.0, SENTRY_THREAT_WEIGHT, None);
+    // The sentry is closer, but at half weight it has to be less than half as far away
+    assert_eq!(select_target(Vec2::ZERO, &[survivor, sentry]), Some(survivor.position));
+    assert_eq!(select_target(Vec2::new(-10.0, 0.0), &[survivor, sentry]), Some(sentry.position));
+    assert_eq!(select_target(Vec2::ZERO, &[]), None);
+}
+
+#[test]
+fn test_a_taunt_overrides_closer_threats_only_inside_its_radius() {
+    let survivor = threat(10.0, SURVIVOR_THREAT_WEIGHT, None);
+    let decoy = threat(-200.0, 1.0, Some(250.0));
+    assert_eq!(select_target(Vec2::ZERO, &[survivor, decoy]), Some(decoy.position));
+    assert_eq!(select_target(Vec2::new(60.0, 0.0), &[survivor, decoy]), Some(survivor.position));
+}
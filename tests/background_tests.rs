@@ -0,0 +1,40 @@
+use bevy::prelude::{IVec2, Vec2};
+use cosmic_gardener::background::{chunk_coord, chunk_load_radius, chunks_to_load, should_unload_chunk, wrap_into_field, BackgroundQuality, BACKGROUND_TILE_SIZE};
+
+#[test]
+fn test_wrap_into_field_keeps_positions_around_center() {
+    let half = Vec2::new(100.0, 50.0);
+    let center = Vec2::new(1000.0, -300.0);
+    assert_eq!(wrap_into_field(Vec2::new(1010.0, -290.0), center, half), Vec2::new(1010.0, -290.0));
+    assert_eq!(wrap_into_field(Vec2::new(1150.0, -360.0), center, half), Vec2::new(950.0, -260.0));
+    let far = wrap_into_field(Vec2::new(-5000.0, 4000.0), center, half);
+    assert!((far - center).abs().cmple(half).all());
+}
+
+#[test]
+fn test_background_quality_scales_layer_and_particle_counts() {
+    let qualities = [BackgroundQuality::Low, BackgroundQuality::Medium, BackgroundQuality::High];
+    for pair in qualities.windows(2) {
+        assert!(pair[0].parallax_layers() <= pair[1].parallax_layers());
+        assert!(pair[0].ambient_particles() < pair[1].ambient_particles());
+        assert_eq!(pair[0].next(), pair[1]);
+    }
+    assert_eq!(BackgroundQuality::High.next(), BackgroundQuality::Low);
+}
+
+#[test]
+fn test_chunks_load_around_the_camera_and_unload_with_slack() {
+    assert_eq!(chunk_coord(Vec2::new(BACKGROUND_TILE_SIZE * 0.4, -BACKGROUND_TILE_SIZE * 0.6)), IVec2::new(0, -1));
+    assert_eq!(chunk_coord(Vec2::splat(BACKGROUND_TILE_SIZE * 1000.2)), IVec2::splat(1000));
+
+    // A zoomed-out view that spans more than a chunk needs an extra ring
+    let radius = chunk_load_radius(Vec2::new(640.0, 360.0));
+    assert!(chunk_load_radius(Vec2::splat(BACKGROUND_TILE_SIZE * 1.5)) > radius);
+
+    let center = IVec2::new(7, -3);
+    let loaded: Vec<IVec2> = chunks_to_load(center, radius).collect();
+    assert_eq!(loaded.len(), ((radius * 2 + 1) * (radius * 2 + 1)) as usize);
+    assert!(loaded.iter().all(|coord| !should_unload_chunk(*coord, center, radius)));
+    assert!(!should_unload_chunk(center + IVec2::new(radius + 1, 0), center, radius));
+    assert!(should_unload_chunk(center + IVec2::new(0, radius + 2), center, radius));
+}
@@ -0,0 +1,35 @@
+use cosmic_gardener::balance::{BalanceConfig, BalanceConfigError, BalanceField};
+use cosmic_gardener::survivor::{DamageSource, HAZARD_GRACE_SECS, PROJECTILE_GRACE_SECS, SURVIVOR_HIT_INVINCIBILITY_SECS};
+
+#[test]
+fn test_shipped_balance_matches_the_previous_constants() {
+    let balance = BalanceConfig::default();
+    assert_eq!(balance.ichor_blast_spread_degrees, 10.0);
+    assert_eq!(balance.spawn_invincibility_secs, SURVIVOR_HIT_INVINCIBILITY_SECS);
+    assert_eq!(balance.grace_secs(DamageSource::Projectile), PROJECTILE_GRACE_SECS);
+    assert_eq!(balance.grace_secs(DamageSource::Hazard), HAZARD_GRACE_SECS);
+    assert_eq!(balance.scaled_soul_value(25), 25);
+}
+
+#[test]
+fn test_out_of_range_values_are_rejected_and_clamped() {
+    let source = "(ichor_blast_spread_degrees: 10.0, soul_value_multiplier: -1.0, spawn_invincibility_secs: 1.0, contact_grace_secs: 0.3, projectile_grace_secs: 0.5, spawn_view_padding: 80.0, spawn_clearance: 40.0)";
+    assert!(matches!(BalanceConfig::from_ron_str(source), Err(BalanceConfigError::OutOfRange(BalanceField::SoulValue))));
+    assert!(matches!(BalanceConfig::from_ron_str("(ichor_blast_spread_degrees: 10.0)"), Err(BalanceConfigError::Parse(_))));
+
+    let mut balance = BalanceConfig::default();
+    balance.set(BalanceField::SpawnViewPadding, 10_000.0);
+    assert_eq!(balance.spawn_view_padding, *BalanceField::SpawnViewPadding.range().end());
+    assert!(balance.validate().is_ok());
+}
+
+#[test]
+fn test_slider_fraction_round_trips_through_each_range() {
+    let balance = BalanceConfig::default();
+    for field in BalanceField::ALL {
+        let value = balance.get(field);
+        assert!((field.value_at(field.fraction(value)) - value).abs() < 1e-4, "{:?}", field);
+    }
+    assert_eq!(BalanceField::SoulValue.value_at(0.5), 2.5);
+    assert_eq!(BalanceField::SoulValue.fraction(9.0), 1.0);
+}
@@ -0,0 +1,56 @@
+use cosmic_gardener::{
+    bestiary::{stat_line, Bestiary, BestiaryEntry, RunKills},
+    horror::HorrorType,
+    horror_stats::HorrorStatsLibrary,
+};
+
+#[test]
+fn test_bestiary_lists_types_in_discovery_order_and_counts_kills() {
+    let mut bestiary = Bestiary::default();
+    assert!(bestiary.record_sighting(HorrorType::FloatingEyeball));
+    assert!(!bestiary.record_sighting(HorrorType::FloatingEyeball));
+    bestiary.record_kill(HorrorType::FloatingEyeball);
+    bestiary.record_kill(HorrorType::FloatingEyeball);
+    // Killed before it was ever seen still counts as a discovery
+    bestiary.record_kill(HorrorType::VoidBlinker);
+
+    assert_eq!(bestiary.entries, vec![
+        BestiaryEntry { horror_type: HorrorType::FloatingEyeball, kills: 2, elite_encountered: false },
+        BestiaryEntry { horror_type: HorrorType::VoidBlinker, kills: 1, elite_encountered: false },
+    ]);
+    assert!(!bestiary.is_discovered(HorrorType::FleshWeaver));
+}
+
+#[test]
+fn test_stat_line_mentions_ranged_attacks_only_for_ranged_horrors() {
+    let library = HorrorStatsLibrary::default();
+    assert!(stat_line(&library.entries[&HorrorType::FloatingEyeball]).contains("Ranged"));
+    assert!(!stat_line(&library.entries[&HorrorType::SkitteringShadowling]).contains("Ranged"));
+}
+
+#[test]
+fn test_meeting_an_elite_unlocks_it_for_boss_practice() {
+    let mut bestiary = Bestiary::default();
+    bestiary.record_sighting(HorrorType::SkitteringShadowling);
+    bestiary.record_elite(HorrorType::AmorphousFleshbeast);
+    bestiary.record_elite(HorrorType::AmorphousFleshbeast);
+    assert!(bestiary.is_discovered(HorrorType::AmorphousFleshbeast));
+    assert_eq!(bestiary.encountered_elites(), vec![HorrorType::AmorphousFleshbeast]);
+}
+
+#[test]
+fn test_run_kills_are_banked_into_the_bestiary_once() {
+    let mut bestiary = Bestiary::default();
+    bestiary.record_kill(HorrorType::FloatingEyeball);
+    let mut run_kills = RunKills::default();
+    run_kills.record(HorrorType::FloatingEyeball);
+    run_kills.record(HorrorType::FloatingEyeball);
+    run_kills.record(HorrorType::CrawlingTorment);
+    assert_eq!(run_kills.kills(HorrorType::FloatingEyeball), 2);
+
+    bestiary.bank(&mut run_kills);
+    bestiary.bank(&mut run_kills);
+    assert_eq!(bestiary.entry(HorrorType::FloatingEyeball).map(|entry| entry.kills), Some(3));
+    assert_eq!(bestiary.entry(HorrorType::CrawlingTorment).map(|entry| entry.kills), Some(1));
+    assert_eq!(run_kills, RunKills::default());
+}
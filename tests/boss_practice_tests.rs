@@ -0,0 +1,40 @@
+use cosmic_gardener::bestiary::Bestiary;
+use cosmic_gardener::boss_practice::{practice_menu_label, BossPractice, PracticeResult};
+use cosmic_gardener::horror::HorrorType;
+
+fn bestiary_with_elites(elites: &[HorrorType]) -> Bestiary {
+    let mut bestiary = Bestiary::default();
+    bestiary.record_sighting(HorrorType::SkitteringShadowling);
+    for elite in elites { bestiary.record_elite(*elite); }
+    bestiary
+}
+
+#[test]
+fn test_only_encountered_elites_can_be_practiced() {
+    let practice = BossPractice::default();
+    assert_eq!(practice.selected_boss(&bestiary_with_elites(&[])), None);
+    assert_eq!(practice.selected_boss(&bestiary_with_elites(&[HorrorType::VoidBlinker])), Some(HorrorType::VoidBlinker));
+    assert!(practice_menu_label(&practice, &bestiary_with_elites(&[])).contains("unlock"));
+}
+
+#[test]
+fn test_boss_selection_wraps_both_ways() {
+    let bestiary = bestiary_with_elites(&[HorrorType::VoidBlinker, HorrorType::FleshWeaver, HorrorType::FrenziedBehemoth]);
+    let mut practice = BossPractice::default();
+    practice.cycle(-1, 3);
+    assert_eq!(practice.selected_boss(&bestiary), Some(HorrorType::FrenziedBehemoth));
+    practice.cycle(1, 3);
+    practice.cycle(1, 3);
+    assert_eq!(practice.selected_boss(&bestiary), Some(HorrorType::FleshWeaver));
+    practice.cycle(1, 0);
+    assert_eq!(practice.selected, 0);
+}
+
+#[test]
+fn test_menu_label_reports_the_last_fight() {
+    let bestiary = bestiary_with_elites(&[HorrorType::FrenziedBehemoth]);
+    let practice = BossPractice { last_result: Some(PracticeResult { boss: HorrorType::FrenziedBehemoth, defeated: true, secs: 42.0 }), ..Default::default() };
+    let label = practice_menu_label(&practice, &bestiary);
+    assert!(label.contains("Frenzied Behemoth"));
+    assert!(label.contains("Last: Defeated Frenzied Behemoth in 42.0s"));
+}
@@ -0,0 +1,23 @@
+use cosmic_gardener::debug_menu::upgrades_to_replay;
+use cosmic_gardener::run_history::BuildCode;
+use cosmic_gardener::upgrades::{UpgradeCard, UpgradeId, UpgradeType};
+
+fn card(id: u32, upgrade_type: UpgradeType) -> UpgradeCard {
+    UpgradeCard { id: UpgradeId(id), name: format!("Card {}", id), description: String::new(), upgrade_type }
+}
+
+#[test]
+fn test_import_replays_upgrades_in_order_and_skips_rolled_rewards() {
+    let pool = vec![
+        card(1, UpgradeType::SurvivorSpeed(10)),
+        card(2, UpgradeType::GrantRandomRelic),
+        card(3, UpgradeType::MaxEndurance(20)),
+        card(4, UpgradeType::AddGlyphSlot),
+    ];
+    let build = BuildCode { upgrades: vec![3, 2, 1, 4, 3, 99], ..Default::default() };
+    let (replayed, missing) = upgrades_to_replay(&build, &pool);
+
+    let replayed_ids: Vec<u32> = replayed.iter().map(|card| card.id.0).collect();
+    assert_eq!(replayed_ids, vec![3, 1, 3]);
+    assert_eq!(missing, vec![99]);
+}
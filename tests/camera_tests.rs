@@ -0,0 +1,36 @@
+use bevy::prelude::Vec2;
+use cosmic_gardener::camera_systems::{approach_zoom, CameraSettings};
+
+#[test]
+fn test_target_zoom_scales_with_threat() {
+    let settings = CameraSettings::default();
+    assert_eq!(settings.target_zoom(0, 0), settings.min_zoom);
+    let crowded = settings.target_zoom(20, 0);
+    assert!(crowded > settings.min_zoom && crowded < settings.max_zoom);
+    assert_eq!(settings.target_zoom(500, 0), settings.max_zoom);
+    // An elite nearby zooms out further than the same crowd without one
+    assert!(settings.target_zoom(20, 1) > crowded);
+
+    let disabled = CameraSettings { adaptive_zoom: false, ..CameraSettings::default() };
+    assert_eq!(disabled.target_zoom(500, 3), disabled.min_zoom);
+}
+
+#[test]
+fn test_approach_zoom_eases_without_overshooting() {
+    let halfway = approach_zoom(1.0, 2.0, 1.0, std::f32::consts::LN_2);
+    assert!((halfway - 1.5).abs() < 1e-4);
+    let settled = (0..600).fold(1.0, |zoom, _| approach_zoom(zoom, 1.6, 1.5, 1.0 / 60.0));
+    assert!(settled <= 1.6 && settled > 1.59);
+}
+
+#[test]
+fn test_follow_target_respects_deadzone_and_look_ahead() {
+    let settings = CameraSettings { look_ahead_distance: 0.0, ..CameraSettings::default() };
+    // Inside the deadzone the camera stays put
+    assert_eq!(settings.follow_target(Vec2::ZERO, Vec2::new(50.0, -30.0), Vec2::ZERO), Vec2::ZERO);
+    // Past it, the camera moves just enough to put the survivor back on the edge
+    assert_eq!(settings.follow_target(Vec2::ZERO, Vec2::new(100.0, 10.0), Vec2::ZERO), Vec2::new(40.0, 0.0));
+
+    let looking = CameraSettings { deadzone_half_extents: Vec2::ZERO, ..CameraSettings::default() };
+    assert_eq!(looking.follow_target(Vec2::ZERO, Vec2::ZERO, Vec2::new(0.0, 2.0)), Vec2::new(0.0, looking.look_ahead_distance));
+}
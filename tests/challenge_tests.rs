@@ -0,0 +1,70 @@
+use cosmic_gardener::{
+    challenge::{days_from_civil, weekly_seed, ActiveChallenge, Challenge, ChallengeRules, IsoWeek, WeeklyBestScore},
+    horror::HorrorType,
+    skills::SkillId,
+    upgrades::{UpgradeCard, UpgradeId, UpgradeType},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn iso_week_of(year: i32, month: u32, day: u32) -> IsoWeek {
+    IsoWeek::from_days_since_epoch(days_from_civil(year, month, day))
+}
+
+fn grant_skill_card(skill: SkillId) -> UpgradeCard {
+    UpgradeCard { id: UpgradeId(900), name: "Grant".to_string(), description: String::new(), upgrade_type: UpgradeType::GrantSkill(skill) }
+}
+
+#[test]
+fn test_iso_weeks_follow_their_thursday_across_year_boundaries() {
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(iso_week_of(2021, 1, 3), IsoWeek { year: 2020, week: 53 });
+    assert_eq!(iso_week_of(2021, 1, 4), IsoWeek { year: 2021, week: 1 });
+    assert_eq!(iso_week_of(2024, 12, 30), IsoWeek { year: 2025, week: 1 });
+    assert_eq!(iso_week_of(2026, 10, 17), IsoWeek { year: 2026, week: 42 });
+    assert_eq!(iso_week_of(2026, 10, 12), iso_week_of(2026, 10, 18));
+}
+
+#[test]
+fn test_weekly_rules_are_fixed_per_week_and_keep_the_starting_skill_allowed() {
+    let week = IsoWeek { year: 2026, week: 42 };
+    let rules = ChallengeRules::from_seed(weekly_seed(week));
+    assert_eq!(rules, Challenge::weekly(week).rules);
+    assert_ne!(weekly_seed(week), weekly_seed(IsoWeek { year: 2026, week: 43 }));
+
+    assert_eq!(rules.banned_skills.len(), 2);
+    assert!(!rules.banned_skills.contains(&rules.starting_skill));
+    assert!(!rules.allows_upgrade(&grant_skill_card(rules.banned_skills[0])));
+    assert!(rules.allows_upgrade(&grant_skill_card(rules.starting_skill)));
+
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..20 { assert!(!rules.banned_horrors.contains(&rules.substitute_horror(rules.banned_horrors[0], &mut rng))); }
+    assert_eq!(rules.substitute_horror(HorrorType::SkitteringShadowling, &mut rng), HorrorType::SkitteringShadowling);
+}
+
+#[test]
+fn test_standard_runs_keep_every_card_and_horror() {
+    let standard = ActiveChallenge::default();
+    let mut rng = StdRng::seed_from_u64(7);
+    assert_eq!(standard.starting_skill(), SkillId(1));
+    assert!(standard.allows_upgrade(&grant_skill_card(SkillId(10))));
+    assert_eq!(standard.substitute_horror(HorrorType::VoidBlinker, &mut rng), HorrorType::VoidBlinker);
+}
+
+#[test]
+fn test_objective_completes_once_and_weekly_best_resets_with_the_week() {
+    let mut challenge = Challenge::weekly(IsoWeek { year: 2026, week: 42 });
+    let target = challenge.rules.objective.target();
+    assert!(!challenge.record_progress(target - 1));
+    assert!(challenge.record_progress(5));
+    assert!(!challenge.record_progress(5));
+    assert_eq!(challenge.progress, target);
+
+    let (this_week, next_week) = (IsoWeek { year: 2026, week: 42 }, IsoWeek { year: 2026, week: 43 });
+    let mut best = WeeklyBestScore::default();
+    assert!(best.record(this_week, 500));
+    assert!(!best.record(this_week, 400));
+    assert_eq!(best.for_week(this_week), Some(500));
+    assert_eq!(best.for_week(next_week), None);
+    assert!(best.record(next_week, 100));
+    assert_eq!(best.for_week(this_week), None);
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::Vec4;
+use cosmic_gardener::color_grade::{blend_tint, ColorGradeError, ColorGradeTable, GradeEvent};
+
+const SHIPPED_COLOR_GRADES: &str = include_str!("../assets/data/color_grades.ron");
+
+const SMALL_TABLE: &str = r#"(
+    blend_secs: 2.0,
+    time_of_day: [
+        (name: "Dusk", from_secs: 0.0, tint: (1.0, 0.0, 0.0, 0.1)),
+        (name: "Night", from_secs: 60.0, tint: (0.0, 0.0, 1.0, 0.2)),
+    ],
+    events: [(event: BloodMoon, tint: (0.5, 0.0, 0.0, 0.3))],
+)"#;
+
+#[test]
+fn test_shipped_color_grades_are_valid() {
+    let table = ColorGradeTable::from_ron_str(SHIPPED_COLOR_GRADES).expect("shipped color grades should be valid");
+    assert!(table.events.iter().any(|grade| grade.event == GradeEvent::BloodMoon));
+}
+
+#[test]
+fn test_target_tint_follows_run_time_and_events_override_it() {
+    let table = ColorGradeTable::from_ron_str(SMALL_TABLE).unwrap();
+    assert_eq!(table.time_of_day_at(59.0).name, "Dusk");
+    assert_eq!(table.target_tint(60.0, None), Vec4::new(0.0, 0.0, 1.0, 0.2));
+    assert_eq!(table.target_tint(10.0, Some(GradeEvent::BloodMoon)), Vec4::new(0.5, 0.0, 0.0, 0.3));
+}
+
+#[test]
+fn test_color_grade_table_rejects_bad_ordering() {
+    let late_start = SMALL_TABLE.replace("from_secs: 0.0", "from_secs: 5.0");
+    assert!(matches!(ColorGradeTable::from_ron_str(&late_start), Err(ColorGradeError::MissingStartOfRun)));
+    let unordered = SMALL_TABLE.replace("from_secs: 60.0", "from_secs: 0.0");
+    assert!(matches!(ColorGradeTable::from_ron_str(&unordered), Err(ColorGradeError::OutOfOrder(_))));
+}
+
+#[test]
+fn test_blend_tint_mostly_lands_within_blend_time_regardless_of_frame_rate() {
+    let (start, target) = (Vec4::ZERO, Vec4::ONE);
+    let one_step = blend_tint(start, target, 2.0, 2.0);
+    let mut stepped = start;
+    for _ in 0..120 { stepped = blend_tint(stepped, target, 2.0 / 120.0, 2.0); }
+    assert!((one_step - stepped).length() < 1e-4);
+    assert!(one_step.x > 0.94 && one_step.x < 1.0);
+}
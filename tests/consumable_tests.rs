@@ -0,0 +1,33 @@
+use cosmic_gardener::consumables::{ConsumableInventory, ConsumableKind, MAX_CONSUMABLE_SLOTS};
+
+#[test]
+fn test_consumable_stacks_respect_limits() {
+    let mut inventory = ConsumableInventory::default();
+    for _ in 0..ConsumableKind::HealingDraught.max_stack() {
+        assert!(inventory.try_add(ConsumableKind::HealingDraught));
+    }
+    assert!(!inventory.try_add(ConsumableKind::HealingDraught)); // Stack is full
+    assert_eq!(inventory.stacks.len(), 1);
+    assert_eq!(inventory.stacks[0].count, ConsumableKind::HealingDraught.max_stack());
+
+    assert!(inventory.try_add(ConsumableKind::CooldownFlask));
+    assert!(inventory.try_add(ConsumableKind::Bomb));
+    assert_eq!(inventory.stacks.len(), MAX_CONSUMABLE_SLOTS);
+}
+
+#[test]
+fn test_take_selected_empties_slot() {
+    let mut inventory = ConsumableInventory::default();
+    assert_eq!(inventory.take_selected(), None);
+
+    inventory.try_add(ConsumableKind::Bomb);
+    inventory.try_add(ConsumableKind::CooldownFlask);
+    inventory.cycle_selection();
+    assert_eq!(inventory.selected, 1);
+
+    assert_eq!(inventory.take_selected(), Some(ConsumableKind::CooldownFlask));
+    assert_eq!(inventory.stacks.len(), 1);
+    assert_eq!(inventory.selected, 0); // Selection falls back once its slot is gone
+    assert_eq!(inventory.take_selected(), Some(ConsumableKind::Bomb));
+    assert!(inventory.stacks.is_empty());
+}
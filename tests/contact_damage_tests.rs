@@ -0,0 +1,34 @@
+use std::time::Duration;
+use bevy::prelude::Vec2;
+use cosmic_gardener::horror::{contact_knockback, ContactDamage, CONTACT_HIT_COOLDOWN_SECS, CONTACT_KNOCKBACK_DISTANCE};
+
+#[test]
+fn test_contact_damage_hits_once_per_cooldown() {
+    let mut contact = ContactDamage::new(12);
+    assert_eq!(contact.try_hit(), Some(12));
+    assert_eq!(contact.try_hit(), None);
+
+    contact.tick(Duration::from_secs_f32(CONTACT_HIT_COOLDOWN_SECS * 0.5));
+    assert_eq!(contact.try_hit(), None);
+    contact.tick(Duration::from_secs_f32(CONTACT_HIT_COOLDOWN_SECS));
+    assert_eq!(contact.try_hit(), Some(12));
+}
+
+#[test]
+fn test_contact_cooldowns_are_per_horror() {
+    let mut tank = ContactDamage::new(20);
+    let mut swarmer = ContactDamage::new(5);
+    assert_eq!(tank.try_hit(), Some(20));
+    // The tank's cooldown doesn't stop a different horror from landing its hit
+    assert_eq!(swarmer.try_hit(), Some(5));
+}
+
+#[test]
+fn test_contact_knockback_pushes_away_from_horror() {
+    let push = contact_knockback(Vec2::new(10.0, 0.0), Vec2::ZERO);
+    assert_eq!(push, Vec2::new(CONTACT_KNOCKBACK_DISTANCE, 0.0));
+
+    // Standing exactly on top of a horror still gets a push
+    let overlapping = contact_knockback(Vec2::ONE, Vec2::ONE);
+    assert!((overlapping.length() - CONTACT_KNOCKBACK_DISTANCE).abs() < 1e-4);
+}
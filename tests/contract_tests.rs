@@ -0,0 +1,26 @@
+use cosmic_gardener::contracts::{Contract, ContractOutcome};
+use std::time::Duration;
+
+#[test]
+fn test_slay_contract_completes_before_deadline_and_fails_after() {
+    let mut contract = Contract::slay(10, 30.0);
+    contract.record_kills(6);
+    assert_eq!(contract.advance(Duration::from_secs(10)), None);
+    contract.record_kills(4);
+    assert_eq!(contract.advance(Duration::from_secs(1)), Some(ContractOutcome::Completed));
+
+    let mut contract = Contract::slay(10, 30.0);
+    contract.record_kills(9);
+    assert_eq!(contract.advance(Duration::from_secs(31)), Some(ContractOutcome::Failed));
+}
+
+#[test]
+fn test_untouched_contract_fails_on_hit() {
+    let mut contract = Contract::untouched(60.0);
+    assert_eq!(contract.advance(Duration::from_secs(30)), None);
+    contract.record_hit();
+    assert_eq!(contract.advance(Duration::from_secs(1)), Some(ContractOutcome::Failed));
+
+    let mut contract = Contract::untouched(60.0);
+    assert_eq!(contract.advance(Duration::from_secs(61)), Some(ContractOutcome::Completed));
+}
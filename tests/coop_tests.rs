@@ -0,0 +1,56 @@
+use bevy::prelude::{ButtonInput, KeyCode, MouseButton};
+use cosmic_gardener::coop::{shared_experience, waiting_label, LevelUpChoices, PlayerSlot, CHOICE_ACTIONS};
+use cosmic_gardener::game::AppState;
+use cosmic_gardener::input_actions::InputMap;
+use cosmic_gardener::survivor::Survivor;
+
+#[test]
+fn test_shared_experience_uses_the_best_multiplier_once() {
+    assert_eq!(shared_experience(100, [1.0]), 100);
+    assert_eq!(shared_experience(100, [1.0, 1.5]), 150);
+    assert_eq!(shared_experience(25, [1.1, 1.0]), 28);
+    // No survivors left standing still banks the soul as it is
+    assert_eq!(shared_experience(40, []), 40);
+}
+
+#[test]
+fn test_banked_experience_keeps_party_levels_in_step() {
+    let mut first = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    let mut second = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    second.xp_gain_multiplier = 2.0;
+    let amount = shared_experience(150, [first.xp_gain_multiplier, second.xp_gain_multiplier]);
+    assert_eq!(first.bank_experience(amount), second.bank_experience(amount));
+    assert_eq!(first.level, second.level);
+    assert_eq!(first.current_level_xp, second.current_level_xp);
+}
+
+#[test]
+fn test_level_up_stays_open_until_every_player_has_chosen() {
+    let mut choices = LevelUpChoices::open([PlayerSlot(1), PlayerSlot(0)]);
+    assert!(choices.is_choosing(PlayerSlot(0)) && choices.is_choosing(PlayerSlot(1)));
+    assert_eq!(choices.complete(PlayerSlot(1), AppState::InGame), None);
+    assert!(!choices.is_choosing(PlayerSlot(1)));
+    assert_eq!(waiting_label(&choices), "Waiting for Player 1");
+    // A second pick from a player who is already done changes nothing
+    assert_eq!(choices.complete(PlayerSlot(1), AppState::InGame), None);
+    assert_eq!(choices.complete(PlayerSlot(0), AppState::InGame), Some(AppState::InGame));
+}
+
+#[test]
+fn test_follow_up_screen_waits_for_the_whole_party() {
+    let mut choices = LevelUpChoices::open([PlayerSlot(0), PlayerSlot(1)]);
+    assert_eq!(choices.complete(PlayerSlot(0), AppState::GlyphSlotChoice), None);
+    assert_eq!(choices.complete(PlayerSlot(1), AppState::InGame), Some(AppState::GlyphSlotChoice));
+
+    let mut solo = LevelUpChoices::open([PlayerSlot(0)]);
+    assert_eq!(solo.complete(PlayerSlot(0), AppState::InGame), Some(AppState::InGame));
+}
+
+#[test]
+fn test_player_two_picks_on_the_numpad_without_touching_player_one() {
+    let mut keys = ButtonInput::<KeyCode>::default();
+    keys.press(KeyCode::Numpad2);
+    let held = InputMap::default().held_actions(&keys, &ButtonInput::<MouseButton>::default());
+    assert!(held.contains(&CHOICE_ACTIONS[1][1]));
+    assert!(CHOICE_ACTIONS[0].iter().all(|action| !held.contains(action)));
+}
@@ -0,0 +1,39 @@
+use cosmic_gardener::{
+    corruption::{Corruption, RunDifficulty, CORRUPTION_HEALING_FACTOR_PER_LEVEL, MAX_CORRUPTION_LEVEL},
+    horror::ELITE_SPAWN_CHANCE,
+};
+
+#[test]
+fn test_corruption_modifiers_stack_per_level() {
+    let base = RunDifficulty::for_corruption(0);
+    assert_eq!(base, RunDifficulty::default());
+    assert_eq!(base.elite_chance, ELITE_SPAWN_CHANCE);
+    assert_eq!(base.scale_healing(10.0), 10.0);
+
+    let corrupted = RunDifficulty::for_corruption(2);
+    assert!(corrupted.elite_chance > RunDifficulty::for_corruption(1).elite_chance);
+    assert!((corrupted.healing_multiplier - CORRUPTION_HEALING_FACTOR_PER_LEVEL * CORRUPTION_HEALING_FACTOR_PER_LEVEL).abs() < 1e-6);
+    assert!(RunDifficulty::for_corruption(MAX_CORRUPTION_LEVEL).elite_chance <= 0.5);
+}
+
+#[test]
+fn test_victories_unlock_one_level_at_a_time() {
+    let mut corruption = Corruption::default();
+    corruption.raise();
+    assert_eq!(corruption.level, 0);
+
+    assert!(corruption.record_victory());
+    assert_eq!(corruption, Corruption { level: 1, unlocked: 1 });
+
+    // Winning below the deepest unlocked level opens nothing new
+    corruption.lower();
+    assert!(!corruption.record_victory());
+    assert_eq!(corruption, Corruption { level: 0, unlocked: 1 });
+    corruption.raise();
+    corruption.raise();
+    assert_eq!(corruption.level, 1);
+
+    let mut deepest = Corruption { level: MAX_CORRUPTION_LEVEL, unlocked: MAX_CORRUPTION_LEVEL };
+    assert!(!deepest.record_victory());
+    assert_eq!(deepest.unlocked, MAX_CORRUPTION_LEVEL);
+}
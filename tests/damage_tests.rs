@@ -0,0 +1,36 @@
+use cosmic_gardener::components::{resolve_hit, HitResult, OverkillCarry};
+use cosmic_gardener::items::{execute_threshold, ItemDefinition, ItemEffect, ItemId, ItemLibrary};
+
+#[test]
+fn test_resolve_hit_reports_overkill_and_executes_low_targets() {
+    let mut health = 30;
+    assert_eq!(resolve_hit(&mut health, Some(100), 50, 0.0), HitResult { dealt: 50, overkill: 20, executed: false });
+    assert_eq!(health, -20);
+
+    // 15 of 100 left is above a 10% threshold, 8 left is not
+    let mut health = 40;
+    assert_eq!(resolve_hit(&mut health, Some(100), 25, 0.1), HitResult { dealt: 25, overkill: 0, executed: false });
+    assert_eq!(resolve_hit(&mut health, Some(100), 7, 0.1), HitResult { dealt: 15, overkill: 0, executed: true });
+    assert_eq!(health, 0);
+
+    // Without a known max health nothing can be executed
+    let mut health = 2;
+    assert!(!resolve_hit(&mut health, None, 1, 0.5).executed);
+}
+
+#[test]
+fn test_overkill_carry_is_spent_on_the_next_hit() {
+    let mut carry = OverkillCarry::new(0.5);
+    carry.store(21);
+    assert_eq!(carry.take(), 11);
+    assert_eq!(carry.take(), 0);
+}
+
+#[test]
+fn test_execute_threshold_takes_the_strongest_relic() {
+    let mut library = ItemLibrary::default();
+    library.items.push(ItemDefinition { id: ItemId(3001), name: "Small Axe".to_string(), description: String::new(), effects: vec![ItemEffect::ExecuteBelowHealth { health_fraction: 0.05 }] });
+    library.items.push(ItemDefinition { id: ItemId(3002), name: "Great Axe".to_string(), description: String::new(), effects: vec![ItemEffect::ExecuteBelowHealth { health_fraction: 0.15 }] });
+    assert_eq!(execute_threshold(&[], &library), 0.0);
+    assert_eq!(execute_threshold(&[ItemId(3001), ItemId(3002)], &library), 0.15);
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::Vec2;
+use cosmic_gardener::darkness::{visibility_at, vision_radius, BASE_VISION_RADIUS, LIGHT_FALLOFF};
+use cosmic_gardener::items::{ItemId, ItemDefinition, ItemEffect, ItemLibrary};
+
+#[test]
+fn test_visibility_fades_at_light_rim_and_takes_brightest_light() {
+    let lights = [(Vec2::ZERO, 300.0), (Vec2::new(1000.0, 0.0), 150.0)];
+    assert_eq!(visibility_at(Vec2::new(100.0, 0.0), &lights), 1.0);
+    let rim = visibility_at(Vec2::new(300.0 - LIGHT_FALLOFF / 2.0, 0.0), &lights);
+    assert!((rim - 0.5).abs() < 1e-4);
+    assert_eq!(visibility_at(Vec2::new(500.0, 0.0), &lights), 0.0);
+    // A second light carves its own pocket
+    assert_eq!(visibility_at(Vec2::new(1000.0, 20.0), &lights), 1.0);
+    assert_eq!(visibility_at(Vec2::ZERO, &[]), 0.0);
+}
+
+#[test]
+fn test_vision_items_extend_radius() {
+    let mut library = ItemLibrary::default();
+    library.items.push(ItemDefinition { id: ItemId(2001), name: "Test Lantern".to_string(), description: String::new(), effects: vec![ItemEffect::ExtendVision { radius_fraction: 0.5 }] });
+    assert_eq!(vision_radius(&[], &library), BASE_VISION_RADIUS);
+    assert_eq!(vision_radius(&[ItemId(2001)], &library), BASE_VISION_RADIUS * 1.5);
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+use bevy::prelude::{Timer, TimerMode};
+use cosmic_gardener::debug_menu::{adjust_remaining, InspectorField};
+
+#[test]
+fn test_adjusting_a_timer_keeps_its_progress() {
+    let mut timer = Timer::from_seconds(4.0, TimerMode::Once);
+    timer.tick(Duration::from_secs_f32(1.0));
+    adjust_remaining(&mut timer, InspectorField::Timer.step());
+    assert!((timer.remaining_secs() - 3.5).abs() < 1e-4);
+    assert!((timer.elapsed_secs() - 1.0).abs() < 1e-4);
+
+    // Cutting more than is left ends the timer on its next tick instead of rewinding it
+    adjust_remaining(&mut timer, -10.0);
+    assert_eq!(timer.remaining_secs(), 0.0);
+    timer.tick(Duration::ZERO);
+    assert!(timer.finished());
+}
+
+#[test]
+fn test_inspector_fields_format_for_their_units() {
+    assert_eq!(InspectorField::Health.format(120.0), "120");
+    assert_eq!(InspectorField::Timer.format(2.5), "2.5s");
+    assert!(InspectorField::ALL.iter().all(|field| field.step() > 0.0));
+}
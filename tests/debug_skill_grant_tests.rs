@@ -0,0 +1,39 @@
+use cosmic_gardener::debug_menu::{equip_glyph_directly, grant_skill_directly, skill_grant_status};
+use cosmic_gardener::glyphs::GlyphId;
+use cosmic_gardener::skills::{ActiveSkillInstance, SkillId, MAX_SKILL_SLOTS};
+use cosmic_gardener::survivor::Survivor;
+
+fn survivor_with_skills(count: u32, glyph_slots: u8) -> Survivor {
+    Survivor::new_with_skills_and_items((1..=count).map(|id| ActiveSkillInstance::new(SkillId(id), glyph_slots)).collect(), Vec::new())
+}
+
+#[test]
+fn test_granting_a_skill_equips_it_or_swaps_in_a_learned_one() {
+    let mut survivor = survivor_with_skills(1, 1);
+    assert!(grant_skill_directly(&mut survivor, SkillId(7), 2));
+    assert_eq!(skill_grant_status(&survivor, SkillId(7)), "slot 2");
+    assert!(!grant_skill_directly(&mut survivor, SkillId(7), 2));
+
+    let mut full = survivor_with_skills(MAX_SKILL_SLOTS as u32, 1);
+    assert!(grant_skill_directly(&mut full, SkillId(50), 1));
+    assert_eq!(skill_grant_status(&full, SkillId(50)), "learned");
+    // Equipping the learned skill bumps the last slot into the learned list
+    assert!(grant_skill_directly(&mut full, SkillId(50), 1));
+    assert_eq!(skill_grant_status(&full, SkillId(50)), format!("slot {}", MAX_SKILL_SLOTS));
+    assert_eq!(skill_grant_status(&full, SkillId(MAX_SKILL_SLOTS as u32)), "learned");
+    assert_eq!(skill_grant_status(&full, SkillId(99)), "");
+}
+
+#[test]
+fn test_equipping_a_glyph_fills_then_swaps_the_last_socket() {
+    let mut survivor = survivor_with_skills(1, 2);
+    survivor.collected_glyphs.push(GlyphId(3));
+    assert!(equip_glyph_directly(&mut survivor, 0, GlyphId(3)));
+    assert!(survivor.collected_glyphs.is_empty());
+    assert!(!equip_glyph_directly(&mut survivor, 0, GlyphId(3)));
+    assert!(equip_glyph_directly(&mut survivor, 0, GlyphId(4)));
+    assert!(equip_glyph_directly(&mut survivor, 0, GlyphId(5)));
+    assert_eq!(survivor.equipped_skills[0].equipped_glyphs, vec![Some(GlyphId(3)), Some(GlyphId(5))]);
+    assert_eq!(survivor.collected_glyphs, vec![GlyphId(4)]);
+    assert!(!equip_glyph_directly(&mut survivor, 1, GlyphId(6)));
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+use bevy::prelude::{Timer, TimerMode};
+use cosmic_gardener::debug_menu::{finish_on_next_tick, next_scheduled_event, ScheduledEvent, DEBUG_FAST_FORWARD_SECS};
+use cosmic_gardener::game::{cycle_spawn_scaling, GameState};
+
+#[test]
+fn test_fast_forward_passes_cycles_even_while_paused() {
+    let mut game_state = GameState { cycle_number: 1, game_timer: Timer::from_seconds(3600.0, TimerMode::Once), difficulty_timer: Timer::from_seconds(30.0, TimerMode::Repeating), ..Default::default() };
+    game_state.difficulty_timer.tick(Duration::from_secs(20));
+    game_state.game_timer.pause();
+    game_state.difficulty_timer.pause();
+
+    // 20s into the cycle plus five minutes is ten whole cycles, ending 20s into the next
+    assert_eq!(game_state.fast_forward(DEBUG_FAST_FORWARD_SECS), 10);
+    assert_eq!(game_state.cycle_number, 11);
+    assert!((game_state.difficulty_timer.elapsed_secs() - 20.0).abs() < 1e-3);
+    assert!((game_state.game_timer.elapsed_secs() - DEBUG_FAST_FORWARD_SECS).abs() < 1e-3);
+}
+
+#[test]
+fn test_cycle_scaling_matches_playing_through() {
+    assert_eq!(cycle_spawn_scaling(1), (20, 2.0));
+    let (cap, interval) = cycle_spawn_scaling(3);
+    assert_eq!(cap, 40);
+    assert!((interval - 2.0 * 0.9 * 0.9).abs() < 1e-5);
+    assert_eq!(cycle_spawn_scaling(100), (200, 0.3));
+}
+
+#[test]
+fn test_forcing_the_soonest_event_keeps_its_interval() {
+    let upcoming = [(ScheduledEvent::ExtractionRift, 420.0), (ScheduledEvent::RespecShrine, 37.0), (ScheduledEvent::ContractOffer, 90.0)];
+    assert_eq!(next_scheduled_event(&upcoming), Some((ScheduledEvent::RespecShrine, 37.0)));
+    assert_eq!(next_scheduled_event(&[]), None);
+
+    let mut timer = Timer::from_seconds(150.0, TimerMode::Repeating);
+    finish_on_next_tick(&mut timer);
+    assert!(timer.tick(Duration::from_millis(16)).just_finished());
+    assert_eq!(timer.duration(), Duration::from_secs(150));
+}
@@ -0,0 +1,74 @@
+use cosmic_gardener::{
+    dps_meter::{DpsMeter, DPS_WINDOW_SECS, origin_label},
+    game_events::DamageOrigin,
+    consumables::ConsumableKind,
+    skills::{SkillId, SkillLibrary},
+    summons::SummonKind,
+    weapons::AutoWeapon,
+};
+
+#[test]
+fn test_dps_is_split_by_source_and_sorted_highest_first() {
+    let mut meter = DpsMeter::default();
+    meter.start(0.0);
+    meter.record(20.0, DamageOrigin::IchorBlast, 50);
+    meter.record(21.0, DamageOrigin::Skill(SkillId(3)), 200);
+    meter.record(22.0, DamageOrigin::Weapon(AutoWeapon::CircleOfWarding), 100);
+    meter.record(23.0, DamageOrigin::Skill(SkillId(3)), 100);
+
+    let sources = meter.per_source(25.0);
+    assert_eq!(sources.iter().map(|(origin, _)| *origin).collect::<Vec<_>>(), vec![
+        DamageOrigin::Skill(SkillId(3)), DamageOrigin::Weapon(AutoWeapon::CircleOfWarding), DamageOrigin::IchorBlast,
+    ]);
+    assert_eq!(sources[0].1, 300.0 / DPS_WINDOW_SECS);
+    assert_eq!(meter.total(25.0), 450.0 / DPS_WINDOW_SECS);
+}
+
+#[test]
+fn test_old_hits_roll_out_of_the_window() {
+    let mut meter = DpsMeter::default();
+    meter.start(0.0);
+    meter.record(20.0, DamageOrigin::IchorBlast, 80);
+    meter.record(29.0, DamageOrigin::IchorBlast, 20);
+    assert_eq!(meter.total(29.5), 100.0 / DPS_WINDOW_SECS);
+    assert_eq!(meter.total(30.5), 20.0 / DPS_WINDOW_SECS);
+    assert!(meter.per_source(45.0).is_empty());
+}
+
+#[test]
+fn test_a_fresh_run_is_not_averaged_over_time_before_it_started() {
+    let mut meter = DpsMeter::default();
+    meter.record(5.0, DamageOrigin::IchorBlast, 500);
+    meter.start(100.0);
+    meter.record(101.0, DamageOrigin::IchorBlast, 40);
+    assert_eq!(meter.total(102.0), 20.0);
+    // Within the first second the window never shrinks below one second
+    meter.start(200.0);
+    meter.record(200.25, DamageOrigin::IchorBlast, 10);
+    assert_eq!(meter.total(200.5), 10.0);
+}
+
+#[test]
+fn test_summons_get_their_own_row_apart_from_the_skill_that_made_them() {
+    let mut meter = DpsMeter::default();
+    meter.start(0.0);
+    meter.record(20.0, DamageOrigin::Skill(SkillId(3)), 40);
+    meter.record(21.0, DamageOrigin::Summon(SummonKind::PsychicSentry), 60);
+    meter.record(22.0, DamageOrigin::Summon(SummonKind::PsychicSentry), 60);
+    let sources = meter.per_source(25.0);
+    assert_eq!(sources.iter().map(|(origin, _)| *origin).collect::<Vec<_>>(), vec![DamageOrigin::Summon(SummonKind::PsychicSentry), DamageOrigin::Skill(SkillId(3))]);
+    assert_eq!(origin_label(DamageOrigin::Summon(SummonKind::PsychicSentry), &SkillLibrary::default()), "Psychic Sentry (summon)");
+}
+
+#[test]
+fn test_relics_and_bombs_are_labelled_apart_from_the_survivor_kit() {
+    let mut meter = DpsMeter::default();
+    meter.start(0.0);
+    meter.record(20.0, DamageOrigin::Relic, 30);
+    meter.record(21.0, DamageOrigin::Consumable(ConsumableKind::Bomb), 80);
+    meter.record(22.0, DamageOrigin::Relic, 30);
+    let sources = meter.per_source(25.0);
+    assert_eq!(sources.iter().map(|(origin, _)| *origin).collect::<Vec<_>>(), vec![DamageOrigin::Consumable(ConsumableKind::Bomb), DamageOrigin::Relic]);
+    assert_eq!(origin_label(DamageOrigin::Relic, &SkillLibrary::default()), "Relics");
+    assert_eq!(origin_label(DamageOrigin::Consumable(ConsumableKind::Bomb), &SkillLibrary::default()), "Pitch Bomb");
+}
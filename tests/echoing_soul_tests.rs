@@ -0,0 +1,24 @@
+use bevy::prelude::Vec2;
+use cosmic_gardener::echoing_soul::{soul_pull_speed, soul_pull_velocity, MovingToSurvivor};
+
+#[test]
+fn test_soul_pull_accelerates_then_levels_off() {
+    let start = soul_pull_speed(0.0);
+    let mid = soul_pull_speed(0.3);
+    let late = soul_pull_speed(5.0);
+    assert!(start < mid && mid < late);
+    assert_eq!(late, soul_pull_speed(10.0));
+}
+
+#[test]
+fn test_spiralling_souls_curve_but_still_close_in() {
+    let to_survivor = Vec2::new(400.0, 0.0);
+    let straight = soul_pull_velocity(to_survivor, &MovingToSurvivor { elapsed_secs: 0.2, spiral: false });
+    assert_eq!(straight.y, 0.0);
+    assert!(straight.x > 0.0);
+
+    let spiral = soul_pull_velocity(to_survivor, &MovingToSurvivor { elapsed_secs: 0.2, spiral: true });
+    assert!(spiral.y.abs() > 0.0);
+    assert!(spiral.dot(to_survivor) > 0.0);
+    assert!((spiral.length() - straight.length()).abs() < 1e-3);
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use cosmic_gardener::aggro::{select_target, ThreatCandidate, SURVIVOR_THREAT_WEIGHT};
+use cosmic_gardener::escort::{advance_caravan, caravan_health_fraction, escort_outcome, Caravan, EscortOutcome, CARAVAN_MAX_HEALTH, CARAVAN_THREAT_WEIGHT};
+use cosmic_gardener::game::GameConfig;
+
+#[test]
+fn test_escort_ends_when_the_journey_does_or_the_caravan_falls() {
+    assert_eq!(escort_outcome(CARAVAN_MAX_HEALTH, false), None);
+    assert_eq!(escort_outcome(1, true), Some(EscortOutcome::Delivered));
+    assert_eq!(escort_outcome(0, false), Some(EscortOutcome::Lost));
+    assert_eq!(escort_outcome(-20, true), Some(EscortOutcome::Lost));
+}
+
+#[test]
+fn test_caravan_health_fraction_is_clamped() {
+    assert_eq!(caravan_health_fraction(CARAVAN_MAX_HEALTH), 1.0);
+    assert_eq!(caravan_health_fraction(CARAVAN_MAX_HEALTH / 2), 0.5);
+    assert_eq!(caravan_health_fraction(-5), 0.0);
+}
+
+#[test]
+fn test_caravan_turns_back_at_the_arena_wall() {
+    let config = GameConfig { arena_half_extents: Vec2::splat(500.0), ..default() };
+    let (position, heading) = advance_caravan(Vec2::ZERO, Vec2::X, 10.0, &config);
+    assert_eq!((position, heading), (Vec2::new(10.0, 0.0), Vec2::X));
+
+    let (position, heading) = advance_caravan(Vec2::new(450.0, 0.0), Vec2::X, 20.0, &config);
+    assert!(position.x < 470.0);
+    assert_eq!(heading, Vec2::NEG_X);
+    assert_eq!(Caravan::new(Vec2::new(0.0, 3.0)).heading, Vec2::Y);
+}
+
+#[test]
+fn test_horrors_nearer_the_caravan_go_after_it() {
+    let survivor = ThreatCandidate { position: Vec2::ZERO, weight: SURVIVOR_THREAT_WEIGHT, taunt_radius: None };
+    let caravan = ThreatCandidate { position: Vec2::new(400.0, 0.0), weight: CARAVAN_THREAT_WEIGHT, taunt_radius: None };
+    assert_eq!(select_target(Vec2::new(380.0, 0.0), &[survivor, caravan]), Some(caravan.position));
+    assert_eq!(select_target(Vec2::new(100.0, 0.0), &[survivor, caravan]), Some(survivor.position));
+}
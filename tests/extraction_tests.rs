@@ -0,0 +1,23 @@
+use cosmic_gardener::extraction::{ExtractionRift, EXTRACTION_CHANNEL_SECS};
+
+#[test]
+fn test_channel_completes_only_after_standing_in_the_rift_long_enough() {
+    let mut rift = ExtractionRift::default();
+    assert!(!rift.advance_channel(EXTRACTION_CHANNEL_SECS / 2.0, true, false));
+    assert!(rift.is_channelling());
+    assert_eq!(rift.channel_fraction(), 0.5);
+    assert!(rift.advance_channel(EXTRACTION_CHANNEL_SECS / 2.0, true, false));
+}
+
+#[test]
+fn test_leaving_or_taking_a_hit_restarts_the_channel() {
+    let mut rift = ExtractionRift::default();
+    rift.advance_channel(EXTRACTION_CHANNEL_SECS - 1.0, true, false);
+    assert!(!rift.advance_channel(2.0, true, true));
+    assert!(!rift.is_channelling());
+
+    rift.advance_channel(EXTRACTION_CHANNEL_SECS - 1.0, true, false);
+    assert!(!rift.advance_channel(2.0, false, false));
+    assert!(!rift.advance_channel(2.0, true, false));
+    assert_eq!(rift.channel_fraction(), 2.0 / EXTRACTION_CHANNEL_SECS);
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::{IVec2, Vec2};
+use cosmic_gardener::{
+    background::{chunk_center, BACKGROUND_TILE_SIZE},
+    floating_origin::{recenter_chunks, FloatingOrigin, ORIGIN_RECENTER_DISTANCE},
+    game::GameConfig,
+};
+
+#[test]
+fn test_recenter_only_once_far_from_origin_and_lands_near_it() {
+    assert_eq!(recenter_chunks(Vec2::new(ORIGIN_RECENTER_DISTANCE - 1.0, -100.0)), None);
+
+    let focus = Vec2::new(ORIGIN_RECENTER_DISTANCE + 700.0, -3000.0);
+    let chunks = recenter_chunks(focus).unwrap();
+    let recentered = focus - chunk_center(chunks);
+    assert!(recentered.abs().max_element() <= BACKGROUND_TILE_SIZE / 2.0);
+
+    let origin = FloatingOrigin { chunk_offset: chunks };
+    assert_eq!(origin.to_world(recentered), focus);
+}
+
+#[test]
+fn test_arena_clamp_follows_the_recentered_arena() {
+    let mut config = GameConfig::default();
+    let edge = config.arena_half_extents.x;
+    assert_eq!(config.clamp_to_arena(Vec2::new(edge + 50.0, 0.0), 10.0).x, edge - 10.0);
+
+    let shift = chunk_center(IVec2::new(8, 0));
+    config.arena_center -= shift;
+    assert_eq!(config.clamp_to_arena(Vec2::new(edge, 0.0) - shift + Vec2::new(50.0, 0.0), 10.0).x, edge - 10.0 - shift.x);
+}
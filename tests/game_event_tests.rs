@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use cosmic_gardener::{
+    audio::{is_audible, sound_event_for_game_event, sound_for_game_event, PlaySoundEvent, SoundEffect, MAX_AUDIBLE_DISTANCE},
+    components::DamageType,
+    game_events::{DamageOrigin, GameEvent, HorrorKill},
+    horror::HorrorType,
+    items::ItemId,
+    skills::SkillId,
+    survivor::DamageSource,
+    weapons::AutoWeapon,
+};
+
+fn kill(is_elite: bool, skill: Option<SkillId>) -> GameEvent {
+    GameEvent::HorrorKilled(HorrorKill { horror_type: HorrorType::SkitteringShadowling, position: Vec3::ZERO, is_elite, xp_value: 10, skill })
+}
+
+#[test]
+fn test_as_horror_kill_picks_only_kills_out_of_the_stream() {
+    let events = [kill(false, None),
+        GameEvent::SkillCast { skill_id: SkillId(1), position: Vec3::ZERO },
+        kill(true, Some(SkillId(3))),
+        GameEvent::LevelUp { new_level: 2, levels_gained: 1 }];
+    let kills: Vec<&HorrorKill> = events.iter().filter_map(GameEvent::as_horror_kill).collect();
+    assert_eq!(kills.len(), 2);
+    assert!(kills[1].is_elite);
+    assert_eq!(kills.iter().filter_map(|kill| kill.skill).collect::<Vec<_>>(), vec![SkillId(3)]);
+}
+
+#[test]
+fn test_game_events_map_to_their_sounds() {
+    assert_eq!(sound_for_game_event(&kill(false, None)), Some(SoundEffect::HorrorDeath));
+    assert_eq!(sound_for_game_event(&GameEvent::SkillCast { skill_id: SkillId(1), position: Vec3::ZERO }), Some(SoundEffect::SkillCast(SkillId(1))));
+    assert_eq!(sound_for_game_event(&GameEvent::DamageDealt { amount: 5, damage_type: DamageType::Frost, position: Vec3::ZERO, origin: DamageOrigin::IchorBlast }), Some(SoundEffect::HorrorHit));
+    assert_eq!(sound_for_game_event(&GameEvent::DamageDealt { amount: 5, damage_type: DamageType::Frost, position: Vec3::ZERO, origin: DamageOrigin::Skill(SkillId(3)) }), Some(SoundEffect::SkillImpact(SkillId(3))));
+    assert_eq!(sound_for_game_event(&GameEvent::DamageDealt { amount: 5, damage_type: DamageType::Frost, position: Vec3::ZERO, origin: DamageOrigin::Weapon(AutoWeapon::SearingGaze) }), None);
+    assert_eq!(sound_for_game_event(&GameEvent::EliteSpawned { horror_type: HorrorType::VoidBlinker, position: Vec3::ZERO }), Some(SoundEffect::BossStinger(HorrorType::VoidBlinker)));
+    assert_eq!(sound_for_game_event(&GameEvent::SurvivorHit { damage: 3, source: DamageSource::Contact }), Some(SoundEffect::SurvivorHit));
+    assert_eq!(sound_for_game_event(&GameEvent::LevelUp { new_level: 3, levels_gained: 1 }), Some(SoundEffect::Revelation));
+    // The reveal screen already plays its own landing sound
+    assert_eq!(sound_for_game_event(&GameEvent::ReliquaryOpened { item_id: ItemId(1) }), None);
+}
+
+#[test]
+fn test_world_events_play_where_they_happened() {
+    let cast = GameEvent::SkillCast { skill_id: SkillId(1), position: Vec3::new(300.0, -20.0, 1.0) };
+    assert_eq!(sound_event_for_game_event(&cast), Some(PlaySoundEvent::at(SoundEffect::SkillCast(SkillId(1)), Vec2::new(300.0, -20.0))));
+    assert_eq!(sound_event_for_game_event(&GameEvent::LevelUp { new_level: 2, levels_gained: 1 }), Some(PlaySoundEvent::global(SoundEffect::Revelation)));
+    assert_eq!(sound_event_for_game_event(&GameEvent::ReliquaryOpened { item_id: ItemId(1) }), None);
+}
+
+#[test]
+fn test_far_away_sounds_are_culled() {
+    let listener = Vec2::new(100.0, 100.0);
+    assert!(is_audible(listener + Vec2::X * (MAX_AUDIBLE_DISTANCE - 1.0), listener));
+    assert!(!is_audible(listener + Vec2::Y * (MAX_AUDIBLE_DISTANCE + 1.0), listener));
+}
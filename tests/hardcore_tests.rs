@@ -0,0 +1,15 @@
+use cosmic_gardener::{
+    extraction::RunOutcome,
+    hardcore::{settle_hardcore_run, HardcoreSettlement},
+};
+
+#[test]
+fn test_hardcore_death_wipes_half_the_pool_including_this_run() {
+    assert_eq!(settle_hardcore_run(100, 40, RunOutcome::Died), HardcoreSettlement { outcome: RunOutcome::Died, banked: 0, unbanked: 70, lost: 70 });
+    assert_eq!(settle_hardcore_run(0, 0, RunOutcome::Died), HardcoreSettlement { outcome: RunOutcome::Died, banked: 0, unbanked: 0, lost: 0 });
+}
+
+#[test]
+fn test_hardcore_extraction_banks_the_whole_pool_with_a_multiplier() {
+    assert_eq!(settle_hardcore_run(100, 40, RunOutcome::Extracted), HardcoreSettlement { outcome: RunOutcome::Extracted, banked: 210, unbanked: 0, lost: 0 });
+}
@@ -0,0 +1,34 @@
+use cosmic_gardener::horror::HorrorType;
+use cosmic_gardener::horror_stats::{HorrorStatsLibrary, HorrorStatsTable, HorrorStatsError};
+
+const DATA_FILE: &str = include_str!("../assets/data/enemies.ron");
+
+#[test]
+fn test_shipped_horror_stats_cover_every_type() {
+    let library = HorrorStatsLibrary::default();
+    for horror_type in HorrorType::ALL {
+        assert!(library.entries.contains_key(&horror_type), "{:?} missing", horror_type);
+    }
+    let eyeball = library.stats_for(HorrorType::FloatingEyeball, 1.0);
+    assert_eq!(eyeball.health, 15);
+    assert_eq!(eyeball.projectile_range, Some(350.0));
+    // Speed only starts scaling after the first cycle
+    assert_eq!(library.stats_for(HorrorType::FloatingEyeball, 2.0).speed, 85.0);
+}
+
+#[test]
+fn test_horror_stats_validation_rejects_bad_tables() {
+    let mut table = HorrorStatsTable::from_ron_str(DATA_FILE).unwrap();
+    let duplicate = table.horrors[0].clone();
+    table.horrors.push(duplicate);
+    assert!(matches!(table.validate(), Err(HorrorStatsError::DuplicateEntry(HorrorType::SkitteringShadowling))));
+
+    table.horrors.truncate(1);
+    assert!(matches!(table.validate(), Err(HorrorStatsError::MissingEntry(_))));
+
+    let mut table = HorrorStatsTable::from_ron_str(DATA_FILE).unwrap();
+    table.horrors[1].item_drop_chance = 1.5;
+    assert!(matches!(table.validate(), Err(HorrorStatsError::InvalidValue { field: "item_drop_chance", .. })));
+
+    assert!(matches!(HorrorStatsTable::from_ron_str("(horrors: [(horror_type: Gazer)])"), Err(HorrorStatsError::Parse(_))));
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::{ButtonInput, KeyCode, MouseButton, Vec2};
+use cosmic_gardener::input_actions::{ActionState, InputAction, InputBinding, InputMap};
+
+fn held(input_map: &InputMap, keys: &[KeyCode], mouse: &[MouseButton]) -> Vec<InputAction> {
+    let mut key_input = ButtonInput::<KeyCode>::default();
+    for key in keys { key_input.press(*key); }
+    let mut mouse_input = ButtonInput::<MouseButton>::default();
+    for button in mouse { mouse_input.press(*button); }
+    input_map.held_actions(&key_input, &mouse_input)
+}
+
+#[test]
+fn test_default_bindings_drive_movement_and_skills() {
+    let input_map = InputMap::default();
+    let mut actions = ActionState::default();
+    actions.update(held(&input_map, &[KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyD], &[MouseButton::Right]));
+    // Left and right cancel out
+    assert_eq!(actions.move_axis(), Vec2::new(0.0, 1.0));
+    assert_eq!(actions.skill_just_pressed(), Some(0));
+
+    // Still holding the mouse; the 1 key is another binding for the same, already held, action
+    actions.update(held(&input_map, &[KeyCode::Digit1, KeyCode::KeyE], &[MouseButton::Right]));
+    assert!(actions.pressed(InputAction::Skill1));
+    assert_eq!(actions.skill_just_pressed(), Some(3));
+    assert_eq!(input_map.bindings_label(InputAction::Skill1), "RMB / 1");
+}
+
+#[test]
+fn test_rebinding_moves_an_action_to_a_new_key() {
+    let mut input_map = InputMap::default();
+    input_map.unbind(InputAction::Pause, InputBinding::Key(KeyCode::Escape));
+    input_map.bind(InputAction::Pause, InputBinding::Key(KeyCode::KeyP));
+    input_map.bind(InputAction::Pause, InputBinding::Key(KeyCode::KeyP));
+    assert_eq!(input_map.bindings_for(InputAction::Pause).collect::<Vec<_>>(), vec![InputBinding::Key(KeyCode::KeyP)]);
+    assert!(held(&input_map, &[KeyCode::Escape], &[]).is_empty());
+    assert_eq!(held(&input_map, &[KeyCode::KeyP], &[]), vec![InputAction::Pause]);
+}
+
+#[test]
+fn test_menu_choices_and_confirm_come_from_the_map() {
+    let mut input_map = InputMap::default();
+    let mut actions = ActionState::default();
+    actions.update(held(&input_map, &[KeyCode::Digit2], &[]));
+    assert_eq!(actions.choice_just_pressed(), Some(1));
+    assert!(actions.just_pressed(InputAction::Skill2));
+
+    input_map.bind(InputAction::Confirm, InputBinding::Key(KeyCode::KeyR));
+    actions.update(held(&input_map, &[KeyCode::KeyR], &[]));
+    assert!(actions.just_pressed(InputAction::Confirm));
+    assert_eq!(actions.choice_just_pressed(), None);
+}
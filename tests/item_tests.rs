@@ -1,8 +1,5 @@
 use cosmic_gardener::items::{ItemId, ItemDefinition, ItemEffect, ItemLibrary};
 use cosmic_gardener::survivor::Survivor; // Assuming survivor.rs is at crate root
-use cosmic_gardener::skills::ActiveSkillInstance; // For Survivor::new_with_skills_and_items
-use cosmic_gardener::components::Health as ComponentHealth; // For checking health component
-use bevy::prelude::default; // For default components if needed for Survivor
 
 // Helper function to create a basic ItemLibrary for testing
 fn setup_test_item_library() -> ItemLibrary {
@@ -70,11 +67,9 @@ fn test_passive_stat_boost_application() {
     if let Some(item_def) = item_library.get_item_definition(ItemId(1001)) {
         survivor.collected_item_ids.push(ItemId(1001)); // Simulate collection
         for effect in &item_def.effects {
-            if let ItemEffect::PassiveStatBoost { max_health_increase, .. } = effect {
-                if let Some(hp_boost) = max_health_increase {
-                    survivor.max_health += *hp_boost;
-                    // In a real scenario, a Health component would also be updated.
-                }
+            if let ItemEffect::PassiveStatBoost { max_health_increase: Some(hp_boost), .. } = effect {
+                survivor.max_health += *hp_boost;
+                // In a real scenario, a Health component would also be updated.
             }
         }
     }
@@ -84,10 +79,8 @@ fn test_passive_stat_boost_application() {
     if let Some(item_def) = item_library.get_item_definition(ItemId(1002)) {
         survivor.collected_item_ids.push(ItemId(1002));
         for effect in &item_def.effects {
-            if let ItemEffect::PassiveStatBoost { speed_multiplier, .. } = effect {
-                if let Some(speed_mult) = speed_multiplier {
-                    survivor.speed *= *speed_mult;
-                }
+            if let ItemEffect::PassiveStatBoost { speed_multiplier: Some(speed_mult), .. } = effect {
+                survivor.speed *= *speed_mult;
             }
         }
     }
@@ -97,10 +90,8 @@ fn test_passive_stat_boost_application() {
     if let Some(item_def) = item_library.get_item_definition(ItemId(1003)) {
         survivor.collected_item_ids.push(ItemId(1003));
         for effect in &item_def.effects {
-            if let ItemEffect::PassiveStatBoost { damage_increase, .. } = effect {
-                if let Some(dmg_inc) = damage_increase {
-                    survivor.ichor_blast_damage_bonus += *dmg_inc;
-                }
+            if let ItemEffect::PassiveStatBoost { damage_increase: Some(dmg_inc), .. } = effect {
+                survivor.ichor_blast_damage_bonus += *dmg_inc;
             }
         }
     }
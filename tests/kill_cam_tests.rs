@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use cosmic_gardener::kill_cam::{kill_cam_time_scale, tally_score, KillCam, KillCamShot, KILL_CAM_SECONDS, KILL_CAM_TIME_SCALE};
+
+#[test]
+fn test_kill_cam_slows_time_then_eases_back() {
+    assert_eq!(kill_cam_time_scale(0.0), KILL_CAM_TIME_SCALE);
+    assert_eq!(kill_cam_time_scale(0.5), KILL_CAM_TIME_SCALE);
+    assert!(kill_cam_time_scale(0.9) > KILL_CAM_TIME_SCALE && kill_cam_time_scale(0.9) < 1.0);
+    assert!((kill_cam_time_scale(1.0) - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_idle_kill_cam_leaves_time_alone() {
+    let mut kill_cam = KillCam::default();
+    assert!(!kill_cam.is_active());
+    assert_eq!(kill_cam.time_scale(), 1.0);
+
+    kill_cam.shot = Some(KillCamShot { focus: Vec2::ZERO, timer: Timer::from_seconds(KILL_CAM_SECONDS, TimerMode::Once), score_before: 0 });
+    assert_eq!(kill_cam.time_scale(), KILL_CAM_TIME_SCALE);
+}
+
+#[test]
+fn test_tally_counts_up_to_the_live_score() {
+    assert_eq!(tally_score(1000, 1300, 0.0), 1000);
+    assert_eq!(tally_score(1000, 1300, 0.25), 1150);
+    assert_eq!(tally_score(1000, 1300, 0.5), 1300);
+    assert_eq!(tally_score(1000, 1300, 1.0), 1300);
+    // A score that somehow dropped never counts below where the tally started
+    assert_eq!(tally_score(1000, 900, 1.0), 1000);
+}
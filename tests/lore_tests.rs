@@ -0,0 +1,30 @@
+use cosmic_gardener::lore::{LoreCollection, LoreLibrary, LoreLibraryError};
+
+const DATA_FILE: &str = include_str!("../assets/data/lore.ron");
+
+#[test]
+fn test_lore_data_file_is_valid() {
+    let library = LoreLibrary::from_ron_str(DATA_FILE).unwrap();
+    assert!(library.pages.len() >= 2);
+}
+
+#[test]
+fn test_lore_validation_rejects_duplicates_and_blank_pages() {
+    let duplicate = r#"(pages: [(id: 1, title: "A", text: "a"), (id: 1, title: "B", text: "b")])"#;
+    assert!(matches!(LoreLibrary::from_ron_str(duplicate), Err(LoreLibraryError::DuplicateId(1))));
+    let blank = r#"(pages: [(id: 4, title: " ", text: "a")])"#;
+    assert!(matches!(LoreLibrary::from_ron_str(blank), Err(LoreLibraryError::EmptyPage(4))));
+    assert!(matches!(LoreLibrary::from_ron_str("(pages: [])"), Err(LoreLibraryError::Empty)));
+}
+
+#[test]
+fn test_pages_are_found_in_book_order_until_the_book_is_complete() {
+    let library = LoreLibrary::from_ron_str(r#"(pages: [(id: 7, title: "A", text: "a"), (id: 2, title: "B", text: "b")])"#).unwrap();
+    let mut collection = LoreCollection::default();
+    assert_eq!(library.next_uncollected(&collection).map(|page| page.id), Some(7));
+    assert!(collection.collect(7));
+    assert!(!collection.collect(7));
+    assert_eq!(library.next_uncollected(&collection).map(|page| page.id), Some(2));
+    collection.collect(2);
+    assert_eq!(library.next_uncollected(&collection), None);
+}
@@ -0,0 +1,35 @@
+use cosmic_gardener::luck::{luck_adjusted_chance, choose_multiple_with_luck, MAX_LUCK_CHANCE};
+use cosmic_gardener::upgrades::{UpgradeCard, UpgradeId, UpgradeType, UpgradePool, UpgradeRarity};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_luck_scales_chance_up_to_cap() {
+    assert_eq!(luck_adjusted_chance(0.1, 0.0), 0.1);
+    assert!((luck_adjusted_chance(0.1, 0.5) - 0.15).abs() < 1e-9);
+    assert_eq!(luck_adjusted_chance(0.5, 10.0), MAX_LUCK_CHANCE);
+    assert_eq!(luck_adjusted_chance(0.0, 10.0), 0.0); // Luck never creates a chance out of nothing
+}
+
+#[test]
+fn test_luck_biases_toward_rare_choices() {
+    let options: Vec<u32> = (0..20).collect();
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut rare_picks = 0;
+    for _ in 0..200 {
+        rare_picks += choose_multiple_with_luck(&mut rng, &options, 1, 1000.0, |option| *option == 0).iter().filter(|option| ***option == 0).count();
+    }
+    assert!(rare_picks > 150);
+}
+
+#[test]
+fn test_upgrade_pool_offers_rare_cards_with_high_luck() {
+    let mut pool = UpgradePool::default();
+    for id in 0..10 {
+        pool.available_upgrades.push(UpgradeCard { id: UpgradeId(id), name: "Common".to_string(), description: String::new(), upgrade_type: UpgradeType::SurvivorSpeed(5) });
+    }
+    pool.available_upgrades.push(UpgradeCard { id: UpgradeId(99), name: "Rare".to_string(), description: String::new(), upgrade_type: UpgradeType::ManifestRearGuard });
+    let mut rng = StdRng::seed_from_u64(3);
+    let offered = pool.get_random_allowed_upgrades(&mut rng, 3, 10_000.0, |_| true);
+    assert_eq!(offered.len(), 3);
+    assert!(offered.iter().any(|card| card.rarity() == UpgradeRarity::Rare));
+}
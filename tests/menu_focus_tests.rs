@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use cosmic_gardener::menu_focus::{focus_order, step_focus, FocusStep};
+
+#[test]
+fn test_focus_order_reads_rows_top_to_bottom_then_left_to_right() {
+    let [a, b, c, d] = [0, 1, 2, 3].map(Entity::from_raw);
+    let order = focus_order(vec![
+        (a, Vec2::new(300.0, 100.0)),
+        (b, Vec2::new(100.0, 103.0)),
+        (c, Vec2::new(100.0, 300.0)),
+        (d, Vec2::new(200.0, 98.0)),
+    ]);
+    assert_eq!(order, vec![b, d, a, c]);
+}
+
+#[test]
+fn test_focus_order_does_not_depend_on_input_order() {
+    // Each neighbour is within the row tolerance of the next, though the outer two are not of each other
+    let [a, b, c, d] = [0, 1, 2, 3].map(Entity::from_raw);
+    let positions = [(a, Vec2::new(300.0, 100.0)), (b, Vec2::new(200.0, 106.0)), (c, Vec2::new(100.0, 112.0)), (d, Vec2::new(100.0, 200.0))];
+    for rotation in 0..positions.len() {
+        let mut shuffled = positions.to_vec();
+        shuffled.rotate_left(rotation);
+        shuffled.swap(0, 2);
+        assert_eq!(focus_order(shuffled), vec![c, b, a, d]);
+    }
+}
+
+#[test]
+fn test_step_focus_wraps_and_starts_at_the_ends() {
+    let order: Vec<Entity> = (0..3).map(Entity::from_raw).collect();
+    assert_eq!(step_focus(&order, None, FocusStep::Next), Some(order[0]));
+    assert_eq!(step_focus(&order, None, FocusStep::Previous), Some(order[2]));
+    assert_eq!(step_focus(&order, Some(order[2]), FocusStep::Next), Some(order[0]));
+    assert_eq!(step_focus(&order, Some(order[0]), FocusStep::Previous), Some(order[2]));
+    // A focused button that has since disappeared restarts from the top
+    assert_eq!(step_focus(&order, Some(Entity::from_raw(9)), FocusStep::Next), Some(order[0]));
+    assert_eq!(step_focus(&[], Some(order[0]), FocusStep::Next), None);
+}
@@ -0,0 +1,99 @@
+use cosmic_gardener::{
+    horror::HorrorType,
+    mods::{scan_mods_folder, ContentKey, DataPack, ModError, ModRegistry, MOD_MANIFEST_FILE},
+    skills::{SkillEffectType, SkillId},
+    upgrades::UpgradeId,
+};
+
+const SAMPLE_PACK: &str = r#"(
+    name: "Deep Ones",
+    version: "1.0",
+    skills: [
+        (id: 900, name: "Brine Spear", description: "Hurls a spear of black water.", cooldown_secs: 2.0, glyph_slots: 1,
+         effect: Projectile(base_damage: 30, speed: 500.0, size: (8.0, 30.0), color: (0.1, 0.4, 0.5, 1.0), lifetime_secs: 2.0)),
+    ],
+    horrors: [
+        (horror_type: FloatingEyeball, base_health: 40.0, health_scaling: 1.2, damage_on_collision: 8, base_speed: 90.0, speed_per_cycle: 10.0,
+         size: (40.0, 40.0), sprite_path: "sprites/drowned_eye.png", xp_value: 30, item_drop_chance: 0.1),
+    ],
+    upgrades: [
+        (id: 900, name: "Call of the Tide", description: "Learn the Brine Spear.", upgrade_type: GrantSkill(900)),
+    ],
+)"#;
+
+fn pack(name: &str, skills: &[u32], upgrades: &[u32], horrors: &[HorrorType]) -> DataPack {
+    let mut pack = DataPack::from_ron_str(SAMPLE_PACK).expect("sample pack should parse");
+    pack.name = name.to_string();
+    let template_skill = pack.skills[0].clone();
+    pack.skills = skills.iter().map(|id| { let mut skill = template_skill.clone(); skill.id = SkillId(*id); skill }).collect();
+    let template_card = pack.upgrades[0].clone();
+    pack.upgrades = upgrades.iter().map(|id| { let mut card = template_card.clone(); card.id = UpgradeId(*id); card }).collect();
+    let template_entry = pack.horrors[0].clone();
+    pack.horrors = horrors.iter().map(|horror_type| { let mut entry = template_entry.clone(); entry.horror_type = *horror_type; entry }).collect();
+    pack
+}
+
+#[test]
+fn test_data_pack_parses_and_converts_skills() {
+    let pack = DataPack::from_ron_str(SAMPLE_PACK).unwrap();
+    assert_eq!(pack.name, "Deep Ones");
+    assert_eq!(pack.horrors[0].horror_type, HorrorType::FloatingEyeball);
+    let definition = pack.skills[0].to_definition();
+    assert_eq!(definition.id, SkillId(900));
+    assert_eq!(definition.base_glyph_slots, 1);
+    assert!(matches!(definition.effect, SkillEffectType::Projectile { base_damage: 30, piercing: 0, .. }));
+}
+
+#[test]
+fn test_data_pack_rejects_invalid_content() {
+    assert!(matches!(DataPack::from_ron_str(&SAMPLE_PACK.replace("\"Deep Ones\"", "\"\"")), Err(ModError::MissingName)));
+    assert!(matches!(DataPack::from_ron_str(&SAMPLE_PACK.replace("cooldown_secs: 2.0", "cooldown_secs: 0.0")), Err(ModError::InvalidSkill { field: "cooldown_secs", .. })));
+    assert!(matches!(DataPack::from_ron_str(&SAMPLE_PACK.replace("base_health: 40.0", "base_health: -1.0")), Err(ModError::Horrors(_))));
+    assert!(matches!(DataPack::from_ron_str("(name: \"Broken\""), Err(ModError::Parse(_))));
+}
+
+#[test]
+fn test_pack_sprites_resolve_inside_their_own_folder() {
+    let pack = DataPack::from_ron_str(SAMPLE_PACK).unwrap().with_asset_folder("deep_ones");
+    assert_eq!(pack.horrors[0].sprite_path, "mods://deep_ones/sprites/drowned_eye.png");
+}
+
+#[test]
+fn test_resolve_reports_conflicts_and_keeps_the_first_claim() {
+    let packs = vec![
+        ("b_second".to_string(), pack("Second", &[901, 1], &[950], &[HorrorType::FloatingEyeball])),
+        ("a_first".to_string(), pack("First", &[901], &[950, 0], &[HorrorType::FloatingEyeball, HorrorType::VoidBlinker])),
+    ];
+    let registry = ModRegistry::resolve(packs, &[SkillId(1)], &[UpgradeId(0)]);
+
+    assert_eq!(registry.mods.iter().map(|loaded| loaded.folder.as_str()).collect::<Vec<_>>(), vec!["a_first", "b_second"]);
+    assert_eq!(registry.skills().map(|skill| skill.id).collect::<Vec<_>>(), vec![SkillId(901)]);
+    assert_eq!(registry.upgrades().map(|card| card.id).collect::<Vec<_>>(), vec![UpgradeId(950)]);
+    assert_eq!(registry.horrors().count(), 2);
+
+    let conflicts: Vec<(ContentKey, &str, &str)> = registry.conflicts.iter().map(|conflict| (conflict.key, conflict.mod_name.as_str(), conflict.claimed_by.as_str())).collect();
+    assert!(conflicts.contains(&(ContentKey::Upgrade(UpgradeId(0)), "First", "base game")));
+    assert!(conflicts.contains(&(ContentKey::Skill(SkillId(901)), "Second", "First")));
+    assert!(conflicts.contains(&(ContentKey::Skill(SkillId(1)), "Second", "base game")));
+    assert!(conflicts.contains(&(ContentKey::Upgrade(UpgradeId(950)), "Second", "First")));
+    assert!(conflicts.contains(&(ContentKey::Horror(HorrorType::FloatingEyeball), "Second", "First")));
+    assert_eq!(conflicts.len(), 5);
+}
+
+#[test]
+fn test_scan_mods_folder_loads_packs_and_reports_broken_ones() {
+    let mods_dir = std::env::temp_dir().join(format!("cosmic_gardener_mods_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&mods_dir);
+    std::fs::create_dir_all(mods_dir.join("deep_ones")).unwrap();
+    std::fs::create_dir_all(mods_dir.join("broken")).unwrap();
+    std::fs::create_dir_all(mods_dir.join("not_a_mod")).unwrap();
+    std::fs::write(mods_dir.join("deep_ones").join(MOD_MANIFEST_FILE), SAMPLE_PACK).unwrap();
+    std::fs::write(mods_dir.join("broken").join(MOD_MANIFEST_FILE), "(name: ").unwrap();
+
+    let scanned = scan_mods_folder(&mods_dir);
+    std::fs::remove_dir_all(&mods_dir).unwrap();
+
+    assert_eq!(scanned.iter().map(|(folder, _)| folder.as_str()).collect::<Vec<_>>(), vec!["broken", "deep_ones"]);
+    assert!(scanned[0].1.is_err());
+    assert_eq!(scanned[1].1.as_ref().unwrap().horrors[0].sprite_path, "mods://deep_ones/sprites/drowned_eye.png");
+}
@@ -0,0 +1,18 @@
+use cosmic_gardener::overwhelm::Overwhelm;
+
+#[test]
+fn test_overwhelm_rises_with_density_and_decays() {
+    let mut overwhelm = Overwhelm::default();
+    assert_eq!(overwhelm.tier(), 0);
+    assert_eq!(overwhelm.fire_rate_bonus(), 0.0);
+
+    overwhelm.set_nearby_horror_count(100); // Far more than needed to fill the meter
+    for _ in 0..40 { overwhelm.advance(0.1); }
+    assert_eq!(overwhelm.meter, 1.0);
+    assert_eq!(overwhelm.tier(), 3);
+    assert!(overwhelm.speed_bonus() > 0.0);
+
+    overwhelm.set_nearby_horror_count(0);
+    overwhelm.advance(1.0);
+    assert!(overwhelm.meter < 1.0 && overwhelm.meter > 0.0); // Drains gradually rather than resetting
+}
@@ -0,0 +1,60 @@
+use cosmic_gardener::{
+    pause_menu::{swap_skill_slots, SkillSlot},
+    skills::{ActiveSkillInstance, SkillId, MAX_SKILL_SLOTS},
+    survivor::Survivor,
+};
+
+fn survivor_with(equipped: &[u32], learned: &[u32]) -> Survivor {
+    let mut survivor = Survivor::new_with_skills_and_items(equipped.iter().map(|id| ActiveSkillInstance::new(SkillId(*id), 0)).collect(), Vec::new());
+    survivor.learned_skills = learned.iter().map(|id| ActiveSkillInstance::new(SkillId(*id), 0)).collect();
+    survivor
+}
+
+fn ids(skills: &[ActiveSkillInstance]) -> Vec<u32> {
+    skills.iter().map(|skill| skill.definition_id.0).collect()
+}
+
+#[test]
+fn test_skills_learned_with_full_slots_wait_in_the_pool() {
+    let mut survivor = survivor_with(&[1, 2, 3, 4], &[]);
+    assert!(survivor.learn_skill(ActiveSkillInstance::new(SkillId(5), 0)));
+    assert!(survivor.learn_skill(ActiveSkillInstance::new(SkillId(6), 0)));
+    assert!(!survivor.learn_skill(ActiveSkillInstance::new(SkillId(2), 0)));
+    assert!(!survivor.learn_skill(ActiveSkillInstance::new(SkillId(6), 0)));
+    assert_eq!(survivor.equipped_skills.len(), MAX_SKILL_SLOTS);
+    assert_eq!(ids(&survivor.learned_skills), vec![6]);
+}
+
+#[test]
+fn test_equipped_skills_reorder_and_trade_with_the_pool() {
+    let mut survivor = survivor_with(&[1, 2, 3], &[7, 8]);
+    let mut upgraded = survivor.equipped_skills[0].clone();
+    upgraded.current_level = 4;
+    survivor.equipped_skills[0] = upgraded;
+
+    assert!(swap_skill_slots(&mut survivor, SkillSlot::Equipped(0), SkillSlot::Equipped(2)));
+    assert_eq!(ids(&survivor.equipped_skills), vec![3, 2, 1]);
+    assert_eq!(survivor.equipped_skills[2].current_level, 4);
+
+    assert!(swap_skill_slots(&mut survivor, SkillSlot::Learned(1), SkillSlot::Equipped(1)));
+    assert_eq!(ids(&survivor.equipped_skills), vec![3, 8, 1]);
+    assert_eq!(ids(&survivor.learned_skills), vec![7, 2]);
+
+    // Free space on either side: equip into the next empty slot, or set a skill aside
+    assert!(swap_skill_slots(&mut survivor, SkillSlot::Learned(0), SkillSlot::Equipped(4)));
+    assert_eq!(ids(&survivor.equipped_skills), vec![3, 8, 1, 7]);
+    assert!(swap_skill_slots(&mut survivor, SkillSlot::Equipped(0), SkillSlot::Learned(1)));
+    assert_eq!(ids(&survivor.equipped_skills), vec![8, 1, 7]);
+    assert_eq!(ids(&survivor.learned_skills), vec![2, 3]);
+}
+
+#[test]
+fn test_swaps_that_would_lose_or_invent_skills_are_refused() {
+    let mut survivor = survivor_with(&[1], &[7]);
+    assert!(!swap_skill_slots(&mut survivor, SkillSlot::Equipped(0), SkillSlot::Learned(1)));
+    assert!(!swap_skill_slots(&mut survivor, SkillSlot::Learned(0), SkillSlot::Learned(1)));
+    assert!(!swap_skill_slots(&mut survivor, SkillSlot::Equipped(2), SkillSlot::Equipped(3)));
+    assert!(!swap_skill_slots(&mut survivor, SkillSlot::Equipped(0), SkillSlot::Equipped(MAX_SKILL_SLOTS)));
+    assert_eq!(ids(&survivor.equipped_skills), vec![1]);
+    assert_eq!(ids(&survivor.learned_skills), vec![7]);
+}
@@ -0,0 +1,36 @@
+use bevy::{prelude::*, ecs::system::RunSystemOnce};
+use cosmic_gardener::perf_overlay::{frame_graph_bar, take_collision_time_ms, timed_collision, EntityBreakdown, FRAME_GRAPH_CEILING_MS};
+use std::{thread, time::Duration};
+
+#[test]
+fn test_frame_graph_bars_scale_and_colour_by_budget() {
+    let (smooth_height, smooth_color) = frame_graph_bar(16.0);
+    let (slow_height, slow_color) = frame_graph_bar(25.0);
+    let (spike_height, spike_color) = frame_graph_bar(FRAME_GRAPH_CEILING_MS * 3.0);
+
+    assert!((smooth_height - 16.0 / FRAME_GRAPH_CEILING_MS).abs() < 1e-6);
+    assert!(slow_height > smooth_height);
+    assert_eq!(spike_height, 1.0);
+    assert_ne!(smooth_color, slow_color);
+    assert_ne!(slow_color, spike_color);
+    assert_eq!(frame_graph_bar(-1.0).0, 0.0);
+}
+
+#[test]
+fn test_entity_breakdown_leaves_the_rest_as_other() {
+    let breakdown = EntityBreakdown { enemies: 40, projectiles: 25, orbs: 10, particles: 100 };
+    assert_eq!(breakdown.other(300), 125);
+    // The total is smoothed, so it can briefly lag behind the live counts
+    assert_eq!(breakdown.other(150), 0);
+}
+
+#[test]
+fn test_timed_collision_systems_add_up_until_taken() {
+    fn slow_collision_system() { thread::sleep(Duration::from_millis(2)); }
+    let mut world = World::new();
+    take_collision_time_ms();
+    world.run_system_once(timed_collision(slow_collision_system));
+    world.run_system_once(timed_collision(slow_collision_system));
+    assert!(take_collision_time_ms() >= 4.0);
+    assert_eq!(take_collision_time_ms(), 0.0);
+}
@@ -0,0 +1,39 @@
+use bevy::math::Vec3;
+use cosmic_gardener::extraction::RunOutcome;
+use cosmic_gardener::game::AppState;
+use cosmic_gardener::game_events::{GameEvent, HorrorKill};
+use cosmic_gardener::horror::HorrorType;
+use cosmic_gardener::platform_services::{achievement_for_event, achievements_for_run, rich_presence, Achievement, ACHIEVEMENT_CYCLE, ACHIEVEMENT_LEVEL};
+
+fn kill(is_elite: bool) -> GameEvent {
+    GameEvent::HorrorKilled(HorrorKill { horror_type: HorrorType::FrenziedBehemoth, position: Vec3::ZERO, is_elite, xp_value: 10, skill: None })
+}
+
+#[test]
+fn test_only_notable_events_unlock_achievements() {
+    assert_eq!(achievement_for_event(&kill(true)), Some(Achievement::SlayElite));
+    assert_eq!(achievement_for_event(&kill(false)), None);
+    assert_eq!(achievement_for_event(&GameEvent::LevelUp { new_level: ACHIEVEMENT_LEVEL, levels_gained: 1 }), Some(Achievement::ReachLevel));
+    assert_eq!(achievement_for_event(&GameEvent::LevelUp { new_level: ACHIEVEMENT_LEVEL - 1, levels_gained: 1 }), None);
+}
+
+#[test]
+fn test_finished_runs_unlock_by_cycle_and_outcome() {
+    assert!(achievements_for_run(ACHIEVEMENT_CYCLE - 1, RunOutcome::Died).is_empty());
+    assert_eq!(achievements_for_run(ACHIEVEMENT_CYCLE, RunOutcome::Extracted), vec![Achievement::SurviveCycles, Achievement::Extract]);
+}
+
+#[cfg(feature = "steam")]
+#[test]
+fn test_achievement_names_are_unique() {
+    for (i, achievement) in Achievement::ALL.iter().enumerate() {
+        assert!(Achievement::ALL[..i].iter().all(|earlier| earlier.api_name() != achievement.api_name()));
+    }
+}
+
+#[test]
+fn test_rich_presence_mentions_the_cycle_in_play() {
+    assert_eq!(rich_presence(&AppState::InGame, 3), "Surviving cycle 3");
+    assert_eq!(rich_presence(&AppState::LevelUp, 3), "Surviving cycle 3");
+    assert_eq!(rich_presence(&AppState::MainMenu, 3), "In the main menu");
+}
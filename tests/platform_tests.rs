@@ -0,0 +1,32 @@
+use bevy::math::Vec2;
+use cosmic_gardener::platform::{backup_key, ui_scale_for, MemoryStorage, Storage};
+use cosmic_gardener::save_data::{read_save, write_save, SaveData, SaveSource};
+use std::io::ErrorKind;
+
+#[test]
+fn test_memory_storage_keeps_the_previous_entry_as_backup() {
+    let storage = MemoryStorage::default();
+    assert_eq!(storage.read("profile").unwrap_err().kind(), ErrorKind::NotFound);
+    storage.write_keeping_backup("profile", "first").unwrap();
+    assert_eq!(storage.read(&backup_key("profile")).unwrap_err().kind(), ErrorKind::NotFound);
+    storage.write_keeping_backup("profile", "second").unwrap();
+    assert_eq!(storage.read("profile").unwrap(), "second");
+    assert_eq!(storage.read(&backup_key("profile")).unwrap(), "first");
+}
+
+#[test]
+fn test_saves_restore_from_backup_in_any_storage() {
+    let storage = MemoryStorage::default();
+    write_save(&storage, "save_data.ron", &SaveData { remnants: 10, ..Default::default() }).unwrap();
+    write_save(&storage, "save_data.ron", &SaveData { remnants: 20, ..Default::default() }).unwrap();
+    storage.write("save_data.ron", "torn").unwrap();
+    assert_eq!(read_save(&storage, "save_data.ron"), (SaveData { remnants: 10, ..Default::default() }, SaveSource::Backup));
+}
+
+#[test]
+fn test_ui_shrinks_to_fit_the_smaller_side_of_the_canvas() {
+    assert_eq!(ui_scale_for(Vec2::new(1280.0, 720.0)), 1.0);
+    assert_eq!(ui_scale_for(Vec2::new(640.0, 720.0)), 0.5);
+    assert_eq!(ui_scale_for(Vec2::new(2560.0, 720.0)), 1.0);
+    assert_eq!(ui_scale_for(Vec2::ZERO), 1.0);
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use cosmic_gardener::projectile::{is_leaving_view, steer_towards};
+
+#[test]
+fn test_steer_towards_limits_turn_and_keeps_speed() {
+    let velocity = Vec2::new(100.0, 0.0);
+    let steered = steer_towards(velocity, Vec2::new(0.0, 50.0), 0.1);
+    assert!((steered.to_angle() - 0.1).abs() < 1e-4);
+    assert!((steered.length() - 100.0).abs() < 1e-3);
+
+    // Within the turn limit the projectile points straight at the target
+    let steered = steer_towards(velocity, Vec2::new(10.0, 0.5), 0.5);
+    assert!((steered.to_angle() - Vec2::new(10.0, 0.5).to_angle()).abs() < 1e-4);
+
+    assert_eq!(steer_towards(Vec2::ZERO, Vec2::X, 1.0), Vec2::ZERO);
+}
+
+#[test]
+fn test_only_shots_past_the_margin_and_heading_out_are_culled() {
+    let (center, half, margin) = (Vec2::new(100.0, 0.0), Vec2::new(640.0, 360.0), 200.0);
+    assert!(!is_leaving_view(Vec2::new(900.0, 0.0), Vec2::X, center, half, margin));
+    assert!(is_leaving_view(Vec2::new(1000.0, 0.0), Vec2::X, center, half, margin));
+    assert!(is_leaving_view(Vec2::new(0.0, -600.0), Vec2::new(0.3, -1.0), center, half, margin));
+    // A shot fired from far away towards the view is left alone
+    assert!(!is_leaving_view(Vec2::new(1000.0, 0.0), -Vec2::X, center, half, margin));
+}
@@ -0,0 +1,66 @@
+use cosmic_gardener::{
+    game_events::DamageOrigin,
+    ranking::{GradeThresholds, GradeThresholdsError, RunGrade, RunStats, COMBO_WINDOW_SECS},
+    skills::SkillId,
+    summons::SummonKind,
+};
+
+const DATA_FILE: &str = include_str!("../assets/data/grades.ron");
+
+#[test]
+fn test_combo_breaks_after_window_and_keeps_best() {
+    let mut stats = RunStats::default();
+    stats.record(0.1, 3, 0);
+    stats.record(1.0, 2, 0);
+    assert_eq!(stats.current_combo, 5);
+    stats.record(COMBO_WINDOW_SECS + 0.1, 1, 12);
+    assert_eq!(stats.current_combo, 1);
+    assert_eq!(stats.highest_combo, 5);
+    assert_eq!(stats.kills, 6);
+    assert_eq!(stats.damage_taken, 12);
+}
+
+#[test]
+fn test_cheated_runs_do_not_count_for_records() {
+    let clean = RunStats::default();
+    assert!(clean.counts_for_records());
+    assert!(!RunStats { cheats_used: true, ..clean }.counts_for_records());
+}
+
+#[test]
+fn test_shipped_grades_rank_runs() {
+    let thresholds = GradeThresholds::from_ron_str(DATA_FILE).unwrap();
+    let flawless = RunStats { kills: 700, damage_taken: 40, highest_combo: 60, ..Default::default() };
+    assert_eq!(thresholds.grade_for(&flawless, 600.0).grade, RunGrade::S);
+    // Plenty of kills, but taking too much damage drops the grade
+    let reckless = RunStats { damage_taken: 600, ..flawless.clone() };
+    assert_eq!(thresholds.grade_for(&reckless, 600.0).grade, RunGrade::B);
+    // Very short runs are graded over at least a minute
+    let opening_burst = RunStats { kills: 30, highest_combo: 30, ..Default::default() };
+    assert_eq!(thresholds.grade_for(&opening_burst, 10.0).grade, RunGrade::B);
+    assert_eq!(thresholds.grade_for(&RunStats::default(), 30.0).grade, RunGrade::C);
+}
+
+#[test]
+fn test_grade_thresholds_validation() {
+    let mut thresholds = GradeThresholds::from_ron_str(DATA_FILE).unwrap();
+    let duplicate = thresholds.grades[0].clone();
+    thresholds.grades.push(duplicate);
+    assert!(matches!(thresholds.validate(), Err(GradeThresholdsError::DuplicateGrade(RunGrade::S))));
+    thresholds.grades.clear();
+    assert!(matches!(thresholds.validate(), Err(GradeThresholdsError::Empty)));
+}
+
+#[test]
+fn test_summon_damage_is_counted_in_the_total_and_broken_out_by_kind() {
+    let mut stats = RunStats::default();
+    stats.record_damage(DamageOrigin::IchorBlast, 30);
+    stats.record_damage(DamageOrigin::Summon(SummonKind::PsychicSentry), 12);
+    stats.record_damage(DamageOrigin::Skill(SkillId(3)), 50);
+    stats.record_damage(DamageOrigin::Summon(SummonKind::NightmareLarva), 4);
+    stats.record_damage(DamageOrigin::Summon(SummonKind::PsychicSentry), 12);
+    stats.record_damage(DamageOrigin::Summon(SummonKind::NightmareLarva), -5);
+    assert_eq!(stats.damage_dealt, 108);
+    assert_eq!(stats.summon_damage, vec![(SummonKind::PsychicSentry, 24), (SummonKind::NightmareLarva, 4)]);
+    assert_eq!(stats.total_summon_damage(), 28);
+}
@@ -0,0 +1,52 @@
+use cosmic_gardener::{
+    components::Health,
+    respec_shrine::{is_refundable, refundable_picks, respec_cost, revert_upgrade, MAX_RESPEC_REFUNDS, RESPEC_GOLD_PER_PICK},
+    run_history::UpgradeLog,
+    survivor::Survivor,
+    upgrades::{UpgradeCard, UpgradeId, UpgradeType},
+};
+
+fn card(id: u32, upgrade_type: UpgradeType) -> UpgradeCard {
+    UpgradeCard { id: UpgradeId(id), name: format!("Card {}", id), description: String::new(), upgrade_type }
+}
+
+fn cards() -> Vec<UpgradeCard> {
+    vec![
+        card(1, UpgradeType::SurvivorSpeed(10)),
+        card(2, UpgradeType::MaxEndurance(20)),
+        card(3, UpgradeType::GrantRandomRelic),
+        card(4, UpgradeType::Luck(15)),
+    ]
+}
+
+#[test]
+fn test_revert_upgrade_undoes_stat_nudges_only() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    let base_speed = survivor.speed;
+    let base_max_health = survivor.max_health;
+    survivor.speed *= 1.1;
+    survivor.max_health += 20;
+    let mut health = Health(survivor.max_health);
+
+    assert!(revert_upgrade(&UpgradeType::SurvivorSpeed(10), &mut survivor, &mut health));
+    assert!((survivor.speed - base_speed).abs() < 1e-3);
+    assert!(revert_upgrade(&UpgradeType::MaxEndurance(20), &mut survivor, &mut health));
+    assert_eq!(survivor.max_health, base_max_health);
+    assert_eq!(health.0, base_max_health);
+
+    assert!(!is_refundable(&UpgradeType::GrantRandomRelic));
+    assert!(!revert_upgrade(&UpgradeType::CooldownReduction(10), &mut survivor, &mut health));
+}
+
+#[test]
+fn test_refundable_picks_are_newest_first_and_stop_at_a_grant() {
+    let cards = cards();
+    let log = UpgradeLog(vec![UpgradeId(1), UpgradeId(3), UpgradeId(2), UpgradeId(4), UpgradeId(1)]);
+    let ids: Vec<u32> = refundable_picks(&log, &cards).iter().map(|card| card.id.0).collect();
+    assert_eq!(ids, vec![1, 4, 2]);
+
+    let capped = UpgradeLog(vec![UpgradeId(1); MAX_RESPEC_REFUNDS + 2]);
+    assert_eq!(refundable_picks(&capped, &cards).len(), MAX_RESPEC_REFUNDS);
+    assert!(refundable_picks(&UpgradeLog(vec![UpgradeId(1), UpgradeId(3)]), &cards).is_empty());
+    assert_eq!(respec_cost(2), RESPEC_GOLD_PER_PICK * 2);
+}
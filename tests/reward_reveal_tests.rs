@@ -0,0 +1,35 @@
+use cosmic_gardener::items::ItemId;
+use cosmic_gardener::reward_reveal::{RewardReveal, RewardRng, REVEAL_SPIN_SECS};
+
+fn pool() -> Vec<ItemId> {
+    (1..=10).map(ItemId).collect()
+}
+
+#[test]
+fn test_reward_reveal_is_deterministic_per_seed() {
+    let mut first_run = RewardRng::new(42);
+    let mut second_run = RewardRng::new(42);
+    for _ in 0..5 {
+        let a = RewardReveal::roll(&mut first_run.next_rng(), &pool()).unwrap();
+        let b = RewardReveal::roll(&mut second_run.next_rng(), &pool()).unwrap();
+        assert_eq!(a, b);
+        assert!((2..=3).contains(&a.candidates.len()));
+        assert!(a.landed < a.candidates.len());
+    }
+    assert!(RewardReveal::roll(&mut RewardRng::new(1).next_rng(), &[]).is_none());
+}
+
+#[test]
+fn test_reward_reveal_spin_lands_on_the_rolled_item() {
+    let mut reveal = RewardReveal { candidates: vec![ItemId(3), ItemId(7), ItemId(9)], landed: 1, elapsed_secs: 0.0 };
+    assert_eq!(reveal.highlighted(), 0);
+    assert!(!reveal.has_landed());
+
+    reveal.skip();
+    assert!(reveal.has_landed());
+    assert_eq!(reveal.highlighted(), 1);
+    assert_eq!(reveal.landed_item(), ItemId(7));
+
+    reveal.elapsed_secs = REVEAL_SPIN_SECS * 10.0;
+    assert_eq!(reveal.highlighted(), 1);
+}
@@ -0,0 +1,71 @@
+use cosmic_gardener::{
+    platform::{MemoryStorage, Storage},
+    run_history::{BuildCode, BuildCodeError, RunHistory, RunRecord, SkillLoadout, UpgradeLog, BUILD_EXPORT_PATH, MAX_RUN_HISTORY},
+    upgrades::UpgradeId,
+};
+
+fn sample_build() -> BuildCode {
+    BuildCode {
+        skills: vec![SkillLoadout { skill_id: 1, glyphs: vec![Some(4), None] }, SkillLoadout { skill_id: 3, glyphs: Vec::new() }],
+        items: vec![2, 5],
+        glyphs: vec![7],
+        upgrades: vec![12, 3, 45],
+    }
+}
+
+#[test]
+fn test_build_code_round_trips() {
+    let build = sample_build();
+    let code = build.encode();
+    assert_eq!(code, "CG1/1:4-_.3/2.5/7/12.3.45");
+    assert_eq!(BuildCode::decode(&code), Ok(build));
+    assert_eq!(BuildCode::decode(&BuildCode::default().encode()), Ok(BuildCode::default()));
+}
+
+#[test]
+fn test_build_code_rejects_malformed_input() {
+    assert_eq!(BuildCode::decode("hello"), Err(BuildCodeError::UnknownFormat));
+    assert_eq!(BuildCode::decode("CG1/1/2"), Err(BuildCodeError::MissingSection("glyphs")));
+    assert_eq!(BuildCode::decode("CG1/1/x/3/4"), Err(BuildCodeError::InvalidId("x".to_string())));
+}
+
+#[test]
+fn test_pasted_code_wins_and_the_export_file_covers_a_missing_clipboard() {
+    let storage = MemoryStorage::default();
+    let exported = BuildCode { upgrades: vec![9], ..sample_build() };
+    storage.write(BUILD_EXPORT_PATH, &exported.encode()).unwrap();
+
+    assert_eq!(BuildCode::from_paste(Ok(format!("  {}\n", sample_build().encode())), &storage), Ok(sample_build()));
+    assert_eq!(BuildCode::from_paste(Err("no display".to_string()), &storage), Ok(exported));
+    assert!(BuildCode::from_paste(Ok("grocery list".to_string()), &storage).is_err());
+}
+
+#[test]
+fn test_run_history_round_trips_and_drops_oldest_runs() {
+    let mut history = RunHistory::default();
+    for score in 0..(MAX_RUN_HISTORY as u32 + 2) {
+        history.push(RunRecord { score, cycle_reached: 1, survived_secs: 10.0, build: sample_build(), cheated: false });
+    }
+    assert_eq!(history.runs.len(), MAX_RUN_HISTORY);
+    assert_eq!(history.runs[0].score, 2);
+
+    let reloaded = RunHistory::from_ron_str(&history.to_ron_string().unwrap()).unwrap();
+    assert_eq!(reloaded, history);
+}
+
+#[test]
+fn test_runs_saved_before_the_cheat_flag_load_as_clean() {
+    let old = RunHistory::from_ron_str("(runs: [(score: 10, cycle_reached: 2, survived_secs: 30.0, build: (skills: [], items: [], glyphs: [], upgrades: []))])").unwrap();
+    assert!(!old.runs[0].cheated);
+
+    let mut history = RunHistory::default();
+    history.push(RunRecord { score: 5, cycle_reached: 1, survived_secs: 10.0, build: BuildCode::default(), cheated: true });
+    assert!(RunHistory::from_ron_str(&history.to_ron_string().unwrap()).unwrap().runs[0].cheated);
+}
+
+#[test]
+fn test_upgrade_log_counts_stacks_in_pick_order() {
+    let log = UpgradeLog(vec![UpgradeId(4), UpgradeId(9), UpgradeId(4), UpgradeId(4), UpgradeId(9)]);
+    assert_eq!(log.picks_with_stacks(), vec![(UpgradeId(4), 1), (UpgradeId(9), 1), (UpgradeId(4), 2), (UpgradeId(4), 3), (UpgradeId(9), 2)]);
+    assert!(UpgradeLog::default().picks_with_stacks().is_empty());
+}
@@ -0,0 +1,71 @@
+use cosmic_gardener::{
+    background::BackgroundQuality,
+    bestiary::{Bestiary, BestiaryEntry},
+    challenge::{IsoWeek, WeeklyBest},
+    horror::HorrorType,
+    lore::LoreCollection,
+    skins::{SkinCollection, SkinId},
+    touch_controls::TouchControlMode,
+    platform::{backup_path, write_atomically, FileStorage},
+    save_data::{read_save, write_save, SaveData, SaveError, SaveSource, SavedSettings},
+};
+use std::path::PathBuf;
+
+fn sample() -> SaveData {
+    SaveData { remnants: 42, unbanked_remnants: 7, corruption_level: 1, corruption_unlocked: 2, settings: SavedSettings { background_quality: BackgroundQuality::High, afterimages_enabled: false, focus_announcer_enabled: true, hardcore_enabled: true, touch_controls: TouchControlMode::On, void_closes_in: true }, weekly_best: Some(WeeklyBest { week: IsoWeek { year: 2026, week: 42 }, score: 1234 }), bestiary: Bestiary { entries: vec![BestiaryEntry { horror_type: HorrorType::VoidBlinker, kills: 17, elite_encountered: true }] }, lore: LoreCollection { collected: vec![1, 3] }, skins: SkinCollection { owned: vec![SkinId::Wanderer, SkinId::Voidborn], selected: SkinId::Voidborn } }
+}
+
+fn save_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cosmic_gardener_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_save_string_round_trips_and_detects_damage() {
+    let saved = sample().to_save_string().unwrap();
+    assert_eq!(SaveData::from_save_str(&saved).unwrap(), sample());
+    assert!(matches!(SaveData::from_save_str(&saved.replace("42", "9999")), Err(SaveError::Corrupted)));
+    assert!(matches!(SaveData::from_save_str(&saved[..saved.len() / 2]), Err(SaveError::Corrupted)));
+    assert!(matches!(SaveData::from_save_str(""), Err(SaveError::Corrupted)));
+}
+
+#[test]
+fn test_atomic_writes_keep_the_previous_file_as_backup() {
+    let dir = save_dir("atomic");
+    let path = dir.join("save_data.ron");
+    write_atomically(&path, "first").unwrap();
+    write_atomically(&path, "second").unwrap();
+    let (current, backup) = (std::fs::read_to_string(&path).unwrap(), std::fs::read_to_string(backup_path(&path)).unwrap());
+    let leftovers = std::fs::read_dir(&dir).unwrap().count();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(current, "second");
+    assert_eq!(backup, "first");
+    assert_eq!(leftovers, 2);
+}
+
+#[test]
+fn test_read_save_falls_back_to_backup_then_defaults() {
+    let dir = save_dir("restore");
+    let path = dir.join("save_data.ron");
+    let storage = FileStorage::new(&dir);
+    let fresh = read_save(&storage, "save_data.ron");
+
+    write_save(&storage, "save_data.ron", &sample()).unwrap();
+    write_save(&storage, "save_data.ron", &SaveData { remnants: 50, ..sample() }).unwrap();
+    let primary = read_save(&storage, "save_data.ron");
+
+    std::fs::write(&path, "// checksum 0000\n(remnants: 0").unwrap();
+    let restored = read_save(&storage, "save_data.ron");
+
+    std::fs::write(backup_path(&path), "garbage").unwrap();
+    let lost = read_save(&storage, "save_data.ron");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(fresh, (SaveData::default(), SaveSource::Fresh));
+    assert_eq!(primary, (SaveData { remnants: 50, ..sample() }, SaveSource::Primary));
+    assert_eq!(restored, (sample(), SaveSource::Backup));
+    assert_eq!(lost, (SaveData::default(), SaveSource::Fresh));
+}
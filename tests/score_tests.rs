@@ -0,0 +1,18 @@
+use cosmic_gardener::score::{cycle_clear_bonus, ScoreBreakdown, ScoreSource, CYCLE_CLEAR_BONUS_PER_CYCLE, NO_DAMAGE_CYCLE_BONUS};
+
+#[test]
+fn test_score_breakdown_totals_each_source_in_first_earned_order() {
+    let mut breakdown = ScoreBreakdown::default();
+    breakdown.add(ScoreSource::Kills, 10);
+    breakdown.add(ScoreSource::EliteKills, 250);
+    breakdown.add(ScoreSource::Kills, 5);
+
+    assert_eq!(breakdown.entries(), &[(ScoreSource::Kills, 15), (ScoreSource::EliteKills, 250)]);
+    assert_eq!(breakdown.total(), 265);
+}
+
+#[test]
+fn test_cycle_clear_bonus_rewards_untouched_cycles() {
+    assert_eq!(cycle_clear_bonus(3, false), vec![(ScoreSource::CycleClear, CYCLE_CLEAR_BONUS_PER_CYCLE * 3)]);
+    assert_eq!(cycle_clear_bonus(1, true), vec![(ScoreSource::CycleClear, CYCLE_CLEAR_BONUS_PER_CYCLE), (ScoreSource::NoDamageCycle, NO_DAMAGE_CYCLE_BONUS)]);
+}
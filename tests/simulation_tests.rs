@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use rand::Rng;
+use cosmic_gardener::{
+    game::GameRng,
+    simulation::{InputScript, SimulationConfig, SimulationError, SimulationSnapshot, DEFAULT_SIMULATION_SEED, SIMULATION_STEPS_PER_SEC},
+};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+fn snapshot() -> SimulationSnapshot {
+    SimulationSnapshot {
+        steps: 3600,
+        score: 1200,
+        cycle_number: 3,
+        survivor: Some((Vec2::new(12.5, -40.0), 80, 6, 340)),
+        horrors: vec![(Vec2::new(100.0, 0.0), 20), (Vec2::new(-50.0, 75.25), 45)],
+    }
+}
+
+#[test]
+fn test_simulation_args_parse_and_default_to_a_normal_run() {
+    assert!(SimulationConfig::from_args(args("")).unwrap().is_none());
+
+    let config = SimulationConfig::from_args(args("--simulate 2.5 --seed 42 --expect 0x00ff")).unwrap().unwrap();
+    assert_eq!(config.minutes, 2.5);
+    assert_eq!(config.seed, 42);
+    assert_eq!(config.expected_hash, Some(0xff));
+    assert_eq!(config.total_steps(), 150 * SIMULATION_STEPS_PER_SEC as u64);
+    assert_eq!(SimulationConfig::from_args(args("--simulate 1")).unwrap().unwrap().seed, DEFAULT_SIMULATION_SEED);
+
+    assert!(matches!(SimulationConfig::from_args(args("--simulate")), Err(SimulationError::MissingValue(_))));
+    assert!(matches!(SimulationConfig::from_args(args("--simulate -1")), Err(SimulationError::InvalidValue(_))));
+    assert!(matches!(SimulationConfig::from_args(args("--seed 7")), Err(SimulationError::MissingValue(_))));
+}
+
+#[test]
+fn test_input_script_holds_keys_for_their_window() {
+    let script = InputScript::from_ron_str("(inputs: [(key: Up, from_secs: 1.0, hold_secs: 2.0), (key: Skill4, from_secs: 2.5, hold_secs: 0.5)])").unwrap();
+    assert!(script.keys_held_at(0.5).is_empty());
+    assert_eq!(script.keys_held_at(1.0), vec![KeyCode::KeyW]);
+    assert_eq!(script.keys_held_at(2.75), vec![KeyCode::KeyW, KeyCode::KeyE]);
+    assert!(script.keys_held_at(3.0).is_empty());
+}
+
+#[test]
+fn test_state_hash_ignores_horror_order_but_not_state() {
+    let base = snapshot();
+    let mut reordered = snapshot();
+    reordered.horrors.reverse();
+    assert_eq!(base.state_hash(), reordered.state_hash());
+
+    let mut moved = snapshot();
+    moved.horrors[0].0.x += 0.5;
+    assert_ne!(base.state_hash(), moved.state_hash());
+
+    let mut wounded = snapshot();
+    wounded.survivor = Some((Vec2::new(12.5, -40.0), 79, 6, 340));
+    assert_ne!(base.state_hash(), wounded.state_hash());
+}
+
+#[test]
+fn test_seeded_game_rng_repeats_its_rolls() {
+    let mut first = GameRng::seeded(99);
+    let mut second = GameRng::seeded(99);
+    let rolls: Vec<u32> = (0..8).map(|_| first.0.gen()).collect();
+    assert_eq!(rolls, (0..8).map(|_| second.0.gen()).collect::<Vec<u32>>());
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use cosmic_gardener::{
+    mods::{DataPack, ModError},
+    skill_scripts::{run_script_actions, ScriptAction, ScriptCommand, ScriptContext, ScriptTick, SkillScript, SkillScriptError, MAX_SCRIPT_COMMANDS},
+    skills::SkillEffectType,
+};
+
+const SCRIPTED_PACK: &str = r#"(
+    name: "Tidecallers",
+    version: "1.0",
+    skills: [
+        (id: 910, name: "Undertow", description: "Drags nearby horrors down.", cooldown_secs: 3.0, glyph_slots: 1,
+         effect: Scripted(script: "undertow")),
+    ],
+    scripts: {
+        "undertow": (
+            on_cast: [DamageEnemiesInRadius(radius: 150.0, damage: 12, max_targets: Some(3))],
+            on_hit: [ChillTarget(speed_multiplier: 0.5, duration_secs: 2.0)],
+        ),
+    },
+)"#;
+
+fn context(target: Option<Entity>) -> ScriptContext {
+    ScriptContext { origin: Vec2::ZERO, aim: Vec2::X, target, damage_bonus: 5 }
+}
+
+fn enemies() -> Vec<(Entity, Vec2)> {
+    vec![
+        (Entity::from_raw(1), Vec2::new(90.0, 0.0)),
+        (Entity::from_raw(2), Vec2::new(10.0, 0.0)),
+        (Entity::from_raw(3), Vec2::new(500.0, 0.0)),
+        (Entity::from_raw(4), Vec2::new(0.0, -40.0)),
+    ]
+}
+
+#[test]
+fn test_radius_actions_hit_the_nearest_enemies_first() {
+    let actions = [ScriptAction::DamageEnemiesInRadius { radius: 100.0, damage: 10, max_targets: Some(2) }];
+    let commands = run_script_actions(&actions, &context(None), &enemies());
+    assert_eq!(commands, vec![
+        ScriptCommand::Damage { target: Entity::from_raw(2), amount: 15 },
+        ScriptCommand::Damage { target: Entity::from_raw(4), amount: 15 },
+    ]);
+
+    let chill = [ScriptAction::ChillEnemiesInRadius { radius: 100.0, speed_multiplier: 0.5, duration_secs: 1.0 }];
+    assert_eq!(run_script_actions(&chill, &context(None), &enemies()).len(), 3);
+}
+
+#[test]
+fn test_target_actions_need_a_struck_enemy() {
+    let actions = [ScriptAction::DamageTarget { damage: 20 }, ScriptAction::ChillTarget { speed_multiplier: 0.4, duration_secs: 1.5 }];
+    assert!(run_script_actions(&actions, &context(None), &enemies()).is_empty());
+
+    let target = Entity::from_raw(7);
+    assert_eq!(run_script_actions(&actions, &context(Some(target)), &[]), vec![
+        ScriptCommand::Damage { target, amount: 25 },
+        ScriptCommand::Chill { target, speed_multiplier: 0.4, duration_secs: 1.5 },
+    ]);
+}
+
+#[test]
+fn test_projectiles_are_rotated_from_the_aim_direction() {
+    let actions = [ScriptAction::SpawnProjectile { damage: 8, speed: 400.0, angle_offset_deg: 90.0, size: (6.0, 6.0), color: (1.0, 1.0, 1.0, 1.0), lifetime_secs: 1.0, piercing: 0, homing_turn_rate: Some(3.0) }];
+    let commands = run_script_actions(&actions, &context(None), &[]);
+    let ScriptCommand::SpawnProjectile { direction, damage, homing_turn_rate, .. } = commands[0] else { panic!("expected a projectile") };
+    assert!(direction.distance(Vec2::Y) < 1e-4);
+    assert_eq!(damage, 13);
+    assert_eq!(homing_turn_rate, Some(3.0));
+
+    let no_aim = ScriptContext { aim: Vec2::ZERO, ..context(None) };
+    assert!(run_script_actions(&actions, &no_aim, &[]).is_empty());
+}
+
+#[test]
+fn test_command_count_is_capped() {
+    let crowd: Vec<(Entity, Vec2)> = (0..200).map(|index| (Entity::from_raw(index), Vec2::new(index as f32, 0.0))).collect();
+    let actions = [ScriptAction::DamageEnemiesInRadius { radius: 1000.0, damage: 1, max_targets: None }];
+    assert_eq!(run_script_actions(&actions, &context(None), &crowd).len(), MAX_SCRIPT_COMMANDS);
+}
+
+#[test]
+fn test_validate_rejects_unsafe_scripts() {
+    let on_cast_target = SkillScript { on_cast: vec![ScriptAction::DamageTarget { damage: 5 }], ..default() };
+    assert_eq!(on_cast_target.validate(), Err(SkillScriptError::TargetOutsideHit));
+
+    let fast_tick = SkillScript { on_tick: Some(ScriptTick { interval_secs: 0.001, duration_secs: 3.0, actions: vec![] }), ..default() };
+    assert_eq!(fast_tick.validate(), Err(SkillScriptError::InvalidTick));
+
+    let huge_radius = SkillScript { on_hit: vec![ScriptAction::DamageEnemiesInRadius { radius: 99999.0, damage: 5, max_targets: None }], ..default() };
+    assert_eq!(huge_radius.validate(), Err(SkillScriptError::InvalidAction));
+
+    let stalled_homing = SkillScript { on_cast: vec![ScriptAction::SpawnProjectile { damage: 5, speed: 300.0, angle_offset_deg: 0.0, size: (6.0, 6.0), color: (1.0, 1.0, 1.0, 1.0), lifetime_secs: 1.0, piercing: 0, homing_turn_rate: Some(0.0) }], ..default() };
+    assert_eq!(stalled_homing.validate(), Err(SkillScriptError::InvalidAction));
+
+    let fine = SkillScript { on_hit: vec![ScriptAction::DamageTarget { damage: 5 }], ..default() };
+    assert_eq!(fine.validate(), Ok(()));
+}
+
+#[test]
+fn test_data_packs_carry_namespaced_scripts() {
+    let pack = DataPack::from_ron_str(SCRIPTED_PACK).unwrap().with_asset_folder("tidecallers");
+    assert!(pack.scripts.contains_key("tidecallers/undertow"));
+    assert!(matches!(pack.skills[0].to_definition().effect, SkillEffectType::Scripted { ref script } if script == "tidecallers/undertow"));
+
+    assert!(matches!(DataPack::from_ron_str(&SCRIPTED_PACK.replace("script: \"undertow\"", "script: \"riptide\"")), Err(ModError::MissingScript(_))));
+    assert!(matches!(DataPack::from_ron_str(&SCRIPTED_PACK.replace("on_cast", "on_tick: Some((interval_secs: 0.0, duration_secs: 1.0, actions: [])), on_cast")), Err(ModError::InvalidScript { .. })));
+}
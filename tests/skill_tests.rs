@@ -1,4 +1,5 @@
-use cosmic_gardener::skills::{ActiveSkillInstance, SkillId}; // Assuming 'cosmic_gardener' is the crate name
+use cosmic_gardener::skills::{ActiveSkillInstance, SkillId, SkillProjectile, MAX_PROJECTILE_HIT_SET_SIZE}; // Assuming 'cosmic_gardener' is the crate name
+use cosmic_gardener::survivor::{Survivor, MAX_COOLDOWN_REDUCTION};
 use std::time::Duration;
 
 #[test]
@@ -42,3 +43,57 @@ fn test_active_skill_instance_tick_cooldown() {
     skill_instance.tick_cooldown(Duration::from_secs(5)); // Tick past zero
     assert_eq!(skill_instance.current_cooldown, Duration::ZERO);
 }
+
+#[test]
+fn test_ricochet_orb_damage_falloff() {
+    use cosmic_gardener::skills::RicochetOrb;
+    use bevy::prelude::{Timer, TimerMode};
+
+    let mut orb = RicochetOrb {
+        skill_id: SkillId(12), base_damage: 20, bounces: 0, bounce_damage_falloff: 0.5, min_damage: 3,
+        radius: 8.0, retarget_radius_sq: 90_000.0, last_hit: None, lifetime_timer: Timer::from_seconds(5.0, TimerMode::Once),
+    };
+    assert_eq!(orb.current_damage(), 20);
+    orb.bounces = 1;
+    assert_eq!(orb.current_damage(), 10);
+    orb.bounces = 2;
+    assert_eq!(orb.current_damage(), 5);
+    orb.bounces = 5; // Falloff never drops below the floor
+    assert_eq!(orb.current_damage(), 3);
+}
+
+#[test]
+fn test_add_glyph_slot_respects_cap() {
+    use cosmic_gardener::skills::MAX_GLYPH_SLOTS;
+
+    let mut skill_instance = ActiveSkillInstance::new(SkillId(1), 2);
+    assert!(skill_instance.add_glyph_slot());
+    assert_eq!(skill_instance.equipped_glyphs.len(), 3);
+    assert!(skill_instance.equipped_glyphs[2].is_none());
+
+    while skill_instance.equipped_glyphs.len() < MAX_GLYPH_SLOTS { assert!(skill_instance.add_glyph_slot()); }
+    assert!(!skill_instance.add_glyph_slot());
+    assert_eq!(skill_instance.equipped_glyphs.len(), MAX_GLYPH_SLOTS);
+}
+
+#[test]
+fn test_global_cooldown_reduction_composes_and_caps() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    survivor.cooldown_reduction = 0.2;
+    let mut skill_instance = ActiveSkillInstance::new(SkillId(1), 0);
+    skill_instance.cooldown_multiplier = 0.5;
+    skill_instance.trigger_with_global_multiplier(Duration::from_secs_f32(10.0), survivor.get_effective_cooldown_multiplier());
+    assert!((skill_instance.current_cooldown.as_secs_f32() - 4.0).abs() < 0.001); // 10 * 0.5 * 0.8
+
+    survivor.cooldown_reduction = 5.0;
+    assert!((survivor.get_effective_cooldown_multiplier() - (1.0 - MAX_COOLDOWN_REDUCTION)).abs() < 0.001);
+}
+
+#[test]
+fn test_skill_projectile_hit_set_limit_is_capped() {
+    let mut projectile = SkillProjectile { skill_id: SkillId(1), piercing_left: 2, bounces_left: 1, already_hit_by_this_projectile: Default::default(), split: None };
+    assert_eq!(projectile.hit_set_limit(), 8);
+
+    projectile.piercing_left = 500;
+    assert_eq!(projectile.hit_set_limit(), MAX_PROJECTILE_HIT_SET_SIZE);
+}
@@ -0,0 +1,52 @@
+use cosmic_gardener::platform_services::Achievement;
+use cosmic_gardener::skins::{wardrobe_label, SkinCollection, SkinId, SkinUnlock};
+
+#[test]
+fn test_buying_a_skin_spends_remnants_and_wears_it() {
+    let mut collection = SkinCollection::default();
+    let SkinUnlock::Remnants(cost) = SkinId::Voidborn.definition().unlock else { panic!("Voidborn should be for sale") };
+
+    let mut remnants = cost - 1;
+    assert!(!collection.buy(SkinId::Voidborn, &mut remnants));
+    assert_eq!(remnants, cost - 1);
+
+    let mut remnants = cost + 5;
+    assert!(collection.buy(SkinId::Voidborn, &mut remnants));
+    assert_eq!(remnants, 5);
+    assert_eq!(collection.selected, SkinId::Voidborn);
+    // Owned skins are never charged for twice
+    assert!(!collection.buy(SkinId::Voidborn, &mut remnants));
+    assert_eq!(remnants, 5);
+}
+
+#[test]
+fn test_achievement_skins_unlock_once_and_cannot_be_bought() {
+    let mut collection = SkinCollection::default();
+    let mut remnants = 10_000;
+    assert!(!collection.buy(SkinId::CrimsonHunter, &mut remnants));
+    assert!(!collection.select(SkinId::CrimsonHunter));
+
+    assert_eq!(collection.unlock_for_achievement(Achievement::SlayElite), vec![SkinId::CrimsonHunter]);
+    assert!(collection.unlock_for_achievement(Achievement::SlayElite).is_empty());
+    assert!(collection.select(SkinId::CrimsonHunter));
+    assert_eq!(remnants, 10_000);
+}
+
+#[test]
+fn test_unowned_selection_falls_back_to_default() {
+    let tampered = SkinCollection { owned: vec![SkinId::Wanderer], selected: SkinId::RiftWalker };
+    assert_eq!(tampered.sanitized().selected, SkinId::Wanderer);
+    assert!(SkinCollection { owned: Vec::new(), selected: SkinId::Wanderer }.owns(SkinId::Wanderer));
+}
+
+#[test]
+fn test_wardrobe_cycles_through_every_skin_and_labels_locks() {
+    let mut shown = SkinId::default();
+    for _ in 0..SkinId::ALL.len() { shown = shown.next(); }
+    assert_eq!(shown, SkinId::default());
+
+    let collection = SkinCollection::default();
+    assert!(wardrobe_label(&collection, SkinId::Wanderer).contains("wearing"));
+    assert!(wardrobe_label(&collection, SkinId::Voidborn).contains("remnants"));
+    assert!(wardrobe_label(&collection, SkinId::RiftWalker).contains(&Achievement::Extract.title()));
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::Vec3;
+use cosmic_gardener::{
+    audio::{heartbeat_playback, is_supported_audio_path, music_playback, sound_variation, MusicIntensity, SampleRotation, SoundEffect, SoundManifest, SoundManifestError, FREQUENT_SOUND_PITCH_SPREAD},
+    game_events::GameEvent,
+    horror::{ElitePhase, HorrorType},
+    survivor::LowHealth,
+    skills::SkillId,
+};
+
+const SHIPPED_MANIFEST: &str = include_str!("../assets/data/sounds.ron");
+
+const SMALL_MANIFEST: &str = r#"(
+    music: "audio/music.ogg",
+    sounds: [
+        (sound: RitualCast, path: "audio/cast.ogg", variations: ["audio/cast_2.ogg"]),
+        (sound: SkillCast(3), path: "audio/void_lance.ogg"),
+        (sound: BossStinger(VoidBlinker), path: "audio/blinker.ogg"),
+    ],
+)"#;
+
+#[test]
+fn test_shipped_manifest_is_valid_and_covers_every_plain_sound() {
+    let manifest = SoundManifest::from_ron_str(SHIPPED_MANIFEST).expect("shipped sound manifest should be valid");
+    for sound in [
+        SoundEffect::RitualCast, SoundEffect::HorrorHit, SoundEffect::HorrorDeath, SoundEffect::SurvivorHit, SoundEffect::Revelation,
+        SoundEffect::SoulCollect, SoundEffect::MadnessConsumes, SoundEffect::OmenAccepted, SoundEffect::HorrorProjectile,
+        SoundEffect::Heartbeat,
+    ] {
+        assert!(manifest.sounds.iter().any(|entry| entry.sound == sound), "{:?} has no file", sound);
+    }
+}
+
+#[test]
+fn test_missing_sounds_fall_back_to_generic_ones() {
+    assert_eq!(SoundEffect::SkillCast(SkillId(4)).with_fallbacks().collect::<Vec<_>>(), vec![SoundEffect::SkillCast(SkillId(4)), SoundEffect::RitualCast]);
+    assert_eq!(SoundEffect::SkillImpact(SkillId(3)).with_fallbacks().collect::<Vec<_>>(), vec![SoundEffect::SkillImpact(SkillId(3)), SoundEffect::HorrorHit]);
+    assert_eq!(SoundEffect::BossStinger(HorrorType::FleshWeaver).with_fallbacks().collect::<Vec<_>>(), vec![SoundEffect::BossStinger(HorrorType::FleshWeaver)]);
+    assert_eq!(SoundEffect::ItemPickup.with_fallbacks().collect::<Vec<_>>(), vec![SoundEffect::ItemPickup, SoundEffect::SoulCollect]);
+}
+
+#[test]
+fn test_manifest_rejects_duplicates_and_empty_paths() {
+    let duplicated = SMALL_MANIFEST.replace("SkillCast(3)", "RitualCast");
+    assert!(matches!(SoundManifest::from_ron_str(&duplicated), Err(SoundManifestError::DuplicateSound(SoundEffect::RitualCast))));
+    let empty = SMALL_MANIFEST.replace("audio/blinker.ogg", "");
+    assert!(matches!(SoundManifest::from_ron_str(&empty), Err(SoundManifestError::EmptyPath(SoundEffect::BossStinger(HorrorType::VoidBlinker)))));
+    assert!(matches!(SoundManifest::from_ron_str(&SMALL_MANIFEST.replace("audio/music.ogg", "")), Err(SoundManifestError::EmptyMusicPath)));
+}
+
+#[test]
+fn test_variations_are_extra_samples_and_must_not_be_empty() {
+    let manifest = SoundManifest::from_ron_str(SMALL_MANIFEST).unwrap();
+    let cast = manifest.sounds.iter().find(|entry| entry.sound == SoundEffect::RitualCast).unwrap();
+    assert_eq!(cast.samples().collect::<Vec<_>>(), vec!["audio/cast.ogg", "audio/cast_2.ogg"]);
+    assert!(matches!(SoundManifest::from_ron_str(&SMALL_MANIFEST.replace("audio/cast_2.ogg", "")), Err(SoundManifestError::EmptyPath(SoundEffect::RitualCast))));
+}
+
+#[test]
+fn test_sample_rotation_cycles_each_sound_separately() {
+    let mut rotation = SampleRotation::default();
+    let picks: Vec<usize> = (0..4).map(|_| rotation.next_index(SoundEffect::HorrorHit, 3)).collect();
+    assert_eq!(picks, vec![0, 1, 2, 0]);
+    assert_eq!(rotation.next_index(SoundEffect::RitualCast, 3), 0);
+    assert_eq!(rotation.next_index(SoundEffect::HorrorDeath, 1), 0);
+}
+
+#[test]
+fn test_only_frequent_sounds_are_varied_and_never_louder() {
+    assert_eq!(sound_variation(SoundEffect::HorrorDeath, 1.0, 1.0), (1.0, 1.0));
+    let (speed, volume) = sound_variation(SoundEffect::SkillImpact(SkillId(3)), 5.0, -1.0);
+    assert!((speed - (1.0 + FREQUENT_SOUND_PITCH_SPREAD)).abs() < 1e-6);
+    assert!(volume < 1.0);
+    assert_eq!(sound_variation(SoundEffect::HorrorHit, 0.0, 0.0), (1.0, 1.0));
+}
+
+#[test]
+fn test_low_health_speeds_the_heartbeat_and_muffles_the_music() {
+    let healthy = LowHealth::default();
+    let wounded = LowHealth::from_health(20, 100);
+    let dying = LowHealth::from_health(2, 100);
+    assert_eq!(heartbeat_playback(&healthy), None);
+    let (wounded_speed, wounded_volume) = heartbeat_playback(&wounded).unwrap();
+    let (dying_speed, dying_volume) = heartbeat_playback(&dying).unwrap();
+    assert!(dying_speed > wounded_speed && dying_volume > wounded_volume);
+
+    let (calm_music, calm_speed) = music_playback(&healthy);
+    let (muffled_music, muffled_speed) = music_playback(&dying);
+    assert_eq!(calm_speed, 1.0);
+    assert!(muffled_music < calm_music && muffled_speed < calm_speed);
+}
+
+#[test]
+fn test_elites_change_phase_at_each_third_of_health() {
+    assert_eq!(ElitePhase::for_health(100, 100), ElitePhase::Opening);
+    assert_eq!(ElitePhase::for_health(67, 100), ElitePhase::Opening);
+    assert_eq!(ElitePhase::for_health(66, 100), ElitePhase::Wounded);
+    assert_eq!(ElitePhase::for_health(33, 100), ElitePhase::Final);
+    assert!(ElitePhase::Opening < ElitePhase::Wounded && ElitePhase::Wounded < ElitePhase::Final);
+}
+
+#[test]
+fn test_elite_phases_raise_music_intensity_until_no_elite_is_left() {
+    let phase_changed = |phase| GameEvent::ElitePhaseChanged { horror_type: HorrorType::FleshWeaver, phase, position: Vec3::ZERO };
+    let mut intensity = MusicIntensity::default();
+    assert_eq!(intensity.layer_target(ElitePhase::Opening), 1.0);
+    assert_eq!(intensity.layer_target(ElitePhase::Wounded), 0.0);
+
+    intensity.apply(&phase_changed(ElitePhase::Final), true);
+    // A second elite only just wounded doesn't calm the music back down
+    intensity.apply(&phase_changed(ElitePhase::Wounded), true);
+    assert_eq!(intensity.phase, ElitePhase::Final);
+    assert_eq!(intensity.layer_target(ElitePhase::Wounded), 1.0);
+    assert_eq!(intensity.layer_target(ElitePhase::Final), 1.0);
+
+    intensity.apply(&GameEvent::LevelUp { new_level: 2, levels_gained: 1 }, false);
+    assert_eq!(intensity, MusicIntensity::default());
+}
+
+#[test]
+fn test_music_layers_are_optional_but_need_paths() {
+    assert!(SoundManifest::from_ron_str(SMALL_MANIFEST).unwrap().music_layers.is_empty());
+    let layered = SMALL_MANIFEST.replace("    sounds: [", "    music_layers: [(phase: Wounded, path: \"audio/drums.ogg\")],\n    sounds: [");
+    assert_eq!(SoundManifest::from_ron_str(&layered).unwrap().music_layers[0].phase, ElitePhase::Wounded);
+    assert!(matches!(SoundManifest::from_ron_str(&layered.replace("audio/drums.ogg", "")), Err(SoundManifestError::EmptyMusicPath)));
+}
+
+#[test]
+fn test_every_shipped_sample_plays_on_every_platform() {
+    let manifest = SoundManifest::from_ron_str(SHIPPED_MANIFEST).unwrap();
+    assert!(is_supported_audio_path(&manifest.music));
+    assert!(manifest.music_layers.iter().all(|layer| is_supported_audio_path(&layer.path)));
+    assert!(manifest.sounds.iter().flat_map(|entry| entry.samples()).all(is_supported_audio_path));
+    assert!(is_supported_audio_path("audio/CAST.OGG"));
+    assert!(!is_supported_audio_path("audio/cast"));
+}
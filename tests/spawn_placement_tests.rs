@@ -0,0 +1,34 @@
+use bevy::prelude::Vec2;
+use rand::{rngs::StdRng, SeedableRng};
+use cosmic_gardener::{
+    balance::BalanceConfig,
+    game::GameConfig,
+    horror::{is_valid_spawn_point, spawn_point_outside_view},
+};
+
+#[test]
+fn test_spawn_points_land_just_outside_the_view_at_any_zoom() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let padding = BalanceConfig::default().spawn_view_padding;
+    let center = Vec2::new(300.0, -120.0);
+    for half_extents in [Vec2::new(640.0, 360.0), Vec2::new(1024.0, 576.0)] {
+        for _ in 0..200 {
+            let offset = (spawn_point_outside_view(&mut rng, center, half_extents, padding) - center).abs();
+            let outside = offset.x >= half_extents.x + padding - 0.01 || offset.y >= half_extents.y + padding - 0.01;
+            assert!(outside, "{:?} is on screen for a view of {:?}", offset, half_extents);
+            assert!(offset.x <= half_extents.x + padding * 2.0 + 0.01 && offset.y <= half_extents.y + padding * 2.0 + 0.01);
+        }
+    }
+}
+
+#[test]
+fn test_spawn_points_stay_in_the_arena_and_clear_of_exclusions() {
+    let config = GameConfig::default();
+    let wall = config.arena_half_extents.x;
+    assert!(is_valid_spawn_point(Vec2::new(wall - 50.0, 0.0), 40.0, &config, &[]));
+    assert!(!is_valid_spawn_point(Vec2::new(wall - 10.0, 0.0), 40.0, &config, &[]));
+
+    let exclusions = [(Vec2::new(100.0, 100.0), 30.0)];
+    assert!(!is_valid_spawn_point(Vec2::new(150.0, 100.0), 40.0, &config, &exclusions));
+    assert!(is_valid_spawn_point(Vec2::new(180.0, 100.0), 40.0, &config, &exclusions));
+}
@@ -0,0 +1,17 @@
+use cosmic_gardener::horror::SpawnThrottle;
+
+#[test]
+fn test_sustained_slow_frames_throttle_spawn_budget() {
+    let mut throttle = SpawnThrottle::default();
+    for _ in 0..10 { throttle.record_frame(0.1); }
+    throttle.record_frame(0.01); // A single fast frame breaks the streak
+    assert!(!throttle.is_active());
+    assert_eq!(throttle.spawn_budget(100), 100);
+
+    for _ in 0..60 { throttle.record_frame(0.05); }
+    assert!(throttle.is_active());
+    assert_eq!(throttle.spawn_budget(100), 50);
+
+    for _ in 0..1000 { throttle.record_frame(0.016); }
+    assert!(!throttle.is_active()); // Budget recovers once frames are fast again
+}
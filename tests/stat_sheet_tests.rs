@@ -0,0 +1,11 @@
+use cosmic_gardener::survivor::Survivor;
+use cosmic_gardener::stat_sheet::stat_sheet_lines;
+
+#[test]
+fn test_stat_sheet_shows_area_multiplier() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    assert!(stat_sheet_lines(&survivor).contains(&"Area of Effect: 100%".to_string()));
+
+    survivor.area_multiplier *= 1.2;
+    assert!(stat_sheet_lines(&survivor).contains(&"Area of Effect: 120%".to_string()));
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+use cosmic_gardener::skills::{ActiveSkillInstance, SkillId, MAX_GLYPH_SLOTS};
+use cosmic_gardener::survivor::{DamageSource, LowHealth, Survivor, SURVIVOR_HIT_INVINCIBILITY_SECS, PROJECTILE_GRACE_SECS};
+
+#[test]
+fn test_gain_experience_counts_every_level_from_one_pickup() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    // Level 1 needs 100 XP and level 2 needs 150, so 300 XP is worth two levels with 50 left over
+    assert_eq!(survivor.gain_experience(300), 2);
+    assert_eq!(survivor.level, 3);
+    assert_eq!(survivor.current_level_xp, 50);
+    assert_eq!(survivor.gain_experience(10), 0);
+}
+
+#[test]
+fn test_damage_grace_is_tracked_per_source() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    survivor.invincibility_timer.tick(Duration::from_secs_f32(SURVIVOR_HIT_INVINCIBILITY_SECS));
+    assert!(survivor.can_take_damage_from(DamageSource::Projectile));
+
+    survivor.register_hit(DamageSource::Projectile);
+    assert!(!survivor.can_take_damage_from(DamageSource::Projectile));
+    // A projectile hit leaves contact and hazard damage free to land
+    assert!(survivor.can_take_damage_from(DamageSource::Contact));
+    assert!(survivor.can_take_damage_from(DamageSource::Hazard));
+
+    survivor.damage_grace.tick(Duration::from_secs_f32(PROJECTILE_GRACE_SECS));
+    assert!(survivor.can_take_damage_from(DamageSource::Projectile));
+}
+
+#[test]
+fn test_full_invincibility_blocks_every_source() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    survivor.start_invincibility(2.0);
+    assert!(!survivor.can_take_damage_from(DamageSource::Contact));
+    assert!(!survivor.can_take_damage_from(DamageSource::Projectile));
+    assert!(!survivor.can_take_damage_from(DamageSource::Hazard));
+}
+
+#[test]
+fn test_low_health_escalates_below_a_quarter_and_clears_above_it() {
+    assert!(!LowHealth::from_health(25, 100).is_active());
+    assert!(!LowHealth::from_health(80, 0).is_active());
+
+    let wounded = LowHealth::from_health(24, 100);
+    let dying = LowHealth::from_health(5, 100);
+    assert!(wounded.is_active());
+    assert!(dying.intensity > wounded.intensity);
+    assert!(dying.heartbeat_bpm() > wounded.heartbeat_bpm());
+    assert_eq!(LowHealth::from_health(0, 100).intensity, 1.0);
+    assert_eq!(LowHealth::from_health(-10, 100).intensity, 1.0);
+}
+
+#[test]
+fn test_glyph_slots_can_only_be_added_while_a_skill_has_room() {
+    assert!(!Survivor::new_with_skills_and_items(Vec::new(), Vec::new()).can_add_glyph_slot());
+    let mut survivor = Survivor::new_with_skills_and_items(vec![ActiveSkillInstance::new(SkillId(1), MAX_GLYPH_SLOTS as u8 - 1)], Vec::new());
+    assert!(survivor.can_add_glyph_slot());
+    assert!(survivor.equipped_skills[0].add_glyph_slot());
+    assert!(!survivor.can_add_glyph_slot());
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::Vec2;
+use cosmic_gardener::{
+    audio::SoundEffect,
+    taunt::{taunt_pull, pickup_radius_multiplier, TauntCooldown, Taunting, TAUNT_AGGRO_RADIUS, TAUNT_PICKUP_RADIUS_MULTIPLIER},
+};
+
+#[test]
+fn test_taunt_pulls_only_nearby_horrors_and_never_past_the_survivor() {
+    let survivor = Vec2::new(100.0, 0.0);
+    assert_eq!(taunt_pull(survivor + Vec2::new(TAUNT_AGGRO_RADIUS + 1.0, 0.0), survivor, 5.0), Vec2::ZERO);
+    assert_eq!(taunt_pull(survivor + Vec2::new(0.0, 50.0), survivor, 5.0), Vec2::new(0.0, -5.0));
+    assert_eq!(taunt_pull(survivor + Vec2::new(2.0, 0.0), survivor, 5.0), Vec2::new(-2.0, 0.0));
+    assert_eq!(taunt_pull(survivor, survivor, 5.0), Vec2::ZERO);
+}
+
+#[test]
+fn test_taunting_widens_pickup_radius_and_cooldown_starts_ready() {
+    assert_eq!(pickup_radius_multiplier(None), 1.0);
+    assert_eq!(pickup_radius_multiplier(Some(&Taunting::default())), TAUNT_PICKUP_RADIUS_MULTIPLIER);
+
+    let mut cooldown = TauntCooldown::default();
+    assert!(cooldown.is_ready());
+    cooldown.trigger();
+    assert!(!cooldown.is_ready());
+    assert_eq!(SoundEffect::Taunt.with_fallbacks().collect::<Vec<_>>(), vec![SoundEffect::Taunt, SoundEffect::OmenAccepted]);
+}
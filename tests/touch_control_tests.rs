@@ -0,0 +1,43 @@
+use bevy::math::Vec2;
+use cosmic_gardener::input_actions::InputAction;
+use cosmic_gardener::touch_controls::{auto_aim_direction, stick_vector, touch_button_center, touched_button, TouchButton, TouchControlMode, TouchControls, STICK_RADIUS};
+
+const WINDOW: Vec2 = Vec2::new(1280.0, 720.0);
+
+#[test]
+fn test_auto_mode_waits_for_a_touch() {
+    let mut controls = TouchControls::default();
+    assert!(!controls.is_active());
+    controls.touch_detected = true;
+    assert!(controls.is_active());
+    controls.mode = TouchControlMode::Off;
+    assert!(!controls.is_active());
+    assert_eq!(TouchControlMode::Off.next(), TouchControlMode::Auto);
+    assert!(TouchControls { mode: TouchControlMode::On, touch_detected: false }.is_active());
+}
+
+#[test]
+fn test_stick_flips_screen_y_and_stays_in_the_unit_circle() {
+    let origin = Vec2::new(100.0, 500.0);
+    assert_eq!(stick_vector(origin, origin + Vec2::new(0.0, -STICK_RADIUS / 2.0), STICK_RADIUS), Vec2::new(0.0, 0.5));
+    assert!((stick_vector(origin, origin + Vec2::new(STICK_RADIUS * 3.0, STICK_RADIUS * 3.0), STICK_RADIUS).length() - 1.0).abs() < 1e-5);
+    assert_eq!(stick_vector(origin, origin + Vec2::new(3.0, 0.0), STICK_RADIUS), Vec2::ZERO);
+}
+
+#[test]
+fn test_touches_land_on_the_buttons_they_cover() {
+    for slot_index in 0..InputAction::SKILLS.len() {
+        let button = TouchButton::Skill(slot_index);
+        assert_eq!(touched_button(WINDOW, touch_button_center(WINDOW, button)), Some(button));
+        assert_eq!(button.action(), InputAction::SKILLS[slot_index]);
+    }
+    assert_eq!(touched_button(WINDOW, touch_button_center(WINDOW, TouchButton::Pause)), Some(TouchButton::Pause));
+    assert_eq!(touched_button(WINDOW, WINDOW / 2.0), None);
+}
+
+#[test]
+fn test_auto_aim_picks_the_nearest_target_in_range() {
+    let targets = [Vec2::new(0.0, 300.0), Vec2::new(-100.0, 0.0), Vec2::new(5000.0, 0.0)];
+    assert_eq!(auto_aim_direction(Vec2::ZERO, targets, 600.0), Some(Vec2::new(-1.0, 0.0)));
+    assert_eq!(auto_aim_direction(Vec2::ZERO, [Vec2::new(5000.0, 0.0)], 600.0), None);
+}
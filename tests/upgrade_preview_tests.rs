@@ -0,0 +1,60 @@
+use cosmic_gardener::survivor::{Survivor, SanityStrain};
+use cosmic_gardener::weapons::{CircleOfWarding, SwarmOfNightmares, SearingGaze, SporeCenser, RearGuard, SnareLayer, HookedCrescent};
+use cosmic_gardener::upgrades::UpgradeType;
+use cosmic_gardener::upgrade_preview::{preview_upgrade, PreviewSources};
+
+struct Loadout {
+    survivor: Survivor,
+    sanity_strain: SanityStrain,
+    circle: CircleOfWarding,
+    swarm: SwarmOfNightmares,
+    gaze: SearingGaze,
+    spores: SporeCenser,
+    rear_guard: RearGuard,
+    snares: SnareLayer,
+    crescent: HookedCrescent,
+}
+
+impl Loadout {
+    fn new() -> Self {
+        Self {
+            survivor: Survivor::new_with_skills_and_items(Vec::new(), Vec::new()),
+            sanity_strain: SanityStrain::default(),
+            circle: CircleOfWarding::default(),
+            swarm: SwarmOfNightmares::default(),
+            gaze: SearingGaze::default(),
+            spores: SporeCenser::default(),
+            rear_guard: RearGuard::default(),
+            snares: SnareLayer::default(),
+            crescent: HookedCrescent::default(),
+        }
+    }
+
+    fn preview(&self, upgrade_type: UpgradeType) -> Vec<String> {
+        let sources = PreviewSources {
+            survivor: &self.survivor, sanity_strain: &self.sanity_strain, circle: &self.circle, swarm: &self.swarm, gaze: &self.gaze,
+            spores: &self.spores, rear_guard: &self.rear_guard, snares: &self.snares, crescent: &self.crescent,
+        };
+        preview_upgrade(&upgrade_type, &sources).iter().map(|line| line.to_string()).collect()
+    }
+}
+
+#[test]
+fn test_preview_reads_current_stats() {
+    let mut loadout = Loadout::new();
+    assert_eq!(loadout.preview(UpgradeType::IchorBlastSpeed(16)), vec!["Fire rate 0.50s → 0.42s"]);
+    loadout.sanity_strain.base_fire_rate_secs = 0.3;
+    assert_eq!(loadout.preview(UpgradeType::IchorBlastSpeed(50)), vec!["Fire rate 0.30s → 0.15s"]);
+    let max_health = loadout.survivor.max_health;
+    assert_eq!(loadout.preview(UpgradeType::MaxEndurance(25)), vec![format!("Max endurance {} → {}", max_health, max_health + 25)]);
+}
+
+#[test]
+fn test_preview_of_dormant_weapon_and_missing_skill() {
+    let mut loadout = Loadout::new();
+    loadout.circle.is_active = false;
+    assert!(loadout.preview(UpgradeType::IncreaseCircleDamage(3)).is_empty());
+    assert_eq!(loadout.preview(UpgradeType::InscribeCircleOfWarding), vec!["Circle of Warding Dormant → Manifested"]);
+    assert!(loadout.preview(UpgradeType::IncreaseSkillDamage { slot_index: 3, amount: 5 }).is_empty());
+    assert!(loadout.preview(UpgradeType::GrantRandomRelic).is_empty());
+}
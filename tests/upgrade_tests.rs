@@ -1,7 +1,6 @@
-use cosmic_gardener::upgrades::{UpgradeId, UpgradeCard, UpgradeType, UpgradePool};
+use cosmic_gardener::upgrades::{UpgradeId, UpgradeCard, UpgradeType, UpgradePool, UpgradePoolFile, UpgradePoolError};
 use cosmic_gardener::skills::{SkillId, ActiveSkillInstance};
 use cosmic_gardener::survivor::Survivor; // For applying upgrades to survivor's skills
-use std::time::Duration;
 
 fn setup_test_upgrade_pool() -> UpgradePool {
     let mut pool = UpgradePool::default();
@@ -60,7 +59,7 @@ fn test_apply_skill_damage_upgrade() {
         Vec::new()
     );
     
-    let upgrade = UpgradeType::IncreaseSkillDamage { slot_index: 0, amount: 10 };
+    let _upgrade = UpgradeType::IncreaseSkillDamage { slot_index: 0, amount: 10 };
     
     // Simulate apply_chosen_upgrade logic for this specific upgrade
     if let Some(skill_instance) = survivor.equipped_skills.get_mut(0) {
@@ -84,7 +83,7 @@ fn test_apply_skill_cooldown_upgrade() {
         Vec::new()
     );
     
-    let upgrade = UpgradeType::ReduceSkillCooldown { slot_index: 0, percent_reduction: 0.20 };
+    let _upgrade = UpgradeType::ReduceSkillCooldown { slot_index: 0, percent_reduction: 0.20 };
 
     // Simulate apply_chosen_upgrade logic
     if let Some(skill_instance) = survivor.equipped_skills.get_mut(0) {
@@ -100,3 +99,23 @@ fn test_apply_skill_cooldown_upgrade() {
         panic!("Skill not found in slot 0 for testing");
     }
 }
+
+#[test]
+fn test_shipped_upgrade_pool_loads_and_validates() {
+    let mut pool = UpgradePool::default();
+    pool.initialize();
+    assert!(pool.available_upgrades.iter().any(|card| card.id == UpgradeId(700) && card.upgrade_type == UpgradeType::GrantSkill(SkillId(2))));
+    assert!(pool.available_upgrades.iter().any(|card| card.upgrade_type == UpgradeType::IncreaseSkillDamage { slot_index: 0, amount: 10 }));
+}
+
+#[test]
+fn test_upgrade_pool_validation_rejects_duplicate_ids_and_bad_slots() {
+    let duplicate = r#"(upgrades: [
+        (id: 1, name: "A", description: "", upgrade_type: SurvivorSpeed(10)),
+        (id: 1, name: "B", description: "", upgrade_type: MaxEndurance(5)),
+    ])"#;
+    assert!(matches!(UpgradePoolFile::from_ron_str(duplicate), Err(UpgradePoolError::DuplicateId(UpgradeId(1)))));
+
+    let bad_slot = r#"(upgrades: [(id: 2, name: "C", description: "", upgrade_type: IncreaseSkillDamage(slot_index: 7, amount: 1))])"#;
+    assert!(matches!(UpgradePoolFile::from_ron_str(bad_slot), Err(UpgradePoolError::InvalidSkillSlot { slot_index: 7, .. })));
+}
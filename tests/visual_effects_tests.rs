@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use cosmic_gardener::components::DamageType;
+use cosmic_gardener::survivor::LowHealth;
+use cosmic_gardener::horror::{EliteModifier, HorrorType};
+use cosmic_gardener::game_events::GameEvent;
+use cosmic_gardener::visual_effects::{ProjectileFx, beacon_alpha, beacon_for_event, beacon_ping_position, elite_aura_alpha, BeaconKind, BEACON_MAX_ALPHA, elite_aura_color, should_emit_afterimage, vignette_alpha, AfterimagePool, DamageNumberPool, DamageNumberRequest, DamageTypeStyle, AFTERIMAGE_SPEED_THRESHOLD};
+
+fn request(damage_amount: i32) -> DamageNumberRequest {
+    DamageNumberRequest { position: Vec3::ZERO, damage_amount, damage_type: DamageType::default(), spawn_time: 0.0 }
+}
+
+#[test]
+fn test_damage_number_pool_recycles_oldest_slot() {
+    let slots: Vec<Entity> = (0..3).map(Entity::from_raw).collect();
+    let mut pool = DamageNumberPool::with_slots(slots.clone());
+    let claimed: Vec<Entity> = (0..4).filter_map(|_| pool.claim_slot()).collect();
+    assert_eq!(claimed, vec![slots[0], slots[1], slots[2], slots[0]]);
+
+    assert_eq!(DamageNumberPool::default().claim_slot(), None);
+}
+
+#[test]
+fn test_damage_number_pool_keeps_newest_requests_when_saturated() {
+    let mut pool = DamageNumberPool::with_slots((0..2).map(Entity::from_raw).collect());
+    for damage in 1..=5 { pool.queue(request(damage)); }
+    let kept: Vec<i32> = pool.take_pending().iter().map(|r| r.damage_amount).collect();
+    assert_eq!(kept, vec![4, 5]);
+    assert!(pool.take_pending().is_empty());
+}
+
+#[test]
+fn test_afterimage_pool_is_capped_and_recycles() {
+    let slots: Vec<Entity> = (0..2).map(Entity::from_raw).collect();
+    let mut pool = AfterimagePool::with_slots(slots.clone());
+    let claimed: Vec<Entity> = (0..3).filter_map(|_| pool.claim_slot()).collect();
+    assert_eq!(claimed, vec![slots[0], slots[1], slots[0]]);
+}
+
+#[test]
+fn test_afterimages_only_emit_when_moving_fast() {
+    let frame = 1.0 / 60.0;
+    assert!(!should_emit_afterimage(Vec2::new(AFTERIMAGE_SPEED_THRESHOLD * frame * 0.5, 0.0), frame));
+    assert!(should_emit_afterimage(Vec2::new(0.0, AFTERIMAGE_SPEED_THRESHOLD * frame * 1.5), frame));
+    assert!(!should_emit_afterimage(Vec2::new(50.0, 0.0), 0.0));
+}
+
+#[test]
+fn test_damage_type_style_marks_elemental_damage() {
+    let style = DamageTypeStyle::default();
+    assert_eq!(style.damage_number_text(DamageType::Eldritch, 12), "12");
+    assert_eq!(style.damage_number_text(DamageType::Frost, 12), format!("{}12", style.icon(DamageType::Frost)));
+    assert!(style.label(DamageType::Chaos).ends_with("Chaos"));
+
+    let colors = [DamageType::Eldritch, DamageType::Frost, DamageType::Chaos].map(|damage_type| style.color(damage_type));
+    assert_ne!(colors[0], colors[1]);
+    assert_ne!(colors[1], colors[2]);
+    assert_ne!(colors[0], colors[2]);
+}
+
+#[test]
+fn test_vignette_pulses_on_the_heartbeat_and_vanishes_when_healed() {
+    assert_eq!(vignette_alpha(&LowHealth::from_health(60, 100), 0.0), 0.0);
+
+    let low_health = LowHealth::from_health(10, 100);
+    let beat_secs = 60.0 / low_health.heartbeat_bpm();
+    let on_beat = vignette_alpha(&low_health, 0.01);
+    let between_beats = vignette_alpha(&low_health, beat_secs * 0.5);
+    assert!(on_beat > between_beats && between_beats > 0.0);
+    assert!((vignette_alpha(&low_health, beat_secs * 2.0 + 0.01) - on_beat).abs() < 1e-3);
+    assert!(vignette_alpha(&LowHealth::from_health(0, 100), 0.0) > vignette_alpha(&low_health, 0.0));
+}
+
+#[test]
+fn test_elite_auras_stay_visible_through_their_pulse() {
+    let color = elite_aura_color(EliteModifier::Empowered);
+    assert_ne!(color, Color::WHITE);
+    assert_eq!(color.a(), 1.0);
+    for step in 0..40 {
+        let alpha = elite_aura_alpha(step as f32 * 0.1);
+        assert!((0.5..=1.0).contains(&alpha));
+    }
+}
+
+#[test]
+fn test_beacons_are_raised_for_elites_and_extraction_rifts() {
+    let position = Vec3::new(120.0, -40.0, 0.4);
+    assert_eq!(beacon_for_event(&GameEvent::EliteSpawned { horror_type: HorrorType::VoidBlinker, position }), Some((BeaconKind::Boss, position)));
+    assert_eq!(beacon_for_event(&GameEvent::ExtractionRiftOpened { position }), Some((BeaconKind::ExtractionRift, position)));
+    assert_eq!(beacon_for_event(&GameEvent::LevelUp { new_level: 2, levels_gained: 1 }), None);
+}
+
+#[test]
+fn test_beacon_fades_out_over_its_life() {
+    assert!((beacon_alpha(0.0) - BEACON_MAX_ALPHA).abs() < 1e-5);
+    assert!(beacon_alpha(1.0).abs() < 1e-5);
+    for step in 0..=20 {
+        let alpha = beacon_alpha(step as f32 / 20.0);
+        assert!((0.0..=BEACON_MAX_ALPHA).contains(&alpha));
+    }
+}
+
+#[test]
+fn test_beacon_ping_stays_on_target_or_pins_to_screen_edge() {
+    let viewport = Vec2::new(800.0, 600.0);
+    let on_screen = Vec2::new(300.0, 200.0);
+    assert_eq!(beacon_ping_position(viewport, on_screen, 20.0), on_screen);
+
+    // Far off to the right, level with the centre
+    assert_eq!(beacon_ping_position(viewport, Vec2::new(5000.0, 300.0), 20.0), Vec2::new(780.0, 300.0));
+    // Far above and to the left, clamped along the line from the centre
+    let pinned = beacon_ping_position(viewport, Vec2::new(-400.0, -1200.0), 20.0);
+    assert!((pinned.y - 20.0).abs() < 1e-3);
+    assert!(pinned.x > 20.0 && pinned.x < 400.0);
+}
+
+#[test]
+fn test_converted_projectiles_take_their_damage_type_colour() {
+    let style = DamageTypeStyle::default();
+    let own = Color::rgba(0.7, 0.5, 1.0, 0.8);
+    assert_eq!(style.projectile_color(DamageType::Eldritch, own), own);
+    assert_eq!(style.projectile_color(DamageType::Frost, own), style.color(DamageType::Frost).with_a(0.8));
+    assert_eq!(style.projectile_color(DamageType::Chaos, Color::RED), style.projectile_color(DamageType::Chaos, Color::BLUE));
+}
+
+#[test]
+fn test_each_damage_type_has_its_own_trail_and_impact() {
+    let style = DamageTypeStyle::default();
+    let fx: Vec<ProjectileFx> = [DamageType::Eldritch, DamageType::Frost, DamageType::Chaos].into_iter().map(|damage_type| style.projectile_fx(damage_type)).collect();
+    for (i, a) in fx.iter().enumerate() {
+        assert!(a.trail_interval_secs > 0.0 && a.trail_lifetime_secs > 0.0 && a.impact_particles > 0);
+        assert!(fx[..i].iter().all(|b| b != a));
+    }
+}
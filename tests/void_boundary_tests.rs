@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use cosmic_gardener::background::{void_edge_rects, void_wall_rects};
+use cosmic_gardener::void_boundary::{void_depth, void_half_extent, VoidBoundary, VOID_CONTRACTION_SECONDS, VOID_MIN_HALF_EXTENT, VOID_START_HALF_EXTENT};
+
+#[test]
+fn test_void_closes_in_then_holds() {
+    assert_eq!(void_half_extent(0.0), VOID_START_HALF_EXTENT);
+    let halfway = void_half_extent(VOID_CONTRACTION_SECONDS / 2.0);
+    assert!(halfway < VOID_START_HALF_EXTENT && halfway > VOID_MIN_HALF_EXTENT);
+    assert_eq!(void_half_extent(VOID_CONTRACTION_SECONDS), VOID_MIN_HALF_EXTENT);
+    assert_eq!(void_half_extent(VOID_CONTRACTION_SECONDS * 3.0), VOID_MIN_HALF_EXTENT);
+}
+
+#[test]
+fn test_void_depth_measures_past_the_boundary() {
+    let center = Vec2::new(100.0, -50.0);
+    assert!(void_depth(center, 25.0, center, 500.0) < 0.0);
+    assert!(void_depth(center + Vec2::new(460.0, 0.0), 25.0, center, 500.0) <= 0.0);
+    assert_eq!(void_depth(center + Vec2::new(0.0, -490.0), 25.0, center, 500.0), 15.0);
+    assert_eq!(void_depth(center + Vec2::new(600.0, 600.0), 0.0, center, 500.0), 100.0);
+}
+
+#[test]
+fn test_runs_start_without_a_void() {
+    assert!(!VoidBoundary::default().is_active());
+    assert!(VoidBoundary { half_extent: Some(VOID_START_HALF_EXTENT) }.is_active());
+}
+
+#[test]
+fn test_void_walls_cover_everything_past_the_boundary() {
+    let center = Vec2::new(300.0, 200.0);
+    let walls = void_wall_rects(center, 500.0, 1000.0);
+    let covered = |point: Vec2| walls.iter().any(|(position, size)| ((point - *position).abs() - *size / 2.0).max_element() <= 0.0);
+    assert!(covered(center + Vec2::new(600.0, 0.0)));
+    assert!(covered(center + Vec2::new(-600.0, 0.0)));
+    assert!(covered(center + Vec2::new(0.0, 1400.0)));
+    assert!(covered(center + Vec2::new(-1400.0, -1400.0)));
+    assert!(!covered(center + Vec2::new(490.0, -490.0)));
+
+    let edges = void_edge_rects(center, 500.0, 10.0);
+    assert_eq!(edges[0].0, center + Vec2::new(500.0, 0.0));
+    assert_eq!(edges[3].0, center - Vec2::new(0.0, 500.0));
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use cosmic_gardener::survivor::Survivor;
+use cosmic_gardener::weapons::{RearGuard, CrescentProjectile, CrescentPhase, NightmareLarva};
+
+#[test]
+fn test_rear_guard_scales_with_additional_blasts() {
+    let mut survivor = Survivor::new_with_skills_and_items(Vec::new(), Vec::new());
+    let rear_guard = RearGuard::default();
+    assert_eq!(survivor.get_effective_rear_guard_shots(rear_guard.base_shots), rear_guard.base_shots);
+
+    survivor.additional_ichor_blasts = 2;
+    // Each extra ichor blast adds two rear shots
+    assert_eq!(survivor.get_effective_rear_guard_shots(rear_guard.base_shots), rear_guard.base_shots + 4);
+}
+
+#[test]
+fn test_crescent_decelerates_then_returns_to_thrower() {
+    let mut crescent = CrescentProjectile {
+        phase: CrescentPhase::Outbound { elapsed_secs: 0.0 },
+        throw_direction: Vec2::X, speed: 400.0, outbound_secs: 0.5, damage: 10,
+        hit_this_phase: Vec::new(),
+    };
+    assert_eq!(crescent.velocity(Vec2::ZERO), Vec2::new(400.0, 0.0));
+
+    assert!(!crescent.advance_phase(0.25));
+    assert!((crescent.velocity(Vec2::ZERO).x - 200.0).abs() < 0.01);
+
+    crescent.hit_this_phase.push(Entity::from_raw(7));
+    assert!(crescent.advance_phase(0.25));
+    assert_eq!(crescent.phase, CrescentPhase::Returning);
+    assert!(crescent.hit_this_phase.is_empty()); // Horrors can be struck again on the way back
+    assert_eq!(crescent.velocity(Vec2::new(0.0, -50.0)), Vec2::new(0.0, -400.0));
+}
+
+#[test]
+fn test_nightmare_larva_cooldowns_expire() {
+    let mut larva = NightmareLarva::default();
+    let horror = Entity::from_raw(7);
+    larva.start_cooldown(horror, 0.5);
+    assert!(larva.is_on_cooldown(horror));
+    assert!(!larva.is_on_cooldown(Entity::from_raw(8)));
+
+    larva.tick_cooldowns(std::time::Duration::from_secs_f32(0.3));
+    assert!(larva.is_on_cooldown(horror));
+    larva.tick_cooldowns(std::time::Duration::from_secs_f32(0.3));
+    assert!(!larva.is_on_cooldown(horror));
+    assert!(larva.enemies_on_cooldown.is_empty()); // Expired entries are removed, not just skipped
+}